@@ -1,4 +1,5 @@
 use std::fmt::Debug;
+use std::time::SystemTime;
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -35,6 +36,21 @@ pub trait AuthContext: Debug + Send + Sync + 'static {
         action: InstanceAction,
         key: &str,
     ) -> Result<bool, AuthError>;
+
+    /// authenticated identity of connection, if any
+    fn principal(&self) -> Option<&str> {
+        None
+    }
+
+    /// address of client passed by TLS proxy, if any
+    fn client_addr(&self) -> Option<&str> {
+        None
+    }
+
+    /// time authentication of connection expires, connection is closed then
+    fn expires_at(&self) -> Option<SystemTime> {
+        None
+    }
 }
 
 #[async_trait]
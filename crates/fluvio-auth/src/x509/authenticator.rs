@@ -13,8 +13,9 @@ use flv_tls_proxy::authenticator::Authenticator;
 
 use super::request::AuthRequest;
 
+/// Scopes of principals, loaded from JSON file
 #[derive(Debug)]
-struct ScopeBindings(HashMap<String, Vec<String>>);
+pub struct ScopeBindings(HashMap<String, Vec<String>>);
 
 impl ScopeBindings {
     pub fn load(scope_binding_file_path: &Path) -> Result<Self, Error> {
@@ -48,6 +49,13 @@ impl X509Authenticator {
         }
     }
 
+    /// authenticator passing only principal, for servers without scope bindings
+    pub fn without_scopes() -> Self {
+        Self {
+            scope_bindings: ScopeBindings(HashMap::new()),
+        }
+    }
+
     async fn send_authorization_request(
         tcp_stream: &TcpStream,
         authorization_request: AuthRequest,
@@ -72,6 +80,9 @@ impl X509Authenticator {
                     | fluvio_socket::SocketError::SocketStale => {
                         IoError::new(IoErrorKind::BrokenPipe, "connection closed")
                     }
+                    err @ fluvio_socket::SocketError::Rejected(_) => {
+                        IoError::new(IoErrorKind::ConnectionRefused, err.to_string())
+                    }
                 })?;
 
         Ok(response.success)
@@ -129,7 +140,14 @@ impl Authenticator for X509Authenticator {
         let principal =
             Self::principal_from_tls_stream(incoming_tls_stream).map_err(std::io::Error::other)?;
         let scopes = self.scope_bindings.get_scopes(&principal);
-        let authorization_request = AuthRequest::new(principal, scopes);
+        let client_addr = incoming_tls_stream
+            .get_ref()
+            .0
+            .peer_addr()
+            .ok()
+            .map(|addr| addr.to_string());
+        let authorization_request =
+            AuthRequest::new(principal, scopes).with_client_addr(client_addr);
         let success =
             Self::send_authorization_request(target_tcp_stream, authorization_request).await?;
         Ok(success)
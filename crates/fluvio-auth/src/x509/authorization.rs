@@ -0,0 +1,116 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use fluvio_controlplane_metadata::extended::ObjectType;
+use fluvio_socket::FluvioSocket;
+
+use crate::{AuthContext, AuthError, Authorization, InstanceAction, TypeAction};
+
+use super::X509Identity;
+
+/// Authorization of identity authenticated by TLS proxy.
+///
+/// Data is accessible to any identity, administration of server (`ObjectType::Spu`)
+/// only to admin principals
+#[derive(Debug, Clone, Default)]
+pub struct IdentityAuthorization {
+    admins: Arc<HashSet<String>>,
+}
+
+#[async_trait]
+impl Authorization for IdentityAuthorization {
+    type Context = IdentityAuthContext;
+
+    async fn create_auth_context(
+        &self,
+        socket: &mut FluvioSocket,
+    ) -> Result<Self::Context, AuthError> {
+        let identity = X509Identity::create_from_connection(socket).await?;
+        Ok(self.context(identity))
+    }
+}
+
+impl IdentityAuthorization {
+    /// without admin principals
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_admins(admins: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            admins: Arc::new(admins.into_iter().collect()),
+        }
+    }
+
+    pub fn context(&self, identity: X509Identity) -> IdentityAuthContext {
+        IdentityAuthContext {
+            identity,
+            admins: self.admins.clone(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct IdentityAuthContext {
+    identity: X509Identity,
+    admins: Arc<HashSet<String>>,
+}
+
+impl IdentityAuthContext {
+    fn allow(&self, ty: ObjectType) -> bool {
+        !matches!(ty, ObjectType::Spu) || self.admins.contains(&self.identity.principal)
+    }
+}
+
+#[async_trait]
+impl AuthContext for IdentityAuthContext {
+    async fn allow_type_action(
+        &self,
+        ty: ObjectType,
+        _action: TypeAction,
+    ) -> Result<bool, AuthError> {
+        Ok(self.allow(ty))
+    }
+
+    async fn allow_instance_action(
+        &self,
+        ty: ObjectType,
+        _action: InstanceAction,
+        _key: &str,
+    ) -> Result<bool, AuthError> {
+        Ok(self.allow(ty))
+    }
+
+    fn principal(&self) -> Option<&str> {
+        Some(&self.identity.principal)
+    }
+
+    fn client_addr(&self) -> Option<&str> {
+        self.identity.client_addr.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    fn context(authorization: &IdentityAuthorization, principal: &str) -> IdentityAuthContext {
+        authorization.context(X509Identity::new(principal.to_owned(), vec![]))
+    }
+
+    #[test]
+    fn test_spu_admin_principal() {
+        let authorization = IdentityAuthorization::with_admins(vec!["admin".to_owned()]);
+        let admin = context(&authorization, "admin");
+        let client = context(&authorization, "client");
+
+        assert!(admin.allow(ObjectType::Spu));
+        assert!(!client.allow(ObjectType::Spu));
+        assert!(client.allow(ObjectType::Topic));
+
+        let without_admins = context(&IdentityAuthorization::new(), "admin");
+        assert!(!without_admins.allow(ObjectType::Spu));
+    }
+}
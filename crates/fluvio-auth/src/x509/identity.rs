@@ -11,11 +11,18 @@ use super::request::{AuthorizationScopes, AuthorizationApiRequest, AuthResponse}
 pub struct X509Identity {
     pub principal: String,
     pub scopes: AuthorizationScopes,
+    /// address of client, when connection is forwarded by TLS proxy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_addr: Option<String>,
 }
 
 impl X509Identity {
     pub fn new(principal: String, scopes: AuthorizationScopes) -> Self {
-        Self { principal, scopes }
+        Self {
+            principal,
+            scopes,
+            client_addr: None,
+        }
     }
 
     pub fn scopes(&self) -> &AuthorizationScopes {
@@ -35,6 +42,7 @@ impl X509Identity {
                         AuthorizationApiRequest::AuthRequest(req_msg) => Self {
                             scopes: req_msg.request.scopes,
                             principal: req_msg.request.principal,
+                            client_addr: req_msg.request.client_addr,
                         },
                     },
                     Err(_e) => {
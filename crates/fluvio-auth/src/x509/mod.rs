@@ -1,8 +1,10 @@
 #[cfg(unix)]
 mod authenticator;
+mod authorization;
 mod identity;
 mod request;
 
 #[cfg(unix)]
 pub use authenticator::*;
+pub use authorization::*;
 pub use identity::*;
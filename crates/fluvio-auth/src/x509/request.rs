@@ -15,16 +15,29 @@ pub const AUTH_REQUEST_API_KEY: u16 = 8;
 pub struct AuthRequest {
     pub principal: String,
     pub scopes: AuthorizationScopes,
+    /// address of client connected to TLS proxy
+    #[fluvio(min_version = 1)]
+    pub client_addr: Option<String>,
 }
 
 impl AuthRequest {
     pub fn new(principal: String, scopes: AuthorizationScopes) -> Self {
-        AuthRequest { principal, scopes }
+        AuthRequest {
+            principal,
+            scopes,
+            client_addr: None,
+        }
+    }
+
+    pub fn with_client_addr(mut self, client_addr: Option<String>) -> Self {
+        self.client_addr = client_addr;
+        self
     }
 }
 
 impl Request for AuthRequest {
     const API_KEY: u16 = AUTH_REQUEST_API_KEY;
+    const DEFAULT_API_VERSION: i16 = 1;
     type Response = AuthResponse;
 }
 
@@ -3,7 +3,10 @@ use std::{fs::File, time::Duration};
 use anyhow::Result;
 use fluvio_future::timer::sleep;
 
-use crate::{cli::BenchmarkMode, config::config_matrix::Matrix, producer_benchmark::ProducerBenchmark};
+use crate::{
+    cli::BenchmarkMode, config::config_matrix::Matrix, consumer_benchmark::ConsumerBenchmark,
+    producer_benchmark::ProducerBenchmark,
+};
 
 pub struct BenchmarkDriver {}
 
@@ -13,8 +16,8 @@ impl BenchmarkDriver {
             BenchmarkMode::Producer(config) => {
                 ProducerBenchmark::run_benchmark(config).await?;
             }
-            BenchmarkMode::Consumer(_) => {
-                println!("consume not implemented");
+            BenchmarkMode::Consumer(config) => {
+                ConsumerBenchmark::run_benchmark(config).await?;
             }
             BenchmarkMode::Matrix { config } => {
                 let matrix_config = if let Some(path) = config {
@@ -30,8 +33,8 @@ impl BenchmarkDriver {
                         crate::config::BenchmarkConfig::Producer(producer) => {
                             ProducerBenchmark::run_benchmark(producer).await?;
                         }
-                        crate::config::BenchmarkConfig::Consumer(_) => {
-                            println!("consume not implemented");
+                        crate::config::BenchmarkConfig::Consumer(consumer) => {
+                            ConsumerBenchmark::run_benchmark(consumer).await?;
                         }
                     }
 
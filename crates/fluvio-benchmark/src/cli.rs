@@ -28,8 +28,7 @@ pub enum BenchmarkMode {
     },
     /// Run a producer benchmark
     Producer(ProducerConfig),
-    /// Run a consumer benchmark
-    #[clap(hide = true)]
+    /// Run a consumer benchmark against existing topic
     Consumer(ConsumerConfig),
 }
 
@@ -1,6 +1,7 @@
 pub mod config_matrix;
 pub mod cross;
 
+use std::path::PathBuf;
 use std::time::Duration;
 
 use clap::{Parser, ValueEnum};
@@ -23,6 +24,7 @@ const DEFAULT_WORKER_TIMEOUT: &str = "3000s";
 const DEFAULT_RECORD_KEY_ALLOCATION_STRATEGY: RecordKeyAllocationStrategy =
     RecordKeyAllocationStrategy::NoKey;
 const DEFAULT_NUM_PRODUCERS: u64 = 1;
+const DEFAULT_NUM_CONSUMERS: u64 = 1;
 const DEFAULT_RECORD_SIZE: &str = "5kib";
 const DEFAULT_NUM_RECORDS: u64 = 10_000;
 const DEFAULT_PARTITIONS: u32 = 1;
@@ -94,10 +96,36 @@ pub struct ProducerConfig {
     /// Ignore rack assignment
     #[clap(long, default_value_t = DEFAULT_IGNORE_RACK)]
     pub ignore_rack: bool,
+
+    /// Write JSON report of the benchmark to file
+    #[arg(long, value_name = "path")]
+    #[builder(default)]
+    pub report: Option<PathBuf>,
 }
 
 #[derive(Debug, Parser, Clone, Builder)]
-pub struct ConsumerConfig {}
+pub struct ConsumerConfig {
+    /// Name of the topic to consume from
+    #[clap(short, long)]
+    pub topic_name: String,
+    /// Number of consumers, partitions of the topic are split between them
+    #[clap(long, default_value_t = DEFAULT_NUM_CONSUMERS)]
+    pub num_consumers: u64,
+    /// Total number of records to consume
+    #[clap(long, default_value_t = DEFAULT_NUM_RECORDS)]
+    pub num_records: u64,
+    /// Consume records already in the topic, instead of waiting for new records
+    #[clap(long)]
+    pub from_beginning: bool,
+    /// Timeout for the benchmark
+    #[arg(long, value_parser = humantime::parse_duration, default_value = DEFAULT_WORKER_TIMEOUT)]
+    pub worker_timeout: Duration,
+
+    /// Write JSON report of the benchmark to file
+    #[arg(long, value_name = "path")]
+    #[builder(default)]
+    pub report: Option<PathBuf>,
+}
 
 #[derive(Debug, Parser, ValueEnum, Clone, Eq, PartialEq, Serialize, Deserialize)]
 #[clap(rename_all = "kebab-case")]
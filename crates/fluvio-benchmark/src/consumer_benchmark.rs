@@ -0,0 +1,333 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Result};
+use bytesize::ByteSize;
+use fluvio::{
+    consumer::{ConsumerConfigExt, ConsumerStream, Record},
+    dataplane::record::NO_TIMESTAMP,
+    metadata::topic::TopicSpec,
+    Fluvio, Offset, PartitionId,
+};
+use fluvio_future::{future::timeout, timer::sleep};
+use futures_util::{stream::FuturesUnordered, StreamExt};
+use hdrhistogram::Histogram;
+use madato::yaml::mk_md_table_from_yaml;
+use tokio::{select, sync::watch};
+use tracing::debug;
+
+use crate::{
+    config::ConsumerConfig,
+    report::{BenchmarkReport, LatencyReport},
+    utils,
+};
+
+pub struct ConsumerBenchmark {}
+
+impl ConsumerBenchmark {
+    pub async fn run_benchmark(config: ConsumerConfig) -> Result<()> {
+        let fluvio = Fluvio::connect().await?;
+        let admin = fluvio.admin().await;
+        let topic = admin
+            .list::<TopicSpec, String>(vec![config.topic_name.clone()])
+            .await?
+            .into_iter()
+            .find(|topic| topic.name == config.topic_name)
+            .ok_or_else(|| anyhow!("topic {} not found", config.topic_name))?;
+
+        let assignments = assign_partitions(topic.spec.partitions(), config.num_consumers);
+        if (assignments.len() as u64) < config.num_consumers {
+            println!(
+                "Topic {} has {} partitions, using {} consumers",
+                config.topic_name,
+                topic.spec.partitions(),
+                assignments.len()
+            );
+        }
+
+        let stats = Arc::new(ConsumerStats::new());
+        let (done_sender, done_receiver) = watch::channel(false);
+        let start = Instant::now();
+
+        println!("Benchmark started");
+        let progress = Self::print_progress(stats.clone(), done_receiver.clone());
+        let consumers = Self::run_consumers(
+            &fluvio,
+            &config,
+            assignments.clone(),
+            stats.clone(),
+            done_sender,
+            done_receiver,
+        );
+        let (result, _) = futures_util::join!(timeout(config.worker_timeout, consumers), progress);
+        match result {
+            Ok(result) => result?,
+            Err(_) => println!(
+                "Benchmark timed out after {}",
+                utils::pretty_duration(config.worker_timeout)
+            ),
+        }
+        let end = stats.end(start.elapsed());
+        // sleep enough time to make sure all stats are printed
+        sleep(Duration::from_millis(100)).await;
+        Self::print_benchmark_on_end(&end);
+        println!("Benchmark completed");
+
+        if let Some(path) = &config.report {
+            end.to_report(&config, assignments.len() as u64)
+                .write(path)?;
+        }
+
+        Ok(())
+    }
+
+    async fn run_consumers(
+        fluvio: &Fluvio,
+        config: &ConsumerConfig,
+        assignments: Vec<Vec<PartitionId>>,
+        stats: Arc<ConsumerStats>,
+        done_sender: watch::Sender<bool>,
+        done_receiver: watch::Receiver<bool>,
+    ) -> Result<()> {
+        let offset = if config.from_beginning {
+            Offset::beginning()
+        } else {
+            Offset::end()
+        };
+
+        let workers = FuturesUnordered::new();
+        for (consumer_id, partitions) in assignments.into_iter().enumerate() {
+            let mut builder = ConsumerConfigExt::builder();
+            builder
+                .topic(config.topic_name.clone())
+                .offset_start(offset.clone());
+            for partition in partitions.iter() {
+                builder.partition(*partition);
+            }
+            debug!(consumer_id, ?partitions, "starting consumer");
+            let stream = fluvio.consumer_with_config(builder.build()?).await?;
+            workers.push(Self::consume(
+                stream,
+                config.num_records,
+                stats.clone(),
+                &done_sender,
+                done_receiver.clone(),
+            ));
+        }
+
+        let result = workers
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<Result<Vec<_>>>();
+        done_sender.send_replace(true);
+        result?;
+        Ok(())
+    }
+
+    async fn consume<S>(
+        mut stream: S,
+        num_records: u64,
+        stats: Arc<ConsumerStats>,
+        done_sender: &watch::Sender<bool>,
+        mut done_receiver: watch::Receiver<bool>,
+    ) -> Result<()>
+    where
+        S: ConsumerStream,
+    {
+        loop {
+            select! {
+                _ = done_receiver.wait_for(|done| *done) => break,
+                record = stream.next() => match record {
+                    Some(Ok(record)) => {
+                        if stats.record(&record) >= num_records {
+                            done_sender.send_replace(true);
+                            break;
+                        }
+                    }
+                    Some(Err(err)) => return Err(anyhow!("consumer failed: {err}")),
+                    None => break,
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn print_progress(stats: Arc<ConsumerStats>, mut done_receiver: watch::Receiver<bool>) {
+        let mut last_records = 0;
+        let mut last_bytes = 0;
+        loop {
+            select! {
+                _ = done_receiver.wait_for(|done| *done) => break,
+                _ = sleep(Duration::from_secs(1)) => {
+                    let records = stats.records.load(Ordering::Relaxed);
+                    let bytes = stats.bytes.load(Ordering::Relaxed);
+                    println!(
+                        "{} records received, {} records/sec: ({}/sec)",
+                        records,
+                        records - last_records,
+                        ByteSize(bytes - last_bytes)
+                    );
+                    last_records = records;
+                    last_bytes = bytes;
+                }
+            }
+        }
+    }
+
+    fn print_benchmark_on_end(end: &EndConsumerStat) {
+        let histogram = &end.latencies_histogram;
+        if histogram.is_empty() {
+            println!();
+            println!("latencies: no record timestamps");
+        } else {
+            let mut latency_yaml = format!(
+                "latencies: {} min, {} avg, {} max",
+                utils::nanos_to_ms_pritable(histogram.min()),
+                utils::nanos_to_ms_pritable(histogram.mean() as u64),
+                utils::nanos_to_ms_pritable(histogram.max())
+            );
+            for percentile in [0.5, 0.95, 0.99] {
+                latency_yaml.push_str(&format!(
+                    ", {} p{percentile:4.2}",
+                    utils::nanos_to_ms_pritable(histogram.value_at_quantile(percentile)),
+                ));
+            }
+            println!();
+            println!("{latency_yaml}");
+        }
+
+        println!(
+            "{} total records received, {} records/sec: ({}/sec), total time: {}",
+            end.total_records,
+            end.records_per_sec,
+            ByteSize(end.bytes_per_sec),
+            utils::pretty_duration(end.elapsed)
+        );
+        println!("{}", Self::to_markdown_table(end));
+    }
+
+    fn to_markdown_table(end: &EndConsumerStat) -> String {
+        let mut md = String::new();
+        md.push('\n');
+        let mut latency_yaml = "- Variable: Latency\n".to_string();
+        for percentile in [0.0, 0.5, 0.95, 0.99, 1.0] {
+            latency_yaml.push_str(&format!(
+                "  p{percentile:4.2}: {}\n",
+                utils::nanos_to_ms_pritable(end.latencies_histogram.value_at_quantile(percentile)),
+            ));
+        }
+        md.push_str("**Per Record E2E Latency**\n\n");
+        md.push_str(&mk_md_table_from_yaml(&latency_yaml, &None));
+        md.push_str("\n\n**Throughput (Total Consumed Bytes / Time)**\n\n");
+        let mut throughput_yaml = String::new();
+        throughput_yaml.push_str("- Variable: Consumed Throughput\n");
+        throughput_yaml.push_str(&format!(
+            "  Speed: \"{}/sec\"\n",
+            ByteSize(end.bytes_per_sec)
+        ));
+        md.push_str(&mk_md_table_from_yaml(&throughput_yaml, &None));
+        md.push('\n');
+        md
+    }
+}
+
+/// Split partitions between consumers round robin, consumers without partitions are dropped
+fn assign_partitions(partitions: u32, consumers: u64) -> Vec<Vec<PartitionId>> {
+    let consumers = consumers.clamp(1, partitions.max(1) as u64) as usize;
+    let mut assignments = vec![Vec::new(); consumers];
+    for partition in 0..partitions {
+        assignments[partition as usize % consumers].push(partition);
+    }
+    assignments.retain(|partitions| !partitions.is_empty());
+    assignments
+}
+
+struct ConsumerStats {
+    records: AtomicU64,
+    bytes: AtomicU64,
+    /// time from record timestamp to consumption, in nanoseconds
+    latencies: Mutex<Histogram<u64>>,
+}
+
+impl ConsumerStats {
+    fn new() -> Self {
+        Self {
+            records: AtomicU64::new(0),
+            bytes: AtomicU64::new(0),
+            latencies: Mutex::new(Histogram::<u64>::new(3).expect("new histogram")),
+        }
+    }
+
+    /// record consumed record, returns total number of records consumed
+    fn record(&self, record: &Record) -> u64 {
+        let bytes = record.value().len() + record.key().map_or(0, |key| key.len());
+        self.bytes.fetch_add(bytes as u64, Ordering::Relaxed);
+
+        let timestamp = record.timestamp();
+        if timestamp != NO_TIMESTAMP {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |now| now.as_millis() as i64);
+            if let Ok(latency) = u64::try_from(now - timestamp) {
+                let mut latencies = self.latencies.lock().expect("latencies lock");
+                let _ = latencies.record(latency * 1_000_000);
+            }
+        }
+
+        self.records.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    fn end(&self, elapsed: Duration) -> EndConsumerStat {
+        let total_records = self.records.load(Ordering::Relaxed);
+        let total_bytes = self.bytes.load(Ordering::Relaxed);
+        let elapsed_seconds = elapsed.as_millis().max(1) as f64 / 1000.0;
+        EndConsumerStat {
+            latencies_histogram: self.latencies.lock().expect("latencies lock").clone(),
+            total_records,
+            total_bytes,
+            records_per_sec: (total_records as f64 / elapsed_seconds).round() as u64,
+            bytes_per_sec: (total_bytes as f64 / elapsed_seconds).round() as u64,
+            elapsed,
+        }
+    }
+}
+
+struct EndConsumerStat {
+    latencies_histogram: Histogram<u64>,
+    total_records: u64,
+    total_bytes: u64,
+    records_per_sec: u64,
+    bytes_per_sec: u64,
+    elapsed: Duration,
+}
+
+impl EndConsumerStat {
+    fn to_report(&self, config: &ConsumerConfig, consumers: u64) -> BenchmarkReport {
+        BenchmarkReport {
+            benchmark: "consumer",
+            topic: config.topic_name.clone(),
+            clients: consumers,
+            records: self.total_records,
+            bytes: self.total_bytes,
+            elapsed_ms: self.elapsed.as_millis() as u64,
+            records_per_sec: self.records_per_sec,
+            bytes_per_sec: self.bytes_per_sec,
+            latency: LatencyReport::from_histogram(&self.latencies_histogram),
+            batches: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assign_partitions() {
+        assert_eq!(assign_partitions(4, 2), vec![vec![0, 2], vec![1, 3]]);
+        assert_eq!(assign_partitions(2, 4), vec![vec![0], vec![1]]);
+        assert_eq!(assign_partitions(3, 0), vec![vec![0, 1, 2]]);
+    }
+}
@@ -4,4 +4,6 @@ pub mod producer_worker;
 pub mod stats_collector;
 pub mod benchmark_driver;
 pub mod producer_benchmark;
+pub mod consumer_benchmark;
+pub mod report;
 pub mod utils;
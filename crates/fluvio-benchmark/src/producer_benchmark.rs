@@ -14,6 +14,7 @@ use tracing::debug;
 use crate::{
     config::ProducerConfig,
     producer_worker::ProducerWorker,
+    report::{BatchReport, BenchmarkReport, LatencyReport},
     stats_collector::{EndProducerStat, StatCollector, Stats},
     utils,
 };
@@ -64,9 +65,13 @@ impl ProducerBenchmark {
         Self::setup_producers(config.clone(), stat_collector).await;
         println!("Benchmark started");
         Self::print_progress_on_backgroud(stats_receiver).await;
-        Self::print_benchmark_on_end(&mut end_receiver).await;
+        let end = Self::print_benchmark_on_end(&mut end_receiver).await;
         println!("Benchmark completed");
 
+        if let (Some(path), Some(end)) = (&config.report, end) {
+            Self::to_report(&config, &end).write(path)?;
+        }
+
         Ok(())
     }
 
@@ -112,7 +117,9 @@ impl ProducerBenchmark {
         });
     }
 
-    async fn print_benchmark_on_end(end_receiver: &mut broadcast::Receiver<EndProducerStat>) {
+    async fn print_benchmark_on_end(
+        end_receiver: &mut broadcast::Receiver<EndProducerStat>,
+    ) -> Option<EndProducerStat> {
         if let Ok(end) = end_receiver.recv().await {
             // sleep enough time to make sure all stats are printed
             sleep(std::time::Duration::from_secs(1)).await;
@@ -142,8 +149,37 @@ impl ProducerBenchmark {
                 human_readable_bytes,
                 utils::pretty_duration(end.elapsed)
             );
+            let batches = BatchReport::new(end.total_batches, end.total_records, end.total_bytes);
+            println!(
+                "{} batches, {:.1} records/batch, {}/batch",
+                batches.batches,
+                batches.records_per_batch,
+                ByteSize(batches.bytes_per_batch)
+            );
 
             println!("{}", Self::to_markdown_table(&end));
+            Some(end)
+        } else {
+            None
+        }
+    }
+
+    fn to_report(config: &ProducerConfig, end: &EndProducerStat) -> BenchmarkReport {
+        BenchmarkReport {
+            benchmark: "producer",
+            topic: config.topic_name.clone(),
+            clients: config.num_producers,
+            records: end.total_records,
+            bytes: end.total_bytes,
+            elapsed_ms: end.elapsed.as_millis() as u64,
+            records_per_sec: end.records_per_sec,
+            bytes_per_sec: end.bytes_per_sec,
+            latency: LatencyReport::from_histogram(&end.latencies_histogram),
+            batches: Some(BatchReport::new(
+                end.total_batches,
+                end.total_records,
+                end.total_bytes,
+            )),
         }
     }
 
@@ -167,6 +203,20 @@ impl ProducerBenchmark {
             ByteSize(end.bytes_per_sec)
         ));
         md.push_str(&mk_md_table_from_yaml(&throughput_yaml, &None));
+        md.push_str("\n\n**Batch Efficiency**\n\n");
+        let batches = BatchReport::new(end.total_batches, end.total_records, end.total_bytes);
+        let mut batch_yaml = String::new();
+        batch_yaml.push_str("- Variable: Produced Batches\n");
+        batch_yaml.push_str(&format!("  Batches: {}\n", batches.batches));
+        batch_yaml.push_str(&format!(
+            "  Records/Batch: \"{:.1}\"\n",
+            batches.records_per_batch
+        ));
+        batch_yaml.push_str(&format!(
+            "  Bytes/Batch: \"{}\"\n",
+            ByteSize(batches.bytes_per_batch)
+        ));
+        md.push_str(&mk_md_table_from_yaml(&batch_yaml, &None));
         md.push('\n');
         md
     }
@@ -0,0 +1,113 @@
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use hdrhistogram::Histogram;
+use serde::Serialize;
+
+/// Summary of benchmark run, written as JSON for comparison between runs
+#[derive(Debug, Serialize)]
+pub struct BenchmarkReport {
+    pub benchmark: &'static str,
+    pub topic: String,
+    /// number of producers or consumers
+    pub clients: u64,
+    pub records: u64,
+    pub bytes: u64,
+    pub elapsed_ms: u64,
+    pub records_per_sec: u64,
+    pub bytes_per_sec: u64,
+    pub latency: LatencyReport,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub batches: Option<BatchReport>,
+}
+
+impl BenchmarkReport {
+    pub fn write(&self, path: &Path) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("unable to write report {}", path.display()))?;
+        println!("Report written to {}", path.display());
+        Ok(())
+    }
+}
+
+/// Latency percentiles in milliseconds
+#[derive(Debug, Serialize, PartialEq)]
+pub struct LatencyReport {
+    pub min_ms: f64,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+}
+
+impl LatencyReport {
+    /// from histogram of latencies in nanoseconds
+    pub fn from_histogram(histogram: &Histogram<u64>) -> Self {
+        let ms = |nanos: u64| Duration::from_nanos(nanos).as_secs_f64() * 1000.0;
+        Self {
+            min_ms: ms(histogram.min()),
+            p50_ms: ms(histogram.value_at_quantile(0.5)),
+            p95_ms: ms(histogram.value_at_quantile(0.95)),
+            p99_ms: ms(histogram.value_at_quantile(0.99)),
+            max_ms: ms(histogram.max()),
+        }
+    }
+}
+
+/// How well records are packed into produced batches
+#[derive(Debug, Serialize, PartialEq)]
+pub struct BatchReport {
+    pub batches: u64,
+    pub records_per_batch: f64,
+    pub bytes_per_batch: u64,
+}
+
+impl BatchReport {
+    pub fn new(batches: u64, records: u64, bytes: u64) -> Self {
+        if batches == 0 {
+            return Self {
+                batches,
+                records_per_batch: 0.0,
+                bytes_per_batch: 0,
+            };
+        }
+        Self {
+            batches,
+            records_per_batch: records as f64 / batches as f64,
+            bytes_per_batch: bytes / batches,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_report() {
+        let mut histogram = Histogram::<u64>::new(3).expect("histogram");
+        for ms in 1..=100 {
+            histogram.record(ms * 1_000_000).expect("record");
+        }
+        let report = LatencyReport::from_histogram(&histogram);
+        assert_eq!(report.min_ms.round(), 1.0);
+        assert_eq!(report.p50_ms.round(), 50.0);
+        assert_eq!(report.p99_ms.round(), 99.0);
+        assert_eq!(report.max_ms.round(), 100.0);
+    }
+
+    #[test]
+    fn test_batch_report() {
+        assert_eq!(
+            BatchReport::new(4, 10, 4096),
+            BatchReport {
+                batches: 4,
+                records_per_batch: 2.5,
+                bytes_per_batch: 1024,
+            }
+        );
+        assert_eq!(BatchReport::new(0, 0, 0).records_per_batch, 0.0);
+    }
+}
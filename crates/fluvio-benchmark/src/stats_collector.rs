@@ -18,6 +18,7 @@ pub(crate) struct ProducerStat {}
 pub struct TotalStats {
     record_send: AtomicU64,
     record_bytes: AtomicU64,
+    batches: AtomicU64,
     first_start_time: OnceCell<Instant>,
 }
 
@@ -40,6 +41,8 @@ pub struct Stats {
 pub struct EndProducerStat {
     pub latencies_histogram: Histogram<u64>,
     pub total_records: u64,
+    pub total_bytes: u64,
+    pub total_batches: u64,
     pub records_per_sec: u64,
     pub bytes_per_sec: u64,
     pub elapsed: Duration,
@@ -92,6 +95,9 @@ impl ProducerStat {
                     total_stats
                         .record_bytes
                         .fetch_add(event.bytes_size, std::sync::atomic::Ordering::Relaxed);
+                    total_stats
+                        .batches
+                        .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
 
                     central_stats_tx
                         .send(CentralStats {
@@ -124,6 +130,9 @@ impl ProducerStat {
             let record_bytes = total_stats
                 .record_bytes
                 .load(std::sync::atomic::Ordering::Relaxed);
+            let total_batches = total_stats
+                .batches
+                .load(std::sync::atomic::Ordering::Relaxed);
             let latency_histogram = latency_histogram.read().await;
             let elapsed = total_stats
                 .first_start_time
@@ -143,6 +152,8 @@ impl ProducerStat {
             let end = EndProducerStat {
                 latencies_histogram,
                 total_records: record_send,
+                total_bytes: record_bytes,
+                total_batches,
                 records_per_sec,
                 bytes_per_sec,
                 elapsed,
@@ -174,6 +185,7 @@ impl StatCollector {
         let total_stats = Arc::new(TotalStats {
             record_send: AtomicU64::new(0),
             record_bytes: AtomicU64::new(0),
+            batches: AtomicU64::new(0),
             first_start_time: OnceCell::new(),
         });
 
@@ -1,22 +1,27 @@
 #[global_allocator]
 static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 
-use clap::Parser;
 use anyhow::Result;
 
-use fluvio_cli::{Root, HelpOpt};
+use fluvio_cli::{Root, HelpOpt, exit_code};
 use fluvio_future::task::run_block_on;
 
 fn main() -> Result<()> {
     fluvio_future::subscriber::init_tracer(None);
 
     print_help_hack()?;
-    let root: Root = Root::parse();
+    let root = match Root::parse_with_config() {
+        Ok(root) => root,
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+    };
 
     // If the CLI comes back with an error, attempt to handle it
     if let Err(e) = run_block_on(root.process()) {
         eprintln!("{e}");
-        std::process::exit(1);
+        std::process::exit(exit_code(&e));
     }
 
     Ok(())
@@ -73,6 +78,14 @@ mod tests {
         assert!(parse("fluvio consume --start --end -5 -n 0 hello").is_err());
     }
 
+    #[test]
+    fn test_global_timeout() {
+        assert!(parse("fluvio --timeout 30s topic list").is_ok());
+        assert!(parse("fluvio topic list --timeout 5m").is_ok());
+        assert!(parse("fluvio consume hello --timeout 1h").is_ok());
+        assert!(parse("fluvio topic list --timeout 30").is_err());
+    }
+
     fn parse(command: &str) -> Result<Root, clap::error::Error> {
         Root::try_parse_from(command.split_whitespace())
     }
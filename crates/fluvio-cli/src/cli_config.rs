@@ -0,0 +1,464 @@
+//! # CLI Config
+//!
+//! Defaults for CLI flags, loaded from `~/.fluvio/cli.toml`.
+//!
+//! ```toml
+//! output = "json"              # -O for list and describe commands
+//! consume_output = "dynamic"   # -O for consume
+//! table_format = "my-format"   # --table-format for consume
+//! isolation = "read_committed" # --isolation for produce and consume
+//! timeout = "30s"              # --timeout of every command
+//! color = "never"              # auto, always or never
+//! theme = "light"              # dark, light, no-color or ascii
+//!
+//! [alias]
+//! ct = "consume --output json --tail 10"
+//! ```
+//!
+//! Values are used as clap defaults, so flags and their environment variables
+//! always take precedence. `FLUVIO_THEME` overrides `theme`, and `NO_COLOR` turns off
+//! colors of any theme.
+//!
+//! Aliases are expanded before command line is parsed, `fluvio ct events` runs
+//! `fluvio consume --output json --tail 10 events`. Aliases may refer to other aliases,
+//! builtin commands take precedence over aliases of the same name.
+
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use clap::{Command, Parser, ValueEnum};
+use serde::Deserialize;
+use tracing::debug;
+use anyhow::Result;
+
+use fluvio_cli_common::install::fluvio_base_dir;
+use fluvio_extension_common::output::{OutputType, Theme, set_theme};
+
+use crate::CliError;
+use crate::client::ConsumeOutputType;
+use crate::util::parse_isolation;
+
+/// overrides location of CLI config file
+pub const FLUVIO_CLI_CONFIG: &str = "FLUVIO_CLI_CONFIG";
+const CLI_CONFIG_FILE: &str = "cli.toml";
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorChoice {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(Debug, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CliConfig {
+    /// output format of list and describe commands
+    pub output: Option<String>,
+    /// output format of consume
+    pub consume_output: Option<String>,
+    /// table format used by consume
+    pub table_format: Option<String>,
+    /// isolation of produce and consume
+    pub isolation: Option<String>,
+    /// default of global `--timeout`, ex: '30s'
+    pub timeout: Option<String>,
+    #[serde(default)]
+    pub color: ColorChoice,
+    /// theme of table and text output
+    pub theme: Option<Theme>,
+    /// command aliases, name to command line it expands to
+    #[serde(default)]
+    pub alias: BTreeMap<String, String>,
+}
+
+impl CliConfig {
+    /// location of CLI config file
+    pub fn path() -> Result<PathBuf> {
+        if let Ok(path) = std::env::var(FLUVIO_CLI_CONFIG) {
+            return Ok(path.into());
+        }
+        Ok(fluvio_base_dir()?.join(CLI_CONFIG_FILE))
+    }
+
+    /// load config from default location, empty config if file doesn't exist
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Self::load_from(path)
+    }
+
+    pub fn load_from(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        debug!(path = %path.display(), "loading CLI config");
+        let content = std::fs::read_to_string(path)?;
+        Self::parse(&content)
+            .map_err(|err| CliError::CliConfig(format!("{}: {err}", path.display())).into())
+    }
+
+    fn parse(content: &str) -> Result<Self, String> {
+        let config: Self = toml::from_str(content).map_err(|err| err.to_string())?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// reject values clap would not accept, so error points to config file instead of flag
+    fn validate(&self) -> Result<(), String> {
+        if let Some(output) = &self.output {
+            OutputType::from_str(output, true).map_err(|err| format!("output: {err}"))?;
+        }
+        if let Some(output) = &self.consume_output {
+            ConsumeOutputType::from_str(output, true)
+                .map_err(|err| format!("consume_output: {err}"))?;
+        }
+        if let Some(isolation) = &self.isolation {
+            parse_isolation(isolation).map_err(|err| format!("isolation: {err}"))?;
+        }
+        self.timeout()?;
+        for (name, command) in &self.alias {
+            match shell_words::split(command) {
+                Ok(words) if !words.is_empty() => {}
+                Ok(_) => return Err(format!("alias.{name}: empty command")),
+                Err(err) => return Err(format!("alias.{name}: {err}")),
+            }
+        }
+        Ok(())
+    }
+
+    pub fn timeout(&self) -> Result<Option<Duration>, String> {
+        self.timeout
+            .as_deref()
+            .map(|timeout| {
+                humantime::parse_duration(timeout).map_err(|err| format!("timeout: {err}"))
+            })
+            .transpose()
+    }
+
+    /// apply settings which are not command flags
+    pub fn apply_env(&self) {
+        let color_env_set = ["NO_COLOR", "CLICOLOR", "CLICOLOR_FORCE"]
+            .iter()
+            .any(|var| std::env::var_os(var).is_some());
+        if !color_env_set {
+            match self.color {
+                ColorChoice::Auto => {}
+                ColorChoice::Always => colored::control::set_override(true),
+                ColorChoice::Never => colored::control::set_override(false),
+            }
+        }
+
+        let theme = match Theme::resolve(self.theme) {
+            theme if theme.is_colored() && self.color == ColorChoice::Never => Theme::NoColor,
+            theme => theme,
+        };
+        if !theme.is_colored() {
+            colored::control::set_override(false);
+        }
+        set_theme(theme);
+    }
+
+    /// replace alias given as subcommand with command line it stands for
+    pub fn expand_aliases(&self, cmd: &Command, mut args: Vec<OsString>) -> Result<Vec<OsString>> {
+        if self.alias.is_empty() {
+            return Ok(args);
+        }
+        let Some(position) = subcommand_position(cmd, &args) else {
+            return Ok(args);
+        };
+
+        let mut expanded: Vec<String> = vec![];
+        while let Some(name) = args[position].to_str().map(str::to_owned) {
+            if cmd.find_subcommand(&name).is_some() {
+                break;
+            }
+            let Some(command) = self.alias.get(&name) else {
+                break;
+            };
+            let cycle = expanded.contains(&name);
+            expanded.push(name);
+            if cycle {
+                return Err(
+                    CliError::CliConfig(format!("alias cycle: {}", expanded.join(" -> "))).into(),
+                );
+            }
+            let words = shell_words::split(command)
+                .map_err(|err| CliError::CliConfig(format!("alias: {err}")))?;
+            debug!(?words, "expanding alias");
+            args.splice(position..=position, words.into_iter().map(OsString::from));
+        }
+        Ok(args)
+    }
+
+    /// set defaults of matching flags on command and all its subcommands
+    pub fn apply_defaults(&self, mut cmd: Command) -> Command {
+        let is_consume = cmd.get_name() == "consume";
+        let defaults: Vec<(String, String)> = cmd
+            .get_arguments()
+            .filter_map(|arg| {
+                let value = match arg.get_long()? {
+                    "output" if is_consume => self.consume_output.as_ref(),
+                    "output" => self.output.as_ref(),
+                    "isolation" => self.isolation.as_ref(),
+                    "table-format" => self.table_format.as_ref(),
+                    // only global flag, subcommands have timeouts of their own operations
+                    "timeout" if arg.is_global_set() => self.timeout.as_ref(),
+                    _ => None,
+                }?;
+                Some((arg.get_id().to_string(), value.clone()))
+            })
+            .collect();
+        for (id, value) in defaults {
+            cmd = cmd.mut_arg(id, |arg| arg.default_value(value));
+        }
+
+        let subcommands: Vec<String> = cmd
+            .get_subcommands()
+            .map(|sub| sub.get_name().to_owned())
+            .collect();
+        for name in subcommands {
+            cmd = cmd.mut_subcommand(name, |sub| self.apply_defaults(sub));
+        }
+        cmd
+    }
+}
+
+/// index of first argument which is neither a flag of the command nor value of a flag
+fn subcommand_position(cmd: &Command, args: &[OsString]) -> Option<usize> {
+    let mut args = args.iter().enumerate().skip(1);
+    while let Some((index, arg)) = args.next() {
+        let arg = arg.to_str()?;
+        if !arg.starts_with('-') {
+            return Some(index);
+        }
+        if arg.contains('=') {
+            continue;
+        }
+        let takes_value = cmd.get_arguments().any(|option| {
+            let matches_long = arg
+                .strip_prefix("--")
+                .is_some_and(|long| option.get_long() == Some(long));
+            let matches_short = arg.len() == 2
+                && arg.strip_prefix('-').and_then(|short| short.chars().next())
+                    == option.get_short();
+            (matches_long || matches_short) && option.get_action().takes_values()
+        });
+        if takes_value {
+            args.next();
+        }
+    }
+    None
+}
+
+/// Manage command aliases of CLI config
+#[derive(Debug, Parser)]
+pub enum AliasCmd {
+    /// List aliases with the commands they expand to
+    #[command(name = "list")]
+    List,
+}
+
+impl AliasCmd {
+    pub fn process(self) -> Result<()> {
+        let config = CliConfig::load()?;
+        match self {
+            Self::List => {
+                if config.alias.is_empty() {
+                    println!(
+                        "no aliases defined, add them to [alias] table of {}",
+                        CliConfig::path()?.display()
+                    );
+                }
+                let width = config
+                    .alias
+                    .keys()
+                    .map(String::len)
+                    .max()
+                    .unwrap_or_default();
+                for (name, command) in &config.alias {
+                    println!("{name:width$}  {command}");
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use clap::CommandFactory;
+    use fluvio_spu_schema::Isolation;
+
+    use crate::Root;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_cli_config() {
+        let config = CliConfig::parse(
+            r#"
+            output = "json"
+            consume_output = "raw"
+            isolation = "read_uncommitted"
+            timeout = "30s"
+            color = "never"
+            theme = "ascii"
+
+            [alias]
+            ct = "consume --output json --tail 10"
+        "#,
+        )
+        .expect("parse");
+
+        assert_eq!(config.output.as_deref(), Some("json"));
+        assert_eq!(config.color, ColorChoice::Never);
+        assert_eq!(config.theme, Some(Theme::Ascii));
+        assert_eq!(
+            config.alias.get("ct").map(String::as_str),
+            Some("consume --output json --tail 10")
+        );
+        assert_eq!(config.timeout(), Ok(Some(Duration::from_secs(30))));
+
+        assert!(CliConfig::parse("output = \"xml\"").is_err());
+        assert!(CliConfig::parse("isolation = \"none\"").is_err());
+        assert!(CliConfig::parse("timeout = \"soon\"").is_err());
+        assert!(CliConfig::parse("unknown = 1").is_err());
+        assert!(CliConfig::parse("theme = \"solarized\"").is_err());
+        assert!(CliConfig::parse("[alias]\nct = \"\"").is_err());
+        assert!(CliConfig::parse("[alias]\nct = \"consume 'events\"").is_err());
+        assert_eq!(CliConfig::parse("").expect("empty"), CliConfig::default());
+    }
+
+    #[test]
+    fn test_config_defaults_overridden_by_flags() {
+        let config = CliConfig {
+            output: Some("yaml".to_owned()),
+            consume_output: Some("raw".to_owned()),
+            isolation: Some("read_uncommitted".to_owned()),
+            ..Default::default()
+        };
+        let cmd = config.apply_defaults(Root::command());
+
+        let matches = cmd
+            .clone()
+            .try_get_matches_from(["fluvio", "consume", "hello"])
+            .expect("parse");
+        let consume = matches.subcommand_matches("consume").expect("consume");
+        assert_eq!(
+            consume.get_one::<ConsumeOutputType>("output"),
+            Some(&ConsumeOutputType::raw)
+        );
+        assert_eq!(
+            consume.get_one::<Isolation>("isolation"),
+            Some(&Isolation::ReadUncommitted)
+        );
+
+        let matches = cmd
+            .clone()
+            .try_get_matches_from(["fluvio", "consume", "hello", "-O", "json"])
+            .expect("parse");
+        let consume = matches.subcommand_matches("consume").expect("consume");
+        assert_eq!(
+            consume.get_one::<ConsumeOutputType>("output"),
+            Some(&ConsumeOutputType::json)
+        );
+
+        let matches = cmd
+            .try_get_matches_from(["fluvio", "topic", "list"])
+            .expect("parse");
+        let (_, list) = matches
+            .subcommand_matches("topic")
+            .and_then(|topic| topic.subcommand())
+            .expect("list");
+        assert_eq!(
+            list.get_one::<OutputType>("format"),
+            Some(&OutputType::yaml)
+        );
+    }
+
+    #[test]
+    fn test_config_timeout_is_global_timeout_default() {
+        let config = CliConfig {
+            timeout: Some("30s".to_owned()),
+            ..Default::default()
+        };
+        let cmd = config.apply_defaults(Root::command());
+
+        let matches = cmd
+            .clone()
+            .try_get_matches_from(["fluvio", "topic", "list"])
+            .expect("parse");
+        assert_eq!(
+            matches.get_one::<Duration>("timeout"),
+            Some(&Duration::from_secs(30))
+        );
+
+        let matches = cmd
+            .try_get_matches_from(["fluvio", "--timeout", "5s", "topic", "list"])
+            .expect("parse");
+        assert_eq!(
+            matches.get_one::<Duration>("timeout"),
+            Some(&Duration::from_secs(5))
+        );
+    }
+
+    fn args(line: &str) -> Vec<OsString> {
+        line.split_whitespace().map(OsString::from).collect()
+    }
+
+    #[test]
+    fn test_expand_aliases() {
+        let config = CliConfig {
+            alias: [
+                ("ct", "c --tail 10"),
+                ("c", "consume --output json"),
+                ("topic", "consume"),
+                ("loop", "again"),
+                ("again", "loop -B"),
+            ]
+            .into_iter()
+            .map(|(name, command)| (name.to_owned(), command.to_owned()))
+            .collect(),
+            ..Default::default()
+        };
+        let cmd = Root::command();
+
+        assert_eq!(
+            config
+                .expand_aliases(&cmd, args("fluvio ct events -k"))
+                .expect("expand"),
+            args("fluvio consume --output json --tail 10 events -k")
+        );
+        // root flags before alias, with and without values
+        assert_eq!(
+            config
+                .expand_aliases(&cmd, args("fluvio -P cloud --timeout=5s ct events"))
+                .expect("expand"),
+            args("fluvio -P cloud --timeout=5s consume --output json --tail 10 events")
+        );
+        // builtin commands are not replaced
+        assert_eq!(
+            config
+                .expand_aliases(&cmd, args("fluvio topic list"))
+                .expect("expand"),
+            args("fluvio topic list")
+        );
+        // only subcommand position is expanded
+        assert_eq!(
+            config
+                .expand_aliases(&cmd, args("fluvio consume ct"))
+                .expect("expand"),
+            args("fluvio consume ct")
+        );
+
+        let err = config
+            .expand_aliases(&cmd, args("fluvio loop events"))
+            .expect_err("cycle");
+        assert!(err.to_string().contains("loop -> again -> loop"));
+    }
+}
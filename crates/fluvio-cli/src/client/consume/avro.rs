@@ -0,0 +1,531 @@
+//!
+//! # Avro records
+//!
+//! Decodes Avro encoded record values to JSON, with schema from file or from schema registry.
+//! Values of topics using schema registry are framed with magic byte `0` and
+//! 4 byte big endian schema id, schemas of the subject are fetched when consumer starts.
+//! Union values are rendered as plain values of their branch, and bytes as strings of chars
+//! with code points of bytes, as in Avro JSON encoding.
+//!
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::{Context, Result, anyhow};
+use serde::Deserialize;
+use serde_json::{Map, Number, Value};
+use tracing::debug;
+
+use fluvio_hub_util::htclient::{self, ResponseExt};
+
+/// first byte of values framed with schema id
+const REGISTRY_MAGIC_BYTE: u8 = 0;
+
+/// Decoder of Avro values
+#[derive(Debug)]
+pub(crate) struct AvroDecoder {
+    schemas: AvroSchemas,
+}
+
+#[derive(Debug)]
+enum AvroSchemas {
+    /// values are plain Avro datums of a schema
+    Single(ParsedSchema),
+    /// values are framed with id of their schema
+    Registry(HashMap<u32, ParsedSchema>),
+}
+
+impl AvroDecoder {
+    /// schema from Avro schema file (.avsc)
+    pub(crate) fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("unable to read schema {}", path.display()))?;
+        let schema = ParsedSchema::parse(&content)
+            .with_context(|| format!("invalid schema {}", path.display()))?;
+        Ok(Self {
+            schemas: AvroSchemas::Single(schema),
+        })
+    }
+
+    /// all versions of schema subject from schema registry
+    pub(crate) async fn from_registry(url: &str, subject: &str) -> Result<Self> {
+        #[derive(Deserialize)]
+        struct SubjectVersion {
+            id: u32,
+            schema: String,
+        }
+
+        let url = url.trim_end_matches('/');
+        let versions: Vec<u32> = get_json(&format!("{url}/subjects/{subject}/versions")).await?;
+        let mut schemas = HashMap::new();
+        for version in versions {
+            let subject_version: SubjectVersion =
+                get_json(&format!("{url}/subjects/{subject}/versions/{version}")).await?;
+            let schema = ParsedSchema::parse(&subject_version.schema).with_context(|| {
+                format!("invalid schema of subject {subject} version {version}")
+            })?;
+            schemas.insert(subject_version.id, schema);
+        }
+        debug!(
+            subject,
+            schemas = schemas.len(),
+            "loaded schemas from registry"
+        );
+        Ok(Self {
+            schemas: AvroSchemas::Registry(schemas),
+        })
+    }
+
+    pub(crate) fn decode(&self, value: &[u8]) -> Result<Value> {
+        let (schema, datum) = match &self.schemas {
+            AvroSchemas::Single(schema) => (schema, value),
+            AvroSchemas::Registry(schemas) => {
+                let [REGISTRY_MAGIC_BYTE, a, b, c, d, datum @ ..] = value else {
+                    return Err(anyhow!("value is not framed with schema id"));
+                };
+                let id = u32::from_be_bytes([*a, *b, *c, *d]);
+                let schema = schemas
+                    .get(&id)
+                    .ok_or_else(|| anyhow!("schema id {id} is not in schema subject"))?;
+                (schema, datum)
+            }
+        };
+        let mut reader = Reader { data: datum };
+        let decoded = schema.decode(&schema.root, &mut reader)?;
+        if !reader.data.is_empty() {
+            return Err(anyhow!(
+                "{} bytes left after value, schema doesn't match",
+                reader.data.len()
+            ));
+        }
+        Ok(decoded)
+    }
+}
+
+async fn get_json<T: serde::de::DeserializeOwned>(url: &str) -> Result<T> {
+    let response = htclient::get(url).await?;
+    if !response.status().is_success() {
+        return Err(anyhow!(
+            "schema registry request {url} failed: {} {}",
+            response.status(),
+            response.body_string().unwrap_or_default()
+        ));
+    }
+    response.json()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Schema {
+    Null,
+    Boolean,
+    Int,
+    Long,
+    Float,
+    Double,
+    Bytes,
+    String,
+    Record(Vec<(String, Schema)>),
+    Enum(Vec<String>),
+    Array(Box<Schema>),
+    Map(Box<Schema>),
+    Union(Vec<Schema>),
+    Fixed(usize),
+    /// reference to named type
+    Named(String),
+}
+
+/// Schema with its named types
+#[derive(Debug)]
+struct ParsedSchema {
+    root: Schema,
+    names: HashMap<String, Schema>,
+}
+
+impl ParsedSchema {
+    fn parse(schema: &str) -> Result<Self> {
+        let json: Value = serde_json::from_str(schema)?;
+        let mut names = HashMap::new();
+        let root = parse_schema(&json, None, &mut names)?;
+        Ok(Self { root, names })
+    }
+
+    fn decode(&self, schema: &Schema, reader: &mut Reader) -> Result<Value> {
+        let value = match schema {
+            Schema::Null => Value::Null,
+            Schema::Boolean => Value::Bool(reader.byte()? != 0),
+            Schema::Int | Schema::Long => Value::from(reader.long()?),
+            Schema::Float => {
+                let value = f32::from_le_bytes(reader.fixed(4)?.try_into()?);
+                float(f64::from(value))
+            }
+            Schema::Double => float(f64::from_le_bytes(reader.fixed(8)?.try_into()?)),
+            Schema::Bytes => {
+                let len = reader.len()?;
+                Value::String(bytes_string(reader.fixed(len)?))
+            }
+            Schema::String => {
+                let len = reader.len()?;
+                Value::String(std::str::from_utf8(reader.fixed(len)?)?.to_owned())
+            }
+            Schema::Record(fields) => {
+                let mut object = Map::new();
+                for (name, field) in fields {
+                    object.insert(name.clone(), self.decode(field, reader)?);
+                }
+                Value::Object(object)
+            }
+            Schema::Enum(symbols) => {
+                let index = reader.long()?;
+                let symbol = usize::try_from(index)
+                    .ok()
+                    .and_then(|index| symbols.get(index))
+                    .ok_or_else(|| anyhow!("invalid enum index {index}"))?;
+                Value::String(symbol.clone())
+            }
+            Schema::Array(items) => {
+                let mut values = vec![];
+                while let Some(count) = reader.block()? {
+                    for _ in 0..count {
+                        values.push(self.decode(items, reader)?);
+                    }
+                }
+                Value::Array(values)
+            }
+            Schema::Map(values) => {
+                let mut object = Map::new();
+                while let Some(count) = reader.block()? {
+                    for _ in 0..count {
+                        let len = reader.len()?;
+                        let key = std::str::from_utf8(reader.fixed(len)?)?.to_owned();
+                        object.insert(key, self.decode(values, reader)?);
+                    }
+                }
+                Value::Object(object)
+            }
+            Schema::Union(branches) => {
+                let index = reader.long()?;
+                let branch = usize::try_from(index)
+                    .ok()
+                    .and_then(|index| branches.get(index))
+                    .ok_or_else(|| anyhow!("invalid union index {index}"))?;
+                self.decode(branch, reader)?
+            }
+            Schema::Fixed(size) => Value::String(bytes_string(reader.fixed(*size)?)),
+            Schema::Named(name) => {
+                let schema = self
+                    .names
+                    .get(name)
+                    .ok_or_else(|| anyhow!("unknown type {name}"))?;
+                self.decode(schema, reader)?
+            }
+        };
+        Ok(value)
+    }
+}
+
+/// NaN and infinity are not valid JSON numbers
+fn float(value: f64) -> Value {
+    Number::from_f64(value).map_or_else(|| Value::String(value.to_string()), Value::Number)
+}
+
+fn bytes_string(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| char::from(*byte)).collect()
+}
+
+fn parse_schema(
+    json: &Value,
+    namespace: Option<&str>,
+    names: &mut HashMap<String, Schema>,
+) -> Result<Schema> {
+    match json {
+        Value::String(name) => {
+            primitive(name).map_or_else(|| Ok(Schema::Named(full_name(name, namespace))), Ok)
+        }
+        Value::Array(branches) => Ok(Schema::Union(
+            branches
+                .iter()
+                .map(|branch| parse_schema(branch, namespace, names))
+                .collect::<Result<_>>()?,
+        )),
+        Value::Object(object) => {
+            let type_name = object
+                .get("type")
+                .ok_or_else(|| anyhow!("missing type in {json}"))?;
+            let Value::String(type_name) = type_name else {
+                // type is nested schema
+                return parse_schema(type_name, namespace, names);
+            };
+            let name = object.get("name").and_then(Value::as_str);
+            let namespace = object
+                .get("namespace")
+                .and_then(Value::as_str)
+                .or(namespace);
+            let schema = match type_name.as_str() {
+                "record" | "error" => {
+                    let name = name.ok_or_else(|| anyhow!("record without name"))?;
+                    let full = full_name(name, namespace);
+                    let namespace = full.rsplit_once('.').map(|(namespace, _)| namespace);
+                    let fields = object
+                        .get("fields")
+                        .and_then(Value::as_array)
+                        .ok_or_else(|| anyhow!("record {full} without fields"))?;
+                    let mut parsed = Vec::with_capacity(fields.len());
+                    for field in fields {
+                        let field_name = field
+                            .get("name")
+                            .and_then(Value::as_str)
+                            .ok_or_else(|| anyhow!("field of record {full} without name"))?;
+                        let field_type = field
+                            .get("type")
+                            .ok_or_else(|| anyhow!("field {field_name} without type"))?;
+                        parsed.push((
+                            field_name.to_owned(),
+                            parse_schema(field_type, namespace, names)?,
+                        ));
+                    }
+                    Schema::Record(parsed)
+                }
+                "enum" => {
+                    let symbols = object
+                        .get("symbols")
+                        .and_then(Value::as_array)
+                        .ok_or_else(|| anyhow!("enum without symbols"))?;
+                    Schema::Enum(
+                        symbols
+                            .iter()
+                            .filter_map(|symbol| symbol.as_str().map(str::to_owned))
+                            .collect(),
+                    )
+                }
+                "array" => Schema::Array(Box::new(parse_schema(
+                    object
+                        .get("items")
+                        .ok_or_else(|| anyhow!("array without items"))?,
+                    namespace,
+                    names,
+                )?)),
+                "map" => Schema::Map(Box::new(parse_schema(
+                    object
+                        .get("values")
+                        .ok_or_else(|| anyhow!("map without values"))?,
+                    namespace,
+                    names,
+                )?)),
+                "fixed" => Schema::Fixed(
+                    object
+                        .get("size")
+                        .and_then(Value::as_u64)
+                        .ok_or_else(|| anyhow!("fixed without size"))? as usize,
+                ),
+                // logical types are decoded as their underlying type
+                other => return parse_schema(&Value::String(other.to_owned()), namespace, names),
+            };
+            if let Some(name) = name {
+                let full = full_name(name, namespace);
+                // short name is used by references in same namespace
+                names.insert(name.to_owned(), schema.clone());
+                names.insert(full.clone(), schema.clone());
+                return Ok(Schema::Named(full));
+            }
+            Ok(schema)
+        }
+        _ => Err(anyhow!("invalid schema {json}")),
+    }
+}
+
+fn primitive(name: &str) -> Option<Schema> {
+    let schema = match name {
+        "null" => Schema::Null,
+        "boolean" => Schema::Boolean,
+        "int" => Schema::Int,
+        "long" => Schema::Long,
+        "float" => Schema::Float,
+        "double" => Schema::Double,
+        "bytes" => Schema::Bytes,
+        "string" => Schema::String,
+        _ => return None,
+    };
+    Some(schema)
+}
+
+fn full_name(name: &str, namespace: Option<&str>) -> String {
+    match namespace {
+        Some(namespace) if !name.contains('.') && !namespace.is_empty() => {
+            format!("{namespace}.{name}")
+        }
+        _ => name.to_owned(),
+    }
+}
+
+/// Reader of Avro binary encoding
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn byte(&mut self) -> Result<u8> {
+        Ok(self.fixed(1)?[0])
+    }
+
+    fn fixed(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.data.len() < len {
+            return Err(anyhow!("unexpected end of value"));
+        }
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(bytes)
+    }
+
+    /// zig-zag encoded variable length integer
+    fn long(&mut self) -> Result<i64> {
+        let mut value: u64 = 0;
+        let mut shift = 0;
+        loop {
+            if shift >= 64 {
+                return Err(anyhow!("invalid variable length integer"));
+            }
+            let byte = self.byte()?;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                break;
+            }
+            shift += 7;
+        }
+        Ok((value >> 1) as i64 ^ -((value & 1) as i64))
+    }
+
+    fn len(&mut self) -> Result<usize> {
+        let len = self.long()?;
+        usize::try_from(len).map_err(|_| anyhow!("invalid length {len}"))
+    }
+
+    /// item count of next block of array or map, none at end
+    fn block(&mut self) -> Result<Option<u64>> {
+        match self.long()? {
+            0 => Ok(None),
+            count if count < 0 => {
+                // block size in bytes follows negative count
+                self.long()?;
+                Ok(Some(count.unsigned_abs()))
+            }
+            count => Ok(Some(count as u64)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use serde_json::json;
+
+    use super::*;
+
+    fn long(value: i64) -> Vec<u8> {
+        let mut zigzag = ((value << 1) ^ (value >> 63)) as u64;
+        let mut bytes = vec![];
+        loop {
+            let byte = (zigzag & 0x7f) as u8;
+            zigzag >>= 7;
+            if zigzag == 0 {
+                bytes.push(byte);
+                return bytes;
+            }
+            bytes.push(byte | 0x80);
+        }
+    }
+
+    fn string(value: &str) -> Vec<u8> {
+        let mut bytes = long(value.len() as i64);
+        bytes.extend_from_slice(value.as_bytes());
+        bytes
+    }
+
+    const USER_SCHEMA: &str = r#"{
+        "type": "record",
+        "name": "User",
+        "namespace": "com.example",
+        "fields": [
+            {"name": "id", "type": "long"},
+            {"name": "email", "type": ["null", "string"]},
+            {"name": "status", "type": {"type": "enum", "name": "Status", "symbols": ["ACTIVE", "DISABLED"]}},
+            {"name": "tags", "type": {"type": "array", "items": "string"}},
+            {"name": "scores", "type": {"type": "map", "values": "double"}},
+            {"name": "manager", "type": ["null", "User"]}
+        ]
+    }"#;
+
+    fn user(id: i64, email: Option<&str>, manager: Option<Vec<u8>>) -> Vec<u8> {
+        let mut bytes = long(id);
+        match email {
+            Some(email) => {
+                bytes.extend(long(1));
+                bytes.extend(string(email));
+            }
+            None => bytes.extend(long(0)),
+        }
+        bytes.extend(long(1));
+        // array in one block
+        bytes.extend(long(2));
+        bytes.extend(string("a"));
+        bytes.extend(string("b"));
+        bytes.extend(long(0));
+        // map in block with byte size
+        bytes.extend(long(-1));
+        bytes.extend(long(10));
+        bytes.extend(string("x"));
+        bytes.extend(1.5f64.to_le_bytes());
+        bytes.extend(long(0));
+        match manager {
+            Some(manager) => {
+                bytes.extend(long(1));
+                bytes.extend(manager);
+            }
+            None => bytes.extend(long(0)),
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_decode_record() {
+        let decoder = AvroDecoder {
+            schemas: AvroSchemas::Single(ParsedSchema::parse(USER_SCHEMA).expect("schema")),
+        };
+        let value = user(-42, Some("a@b.c"), Some(user(7, None, None)));
+        let decoded = decoder.decode(&value).expect("decode");
+        let manager = json!({
+            "id": 7, "email": null, "status": "DISABLED", "tags": ["a", "b"],
+            "scores": {"x": 1.5}, "manager": null,
+        });
+        assert_eq!(
+            decoded,
+            json!({
+                "id": -42, "email": "a@b.c", "status": "DISABLED", "tags": ["a", "b"],
+                "scores": {"x": 1.5}, "manager": manager,
+            })
+        );
+
+        let mut truncated = value.clone();
+        truncated.pop();
+        assert!(decoder.decode(&truncated).is_err());
+        let mut extra = value;
+        extra.push(0);
+        assert!(decoder.decode(&extra).is_err());
+    }
+
+    #[test]
+    fn test_decode_registry_value() {
+        let schema =
+            ParsedSchema::parse(r#"{"type": "string", "logicalType": "uuid"}"#).expect("schema");
+        let decoder = AvroDecoder {
+            schemas: AvroSchemas::Registry(HashMap::from([(5, schema)])),
+        };
+
+        let mut value = vec![REGISTRY_MAGIC_BYTE, 0, 0, 0, 5];
+        value.extend(string("hello"));
+        assert_eq!(decoder.decode(&value).expect("decode"), json!("hello"));
+
+        value[4] = 6;
+        assert!(decoder.decode(&value).is_err());
+        assert!(decoder.decode(&string("hello")).is_err());
+    }
+}
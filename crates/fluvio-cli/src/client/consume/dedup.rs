@@ -0,0 +1,185 @@
+//!
+//! # Client side de-duplication
+//!
+//! Suppresses records whose record key or JSON field repeats within a time window.
+//! Each distinct value is printed at most once per window, counted from the record that was printed.
+//!
+
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, SystemTime};
+
+use serde_json::Value;
+
+use fluvio::consumer::Record;
+use fluvio_protocol::record::NO_TIMESTAMP;
+
+/// `--dedup-key` value selecting record key instead of a field of record value
+const RECORD_KEY: &str = "key";
+
+#[derive(Debug, PartialEq)]
+enum DedupKey {
+    RecordKey,
+    /// path of field in JSON record value
+    Field(Vec<String>),
+}
+
+impl From<&str> for DedupKey {
+    fn from(key: &str) -> Self {
+        if key == RECORD_KEY {
+            Self::RecordKey
+        } else {
+            Self::Field(key.split('.').map(ToOwned::to_owned).collect())
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct Deduplicator {
+    key: DedupKey,
+    window_ms: i64,
+    /// time in ms at which value was last printed
+    last_printed: HashMap<String, i64>,
+    /// printed values in time order, to evict values out of window
+    printed: VecDeque<(i64, String)>,
+    suppressed: u64,
+}
+
+impl Deduplicator {
+    pub(crate) fn new(key: &str, window: Duration) -> Self {
+        Self {
+            key: key.into(),
+            window_ms: window.as_millis().try_into().unwrap_or(i64::MAX),
+            last_printed: HashMap::new(),
+            printed: VecDeque::new(),
+            suppressed: 0,
+        }
+    }
+
+    /// true if record repeats value printed within window, in which case it should be skipped.
+    /// Records without the selected key or field are never duplicates
+    pub(crate) fn is_duplicate(&mut self, record: &Record) -> bool {
+        let Some(value) = self.dedup_value(record) else {
+            return false;
+        };
+        let time = match record.timestamp() {
+            NO_TIMESTAMP => now_ms(),
+            timestamp => timestamp,
+        };
+        self.check(value, time)
+    }
+
+    /// number of records reported as duplicates
+    pub(crate) fn suppressed(&self) -> u64 {
+        self.suppressed
+    }
+
+    fn check(&mut self, value: String, time: i64) -> bool {
+        self.evict(time);
+        if let Some(printed_at) = self.last_printed.get(&value)
+            && time.saturating_sub(*printed_at) < self.window_ms
+        {
+            self.suppressed += 1;
+            return true;
+        }
+        self.last_printed.insert(value.clone(), time);
+        self.printed.push_back((time, value));
+        false
+    }
+
+    fn evict(&mut self, time: i64) {
+        while let Some((printed_at, _)) = self.printed.front()
+            && time.saturating_sub(*printed_at) >= self.window_ms
+        {
+            if let Some((printed_at, value)) = self.printed.pop_front()
+                && self.last_printed.get(&value) == Some(&printed_at)
+            {
+                self.last_printed.remove(&value);
+            }
+        }
+    }
+
+    fn dedup_value(&self, record: &Record) -> Option<String> {
+        match &self.key {
+            DedupKey::RecordKey => record
+                .get_key()
+                .map(|key| key.as_utf8_lossy_string().into_owned()),
+            DedupKey::Field(path) => {
+                let json: Value = serde_json::from_slice(record.value()).ok()?;
+                let field = path
+                    .iter()
+                    .try_fold(&json, |value, name| value.get(name.as_str()))?;
+                match field {
+                    Value::String(text) => Some(text.clone()),
+                    other => Some(other.to_string()),
+                }
+            }
+        }
+    }
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis()
+        .try_into()
+        .unwrap_or(i64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use fluvio_protocol::record::{Batch, RecordData};
+
+    use super::*;
+
+    fn record(value: &str) -> Record {
+        let mut batch = Batch::default();
+        batch.add_records(&mut vec![fluvio_protocol::record::Record::new(
+            RecordData::from(value.as_bytes()),
+        )]);
+        batch.into_consumer_records_iter(0).next().expect("record")
+    }
+
+    #[test]
+    fn test_dedup_key_parse() {
+        assert_eq!(DedupKey::from("key"), DedupKey::RecordKey);
+        assert_eq!(
+            DedupKey::from("user.id"),
+            DedupKey::Field(vec!["user".to_owned(), "id".to_owned()])
+        );
+    }
+
+    #[test]
+    fn test_window() {
+        let mut dedup = Deduplicator::new("key", Duration::from_secs(10));
+
+        assert!(!dedup.check("a".to_owned(), 0));
+        assert!(!dedup.check("b".to_owned(), 1_000));
+        assert!(dedup.check("a".to_owned(), 5_000));
+        assert!(dedup.check("a".to_owned(), 9_999));
+        assert!(!dedup.check("a".to_owned(), 10_000));
+        assert!(!dedup.check("b".to_owned(), 11_000));
+        assert_eq!(dedup.suppressed(), 2);
+        assert_eq!(dedup.last_printed.len(), 2);
+
+        assert!(!dedup.check("c".to_owned(), 30_000));
+        assert_eq!(dedup.last_printed.len(), 1);
+    }
+
+    #[test]
+    fn test_field_value() {
+        let dedup = Deduplicator::new("user.id", Duration::from_secs(10));
+
+        assert_eq!(
+            dedup.dedup_value(&record(r#"{"user":{"id":"alice"}}"#)),
+            Some("alice".to_owned())
+        );
+        assert_eq!(
+            dedup.dedup_value(&record(r#"{"user":{"id":7}}"#)),
+            Some("7".to_owned())
+        );
+        assert_eq!(dedup.dedup_value(&record(r#"{"user":{}}"#)), None);
+        assert_eq!(dedup.dedup_value(&record("not json")), None);
+    }
+}
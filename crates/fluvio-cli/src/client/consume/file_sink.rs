@@ -0,0 +1,323 @@
+//!
+//! # File sink
+//!
+//! Writes consumed records to files, one file per partition.
+//! Files are rotated by size or age, rotated files are named by template with
+//! partition and offsets of their records, and optionally compressed with gzip.
+//! Existing files are never replaced, sequence number is added to name instead.
+//!
+
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use tracing::debug;
+
+use fluvio_types::PartitionId;
+
+pub(crate) const DEFAULT_FILE_TEMPLATE: &str =
+    "{topic}-{partition}-{start_offset}-{end_offset}.log";
+
+/// Rotation and naming of files
+#[derive(Debug, Clone)]
+pub(crate) struct FileSinkConfig {
+    pub dir: PathBuf,
+    /// name of rotated files, with {topic}, {partition}, {start_offset} and {end_offset}
+    pub template: String,
+    pub rotate_size: Option<u64>,
+    pub rotate_interval: Option<Duration>,
+    pub compress: bool,
+}
+
+/// File being written for a partition
+#[derive(Debug)]
+struct ActiveFile {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    size: u64,
+    start_offset: i64,
+    end_offset: i64,
+    opened: Instant,
+}
+
+impl ActiveFile {
+    fn open(dir: &Path, topic: &str, partition: PartitionId, start_offset: i64) -> Result<Self> {
+        let path = dir.join(format!(".{topic}-{partition}-{start_offset}.active"));
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("unable to open file {}", path.display()))?;
+        let size = file.metadata()?.len();
+        debug!(path = %path.display(), "opened file");
+        Ok(Self {
+            path,
+            writer: BufWriter::new(file),
+            size,
+            start_offset,
+            end_offset: start_offset,
+            opened: Instant::now(),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct FileSink {
+    topic: String,
+    config: FileSinkConfig,
+    files: HashMap<PartitionId, ActiveFile>,
+}
+
+impl FileSink {
+    pub(crate) fn new(topic: &str, config: FileSinkConfig) -> Result<Self> {
+        fs::create_dir_all(&config.dir)
+            .with_context(|| format!("unable to create directory {}", config.dir.display()))?;
+        Ok(Self {
+            topic: topic.to_owned(),
+            config,
+            files: HashMap::new(),
+        })
+    }
+
+    /// append line of record to file of its partition, rotating file if it's full
+    pub(crate) fn write(&mut self, partition: PartitionId, offset: i64, line: &str) -> Result<()> {
+        let file = match self.files.entry(partition) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(ActiveFile::open(
+                &self.config.dir,
+                &self.topic,
+                partition,
+                offset,
+            )?),
+        };
+        file.writer.write_all(line.as_bytes())?;
+        file.writer.write_all(b"\n")?;
+        file.size += line.len() as u64 + 1;
+        file.end_offset = offset;
+
+        let full = self
+            .config
+            .rotate_size
+            .is_some_and(|max_size| file.size >= max_size);
+        if full {
+            self.rotate(partition)?;
+        }
+        Ok(())
+    }
+
+    /// rotate files open longer than rotation interval
+    pub(crate) fn rotate_expired(&mut self) -> Result<()> {
+        let Some(interval) = self.config.rotate_interval else {
+            return Ok(());
+        };
+        let expired: Vec<PartitionId> = self
+            .files
+            .iter()
+            .filter(|(_, file)| file.opened.elapsed() >= interval)
+            .map(|(partition, _)| *partition)
+            .collect();
+        for partition in expired {
+            self.rotate(partition)?;
+        }
+        Ok(())
+    }
+
+    /// rotate all files, so no records are left in active files
+    pub(crate) fn close(mut self) -> Result<()> {
+        let partitions: Vec<PartitionId> = self.files.keys().copied().collect();
+        for partition in partitions {
+            self.rotate(partition)?;
+        }
+        Ok(())
+    }
+
+    /// close active file of partition and move it to its final name
+    fn rotate(&mut self, partition: PartitionId) -> Result<()> {
+        let Some(mut file) = self.files.remove(&partition) else {
+            return Ok(());
+        };
+        file.writer.flush()?;
+        let name = file_name(
+            &self.config.template,
+            &self.topic,
+            partition,
+            file.start_offset,
+            file.end_offset,
+        );
+        let path = unused_path(&self.config.dir.join(name), self.config.compress);
+        fs::rename(&file.path, &path)
+            .with_context(|| format!("unable to rotate file {}", file.path.display()))?;
+        let path = if self.config.compress {
+            compress(&path)?
+        } else {
+            path
+        };
+        eprintln!("{}", path.display());
+        Ok(())
+    }
+}
+
+/// name of rotated file from template
+fn file_name(
+    template: &str,
+    topic: &str,
+    partition: PartitionId,
+    start_offset: i64,
+    end_offset: i64,
+) -> String {
+    template
+        .replace("{topic}", topic)
+        .replace("{partition}", &partition.to_string())
+        .replace("{start_offset}", &start_offset.to_string())
+        .replace("{end_offset}", &end_offset.to_string())
+}
+
+/// path of rotated file which doesn't exist yet, as template may not have offsets.
+/// Sequence number is added before extension: `logs.1.txt`
+fn unused_path(path: &Path, compress: bool) -> PathBuf {
+    let exists = |path: &Path| path.exists() || (compress && gz_path(path).exists());
+    if !exists(path) {
+        return path.to_owned();
+    }
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let extension = path
+        .extension()
+        .map(|ext| format!(".{}", ext.to_string_lossy()))
+        .unwrap_or_default();
+    (1..)
+        .map(|seq| path.with_file_name(format!("{stem}.{seq}{extension}")))
+        .find(|candidate| !exists(candidate))
+        .expect("sequence is unbounded")
+}
+
+fn gz_path(path: &Path) -> PathBuf {
+    let mut gz_path = path.as_os_str().to_owned();
+    gz_path.push(".gz");
+    PathBuf::from(gz_path)
+}
+
+/// replace file with gzip compressed file
+fn compress(path: &Path) -> Result<PathBuf> {
+    let gz_path = gz_path(path);
+
+    let mut source = File::open(path)?;
+    let mut encoder = GzEncoder::new(File::create(&gz_path)?, Compression::default());
+    io::copy(&mut source, &mut encoder)?;
+    encoder.finish()?.sync_all()?;
+    fs::remove_file(path)?;
+    Ok(gz_path)
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::io::Read;
+
+    use flate2::read::GzDecoder;
+
+    use super::*;
+
+    #[test]
+    fn test_rotate_by_size() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let dir = temp.path().to_owned();
+        let mut sink = FileSink::new(
+            "logs",
+            FileSinkConfig {
+                dir: dir.clone(),
+                template: DEFAULT_FILE_TEMPLATE.to_owned(),
+                rotate_size: Some(10),
+                rotate_interval: None,
+                compress: false,
+            },
+        )
+        .expect("sink");
+
+        sink.write(0, 0, "first").expect("write");
+        sink.write(0, 1, "second").expect("write");
+        sink.write(1, 5, "other").expect("write");
+        sink.close().expect("close");
+
+        assert_eq!(
+            fs::read_to_string(dir.join("logs-0-0-1.log")).expect("rotated"),
+            "first\nsecond\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("logs-1-5-5.log")).expect("closed"),
+            "other\n"
+        );
+        assert_eq!(fs::read_dir(&dir).expect("dir").count(), 2);
+    }
+
+    #[test]
+    fn test_compress_rotated() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let dir = temp.path().to_owned();
+        let mut sink = FileSink::new(
+            "logs",
+            FileSinkConfig {
+                dir: dir.clone(),
+                template: "{partition}.txt".to_owned(),
+                rotate_size: None,
+                rotate_interval: Some(Duration::ZERO),
+                compress: true,
+            },
+        )
+        .expect("sink");
+
+        sink.write(2, 7, "line").expect("write");
+        sink.rotate_expired().expect("rotate");
+
+        let mut content = String::new();
+        GzDecoder::new(File::open(dir.join("2.txt.gz")).expect("gzip"))
+            .read_to_string(&mut content)
+            .expect("decode");
+        assert_eq!(content, "line\n");
+        assert!(!dir.join("2.txt").exists());
+    }
+
+    #[test]
+    fn test_rotate_keeps_existing_files() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let dir = temp.path().to_owned();
+        fs::write(dir.join("logs.txt"), "previous run\n").expect("existing");
+        let mut sink = FileSink::new(
+            "logs",
+            FileSinkConfig {
+                dir: dir.clone(),
+                // no offsets in name
+                template: "{topic}.txt".to_owned(),
+                rotate_size: Some(1),
+                rotate_interval: None,
+                compress: false,
+            },
+        )
+        .expect("sink");
+
+        sink.write(0, 0, "first").expect("write");
+        sink.write(0, 1, "second").expect("write");
+        sink.close().expect("close");
+
+        assert_eq!(
+            fs::read_to_string(dir.join("logs.txt")).expect("existing"),
+            "previous run\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("logs.1.txt")).expect("first"),
+            "first\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("logs.2.txt")).expect("second"),
+            "second\n"
+        );
+    }
+}
@@ -7,18 +7,21 @@
 //! mod record_format;
 mod table_format;
 mod record_format;
+mod dedup;
+mod workers;
+mod file_sink;
+mod avro;
+mod template;
 
 use table_format::TableModel;
 
-pub use cmd::ConsumeOpt;
+pub use cmd::{ConsumeOpt, ConsumeOutputType};
 
 mod cmd {
 
-    use std::sync::atomic::{AtomicBool, Ordering};
-    use std::time::{UNIX_EPOCH, Duration};
-    use std::{io::Error as IoError, path::PathBuf};
-    use std::io::{self, ErrorKind, IsTerminal, Stdout};
-    use std::collections::BTreeMap;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+    use std::path::PathBuf;
+    use std::io::{self, IsTerminal, Stdout, Write};
     use std::fmt::Debug;
     use std::sync::Arc;
 
@@ -36,18 +39,21 @@ mod cmd {
         execute,
         terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
     };
-    use handlebars::{self, Handlebars};
+    use fluvio_future::timer::sleep;
     use anyhow::Result;
 
     use fluvio_types::PartitionId;
     use fluvio_spu_schema::server::smartmodule::SmartModuleContextData;
-    use fluvio_protocol::record::NO_TIMESTAMP;
     use fluvio::metadata::tableformat::TableFormatSpec;
+    use fluvio::metadata::topic::TopicSpec;
     use fluvio::{Fluvio, Offset, FluvioError};
-    use fluvio::consumer::{ConsumerConfigExt, ConsumerStream, OffsetManagementStrategy};
+    use fluvio::consumer::{
+        BatchBounds, ConsumerConfigExt, ConsumerStream, OffsetManagementStrategy, RecordSampling,
+    };
 
     use fluvio::consumer::Record;
     use fluvio_spu_schema::Isolation;
+    use fluvio_spu_schema::server::stream_fetch::SAMPLING_RATIO_SCALE;
 
     use crate::monitoring::init_monitoring;
     use crate::render::ProgressRenderer;
@@ -57,6 +63,7 @@ mod cmd {
     use crate::common::Terminal;
     use crate::client::smartmodule_invocation::{
         create_smartmodule, create_smartmodule_from_path, create_smartmodule_list,
+        smartmodule_params,
     };
 
     use super::record_format::{
@@ -64,18 +71,24 @@ mod cmd {
         format_json, format_basic_table_record, format_fancy_table_record,
     };
     use super::super::ClientCmd;
+    use super::super::session::{Interrupt, SessionSummary};
     use super::table_format::{TableEventResponse, TableModel};
+    use super::dedup::Deduplicator;
+    use super::workers::{FormattedRecord, start_workers};
+    use super::file_sink::{DEFAULT_FILE_TEMPLATE, FileSink, FileSinkConfig};
+    use super::avro::AvroDecoder;
+    use super::template::RecordTemplate;
     use fluvio_smartengine::transformation::TransformationConfig;
 
-    const USER_TEMPLATE: &str = "user_template";
     const DEFAULT_OFFSET_FLUSH_INTERVAL: Duration = Duration::from_secs(2);
+    const FILE_ROTATION_CHECK_INTERVAL: Duration = Duration::from_secs(1);
 
     /// Read messages from a topic/partition
     ///
     /// By default, consume operates in "streaming" mode, where the command will remain
     /// active and wait for new messages, printing them as they arrive. You can use the
     /// '-d' flag to exit after consuming all available messages.
-    #[derive(Debug, Parser)]
+    #[derive(Debug, Clone, Parser)]
     pub struct ConsumeOpt {
         /// Topic name
         #[arg(value_name = "topic")]
@@ -108,22 +121,27 @@ mod cmd {
         /// Provide a template string to print records with a custom format.
         /// See --help for details.
         ///
-        /// Template strings may include the variables {{key}}, {{value}}, {{offset}}, {{partition}} and {{time}}
-        /// which will have each record's contents substituted in their place.
-        /// Note that timestamp is displayed using RFC3339, is always UTC and ignores system timezone.
+        /// Template strings may include the variables {key}, {value}, {offset}, {partition}, {time}
+        /// and {timestamp} which will have each record's contents substituted in their place.
+        /// Fields of JSON keys and values are selected with dotted paths, such as {value.user.name}
+        /// or {value.items.0}, missing fields are printed empty.
+        /// Note that time is displayed using RFC3339, is always UTC and ignores system timezone,
+        /// timestamp is milliseconds since epoch.
         ///
         /// For example, the following template string:
         ///
-        /// Offset {{offset}} has key {{key}} and value {{value}}
+        /// {key} | {offset} | {value.fruit}
         ///
         /// Would produce a printout where records might look like this:
         ///
-        /// Offset 0 has key A and value Apple
+        /// A | 0 | Apple
+        ///
+        /// Handlebars templates with variables as {{key}} are supported too.
         #[arg(short = 'F', long, conflicts_with_all = &["output"])]
         pub format: Option<String>,
 
         /// Consume records using the formatting rules defined by TableFormat name
-        #[arg(long)]
+        #[arg(long, env = "FLUVIO_TABLE_FORMAT")]
         pub table_format: Option<String>,
 
         /// Consume records from the beginning of the log
@@ -153,7 +171,7 @@ mod cmd {
         /// Isolation level that consumer must respect.
         /// Supported values: read_committed (ReadCommitted) - consume only committed records,
         /// read_uncommitted (ReadUncommitted) - consume all records accepted by leader.
-        #[arg(long, value_parser=parse_isolation)]
+        #[arg(long, value_parser=parse_isolation, env = "FLUVIO_ISOLATION")]
         pub isolation: Option<Isolation>,
 
         /// Suppress items items that have an unknown output type
@@ -206,6 +224,12 @@ mod cmd {
         )]
         pub params: Option<Vec<(String, String)>>,
 
+        /// (Optional) Path to a YAML file with extra input parameters passed to the smartmodule.
+        /// Values given with `--params` take precedence
+        /// Eg. fluvio consume topic-name --smartmodule my_filter --params-file params.yaml
+        #[arg(long, requires = "smartmodule_group", value_name = "PATH")]
+        pub params_file: Option<PathBuf>,
+
         /// (Optional) Path to a file with transformation specification.
         #[arg(
             short,
@@ -220,6 +244,10 @@ mod cmd {
         #[arg(long, conflicts_with_all = &["smartmodule_group", "transforms"], alias = "transform")]
         pub transforms_line: Vec<String>,
 
+        /// Don't apply default transforms declared by the topic
+        #[arg(long)]
+        pub no_default_transforms: bool,
+
         /// Truncate the output to one line
         #[arg(long, conflicts_with_all = &["output", "format"])]
         pub truncate: bool,
@@ -227,6 +255,133 @@ mod cmd {
         /// Consumer id
         #[arg(short, long)]
         pub consumer: Option<String>,
+
+        /// Suppress records whose value of this key repeats within `--dedup-window`.
+        /// Use `key` for record key, or dotted path of field in JSON record value, ex: user.id
+        #[arg(long, value_name = "key|field")]
+        pub dedup_key: Option<String>,
+
+        /// Window in which repeated records are suppressed, ex: 30s, 5m
+        #[arg(
+            long,
+            value_name = "duration",
+            default_value = "1m",
+            value_parser = humantime::parse_duration,
+            requires = "dedup_key"
+        )]
+        pub dedup_window: Duration,
+
+        /// Receive only a sample of records, ex: 1%, 0.5%.
+        /// Records are selected on the SPU by offset, so repeated reads return the same records
+        #[arg(long, value_name = "percent", value_parser = parse_sample, conflicts_with = "every")]
+        pub sample: Option<u16>,
+
+        /// Receive only every n-th record, selected on the SPU by offset
+        #[arg(long, value_name = "n", value_parser = clap::value_parser!(u32).range(1..))]
+        pub every: Option<u32>,
+
+        /// Consume only records produced since this time,
+        /// as RFC 3339 timestamp ex: 2024-01-01T00:00:00Z, or duration ago ex: 1h.
+        /// Starts from the beginning of log if no start offset is given
+        #[arg(long, value_name = "time", value_parser = parse_time)]
+        pub since: Option<i64>,
+
+        /// Consume only records produced until this time,
+        /// as RFC 3339 timestamp ex: 2024-01-01T00:00:00Z, or duration ago ex: 10m.
+        /// Stream ends at first batch produced after this time
+        #[arg(long, value_name = "time", value_parser = parse_time)]
+        pub until: Option<i64>,
+
+        /// Read records from disk instead of the SPU read cache of recent batches,
+        /// for large scans of older records
+        #[arg(long)]
+        pub bypass_cache: bool,
+
+        /// Produce records SmartModules fail on to this topic with error headers,
+        /// instead of stopping
+        #[arg(long, value_name = "topic")]
+        pub dead_letter_topic: Option<String>,
+
+        /// Consume and format partitions in parallel with this many workers.
+        /// Records of a partition are printed in order, partitions are interleaved
+        #[arg(
+            long,
+            value_name = "integer",
+            conflicts_with = "consumer",
+            value_parser = clap::value_parser!(u32).range(1..)
+        )]
+        pub workers: Option<u32>,
+
+        /// Directory of files written with `--output file`
+        #[arg(long, value_name = "path", default_value = ".")]
+        pub file_dir: PathBuf,
+
+        /// Name of files written with `--output file`.
+        /// Template may include {topic}, {partition}, {start_offset} and {end_offset}
+        #[arg(long, value_name = "template", default_value = DEFAULT_FILE_TEMPLATE)]
+        pub file_name: String,
+
+        /// Rotate file of partition when it reaches this size, ex: 100MB
+        #[arg(long, value_name = "bytes")]
+        pub rotate_size: Option<bytesize::ByteSize>,
+
+        /// Rotate file of partition when it's open for this long, ex: 1h
+        #[arg(long, value_name = "duration", value_parser = humantime::parse_duration)]
+        pub rotate_interval: Option<Duration>,
+
+        /// Compress rotated files with gzip
+        #[arg(long)]
+        pub compress: bool,
+
+        /// Avro schema file (.avsc) of record values, for `--output avro`
+        #[arg(long, value_name = "path", conflicts_with = "schema_registry")]
+        pub schema_path: Option<PathBuf>,
+
+        /// URL of schema registry with schemas of record values, for `--output avro`.
+        /// Values are framed with schema id
+        #[arg(long, value_name = "url")]
+        pub schema_registry: Option<String>,
+
+        /// Subject of schemas in schema registry, default is "<topic>-value"
+        #[arg(long, value_name = "subject", requires = "schema_registry")]
+        pub schema_subject: Option<String>,
+
+        /// decoder of `--output avro`, loaded before consuming
+        #[arg(skip)]
+        avro: Option<Arc<AvroDecoder>>,
+    }
+
+    /// sample percentage in basis points of records
+    fn parse_sample(s: &str) -> Result<u16, String> {
+        let percent: f64 = s
+            .trim()
+            .trim_end_matches('%')
+            .parse()
+            .map_err(|_| format!("invalid sample: {s}, expected percentage such as 1%"))?;
+        let ratio = (percent * 100.0).round();
+        if !(1.0..=f64::from(SAMPLING_RATIO_SCALE)).contains(&ratio) {
+            return Err(format!("sample must be between 0.01% and 100%, got {s}"));
+        }
+        Ok(ratio as u16)
+    }
+
+    /// time in milliseconds since epoch, from RFC 3339 timestamp or duration before now
+    fn parse_time(s: &str) -> Result<i64, String> {
+        let time = match humantime::parse_rfc3339_weak(s) {
+            Ok(time) => time,
+            Err(_) => {
+                let ago = humantime::parse_duration(s).map_err(|_| {
+                    format!("invalid time: {s}, expected timestamp such as 2024-01-01T00:00:00Z or duration such as 1h")
+                })?;
+                SystemTime::now()
+                    .checked_sub(ago)
+                    .ok_or_else(|| format!("time {s} ago is before epoch"))?
+            }
+        };
+        let since_epoch = time
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| format!("time {s} is before epoch"))?;
+        Ok(since_epoch.as_millis() as i64)
     }
 
     #[async_trait]
@@ -237,12 +392,16 @@ mod cmd {
             fields(topic = %self.topic, partition = ?self.partition),
         )]
         async fn process_client<O: Terminal + Debug + Send + Sync>(
-            self,
+            mut self,
             _out: Arc<O>,
             fluvio: &Fluvio,
         ) -> Result<()> {
             init_monitoring(fluvio.metrics());
 
+            if self.output == Some(ConsumeOutputType::avro) {
+                self.avro = Some(Arc::new(self.avro_decoder().await?));
+            }
+
             //println!("client id",fluvio);
 
             let maybe_tableformat = if let Some(ref tableformat_name) = self.table_format {
@@ -290,6 +449,23 @@ mod cmd {
             }
         }
 
+        async fn avro_decoder(&self) -> Result<AvroDecoder> {
+            if let Some(path) = &self.schema_path {
+                return AvroDecoder::from_file(path);
+            }
+            if let Some(url) = &self.schema_registry {
+                let subject = self
+                    .schema_subject
+                    .clone()
+                    .unwrap_or_else(|| format!("{}-value", self.topic));
+                return AvroDecoder::from_registry(url, &subject).await;
+            }
+            Err(CliError::InvalidArg(
+                "avro output requires --schema-path or --schema-registry".to_owned(),
+            )
+            .into())
+        }
+
         fn smart_module_ctx(&self) -> SmartModuleContextData {
             if let Some(agg_initial) = &self.aggregate_initial {
                 SmartModuleContextData::Aggregate {
@@ -306,7 +482,7 @@ mod cmd {
             tableformat: Option<TableFormatSpec>,
         ) -> Result<()> {
             trace!(config = ?self, "Starting consumer:");
-            let stop_signal = self.init_ctrlc()?;
+            let interrupt = Interrupt::init()?;
             let offset = self.calculate_offset()?;
 
             let mut builder = ConsumerConfigExt::builder();
@@ -329,10 +505,8 @@ mod cmd {
                 builder.max_bytes(max_bytes);
             }
 
-            let initial_param = match &self.params {
-                None => BTreeMap::default(),
-                Some(params) => params.clone().into_iter().collect(),
-            };
+            let initial_param =
+                smartmodule_params(self.params_file.as_deref(), self.params.as_deref())?;
 
             let smart_module = if let Some(smart_module_name) = &self.smartmodule {
                 vec![create_smartmodule(
@@ -363,11 +537,38 @@ mod cmd {
             };
 
             builder.smartmodule(smart_module);
+            builder.disable_default_transforms(self.no_default_transforms);
 
             if self.disable_continuous {
                 builder.disable_continuous(true);
             }
 
+            if let Some(ratio) = self.sample {
+                builder.sampling(RecordSampling::Ratio(ratio));
+            } else if let Some(every) = self.every {
+                builder.sampling(RecordSampling::Every(every));
+            }
+
+            if let Some(since) = self.since
+                && let Some(until) = self.until
+                && until < since
+            {
+                return Err(CliError::InvalidArg(
+                    "`--until` must not be before `--since`".to_owned(),
+                )
+                .into());
+            }
+            builder.bounds(BatchBounds {
+                min_timestamp: self.since,
+                max_timestamp: self.until,
+                max_offset: self.end.map(i64::from),
+            });
+            builder.bypass_cache(self.bypass_cache);
+
+            if let Some(dead_letter_topic) = &self.dead_letter_topic {
+                builder.dead_letter_topic(dead_letter_topic.clone());
+            }
+
             if let Some(end_offset) = self.end
                 && let Some(start_offset) = self.start
                 && end_offset < start_offset
@@ -388,27 +589,250 @@ mod cmd {
             debug!("consume config: {:#?}", consume_config);
 
             self.print_status();
-            let mut stream = fluvio.consumer_with_config(consume_config).await?;
-            self.consume_records_stream(&mut stream, stop_signal, tableformat)
-                .await?;
+            let mut summary = SessionSummary::default();
+            match self.workers.filter(|workers| *workers > 1) {
+                _ if self.output == Some(ConsumeOutputType::file) => {
+                    let mut stream = fluvio.consumer_with_config(consume_config).await?;
+                    self.consume_records_to_files(&mut stream, &interrupt, &mut summary)
+                        .await?;
+
+                    if self.consumer.is_some() {
+                        stream.offset_commit().await?;
+                        stream.offset_flush().await?;
+                    }
+                }
+                Some(workers) => {
+                    self.consume_records_with_workers(
+                        fluvio,
+                        &consume_config,
+                        workers as usize,
+                        &interrupt,
+                        &mut summary,
+                    )
+                    .await?;
+                    io::stdout().flush()?;
+                }
+                None => {
+                    let mut stream = fluvio.consumer_with_config(consume_config).await?;
+                    self.consume_records_stream(&mut stream, &interrupt, &mut summary, tableformat)
+                        .await?;
+                    io::stdout().flush()?;
+
+                    if self.consumer.is_some() {
+                        stream.offset_commit().await?;
+                        stream.offset_flush().await?;
+                    }
+                }
+            }
 
-            if !self.disable_continuous {
+            if interrupt.is_interrupted() {
+                eprintln!("Interrupted, consumed {summary}");
+            } else if !self.disable_continuous {
                 eprintln!("Consumer stream has closed");
             }
 
-            if self.consumer.is_some() {
-                stream.offset_commit().await?;
-                stream.offset_flush().await?;
+            Ok(())
+        }
+
+        /// Consume records into files of partitions, see `--output file`
+        async fn consume_records_to_files<S>(
+            &self,
+            stream: &mut S,
+            interrupt: &Interrupt,
+            summary: &mut SessionSummary,
+        ) -> Result<()>
+        where
+            S: ConsumerStream + Unpin + Send,
+        {
+            if self.workers.is_some_and(|workers| workers > 1) {
+                return Err(CliError::InvalidArg(
+                    "file output can't be used with workers".to_owned(),
+                )
+                .into());
             }
+            let mut sink = FileSink::new(
+                &self.topic,
+                FileSinkConfig {
+                    dir: self.file_dir.clone(),
+                    template: self.file_name.clone(),
+                    rotate_size: self.rotate_size.map(|size| size.as_u64()),
+                    rotate_interval: self.rotate_interval,
+                    compress: self.compress,
+                },
+            )?;
+            let mut dedup = self
+                .dedup_key
+                .as_deref()
+                .map(|key| Deduplicator::new(key, self.dedup_window));
+
+            loop {
+                select! {
+                    stream_next = stream.next().fuse() => match stream_next {
+                        Some(result) => {
+                            let result: std::result::Result<Record, _> = result;
+                            let record = match result {
+                                Ok(record) => record,
+                                Err(ErrorCode::MaxRetryReached) => {
+                                    eprintln!("Max limit of retry connection reached");
+                                    break;
+                                }
+                                Err(other) => {
+                                    sink.close()?;
+                                    return Err(other.into());
+                                }
+                            };
+                            summary.record(
+                                record.key().map_or(0, |key| key.len()) + record.value().len(),
+                                Some((record.partition(), record.offset())),
+                            );
+
+                            if !dedup.as_mut().is_some_and(|dedup| dedup.is_duplicate(&record))
+                                && let Some(line) =
+                                    self.format_record(None, &record, &mut false, &mut None)
+                            {
+                                sink.write(record.partition(), record.offset(), &line)?;
+                            }
+                            sink.rotate_expired()?;
 
+                            if let Some(end_offset) = self.end
+                                && record.offset >= end_offset as i64 {
+                                    eprintln!("End-offset has been reached; exiting");
+                                    break;
+                                }
+                        },
+                        None => break,
+                    },
+                    // files of idle partitions are rotated too
+                    _ = sleep(FILE_ROTATION_CHECK_INTERVAL).fuse() => {
+                        sink.rotate_expired()?;
+                    },
+                    _ = interrupt.wait().fuse() => {
+                        debug!("Received stop signal, exiting consume loop");
+                        break;
+                    },
+                }
+            }
+
+            sink.close()?;
+            if let Some(dedup) = dedup
+                && dedup.suppressed() > 0
+            {
+                eprintln!("{} duplicate records suppressed", dedup.suppressed());
+            }
             Ok(())
         }
 
+        /// Consume partitions in parallel, records are formatted by workers and printed here
+        async fn consume_records_with_workers(
+            &self,
+            fluvio: &Fluvio,
+            consume_config: &ConsumerConfigExt,
+            workers: usize,
+            interrupt: &Interrupt,
+            summary: &mut SessionSummary,
+        ) -> Result<()> {
+            if matches!(
+                self.output,
+                Some(ConsumeOutputType::table | ConsumeOutputType::full_table)
+            ) {
+                return Err(CliError::InvalidArg(
+                    "table output can't be used with workers".to_owned(),
+                )
+                .into());
+            }
+            let partitions = self.partitions_to_consume(fluvio).await?;
+
+            let opt = Arc::new(self.clone());
+            let templates = self.templates()?;
+            let format = move |record: &Record| {
+                opt.format_record(templates.as_ref(), record, &mut false, &mut None)
+            };
+            let records =
+                start_workers(fluvio, consume_config, &partitions, workers, format).await?;
+
+            let pb = ProgressRenderer::default();
+            let mut dedup = self
+                .dedup_key
+                .as_deref()
+                .map(|key| Deduplicator::new(key, self.dedup_window));
+            loop {
+                select! {
+                    next = records.recv().fuse() => match next {
+                        Ok(result) => {
+                            let result: std::result::Result<FormattedRecord, _> = result;
+                            let (record, formatted) = match result {
+                                Ok(output) => output,
+                                Err(ErrorCode::MaxRetryReached) => {
+                                    eprintln!("Max limit of retry connection reached");
+                                    break;
+                                }
+                                Err(other) => return Err(other.into()),
+                            };
+                            summary.record(
+                                record.key().map_or(0, |key| key.len()) + record.value().len(),
+                                Some((record.partition(), record.offset())),
+                            );
+
+                            if !dedup.as_mut().is_some_and(|dedup| dedup.is_duplicate(&record)) {
+                                match formatted {
+                                    Some(line) => pb.println(&line),
+                                    None => debug!("Skipping record that cannot be formatted"),
+                                }
+                            }
+
+                            if let Some(end_offset) = self.end
+                                && record.offset >= end_offset as i64 {
+                                    eprintln!("End-offset has been reached; exiting");
+                                    break;
+                                }
+                        },
+                        // all workers are finished
+                        Err(_) => break,
+                    },
+                    _ = interrupt.wait().fuse() => {
+                        debug!("Received stop signal, exiting consume loop");
+                        break;
+                    },
+                }
+            }
+
+            if let Some(dedup) = dedup
+                && dedup.suppressed() > 0
+            {
+                eprintln!("{} duplicate records suppressed", dedup.suppressed());
+            }
+            Ok(())
+        }
+
+        /// selected partitions, or all partitions of topic
+        async fn partitions_to_consume(&self, fluvio: &Fluvio) -> Result<Vec<PartitionId>> {
+            if !self.partition.is_empty() {
+                return Ok(self.partition.clone());
+            }
+            let admin = fluvio.admin().await;
+            let topic = admin
+                .list::<TopicSpec, _>(vec![self.topic.clone()])
+                .await?
+                .into_iter()
+                .find(|topic| topic.name == self.topic)
+                .ok_or_else(|| CliError::Other(format!("topic \"{}\" not found", self.topic)))?;
+            Ok((0..topic.spec.partitions()).collect())
+        }
+
+        /// user template of `--format`
+        fn templates(&self) -> Result<Option<RecordTemplate>> {
+            self.format
+                .as_deref()
+                .map(RecordTemplate::parse)
+                .transpose()
+        }
+
         /// Consume records as a stream, waiting for new records to arrive
         async fn consume_records_stream<S>(
             &self,
             stream: &mut S,
-            stop_signal: async_channel::Receiver<()>,
+            interrupt: &Interrupt,
+            summary: &mut SessionSummary,
             tableformat: Option<TableFormatSpec>,
         ) -> Result<()>
         where
@@ -416,30 +840,21 @@ mod cmd {
         {
             let maybe_potential_end_offset: Option<u32> = self.end;
 
-            let templates = match self.format.as_deref() {
-                None => None,
-                Some(format) => {
-                    let mut reg = Handlebars::new();
-                    // opt-out of HTML escaping of printable record data
-                    reg.register_escape_fn(handlebars::no_escape);
-                    reg.register_template_string(USER_TEMPLATE, format)?;
-                    Some(reg)
-                }
-            };
+            let templates = self.templates()?;
 
             // TableModel and Terminal for full_table rendering
             let mut maybe_table_model = None;
             let mut maybe_terminal_stdout =
                 if let Some(ConsumeOutputType::full_table) = &self.output {
                     if io::stdout().is_tty() {
-                        enable_raw_mode()?;
-                        let mut stdout = io::stdout();
-                        execute!(stdout, EnterAlternateScreen)?;
-
                         let mut model = TableModel::new();
 
                         // Customize display options w/ tableformat spec
-                        model.with_tableformat(tableformat);
+                        model.with_tableformat(tableformat)?;
+
+                        enable_raw_mode()?;
+                        let mut stdout = io::stdout();
+                        execute!(stdout, EnterAlternateScreen)?;
 
                         maybe_table_model = Some(model);
 
@@ -455,6 +870,11 @@ mod cmd {
             // This is used by table output, to manage printing the table titles only one time
             let mut header_print = true;
 
+            let mut dedup = self
+                .dedup_key
+                .as_deref()
+                .map(|key| Deduplicator::new(key, self.dedup_window));
+
             // Below is code duplication that was needed to help CI pass
             // Without TTY, we panic when attempting to read from EventStream
             // In CI, we do not have a TTY, so we need this check to avoid reading EventStream
@@ -494,16 +914,22 @@ mod cmd {
                                     }
                                     Err(other) => return Err(other.into()),
                                 };
-
-                                self.print_record(
-                                    templates.as_ref(),
-                                    &record,
-                                    &mut header_print,
-                                    &mut maybe_terminal_stdout,
-                                    &mut maybe_table_model,
-                                    &pb,
+                                summary.record(
+                                    record.key().map_or(0, |key| key.len()) + record.value().len(),
+                                    Some((record.partition(), record.offset())),
                                 );
 
+                                if !dedup.as_mut().is_some_and(|dedup| dedup.is_duplicate(&record)) {
+                                    self.print_record(
+                                        templates.as_ref(),
+                                        &record,
+                                        &mut header_print,
+                                        &mut maybe_terminal_stdout,
+                                        &mut maybe_table_model,
+                                        &pb,
+                                    );
+                                }
+
                                 if let Some(potential_offset) = maybe_potential_end_offset
                                     && record.offset >= potential_offset as i64 {
                                         eprintln!("End-offset has been reached; exiting");
@@ -512,7 +938,7 @@ mod cmd {
                             },
                             None => break,
                         },
-                        _ = stop_signal.recv().fuse() => {
+                        _ = interrupt.wait().fuse() => {
                             debug!("Received stop signal, exiting consume loop");
                             break;
                         },
@@ -553,16 +979,22 @@ mod cmd {
                                     */
                                     Err(other) => return Err(other.into()),
                                 };
-
-                                self.print_record(
-                                    templates.as_ref(),
-                                    &record,
-                                    &mut header_print,
-                                    &mut None,
-                                    &mut None,
-                                    &pb,
+                                summary.record(
+                                    record.key().map_or(0, |key| key.len()) + record.value().len(),
+                                    Some((record.partition(), record.offset())),
                                 );
 
+                                if !dedup.as_mut().is_some_and(|dedup| dedup.is_duplicate(&record)) {
+                                    self.print_record(
+                                        templates.as_ref(),
+                                        &record,
+                                        &mut header_print,
+                                        &mut None,
+                                        &mut None,
+                                        &pb,
+                                    );
+                                }
+
                                 if let Some(potential_offset) = maybe_potential_end_offset
                                     && record.offset >= potential_offset as i64 {
                                         eprintln!("End-offset has been reached; exiting");
@@ -571,7 +1003,7 @@ mod cmd {
                             },
                             None => break,
                         },
-                        _ = stop_signal.recv().fuse() => {
+                        _ = interrupt.wait().fuse() => {
                             debug!("Received stop signal, exiting consume loop");
                             break;
                         },
@@ -587,6 +1019,12 @@ mod cmd {
                 terminal_stdout.show_cursor()?;
             }
 
+            if let Some(dedup) = dedup
+                && dedup.suppressed() > 0
+            {
+                eprintln!("{} duplicate records suppressed", dedup.suppressed());
+            }
+
             debug!("fetch loop exited");
             Ok(())
         }
@@ -601,13 +1039,37 @@ mod cmd {
         /// Process fetch topic response based on output type
         pub fn print_record(
             &self,
-            templates: Option<&Handlebars>,
+            templates: Option<&RecordTemplate>,
             record: &Record,
             header_print: &mut bool,
             terminal: &mut Option<TuiTerminal<CrosstermBackend<Stdout>>>,
             table_model: &mut Option<TableModel>,
             pb: &ProgressRenderer,
         ) {
+            let formatted = self.format_record(templates, record, header_print, table_model);
+
+            // If the consume type is table, we don't want to accidentally print a newline
+            if self.output != Some(ConsumeOutputType::full_table) {
+                match formatted {
+                    Some(output) => pb.println(&output),
+                    // (Some(_), None) only if JSON cannot be printed, so skip.
+                    None => debug!("Skipping record that cannot be formatted"),
+                }
+            } else if let Some(term) = terminal
+                && let Some(table) = table_model
+            {
+                table.render(term);
+            }
+        }
+
+        /// Format record based on output type, none if record can't be formatted
+        fn format_record(
+            &self,
+            templates: Option<&RecordTemplate>,
+            record: &Record,
+            header_print: &mut bool,
+            table_model: &mut Option<TableModel>,
+        ) -> Option<String> {
             let formatted_key = record
                 .get_key()
                 .map(|key| key.as_utf8_lossy_string())
@@ -627,7 +1089,23 @@ mod cmd {
                 (Some(ConsumeOutputType::dynamic) | None, None) => {
                     Some(format_dynamic_record(record.value()))
                 }
-                (Some(ConsumeOutputType::raw), None) => Some(format_raw_record(record.value())),
+                (Some(ConsumeOutputType::raw | ConsumeOutputType::file), None) => {
+                    Some(format_raw_record(record.value()))
+                }
+                (Some(ConsumeOutputType::avro), None) => {
+                    let decoded = match &self.avro {
+                        Some(avro) => avro.decode(record.value()),
+                        None => Err(anyhow::anyhow!("avro schema is not loaded")),
+                    };
+                    match decoded {
+                        Ok(value) => serde_json::to_string_pretty(&value).ok(),
+                        Err(err) if !self.suppress_unknown => serde_json::to_string_pretty(
+                            &serde_json::json!({ "error": format!("{err}") }),
+                        )
+                        .ok(),
+                        Err(_) => None,
+                    }
+                }
                 (Some(ConsumeOutputType::table), None) => {
                     let value = format_basic_table_record(record.value(), *header_print);
 
@@ -645,57 +1123,22 @@ mod cmd {
                         unreachable!()
                     }
                 }
-                (_, Some(templates)) => {
-                    let value = record.get_value().as_utf8_lossy_string();
-                    let timestamp_rfc3339 = if record.timestamp() == NO_TIMESTAMP {
-                        "NA".to_string()
-                    } else {
-                        format!(
-                            "{}",
-                            humantime::format_rfc3339_millis(
-                                UNIX_EPOCH
-                                    + std::time::Duration::from_millis(
-                                        record.timestamp().try_into().unwrap_or_default()
-                                    )
-                            )
-                        )
-                    };
-
-                    let object = serde_json::json!({
-                        "key": formatted_key,
-                        "value": value,
-                        "offset": record.offset(),
-                        "partition": record.partition(),
-                        "time": timestamp_rfc3339,
-                    });
-                    templates.render(USER_TEMPLATE, &object).ok()
-                }
+                (_, Some(templates)) => templates.render(&record.into()),
             };
 
-            // If the consume type is table, we don't want to accidentally print a newline
-            if self.output != Some(ConsumeOutputType::full_table) {
-                match formatted_value {
-                    Some(value) if self.key_value => {
-                        let output = format!("[{formatted_key}] {value}");
-                        pb.println(&output);
-                    }
-                    Some(mut value) => {
-                        if self.truncate {
-                            // `indicatif` doesn't handle well lengthy messages
-                            // TODO: use `indicatif` truncation once the issue is solved
-                            // https://github.com/console-rs/indicatif/issues/591
-                            let (width, _) = crossterm::terminal::size().unwrap_or((u16::MAX, 0));
-                            value = value.chars().take(width as usize).collect();
-                        }
-                        pb.println(&value);
+            match formatted_value {
+                Some(value) if self.key_value => Some(format!("[{formatted_key}] {value}")),
+                Some(mut value) => {
+                    if self.truncate {
+                        // `indicatif` doesn't handle well lengthy messages
+                        // TODO: use `indicatif` truncation once the issue is solved
+                        // https://github.com/console-rs/indicatif/issues/591
+                        let (width, _) = crossterm::terminal::size().unwrap_or((u16::MAX, 0));
+                        value = value.chars().take(width as usize).collect();
                     }
-                    // (Some(_), None) only if JSON cannot be printed, so skip.
-                    _ => debug!("Skipping record that cannot be formatted"),
+                    Some(value)
                 }
-            } else if let Some(term) = terminal
-                && let Some(table) = table_model
-            {
-                table.render(term);
+                None => None,
             }
         }
 
@@ -732,32 +1175,6 @@ mod cmd {
             eprintln!("{}", self.format_status_string().bold());
         }
 
-        /// Initialize Ctrl-C event handler
-        fn init_ctrlc(&self) -> Result<async_channel::Receiver<()>> {
-            let (s, r) = async_channel::bounded(1);
-            let invoked = AtomicBool::new(false);
-            let result = ctrlc::set_handler(move || {
-                debug!("detected control c, setting end");
-                if invoked.load(Ordering::SeqCst) {
-                    std::process::exit(0);
-                } else {
-                    invoked.store(true, Ordering::SeqCst);
-                    let _ = s.try_send(());
-                    std::thread::sleep(Duration::from_secs(2));
-                    std::process::exit(0);
-                }
-            });
-
-            if let Err(err) = result {
-                return Err(IoError::new(
-                    ErrorKind::InvalidData,
-                    format!("CTRL-C handler can't be initialized {err}"),
-                )
-                .into());
-            }
-            Ok(r)
-        }
-
         /// Calculate the Offset to use with the consumer based on the provided offset number
         fn calculate_offset(&self) -> Result<Offset> {
             let offset = if self.beginning {
@@ -768,6 +1185,9 @@ mod cmd {
                 Offset::absolute(offset as i64).unwrap()
             } else if let Some(offset) = self.tail {
                 Offset::from_end(offset)
+            } else if self.since.is_some() {
+                // SPU skips batches before since
+                Offset::beginning()
             } else {
                 Offset::end()
             };
@@ -787,6 +1207,10 @@ mod cmd {
         raw,
         table,
         full_table,
+        /// write records to files of partitions, see `--file-dir` and `--rotate-size`
+        file,
+        /// decode Avro values to JSON, see `--schema-path` and `--schema-registry`
+        avro,
     }
 
     /// Consume output type defaults to text formatting
@@ -799,7 +1223,7 @@ mod cmd {
     mod tests {
         use fluvio::Offset;
 
-        use super::ConsumeOpt;
+        use super::{ConsumeOpt, parse_sample, parse_time};
 
         fn get_opt() -> ConsumeOpt {
             ConsumeOpt {
@@ -823,12 +1247,32 @@ mod cmd {
                 smartmodule_path: Default::default(),
                 aggregate_initial: Default::default(),
                 params: Default::default(),
+                params_file: Default::default(),
                 isolation: Default::default(),
                 beginning: Default::default(),
                 transforms: Default::default(),
                 transforms_line: Default::default(),
+                no_default_transforms: Default::default(),
                 truncate: Default::default(),
                 consumer: Default::default(),
+                dedup_key: Default::default(),
+                dedup_window: Default::default(),
+                sample: Default::default(),
+                every: Default::default(),
+                since: Default::default(),
+                until: Default::default(),
+                bypass_cache: Default::default(),
+                dead_letter_topic: Default::default(),
+                workers: Default::default(),
+                file_dir: Default::default(),
+                file_name: Default::default(),
+                rotate_size: Default::default(),
+                rotate_interval: Default::default(),
+                compress: Default::default(),
+                schema_path: Default::default(),
+                schema_registry: Default::default(),
+                schema_subject: Default::default(),
+                avro: Default::default(),
             }
         }
         #[test]
@@ -925,6 +1369,37 @@ mod cmd {
             opt.start = Some(1);
             let offset = opt.calculate_offset().unwrap();
             assert_eq!(offset, Offset::absolute(1).unwrap());
+
+            // --since
+            let mut opt = get_opt();
+            opt.since = Some(1_000);
+            let offset = opt.calculate_offset().unwrap();
+            assert_eq!(offset, Offset::beginning());
+
+            // --since with --tail
+            opt.tail = Some(1);
+            let offset = opt.calculate_offset().unwrap();
+            assert_eq!(offset, Offset::from_end(1));
+        }
+
+        #[test]
+        fn test_parse_sample() {
+            assert_eq!(parse_sample("1%"), Ok(100));
+            assert_eq!(parse_sample("0.5%"), Ok(50));
+            assert_eq!(parse_sample("100"), Ok(10_000));
+            assert!(parse_sample("0%").is_err());
+            assert!(parse_sample("101%").is_err());
+            assert!(parse_sample("half").is_err());
+        }
+
+        #[test]
+        fn test_parse_time() {
+            assert_eq!(parse_time("2024-01-01T00:00:00Z"), Ok(1_704_067_200_000));
+            assert_eq!(parse_time("2024-01-01 00:00:01"), Ok(1_704_067_201_000));
+            let hour_ago = parse_time("1h").expect("duration");
+            let now = parse_time("0s").expect("now");
+            assert!((now - hour_ago - 3_600_000).abs() < 1_000);
+            assert!(parse_time("yesterday").is_err());
         }
     }
 }
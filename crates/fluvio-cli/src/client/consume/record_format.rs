@@ -192,7 +192,7 @@ fn update_table_row(
 ) -> Result<()> {
     //if let serde_json::Value::Object(obj) = object {
     let mut new_data: BTreeMap<String, String> = BTreeMap::new();
-    for (k, v) in object {
+    for (k, v) in &object {
         let key = k.to_string();
         let value = if v.is_string() {
             if let Some(s) = v.as_str() {
@@ -224,6 +224,8 @@ fn update_table_row(
         }
     }
 
+    new_data.extend(table_model.computed_values(&object));
+
     // This will append if now primary keys or update rows based on primary key values
     if table_model.update_row(new_data).is_err() {
         println!("Unable to update table row");
@@ -4,6 +4,10 @@
 
 use std::io::Stdout;
 use std::collections::BTreeMap;
+use std::time::{Duration, UNIX_EPOCH};
+
+use bytesize::ByteSize;
+use serde_json::{Map, Value};
 
 use tui::widgets::TableState;
 use tui::{
@@ -16,12 +20,17 @@ use tui::{
 use crossterm::event::{Event, KeyCode, MouseEventKind};
 use anyhow::Result;
 
-use fluvio::metadata::tableformat::{TableFormatColumnConfig, TableFormatSpec, DataFormat};
+use fluvio::metadata::tableformat::{
+    TableFormatColumnConfig, TableFormatSpec, DataFormat, Color as ColumnColor,
+};
+
+use crate::client::tableformat::expr::{display, json_field, Expr};
 
 #[derive(Debug, Default, Clone)]
 pub struct TableModel {
     state: TableState,
     columns: Vec<TableFormatColumnConfig>, // List of json key paths. Should be initialized either at Self::new() or at first row entered
+    rules: Vec<ColumnRules>,               // Parsed expressions of columns, in order of columns
 
     // Maybe data should be some kind of map structure, so we can enforce headers as column order easier
     data: Vec<BTreeMap<String, String>>,
@@ -42,24 +51,42 @@ impl TableModel {
         self.data.clone()
     }
 
-    pub fn with_tableformat(&mut self, tableformat: Option<TableFormatSpec>) {
+    pub fn with_tableformat(&mut self, tableformat: Option<TableFormatSpec>) -> Result<()> {
         if let Some(format) = tableformat {
             if let Some(input_format) = format.input_format {
                 self.input_format = input_format;
             }
             if let Some(columns) = format.columns {
-                self.columns = columns;
+                self.update_columns(columns)?;
             }
         }
+        Ok(())
     }
 
     // I think this should accept columns that don't exist in the data. Print empty columns
     pub fn update_columns(&mut self, columns: Vec<TableFormatColumnConfig>) -> Result<()> {
+        self.rules = columns
+            .iter()
+            .map(ColumnRules::new)
+            .collect::<Result<_>>()?;
         self.columns = columns;
 
         Ok(())
     }
 
+    /// Values of computed columns for the record
+    pub fn computed_values(&self, object: &Map<String, Value>) -> Vec<(String, String)> {
+        self.columns
+            .iter()
+            .zip(&self.rules)
+            .filter_map(|(column, rules)| {
+                let expression = rules.expression.as_ref()?;
+                let value = expression.eval(&|path| json_field(object, path));
+                Some((column.key_path.clone(), display(&value)))
+            })
+            .collect()
+    }
+
     // By default, we append rows
     // To in-place update rows, create a tableformat and define what columns are your primary keys
     // Then new_data is compared against all the table data
@@ -356,13 +383,15 @@ impl TableModel {
             100
         };
 
-        let mut column_constraints: Vec<Constraint> = Vec::new();
-
-        // TODO: Maybe provide a way to size the width of a column
-        // Define the widths of the columns
-        for _ in 0..self.num_columns() {
-            column_constraints.push(Constraint::Percentage(equal_column_width));
-        }
+        // Define the widths of the columns, equal share unless width is configured
+        let column_constraints: Vec<Constraint> = self
+            .columns
+            .iter()
+            .map(|column| {
+                column_width(column.width.as_deref())
+                    .unwrap_or(Constraint::Percentage(equal_column_width))
+            })
+            .collect();
 
         let selected_symbol = format!("{} >> ", self.current_selected());
 
@@ -396,7 +425,7 @@ impl TableModel {
 
         for row_data in self.data.iter() {
             let mut cells = vec![];
-            for col in &self.columns {
+            for (col, rules) in self.columns.iter().zip(&self.rules) {
                 let key_path = col.key_path.as_str();
                 let value = if let Some(v) = row_data.get(key_path) {
                     v
@@ -406,7 +435,11 @@ impl TableModel {
                     ""
                 };
 
-                cells.push(Cell::from(value));
+                let mut cell = Cell::from(format_cell(value, col));
+                if let Some(color) = rules.color(row_data) {
+                    cell = cell.style(Style::default().fg(color));
+                }
+                cells.push(cell);
             }
 
             //rows.push(Row::new(cells).height(height as u16).bottom_margin(0))
@@ -434,6 +467,102 @@ impl TableModel {
     }
 }
 
+/// Parsed expressions of a column
+#[derive(Debug, Default, Clone)]
+struct ColumnRules {
+    expression: Option<Expr>,
+    colors: Vec<(Expr, ColumnColor)>,
+}
+
+impl ColumnRules {
+    fn new(column: &TableFormatColumnConfig) -> Result<Self> {
+        let expression = column.expression.as_deref().map(Expr::parse).transpose()?;
+        let colors = column
+            .color_rules
+            .iter()
+            .map(|rule| Ok((Expr::parse(&rule.when)?, rule.color.clone())))
+            .collect::<Result<_>>()?;
+        Ok(Self { expression, colors })
+    }
+
+    /// Color of first rule holding for the row
+    fn color(&self, row: &BTreeMap<String, String>) -> Option<Color> {
+        self.colors
+            .iter()
+            .find(|(when, _)| when.holds(&|path| row_field(row, path)))
+            .map(|(_, color)| match color {
+                ColumnColor::Blue => Color::Blue,
+                ColumnColor::Yellow => Color::Yellow,
+                ColumnColor::Green => Color::Green,
+                ColumnColor::Red => Color::Red,
+                ColumnColor::Cyan => Color::Cyan,
+                ColumnColor::Magenta => Color::Magenta,
+            })
+    }
+}
+
+/// Value of field in row, cells of nested values hold JSON text
+fn row_field(row: &BTreeMap<String, String>, path: &str) -> Option<Value> {
+    let cell_value =
+        |text: &str| serde_json::from_str(text).unwrap_or_else(|_| Value::String(text.to_owned()));
+    if let Some(text) = row.get(path) {
+        return Some(cell_value(text));
+    }
+    let (root, _) = path.split_once('.')?;
+    let object: Map<String, Value> = [(root.to_owned(), cell_value(row.get(root)?))]
+        .into_iter()
+        .collect();
+    json_field(&object, path)
+}
+
+/// Width of column in characters, or percentage of table if it ends with `%`
+fn column_width(width: Option<&str>) -> Option<Constraint> {
+    let width = width?.trim();
+    match width.strip_suffix('%') {
+        Some(percent) => percent.trim().parse().ok().map(Constraint::Percentage),
+        None => width.parse().ok().map(Constraint::Length),
+    }
+}
+
+/// Apply `format` and `max_length` of column to the value.
+///
+/// Formats of numeric values: `fixed:<decimals>`, `percent:<decimals>`, `bytes`,
+/// and `date` for epoch milliseconds, or `date:s` for epoch seconds.
+/// Values which are not numbers are kept as they are
+fn format_cell(value: &str, column: &TableFormatColumnConfig) -> String {
+    let text = match column.format.as_deref() {
+        Some(format) => format_value(value, format),
+        None => value.to_owned(),
+    };
+    match column.max_length.map(|max| max as usize) {
+        Some(max) if text.chars().count() > max => {
+            let mut truncated: String = text.chars().take(max.saturating_sub(1)).collect();
+            truncated.push('…');
+            truncated
+        }
+        _ => text,
+    }
+}
+
+fn format_value(value: &str, format: &str) -> String {
+    let (kind, arg) = format.split_once(':').unwrap_or((format, ""));
+    let Ok(number) = value.trim().parse::<f64>() else {
+        return value.to_owned();
+    };
+    let decimals = arg.parse().unwrap_or(2);
+    match kind {
+        "fixed" => format!("{number:.decimals$}"),
+        "percent" => format!("{:.decimals$}%", number * 100.0),
+        "bytes" if number >= 0.0 => ByteSize::b(number as u64).to_string(),
+        "date" if number >= 0.0 => {
+            let millis = if arg == "s" { number * 1000.0 } else { number };
+            let time = UNIX_EPOCH + Duration::from_millis(millis as u64);
+            humantime::format_rfc3339_millis(time).to_string()
+        }
+        _ => value.to_owned(),
+    }
+}
+
 /// Return response from user input on TUI table
 #[derive(Debug)]
 pub enum TableEventResponse {
@@ -441,3 +570,87 @@ pub enum TableEventResponse {
     InputIgnored(Event),
     Terminate,
 }
+
+#[cfg(test)]
+mod test {
+
+    use fluvio::metadata::tableformat::TableFormatColorRule;
+
+    use super::*;
+
+    #[test]
+    fn test_format_cell() {
+        let column = |format: &str| TableFormatColumnConfig {
+            format: Some(format.to_owned()),
+            ..Default::default()
+        };
+        assert_eq!(format_cell("3.14159", &column("fixed:2")), "3.14");
+        assert_eq!(format_cell("0.256", &column("percent:1")), "25.6%");
+        assert_eq!(format_cell("2000", &column("bytes")), "2.0 KB");
+        assert_eq!(
+            format_cell("1700000000", &column("date:s")),
+            "2023-11-14T22:13:20.000Z"
+        );
+        assert_eq!(format_cell("n/a", &column("fixed:2")), "n/a");
+
+        let truncated = TableFormatColumnConfig {
+            max_length: Some(5),
+            ..Default::default()
+        };
+        assert_eq!(format_cell("abcdefgh", &truncated), "abcd…");
+        assert_eq!(format_cell("abc", &truncated), "abc");
+
+        assert_eq!(column_width(Some("20")), Some(Constraint::Length(20)));
+        assert_eq!(column_width(Some("25%")), Some(Constraint::Percentage(25)));
+        assert_eq!(column_width(Some("wide")), None);
+    }
+
+    #[test]
+    fn test_computed_columns_and_colors() {
+        let mut model = TableModel::new();
+        model
+            .update_columns(vec![
+                TableFormatColumnConfig::new("total".to_owned())
+                    .with_expression(Some("price * quantity".to_owned())),
+                TableFormatColumnConfig {
+                    key_path: "status".to_owned(),
+                    color_rules: vec![TableFormatColorRule {
+                        when: "status == 'error' || meta.retries > 3".to_owned(),
+                        color: ColumnColor::Red,
+                    }],
+                    ..Default::default()
+                },
+            ])
+            .expect("valid columns");
+
+        let object = serde_json::json!({"price": 2.5, "quantity": 4});
+        assert_eq!(
+            model.computed_values(object.as_object().expect("object")),
+            vec![("total".to_owned(), "10".to_owned())]
+        );
+
+        let row = |status: &str, meta: &str| -> BTreeMap<String, String> {
+            [
+                ("status".to_owned(), status.to_owned()),
+                ("meta".to_owned(), meta.to_owned()),
+            ]
+            .into()
+        };
+        let status = &model.rules[1];
+        assert_eq!(status.color(&row("error", "{}")), Some(Color::Red));
+        assert_eq!(
+            status.color(&row("ok", r#"{"retries":5}"#)),
+            Some(Color::Red)
+        );
+        assert_eq!(status.color(&row("ok", r#"{"retries":1}"#)), None);
+
+        assert!(
+            model
+                .update_columns(vec![
+                    TableFormatColumnConfig::new("total".to_owned())
+                        .with_expression(Some("price *".to_owned()))
+                ])
+                .is_err()
+        );
+    }
+}
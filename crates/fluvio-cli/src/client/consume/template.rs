@@ -0,0 +1,253 @@
+//!
+//! # Record templates
+//!
+//! Templates of `--format`, either with `{name}` placeholders, ex: `{key} | {offset} | {value.foo}`,
+//! or handlebars templates with `{{name}}` placeholders.
+//! Placeholders of keys and values holding JSON can select fields with dotted paths,
+//! array elements are selected by index, ex: `{value.items.0.name}`.
+//!
+
+use std::time::{Duration, UNIX_EPOCH};
+
+use anyhow::{Result, anyhow};
+use handlebars::{self, Handlebars};
+use serde_json::Value;
+
+use fluvio::consumer::Record;
+use fluvio_protocol::record::NO_TIMESTAMP;
+use fluvio_types::PartitionId;
+
+const USER_TEMPLATE: &str = "user_template";
+
+const FIELDS: [&str; 6] = ["key", "value", "offset", "partition", "time", "timestamp"];
+
+/// Template of printed records
+pub(crate) enum RecordTemplate {
+    Fields(Vec<Part>),
+    Handlebars(Handlebars<'static>),
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum Part {
+    Literal(String),
+    /// field name followed by JSON path
+    Field(String, Vec<String>),
+}
+
+/// Parts of record available to templates
+pub(crate) struct TemplateRecord<'a> {
+    pub key: Option<&'a [u8]>,
+    pub value: &'a [u8],
+    pub offset: i64,
+    pub partition: PartitionId,
+    pub timestamp: i64,
+}
+
+impl<'a> From<&'a Record> for TemplateRecord<'a> {
+    fn from(record: &'a Record) -> Self {
+        Self {
+            key: record.key(),
+            value: record.value(),
+            offset: record.offset(),
+            partition: record.partition(),
+            timestamp: record.timestamp(),
+        }
+    }
+}
+
+impl RecordTemplate {
+    /// templates with `{{` are handlebars templates
+    pub fn parse(format: &str) -> Result<Self> {
+        if format.contains("{{") {
+            let mut reg = Handlebars::new();
+            // opt-out of HTML escaping of printable record data
+            reg.register_escape_fn(handlebars::no_escape);
+            reg.register_template_string(USER_TEMPLATE, format)?;
+            Ok(Self::Handlebars(reg))
+        } else {
+            Ok(Self::Fields(parse_parts(format)?))
+        }
+    }
+
+    pub fn render(&self, record: &TemplateRecord) -> Option<String> {
+        match self {
+            Self::Fields(parts) => Some(
+                parts
+                    .iter()
+                    .map(|part| match part {
+                        Part::Literal(literal) => literal.clone(),
+                        Part::Field(name, path) => render_field(record, name, path),
+                    })
+                    .collect(),
+            ),
+            Self::Handlebars(reg) => {
+                let object = serde_json::json!({
+                    "key": record
+                        .key
+                        .map(|key| String::from_utf8_lossy(key).into_owned())
+                        .unwrap_or_else(|| "null".into()),
+                    "value": String::from_utf8_lossy(record.value),
+                    "offset": record.offset,
+                    "partition": record.partition,
+                    "time": format_time(record.timestamp),
+                });
+                reg.render(USER_TEMPLATE, &object).ok()
+            }
+        }
+    }
+}
+
+/// split template into literals and placeholders.
+/// braces not enclosing a placeholder, as in JSON literals, are printed as they are
+fn parse_parts(format: &str) -> Result<Vec<Part>> {
+    let mut parts = vec![];
+    let mut literal = String::new();
+    let mut rest = format;
+    while let Some(start) = rest.find('{') {
+        literal.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let placeholder = after
+            .find('}')
+            .map(|end| &after[..end])
+            .filter(|placeholder| is_placeholder(placeholder));
+        match placeholder {
+            Some(placeholder) => {
+                let mut path = placeholder.split('.').map(str::to_owned);
+                let name = path.next().unwrap_or_default();
+                if !FIELDS.contains(&name.as_str()) {
+                    return Err(anyhow!(
+                        "unknown field `{name}` in format, expected one of: {}",
+                        FIELDS.join(", ")
+                    ));
+                }
+                if !literal.is_empty() {
+                    parts.push(Part::Literal(std::mem::take(&mut literal)));
+                }
+                parts.push(Part::Field(name, path.collect()));
+                rest = &after[placeholder.len() + 1..];
+            }
+            None => {
+                literal.push('{');
+                rest = after;
+            }
+        }
+    }
+    literal.push_str(rest);
+    if !literal.is_empty() {
+        parts.push(Part::Literal(literal));
+    }
+    Ok(parts)
+}
+
+fn is_placeholder(placeholder: &str) -> bool {
+    !placeholder.is_empty()
+        && placeholder.split('.').all(|segment| {
+            !segment.is_empty()
+                && segment
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+        })
+}
+
+fn render_field(record: &TemplateRecord, name: &str, path: &[String]) -> String {
+    match name {
+        "key" => match record.key {
+            Some(key) => render_bytes(key, path),
+            None => "null".to_owned(),
+        },
+        "value" => render_bytes(record.value, path),
+        "offset" => record.offset.to_string(),
+        "partition" => record.partition.to_string(),
+        "time" => format_time(record.timestamp),
+        "timestamp" => record.timestamp.to_string(),
+        _ => String::new(),
+    }
+}
+
+/// bytes as text, or selected field of JSON. Missing fields are empty
+fn render_bytes(bytes: &[u8], path: &[String]) -> String {
+    if path.is_empty() {
+        return String::from_utf8_lossy(bytes).into_owned();
+    }
+    let Ok(json) = serde_json::from_slice::<Value>(bytes) else {
+        return String::new();
+    };
+    let selected = path.iter().try_fold(&json, |json, segment| match json {
+        Value::Object(object) => object.get(segment),
+        Value::Array(array) => segment.parse::<usize>().ok().and_then(|i| array.get(i)),
+        _ => None,
+    });
+    match selected {
+        Some(Value::String(string)) => string.clone(),
+        Some(Value::Null) | None => String::new(),
+        Some(other) => other.to_string(),
+    }
+}
+
+/// timestamp using RFC3339, always UTC
+fn format_time(timestamp: i64) -> String {
+    if timestamp == NO_TIMESTAMP {
+        "NA".to_string()
+    } else {
+        humantime::format_rfc3339_millis(
+            UNIX_EPOCH + Duration::from_millis(timestamp.try_into().unwrap_or_default()),
+        )
+        .to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    fn record<'a>(key: Option<&'a [u8]>, value: &'a [u8]) -> TemplateRecord<'a> {
+        TemplateRecord {
+            key,
+            value,
+            offset: 7,
+            partition: 1,
+            timestamp: 1_700_000_000_000,
+        }
+    }
+
+    #[test]
+    fn test_field_template() {
+        let template = RecordTemplate::parse("{key} | {offset} | {value.foo}").expect("parse");
+        let value = br#"{"foo": "bar", "n": 1}"#;
+        assert_eq!(
+            template.render(&record(Some(b"k1"), value)),
+            Some("k1 | 7 | bar".to_owned())
+        );
+        assert_eq!(
+            template.render(&record(None, b"not json")),
+            Some("null | 7 | ".to_owned())
+        );
+
+        let template =
+            RecordTemplate::parse(r#"{"p": {partition}, "v": {value.items.1}}"#).expect("parse");
+        assert_eq!(
+            template.render(&record(None, br#"{"items": [1, {"a": true}]}"#)),
+            Some(r#"{"p": 1, "v": {"a":true}}"#.to_owned())
+        );
+
+        let template = RecordTemplate::parse("{time} {timestamp}").expect("parse");
+        assert_eq!(
+            template.render(&record(None, b"")),
+            Some("2023-11-14T22:13:20.000Z 1700000000000".to_owned())
+        );
+
+        assert!(RecordTemplate::parse("{values}").is_err());
+    }
+
+    #[test]
+    fn test_handlebars_template() {
+        let template =
+            RecordTemplate::parse("Offset {{offset}} has key {{key}} and value {{value}}")
+                .expect("parse");
+        assert_eq!(
+            template.render(&record(Some(b"A"), b"<Apple>")),
+            Some("Offset 7 has key A and value <Apple>".to_owned())
+        );
+    }
+}
@@ -0,0 +1,93 @@
+//!
+//! # Consume workers
+//!
+//! Partitions are assigned to workers, each worker consumes and formats records of its
+//! partitions in its own task. Output of all workers is merged into one channel,
+//! so records of a partition keep their order while partitions are processed in parallel.
+//!
+
+use anyhow::Result;
+use async_channel::{Receiver, bounded};
+use futures::StreamExt;
+use tracing::debug;
+
+use fluvio::Fluvio;
+use fluvio::consumer::{ConsumerConfigExt, Record};
+use fluvio_future::task::spawn;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_types::PartitionId;
+
+/// formatted records buffered before workers wait for output
+const WORKER_BUFFER: usize = 1000;
+
+/// record with its formatted output, none if record can't be formatted
+pub(crate) type FormattedRecord = (Record, Option<String>);
+
+/// start consuming `partitions` with `workers` tasks, records are formatted with `format`
+pub(crate) async fn start_workers<F>(
+    fluvio: &Fluvio,
+    config: &ConsumerConfigExt,
+    partitions: &[PartitionId],
+    workers: usize,
+    format: F,
+) -> Result<Receiver<Result<FormattedRecord, ErrorCode>>>
+where
+    F: Fn(&Record) -> Option<String> + Clone + Send + Sync + 'static,
+{
+    let assignments = assign_partitions(partitions, workers);
+    let (sender, receiver) = bounded(WORKER_BUFFER);
+    for (worker, assigned) in assignments.into_iter().enumerate() {
+        let mut streams = Vec::with_capacity(assigned.len());
+        for partition in &assigned {
+            let mut config = config.clone();
+            config.partition = vec![*partition];
+            streams.push(Box::pin(fluvio.consumer_with_config(config).await?));
+        }
+        debug!(worker, partitions = ?assigned, "starting consume worker");
+
+        let sender = sender.clone();
+        let format = format.clone();
+        spawn(async move {
+            let mut records = futures::stream::select_all(streams);
+            while let Some(record) = records.next().await {
+                let output = record.map(|record| {
+                    let formatted = format(&record);
+                    (record, formatted)
+                });
+                if sender.send(output).await.is_err() {
+                    debug!(worker, "output closed, stopping consume worker");
+                    break;
+                }
+            }
+        });
+    }
+    // channel is closed when all workers are finished
+    Ok(receiver)
+}
+
+/// assign partitions to workers round robin, workers without partitions are not started
+fn assign_partitions(partitions: &[PartitionId], workers: usize) -> Vec<Vec<PartitionId>> {
+    let workers = workers.clamp(1, partitions.len().max(1));
+    let mut assignments = vec![Vec::new(); workers];
+    for (index, partition) in partitions.iter().enumerate() {
+        assignments[index % workers].push(*partition);
+    }
+    assignments.retain(|assigned| !assigned.is_empty());
+    assignments
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_assign_partitions() {
+        assert_eq!(
+            assign_partitions(&[0, 1, 2, 3, 4], 2),
+            vec![vec![0, 2, 4], vec![1, 3]]
+        );
+        assert_eq!(assign_partitions(&[0, 1], 4), vec![vec![0], vec![1]]);
+        assert!(assign_partitions(&[], 4).is_empty());
+    }
+}
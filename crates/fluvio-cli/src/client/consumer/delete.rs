@@ -13,11 +13,20 @@ pub struct DeleteConsumerOpt {
     #[clap(flatten)]
     output: OutputFormat,
 
-    consumer: String,
+    #[arg(required_unless_present = "all")]
+    consumer: Option<String>,
     #[arg(short, long, required = false)]
     topic: Option<String>,
     #[arg(short, long, required = false, requires = "topic")]
     partition: Option<PartitionId>,
+
+    /// Delete offsets of consumers which have not committed for at least this long, ex: 30d
+    #[arg(long, value_name = "DURATION")]
+    stale: Option<humantime::Duration>,
+
+    /// Delete stale offsets of all consumers instead of named one
+    #[arg(long, requires = "stale", conflicts_with = "consumer")]
+    all: bool,
 }
 
 impl DeleteConsumerOpt {
@@ -26,17 +35,28 @@ impl DeleteConsumerOpt {
     where
         O: Terminal,
     {
-        if let Some((topic, partition)) = self.topic.as_ref().zip(self.partition.as_ref()) {
-            delete(fluvio, self.consumer, topic.clone(), *partition).await?;
+        if let (Some(consumer), Some((topic, partition)), None) = (
+            &self.consumer,
+            self.topic.as_ref().zip(self.partition.as_ref()),
+            self.stale,
+        ) {
+            delete(fluvio, consumer.clone(), topic.clone(), *partition).await?;
         } else {
             let consumers: Vec<_> = fluvio
                 .consumer_offsets()
                 .await?
                 .into_iter()
-                .filter(|c| c.consumer_id.eq(&self.consumer))
+                .filter(|c| {
+                    self.consumer.is_none() || self.consumer.as_ref() == Some(&c.consumer_id)
+                })
                 .filter(|c| {
                     self.topic.is_none() || c.topic.eq(self.topic.as_deref().unwrap_or_default())
                 })
+                .filter(|c| self.partition.is_none() || self.partition == Some(c.partition))
+                .filter(|c| {
+                    self.stale
+                        .is_none_or(|stale| super::is_stale(c.modified_time, *stale))
+                })
                 .collect();
             if consumers.is_empty() {
                 println!("no consumers found");
@@ -72,3 +92,25 @@ async fn delete(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_stale_of_all_consumers_requires_all() {
+        assert!(DeleteConsumerOpt::try_parse_from(["delete", "--stale", "30d"]).is_err());
+        assert!(DeleteConsumerOpt::try_parse_from(["delete", "--all"]).is_err());
+        assert!(
+            DeleteConsumerOpt::try_parse_from(["delete", "c1", "--stale", "30d", "--all"]).is_err()
+        );
+
+        let opt = DeleteConsumerOpt::try_parse_from(["delete", "--stale", "30d", "--all"])
+            .expect("parse");
+        assert!(opt.all && opt.consumer.is_none());
+        let opt =
+            DeleteConsumerOpt::try_parse_from(["delete", "c1", "--stale", "30d"]).expect("parse");
+        assert_eq!(opt.consumer.as_deref(), Some("c1"));
+    }
+}
@@ -0,0 +1,197 @@
+use std::collections::{BTreeSet, HashMap};
+
+use clap::Parser;
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+
+use fluvio::Fluvio;
+use fluvio::consumer::{ConsumerOffset, PartitionOffsets};
+use fluvio_types::PartitionId;
+
+use crate::common::output::Terminal;
+use crate::common::OutputFormat;
+
+/// Option for Describing Consumer
+#[derive(Debug, Parser)]
+pub struct DescribeConsumerOpt {
+    consumer: String,
+
+    /// Only describe offsets on this topic
+    #[arg(short, long)]
+    topic: Option<String>,
+
+    #[clap(flatten)]
+    output: OutputFormat,
+}
+
+impl DescribeConsumerOpt {
+    pub async fn process<O>(self, out: std::sync::Arc<O>, fluvio: &Fluvio) -> Result<()>
+    where
+        O: Terminal,
+    {
+        let consumers: Vec<ConsumerOffset> = fluvio
+            .consumer_offsets()
+            .await?
+            .into_iter()
+            .filter(|c| c.consumer_id == self.consumer)
+            .filter(|c| self.topic.as_ref().is_none_or(|topic| *topic == c.topic))
+            .collect();
+        if consumers.is_empty() {
+            return Err(anyhow!("consumer \"{}\" not found", self.consumer));
+        }
+
+        let topics: BTreeSet<&str> = consumers.iter().map(|c| c.topic.as_str()).collect();
+        let mut offsets = vec![];
+        for topic in topics {
+            offsets.extend(fluvio.topic_offsets(topic).await.unwrap_or_default());
+        }
+
+        let partitions = describe_partitions(consumers, offsets);
+        out.render_list(&display::ConsumerPartitions(partitions), self.output.format)?;
+        Ok(())
+    }
+}
+
+/// offset of consumer on partition, with partition offsets when they are available
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+struct ConsumerPartition {
+    topic: String,
+    partition: PartitionId,
+    offset: i64,
+    earliest: Option<i64>,
+    high_watermark: Option<i64>,
+    lag: Option<i64>,
+    modified_time: u64,
+}
+
+fn describe_partitions(
+    consumers: Vec<ConsumerOffset>,
+    offsets: Vec<PartitionOffsets>,
+) -> Vec<ConsumerPartition> {
+    let offsets: HashMap<(String, PartitionId), PartitionOffsets> = offsets
+        .into_iter()
+        .map(|offsets| ((offsets.topic.clone(), offsets.partition), offsets))
+        .collect();
+    let mut partitions: Vec<ConsumerPartition> = consumers
+        .into_iter()
+        .map(|consumer| {
+            let partition_offsets = offsets.get(&(consumer.topic.clone(), consumer.partition));
+            ConsumerPartition {
+                earliest: partition_offsets.map(|o| o.earliest),
+                high_watermark: partition_offsets.map(|o| o.high_watermark),
+                lag: partition_offsets.map(|o| o.lag(consumer.offset)),
+                topic: consumer.topic,
+                partition: consumer.partition,
+                offset: consumer.offset,
+                modified_time: consumer.modified_time,
+            }
+        })
+        .collect();
+    partitions.sort();
+    partitions
+}
+
+mod display {
+
+    use std::time::{Duration, SystemTime};
+
+    use comfy_table::Row;
+    use serde::Serialize;
+
+    use crate::common::output::TableOutputHandler;
+
+    use super::ConsumerPartition;
+
+    #[derive(Serialize)]
+    pub(super) struct ConsumerPartitions(pub Vec<ConsumerPartition>);
+
+    impl TableOutputHandler for ConsumerPartitions {
+        fn header(&self) -> Row {
+            Row::from([
+                "TOPIC",
+                "PARTITION",
+                "OFFSET",
+                "EARLIEST",
+                "HIGH WATERMARK",
+                "LAG",
+                "LAST SEEN",
+            ])
+        }
+
+        fn errors(&self) -> Vec<String> {
+            vec![]
+        }
+
+        fn content(&self) -> Vec<Row> {
+            let now = SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            let optional =
+                |value: Option<i64>| value.map_or_else(|| "-".to_owned(), |v| v.to_string());
+            self.0
+                .iter()
+                .map(|partition| {
+                    let last_seen = humantime::Duration::from(Duration::from_secs(
+                        now.saturating_sub(partition.modified_time),
+                    ));
+                    Row::from([
+                        partition.topic.clone(),
+                        partition.partition.to_string(),
+                        partition.offset.to_string(),
+                        optional(partition.earliest),
+                        optional(partition.high_watermark),
+                        optional(partition.lag),
+                        last_seen.to_string(),
+                    ])
+                })
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_describe_partitions() {
+        let consumer = |topic: &str, partition, offset| ConsumerOffset {
+            consumer_id: "c1".to_owned(),
+            topic: topic.to_owned(),
+            partition,
+            offset,
+            modified_time: 0,
+        };
+        let offsets = vec![PartitionOffsets {
+            topic: "orders".to_owned(),
+            partition: 1,
+            earliest: 20,
+            high_watermark: 100,
+            latest: 100,
+        }];
+
+        let partitions = describe_partitions(
+            vec![
+                consumer("orders", 1, 9),
+                consumer("orders", 0, 5),
+                consumer("audit", 0, 1),
+            ],
+            offsets,
+        );
+
+        let summary: Vec<_> = partitions
+            .iter()
+            .map(|p| (p.topic.as_str(), p.partition, p.lag))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("audit", 0, None),
+                ("orders", 0, None),
+                ("orders", 1, Some(80))
+            ]
+        );
+    }
+}
@@ -11,6 +11,10 @@ use crate::common::OutputFormat;
 pub struct ListConsumerOpt {
     #[clap(flatten)]
     output: OutputFormat,
+
+    /// Only list consumers which have not committed offsets for at least this long, ex: 7d
+    #[arg(long, value_name = "DURATION")]
+    stale: Option<humantime::Duration>,
 }
 
 impl ListConsumerOpt {
@@ -18,7 +22,10 @@ impl ListConsumerOpt {
     where
         O: Terminal,
     {
-        let consumers = fluvio.consumer_offsets().await?;
+        let mut consumers = fluvio.consumer_offsets().await?;
+        if let Some(stale) = self.stale {
+            consumers.retain(|consumer| super::is_stale(consumer.modified_time, *stale));
+        }
 
         display::format_response_output(out, consumers, self.output.format)?;
         Ok(())
@@ -91,8 +98,9 @@ mod display {
                         topic,
                         partition,
                     } = consumer;
-                    let last_seen =
-                        humantime::Duration::from(Duration::from_secs(now - modified_time));
+                    let last_seen = humantime::Duration::from(Duration::from_secs(
+                        now.saturating_sub(modified_time),
+                    ));
                     Row::from([
                         Cell::new(consumer_id),
                         Cell::new(topic),
@@ -1,8 +1,21 @@
 mod list;
+mod describe;
 mod delete;
+mod reset;
 
 pub use cmd::ConsumerCmd;
 
+use std::time::{Duration, SystemTime};
+
+/// consumer is stale if its offset was not committed within `threshold`
+fn is_stale(modified_time: u64, threshold: Duration) -> bool {
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    now.saturating_sub(modified_time) >= threshold.as_secs()
+}
+
 mod cmd {
 
     use std::sync::Arc;
@@ -19,23 +32,37 @@ mod cmd {
     use crate::common::FluvioExtensionMetadata;
 
     use super::delete::DeleteConsumerOpt;
+    use super::describe::DescribeConsumerOpt;
     use super::list::ListConsumerOpt;
+    use super::reset::ResetConsumerOpt;
 
     #[derive(Debug, Parser)]
     #[command(name = "consumer", about = "Consumer operations")]
     pub enum ConsumerCmd {
-        /// List all of the Consumer Offsets in this cluster
+        /// List all of the Consumer Offsets in this cluster, or only stale ones
         #[command(
             name = "list",
             help_template = crate::common::COMMAND_TEMPLATE,
         )]
         List(ListConsumerOpt),
-        /// Delete the Consumer Offset
+        /// Show offsets and lag of the Consumer on each partition
+        #[command(
+            name = "describe",
+            help_template = crate::common::COMMAND_TEMPLATE,
+        )]
+        Describe(DescribeConsumerOpt),
+        /// Delete the Consumer Offset, or offsets of all stale consumers with `--stale --all`
         #[command(
             name = "delete",
             help_template = crate::common::COMMAND_TEMPLATE,
         )]
         Delete(DeleteConsumerOpt),
+        /// Reset the Consumer Offset to earliest, latest, absolute offset or timestamp
+        #[command(
+            name = "reset",
+            help_template = crate::common::COMMAND_TEMPLATE,
+        )]
+        Reset(ResetConsumerOpt),
     }
 
     #[async_trait]
@@ -49,9 +76,15 @@ mod cmd {
                 Self::List(list) => {
                     list.process(out, fluvio).await?;
                 }
+                Self::Describe(describe) => {
+                    describe.process(out, fluvio).await?;
+                }
                 Self::Delete(delete) => {
                     delete.process(out, fluvio).await?;
                 }
+                Self::Reset(reset) => {
+                    reset.process(out, fluvio).await?;
+                }
             }
 
             Ok(())
@@ -69,3 +102,22 @@ mod cmd {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_is_stale() {
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let day = Duration::from_secs(24 * 3600);
+
+        assert!(is_stale(now - 2 * 24 * 3600, day));
+        assert!(!is_stale(now - 60, day));
+        assert!(!is_stale(now + 60, day));
+    }
+}
@@ -0,0 +1,139 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use clap::{Args, Parser};
+use anyhow::{anyhow, Result};
+use futures::StreamExt;
+
+use fluvio::{Fluvio, Offset};
+use fluvio::consumer::{ConsumerConfigExt, PartitionOffsets};
+use fluvio_types::PartitionId;
+
+use crate::common::output::Terminal;
+
+/// Option for Resetting Consumer Offsets
+#[derive(Debug, Parser)]
+pub struct ResetConsumerOpt {
+    consumer: String,
+    #[arg(short, long)]
+    topic: String,
+    /// Only reset offset on this partition, all partitions of the topic by default
+    #[arg(short, long)]
+    partition: Option<PartitionId>,
+
+    #[clap(flatten)]
+    to: ResetTo,
+}
+
+/// Position consumer resumes reading from
+#[derive(Debug, Args)]
+#[group(required = true, multiple = false)]
+struct ResetTo {
+    /// Re-read partitions from the first available record
+    #[arg(long)]
+    to_earliest: bool,
+
+    /// Skip all committed records
+    #[arg(long)]
+    to_latest: bool,
+
+    /// Offset of next record to read
+    #[arg(long, value_name = "OFFSET")]
+    to_offset: Option<i64>,
+
+    /// Read from first record with timestamp at or after this time, ex: 2024-05-01T10:00:00Z.
+    /// Requires scanning partitions from the beginning
+    #[arg(long, value_name = "RFC3339")]
+    to_timestamp: Option<humantime::Timestamp>,
+}
+
+impl ResetConsumerOpt {
+    pub async fn process<O>(self, _out: std::sync::Arc<O>, fluvio: &Fluvio) -> Result<()>
+    where
+        O: Terminal,
+    {
+        let partitions: Vec<PartitionOffsets> = fluvio
+            .topic_offsets(&self.topic)
+            .await?
+            .into_iter()
+            .filter(|offsets| self.partition.is_none_or(|p| p == offsets.partition))
+            .collect();
+        if partitions.is_empty() {
+            return Err(anyhow!(
+                "no partitions of topic \"{}\" available",
+                self.topic
+            ));
+        }
+
+        for offsets in partitions {
+            let next = self.next_offset(fluvio, &offsets).await?;
+            // stored offset is of the last consumed record
+            fluvio
+                .reset_consumer_offset(
+                    &self.consumer,
+                    (offsets.topic.clone(), offsets.partition),
+                    next - 1,
+                )
+                .await?;
+            println!(
+                "consumer \"{}\" on topic \"{}\" and partition \"{}\" reset to offset {next}",
+                self.consumer, offsets.topic, offsets.partition
+            );
+        }
+        Ok(())
+    }
+
+    /// offset of next record consumer reads on partition
+    async fn next_offset(&self, fluvio: &Fluvio, offsets: &PartitionOffsets) -> Result<i64> {
+        if self.to.to_earliest {
+            Ok(offsets.earliest)
+        } else if self.to.to_latest {
+            Ok(offsets.high_watermark)
+        } else if let Some(offset) = self.to.to_offset {
+            if offset < offsets.earliest || offset > offsets.high_watermark {
+                return Err(anyhow!(
+                    "offset {offset} is out of range {}..={} of partition {}",
+                    offsets.earliest,
+                    offsets.high_watermark,
+                    offsets.partition
+                ));
+            }
+            Ok(offset)
+        } else if let Some(timestamp) = self.to.to_timestamp {
+            let millis = SystemTime::from(timestamp)
+                .duration_since(UNIX_EPOCH)?
+                .as_millis() as i64;
+            offset_at_timestamp(fluvio, offsets, millis).await
+        } else {
+            unreachable!("one reset target is required")
+        }
+    }
+}
+
+/// offset of first record with timestamp at or after `millis`, high watermark if there is none
+async fn offset_at_timestamp(
+    fluvio: &Fluvio,
+    offsets: &PartitionOffsets,
+    millis: i64,
+) -> Result<i64> {
+    if offsets.records() == 0 {
+        return Ok(offsets.high_watermark);
+    }
+    let mut builder = ConsumerConfigExt::builder();
+    builder
+        .topic(&offsets.topic)
+        .partition(offsets.partition)
+        .offset_start(Offset::absolute(offsets.earliest)?)
+        .disable_continuous(true);
+    let mut stream = fluvio.consumer_with_config(builder.build()?).await?;
+
+    while let Some(record) = stream.next().await {
+        let record = record?;
+        if record.timestamp() >= millis {
+            return Ok(record.offset());
+        }
+        if record.offset() + 1 >= offsets.high_watermark {
+            break;
+        }
+    }
+    Ok(offsets.high_watermark)
+}
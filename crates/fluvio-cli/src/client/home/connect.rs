@@ -3,6 +3,7 @@ use std::io::BufReader;
 use std::sync::Arc;
 use anyhow::{anyhow, Result};
 use clap::Parser;
+use fluvio::Capability;
 use fluvio_extension_common::target::ClusterTarget;
 use fluvio_extension_common::Terminal;
 use fluvio_sc_schema::mirror::{MirrorSpec, MirrorType};
@@ -21,10 +22,9 @@ impl ConnectOpt {
         cluster_target: ClusterTarget,
     ) -> Result<()> {
         let fluvio_config = cluster_target.load()?;
-        let admin = fluvio::Fluvio::connect_with_config(&fluvio_config)
-            .await?
-            .admin()
-            .await;
+        let fluvio = fluvio::Fluvio::connect_with_config(&fluvio_config).await?;
+        fluvio.check_capability(Capability::Mirroring)?;
+        let admin = fluvio.admin().await;
 
         let reader = BufReader::new(File::open(self.file)?);
         let remote_metadata: RemoteMetadataExport = serde_json::from_reader(reader)
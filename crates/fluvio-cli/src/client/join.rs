@@ -0,0 +1,350 @@
+//!
+//! # Join CLI
+//!
+//! Joins records of two topics having same key, with timestamps within a window of each other.
+//! Joined records are printed or produced to a third topic, for simple enrichment pipelines.
+//! Records of each topic are buffered in the CLI for the length of the window.
+//!
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use clap::Parser;
+use anyhow::Result;
+use futures::{Stream, StreamExt};
+use serde_json::{Value, json};
+use tracing::debug;
+
+use fluvio::{Fluvio, Offset};
+use fluvio::consumer::{ConsumerConfigExt, ConsumerRecord};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::NO_TIMESTAMP;
+
+use crate::client::cmd::ClientCmd;
+use crate::common::output::Terminal;
+use crate::CliError;
+
+/// Join records of two topics by key within a time window
+#[derive(Debug, Parser)]
+pub struct JoinOpt {
+    /// Topic of left side of join
+    #[arg(value_name = "LEFT")]
+    left: String,
+
+    /// Topic of right side of join
+    #[arg(value_name = "RIGHT")]
+    right: String,
+
+    /// Max difference between timestamps of joined records, ex: 30s
+    #[arg(long, value_name = "DURATION", default_value = "1m")]
+    window: humantime::Duration,
+
+    /// JSON pointer of key in values of left topic, ex: /user_id. Record key is used by default
+    #[arg(long, value_name = "POINTER")]
+    left_key: Option<String>,
+
+    /// JSON pointer of key in values of right topic, ex: /id. Record key is used by default
+    #[arg(long, value_name = "POINTER")]
+    right_key: Option<String>,
+
+    /// Produce joined records to this topic instead of printing them
+    #[arg(long, value_name = "TOPIC")]
+    output_topic: Option<String>,
+
+    /// Join records from the beginning of topics, instead of new records only
+    #[arg(short = 'B', long)]
+    from_beginning: bool,
+
+    /// Stop when end of both topics is reached
+    #[arg(short = 'd', long)]
+    disable_continuous: bool,
+}
+
+#[async_trait]
+impl ClientCmd for JoinOpt {
+    async fn process_client<O: Terminal + Debug + Send + Sync>(
+        self,
+        out: Arc<O>,
+        fluvio: &Fluvio,
+    ) -> Result<()> {
+        let left = self
+            .stream(fluvio, &self.left)
+            .await?
+            .map(|record| (Side::Left, record));
+        let right = self
+            .stream(fluvio, &self.right)
+            .await?
+            .map(|record| (Side::Right, record));
+        let mut records = futures::stream::select(left, right);
+
+        let producer = match &self.output_topic {
+            Some(topic) => Some(fluvio.topic_producer(topic.clone()).await?),
+            None => None,
+        };
+
+        let mut join = WindowJoin::new(*self.window);
+        let mut joined_count = 0;
+        while let Some((side, record)) = records.next().await {
+            let record = record?;
+            let key_path = match side {
+                Side::Left => self.left_key.as_deref(),
+                Side::Right => self.right_key.as_deref(),
+            };
+            let Some(key) = record_key(&record, key_path) else {
+                debug!(
+                    ?side,
+                    offset = record.offset(),
+                    "record without key, skipped"
+                );
+                continue;
+            };
+            let timestamp = match record.timestamp() {
+                NO_TIMESTAMP => now_millis(),
+                timestamp => timestamp,
+            };
+
+            for joined in join.push(side, key, timestamp, record_value(record.value())) {
+                joined_count += 1;
+                match &producer {
+                    Some(producer) => {
+                        producer
+                            .send(joined.key.clone(), joined.to_json().to_string())
+                            .await?;
+                    }
+                    None => out.println(&joined.to_json().to_string()),
+                }
+            }
+        }
+
+        if let Some(producer) = producer {
+            producer.flush().await?;
+        }
+        debug!(joined_count, "join finished");
+        Ok(())
+    }
+}
+
+impl JoinOpt {
+    async fn stream(
+        &self,
+        fluvio: &Fluvio,
+        topic: &str,
+    ) -> Result<impl Stream<Item = Result<ConsumerRecord, ErrorCode>> + use<>> {
+        let offset = if self.from_beginning {
+            Offset::beginning()
+        } else {
+            Offset::end()
+        };
+        let mut builder = ConsumerConfigExt::builder();
+        builder
+            .topic(topic)
+            .offset_start(offset)
+            .disable_continuous(self.disable_continuous);
+        let stream = fluvio
+            .consumer_with_config(builder.build()?)
+            .await
+            .map_err(|err| {
+                CliError::Other(format!("unable to consume topic \"{topic}\": {err}"))
+            })?;
+        Ok(stream)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Side {
+    Left,
+    Right,
+}
+
+/// key from JSON value at pointer, or record key
+fn record_key(record: &ConsumerRecord, pointer: Option<&str>) -> Option<String> {
+    match pointer {
+        Some(pointer) => {
+            let value: Value = serde_json::from_slice(record.value()).ok()?;
+            match value.pointer(pointer)? {
+                Value::String(key) => Some(key.clone()),
+                Value::Null => None,
+                key => Some(key.to_string()),
+            }
+        }
+        None => record
+            .key()
+            .map(|key| String::from_utf8_lossy(key).into_owned()),
+    }
+}
+
+/// JSON value, or string if value is not JSON
+fn record_value(value: &[u8]) -> Value {
+    serde_json::from_slice(value)
+        .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(value).into_owned()))
+}
+
+fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |now| now.as_millis() as i64)
+}
+
+#[derive(Debug, Clone)]
+struct BufferedRecord {
+    timestamp: i64,
+    value: Value,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct JoinedRecord {
+    key: String,
+    left: Value,
+    right: Value,
+}
+
+impl JoinedRecord {
+    fn to_json(&self) -> Value {
+        json!({ "key": self.key, "left": self.left, "right": self.right })
+    }
+}
+
+/// Inner join of two streams, records are kept until they are older than latest timestamp by window
+#[derive(Debug)]
+struct WindowJoin {
+    window_ms: i64,
+    left: HashMap<String, VecDeque<BufferedRecord>>,
+    right: HashMap<String, VecDeque<BufferedRecord>>,
+    /// buffered records of both sides in arrival order, for eviction
+    arrivals: VecDeque<(Side, String, i64)>,
+    latest_timestamp: i64,
+}
+
+impl WindowJoin {
+    fn new(window: Duration) -> Self {
+        Self {
+            window_ms: window.as_millis() as i64,
+            left: HashMap::new(),
+            right: HashMap::new(),
+            arrivals: VecDeque::new(),
+            latest_timestamp: i64::MIN,
+        }
+    }
+
+    /// add record, returning records joined with buffered records of other side
+    fn push(&mut self, side: Side, key: String, timestamp: i64, value: Value) -> Vec<JoinedRecord> {
+        self.latest_timestamp = self.latest_timestamp.max(timestamp);
+        self.evict();
+
+        let (own, other) = match side {
+            Side::Left => (&mut self.left, &self.right),
+            Side::Right => (&mut self.right, &self.left),
+        };
+        let joined = other
+            .get(&key)
+            .into_iter()
+            .flatten()
+            .filter(|buffered| (buffered.timestamp - timestamp).abs() <= self.window_ms)
+            .map(|buffered| {
+                let (left, right) = match side {
+                    Side::Left => (value.clone(), buffered.value.clone()),
+                    Side::Right => (buffered.value.clone(), value.clone()),
+                };
+                JoinedRecord {
+                    key: key.clone(),
+                    left,
+                    right,
+                }
+            })
+            .collect();
+
+        own.entry(key.clone())
+            .or_default()
+            .push_back(BufferedRecord { timestamp, value });
+        self.arrivals.push_back((side, key, timestamp));
+        joined
+    }
+
+    /// drop records which can't join with records newer than latest timestamp
+    fn evict(&mut self) {
+        let expired = self.latest_timestamp.saturating_sub(self.window_ms);
+        while let Some((side, key, timestamp)) = self.arrivals.front() {
+            if *timestamp >= expired {
+                break;
+            }
+            let buffers = match side {
+                Side::Left => &mut self.left,
+                Side::Right => &mut self.right,
+            };
+            if let Some(records) = buffers.get_mut(key) {
+                records.retain(|record| record.timestamp >= expired);
+                if records.is_empty() {
+                    buffers.remove(key);
+                }
+            }
+            self.arrivals.pop_front();
+        }
+    }
+
+    #[cfg(test)]
+    fn buffered(&self) -> usize {
+        self.left
+            .values()
+            .chain(self.right.values())
+            .map(VecDeque::len)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_window_join() {
+        let mut join = WindowJoin::new(Duration::from_secs(10));
+
+        assert!(
+            join.push(Side::Left, "u1".to_owned(), 1_000, json!({"order": 1}))
+                .is_empty()
+        );
+        assert!(
+            join.push(Side::Right, "u2".to_owned(), 2_000, json!({"name": "bob"}))
+                .is_empty()
+        );
+
+        let joined = join.push(
+            Side::Right,
+            "u1".to_owned(),
+            5_000,
+            json!({"name": "alice"}),
+        );
+        assert_eq!(
+            joined,
+            vec![JoinedRecord {
+                key: "u1".to_owned(),
+                left: json!({"order": 1}),
+                right: json!({"name": "alice"}),
+            }]
+        );
+        assert_eq!(
+            joined[0].to_json(),
+            json!({"key": "u1", "left": {"order": 1}, "right": {"name": "alice"}})
+        );
+
+        // records at 1s and 2s are out of window and evicted
+        let joined = join.push(Side::Left, "u1".to_owned(), 14_000, json!({"order": 2}));
+        assert_eq!(joined.len(), 1);
+        assert_eq!(joined[0].right, json!({"name": "alice"}));
+        assert_eq!(join.buffered(), 2);
+
+        // records older than window are evicted
+        join.push(Side::Left, "u3".to_owned(), 30_000, json!({"order": 3}));
+        assert_eq!(join.buffered(), 1);
+    }
+
+    #[test]
+    fn test_record_value() {
+        assert_eq!(record_value(br#"{"a":1}"#), json!({"a": 1}));
+        assert_eq!(record_value(b"plain"), json!("plain"));
+    }
+}
@@ -0,0 +1,280 @@
+//!
+//! # Consumer Lag CLI
+//!
+//! Tabulates lag of consumers, number of committed records after their offset, per partition.
+//! With `--threshold` the command fails when any lag exceeds it, so it can be used in alert scripts.
+//! Lag that cannot be computed, because offsets of the topic are not available, also fails it.
+//!
+
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use clap::Parser;
+use anyhow::Result;
+use futures::future::join_all;
+use serde::Serialize;
+use tracing::debug;
+
+use fluvio::{Fluvio, PartitionId};
+use fluvio::consumer::{ConsumerOffset, PartitionOffsets};
+use fluvio_future::timer::sleep;
+
+use crate::client::cmd::ClientCmd;
+use crate::common::output::Terminal;
+use crate::common::OutputFormat;
+use crate::CliError;
+
+/// Report lag of consumers per partition
+#[derive(Debug, Parser)]
+pub struct LagOpt {
+    /// Only report lag of this consumer
+    #[arg(short, long, value_name = "CONSUMER")]
+    consumer: Option<String>,
+
+    /// Only report lag on this topic
+    #[arg(short, long, value_name = "TOPIC")]
+    topic: Option<String>,
+
+    /// Exit with error if lag of any consumer exceeds this number of records, or is unknown.
+    /// With `--watch`, keep watching until it is exceeded
+    #[arg(long, value_name = "RECORDS")]
+    threshold: Option<u64>,
+
+    /// Keep reporting lag periodically
+    #[arg(short, long)]
+    watch: bool,
+
+    /// Period of reports with `--watch`, ex: 10s
+    #[arg(
+        long,
+        value_name = "DURATION",
+        default_value = "5s",
+        requires = "watch"
+    )]
+    interval: humantime::Duration,
+
+    #[clap(flatten)]
+    output: OutputFormat,
+}
+
+#[async_trait]
+impl ClientCmd for LagOpt {
+    async fn process_client<O: Terminal + Debug + Send + Sync>(
+        self,
+        out: Arc<O>,
+        fluvio: &Fluvio,
+    ) -> Result<()> {
+        loop {
+            let (lags, errors) = self.consumer_lags(fluvio).await?;
+            let max_lag = lags.iter().filter_map(|lag| lag.lag).max();
+            let unknown = lags.iter().filter(|lag| lag.lag.is_none()).count();
+            out.render_list(
+                &display::ConsumerLags { lags, errors },
+                self.output.format.clone(),
+            )?;
+
+            if let Some(threshold) = self.threshold {
+                if unknown > 0 {
+                    return Err(CliError::Other(format!(
+                        "lag of {unknown} consumer partition(s) is unknown"
+                    ))
+                    .into());
+                }
+                if let Some(max_lag) = max_lag
+                    && max_lag > threshold
+                {
+                    return Err(CliError::Other(format!(
+                        "consumer lag {max_lag} exceeds threshold {threshold}"
+                    ))
+                    .into());
+                }
+            }
+            if !self.watch {
+                return Ok(());
+            }
+            sleep(*self.interval).await;
+            out.println("");
+        }
+    }
+}
+
+impl LagOpt {
+    /// lags of consumers, and errors of topics which offsets could not be fetched
+    async fn consumer_lags(&self, fluvio: &Fluvio) -> Result<(Vec<ConsumerLag>, Vec<String>)> {
+        let consumers: Vec<ConsumerOffset> = fluvio
+            .consumer_offsets()
+            .await?
+            .into_iter()
+            .filter(|consumer| {
+                self.consumer
+                    .as_ref()
+                    .is_none_or(|id| *id == consumer.consumer_id)
+                    && self
+                        .topic
+                        .as_ref()
+                        .is_none_or(|topic| *topic == consumer.topic)
+            })
+            .collect();
+
+        let topics: BTreeSet<&str> = consumers.iter().map(|c| c.topic.as_str()).collect();
+        let results = join_all(topics.into_iter().map(|topic| async move {
+            fluvio
+                .topic_offsets(topic)
+                .await
+                .inspect_err(|err| debug!(topic, %err, "offsets not available"))
+                .map_err(|err| format!("offsets of topic \"{topic}\" not available: {err}"))
+        }))
+        .await;
+
+        let mut offsets = vec![];
+        let mut errors = vec![];
+        for result in results {
+            match result {
+                Ok(topic_offsets) => offsets.extend(topic_offsets),
+                Err(err) => errors.push(err),
+            }
+        }
+
+        Ok((lag_of(consumers, offsets), errors))
+    }
+}
+
+/// lag of consumer on partition, unknown if offsets of partition are not available
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub(crate) struct ConsumerLag {
+    pub(crate) consumer_id: String,
+    pub(crate) topic: String,
+    pub(crate) partition: PartitionId,
+    pub(crate) offset: i64,
+    pub(crate) high_watermark: Option<i64>,
+    pub(crate) lag: Option<u64>,
+}
+
+pub(crate) fn lag_of(
+    consumers: Vec<ConsumerOffset>,
+    offsets: Vec<PartitionOffsets>,
+) -> Vec<ConsumerLag> {
+    let offsets: HashMap<(String, PartitionId), PartitionOffsets> = offsets
+        .into_iter()
+        .map(|offsets| ((offsets.topic.clone(), offsets.partition), offsets))
+        .collect();
+    let mut lags: Vec<ConsumerLag> = consumers
+        .into_iter()
+        .map(|consumer| {
+            let partition_offsets = offsets.get(&(consumer.topic.clone(), consumer.partition));
+            ConsumerLag {
+                high_watermark: partition_offsets.map(|o| o.high_watermark),
+                lag: partition_offsets.map(|o| o.lag(consumer.offset).unsigned_abs()),
+                consumer_id: consumer.consumer_id,
+                topic: consumer.topic,
+                partition: consumer.partition,
+                offset: consumer.offset,
+            }
+        })
+        .collect();
+    lags.sort();
+    lags
+}
+
+mod display {
+
+    use comfy_table::Row;
+    use serde::Serialize;
+
+    use crate::common::output::TableOutputHandler;
+
+    use super::ConsumerLag;
+
+    #[derive(Serialize)]
+    #[serde(transparent)]
+    pub(crate) struct ConsumerLags {
+        pub lags: Vec<ConsumerLag>,
+        #[serde(skip)]
+        pub errors: Vec<String>,
+    }
+
+    impl TableOutputHandler for ConsumerLags {
+        fn header(&self) -> Row {
+            Row::from([
+                "CONSUMER",
+                "TOPIC",
+                "PARTITION",
+                "OFFSET",
+                "HIGH WATERMARK",
+                "LAG",
+            ])
+        }
+
+        fn errors(&self) -> Vec<String> {
+            self.errors.clone()
+        }
+
+        fn content(&self) -> Vec<Row> {
+            let unknown = || "-".to_owned();
+            self.lags
+                .iter()
+                .map(|lag| {
+                    Row::from([
+                        lag.consumer_id.clone(),
+                        lag.topic.clone(),
+                        lag.partition.to_string(),
+                        lag.offset.to_string(),
+                        lag.high_watermark.map_or_else(unknown, |hw| hw.to_string()),
+                        lag.lag.map_or_else(unknown, |lag| lag.to_string()),
+                    ])
+                })
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    fn consumer(
+        consumer_id: &str,
+        topic: &str,
+        partition: PartitionId,
+        offset: i64,
+    ) -> ConsumerOffset {
+        ConsumerOffset {
+            consumer_id: consumer_id.to_owned(),
+            topic: topic.to_owned(),
+            partition,
+            offset,
+            modified_time: 0,
+        }
+    }
+
+    #[test]
+    fn test_lag_of() {
+        let offsets = vec![PartitionOffsets {
+            topic: "orders".to_owned(),
+            partition: 0,
+            earliest: 0,
+            high_watermark: 100,
+            latest: 102,
+        }];
+        let lags = lag_of(
+            vec![
+                consumer("b", "orders", 0, 99),
+                consumer("a", "orders", 0, 49),
+                consumer("a", "orders", 1, 10),
+            ],
+            offsets,
+        );
+
+        let summary: Vec<_> = lags
+            .iter()
+            .map(|lag| (lag.consumer_id.as_str(), lag.partition, lag.lag))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("a", 0, Some(50)), ("a", 1, None), ("b", 0, Some(0))]
+        );
+    }
+}
@@ -6,13 +6,20 @@ mod partition;
 mod tableformat;
 mod smartmodule;
 mod smartmodule_invocation;
+mod session;
 mod consumer;
+mod lag;
+mod trace;
+mod top;
 mod remote;
 mod home;
+mod join;
 
 pub use metadata::client_metadata;
 pub use cmd::FluvioCmd;
+pub(crate) use cmd::connect;
 pub use tableformat::TableFormatConfig;
+pub(crate) use consume::ConsumeOutputType;
 use cmd::ClientCmd;
 mod metadata {
 
@@ -49,6 +56,10 @@ mod cmd {
     use crate::common::Terminal;
 
     use super::consumer::ConsumerCmd;
+    use super::lag::LagOpt;
+    use super::trace::TraceOpt;
+    use super::top::TopOpt;
+    use super::join::JoinOpt;
     use super::remote::RemoteCmd;
     use super::home::HomeCmd;
     use super::smartmodule::SmartModuleCmd;
@@ -58,6 +69,7 @@ mod cmd {
     use super::partition::PartitionCmd;
     use super::tableformat::TableFormatCmd;
     use super::hub::HubCmd;
+    use super::session::Interrupt;
 
     #[async_trait]
     pub trait ClientCmd: Sized {
@@ -67,13 +79,7 @@ mod cmd {
             out: Arc<O>,
             target: ClusterTarget,
         ) -> Result<()> {
-            let mut fluvio_config = target.load()?;
-            let client_id = match std::env::var("FLUVIO_CLIENT_ID") {
-                Ok(id) => id,
-                Err(_) => "FLUVIO_CLI".to_owned(),
-            };
-            fluvio_config.client_id = Some(client_id);
-            let fluvio = Fluvio::connect_with_config(&fluvio_config).await?;
+            let fluvio = connect(&target).await?;
             self.process_client(out, &fluvio).await?;
             Ok(())
         }
@@ -86,6 +92,18 @@ mod cmd {
         ) -> Result<()>;
     }
 
+    /// connect to target cluster as CLI client.
+    /// Client id is taken from `FLUVIO_CLIENT_ID`, then from profile, defaulting to `FLUVIO_CLI`
+    pub(crate) async fn connect(target: &ClusterTarget) -> Result<Fluvio> {
+        let mut fluvio_config = target.clone().load()?;
+        let client_id = std::env::var("FLUVIO_CLIENT_ID")
+            .ok()
+            .or(fluvio_config.client_id.take())
+            .unwrap_or_else(|| "FLUVIO_CLI".to_owned());
+        fluvio_config.client_id = Some(client_id);
+        Ok(Fluvio::connect_with_config(&fluvio_config).await?)
+    }
+
     // For some reason this doc string is the one that gets used for the top-level help menu.
     // Please don't change it unless you want to update the top-level help menu "about".
     /// Fluvio command-line interface
@@ -145,6 +163,29 @@ mod cmd {
         #[command(subcommand, name = "consumer")]
         Consumer(ConsumerCmd),
 
+        /// Report lag of Consumers per partition
+        #[command(name = "lag")]
+        Lag(LagOpt),
+
+        /// Follow a record across topics it was copied, transformed or mirrored into
+        ///
+        /// Records must be produced with `--trace`, which stamps them with an origin id
+        /// and the list of topics they pass through.
+        #[command(name = "trace")]
+        Trace(TraceOpt),
+
+        /// Live display of topics, partition leaders, throughput per SPU and consumer lag
+        #[command(name = "top")]
+        Top(TopOpt),
+
+        /// Join records of two topics by key within a time window
+        ///
+        /// Joined records are printed as JSON objects with key, left and right values,
+        /// or produced to an output topic. The join runs in the CLI, records are buffered
+        /// in memory for the length of the window.
+        #[command(name = "join")]
+        Join(JoinOpt),
+
         /// Manage and view remote clusters mirrored
         #[command(subcommand, name = "remote")]
         Remote(Box<RemoteCmd>),
@@ -162,39 +203,44 @@ mod cmd {
             target: ClusterTarget,
         ) -> Result<()> {
             match self {
-                Self::Consume(consume) => {
-                    consume.process(out, target).await?;
-                }
-                Self::Produce(produce) => {
-                    produce.process(out, target).await?;
-                }
-                Self::Topic(topic) => {
-                    topic.process(out, target).await?;
-                }
-                Self::Partition(partition) => {
-                    partition.process(out, target).await?;
-                }
-                Self::SmartModule(smartmodule) => {
-                    smartmodule.process(out, target).await?;
-                }
-                Self::TableFormat(tableformat) => {
-                    tableformat.process(out, target).await?;
-                }
-                Self::Hub(hub) => {
-                    hub.process(out, target).await?;
-                }
-                Self::Consumer(consumer) => {
-                    consumer.process(out, target).await?;
-                }
-                Self::Remote(remote) => {
-                    remote.process(out, target).await?;
-                }
-                Self::Home(home) => {
-                    home.process(out, target).await?;
+                // don't use connection to target cluster
+                Self::Hub(hub) => hub.process(out, target).await,
+                Self::Remote(remote) => remote.process(out, target).await,
+                Self::Home(home) => home.process(out, target).await,
+                cmd => {
+                    let fluvio = connect(&target).await?;
+                    cmd.process_client(out, &fluvio, target).await
                 }
             }
+        }
 
-            Ok(())
+        /// Process with already connected Fluvio client, so connection can be reused across commands.
+        /// Commands for remote clusters connect themselves using the target.
+        pub async fn process_client<O: Terminal + Debug + Send + Sync>(
+            self,
+            out: Arc<O>,
+            fluvio: &Fluvio,
+            target: ClusterTarget,
+        ) -> Result<()> {
+            let result = match self {
+                Self::Consume(consume) => consume.process_client(out, fluvio).await,
+                Self::Produce(produce) => produce.process_client(out, fluvio).await,
+                Self::Topic(topic) => topic.process_client(out, fluvio).await,
+                Self::Partition(partition) => partition.process_client(out, fluvio).await,
+                Self::SmartModule(smartmodule) => smartmodule.process_client(out, fluvio).await,
+                Self::TableFormat(tableformat) => tableformat.process_client(out, fluvio).await,
+                Self::Hub(hub) => hub.process(out, target).await,
+                Self::Consumer(consumer) => consumer.process_client(out, fluvio).await,
+                Self::Lag(lag) => lag.process_client(out, fluvio).await,
+                Self::Trace(trace) => trace.process_client(out, fluvio).await,
+                Self::Top(top) => top.process_client(out, fluvio).await,
+                Self::Join(join) => join.process_client(out, fluvio).await,
+                Self::Remote(remote) => remote.process(out, target).await,
+                Self::Home(home) => home.process(out, target).await,
+            };
+            // later Ctrl-C doesn't go to finished command
+            Interrupt::end_session();
+            result
         }
     }
 }
@@ -11,7 +11,7 @@ use fluvio::Fluvio;
 use fluvio::metadata::partition::*;
 use fluvio_sc_schema::objects::ListRequest;
 
-use crate::common::output::Terminal;
+use crate::common::output::{Terminal, set_raw_units};
 use crate::common::OutputFormat;
 
 /// Option for Listing Partition
@@ -22,6 +22,10 @@ pub struct ListPartitionOpt {
     /// Show system partitions only
     #[arg(long, short, required = false)]
     system: bool,
+
+    /// Show sizes in bytes and durations in seconds, instead of human readable units
+    #[arg(long)]
+    raw: bool,
 }
 
 impl ListPartitionOpt {
@@ -31,6 +35,7 @@ impl ListPartitionOpt {
         O: Terminal,
     {
         let output = self.output.format;
+        set_raw_units(self.raw);
         let admin = fluvio.admin().await;
 
         let partitions = admin
@@ -57,7 +62,7 @@ mod display {
 
     //use crate::error::CliError;
     use crate::common::t_println;
-    use crate::common::output::{OutputType, OutputError, Terminal, TableOutputHandler};
+    use crate::common::output::{OutputType, OutputError, Terminal, TableOutputHandler, format_size};
 
     #[derive(Serialize)]
     struct ListSpus(Vec<Metadata<PartitionSpec>>);
@@ -137,7 +142,7 @@ mod display {
                     let printable_size = match status.size {
                         PartitionStatus::SIZE_NOT_SUPPORTED => "NA".to_string(),
                         PartitionStatus::SIZE_ERROR => "ERROR".to_string(),
-                        _ => bytesize::ByteSize::b(status.size as u64).to_string(),
+                        _ => format_size(status.size as u64),
                     };
 
                     Row::from([
@@ -1,4 +1,6 @@
 mod list;
+mod truncate;
+mod replication_status;
 
 pub use cmd::PartitionCmd;
 
@@ -18,6 +20,8 @@ mod cmd {
     use crate::common::FluvioExtensionMetadata;
 
     use super::list::ListPartitionOpt;
+    use super::truncate::TruncatePartitionOpt;
+    use super::replication_status::ReplicationStatusOpt;
 
     #[derive(Debug, Parser)]
     #[command(name = "partition", about = "Partition operations")]
@@ -28,6 +32,20 @@ mod cmd {
             help_template = crate::common::COMMAND_TEMPLATE,
         )]
         List(ListPartitionOpt),
+
+        /// Delete records of a Partition before an offset
+        #[command(
+            name = "truncate",
+            help_template = crate::common::COMMAND_TEMPLATE,
+        )]
+        Truncate(TruncatePartitionOpt),
+
+        /// Show replication progress of Partition replicas, as reported by leaders
+        #[command(
+            name = "replication-status",
+            help_template = crate::common::COMMAND_TEMPLATE,
+        )]
+        ReplicationStatus(ReplicationStatusOpt),
     }
 
     #[async_trait]
@@ -41,6 +59,12 @@ mod cmd {
                 Self::List(list) => {
                     list.process(out, fluvio).await?;
                 }
+                Self::Truncate(truncate) => {
+                    truncate.process(fluvio).await?;
+                }
+                Self::ReplicationStatus(replication_status) => {
+                    replication_status.process(out, fluvio).await?;
+                }
             }
 
             Ok(())
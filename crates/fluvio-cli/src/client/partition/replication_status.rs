@@ -0,0 +1,352 @@
+//!
+//! # Partition Replication Status
+//!
+//! CLI tree and processing to show replication progress of partition replicas.
+//! Controller decisions come from partition status kept by SC, follower lag,
+//! fetch times and sync transitions are reported by partition leader.
+//!
+
+use std::convert::TryInto;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use clap::Parser;
+use anyhow::Result;
+use serde::Serialize;
+use tracing::debug;
+
+use fluvio::{Fluvio, PartitionId, ReplicationStatus};
+use fluvio::metadata::objects::Metadata;
+use fluvio::metadata::partition::*;
+use fluvio_spu_schema::server::replication_status::NO_TIME;
+
+use crate::common::output::{Terminal, set_raw_units};
+use crate::common::OutputFormat;
+
+/// Option for showing replication status of partitions
+#[derive(Debug, Parser)]
+pub struct ReplicationStatusOpt {
+    /// Name of topic, all topics by default
+    #[arg(value_name = "topic")]
+    topic: Option<String>,
+
+    /// Partition of topic, all partitions by default
+    #[arg(short, long, requires = "topic")]
+    partition: Option<PartitionId>,
+
+    /// Show only partitions with followers not in sync
+    #[arg(long)]
+    under_replicated: bool,
+
+    /// Show durations in milliseconds, instead of human readable units
+    #[arg(long)]
+    raw: bool,
+
+    #[clap(flatten)]
+    output: OutputFormat,
+}
+
+/// Replication state of partition, as decided by SC and reported by leader
+#[derive(Debug, Serialize)]
+pub(crate) struct PartitionReplication {
+    topic: String,
+    partition: PartitionId,
+    resolution: String,
+    leader: i32,
+    lrs: u32,
+    replicas: Vec<ReplicaProgress>,
+    /// reason leader status is unavailable, replicas are from SC then
+    error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ReplicaProgress {
+    spu: i32,
+    leader: bool,
+    leo: i64,
+    hw: i64,
+    lag_records: i64,
+    lag_ms: Option<i64>,
+    last_fetch_ms: Option<i64>,
+    in_sync: Option<bool>,
+    /// latest changes of in sync state as (milliseconds since epoch, in sync)
+    transitions: Vec<(i64, bool)>,
+}
+
+impl ReplicationStatusOpt {
+    pub async fn process<O>(self, out: std::sync::Arc<O>, fluvio: &Fluvio) -> Result<()>
+    where
+        O: Terminal,
+    {
+        set_raw_units(self.raw);
+        let admin = fluvio.admin().await;
+        let partitions = admin.all::<PartitionSpec>().await?;
+
+        let mut replications = vec![];
+        for partition in partitions {
+            let Ok(key) = TryInto::<ReplicaKey>::try_into(partition.name.clone()) else {
+                continue;
+            };
+            if self.topic.as_ref().is_some_and(|topic| *topic != key.topic)
+                || self
+                    .partition
+                    .is_some_and(|partition| partition != key.partition)
+            {
+                continue;
+            }
+
+            let leader_status = fluvio
+                .replication_status(key.topic.clone(), key.partition)
+                .await;
+            let replication = PartitionReplication::new(key, partition, leader_status);
+            if self.under_replicated && !replication.under_replicated() {
+                continue;
+            }
+            replications.push(replication);
+        }
+        replications.sort_by(|a, b| (&a.topic, a.partition).cmp(&(&b.topic, b.partition)));
+
+        out.render_list(
+            &display::PartitionReplications(replications),
+            self.output.format,
+        )?;
+        Ok(())
+    }
+}
+
+impl PartitionReplication {
+    fn new(
+        key: ReplicaKey,
+        partition: Metadata<PartitionSpec>,
+        leader_status: Result<ReplicationStatus>,
+    ) -> Self {
+        let (topic, partition_id) = key.split();
+        let spec = &partition.spec;
+        let status = &partition.status;
+
+        let (replicas, error) = match leader_status {
+            Ok(leader_status) => (from_leader(leader_status), None),
+            Err(err) => {
+                debug!(topic, partition_id, %err, "leader replication status unavailable");
+                (from_sc(spec.leader, status), Some(err.to_string()))
+            }
+        };
+
+        Self {
+            topic,
+            partition: partition_id,
+            resolution: format!("{:?}", status.resolution),
+            leader: spec.leader,
+            lrs: status.lrs(),
+            replicas,
+            error,
+        }
+    }
+
+    /// without leader status, SC's count of followers caught up with leader is used
+    fn under_replicated(&self) -> bool {
+        if self.error.is_some() {
+            (self.lrs as usize) < self.replicas.len() - 1
+        } else {
+            self.replicas
+                .iter()
+                .any(|replica| replica.in_sync == Some(false))
+        }
+    }
+}
+
+fn from_leader(status: ReplicationStatus) -> Vec<ReplicaProgress> {
+    let leader = ReplicaProgress {
+        spu: status.leader,
+        leader: true,
+        leo: status.leo,
+        hw: status.hw,
+        lag_records: 0,
+        lag_ms: Some(0),
+        last_fetch_ms: None,
+        in_sync: Some(true),
+        transitions: vec![],
+    };
+    let known_time = |time: i64| (time != NO_TIME).then_some(time);
+    std::iter::once(leader)
+        .chain(status.followers.into_iter().map(|follower| {
+            ReplicaProgress {
+                spu: follower.spu,
+                leader: false,
+                leo: follower.leo,
+                hw: follower.hw,
+                lag_records: follower.lag_records,
+                lag_ms: known_time(follower.lag_ms),
+                last_fetch_ms: known_time(follower.last_fetch_ms),
+                in_sync: Some(follower.in_sync),
+                transitions: follower
+                    .transitions
+                    .into_iter()
+                    .map(|transition| (transition.timestamp, transition.in_sync))
+                    .collect(),
+            }
+        }))
+        .collect()
+}
+
+/// replicas as last reported to SC, without fetch times and sync state
+fn from_sc(leader: i32, status: &PartitionStatus) -> Vec<ReplicaProgress> {
+    let leader = ReplicaProgress {
+        spu: leader,
+        leader: true,
+        leo: status.leader.leo,
+        hw: status.leader.hw,
+        lag_records: 0,
+        lag_ms: None,
+        last_fetch_ms: None,
+        in_sync: None,
+        transitions: vec![],
+    };
+    let leader_leo = leader.leo;
+    std::iter::once(leader)
+        .chain(status.replicas.iter().map(|replica| ReplicaProgress {
+            spu: replica.spu,
+            leader: false,
+            leo: replica.leo,
+            hw: replica.hw,
+            lag_records: (leader_leo - replica.leo.max(0)).max(0),
+            lag_ms: None,
+            last_fetch_ms: None,
+            in_sync: None,
+            transitions: vec![],
+        }))
+        .collect()
+}
+
+/// milliseconds since epoch
+fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_millis() as i64)
+}
+
+mod display {
+
+    use std::time::Duration;
+
+    use comfy_table::Row;
+    use serde::Serialize;
+
+    use crate::common::output::{TableOutputHandler, format_duration};
+
+    use super::{PartitionReplication, now_millis};
+
+    #[derive(Serialize)]
+    pub(crate) struct PartitionReplications(pub Vec<PartitionReplication>);
+
+    impl TableOutputHandler for PartitionReplications {
+        fn header(&self) -> Row {
+            Row::from([
+                "TOPIC",
+                "PARTITION",
+                "SPU",
+                "ROLE",
+                "LEO",
+                "LAG",
+                "LAG TIME",
+                "LAST FETCH",
+                "IN SYNC",
+                "LAST CHANGE",
+                "RESOLUTION",
+            ])
+        }
+
+        fn errors(&self) -> Vec<String> {
+            self.0
+                .iter()
+                .filter_map(|replication| {
+                    replication.error.as_ref().map(|err| {
+                        format!(
+                            "{}/{}: showing replicas reported to SC, {err}",
+                            replication.topic, replication.partition
+                        )
+                    })
+                })
+                .collect()
+        }
+
+        fn content(&self) -> Vec<Row> {
+            let now = now_millis();
+            let millis = |ms: Option<i64>| {
+                ms.map_or_else(
+                    || "-".to_owned(),
+                    |ms| format_duration(Duration::from_millis(ms.max(0) as u64)),
+                )
+            };
+            self.0
+                .iter()
+                .flat_map(|replication| {
+                    replication.replicas.iter().map(move |replica| {
+                        Row::from([
+                            replication.topic.clone(),
+                            replication.partition.to_string(),
+                            replica.spu.to_string(),
+                            if replica.leader { "leader" } else { "follower" }.to_owned(),
+                            replica.leo.to_string(),
+                            replica.lag_records.to_string(),
+                            millis(replica.lag_ms),
+                            millis(replica.last_fetch_ms),
+                            replica
+                                .in_sync
+                                .map_or_else(|| "-".to_owned(), |in_sync| in_sync.to_string()),
+                            replica.transitions.last().map_or_else(
+                                || "-".to_owned(),
+                                |(timestamp, in_sync)| {
+                                    format!(
+                                        "{} {} ago",
+                                        if *in_sync { "joined" } else { "left" },
+                                        millis(Some(now - timestamp))
+                                    )
+                                },
+                            ),
+                            replication.resolution.clone(),
+                        ])
+                    })
+                })
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use fluvio::FollowerStatus;
+    use fluvio_spu_schema::server::replication_status::SyncTransition;
+
+    use super::*;
+
+    #[test]
+    fn test_replicas_from_leader() {
+        let replicas = from_leader(ReplicationStatus {
+            leader: 5001,
+            leader_epoch: 1,
+            leo: 100,
+            hw: 80,
+            in_sync_replica: 2,
+            followers: vec![FollowerStatus {
+                spu: 5002,
+                leo: 60,
+                hw: 60,
+                lag_records: 40,
+                lag_ms: 3_000,
+                last_fetch_ms: NO_TIME,
+                in_sync: false,
+                transitions: vec![SyncTransition {
+                    timestamp: 1_000,
+                    in_sync: false,
+                }],
+            }],
+        });
+        assert_eq!(replicas.len(), 2);
+        assert!(replicas[0].leader);
+        assert_eq!(replicas[1].lag_ms, Some(3_000));
+        assert_eq!(replicas[1].last_fetch_ms, None);
+        assert_eq!(replicas[1].in_sync, Some(false));
+        assert_eq!(replicas[1].transitions, vec![(1_000, false)]);
+    }
+}
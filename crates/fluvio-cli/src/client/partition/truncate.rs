@@ -0,0 +1,64 @@
+//!
+//! # Truncate Partition
+//!
+//! CLI tree to delete records of partition before an offset
+//!
+
+use clap::Parser;
+use anyhow::Result;
+use tracing::debug;
+
+use fluvio::{Fluvio, PartitionId};
+
+/// Option for deleting records of partition before an offset.
+/// Records are deleted on leader and followers, and can't be recovered
+#[derive(Debug, Parser)]
+pub struct TruncatePartitionOpt {
+    /// Name of topic
+    #[arg(value_name = "topic")]
+    topic: String,
+
+    /// Partition of topic
+    #[arg(short, long, default_value = "0")]
+    partition: PartitionId,
+
+    /// Delete records with offset lower than this, must not be above high watermark
+    #[arg(long, value_name = "offset")]
+    before_offset: i64,
+
+    /// Skip deletion confirmation
+    #[arg(short, long, required = false)]
+    force: bool,
+}
+
+impl TruncatePartitionOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        if !self.force && !self.user_confirms()? {
+            println!("Aborted");
+            return Ok(());
+        }
+
+        debug!(topic = %self.topic, partition = self.partition, before_offset = self.before_offset, "truncating partition");
+        let log_start = fluvio
+            .delete_records_before(&self.topic, self.partition, self.before_offset)
+            .await?;
+        println!(
+            "records of partition \"{}-{}\" before offset {} deleted, first offset is {log_start}",
+            self.topic, self.partition, self.before_offset
+        );
+        Ok(())
+    }
+
+    fn user_confirms(&self) -> Result<bool> {
+        println!(
+            "You are trying to permanently delete records of partition '{}-{}' before offset {}.\
+             \nAre you sure you want to proceed? (y/n)",
+            self.topic, self.partition, self.before_offset
+        );
+
+        let mut ans = String::new();
+        std::io::stdin().read_line(&mut ans)?;
+        let ans = ans.trim_end().to_lowercase();
+        Ok(matches!(ans.as_str(), "y" | "yes"))
+    }
+}
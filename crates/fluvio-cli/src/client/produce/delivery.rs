@@ -0,0 +1,206 @@
+//!
+//! # Delivery tracking of produced lines
+//!
+//! Bounds number of records waiting for delivery confirmation, so reading input pauses
+//! when cluster can't keep up, and summarizes delivery once input is exhausted.
+//!
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use anyhow::Result;
+
+use fluvio::{ProduceOutput, RecordMetadata};
+
+use crate::client::session::SessionSummary;
+
+/// line of input being produced
+#[derive(Debug)]
+pub(crate) struct PendingLine {
+    /// line number in input, starting from 1
+    pub number: u64,
+    pub value: String,
+    /// number of times line was resent
+    pub attempts: u32,
+}
+
+impl PendingLine {
+    pub(crate) fn new(number: u64, value: String) -> Self {
+        Self {
+            number,
+            value,
+            attempts: 0,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct DeliverySummary {
+    pub sent: u64,
+    pub failed: u64,
+    pub retried: u64,
+    pub first_failed_line: Option<u64>,
+}
+
+impl fmt::Display for DeliverySummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "sent: {}, failed: {}, retried: {}",
+            self.sent, self.failed, self.retried
+        )?;
+        if let Some(line) = self.first_failed_line {
+            write!(f, ", first failed line: {line}")?;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) struct DeliveryTracker {
+    in_flight: VecDeque<(PendingLine, ProduceOutput)>,
+    max_in_flight: usize,
+    summary: DeliverySummary,
+    session: SessionSummary,
+    last_error: Option<String>,
+    error_file: Option<BufWriter<File>>,
+}
+
+impl DeliveryTracker {
+    pub(crate) fn new(max_in_flight: usize, error_file: Option<&Path>) -> Result<Self> {
+        let error_file = match error_file {
+            Some(path) => Some(BufWriter::new(File::create(path)?)),
+            None => None,
+        };
+        Ok(Self {
+            in_flight: VecDeque::new(),
+            max_in_flight: max_in_flight.max(1),
+            summary: DeliverySummary::default(),
+            session: SessionSummary::default(),
+            last_error: None,
+            error_file,
+        })
+    }
+
+    /// true if oldest record must be confirmed before sending more
+    pub(crate) fn is_full(&self) -> bool {
+        self.in_flight.len() >= self.max_in_flight
+    }
+
+    pub(crate) fn push(&mut self, line: PendingLine, output: ProduceOutput) {
+        self.in_flight.push_back((line, output));
+    }
+
+    /// oldest record waiting for confirmation
+    pub(crate) fn pop(&mut self) -> Option<(PendingLine, ProduceOutput)> {
+        self.in_flight.pop_front()
+    }
+
+    /// record line sent, with metadata of record if delivery was confirmed
+    pub(crate) fn record_sent(&mut self, line: &PendingLine, metadata: Option<RecordMetadata>) {
+        self.summary.sent += 1;
+        self.session.record(
+            line.value.len(),
+            metadata.map(|metadata| (metadata.partition_id(), metadata.offset())),
+        );
+    }
+
+    pub(crate) fn record_retry(&mut self) {
+        self.summary.retried += 1;
+    }
+
+    /// record line which can't be delivered, writing it to error file if any
+    pub(crate) fn record_failed(
+        &mut self,
+        line: PendingLine,
+        error: impl fmt::Display,
+    ) -> Result<()> {
+        self.summary.failed += 1;
+        self.summary.first_failed_line.get_or_insert(line.number);
+        self.last_error = Some(format!("line {}: {error}", line.number));
+        if let Some(file) = &mut self.error_file {
+            writeln!(file, "{}", line.value)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn summary(&self) -> &DeliverySummary {
+        &self.summary
+    }
+
+    /// records sent so far, with their size and offsets
+    pub(crate) fn session(&self) -> &SessionSummary {
+        &self.session
+    }
+
+    /// description of last failure
+    pub(crate) fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+
+    /// flush error file and return summary, all records must be confirmed
+    pub(crate) fn finish(mut self) -> Result<DeliverySummary> {
+        debug_assert!(self.in_flight.is_empty());
+        if let Some(file) = &mut self.error_file {
+            file.flush()?;
+        }
+        Ok(self.summary)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::io::Read;
+
+    use super::*;
+
+    #[test]
+    fn test_failed_lines() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("failed-lines.txt");
+        let mut tracker = DeliveryTracker::new(0, Some(&path)).expect("tracker");
+        assert!(!tracker.is_full());
+
+        tracker.record_sent(&PendingLine::new(1, "a".to_owned()), None);
+        tracker.record_retry();
+        tracker
+            .record_failed(PendingLine::new(3, "c".to_owned()), "timeout")
+            .expect("failed");
+        tracker.record_sent(&PendingLine::new(4, "d".to_owned()), None);
+        tracker
+            .record_failed(PendingLine::new(5, "e".to_owned()), "offline")
+            .expect("failed");
+        assert_eq!(tracker.last_error(), Some("line 5: offline"));
+        assert!(
+            tracker
+                .session()
+                .to_string()
+                .starts_with("2 records, 2 B in ")
+        );
+
+        let summary = tracker.finish().expect("finish");
+        assert_eq!(
+            summary,
+            DeliverySummary {
+                sent: 2,
+                failed: 2,
+                retried: 1,
+                first_failed_line: Some(3),
+            }
+        );
+        assert_eq!(
+            summary.to_string(),
+            "sent: 2, failed: 2, retried: 1, first failed line: 3"
+        );
+
+        let mut failed = String::new();
+        File::open(&path)
+            .expect("open")
+            .read_to_string(&mut failed)
+            .expect("read");
+        assert_eq!(failed, "c\ne\n");
+    }
+}
@@ -1,23 +1,26 @@
+mod delivery;
+mod preview;
+#[cfg(feature = "producer-parquet")]
+mod parquet;
+
 pub use cmd::ProduceOpt;
 
 mod cmd {
     use std::sync::Arc;
-    use std::io::{BufReader, BufRead};
+    use std::io::{BufReader, BufRead, IsTerminal};
     use std::collections::BTreeMap;
     use std::fmt::Debug;
     use std::time::Duration;
     #[cfg(feature = "producer-file-io")]
     use std::fs::File;
-    #[cfg(feature = "producer-file-io")]
     use std::path::PathBuf;
 
     use async_trait::async_trait;
     use fluvio_sc_schema::partition::PartitionMirrorConfig;
     use fluvio_sc_schema::topic::{MirrorConfig, PartitionMap, ReplicaSpec, TopicSpec};
-    #[cfg(feature = "producer-file-io")]
-    use futures::future::join_all;
     use clap::Parser;
-    use tracing::{error, warn};
+    use futures::{select, FutureExt};
+    use tracing::{debug, error, warn};
     use humantime::parse_duration;
     use anyhow::{bail, Result};
 
@@ -26,6 +29,7 @@ mod cmd {
         ProduceOutput, DeliverySemantic, SmartModuleContextData, Isolation, SmartModuleInvocation,
     };
     use fluvio_extension_common::Terminal;
+    use fluvio_future::timer::sleep;
     use fluvio_types::{print_cli_ok, PartitionId};
 
     #[cfg(feature = "producer-file-io")]
@@ -39,10 +43,17 @@ mod cmd {
     use crate::common::FluvioExtensionMetadata;
     use crate::monitoring::init_monitoring;
     use crate::util::{parse_isolation, parse_key_val};
-    use crate::client::smartmodule_invocation::{create_smartmodule, create_smartmodule_list};
+    use crate::client::smartmodule_invocation::{
+        create_smartmodule, create_smartmodule_list, smartmodule_params,
+    };
     #[cfg(feature = "producer-file-io")]
     use crate::client::smartmodule_invocation::create_smartmodule_from_path;
     use crate::CliError;
+    use crate::client::session::Interrupt;
+    use super::delivery::{DeliveryTracker, PendingLine};
+    use super::preview::{DryRunReport, PREVIEW_HEADER};
+    #[cfg(feature = "producer-parquet")]
+    use super::parquet::{parquet_rows, row_key};
     use fluvio_smartengine::transformation::TransformationConfig;
 
     // -----------------------------------
@@ -56,6 +67,8 @@ mod cmd {
     ///
     /// If a file is given with '--file', the file is sent as one entire record.
     ///
+    /// If a Parquet file is given with '--parquet', each row is sent as one JSON record.
+    ///
     /// If '--key-separator' is used, records are sent as key/value pairs, and
     /// the keys are used to determine which partition the records are sent to.
     #[derive(Debug, Parser)]
@@ -97,6 +110,17 @@ mod cmd {
         #[arg(short, long, groups = ["TestFile"])]
         pub file: Option<PathBuf>,
 
+        #[cfg(feature = "producer-parquet")]
+        /// Path to a Parquet file to produce to the topic.
+        /// Each row is sent as one record with the row as JSON object
+        #[arg(long, value_name = "path", conflicts_with_all = ["file", "raw"])]
+        pub parquet: Option<PathBuf>,
+
+        #[cfg(feature = "producer-parquet")]
+        /// Sends key/value records with value of this column of Parquet rows as key
+        #[arg(long, value_name = "column", requires = "parquet", group = "RecordKey")]
+        pub key_column: Option<String>,
+
         /// Time to wait before sending
         /// Ex: '150ms', '20s'
         #[arg(long, value_parser=parse_duration)]
@@ -113,7 +137,7 @@ mod cmd {
         /// Isolation level that producer must respect.
         /// Supported values: read_committed (ReadCommitted) - wait for records to be committed before response,
         /// read_uncommitted (ReadUncommitted) - just wait for leader to accept records.
-        #[arg(long, value_parser=parse_isolation)]
+        #[arg(long, value_parser=parse_isolation, env = "FLUVIO_ISOLATION")]
         pub isolation: Option<Isolation>,
 
         /// Delivery guarantees that producer must respect. Supported values:
@@ -159,6 +183,12 @@ mod cmd {
         )]
         pub params: Option<Vec<(String, String)>>,
 
+        /// (Optional) Path to a YAML file with extra input parameters passed to the smartmodule.
+        /// Values given with `--params` take precedence
+        /// Eg. fluvio produce topic-name --smartmodule my_filter --params-file params.yaml
+        #[arg(long, requires = "smartmodule_group", value_name = "PATH")]
+        pub params_file: Option<PathBuf>,
+
         #[cfg(feature = "producer-file-io")]
         /// (Optional) Path to a file with transformation specification.
         #[arg(
@@ -181,6 +211,55 @@ mod cmd {
         /// Remote cluster to consume from
         #[arg(short = 'm', long, conflicts_with = "partition")]
         pub mirror: Option<String>,
+
+        /// Max number of records waiting for delivery confirmation.
+        /// Reading input pauses when reached
+        #[arg(long, value_name = "integer", default_value_t = 1000, value_parser = clap::value_parser!(u64).range(1..))]
+        pub max_in_flight: u64,
+
+        /// Number of times a record failed to be delivered is sent again.
+        /// Resent records may be written after records that followed them in input
+        #[arg(long, value_name = "integer", default_value_t = 0)]
+        pub retries: u32,
+
+        /// Time to wait before sending failed record again, grows with each attempt
+        /// Ex: '150ms', '2s'
+        #[arg(long, value_parser=parse_duration, default_value = "100ms")]
+        pub retry_backoff: Duration,
+
+        /// Write lines which failed to be delivered to this file, so they can be produced again
+        #[arg(long, value_name = "path")]
+        pub error_file: Option<PathBuf>,
+
+        /// Print delivery summary once input is produced.
+        /// By default it's only printed when stderr is a terminal
+        #[arg(long)]
+        pub summary: bool,
+
+        /// Print partition, key and size of records which would be produced, after key extraction
+        /// and SmartModule transformations, without sending them
+        #[arg(long)]
+        pub dry_run: bool,
+
+        /// Stamp records with a trace origin id, so they can be followed
+        /// across topics with `fluvio trace`
+        #[arg(long)]
+        pub trace: bool,
+    }
+
+    /// read lines on separate thread, so waiting for input can be interrupted
+    fn read_lines(
+        lines: impl Iterator<Item = String> + Send + 'static,
+    ) -> async_channel::Receiver<String> {
+        let (sender, receiver) = async_channel::bounded(1);
+        std::thread::spawn(move || {
+            for line in lines {
+                if sender.send_blocking(line).is_err() {
+                    break;
+                }
+            }
+        });
+        receiver
     }
 
     fn validate_key_separator(separator: &str) -> std::result::Result<String, String> {
@@ -220,15 +299,17 @@ mod cmd {
             if let Some(isolation) = self.isolation {
                 config_builder.isolation(isolation);
             }
+            // Trace
+            if self.trace {
+                config_builder.trace(true);
+            }
             // Delivery Semantic
             if self.delivery_semantic == DeliverySemantic::AtMostOnce && self.isolation.is_some() {
                 warn!("Isolation is ignored for AtMostOnce delivery semantic");
             }
 
-            let initial_param = match &self.params {
-                None => BTreeMap::default(),
-                Some(params) => params.clone().into_iter().collect(),
-            };
+            let initial_param =
+                smartmodule_params(self.params_file.as_deref(), self.params.as_deref())?;
 
             let config_builder =
                 config_builder.smartmodules(self.smartmodule_invocations(initial_param)?);
@@ -345,6 +426,16 @@ mod cmd {
 
             let data: RecordData = buffer.into();
 
+            if self.dry_run {
+                let mut report = DryRunReport::default();
+                println!("{PREVIEW_HEADER}");
+                for row in report.add_line(1, &producer.preview(key, data).await?) {
+                    println!("{row}");
+                }
+                eprintln!("{report}");
+                return Ok(());
+            }
+
             let produce_output = producer.send(key, data).await?;
 
             if self.delivery_semantic != DeliverySemantic::AtMostOnce {
@@ -365,61 +456,221 @@ mod cmd {
         }
 
         async fn produce_lines(&self, producer: Arc<TopicProducerPool>) -> Result<()> {
+            #[cfg(feature = "producer-parquet")]
+            if let Some(path) = &self.parquet {
+                // reading stops at first row which can't be decoded, its error fails the command
+                let failed = Arc::new(std::sync::Mutex::new(None));
+                let row_error = failed.clone();
+                let rows = parquet_rows(path)?.map_while(move |row| {
+                    row.map_err(|err| {
+                        if let Ok(mut failed) = row_error.lock() {
+                            *failed = Some(err);
+                        }
+                    })
+                    .ok()
+                });
+                let result = if self.dry_run {
+                    self.preview_lines(&producer, rows).await
+                } else {
+                    self.produce_input(&producer, rows).await
+                };
+                if let Some(err) = failed.lock().ok().and_then(|mut failed| failed.take()) {
+                    return Err(err);
+                }
+                return result;
+            }
+
             #[cfg(feature = "producer-file-io")]
             if let Some(path) = &self.file {
                 let reader = BufReader::new(File::open(path)?);
-                let mut produce_outputs = vec![];
-                for line in reader.lines().map_while(|it| it.ok()) {
-                    let produce_output = self.produce_line(&producer, &line).await?;
+                let lines = reader.lines().map_while(|it| it.ok());
+                return if self.dry_run {
+                    self.preview_lines(&producer, lines).await
+                } else {
+                    self.produce_input(&producer, lines).await
+                };
+            }
 
-                    if let Some(produce_output) = produce_output {
-                        produce_outputs.push(produce_output);
+            let lines = BufReader::new(std::io::stdin())
+                .lines()
+                .map_while(|it| it.ok());
+            if self.dry_run {
+                self.preview_lines(&producer, lines).await
+            } else {
+                self.produce_input(&producer, lines).await
+            }
+        }
+
+        /// print records which would be produced for each line, without sending them
+        async fn preview_lines(
+            &self,
+            producer: &Arc<TopicProducerPool>,
+            lines: impl Iterator<Item = String>,
+        ) -> Result<()> {
+            let mut report = DryRunReport::default();
+            println!("{PREVIEW_HEADER}");
+            for (number, line) in (1..).zip(lines) {
+                match self.split_line(&line) {
+                    Some((key, value)) => {
+                        let records = producer.preview(key, value).await?;
+                        for row in report.add_line(number, &records) {
+                            println!("{row}");
+                        }
                     }
+                    None => report.skip_line(),
                 }
+            }
+            eprintln!("{report}");
+            Ok(())
+        }
 
-                if self.delivery_semantic != DeliverySemantic::AtMostOnce {
-                    // ensure all records were properly sent
-                    join_all(
-                        produce_outputs
-                            .into_iter()
-                            .map(|produce_output| produce_output.wait()),
-                    )
-                    .await
-                    .into_iter()
-                    .collect::<Result<Vec<_>, _>>()?;
-                }
+        /// produce each line as record, failed records don't stop reading.
+        /// Summary of delivery is printed if requested or stderr is a terminal,
+        /// except in interactive mode.
+        /// On Ctrl-C reading stops and records already read are delivered
+        async fn produce_input(
+            &self,
+            producer: &Arc<TopicProducerPool>,
+            lines: impl Iterator<Item = String> + Send + 'static,
+        ) -> Result<()> {
+            let interrupt = Interrupt::init()?;
+            let lines = read_lines(lines);
+            let interactive = self.interactive_mode();
+            // in interactive mode each record is confirmed before prompting for next one
+            let max_in_flight = if interactive {
+                1
             } else {
-                self.producer_stdin(&producer).await?
+                self.max_in_flight as usize
+            };
+            let mut tracker = DeliveryTracker::new(max_in_flight, self.error_file.as_deref())?;
+
+            if interactive {
+                eprint!("> ");
             }
+            for number in 1.. {
+                let line = select! {
+                    line = lines.recv().fuse() => match line {
+                        Ok(line) => line,
+                        Err(_) => break,
+                    },
+                    _ = interrupt.wait().fuse() => {
+                        debug!("Received stop signal, flushing records");
+                        break;
+                    },
+                };
+                let failed = tracker.summary().failed;
+                self.send_tracked(producer, &mut tracker, PendingLine::new(number, line))
+                    .await?;
+                while tracker.is_full() {
+                    self.confirm_oldest(producer, &mut tracker).await?;
+                }
 
-            #[cfg(not(feature = "producer-file-io"))]
-            self.producer_stdin(&producer).await?;
+                if interactive {
+                    match tracker.last_error() {
+                        Some(err) if tracker.summary().failed > failed => {
+                            eprintln!("failed to produce {err}")
+                        }
+                        _ => print_cli_ok!(),
+                    }
+                    eprint!("> ");
+                }
+            }
+            producer.flush().await?;
+            while let Some(in_flight) = tracker.pop() {
+                self.confirm(producer, &mut tracker, in_flight).await?;
+            }
 
+            if interrupt.is_interrupted() {
+                if interactive {
+                    eprintln!();
+                }
+                eprintln!("Interrupted, produced {}", tracker.session());
+            }
+            let summary = tracker.finish()?;
+            if !interactive && (self.summary || std::io::stderr().is_terminal()) {
+                eprintln!("{summary}");
+            }
+            if summary.failed > 0 {
+                return Err(CliError::Other(format!(
+                    "{} records failed to be produced, first at line {}",
+                    summary.failed,
+                    summary.first_failed_line.unwrap_or_default()
+                ))
+                .into());
+            }
             Ok(())
         }
 
-        async fn producer_stdin(&self, producer: &Arc<TopicProducerPool>) -> Result<()> {
-            let mut lines = BufReader::new(std::io::stdin()).lines();
-            if self.interactive_mode() {
-                eprint!("> ");
+        /// send line, retrying it if it can't be sent
+        async fn send_tracked(
+            &self,
+            producer: &Arc<TopicProducerPool>,
+            tracker: &mut DeliveryTracker,
+            mut line: PendingLine,
+        ) -> Result<()> {
+            loop {
+                match self.produce_line(producer, &line.value).await {
+                    Ok(Some(output)) if self.delivery_semantic != DeliverySemantic::AtMostOnce => {
+                        tracker.push(line, output);
+                        return Ok(());
+                    }
+                    Ok(Some(_)) => {
+                        tracker.record_sent(&line, None);
+                        return Ok(());
+                    }
+                    // line skipped
+                    Ok(None) => return Ok(()),
+                    Err(err) => {
+                        if !self.retry(tracker, &mut line).await {
+                            return tracker.record_failed(line, err);
+                        }
+                    }
+                }
             }
+        }
 
-            while let Some(Ok(line)) = lines.next() {
-                let produce_output = self.produce_line(producer, &line).await?;
+        async fn confirm_oldest(
+            &self,
+            producer: &Arc<TopicProducerPool>,
+            tracker: &mut DeliveryTracker,
+        ) -> Result<()> {
+            match tracker.pop() {
+                Some(in_flight) => self.confirm(producer, tracker, in_flight).await,
+                None => Ok(()),
+            }
+        }
 
-                if let Some(produce_output) = produce_output
-                    && self.delivery_semantic != DeliverySemantic::AtMostOnce
-                {
-                    // ensure it was properly sent
-                    produce_output.wait().await?;
+        /// wait for delivery of record, sending it again if it failed
+        async fn confirm(
+            &self,
+            producer: &Arc<TopicProducerPool>,
+            tracker: &mut DeliveryTracker,
+            (mut line, output): (PendingLine, ProduceOutput),
+        ) -> Result<()> {
+            match output.wait().await {
+                Ok(metadata) => {
+                    tracker.record_sent(&line, Some(metadata));
+                    Ok(())
                 }
-
-                if self.interactive_mode() {
-                    print_cli_ok!();
-                    eprint!("> ");
+                Err(err) => {
+                    if self.retry(tracker, &mut line).await {
+                        self.send_tracked(producer, tracker, line).await
+                    } else {
+                        tracker.record_failed(line, err)
+                    }
                 }
             }
-            Ok(())
+        }
+
+        /// true if line should be sent again, after waiting for backoff
+        async fn retry(&self, tracker: &mut DeliveryTracker, line: &mut PendingLine) -> bool {
+            if line.attempts >= self.retries {
+                return false;
+            }
+            line.attempts += 1;
+            tracker.record_retry();
+            sleep(self.retry_backoff * line.attempts).await;
+            true
         }
 
         async fn produce_line(
@@ -427,46 +678,52 @@ mod cmd {
             producer: &Arc<TopicProducerPool>,
             line: &str,
         ) -> Result<Option<ProduceOutput>> {
-            let produce_output = if let Some(separator) = &self.key_separator {
-                self.produce_key_value(producer.clone(), line, separator)
-                    .await?
-            } else if let Some(key) = &self.key {
-                Some(producer.send(RecordKey::from(key.as_bytes()), line).await?)
-            } else {
-                Some(producer.send(RecordKey::NULL, line).await?)
-            };
-
-            Ok(produce_output)
+            match self.split_line(line) {
+                Some((key, value)) => Ok(Some(producer.send(key, value).await?)),
+                None => Ok(None),
+            }
         }
 
-        async fn produce_key_value(
-            &self,
-            producer: Arc<TopicProducerPool>,
-            line: &str,
-            separator: &str,
-        ) -> Result<Option<ProduceOutput>> {
-            let maybe_kv = line.split_once(separator);
-            let (key, value) = match maybe_kv {
-                Some(kv) => kv,
-                None => {
+        /// key and value of record for the line, None if line must be skipped
+        fn split_line<'a>(&self, line: &'a str) -> Option<(RecordKey, &'a str)> {
+            if let Some(separator) = &self.key_separator {
+                let Some((key, value)) = line.split_once(separator.as_str()) else {
                     error!(
                         "Failed to find separator '{}' in record, skipping: '{}'",
                         separator, line
                     );
-                    return Ok(None);
+                    return None;
+                };
+
+                if self.verbose {
+                    println!("[{key}] {value}");
                 }
-            };
 
-            if self.verbose {
-                println!("[{key}] {value}");
+                Some((RecordKey::from(key), value))
+            } else if let Some(key) = &self.key {
+                Some((RecordKey::from(key.as_bytes()), line))
+            } else {
+                #[cfg(feature = "producer-parquet")]
+                if let Some(column) = &self.key_column {
+                    let Some(key) = row_key(line, column) else {
+                        error!(
+                            "Failed to find key column '{}' in row, skipping: '{}'",
+                            column, line
+                        );
+                        return None;
+                    };
+                    return Some((RecordKey::from(key), line));
+                }
+                Some((RecordKey::NULL, line))
             }
-
-            Ok(Some(producer.send(key, value).await?))
         }
 
         #[cfg(feature = "producer-file-io")]
         fn interactive_mode(&self) -> bool {
-            use std::io::IsTerminal;
+            #[cfg(feature = "producer-parquet")]
+            if self.parquet.is_some() {
+                return false;
+            }
 
             self.file.is_none() && std::io::stdin().is_terminal()
         }
@@ -0,0 +1,98 @@
+//!
+//! # Parquet input
+//!
+//! Rows of a Parquet file are produced as JSON objects, one record per row.
+//! Key of record can be taken from a column of the row.
+//!
+
+use std::fs::File;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use parquet::file::reader::SerializedFileReader;
+use serde_json::Value;
+
+/// rows of file as JSON objects, or error of row which can't be decoded
+pub(crate) fn parquet_rows(
+    path: &Path,
+) -> Result<impl Iterator<Item = Result<String>> + Send + 'static> {
+    let file = File::open(path).with_context(|| format!("unable to open {}", path.display()))?;
+    let reader = SerializedFileReader::new(file)
+        .with_context(|| format!("{} is not a valid parquet file", path.display()))?;
+    Ok(reader.into_iter().zip(1..).map(|(row, number)| {
+        row.map(|row| row.to_json_value().to_string())
+            .with_context(|| format!("failed to read parquet row {number}"))
+    }))
+}
+
+/// value of column in row, strings are used as is and other values as JSON
+pub(crate) fn row_key(row: &str, column: &str) -> Option<String> {
+    let value = serde_json::from_str::<Value>(row).ok()?;
+    match value.get(column)? {
+        Value::Null => None,
+        Value::String(key) => Some(key.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::sync::Arc;
+
+    use parquet::data_type::{ByteArray, ByteArrayType, Int32Type};
+    use parquet::file::writer::SerializedFileWriter;
+    use parquet::schema::parser::parse_message_type;
+
+    use super::*;
+
+    #[test]
+    fn test_parquet_rows() {
+        let file = tempfile::NamedTempFile::new().expect("temp file");
+        let schema = parse_message_type(
+            "message schema { REQUIRED INT32 id; REQUIRED BYTE_ARRAY name (UTF8); }",
+        )
+        .expect("schema");
+        let mut writer = SerializedFileWriter::new(
+            file.reopen().expect("file"),
+            Arc::new(schema),
+            Default::default(),
+        )
+        .expect("writer");
+        let mut row_group = writer.next_row_group().expect("row group");
+        let mut column = row_group.next_column().expect("column").expect("id");
+        column
+            .typed::<Int32Type>()
+            .write_batch(&[1, 2], None, None)
+            .expect("ids");
+        column.close().expect("close");
+        let mut column = row_group.next_column().expect("column").expect("name");
+        column
+            .typed::<ByteArrayType>()
+            .write_batch(
+                &[ByteArray::from("alice"), ByteArray::from("bob")],
+                None,
+                None,
+            )
+            .expect("names");
+        column.close().expect("close");
+        row_group.close().expect("close");
+        writer.close().expect("close");
+
+        let rows: Vec<String> = parquet_rows(file.path())
+            .expect("rows")
+            .collect::<Result<_>>()
+            .expect("valid rows");
+
+        assert_eq!(
+            rows,
+            vec![
+                r#"{"id":1,"name":"alice"}"#.to_owned(),
+                r#"{"id":2,"name":"bob"}"#.to_owned()
+            ]
+        );
+        assert_eq!(row_key(&rows[1], "name"), Some("bob".to_owned()));
+        assert_eq!(row_key(&rows[1], "id"), Some("2".to_owned()));
+        assert_eq!(row_key(&rows[1], "missing"), None);
+    }
+}
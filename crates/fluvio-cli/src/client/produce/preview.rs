@@ -0,0 +1,103 @@
+//!
+//! # Dry run of produce
+//!
+//! Describes records which would be produced, with partitions they would be routed to,
+//! and totals per partition.
+//!
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use bytesize::ByteSize;
+
+use fluvio_protocol::record::Record;
+use fluvio_types::PartitionId;
+
+pub(crate) const PREVIEW_HEADER: &str = "LINE\tPARTITION\tKEY\tSIZE";
+
+#[derive(Debug, Default)]
+pub(crate) struct DryRunReport {
+    /// records and bytes per partition
+    partitions: BTreeMap<PartitionId, (u64, u64)>,
+    lines: u64,
+    skipped: u64,
+}
+
+impl DryRunReport {
+    /// add records produced for the line, returning their description
+    pub(crate) fn add_line(&mut self, line: u64, records: &[(PartitionId, Record)]) -> Vec<String> {
+        self.lines += 1;
+        records
+            .iter()
+            .map(|(partition, record)| {
+                let size = record.key().map_or(0, |key| key.len()) + record.value().len();
+                let (count, bytes) = self.partitions.entry(*partition).or_default();
+                *count += 1;
+                *bytes += size as u64;
+                let key = record
+                    .key()
+                    .map_or_else(|| "null".to_owned(), |key| key.describe());
+                format!("{line}\t{partition}\t{key}\t{size}")
+            })
+            .collect()
+    }
+
+    /// line which would not be sent, missing key separator
+    pub(crate) fn skip_line(&mut self) {
+        self.lines += 1;
+        self.skipped += 1;
+    }
+}
+
+impl fmt::Display for DryRunReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let records: u64 = self.partitions.values().map(|(count, _)| count).sum();
+        write!(
+            f,
+            "dry run: {} lines, {} skipped, {records} records would be sent",
+            self.lines, self.skipped
+        )?;
+        for (partition, (count, bytes)) in &self.partitions {
+            write!(
+                f,
+                "\n  partition {partition}: {count} records, {}",
+                ByteSize::b(*bytes)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use fluvio_protocol::record::{RecordData, RecordKey};
+
+    use super::*;
+
+    fn record(key: Option<&str>, value: &str) -> Record {
+        let key = key.map_or(RecordKey::NULL, RecordKey::from);
+        Record::from((key, RecordData::from(value)))
+    }
+
+    #[test]
+    fn test_dry_run_report() {
+        let mut report = DryRunReport::default();
+
+        let rows = report.add_line(1, &[(0, record(Some("k1"), "hello"))]);
+        assert_eq!(rows, vec!["1\t0\ttext: 'k1'\t7"]);
+        report.skip_line();
+        let rows = report.add_line(
+            3,
+            &[(1, record(None, "abc")), (0, record(Some("k2"), "abcd"))],
+        );
+        assert_eq!(rows, vec!["3\t1\tnull\t3", "3\t0\ttext: 'k2'\t6"]);
+        // filtered out by smartmodule
+        assert!(report.add_line(4, &[]).is_empty());
+
+        assert_eq!(
+            report.to_string(),
+            "dry run: 4 lines, 1 skipped, 3 records would be sent\n  partition 0: 2 records, 13 B\n  partition 1: 1 records, 3 B"
+        );
+    }
+}
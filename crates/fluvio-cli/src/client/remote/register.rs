@@ -1,6 +1,7 @@
 use std::sync::Arc;
 use anyhow::Result;
 use clap::Parser;
+use fluvio::Capability;
 use fluvio_controlplane_metadata::mirror::{MirrorSpec, MirrorType};
 use fluvio_extension_common::target::ClusterTarget;
 use fluvio_extension_common::Terminal;
@@ -18,10 +19,9 @@ impl RegisterOpt {
         cluster_target: ClusterTarget,
     ) -> Result<()> {
         let fluvio_config = cluster_target.load()?;
-        let admin = fluvio::Fluvio::connect_with_config(&fluvio_config)
-            .await?
-            .admin()
-            .await;
+        let fluvio = fluvio::Fluvio::connect_with_config(&fluvio_config).await?;
+        fluvio.check_capability(Capability::Mirroring)?;
+        let admin = fluvio.admin().await;
 
         let spec = MirrorSpec {
             mirror_type: MirrorType::Remote(Remote {
@@ -0,0 +1,198 @@
+//!
+//! # Interruptible sessions
+//!
+//! Ctrl-C handling of long running produce and consume commands. First interrupt asks
+//! the command to stop, so it can flush pending records and commit offsets,
+//! second interrupt exits right away.
+//!
+//! The handler is installed once per process and delivers interrupts to the latest session,
+//! so commands run from `fluvio shell` can be interrupted one after another.
+//!
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use bytesize::ByteSize;
+use tracing::debug;
+use anyhow::Result;
+
+use fluvio_types::PartitionId;
+
+use crate::CliError;
+
+/// exit code of command interrupted twice, as if killed by SIGINT
+const FORCED_EXIT_CODE: i32 = 130;
+
+/// interrupt flag and channel of current session
+type SessionSlot = Mutex<Option<(Arc<AtomicBool>, async_channel::Sender<()>)>>;
+
+/// session receiving interrupts, set when handler is installed
+static CURRENT_SESSION: OnceLock<SessionSlot> = OnceLock::new();
+
+#[derive(Debug, Clone)]
+pub(crate) struct Interrupt {
+    interrupted: Arc<AtomicBool>,
+    receiver: async_channel::Receiver<()>,
+}
+
+impl Interrupt {
+    /// start new session receiving Ctrl-C, handler is installed on first call
+    pub(crate) fn init() -> Result<Self> {
+        let (sender, receiver) = async_channel::bounded(1);
+        let interrupted = Arc::new(AtomicBool::new(false));
+
+        let mut installed = Ok(());
+        let slot = CURRENT_SESSION.get_or_init(|| {
+            installed = ctrlc::set_handler(handle_interrupt);
+            Mutex::new(None)
+        });
+        installed
+            .map_err(|err| CliError::Other(format!("CTRL-C handler can't be initialized {err}")))?;
+
+        if let Ok(mut current) = slot.lock() {
+            *current = Some((interrupted.clone(), sender));
+        }
+        Ok(Self {
+            interrupted,
+            receiver,
+        })
+    }
+
+    pub(crate) fn is_interrupted(&self) -> bool {
+        self.interrupted.load(Ordering::SeqCst)
+    }
+
+    /// wait for first interrupt
+    pub(crate) async fn wait(&self) {
+        // channel is only closed by handler
+        let _ = self.receiver.recv().await;
+    }
+
+    /// stop delivering interrupts to session of finished command
+    pub(crate) fn end_session() {
+        if let Some(mut current) = CURRENT_SESSION.get().and_then(|slot| slot.lock().ok()) {
+            *current = None;
+        }
+    }
+}
+
+fn handle_interrupt() {
+    let session = CURRENT_SESSION
+        .get()
+        .and_then(|slot| slot.lock().ok())
+        .and_then(|current| current.clone());
+    // no command running, shell prompt handles its own Ctrl-C
+    let Some((flag, sender)) = session else {
+        debug!("ignoring control c without session");
+        return;
+    };
+    if flag.swap(true, Ordering::SeqCst) {
+        eprintln!("interrupted again, exiting without flushing");
+        std::process::exit(FORCED_EXIT_CODE);
+    }
+    debug!("detected control c, stopping");
+    sender.close();
+}
+
+/// records processed by the command, printed when it is interrupted
+#[derive(Debug)]
+pub(crate) struct SessionSummary {
+    started: Instant,
+    records: u64,
+    bytes: u64,
+    last_offsets: BTreeMap<PartitionId, i64>,
+}
+
+impl Default for SessionSummary {
+    fn default() -> Self {
+        Self {
+            started: Instant::now(),
+            records: 0,
+            bytes: 0,
+            last_offsets: BTreeMap::new(),
+        }
+    }
+}
+
+impl SessionSummary {
+    /// add record of given size, with its partition and offset if known
+    pub(crate) fn record(&mut self, bytes: usize, position: Option<(PartitionId, i64)>) {
+        self.records += 1;
+        self.bytes += bytes as u64;
+        if let Some((partition, offset)) = position {
+            let last = self.last_offsets.entry(partition).or_insert(offset);
+            *last = (*last).max(offset);
+        }
+    }
+
+    /// summary with duration rounded to milliseconds
+    fn describe(&self, elapsed: Duration) -> String {
+        let elapsed = Duration::from_millis(elapsed.as_millis() as u64);
+        let mut summary = format!(
+            "{} records, {} in {}",
+            self.records,
+            ByteSize::b(self.bytes),
+            humantime::format_duration(elapsed)
+        );
+        if !self.last_offsets.is_empty() {
+            let offsets: Vec<String> = self
+                .last_offsets
+                .iter()
+                .map(|(partition, offset)| format!("{partition}: {offset}"))
+                .collect();
+            summary.push_str(&format!(", last offsets: {}", offsets.join(", ")));
+        }
+        summary
+    }
+}
+
+impl fmt::Display for SessionSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.describe(self.started.elapsed()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_session_summary() {
+        let mut summary = SessionSummary::default();
+        assert_eq!(
+            summary.describe(Duration::from_micros(1_500_800)),
+            "0 records, 0 B in 1s 500ms"
+        );
+
+        summary.record(100, Some((1, 10)));
+        summary.record(20, Some((0, 3)));
+        // resent record confirmed after following ones
+        summary.record(30, Some((1, 8)));
+        summary.record(5, None);
+        assert_eq!(
+            summary.describe(Duration::from_secs(2)),
+            "4 records, 155 B in 2s, last offsets: 0: 3, 1: 10"
+        );
+    }
+
+    #[test]
+    fn test_interrupt_after_session_end() {
+        let first = Interrupt::init().expect("init");
+        handle_interrupt();
+        assert!(first.is_interrupted());
+        Interrupt::end_session();
+
+        // interrupt without running command is ignored
+        handle_interrupt();
+
+        let second = Interrupt::init().expect("init");
+        assert!(!second.is_interrupted());
+        handle_interrupt();
+        assert!(second.is_interrupted());
+        Interrupt::end_session();
+    }
+}
@@ -9,7 +9,7 @@ use fluvio::metadata::smartmodule::SmartModuleSpec;
 use fluvio::Fluvio;
 
 use crate::client::cmd::ClientCmd;
-use crate::common::output::Terminal;
+use crate::common::output::{Terminal, set_raw_units};
 use crate::common::OutputFormat;
 
 /// List all existing SmartModules
@@ -20,6 +20,10 @@ pub struct ListSmartModuleOpt {
 
     #[arg(long)]
     filter: Option<String>,
+
+    /// Show sizes in bytes and durations in seconds, instead of human readable units
+    #[arg(long)]
+    raw: bool,
 }
 
 impl ListSmartModuleOpt {
@@ -27,6 +31,7 @@ impl ListSmartModuleOpt {
         Self {
             output,
             filter: None,
+            raw: false,
         }
     }
 }
@@ -38,6 +43,7 @@ impl ClientCmd for ListSmartModuleOpt {
         out: Arc<O>,
         fluvio: &Fluvio,
     ) -> Result<()> {
+        set_raw_units(self.raw);
         let admin = fluvio.admin().await;
         let filters = if let Some(filter) = self.filter {
             vec![filter]
@@ -69,7 +75,7 @@ mod output {
     use fluvio::metadata::objects::Metadata;
     use fluvio::metadata::smartmodule::SmartModuleSpec;
 
-    use fluvio_extension_common::output::TableOutputHandler;
+    use fluvio_extension_common::output::{TableOutputHandler, format_size};
     use fluvio_extension_common::t_println;
 
     #[derive(Serialize)]
@@ -120,12 +126,9 @@ mod output {
 
                     Row::from([
                         Cell::new(r.spec.fqdn(&r.name)).set_alignment(CellAlignment::Left),
-                        Cell::new(
-                            bytesize::ByteSize::b(
-                                r.spec.summary.clone().unwrap_or_default().wasm_length as u64,
-                            )
-                            .to_string(),
-                        )
+                        Cell::new(format_size(
+                            r.spec.summary.clone().unwrap_or_default().wasm_length as u64,
+                        ))
                         .set_alignment(CellAlignment::Right),
                     ])
                 })
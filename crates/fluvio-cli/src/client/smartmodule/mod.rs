@@ -19,7 +19,6 @@ mod cmd {
     use anyhow::Result;
 
     use fluvio::Fluvio;
-    use fluvio_extension_common::target::ClusterTarget;
 
     use crate::client::cmd::ClientCmd;
     use crate::common::output::Terminal;
@@ -42,38 +41,30 @@ mod cmd {
 
     #[async_trait]
     impl ClientCmd for SmartModuleCmd {
-        async fn process<O: Terminal + Send + Sync + Debug>(
+        async fn process_client<O: Terminal + Debug + Send + Sync>(
             self,
             out: Arc<O>,
-            target: ClusterTarget,
+            fluvio: &Fluvio,
         ) -> Result<()> {
             match self {
                 Self::Create(opt) => {
-                    opt.process(out, target).await?;
+                    opt.process_client(out, fluvio).await?;
                 }
                 Self::List(opt) => {
-                    opt.process(out, target).await?;
+                    opt.process_client(out, fluvio).await?;
                 }
                 Self::Delete(opt) => {
-                    opt.process(out, target).await?;
+                    opt.process_client(out, fluvio).await?;
                 }
                 Self::Watch(opt) => {
-                    opt.process(out, target).await?;
+                    opt.process_client(out, fluvio).await?;
                 }
                 #[cfg(not(target_arch = "arm"))]
                 Self::Test(opt) => {
-                    opt.process(out, target).await?;
+                    opt.process_client(out, fluvio).await?;
                 }
             }
             Ok(())
         }
-
-        async fn process_client<O: Terminal + Debug + Send + Sync>(
-            self,
-            _out: Arc<O>,
-            _fluvio: &Fluvio,
-        ) -> Result<()> {
-            Ok(())
-        }
     }
 }
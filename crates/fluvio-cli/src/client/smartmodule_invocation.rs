@@ -13,6 +13,49 @@ use flate2::Compression;
 use anyhow::Result;
 use tracing::debug;
 
+use crate::CliError;
+
+/// collect parameters of smartmodule from YAML file and `key=value` arguments,
+/// arguments override values from file
+pub(crate) fn smartmodule_params(
+    file: Option<&Path>,
+    params: Option<&[(String, String)]>,
+) -> Result<BTreeMap<String, String>> {
+    let mut result = match file {
+        Some(path) => {
+            let content = std::fs::read_to_string(path)?;
+            parse_params_file(&content).map_err(|err| {
+                CliError::InvalidArg(format!(
+                    "unable to parse params file {}: {err}",
+                    path.display()
+                ))
+            })?
+        }
+        None => BTreeMap::new(),
+    };
+    result.extend(params.unwrap_or_default().iter().cloned());
+    Ok(result)
+}
+
+/// parse map of parameters, scalar values are converted to strings
+fn parse_params_file(content: &str) -> Result<BTreeMap<String, String>> {
+    use serde_yaml::Value;
+
+    let values: BTreeMap<String, Value> = serde_yaml::from_str(content)?;
+    values
+        .into_iter()
+        .map(|(key, value)| {
+            let value = match value {
+                Value::String(value) => value,
+                Value::Number(value) => value.to_string(),
+                Value::Bool(value) => value.to_string(),
+                _ => anyhow::bail!("value of `{key}` must be a string, number or boolean"),
+            };
+            Ok((key, value))
+        })
+        .collect()
+}
+
 /// create smartmodule from predefined name
 pub(crate) fn create_smartmodule(
     name: &str,
@@ -80,3 +123,20 @@ pub(crate) fn create_smartmodule_list(
         })
         .collect())
 }
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_parse_params_file() {
+        let params =
+            parse_params_file("multiplier: 2\nverbose: true\nmode: fast\n").expect("params");
+        assert_eq!(params.get("multiplier").map(String::as_str), Some("2"));
+        assert_eq!(params.get("verbose").map(String::as_str), Some("true"));
+        assert_eq!(params.get("mode").map(String::as_str), Some("fast"));
+
+        assert!(parse_params_file("list: [1, 2]").is_err());
+    }
+}
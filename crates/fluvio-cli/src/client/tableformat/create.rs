@@ -29,6 +29,7 @@ pub struct CreateTableFormatOpt {
 impl CreateTableFormatOpt {
     pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
         let config = TableFormatConfig::from_file(self.config)?;
+        config.validate()?;
         let tableformat_spec: TableFormatSpec = config.into();
         let name = tableformat_spec.name.clone();
 
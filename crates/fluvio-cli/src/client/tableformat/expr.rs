@@ -0,0 +1,367 @@
+//!
+//! # TableFormat expressions
+//!
+//! Expressions over fields of JSON records, used by computed columns and color rules.
+//! Ex: `price * quantity`, `first + " " + last`, `status == "error" || retries > 3`
+//!
+//! Fields are referred by dotted path, missing fields evaluate to `null`.
+//! `+` concatenates when either side is a string, other arithmetic yields `null` for non numbers.
+//!
+
+use std::cmp::Ordering;
+
+use serde_json::{Map, Number, Value};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("invalid expression `{expression}`: {reason}")]
+pub struct ExprError {
+    expression: String,
+    reason: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expr(Node);
+
+impl Expr {
+    pub fn parse(expression: &str) -> Result<Self, ExprError> {
+        let error = |reason: String| ExprError {
+            expression: expression.to_owned(),
+            reason,
+        };
+        let tokens = tokenize(expression).map_err(error)?;
+        let mut parser = Parser { tokens, next: 0 };
+        let node = parser.or().map_err(error)?;
+        match parser.peek() {
+            None => Ok(Self(node)),
+            Some(token) => Err(error(format!("unexpected {token:?}"))),
+        }
+    }
+
+    /// evaluate with value of fields given by `field`
+    pub fn eval<F>(&self, field: &F) -> Value
+    where
+        F: Fn(&str) -> Option<Value>,
+    {
+        self.0.eval(field)
+    }
+
+    /// true if expression yields non empty value
+    pub fn holds<F>(&self, field: &F) -> bool
+    where
+        F: Fn(&str) -> Option<Value>,
+    {
+        truthy(&self.eval(field))
+    }
+}
+
+/// value of field at dotted path in JSON object
+pub fn json_field(object: &Map<String, Value>, path: &str) -> Option<Value> {
+    let mut segments = path.split('.');
+    let root = object.get(segments.next()?)?;
+    segments
+        .try_fold(root, |value, segment| match value {
+            Value::Object(object) => object.get(segment),
+            Value::Array(values) => values.get(segment.parse::<usize>().ok()?),
+            _ => None,
+        })
+        .cloned()
+}
+
+/// text of value as shown in table
+pub fn display(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Text(String),
+    Ident(String),
+    Op(&'static str),
+}
+
+const OPERATORS: [&str; 17] = [
+    "==", "!=", "<=", ">=", "&&", "||", "+", "-", "*", "/", "%", "<", ">", "!", "(", ")", "=",
+];
+
+fn tokenize(expression: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = vec![];
+    let mut rest = expression.trim_start();
+    while let Some(next) = rest.chars().next() {
+        if next.is_ascii_digit() {
+            let end = rest
+                .find(|c: char| !c.is_ascii_digit() && c != '.')
+                .unwrap_or(rest.len());
+            let number = rest[..end]
+                .parse()
+                .map_err(|_| format!("invalid number {}", &rest[..end]))?;
+            tokens.push(Token::Number(number));
+            rest = &rest[end..];
+        } else if next == '"' || next == '\'' {
+            let end = rest[1..]
+                .find(next)
+                .ok_or_else(|| "unterminated string".to_owned())?;
+            tokens.push(Token::Text(rest[1..=end].to_owned()));
+            rest = &rest[end + 2..];
+        } else if next.is_alphabetic() || next == '_' {
+            let end = rest
+                .find(|c: char| !c.is_alphanumeric() && c != '_' && c != '.')
+                .unwrap_or(rest.len());
+            tokens.push(Token::Ident(rest[..end].to_owned()));
+            rest = &rest[end..];
+        } else {
+            let op = *OPERATORS
+                .iter()
+                .find(|op| rest.starts_with(**op))
+                .ok_or_else(|| format!("unexpected character '{next}'"))?;
+            // single `=` is accepted as equality
+            tokens.push(Token::Op(if op == "=" { "==" } else { op }));
+            rest = &rest[op.len()..];
+        }
+        rest = rest.trim_start();
+    }
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Literal(Value),
+    Field(String),
+    Not(Box<Node>),
+    Negate(Box<Node>),
+    Binary(&'static str, Box<Node>, Box<Node>),
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    next: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.next)
+    }
+
+    /// consume next token if it is one of operators
+    fn operator(&mut self, operators: &[&'static str]) -> Option<&'static str> {
+        match self.peek() {
+            Some(Token::Op(op)) if operators.contains(op) => {
+                let op = *op;
+                self.next += 1;
+                Some(op)
+            }
+            _ => None,
+        }
+    }
+
+    /// left associative binary operators of same precedence
+    fn binary(
+        &mut self,
+        operators: &[&'static str],
+        operand: fn(&mut Self) -> Result<Node, String>,
+    ) -> Result<Node, String> {
+        let mut node = operand(self)?;
+        while let Some(op) = self.operator(operators) {
+            node = Node::Binary(op, Box::new(node), Box::new(operand(self)?));
+        }
+        Ok(node)
+    }
+
+    fn or(&mut self) -> Result<Node, String> {
+        self.binary(&["||"], Self::and)
+    }
+
+    fn and(&mut self) -> Result<Node, String> {
+        self.binary(&["&&"], Self::comparison)
+    }
+
+    fn comparison(&mut self) -> Result<Node, String> {
+        self.binary(&["==", "!=", "<=", ">=", "<", ">"], Self::sum)
+    }
+
+    fn sum(&mut self) -> Result<Node, String> {
+        self.binary(&["+", "-"], Self::product)
+    }
+
+    fn product(&mut self) -> Result<Node, String> {
+        self.binary(&["*", "/", "%"], Self::unary)
+    }
+
+    fn unary(&mut self) -> Result<Node, String> {
+        match self.operator(&["!", "-"]) {
+            Some("!") => Ok(Node::Not(Box::new(self.unary()?))),
+            Some(_) => Ok(Node::Negate(Box::new(self.unary()?))),
+            None => self.primary(),
+        }
+    }
+
+    fn primary(&mut self) -> Result<Node, String> {
+        let token = self
+            .tokens
+            .get(self.next)
+            .cloned()
+            .ok_or_else(|| "unexpected end".to_owned())?;
+        self.next += 1;
+        match token {
+            Token::Number(number) => Ok(Node::Literal(number_value(number))),
+            Token::Text(text) => Ok(Node::Literal(Value::String(text))),
+            Token::Ident(ident) => Ok(match ident.as_str() {
+                "true" => Node::Literal(Value::Bool(true)),
+                "false" => Node::Literal(Value::Bool(false)),
+                "null" => Node::Literal(Value::Null),
+                _ => Node::Field(ident),
+            }),
+            Token::Op("(") => {
+                let node = self.or()?;
+                match self.operator(&[")"]) {
+                    Some(_) => Ok(node),
+                    None => Err("missing ')'".to_owned()),
+                }
+            }
+            Token::Op(op) => Err(format!("unexpected '{op}'")),
+        }
+    }
+}
+
+impl Node {
+    fn eval<F>(&self, field: &F) -> Value
+    where
+        F: Fn(&str) -> Option<Value>,
+    {
+        match self {
+            Self::Literal(value) => value.clone(),
+            Self::Field(path) => field(path).unwrap_or(Value::Null),
+            Self::Not(node) => Value::Bool(!truthy(&node.eval(field))),
+            Self::Negate(node) => {
+                number(&node.eval(field)).map_or(Value::Null, |n| number_value(-n))
+            }
+            Self::Binary("&&", left, right) => {
+                Value::Bool(truthy(&left.eval(field)) && truthy(&right.eval(field)))
+            }
+            Self::Binary("||", left, right) => {
+                Value::Bool(truthy(&left.eval(field)) || truthy(&right.eval(field)))
+            }
+            Self::Binary(op, left, right) => binary(op, left.eval(field), right.eval(field)),
+        }
+    }
+}
+
+fn binary(op: &str, left: Value, right: Value) -> Value {
+    match op {
+        "==" => Value::Bool(compare(&left, &right) == Ordering::Equal),
+        "!=" => Value::Bool(compare(&left, &right) != Ordering::Equal),
+        "<" => Value::Bool(compare(&left, &right) == Ordering::Less),
+        "<=" => Value::Bool(compare(&left, &right) != Ordering::Greater),
+        ">" => Value::Bool(compare(&left, &right) == Ordering::Greater),
+        ">=" => Value::Bool(compare(&left, &right) != Ordering::Less),
+        "+" if left.is_string() || right.is_string() => {
+            Value::String(display(&left) + &display(&right))
+        }
+        _ => match (number(&left), number(&right)) {
+            (Some(left), Some(right)) => match op {
+                "+" => number_value(left + right),
+                "-" => number_value(left - right),
+                "*" => number_value(left * right),
+                "/" if right != 0.0 => number_value(left / right),
+                "%" if right != 0.0 => number_value(left % right),
+                _ => Value::Null,
+            },
+            _ => Value::Null,
+        },
+    }
+}
+
+/// numbers are compared by value, anything else by text
+fn compare(left: &Value, right: &Value) -> Ordering {
+    match (number(left), number(right)) {
+        (Some(left), Some(right)) => left.partial_cmp(&right).unwrap_or(Ordering::Equal),
+        _ => display(left).cmp(&display(right)),
+    }
+}
+
+/// numeric value of numbers and numeric strings
+fn number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(text) => text.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// integral numbers are kept as integers, so they are shown without fraction
+fn number_value(number: f64) -> Value {
+    if number.fract() == 0.0 && number.abs() < i64::MAX as f64 {
+        Value::Number((number as i64).into())
+    } else {
+        Number::from_f64(number).map_or(Value::Null, Value::Number)
+    }
+}
+
+fn truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(value) => *value,
+        Value::Number(number) => number.as_f64().is_some_and(|n| n != 0.0),
+        Value::String(text) => !text.is_empty(),
+        Value::Array(values) => !values.is_empty(),
+        Value::Object(object) => !object.is_empty(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use serde_json::json;
+
+    use super::*;
+
+    fn eval(expression: &str, record: &Value) -> Value {
+        let object = record.as_object().expect("object");
+        Expr::parse(expression)
+            .expect("valid expression")
+            .eval(&|path| json_field(object, path))
+    }
+
+    #[test]
+    fn test_eval_expressions() {
+        let record = json!({
+            "price": 2.5,
+            "quantity": 4,
+            "first": "Jane",
+            "last": "Doe",
+            "status": "error",
+            "retries": "5",
+            "user": {"tags": ["a", "b"]}
+        });
+
+        assert_eq!(eval("price * quantity", &record), json!(10));
+        assert_eq!(eval("quantity / 3 > 1", &record), json!(true));
+        assert_eq!(eval("-(quantity + 1) % 3", &record), json!(-2));
+        assert_eq!(eval("first + ' ' + last", &record), json!("Jane Doe"));
+        assert_eq!(eval("user.tags.1", &record), json!("b"));
+        assert_eq!(eval("missing * 2", &record), Value::Null);
+        assert_eq!(eval("quantity / 0", &record), Value::Null);
+        assert_eq!(
+            eval(r#"status == "error" && retries >= 5"#, &record),
+            json!(true)
+        );
+        assert_eq!(eval("status = 'ok' || !missing", &record), json!(true));
+    }
+
+    #[test]
+    fn test_invalid_expressions() {
+        assert_eq!(
+            Expr::parse("price *").unwrap_err().to_string(),
+            "invalid expression `price *`: unexpected end"
+        );
+        assert!(Expr::parse("(a + b").is_err());
+        assert!(Expr::parse("a b").is_err());
+        assert!(Expr::parse("'open").is_err());
+        assert!(Expr::parse("a # b").is_err());
+    }
+}
@@ -0,0 +1,286 @@
+//!
+//! # Infer a TableFormat display specification
+//!
+//! Samples JSON records of a topic and generates TableFormat config with a column
+//! for every top level key, which can be edited and passed to `fluvio tableformat create`
+//!
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use clap::Parser;
+use futures::StreamExt;
+use serde_json::{Map, Value};
+use tracing::debug;
+use anyhow::Result;
+
+use fluvio::{Fluvio, Offset};
+use fluvio::consumer::ConsumerConfigExt;
+
+use crate::CliError;
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct InferTableFormatOpt {
+    /// Topic to sample records from
+    #[arg(short, long)]
+    pub topic: String,
+
+    /// Number of latest records sampled from each partition
+    #[arg(long, default_value = "100")]
+    pub rows: u32,
+
+    /// Name of the TableFormat, defaults to topic name
+    #[arg(long)]
+    pub name: Option<String>,
+
+    /// Write TableFormat config to file instead of stdout
+    #[arg(short, long, value_name = "PATH")]
+    pub output: Option<PathBuf>,
+}
+
+impl InferTableFormatOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let objects = self.sample(fluvio).await?;
+        let columns = infer_columns(&objects);
+        if columns.is_empty() {
+            return Err(CliError::Other(format!(
+                "no JSON objects found in latest records of topic \"{}\"",
+                self.topic
+            ))
+            .into());
+        }
+
+        let name = self.name.as_deref().unwrap_or(&self.topic);
+        let config = render_config(name, &self.topic, objects.len(), &columns);
+        match &self.output {
+            Some(path) => {
+                std::fs::write(path, config)?;
+                println!(
+                    "tableformat config written to {}, create it with `fluvio tableformat create --config {}`",
+                    path.display(),
+                    path.display()
+                );
+            }
+            None => print!("{config}"),
+        }
+        Ok(())
+    }
+
+    /// read latest records, keeping JSON objects and objects of JSON arrays
+    async fn sample(&self, fluvio: &Fluvio) -> Result<Vec<Map<String, Value>>> {
+        let mut builder = ConsumerConfigExt::builder();
+        builder
+            .topic(&self.topic)
+            .offset_start(Offset::from_end(self.rows))
+            .disable_continuous(true);
+        let mut stream = fluvio.consumer_with_config(builder.build()?).await?;
+
+        let mut objects = Vec::new();
+        let mut skipped = 0;
+        while let Some(record) = stream.next().await {
+            match serde_json::from_slice::<Value>(record?.value()) {
+                Ok(Value::Object(object)) => objects.push(object),
+                Ok(Value::Array(values)) => {
+                    objects.extend(values.into_iter().filter_map(|value| match value {
+                        Value::Object(object) => Some(object),
+                        _ => None,
+                    }))
+                }
+                _ => skipped += 1,
+            }
+        }
+        debug!(objects = objects.len(), skipped, "sampled records");
+        Ok(objects)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColumnType {
+    Null,
+    Bool,
+    Integer,
+    Float,
+    String,
+    Object,
+    Array,
+    Mixed,
+}
+
+impl ColumnType {
+    fn of(value: &Value) -> Self {
+        match value {
+            Value::Null => Self::Null,
+            Value::Bool(_) => Self::Bool,
+            Value::Number(number) if number.is_f64() => Self::Float,
+            Value::Number(_) => Self::Integer,
+            Value::String(_) => Self::String,
+            Value::Object(_) => Self::Object,
+            Value::Array(_) => Self::Array,
+        }
+    }
+
+    /// type of column having values of both types
+    fn merge(self, other: Self) -> Self {
+        match (self, other) {
+            (left, right) if left == right => left,
+            (Self::Null, kind) | (kind, Self::Null) => kind,
+            (Self::Integer, Self::Float) | (Self::Float, Self::Integer) => Self::Float,
+            _ => Self::Mixed,
+        }
+    }
+
+    fn is_numeric(self) -> bool {
+        matches!(self, Self::Integer | Self::Float)
+    }
+
+    fn is_nested(self) -> bool {
+        matches!(self, Self::Object | Self::Array)
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Null => "null",
+            Self::Bool => "boolean",
+            Self::Integer => "integer",
+            Self::Float => "float",
+            Self::String => "string",
+            Self::Object => "object",
+            Self::Array => "array",
+            Self::Mixed => "mixed",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct InferredColumn {
+    key: String,
+    kind: ColumnType,
+    /// number of objects having the key
+    present: usize,
+    values: HashSet<String>,
+}
+
+impl InferredColumn {
+    /// column identifying rows: named id, found in every object with distinct scalar values
+    fn is_primary_key(&self, objects: usize) -> bool {
+        self.key.eq_ignore_ascii_case("id")
+            && self.present == objects
+            && self.values.len() == objects
+            && matches!(self.kind, ColumnType::Integer | ColumnType::String)
+    }
+}
+
+/// columns in order of first appearance of keys
+fn infer_columns(objects: &[Map<String, Value>]) -> Vec<InferredColumn> {
+    let mut columns: Vec<InferredColumn> = Vec::new();
+    for object in objects {
+        for (key, value) in object {
+            let index = match columns.iter().position(|column| column.key == *key) {
+                Some(index) => index,
+                None => {
+                    columns.push(InferredColumn {
+                        key: key.clone(),
+                        kind: ColumnType::Null,
+                        present: 0,
+                        values: HashSet::new(),
+                    });
+                    columns.len() - 1
+                }
+            };
+            let column = &mut columns[index];
+            column.kind = column.kind.merge(ColumnType::of(value));
+            column.present += 1;
+            column.values.insert(value.to_string());
+        }
+    }
+    columns
+}
+
+/// TableFormat config in YAML, with inferred types as comments
+fn render_config(name: &str, topic: &str, objects: usize, columns: &[InferredColumn]) -> String {
+    let quote = |value: &str| serde_json::to_string(value).unwrap_or_default();
+
+    let mut lines = vec![
+        format!(
+            "# inferred from {objects} records of topic {}",
+            quote(topic)
+        ),
+        "# edit and create with `fluvio tableformat create --config <file>`".to_owned(),
+        format!("name: {}", quote(name)),
+        "input_format: JSON".to_owned(),
+        "columns:".to_owned(),
+    ];
+    for column in columns {
+        lines.push(format!(
+            "  # {}, in {} of {objects} records",
+            column.kind.label(),
+            column.present
+        ));
+        lines.push(format!("  - keyPath: {}", quote(&column.key)));
+        if column.is_primary_key(objects) {
+            lines.push("    primaryKey: true".to_owned());
+        }
+        if column.kind.is_numeric() {
+            lines.push("    alignment: RIGHT".to_owned());
+        }
+        lines.push(format!("    display: {}", !column.kind.is_nested()));
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::client::tableformat::TableFormatConfig;
+
+    use super::*;
+
+    fn objects(records: &[&str]) -> Vec<Map<String, Value>> {
+        records
+            .iter()
+            .map(|record| serde_json::from_str(record).expect("json object"))
+            .collect()
+    }
+
+    #[test]
+    fn test_infer_columns() {
+        let objects = objects(&[
+            r#"{"id": 1, "name": "a", "price": 10, "tags": ["x"]}"#,
+            r#"{"id": 2, "name": null, "price": 10.5, "active": true}"#,
+            r#"{"id": 3, "name": "c", "price": "free"}"#,
+        ]);
+        let columns = infer_columns(&objects);
+
+        let summary: Vec<_> = columns
+            .iter()
+            .map(|column| (column.key.as_str(), column.kind, column.present))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("id", ColumnType::Integer, 3),
+                ("name", ColumnType::String, 3),
+                ("price", ColumnType::Mixed, 3),
+                ("tags", ColumnType::Array, 1),
+                ("active", ColumnType::Bool, 1),
+            ]
+        );
+        assert!(columns[0].is_primary_key(objects.len()));
+        assert!(!columns[1].is_primary_key(objects.len()));
+
+        let config = render_config("orders", "orders", objects.len(), &columns);
+        assert!(config.contains(
+            "  # integer, in 3 of 3 records\n  - keyPath: \"id\"\n    primaryKey: true\n    alignment: RIGHT\n    display: true\n"
+        ));
+        assert!(config.contains("  - keyPath: \"tags\"\n    display: false\n"));
+
+        let parsed: TableFormatConfig = serde_yaml::from_str(&config).expect("valid config");
+        assert_eq!(parsed.name, "orders");
+        assert_eq!(parsed.columns.expect("columns").len(), 5);
+    }
+}
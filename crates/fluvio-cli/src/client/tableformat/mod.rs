@@ -1,5 +1,7 @@
 mod create;
 mod delete;
+pub(crate) mod expr;
+mod infer;
 mod list;
 
 pub use cmd::{TableFormatConfig, TableFormatCmd};
@@ -26,7 +28,9 @@ mod cmd {
 
     use super::create::CreateTableFormatOpt;
     use super::delete::DeleteTableFormatOpt;
+    use super::infer::InferTableFormatOpt;
     use super::list::ListTableFormatsOpt;
+    use super::expr::Expr;
 
     #[derive(Debug, Parser)]
     pub enum TableFormatCmd {
@@ -50,6 +54,13 @@ mod cmd {
             help_template = COMMAND_TEMPLATE,
         )]
         List(ListTableFormatsOpt),
+
+        /// Generate TableFormat config from sampled records of a topic
+        #[command(
+            name = "infer",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Infer(InferTableFormatOpt),
     }
 
     #[async_trait]
@@ -69,6 +80,9 @@ mod cmd {
                 Self::List(list) => {
                     list.process(out, fluvio).await?;
                 }
+                Self::Infer(infer) => {
+                    infer.process(fluvio).await?;
+                }
             }
             Ok(())
         }
@@ -90,6 +104,19 @@ mod cmd {
             let table_format_config: TableFormatConfig = serde_yaml::from_str(&contents)?;
             Ok(table_format_config)
         }
+
+        /// check expressions of computed columns and color rules
+        pub fn validate(&self) -> Result<()> {
+            for column in self.columns.iter().flatten() {
+                if let Some(expression) = &column.expression {
+                    Expr::parse(expression)?;
+                }
+                for rule in &column.color_rules {
+                    Expr::parse(&rule.when)?;
+                }
+            }
+            Ok(())
+        }
     }
 
     impl From<TableFormatConfig> for TableFormatSpec {
@@ -105,9 +132,12 @@ mod cmd {
 
     #[test]
     fn config_test() {
-        let _: TableFormatSpec =
-            TableFormatConfig::from_file("test-data/test-tableformat-config.yaml")
-                .expect("Failed to load test config")
-                .into();
+        let config = TableFormatConfig::from_file("test-data/test-tableformat-config.yaml")
+            .expect("Failed to load test config");
+        config.validate().expect("valid expressions");
+        let spec: TableFormatSpec = config.into();
+        let total = &spec.columns.expect("columns")[2];
+        assert_eq!(total.expression.as_deref(), Some("key1 * 2"));
+        assert_eq!(total.color_rules.len(), 1);
     }
 }
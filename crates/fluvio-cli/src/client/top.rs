@@ -0,0 +1,613 @@
+//!
+//! # Top CLI
+//!
+//! Terminal UI live displaying topics, partition leaders, throughput per SPU and consumer lag.
+//! Metadata comes from SC, traffic and offsets from partition statistics reported by leaders.
+//! Throughput is the change of traffic counters between refreshes, so it is shown from the second refresh.
+//!
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Debug;
+use std::io::{IsTerminal, Stdout};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use clap::Parser;
+use anyhow::Result;
+use crossterm::event::{Event, EventStream, KeyCode, KeyModifiers};
+use crossterm::execute;
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use futures::{select, FutureExt, StreamExt};
+use futures::future::join_all;
+use tracing::debug;
+use tui::Terminal as TuiTerminal;
+use tui::backend::CrosstermBackend;
+
+use fluvio::{Fluvio, PartitionId, PartitionStats};
+use fluvio::consumer::PartitionOffsets;
+use fluvio::metadata::partition::{PartitionSpec, ReplicaKey};
+use fluvio::metadata::spu::SpuSpec;
+use fluvio_future::timer::sleep;
+use fluvio_types::SpuId;
+
+use crate::client::cmd::ClientCmd;
+use crate::client::lag::{ConsumerLag, lag_of};
+use crate::common::output::Terminal;
+use crate::CliError;
+
+/// Live display of topics, partition leaders, throughput per SPU and consumer lag
+#[derive(Debug, Parser)]
+pub struct TopOpt {
+    /// Only show these topics, all topics by default
+    #[arg(short, long = "topic", value_name = "TOPIC")]
+    topics: Vec<String>,
+
+    /// Period of refreshes, ex: 5s
+    #[arg(long, value_name = "DURATION", default_value = "2s")]
+    interval: humantime::Duration,
+}
+
+#[async_trait]
+impl ClientCmd for TopOpt {
+    async fn process_client<O: Terminal + Debug + Send + Sync>(
+        self,
+        _out: Arc<O>,
+        fluvio: &Fluvio,
+    ) -> Result<()> {
+        if !std::io::stdout().is_terminal() {
+            return Err(CliError::Other("fluvio top requires a terminal".to_owned()).into());
+        }
+
+        let mut stdout = std::io::stdout();
+        enable_raw_mode()?;
+        execute!(stdout, EnterAlternateScreen)?;
+        let mut terminal = TuiTerminal::new(CrosstermBackend::new(stdout))?;
+
+        // restore terminal before returning any error
+        let result = self.run(&mut terminal, fluvio).await;
+
+        disable_raw_mode()?;
+        execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+        terminal.show_cursor()?;
+        result
+    }
+}
+
+impl TopOpt {
+    async fn run(
+        &self,
+        terminal: &mut TuiTerminal<CrosstermBackend<Stdout>>,
+        fluvio: &Fluvio,
+    ) -> Result<()> {
+        let mut events = EventStream::new();
+        let mut snapshot: Option<Snapshot> = None;
+        let mut error = None;
+        let mut next_refresh = Instant::now();
+
+        loop {
+            if Instant::now() >= next_refresh {
+                // keep showing last snapshot while cluster can't be reached
+                match Snapshot::collect(fluvio, &self.topics, snapshot.as_ref()).await {
+                    Ok(collected) => {
+                        snapshot = Some(collected);
+                        error = None;
+                    }
+                    Err(err) => {
+                        debug!(%err, "refresh failed");
+                        error = Some(err.to_string());
+                    }
+                }
+                next_refresh = Instant::now() + *self.interval;
+            }
+
+            terminal.draw(|frame| {
+                ui::draw(frame, snapshot.as_ref(), error.as_deref(), *self.interval)
+            })?;
+
+            select! {
+                event = events.next().fuse() => match event {
+                    Some(Ok(Event::Key(key))) => {
+                        let ctrl_c = key.code == KeyCode::Char('c')
+                            && key.modifiers.contains(KeyModifiers::CONTROL);
+                        if ctrl_c || matches!(key.code, KeyCode::Char('q') | KeyCode::Esc) {
+                            return Ok(());
+                        }
+                    }
+                    Some(Ok(_)) => {}
+                    Some(Err(err)) => return Err(err.into()),
+                    None => return Ok(()),
+                },
+                _ = sleep(next_refresh.saturating_duration_since(Instant::now())).fuse() => {}
+            }
+        }
+    }
+}
+
+/// bytes per second
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub(crate) struct Rate {
+    produced: f64,
+    consumed: f64,
+}
+
+impl Rate {
+    /// rate of traffic counters, counters restart when leader changes
+    fn between(previous: &PartitionStats, current: &PartitionStats, elapsed: Duration) -> Self {
+        let seconds = elapsed.as_secs_f64();
+        if seconds <= 0.0 {
+            return Self::default();
+        }
+        Self {
+            produced: current
+                .produced_bytes
+                .saturating_sub(previous.produced_bytes) as f64
+                / seconds,
+            consumed: current
+                .consumed_bytes
+                .saturating_sub(previous.consumed_bytes) as f64
+                / seconds,
+        }
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.produced += other.produced;
+        self.consumed += other.consumed;
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct PartitionInfo {
+    topic: String,
+    partition: PartitionId,
+    leader: SpuId,
+    resolution: String,
+    replicas: usize,
+    lrs: u32,
+    leo: i64,
+    /// none if leader can't be reached
+    stats: Option<PartitionStats>,
+    /// none until previous stats are known
+    rate: Option<Rate>,
+}
+
+#[derive(Debug)]
+pub(crate) struct Snapshot {
+    taken: Instant,
+    spus: Vec<(SpuId, bool)>,
+    partitions: Vec<PartitionInfo>,
+    lags: Vec<ConsumerLag>,
+}
+
+impl Snapshot {
+    async fn collect(fluvio: &Fluvio, topics: &[String], previous: Option<&Self>) -> Result<Self> {
+        let admin = fluvio.admin().await;
+        let spus = admin
+            .all::<SpuSpec>()
+            .await?
+            .into_iter()
+            .map(|spu| (spu.spec.id, spu.status.is_online()))
+            .collect();
+
+        let partitions = admin
+            .all::<PartitionSpec>()
+            .await?
+            .into_iter()
+            .filter_map(|partition| {
+                let key: ReplicaKey = partition.name.clone().try_into().ok()?;
+                (topics.is_empty() || topics.contains(&key.topic)).then_some((key, partition))
+            })
+            .collect::<Vec<_>>();
+        let stats = join_all(partitions.iter().map(|(key, _)| async move {
+            fluvio
+                .partition_stats(key.topic.clone(), key.partition)
+                .await
+                .inspect_err(|err| debug!(%key, %err, "partition stats not available"))
+                .ok()
+        }))
+        .await;
+
+        let taken = Instant::now();
+        let mut partitions: Vec<PartitionInfo> = partitions
+            .into_iter()
+            .zip(stats)
+            .map(|((key, partition), stats)| PartitionInfo {
+                topic: key.topic,
+                partition: key.partition,
+                leader: partition.spec.leader,
+                resolution: format!("{:?}", partition.status.resolution),
+                replicas: partition.spec.replicas.len(),
+                lrs: partition.status.lrs(),
+                leo: partition.status.leader.leo,
+                stats,
+                rate: None,
+            })
+            .collect();
+        partitions.sort_by(|a, b| (&a.topic, a.partition).cmp(&(&b.topic, b.partition)));
+        if let Some(previous) = previous {
+            set_rates(&mut partitions, previous, taken);
+        }
+
+        let offsets = partitions
+            .iter()
+            .filter_map(|partition| {
+                partition.stats.as_ref().map(|stats| PartitionOffsets {
+                    topic: partition.topic.clone(),
+                    partition: partition.partition,
+                    earliest: stats.start_offset,
+                    high_watermark: stats.high_watermark,
+                    latest: partition.leo,
+                })
+            })
+            .collect();
+        let consumers = fluvio
+            .consumer_offsets()
+            .await?
+            .into_iter()
+            .filter(|consumer| topics.is_empty() || topics.contains(&consumer.topic))
+            .collect();
+
+        Ok(Self {
+            taken,
+            spus,
+            partitions,
+            lags: lag_of(consumers, offsets),
+        })
+    }
+
+    /// partitions led, and throughput of partitions led, per SPU
+    fn spu_traffic(&self) -> BTreeMap<SpuId, (usize, Option<Rate>)> {
+        let mut traffic: BTreeMap<SpuId, (usize, Option<Rate>)> =
+            self.spus.iter().map(|(spu, _)| (*spu, (0, None))).collect();
+        for partition in &self.partitions {
+            let (leaders, rate) = traffic.entry(partition.leader).or_default();
+            *leaders += 1;
+            if let Some(partition_rate) = &partition.rate {
+                rate.get_or_insert_with(Rate::default).merge(partition_rate);
+            }
+        }
+        traffic
+    }
+
+    /// partitions, size and throughput per topic
+    fn topic_traffic(&self) -> BTreeMap<&str, (usize, u64, Option<Rate>)> {
+        let mut traffic: BTreeMap<&str, (usize, u64, Option<Rate>)> = BTreeMap::new();
+        for partition in &self.partitions {
+            let (partitions, size, rate) = traffic.entry(partition.topic.as_str()).or_default();
+            *partitions += 1;
+            *size += partition.stats.as_ref().map_or(0, |stats| stats.size);
+            if let Some(partition_rate) = &partition.rate {
+                rate.get_or_insert_with(Rate::default).merge(partition_rate);
+            }
+        }
+        traffic
+    }
+}
+
+fn set_rates(partitions: &mut [PartitionInfo], previous: &Snapshot, taken: Instant) {
+    let elapsed = taken.saturating_duration_since(previous.taken);
+    let previous_stats: HashMap<(&str, PartitionId), &PartitionStats> = previous
+        .partitions
+        .iter()
+        .filter_map(|partition| {
+            partition
+                .stats
+                .as_ref()
+                .map(|stats| ((partition.topic.as_str(), partition.partition), stats))
+        })
+        .collect();
+    for partition in partitions {
+        partition.rate = match (
+            previous_stats.get(&(partition.topic.as_str(), partition.partition)),
+            &partition.stats,
+        ) {
+            (Some(previous), Some(current)) => Some(Rate::between(previous, current, elapsed)),
+            _ => None,
+        };
+    }
+}
+
+mod ui {
+
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    use tui::Frame;
+    use tui::backend::Backend;
+    use tui::layout::{Constraint, Direction, Layout, Rect};
+    use tui::style::{Color, Modifier, Style};
+    use tui::widgets::{Block, Borders, Paragraph, Row, Table};
+
+    use crate::common::output::human_size;
+
+    use super::{Rate, Snapshot};
+
+    pub(super) fn draw<B: Backend>(
+        frame: &mut Frame<B>,
+        snapshot: Option<&Snapshot>,
+        error: Option<&str>,
+        interval: Duration,
+    ) {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Percentage(50),
+                Constraint::Percentage(50),
+            ])
+            .split(frame.size());
+
+        let status = match error {
+            Some(error) => format!("refresh failed: {error}"),
+            None => format!(
+                "refreshing every {} | 'q' or ESC to exit",
+                humantime::format_duration(interval)
+            ),
+        };
+        frame.render_widget(
+            Paragraph::new(status).style(Style::default().add_modifier(Modifier::REVERSED)),
+            rows[0],
+        );
+
+        let Some(snapshot) = snapshot else {
+            return;
+        };
+        let top = split(rows[1]);
+        let bottom = split(rows[2]);
+        draw_topics(frame, top[0], snapshot);
+        draw_spus(frame, top[1], snapshot);
+        draw_partitions(frame, bottom[0], snapshot);
+        draw_lags(frame, bottom[1], snapshot);
+    }
+
+    fn split(area: Rect) -> Vec<Rect> {
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+            .split(area)
+    }
+
+    fn draw_topics<B: Backend>(frame: &mut Frame<B>, area: Rect, snapshot: &Snapshot) {
+        let rows = snapshot
+            .topic_traffic()
+            .into_iter()
+            .map(|(topic, (partitions, size, rate))| {
+                let (produced, consumed) = rates(rate);
+                Row::new([
+                    topic.to_owned(),
+                    partitions.to_string(),
+                    human_size(size),
+                    produced,
+                    consumed,
+                ])
+            })
+            .collect::<Vec<_>>();
+        let widths = [
+            Constraint::Percentage(32),
+            Constraint::Percentage(12),
+            Constraint::Percentage(16),
+            Constraint::Percentage(20),
+            Constraint::Percentage(20),
+        ];
+        frame.render_widget(
+            table(
+                "Topics",
+                ["TOPIC", "PARTITIONS", "SIZE", "PRODUCE/S", "CONSUME/S"],
+                rows,
+                &widths,
+            ),
+            area,
+        );
+    }
+
+    fn draw_spus<B: Backend>(frame: &mut Frame<B>, area: Rect, snapshot: &Snapshot) {
+        let online = snapshot.spus.iter().copied().collect::<HashMap<_, _>>();
+        let rows = snapshot
+            .spu_traffic()
+            .into_iter()
+            .map(|(spu, (leaders, rate))| {
+                let (produced, consumed) = rates(rate);
+                let status = match online.get(&spu) {
+                    Some(true) => "Online",
+                    Some(false) => "Offline",
+                    None => "Unknown",
+                };
+                Row::new([
+                    spu.to_string(),
+                    status.to_owned(),
+                    leaders.to_string(),
+                    produced,
+                    consumed,
+                ])
+            })
+            .collect::<Vec<_>>();
+        let widths = [
+            Constraint::Percentage(15),
+            Constraint::Percentage(20),
+            Constraint::Percentage(15),
+            Constraint::Percentage(25),
+            Constraint::Percentage(25),
+        ];
+        frame.render_widget(
+            table(
+                "SPUs",
+                ["SPU", "STATUS", "LEADERS", "PRODUCE/S", "CONSUME/S"],
+                rows,
+                &widths,
+            ),
+            area,
+        );
+    }
+
+    fn draw_partitions<B: Backend>(frame: &mut Frame<B>, area: Rect, snapshot: &Snapshot) {
+        let rows = snapshot
+            .partitions
+            .iter()
+            .map(|partition| {
+                let (produced, _) = rates(partition.rate);
+                let row = Row::new([
+                    partition.topic.clone(),
+                    partition.partition.to_string(),
+                    partition.leader.to_string(),
+                    partition.resolution.clone(),
+                    format!("{}/{}", partition.lrs, partition.replicas.saturating_sub(1)),
+                    partition
+                        .stats
+                        .as_ref()
+                        .map_or_else(|| "-".to_owned(), |stats| stats.high_watermark.to_string()),
+                    produced,
+                ]);
+                if partition.resolution == "Online" {
+                    row
+                } else {
+                    row.style(Style::default().fg(Color::Red))
+                }
+            })
+            .collect::<Vec<_>>();
+        let widths = [
+            Constraint::Percentage(25),
+            Constraint::Percentage(10),
+            Constraint::Percentage(10),
+            Constraint::Percentage(17),
+            Constraint::Percentage(8),
+            Constraint::Percentage(12),
+            Constraint::Percentage(18),
+        ];
+        frame.render_widget(
+            table(
+                "Partitions",
+                [
+                    "TOPIC",
+                    "PARTITION",
+                    "LEADER",
+                    "STATUS",
+                    "LRS",
+                    "HW",
+                    "PRODUCE/S",
+                ],
+                rows,
+                &widths,
+            ),
+            area,
+        );
+    }
+
+    fn draw_lags<B: Backend>(frame: &mut Frame<B>, area: Rect, snapshot: &Snapshot) {
+        let rows = snapshot
+            .lags
+            .iter()
+            .map(|lag| {
+                Row::new([
+                    lag.consumer_id.clone(),
+                    lag.topic.clone(),
+                    lag.partition.to_string(),
+                    lag.lag
+                        .map_or_else(|| "-".to_owned(), |lag| lag.to_string()),
+                ])
+            })
+            .collect::<Vec<_>>();
+        let widths = [
+            Constraint::Percentage(35),
+            Constraint::Percentage(35),
+            Constraint::Percentage(15),
+            Constraint::Percentage(15),
+        ];
+        frame.render_widget(
+            table(
+                "Consumer Lag",
+                ["CONSUMER", "TOPIC", "PARTITION", "LAG"],
+                rows,
+                &widths,
+            ),
+            area,
+        );
+    }
+
+    fn table<'a, const N: usize>(
+        title: &'a str,
+        header: [&'a str; N],
+        rows: Vec<Row<'a>>,
+        widths: &'a [Constraint],
+    ) -> Table<'a> {
+        Table::new(rows)
+            .header(
+                Row::new(header).style(
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            )
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .widths(widths)
+    }
+
+    /// produced and consumed throughput, unknown until second refresh
+    fn rates(rate: Option<Rate>) -> (String, String) {
+        match rate {
+            Some(rate) => (per_second(rate.produced), per_second(rate.consumed)),
+            None => ("-".to_owned(), "-".to_owned()),
+        }
+    }
+
+    fn per_second(bytes: f64) -> String {
+        format!("{}/s", human_size(bytes.round() as u64))
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    fn partition(topic: &str, leader: SpuId, produced_bytes: u64) -> PartitionInfo {
+        PartitionInfo {
+            topic: topic.to_owned(),
+            partition: 0,
+            leader,
+            resolution: "Online".to_owned(),
+            replicas: 1,
+            lrs: 0,
+            leo: 0,
+            stats: Some(PartitionStats {
+                produced_bytes,
+                ..Default::default()
+            }),
+            rate: None,
+        }
+    }
+
+    #[test]
+    fn test_spu_throughput() {
+        let start = Instant::now();
+        let previous = Snapshot {
+            taken: start,
+            spus: vec![(5001, true), (5002, true)],
+            partitions: vec![partition("orders", 5001, 1000), partition("users", 5001, 0)],
+            lags: vec![],
+        };
+        let mut partitions = vec![
+            partition("orders", 5001, 3000),
+            partition("users", 5001, 1000),
+            partition("events", 5002, 500),
+        ];
+        set_rates(&mut partitions, &previous, start + Duration::from_secs(2));
+        let current = Snapshot {
+            taken: start + Duration::from_secs(2),
+            spus: previous.spus.clone(),
+            partitions,
+            lags: vec![],
+        };
+
+        let traffic = current.spu_traffic();
+        let (leaders, rate) = traffic[&5001];
+        assert_eq!(leaders, 2);
+        assert_eq!(rate.expect("rate").produced, 1500.0);
+        // partition without previous stats has no rate yet
+        assert_eq!(traffic[&5002], (1, None));
+
+        let topics = current.topic_traffic();
+        assert_eq!(topics["orders"].2.expect("rate").produced, 1000.0);
+    }
+}
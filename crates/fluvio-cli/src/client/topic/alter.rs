@@ -0,0 +1,95 @@
+//!
+//! # Alter Topic
+//!
+//! Changes settings of existing topic, such as retention, compression and deduplication.
+//! Settings not given are kept. SPUs apply changed settings to partitions without restart.
+//!
+use std::time::Duration;
+
+use clap::Parser;
+use humantime::parse_duration;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::topic::CompressionAlgorithm;
+use fluvio_sc_schema::topic::{AlterTopicConfig, TopicSpec, UpdateTopicAction};
+
+use crate::CliError;
+use super::create::{create_deduplication, ensure_dedup_filter};
+
+/// Option for altering topic settings
+#[derive(Debug, Parser)]
+pub struct AlterTopicOpt {
+    /// Topic name
+    topic: String,
+
+    /// Retention time (round to seconds)
+    /// Ex: '1h', '2d 10s', '7 days'
+    #[arg(long, value_name = "time", value_parser=parse_duration)]
+    retention_time: Option<Duration>,
+
+    /// Max partition size (by default measured in bytes)
+    /// Ex: `2048`, '2 Ki', '10 MiB', `1 GB`
+    #[arg(long, value_name = "bytes")]
+    max_partition_size: Option<bytesize::ByteSize>,
+
+    /// Compression of records: `none`, `gzip`, `snappy`, `lz4`, `zstd` or `any`
+    #[arg(long, visible_alias = "compression", value_name = "compression")]
+    compression_type: Option<CompressionAlgorithm>,
+
+    /// Deduplicate records in the topic
+    #[arg(long, conflicts_with = "no_dedup")]
+    dedup: bool,
+
+    /// Number of records to keep in deduplication filter
+    #[arg(long, value_name = "integer", requires = "dedup", default_value = "5")]
+    dedup_count: u64,
+
+    /// Age of records to keep in deduplication filter
+    /// Ex: '1h', '2d 10s', '7 days'
+    #[arg(long, value_name = "time", value_parser=parse_duration, requires = "dedup", default_value = "5s")]
+    dedup_age: Duration,
+
+    /// Stop deduplicating records in the topic
+    #[arg(long)]
+    no_dedup: bool,
+
+    /// Max size of batch accepted by SPU, which bounds size of records (by default measured in bytes)
+    /// Ex: `2048`, '2 Ki', '10 MiB'
+    #[arg(long, visible_alias = "max-record-size", value_name = "bytes")]
+    max_batch_size: Option<bytesize::ByteSize>,
+}
+
+impl AlterTopicOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+
+        let mut alter = AlterTopicConfig {
+            retention_time_seconds: self.retention_time.map(|time| time.as_secs() as u32),
+            max_partition_size: self.max_partition_size.map(|size| size.as_u64()),
+            compression_type: self.compression_type,
+            remove_deduplication: self.no_dedup,
+            max_batch_size: self.max_batch_size.map(|size| size.as_u64() as u32),
+            ..Default::default()
+        };
+        if self.dedup {
+            alter.deduplication =
+                Some(create_deduplication(self.dedup_count, Some(self.dedup_age)));
+        }
+        if alter.is_empty() {
+            return Err(
+                CliError::InvalidArg("no settings to change were given".to_string()).into(),
+            );
+        }
+        if self.dedup {
+            ensure_dedup_filter(&admin).await?;
+        }
+
+        admin
+            .update::<TopicSpec>(self.topic.clone(), UpdateTopicAction::AlterConfig(alter))
+            .await?;
+
+        println!("topic \"{}\" updated", self.topic);
+        Ok(())
+    }
+}
@@ -0,0 +1,262 @@
+//!
+//! # Copy records between topics
+//!
+//! Copies records of a topic to another topic, in same cluster or in cluster of another profile.
+//! Keys, timestamps and headers of records are preserved, traced records get target topic
+//! appended to their hops. Next offset of each source partition is saved
+//! to a checkpoint file after records are flushed to target topic, so interrupted copy resumes
+//! from checkpoint instead of beginning.
+//!
+//! Records can be re-keyed while copied, with key taken from JSON value,
+//! so target topic is partitioned by the new key.
+//!
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::Parser;
+use futures::StreamExt;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::debug;
+
+use fluvio::{Fluvio, Offset, PartitionId, RecordKey, TopicProducerPool};
+use fluvio::config::ConfigFile;
+use fluvio::consumer::ConsumerConfigExt;
+use fluvio::metadata::topic::TopicSpec;
+use fluvio_protocol::record::NO_TIMESTAMP;
+
+use crate::CliError;
+
+/// records copied between checkpoints
+const DEFAULT_CHECKPOINT_INTERVAL: usize = 1000;
+
+/// Option for copying records of a topic to another topic
+#[derive(Debug, Parser)]
+pub struct CopyTopicOpt {
+    /// Topic to copy records from
+    #[arg(value_name = "SOURCE")]
+    source: String,
+
+    /// Topic to copy records to, must exist
+    #[arg(value_name = "TARGET")]
+    target: String,
+
+    /// Profile of cluster of target topic, current cluster by default
+    #[arg(long, value_name = "PROFILE")]
+    target_profile: Option<String>,
+
+    /// Checkpoint file, default is "<SOURCE>-<TARGET>.checkpoint.json" in current directory
+    #[arg(long, value_name = "PATH")]
+    checkpoint: Option<PathBuf>,
+
+    /// Number of records copied between checkpoints
+    #[arg(long, value_name = "RECORDS", default_value_t = DEFAULT_CHECKPOINT_INTERVAL)]
+    checkpoint_interval: usize,
+
+    /// Keep copying new records instead of stopping at end of source topic
+    #[arg(long)]
+    follow: bool,
+
+    /// JSON pointer of new key in record values, ex: /account_id.
+    /// Records without value at pointer keep their key
+    #[arg(long, value_name = "POINTER")]
+    key_pointer: Option<String>,
+}
+
+impl CopyTopicOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let partitions = source_partitions(fluvio, &self.source).await?;
+        let checkpoint_path = self.checkpoint_path();
+        let mut checkpoint = Checkpoint::load(&checkpoint_path)?;
+        debug!(?checkpoint, path = %checkpoint_path.display(), "loaded checkpoint");
+
+        let target_fluvio = match &self.target_profile {
+            Some(profile) => Some(connect_profile(profile).await?),
+            None => None,
+        };
+        let producer = target_fluvio
+            .as_ref()
+            .unwrap_or(fluvio)
+            .topic_producer(self.target.clone())
+            .await?;
+
+        let mut streams = Vec::with_capacity(partitions as usize);
+        for partition in 0..partitions {
+            let offset = match checkpoint.next_offset(partition) {
+                Some(offset) => Offset::absolute(offset)?,
+                None => Offset::beginning(),
+            };
+            let mut builder = ConsumerConfigExt::builder();
+            builder
+                .topic(&self.source)
+                .partition(partition)
+                .offset_start(offset)
+                .disable_continuous(!self.follow);
+            streams.push(Box::pin(
+                fluvio.consumer_with_config(builder.build()?).await?,
+            ));
+        }
+        let mut records = futures::stream::select_all(streams);
+
+        let mut copied = 0;
+        let mut pending = 0;
+        while let Some(record) = records.next().await {
+            let record = record?;
+            let key = match self
+                .key_pointer
+                .as_deref()
+                .and_then(|pointer| json_key(record.value(), pointer))
+            {
+                Some(key) => RecordKey::from(key),
+                None => record.key().map_or(RecordKey::NULL, RecordKey::from),
+            };
+            let headers = record.inner().headers.clone();
+            let timestamp = (record.timestamp() != NO_TIMESTAMP).then_some(record.timestamp());
+            producer
+                .send_with_headers(key, record.value().to_vec(), headers, timestamp)
+                .await?;
+            checkpoint.record_copied(record.partition(), record.offset());
+            copied += 1;
+            pending += 1;
+
+            if pending >= self.checkpoint_interval {
+                save_checkpoint(&producer, &checkpoint, &checkpoint_path).await?;
+                pending = 0;
+            }
+        }
+        save_checkpoint(&producer, &checkpoint, &checkpoint_path).await?;
+
+        println!(
+            "copied {copied} records from topic \"{}\" to topic \"{}\"",
+            self.source, self.target
+        );
+        Ok(())
+    }
+
+    fn checkpoint_path(&self) -> PathBuf {
+        self.checkpoint.clone().unwrap_or_else(|| {
+            PathBuf::from(format!("{}-{}.checkpoint.json", self.source, self.target))
+        })
+    }
+}
+
+/// key at JSON pointer of value, strings are used without quotes
+fn json_key(value: &[u8], pointer: &str) -> Option<String> {
+    let value: Value = serde_json::from_slice(value).ok()?;
+    match value.pointer(pointer)? {
+        Value::String(key) => Some(key.clone()),
+        Value::Null => None,
+        key => Some(key.to_string()),
+    }
+}
+
+async fn source_partitions(fluvio: &Fluvio, topic: &str) -> Result<PartitionId> {
+    let admin = fluvio.admin().await;
+    let topics = admin.list::<TopicSpec, _>(vec![topic.to_owned()]).await?;
+    let topic_meta = topics
+        .into_iter()
+        .find(|meta| meta.name == topic)
+        .ok_or_else(|| CliError::Other(format!("topic \"{topic}\" not found")))?;
+    Ok(topic_meta.spec.partitions())
+}
+
+async fn connect_profile(profile: &str) -> Result<Fluvio> {
+    let config_file = ConfigFile::load(None)?;
+    let cluster_config = config_file
+        .config()
+        .cluster_with_profile(profile)
+        .ok_or_else(|| CliError::ProfileNotFoundInConfig(profile.to_owned()))?;
+    let fluvio = Fluvio::connect_with_config(cluster_config).await?;
+    Ok(fluvio)
+}
+
+/// checkpoint is saved only after copied records are acknowledged by target cluster
+async fn save_checkpoint(
+    producer: &TopicProducerPool,
+    checkpoint: &Checkpoint,
+    path: &Path,
+) -> Result<()> {
+    producer.flush().await?;
+    checkpoint.save(path)?;
+    debug!(?checkpoint, "saved checkpoint");
+    Ok(())
+}
+
+/// Next offset to copy of each source partition
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+struct Checkpoint {
+    partitions: BTreeMap<PartitionId, i64>,
+}
+
+impl Checkpoint {
+    /// empty checkpoint if file doesn't exist
+    fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("unable to read checkpoint {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("invalid checkpoint {}", path.display()))
+    }
+
+    /// write to temporary file and rename it, so checkpoint is never partially written
+    fn save(&self, path: &Path) -> Result<()> {
+        let mut tmp_path = path.as_os_str().to_owned();
+        tmp_path.push(".tmp");
+        fs::write(&tmp_path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("unable to write checkpoint {}", path.display()))?;
+        fs::rename(&tmp_path, path)
+            .with_context(|| format!("unable to write checkpoint {}", path.display()))?;
+        Ok(())
+    }
+
+    fn next_offset(&self, partition: PartitionId) -> Option<i64> {
+        self.partitions.get(&partition).copied()
+    }
+
+    fn record_copied(&mut self, partition: PartitionId, offset: i64) {
+        self.partitions.insert(partition, offset + 1);
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_checkpoint() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let path = temp.path().join("copy.checkpoint.json");
+        assert_eq!(
+            Checkpoint::load(&path).expect("load"),
+            Checkpoint::default()
+        );
+
+        let mut checkpoint = Checkpoint::default();
+        checkpoint.record_copied(0, 9);
+        checkpoint.record_copied(1, 4);
+        checkpoint.record_copied(0, 10);
+        checkpoint.save(&path).expect("save");
+
+        let loaded = Checkpoint::load(&path).expect("load");
+        assert_eq!(loaded, checkpoint);
+        assert_eq!(loaded.next_offset(0), Some(11));
+        assert_eq!(loaded.next_offset(1), Some(5));
+        assert_eq!(loaded.next_offset(2), None);
+    }
+
+    #[test]
+    fn test_json_key() {
+        let value = br#"{"user_id": "u1", "account": {"id": 42}, "region": null}"#;
+        assert_eq!(json_key(value, "/user_id"), Some("u1".to_owned()));
+        assert_eq!(json_key(value, "/account/id"), Some("42".to_owned()));
+        assert_eq!(json_key(value, "/region"), None);
+        assert_eq!(json_key(value, "/missing"), None);
+        assert_eq!(json_key(b"not json", "/user_id"), None);
+    }
+}
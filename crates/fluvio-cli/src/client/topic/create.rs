@@ -19,6 +19,8 @@ use fluvio::metadata::topic::ReplicaSpec;
 use fluvio::metadata::topic::SegmentBasedPolicy;
 use fluvio::metadata::topic::TopicStorageConfig;
 use fluvio::metadata::topic::CompressionAlgorithm;
+use fluvio::metadata::topic::{TimestampPolicy, TimestampType};
+use fluvio::metadata::clusterconfig::{ClusterConfigSpec, DEFAULT_TOPIC_REPLICATION};
 
 use fluvio_controlplane_metadata::topic::config::TopicConfig;
 use fluvio_sc_schema::shared::validate_resource_name;
@@ -73,14 +75,17 @@ pub struct CreateTopicOpt {
     /// This applies to each Partition in the Topic. If we have
     /// 3 partitions and a replication factor of 2, then all 3
     /// of the partitions must exist on at least 2 SPUs.
+    ///
+    /// Defaults to replication of cluster config, which is 1 unless changed
+    /// with `fluvio cluster config set`.
     #[arg(
         short = 'r',
         long = "replication",
         value_name = "integer",
-        default_value = "1",
+        value_parser = clap::value_parser!(i16).range(1..),
         group = "config-arg"
     )]
-    replication: i16,
+    replication: Option<i16>,
 
     /// Ignore racks while computing replica assignment
     #[arg(
@@ -221,9 +226,13 @@ impl CreateTopicOpt {
             let mirror_map = MirrorConfig::Home(home_mirror);
             ReplicaSpec::Mirror(mirror_map)
         } else {
+            let replication_factor = match self.replication {
+                Some(replication) => replication as ReplicationFactor,
+                None => default_replication(admin).await,
+            };
             ReplicaSpec::Computed(TopicReplicaParam {
                 partitions: self.partitions,
-                replication_factor: self.replication as ReplicationFactor,
+                replication_factor,
                 ignore_rack_assignment: self.ignore_rack_assignment,
             })
         };
@@ -240,28 +249,48 @@ impl CreateTopicOpt {
         }
 
         if self.setting.dedup {
-            let sm = admin
-                .list::<SmartModuleSpec, _>(vec![DEFAULT_DEDUP_FILTER.to_string()])
-                .await?
-                .into_iter()
-                .next();
-
-            if sm.is_none() {
-                println!("deduplication filter not found, downloading");
-                let access = get_hub_access(&None)?;
-                let pkgname = DEFAULT_DEDUP_FILTER;
-                let pkgfile = download_local(pkgname, &access, None).await?;
-                download_cluster(admin, &pkgfile).await?;
-            }
-
+            ensure_dedup_filter(admin).await?;
             let deduplication =
                 create_deduplication(self.setting.dedup_count, Some(self.setting.dedup_age));
             topic_spec.set_deduplication(Some(deduplication));
         }
 
+        if self.setting.timestamp_type.is_some() || self.setting.max_timestamp_skew.is_some() {
+            if self.setting.timestamp_type == Some(TimestampType::LogAppendTime)
+                && self.setting.max_timestamp_skew.is_some()
+            {
+                return Err(CliError::InvalidArg(
+                    "--max-timestamp-skew only applies to create-time timestamps".to_string(),
+                )
+                .into());
+            }
+            topic_spec.set_timestamp_policy(TimestampPolicy {
+                type_: self.setting.timestamp_type.unwrap_or_default(),
+                max_skew: self.setting.max_timestamp_skew,
+            });
+        }
+
+        if let Some(format_version) = self.setting.format_version {
+            topic_spec.set_format_version(format_version);
+        }
+
+        topic_spec.set_transforms(
+            self.setting
+                .transforms
+                .into_iter()
+                .map(|uses| Transform {
+                    uses,
+                    with: Default::default(),
+                })
+                .collect(),
+        );
+
         topic_spec.set_system(self.setting.system);
 
-        if self.setting.segment_size.is_some() || self.setting.max_partition_size.is_some() {
+        if self.setting.segment_size.is_some()
+            || self.setting.max_partition_size.is_some()
+            || self.setting.local_retention_time.is_some()
+        {
             let mut storage = TopicStorageConfig::default();
 
             if let Some(segment_size) = self.setting.segment_size {
@@ -272,6 +301,10 @@ impl CreateTopicOpt {
                 storage.max_partition_size = Some(max_partition_size.as_u64());
             }
 
+            if let Some(local_retention) = self.setting.local_retention_time {
+                storage.local_retention_seconds = Some(local_retention.as_secs() as u32);
+            }
+
             topic_spec.set_storage(storage);
         }
 
@@ -279,6 +312,18 @@ impl CreateTopicOpt {
     }
 }
 
+/// replication of topic created without `-r`. 0 is replaced with cluster config default by SC,
+/// SC without cluster config gets default of previous versions
+async fn default_replication(admin: &FluvioAdmin) -> ReplicationFactor {
+    match admin.list::<ClusterConfigSpec, String>(vec![]).await {
+        Ok(_) => 0,
+        Err(err) => {
+            debug!(%err, "cluster config is not supported, using default replication");
+            DEFAULT_TOPIC_REPLICATION
+        }
+    }
+}
+
 fn validate(name: &str, _spec: &TopicSpec) -> Result<()> {
     if name.trim().is_empty() {
         return Err(CliError::InvalidArg("Topic name is required".to_string()).into());
@@ -288,7 +333,25 @@ fn validate(name: &str, _spec: &TopicSpec) -> Result<()> {
     Ok(())
 }
 
-fn create_deduplication(dedup_count: u64, dedup_age: Option<Duration>) -> Deduplication {
+/// download deduplication filter to cluster, if it is not there
+pub(crate) async fn ensure_dedup_filter(admin: &FluvioAdmin) -> Result<()> {
+    let sm = admin
+        .list::<SmartModuleSpec, _>(vec![DEFAULT_DEDUP_FILTER.to_string()])
+        .await?
+        .into_iter()
+        .next();
+
+    if sm.is_none() {
+        println!("deduplication filter not found, downloading");
+        let access = get_hub_access(&None)?;
+        let pkgname = DEFAULT_DEDUP_FILTER;
+        let pkgfile = download_local(pkgname, &access, None).await?;
+        download_cluster(admin, &pkgfile).await?;
+    }
+    Ok(())
+}
+
+pub(crate) fn create_deduplication(dedup_count: u64, dedup_age: Option<Duration>) -> Deduplication {
     Deduplication {
         bounds: Bounds {
             count: dedup_count,
@@ -316,8 +379,9 @@ pub struct TopicConfigOpt {
     #[arg(long, value_name = "bytes")]
     segment_size: Option<bytesize::ByteSize>,
 
-    /// Compression configuration for topic
-    #[arg(long, value_name = "compression")]
+    /// Compression of records: `none`, `gzip`, `snappy`, `lz4`, `zstd` or `any` (default).
+    /// Producers compress records with it, SPU compresses records of producers which can't
+    #[arg(long, visible_alias = "compression", value_name = "compression")]
     compression_type: Option<CompressionAlgorithm>,
 
     /// Max partition size (by default measured in bytes)
@@ -325,6 +389,11 @@ pub struct TopicConfigOpt {
     #[arg(long, value_name = "bytes")]
     max_partition_size: Option<bytesize::ByteSize>,
 
+    /// Time segments are kept on SPU disk before they are offloaded to tiered storage.
+    /// Requires SPUs to be started with tiered storage. Ex: '1h', '2d'
+    #[arg(long, value_name = "time", value_parser=parse_duration)]
+    local_retention_time: Option<Duration>,
+
     /// Deduplicate records in the topic
     #[arg(long)]
     dedup: bool,
@@ -338,6 +407,26 @@ pub struct TopicConfigOpt {
     #[arg(long, value_name = "time", value_parser=parse_duration, requires = "dedup", default_value = "5s")]
     dedup_age: Duration,
 
+    /// Timestamp stored with records: producer assigned `create-time` (default)
+    /// or `log-append-time` set by SPU when records are appended
+    #[arg(long, value_name = "type")]
+    timestamp_type: Option<TimestampType>,
+
+    /// Reject records whose producer timestamp differs from SPU clock by more than this.
+    /// Only applies to `create-time`. Ex: '5m', '1h'
+    #[arg(long, value_name = "time", value_parser=parse_duration)]
+    max_timestamp_skew: Option<Duration>,
+
+    /// Version of record payloads, stamped on records by producers.
+    /// Consumers upcast records of older versions with upcasters registered in client
+    #[arg(long, value_name = "version")]
+    format_version: Option<u32>,
+
+    /// SmartModule applied by consumers of the topic, unless they opt out with `--no-default-transforms`.
+    /// Can be repeated, transforms are applied in order
+    #[arg(long = "transform", value_name = "smartmodule")]
+    transforms: Vec<String>,
+
     /// Flag to create a system topic
     /// System topics are for internal operations
     #[arg(long, short = 's', hide = true)]
@@ -431,3 +520,21 @@ mod load {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+
+    use clap::Parser;
+
+    use super::CreateTopicOpt;
+
+    #[test]
+    fn test_replication_arg() {
+        let opt = CreateTopicOpt::try_parse_from(["create", "orders"]).expect("parse");
+        assert_eq!(opt.replication, None);
+        let opt = CreateTopicOpt::try_parse_from(["create", "orders", "-r", "2"]).expect("parse");
+        assert_eq!(opt.replication, Some(2));
+        assert!(CreateTopicOpt::try_parse_from(["create", "orders", "-r", "0"]).is_err());
+        assert!(CreateTopicOpt::try_parse_from(["create", "orders", "-r", "-1"]).is_err());
+    }
+}
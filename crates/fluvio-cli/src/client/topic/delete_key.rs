@@ -0,0 +1,133 @@
+//!
+//! # Delete records of a key
+//!
+//! Writes tombstone for a key, a record with the key and empty value.
+//! Prior values of the key stay readable until they are removed from storage,
+//! by compaction, retention or partition truncation, so command can wait and report when
+//! no prior value is left.
+//!
+use std::time::{Duration, Instant};
+
+use clap::Parser;
+use futures::StreamExt;
+use anyhow::{anyhow, Result};
+use tracing::debug;
+
+use fluvio_future::timer::sleep;
+use fluvio::{Fluvio, Offset, PartitionId};
+use fluvio::consumer::ConsumerConfigExt;
+
+const REMOVAL_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Option for deleting records of a key
+#[derive(Debug, Parser)]
+pub struct DeleteKeyOpt {
+    /// Topic name
+    topic: String,
+
+    /// Key to delete
+    #[arg(short, long, value_name = "KEY")]
+    key: String,
+
+    /// Wait until prior values of key are removed from storage
+    #[arg(long)]
+    wait: bool,
+
+    /// Max time to wait for prior values to be removed, ex: 24h
+    #[arg(long, value_name = "DURATION", default_value = "1h", requires = "wait")]
+    wait_timeout: humantime::Duration,
+}
+
+impl DeleteKeyOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let producer = fluvio.topic_producer(self.topic.clone()).await?;
+        let output = producer.send(self.key.clone(), Vec::<u8>::new()).await?;
+        producer.flush().await?;
+        let metadata = output.wait().await?;
+        let (partition, offset) = (metadata.partition_id(), metadata.offset());
+        println!(
+            "tombstone for key \"{}\" written to partition \"{}-{partition}\" at offset {offset}",
+            self.key, self.topic
+        );
+
+        let started = Instant::now();
+        loop {
+            let remaining = self.prior_values(fluvio, partition, offset).await?;
+            if remaining == 0 {
+                println!("no prior values of key \"{}\" are stored", self.key);
+                return Ok(());
+            }
+            if !self.wait {
+                println!(
+                    "{remaining} prior values of key \"{}\" are stored until they are compacted or expired",
+                    self.key
+                );
+                return Ok(());
+            }
+            if started.elapsed() >= *self.wait_timeout {
+                return Err(anyhow!(
+                    "{remaining} prior values of key \"{}\" still stored after {}",
+                    self.key,
+                    self.wait_timeout
+                ));
+            }
+            debug!(remaining, "waiting for prior values to be removed");
+            sleep(REMOVAL_POLL_INTERVAL).await;
+        }
+    }
+
+    /// number of readable records of key before tombstone
+    async fn prior_values(
+        &self,
+        fluvio: &Fluvio,
+        partition: PartitionId,
+        tombstone_offset: i64,
+    ) -> Result<usize> {
+        let mut builder = ConsumerConfigExt::builder();
+        builder
+            .topic(&self.topic)
+            .partition(partition)
+            .offset_start(Offset::beginning())
+            .disable_continuous(true);
+        let mut stream = fluvio.consumer_with_config(builder.build()?).await?;
+
+        let mut remaining = 0;
+        while let Some(record) = stream.next().await {
+            let record = record?;
+            if record.offset() >= tombstone_offset {
+                break;
+            }
+            if is_value_of_key(record.key(), record.value(), self.key.as_bytes()) {
+                remaining += 1;
+            }
+        }
+        Ok(remaining)
+    }
+}
+
+/// record with key and value, tombstones don't count
+fn is_value_of_key(record_key: Option<&[u8]>, value: &[u8], key: &[u8]) -> bool {
+    record_key == Some(key) && !value.is_empty()
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_is_value_of_key() {
+        assert!(is_value_of_key(
+            Some(b"user-1".as_slice()),
+            b"{}",
+            b"user-1"
+        ));
+        assert!(!is_value_of_key(Some(b"user-1".as_slice()), b"", b"user-1"));
+        assert!(!is_value_of_key(
+            Some(b"user-2".as_slice()),
+            b"{}",
+            b"user-1"
+        ));
+        assert!(!is_value_of_key(None, b"{}", b"user-1"));
+    }
+}
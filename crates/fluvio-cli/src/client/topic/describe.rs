@@ -1,7 +1,9 @@
 //!
 //! # Describe Topic CLI
 //!
-//! CLI to describe Topics and their corresponding Partitions
+//! CLI to describe Topics and their corresponding Partitions,
+//! or record schema inferred from latest records.
+//! Serialized output includes leader, replicas and offsets of each partition.
 //!
 
 use std::sync::Arc;
@@ -11,11 +13,15 @@ use clap::Parser;
 use anyhow::Result;
 
 use fluvio::Fluvio;
+use fluvio::metadata::partition::PartitionSpec;
 use fluvio::metadata::topic::TopicSpec;
+use fluvio_protocol::record::ReplicaKey;
 
-use crate::common::output::Terminal;
+use crate::common::output::{Terminal, set_raw_units};
 use crate::common::OutputFormat;
 
+use super::schema::{SchemaFormat, print_inferred_schema};
+
 // -----------------------------------
 // CLI Options
 // -----------------------------------
@@ -28,6 +34,18 @@ pub struct DescribeTopicsOpt {
 
     #[clap(flatten)]
     output: OutputFormat,
+
+    /// Show sizes in bytes and durations in seconds, instead of human readable units
+    #[arg(long)]
+    raw: bool,
+
+    /// Print record schema inferred from latest records instead of topic
+    #[arg(long = "format", value_name = "schema", value_enum)]
+    schema_format: Option<SchemaFormat>,
+
+    /// Number of latest records sampled from each partition to infer schema
+    #[arg(long, default_value = "100", requires = "schema_format")]
+    sample_size: u32,
 }
 
 impl DescribeTopicsOpt {
@@ -35,11 +53,30 @@ impl DescribeTopicsOpt {
         let topic = self.topic;
         let output_type = self.output.format;
         debug!("describe topic: {}, {:?}", topic, output_type);
+        set_raw_units(self.raw);
+
+        if let Some(format) = self.schema_format {
+            return print_inferred_schema(fluvio, &topic, self.sample_size, format).await;
+        }
 
         let admin = fluvio.admin().await;
-        let topics = admin.list::<TopicSpec, _>(vec![topic]).await?;
+        let topics = admin.list::<TopicSpec, _>(vec![topic.clone()]).await?;
+
+        // partitions are only part of serialized output
+        let partitions = if output_type.is_table() || topics.is_empty() {
+            vec![]
+        } else {
+            admin
+                .all::<PartitionSpec>()
+                .await?
+                .into_iter()
+                .filter(|partition| {
+                    ReplicaKey::try_from(partition.name.clone()).is_ok_and(|key| key.topic == topic)
+                })
+                .collect()
+        };
 
-        display::describe_topics(topics, output_type, out).await?;
+        display::describe_topics(topics, partitions, output_type, out).await?;
         Ok(())
     }
 }
@@ -48,33 +85,100 @@ mod display {
 
     use fluvio::metadata::topic::ReplicaSpec;
     use comfy_table::Row;
-    use humantime::format_duration;
     use serde::Serialize;
 
     use fluvio::metadata::objects::Metadata;
+    use fluvio::metadata::partition::{PartitionResolution, PartitionSpec, PartitionStatus};
     use fluvio::metadata::topic::TopicSpec;
+    use fluvio_protocol::record::ReplicaKey;
+    use fluvio_types::{PartitionId, SpuId};
 
     use crate::common::output::{
         OutputType, OutputError, DescribeObjectHandler, KeyValOutputHandler, TableOutputHandler,
-        Terminal,
+        Terminal, format_duration,
     };
 
     #[allow(clippy::redundant_closure)]
     // Connect to Controller and query server for topic
     pub async fn describe_topics<O>(
         topics: Vec<Metadata<TopicSpec>>,
+        partitions: Vec<Metadata<PartitionSpec>>,
         output_type: OutputType,
         out: std::sync::Arc<O>,
     ) -> Result<(), OutputError>
     where
         O: Terminal,
     {
-        let topic_list: Vec<TopicMetadata> = topics.into_iter().map(|m| TopicMetadata(m)).collect();
+        let mut partitions: Vec<PartitionDescription> = partitions
+            .into_iter()
+            .filter_map(|partition| PartitionDescription::new(partition))
+            .collect();
+        partitions.sort_by_key(|partition| partition.partition);
+        let topic_list: Vec<TopicMetadata> = topics
+            .into_iter()
+            .map(|metadata| TopicMetadata {
+                metadata,
+                partitions: partitions.clone(),
+            })
+            .collect();
         out.describe_objects(&topic_list, output_type)
     }
 
     #[derive(Serialize, Clone)]
-    struct TopicMetadata(Metadata<TopicSpec>);
+    struct TopicMetadata {
+        #[serde(flatten)]
+        metadata: Metadata<TopicSpec>,
+        partitions: Vec<PartitionDescription>,
+    }
+
+    #[derive(Serialize, Clone, Debug, PartialEq)]
+    #[serde(rename_all = "camelCase")]
+    struct PartitionDescription {
+        partition: PartitionId,
+        resolution: PartitionResolution,
+        leader: SpuId,
+        replicas: Vec<SpuId>,
+        in_sync_replicas: Vec<SpuId>,
+        high_watermark: i64,
+        log_start_offset: i64,
+        log_end_offset: i64,
+    }
+
+    impl PartitionDescription {
+        fn new(partition: Metadata<PartitionSpec>) -> Option<Self> {
+            let key = ReplicaKey::try_from(partition.name).ok()?;
+            let Metadata { spec, status, .. } = partition;
+            Some(Self {
+                partition: key.partition,
+                in_sync_replicas: in_sync_replicas(&spec, &status),
+                resolution: status.resolution,
+                leader: spec.leader,
+                replicas: spec.replicas,
+                high_watermark: status.leader.hw,
+                log_start_offset: status.base_offset,
+                log_end_offset: status.leader.leo,
+            })
+        }
+    }
+
+    /// online leader and followers which have caught up with it, in order of replica assignment
+    fn in_sync_replicas(spec: &PartitionSpec, status: &PartitionStatus) -> Vec<SpuId> {
+        spec.replicas
+            .iter()
+            .copied()
+            .filter(|spu| {
+                if *spu == spec.leader {
+                    status.is_online() && status.leader.spu == *spu
+                } else {
+                    status.replicas.iter().any(|replica| {
+                        replica.spu == *spu
+                            && replica.leo >= 0
+                            && replica.leader_lag(&status.leader) <= 0
+                    })
+                }
+            })
+            .collect()
+    }
 
     impl DescribeObjectHandler for TopicMetadata {
         fn label() -> &'static str {
@@ -116,10 +220,10 @@ mod display {
         /// key value hash map implementation
         fn key_values(&self) -> Vec<(String, Option<String>)> {
             let mut key_values = Vec::new();
-            let spec = &self.0.spec;
-            let status = &self.0.status;
+            let spec = &self.metadata.spec;
+            let status = &self.metadata.status;
 
-            key_values.push(("Name".to_owned(), Some(self.0.name.clone())));
+            key_values.push(("Name".to_owned(), Some(self.metadata.name.clone())));
             key_values.push(("Type".to_owned(), Some(spec.type_label().to_string())));
             match spec.replicas() {
                 ReplicaSpec::Computed(param) => {
@@ -147,6 +251,11 @@ mod display {
                 ReplicaSpec::Mirror(_config) => {}
             }
 
+            key_values.push((
+                "Compression".to_owned(),
+                Some(spec.get_compression_type().to_string()),
+            ));
+
             if let Some(dedup) = spec.get_deduplication() {
                 key_values.push((
                     "Deduplication Filter".to_owned(),
@@ -161,10 +270,39 @@ mod display {
                 ));
                 key_values.push((
                     "Deduplication Age Bound".to_owned(),
-                    dedup.bounds.age.map(|a| format_duration(a).to_string()),
+                    dedup.bounds.age.map(format_duration),
                 ));
             };
 
+            if !spec.get_transforms().is_empty() {
+                let transforms: Vec<&str> = spec
+                    .get_transforms()
+                    .iter()
+                    .map(|transform| transform.uses.as_str())
+                    .collect();
+                key_values.push(("Default Transforms".to_owned(), Some(transforms.join(", "))));
+            }
+
+            let timestamp_policy = spec.get_timestamp_policy();
+            key_values.push((
+                "Timestamp Type".to_owned(),
+                Some(timestamp_policy.type_.to_string()),
+            ));
+            if let Some(max_skew) = timestamp_policy.max_skew {
+                key_values.push((
+                    "Max Timestamp Skew".to_owned(),
+                    Some(format_duration(max_skew)),
+                ));
+            }
+
+            let format_version = spec.get_format_version();
+            if format_version > 0 {
+                key_values.push((
+                    "Format Version".to_owned(),
+                    Some(format_version.to_string()),
+                ));
+            }
+
             key_values.push((
                 "Status".to_owned(),
                 Some(status.resolution.resolution_label().to_string()),
@@ -176,4 +314,45 @@ mod display {
             key_values
         }
     }
+    #[cfg(test)]
+    mod test {
+
+        use fluvio::metadata::partition::ReplicaStatus;
+
+        use super::*;
+
+        #[test]
+        fn test_partition_description() {
+            let spec = PartitionSpec::new(5001, vec![5001, 5002, 5003]);
+            let status = PartitionStatus {
+                resolution: PartitionResolution::Online,
+                leader: ReplicaStatus::new(5001, 10, 12),
+                replicas: vec![
+                    ReplicaStatus::new(5002, 10, 12),
+                    ReplicaStatus::new(5003, 4, 8),
+                ],
+                base_offset: 2,
+                ..Default::default()
+            };
+            let partition = Metadata {
+                name: "topic1-1".to_owned(),
+                spec,
+                status,
+            };
+
+            assert_eq!(
+                PartitionDescription::new(partition),
+                Some(PartitionDescription {
+                    partition: 1,
+                    resolution: PartitionResolution::Online,
+                    leader: 5001,
+                    replicas: vec![5001, 5002, 5003],
+                    in_sync_replicas: vec![5001, 5002],
+                    high_watermark: 10,
+                    log_start_offset: 2,
+                    log_end_offset: 12,
+                })
+            );
+        }
+    }
 }
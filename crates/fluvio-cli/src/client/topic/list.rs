@@ -14,7 +14,7 @@ use anyhow::Result;
 use fluvio::Fluvio;
 use fluvio::metadata::topic::TopicSpec;
 
-use crate::common::output::Terminal;
+use crate::common::output::{Terminal, set_raw_units};
 use crate::common::OutputFormat;
 
 // -----------------------------------
@@ -29,12 +29,17 @@ pub struct ListTopicsOpt {
     /// Show system topics only
     #[arg(long, short, required = false)]
     system: bool,
+
+    /// Show sizes in bytes and durations in seconds, instead of human readable units
+    #[arg(long)]
+    raw: bool,
 }
 
 impl ListTopicsOpt {
     pub async fn process<O: Terminal>(self, out: Arc<O>, fluvio: &Fluvio) -> Result<()> {
         let output_type = self.output.format;
         debug!("list topics {:#?} ", output_type);
+        set_raw_units(self.raw);
         let admin = fluvio.admin().await;
 
         let topics = admin
@@ -49,14 +54,15 @@ mod display {
 
     use std::time::Duration;
 
-    use humantime::format_duration;
     use comfy_table::{Row, Cell, CellAlignment};
     use serde::Serialize;
 
     use fluvio::metadata::objects::Metadata;
     use fluvio::metadata::topic::TopicSpec;
 
-    use crate::common::output::{OutputType, TableOutputHandler, Terminal, OutputError};
+    use crate::common::output::{
+        OutputType, TableOutputHandler, Terminal, OutputError, format_duration,
+    };
     use crate::common::t_println;
 
     #[derive(Serialize)]
@@ -1,9 +1,17 @@
+mod copy;
 mod create;
 mod delete;
+mod delete_key;
 mod describe;
 mod list;
 mod add_partition;
 mod add_mirror;
+mod set_replication;
+mod alter;
+mod offsets;
+mod stats;
+mod usage;
+mod schema;
 
 pub use cmd::TopicCmd;
 
@@ -25,10 +33,17 @@ mod cmd {
 
     use super::add_mirror::AddMirrorOpt;
     use super::add_partition::AddPartitionOpt;
+    use super::copy::CopyTopicOpt;
     use super::create::CreateTopicOpt;
+    use super::set_replication::SetReplicationOpt;
+    use super::alter::AlterTopicOpt;
     use super::delete::DeleteTopicOpt;
+    use super::delete_key::DeleteKeyOpt;
     use super::describe::DescribeTopicsOpt;
     use super::list::ListTopicsOpt;
+    use super::offsets::TopicOffsetsOpt;
+    use super::stats::TopicStatsOpt;
+    use super::usage::TopicUsageOpt;
 
     #[derive(Debug, Parser)]
     #[command(name = "topic", about = "Topic operations")]
@@ -47,6 +62,13 @@ mod cmd {
         )]
         Delete(DeleteTopicOpt),
 
+        /// Write tombstone for a key, and report when prior values of key are removed
+        #[command(
+            name = "delete-key",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        DeleteKey(DeleteKeyOpt),
+
         /// Print detailed information about a Topic
         #[command(
             name = "describe",
@@ -74,6 +96,52 @@ mod cmd {
             help_template = COMMAND_TEMPLATE,
         )]
         AddMirror(AddMirrorOpt),
+
+        /// Change replication factor of a Topic and wait for new replicas to be in sync
+        #[command(
+            name = "set-replication",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        SetReplication(SetReplicationOpt),
+
+        /// Change retention, compression, deduplication or max batch size of a Topic
+        #[command(
+            name = "alter",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Alter(AlterTopicOpt),
+
+        /// Report record counts, size, rates and consumers of a Topic
+        #[command(
+            name = "stats",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Stats(TopicStatsOpt),
+
+        /// Report size history, growth rate and projected capacity of a Topic
+        ///
+        /// Size history is sampled by SC every 5 minutes and kept in memory for up to 7 days.
+        /// History is not persisted, it starts empty whenever SC restarts,
+        /// so growth and projections are only available once new samples are taken.
+        #[command(
+            name = "usage",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Usage(TopicUsageOpt),
+
+        /// Print earliest, high watermark and latest offsets of partitions of a Topic
+        #[command(
+            name = "offsets",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Offsets(TopicOffsetsOpt),
+
+        /// Copy records of a Topic to another Topic, resuming from checkpoint if interrupted
+        #[command(
+            name = "copy",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Copy(CopyTopicOpt),
     }
 
     #[async_trait]
@@ -90,6 +158,9 @@ mod cmd {
                 Self::Delete(delete) => {
                     delete.process(fluvio).await?;
                 }
+                Self::DeleteKey(delete_key) => {
+                    delete_key.process(fluvio).await?;
+                }
                 Self::Describe(describe) => {
                     describe.process(out, fluvio).await?;
                 }
@@ -102,6 +173,24 @@ mod cmd {
                 Self::AddMirror(add_mirror) => {
                     add_mirror.process(fluvio).await?;
                 }
+                Self::SetReplication(set_replication) => {
+                    set_replication.process(fluvio).await?;
+                }
+                Self::Alter(alter) => {
+                    alter.process(fluvio).await?;
+                }
+                Self::Stats(stats) => {
+                    stats.process(out, fluvio).await?;
+                }
+                Self::Usage(usage) => {
+                    usage.process(out, fluvio).await?;
+                }
+                Self::Offsets(offsets) => {
+                    offsets.process(out, fluvio).await?;
+                }
+                Self::Copy(copy) => {
+                    copy.process(fluvio).await?;
+                }
             }
 
             Ok(())
@@ -0,0 +1,79 @@
+//!
+//! # Topic Offsets CLI
+//!
+//! CLI to print earliest, high watermark and latest offsets of partitions of a Topic
+//!
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use clap::Parser;
+
+use fluvio::Fluvio;
+
+use crate::common::output::Terminal;
+use crate::common::OutputFormat;
+
+#[derive(Debug, Parser)]
+pub struct TopicOffsetsOpt {
+    /// The name of the Topic
+    #[arg(value_name = "name")]
+    topic: String,
+
+    #[clap(flatten)]
+    output: OutputFormat,
+}
+
+impl TopicOffsetsOpt {
+    pub async fn process<O: Terminal>(self, out: Arc<O>, fluvio: &Fluvio) -> Result<()> {
+        let mut offsets = fluvio.topic_offsets(&self.topic).await?;
+        offsets.sort();
+
+        out.render_list(&display::TopicOffsets(offsets), self.output.format)?;
+        Ok(())
+    }
+}
+
+mod display {
+
+    use comfy_table::Row;
+    use serde::Serialize;
+
+    use fluvio::consumer::PartitionOffsets;
+
+    use crate::common::output::TableOutputHandler;
+
+    #[derive(Serialize)]
+    pub(crate) struct TopicOffsets(pub Vec<PartitionOffsets>);
+
+    impl TableOutputHandler for TopicOffsets {
+        fn header(&self) -> Row {
+            Row::from([
+                "PARTITION",
+                "EARLIEST",
+                "HIGH WATERMARK",
+                "LATEST",
+                "RECORDS",
+            ])
+        }
+
+        fn errors(&self) -> Vec<String> {
+            vec![]
+        }
+
+        fn content(&self) -> Vec<Row> {
+            self.0
+                .iter()
+                .map(|offsets| {
+                    Row::from([
+                        offsets.partition.to_string(),
+                        offsets.earliest.to_string(),
+                        offsets.high_watermark.to_string(),
+                        offsets.latest.to_string(),
+                        offsets.records().to_string(),
+                    ])
+                })
+                .collect()
+        }
+    }
+}
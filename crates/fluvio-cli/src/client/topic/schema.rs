@@ -0,0 +1,397 @@
+//!
+//! # Infer record schema of a topic
+//!
+//! Samples latest JSON records of a topic and generates JSON schema or Avro schema draft,
+//! to be used as starting point of a contract between producers and consumers.
+//! Fields found in every sampled object are required, others are optional.
+//!
+
+use clap::ValueEnum;
+use futures::StreamExt;
+use serde_json::{Map, Value, json};
+use tracing::debug;
+use anyhow::Result;
+
+use fluvio::{Fluvio, Offset};
+use fluvio::consumer::ConsumerConfigExt;
+
+use crate::CliError;
+
+#[derive(ValueEnum, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum SchemaFormat {
+    /// JSON schema, draft 2020-12
+    JsonSchema,
+    /// Avro schema
+    Avro,
+}
+
+/// sample latest records of topic and print inferred schema
+pub async fn print_inferred_schema(
+    fluvio: &Fluvio,
+    topic: &str,
+    sample_size: u32,
+    format: SchemaFormat,
+) -> Result<()> {
+    let (schema, records) = sample(fluvio, topic, sample_size).await?;
+    if records == 0 {
+        return Err(CliError::Other(format!(
+            "no JSON records found in latest records of topic \"{topic}\""
+        ))
+        .into());
+    }
+
+    let rendered = match format {
+        SchemaFormat::JsonSchema => to_json_schema(&schema, topic, records),
+        SchemaFormat::Avro => to_avro_schema(&schema, topic),
+    };
+    println!("{}", serde_json::to_string_pretty(&rendered)?);
+    Ok(())
+}
+
+/// read latest records of each partition, skipping records which are not JSON
+async fn sample(fluvio: &Fluvio, topic: &str, sample_size: u32) -> Result<(SchemaNode, usize)> {
+    let mut builder = ConsumerConfigExt::builder();
+    builder
+        .topic(topic)
+        .offset_start(Offset::from_end(sample_size))
+        .disable_continuous(true);
+    let mut stream = fluvio.consumer_with_config(builder.build()?).await?;
+
+    let mut schema = SchemaNode::default();
+    let mut records = 0;
+    let mut skipped = 0;
+    while let Some(record) = stream.next().await {
+        match serde_json::from_slice::<Value>(record?.value()) {
+            Ok(value) => {
+                schema.add(&value);
+                records += 1;
+            }
+            Err(_) => skipped += 1,
+        }
+    }
+    debug!(records, skipped, "sampled records");
+    Ok((schema, records))
+}
+
+/// Union of types of values seen at same position
+#[derive(Debug, Default, Clone, PartialEq)]
+struct SchemaNode {
+    null: bool,
+    boolean: bool,
+    integer: bool,
+    number: bool,
+    string: bool,
+    object: Option<ObjectNode>,
+    /// schema of array items
+    array: Option<Box<SchemaNode>>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+struct ObjectNode {
+    /// fields in order of first appearance
+    fields: Vec<FieldNode>,
+    /// number of objects seen
+    samples: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct FieldNode {
+    name: String,
+    schema: SchemaNode,
+    /// number of objects having the field
+    present: usize,
+}
+
+impl ObjectNode {
+    fn add(&mut self, object: &Map<String, Value>) {
+        self.samples += 1;
+        for (name, value) in object {
+            let index = match self.fields.iter().position(|field| field.name == *name) {
+                Some(index) => index,
+                None => {
+                    self.fields.push(FieldNode {
+                        name: name.clone(),
+                        schema: SchemaNode::default(),
+                        present: 0,
+                    });
+                    self.fields.len() - 1
+                }
+            };
+            let field = &mut self.fields[index];
+            field.schema.add(value);
+            field.present += 1;
+        }
+    }
+
+    fn is_required(&self, field: &FieldNode) -> bool {
+        field.present == self.samples
+    }
+}
+
+impl SchemaNode {
+    fn add(&mut self, value: &Value) {
+        match value {
+            Value::Null => self.null = true,
+            Value::Bool(_) => self.boolean = true,
+            Value::Number(number) if number.is_f64() => self.number = true,
+            Value::Number(_) => self.integer = true,
+            Value::String(_) => self.string = true,
+            Value::Object(object) => self.object.get_or_insert_with(Default::default).add(object),
+            Value::Array(values) => {
+                let items = self.array.get_or_insert_with(Default::default);
+                for value in values {
+                    items.add(value);
+                }
+            }
+        }
+    }
+
+    /// no values seen, ex: items of empty arrays
+    fn is_unknown(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// JSON schema of sampled records
+fn to_json_schema(schema: &SchemaNode, topic: &str, records: usize) -> Value {
+    let mut root = json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": topic,
+        "description": format!("inferred from {records} records of topic {topic}"),
+    });
+    if let (Value::Object(root), Value::Object(node)) = (&mut root, json_schema_node(schema)) {
+        root.extend(node);
+    }
+    root
+}
+
+fn json_schema_node(schema: &SchemaNode) -> Value {
+    let mut node = Map::new();
+    let mut types = vec![];
+    if schema.null {
+        types.push("null");
+    }
+    if schema.boolean {
+        types.push("boolean");
+    }
+    // integers are valid numbers
+    if schema.number {
+        types.push("number");
+    } else if schema.integer {
+        types.push("integer");
+    }
+    if schema.string {
+        types.push("string");
+    }
+    if let Some(object) = &schema.object {
+        types.push("object");
+        let properties: Map<String, Value> = object
+            .fields
+            .iter()
+            .map(|field| (field.name.clone(), json_schema_node(&field.schema)))
+            .collect();
+        let required: Vec<&str> = object
+            .fields
+            .iter()
+            .filter(|field| object.is_required(field))
+            .map(|field| field.name.as_str())
+            .collect();
+        node.insert("properties".to_owned(), Value::Object(properties));
+        if !required.is_empty() {
+            node.insert("required".to_owned(), json!(required));
+        }
+    }
+    if let Some(items) = &schema.array {
+        types.push("array");
+        if !items.is_unknown() {
+            node.insert("items".to_owned(), json_schema_node(items));
+        }
+    }
+
+    match types.as_slice() {
+        [] => {}
+        [single] => {
+            node.insert("type".to_owned(), json!(single));
+        }
+        multiple => {
+            node.insert("type".to_owned(), json!(multiple));
+        }
+    }
+    Value::Object(node)
+}
+
+/// Avro schema of sampled records.
+/// Nested records are named after their path, since Avro names must be unique
+fn to_avro_schema(schema: &SchemaNode, topic: &str) -> Value {
+    avro_node(schema, &avro_name(topic))
+}
+
+fn avro_node(schema: &SchemaNode, name: &str) -> Value {
+    let mut types = vec![];
+    if schema.null {
+        types.push(json!("null"));
+    }
+    if schema.boolean {
+        types.push(json!("boolean"));
+    }
+    if schema.number {
+        types.push(json!("double"));
+    } else if schema.integer {
+        types.push(json!("long"));
+    }
+    if schema.string {
+        types.push(json!("string"));
+    }
+    if let Some(object) = &schema.object {
+        let fields: Vec<Value> = object
+            .fields
+            .iter()
+            .map(|field| {
+                let field_name = avro_name(&field.name);
+                let field_type = avro_node(&field.schema, &format!("{name}_{field_name}"));
+                let mut avro_field = if object.is_required(field) {
+                    json!({ "name": field_name, "type": field_type })
+                } else {
+                    json!({ "name": field_name, "type": nullable(field_type), "default": null })
+                };
+                // keep key of records when it isn't a valid Avro name
+                if field_name != field.name {
+                    avro_field["aliases"] = json!([field.name]);
+                }
+                avro_field
+            })
+            .collect();
+        types.push(json!({ "type": "record", "name": name, "fields": fields }));
+    }
+    if let Some(items) = &schema.array {
+        types.push(json!({
+            "type": "array",
+            "items": avro_node(items, &format!("{name}_item")),
+        }));
+    }
+
+    match types.len() {
+        0 => json!("null"),
+        1 => types.remove(0),
+        _ => Value::Array(types),
+    }
+}
+
+/// union with null first, so null can be default
+fn nullable(avro_type: Value) -> Value {
+    let mut types = match avro_type {
+        Value::Array(types) => types,
+        single => vec![single],
+    };
+    types.retain(|avro_type| avro_type != "null");
+    types.insert(0, json!("null"));
+    Value::Array(types)
+}
+
+/// Avro names start with letter or underscore, followed by letters, digits or underscores
+fn avro_name(name: &str) -> String {
+    let mut avro_name: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if !avro_name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        avro_name.insert(0, '_');
+    }
+    avro_name
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    fn schema(records: &[&str]) -> SchemaNode {
+        let mut schema = SchemaNode::default();
+        for record in records {
+            schema.add(&serde_json::from_str(record).expect("json"));
+        }
+        schema
+    }
+
+    #[test]
+    fn test_infer_json_schema() {
+        let schema = schema(&[
+            r#"{"id": 1, "name": "a", "price": 10, "tags": ["x"], "address": {"city": "SF"}}"#,
+            r#"{"id": 2, "name": null, "price": 10.5, "tags": []}"#,
+        ]);
+        let json_schema = to_json_schema(&schema, "orders", 2);
+
+        assert_eq!(json_schema["type"], "object");
+        assert_eq!(
+            json_schema["required"],
+            json!(["id", "name", "price", "tags"])
+        );
+        let properties = &json_schema["properties"];
+        assert_eq!(properties["id"], json!({ "type": "integer" }));
+        assert_eq!(properties["name"], json!({ "type": ["null", "string"] }));
+        assert_eq!(properties["price"], json!({ "type": "number" }));
+        assert_eq!(
+            properties["tags"],
+            json!({ "type": "array", "items": { "type": "string" } })
+        );
+        assert_eq!(
+            properties["address"],
+            json!({
+                "type": "object",
+                "properties": { "city": { "type": "string" } },
+                "required": ["city"],
+            })
+        );
+    }
+
+    #[test]
+    fn test_infer_avro_schema() {
+        let schema = schema(&[
+            r#"{"id": 1, "name": "a", "address": {"city": "SF"}}"#,
+            r#"{"id": 2, "name": null}"#,
+        ]);
+        let avro = to_avro_schema(&schema, "my-orders");
+
+        assert_eq!(avro["type"], "record");
+        assert_eq!(avro["name"], "my_orders");
+        assert_eq!(
+            avro["fields"],
+            json!([
+                { "name": "id", "type": "long" },
+                { "name": "name", "type": ["null", "string"] },
+                {
+                    "name": "address",
+                    "type": ["null", {
+                        "type": "record",
+                        "name": "my_orders_address",
+                        "fields": [{ "name": "city", "type": "string" }],
+                    }],
+                    "default": null,
+                },
+            ])
+        );
+        assert_eq!(avro_name("1st"), "_1st");
+    }
+
+    #[test]
+    fn test_avro_field_names_sanitized() {
+        let schema = schema(&[r#"{"user-id": 1, "1st": {"a.b": true}}"#]);
+        let avro = to_avro_schema(&schema, "users");
+
+        assert_eq!(
+            avro["fields"],
+            json!([
+                { "name": "user_id", "type": "long", "aliases": ["user-id"] },
+                {
+                    "name": "_1st",
+                    "type": {
+                        "type": "record",
+                        "name": "users__1st",
+                        "fields": [{ "name": "a_b", "type": "boolean", "aliases": ["a.b"] }],
+                    },
+                    "aliases": ["1st"],
+                },
+            ])
+        );
+    }
+}
@@ -0,0 +1,139 @@
+//!
+//! # Change replication factor of a Topic
+//!
+//! SC adds or removes followers of each partition, keeping their leaders.
+//! New followers copy partition from leader, so command waits until they are in sync.
+//!
+use std::time::{Duration, Instant};
+
+use clap::Parser;
+use anyhow::{anyhow, Result};
+
+use fluvio_future::timer::sleep;
+use fluvio_protocol::record::ReplicaKey;
+use fluvio_sc_schema::partition::{PartitionSpec, PartitionStatus};
+use fluvio_sc_schema::topic::{SetReplication, TopicSpec, UpdateTopicAction};
+use fluvio::Fluvio;
+
+const SYNC_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Option for changing replication factor
+#[derive(Debug, Parser)]
+pub struct SetReplicationOpt {
+    /// Topic name
+    topic: String,
+
+    /// New replication factor
+    #[arg(short, long, value_name = "FACTOR")]
+    factor: u32,
+
+    /// Max time to wait for replicas to be in sync, ex: 10m
+    #[arg(long, value_name = "DURATION", default_value = "5m")]
+    wait_timeout: humantime::Duration,
+
+    /// Don't wait for replicas to be in sync
+    #[arg(long)]
+    no_wait: bool,
+}
+
+impl SetReplicationOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+
+        let action = UpdateTopicAction::SetReplication(SetReplication {
+            factor: self.factor,
+        });
+        admin
+            .update::<TopicSpec>(self.topic.clone(), action)
+            .await?;
+
+        println!(
+            "replication factor of topic \"{}\" set to {}",
+            self.topic, self.factor
+        );
+        if self.no_wait {
+            return Ok(());
+        }
+
+        let started = Instant::now();
+        let mut reported = None;
+        loop {
+            let partitions: Vec<(PartitionSpec, PartitionStatus)> = admin
+                .all::<PartitionSpec>()
+                .await?
+                .into_iter()
+                .filter(|partition| {
+                    ReplicaKey::try_from(partition.name.clone())
+                        .is_ok_and(|key| key.topic == self.topic)
+                })
+                .map(|partition| (partition.spec, partition.status))
+                .collect();
+            let total = partitions.len();
+            let synced = partitions
+                .iter()
+                .filter(|(spec, status)| is_synced(spec, status, self.factor))
+                .count();
+
+            if reported != Some(synced) {
+                println!("{synced}/{total} partitions in sync");
+                reported = Some(synced);
+            }
+            if synced == total {
+                println!("topic \"{}\" is fully replicated", self.topic);
+                return Ok(());
+            }
+            if started.elapsed() > *self.wait_timeout {
+                return Err(anyhow!(
+                    "timed out waiting for replicas of topic \"{}\" to be in sync, {synced}/{total} partitions in sync",
+                    self.topic
+                ));
+            }
+            sleep(SYNC_POLL_INTERVAL).await;
+        }
+    }
+}
+
+/// partition has all replicas, and every follower has caught up with leader
+fn is_synced(spec: &PartitionSpec, status: &PartitionStatus, factor: u32) -> bool {
+    if spec.replicas.len() != factor as usize {
+        return false;
+    }
+    spec.replicas
+        .iter()
+        .filter(|spu| **spu != spec.leader)
+        .all(|spu| {
+            status.replicas.iter().any(|replica| {
+                replica.spu == *spu && replica.leo >= 0 && replica.leader_lag(&status.leader) <= 0
+            })
+        })
+}
+
+#[cfg(test)]
+mod test {
+
+    use fluvio_sc_schema::partition::ReplicaStatus;
+
+    use super::*;
+
+    #[test]
+    fn test_is_synced() {
+        let spec = PartitionSpec::new(5001, vec![5001, 5002, 5003]);
+        let mut status = PartitionStatus {
+            leader: ReplicaStatus::new(5001, 10, 10),
+            replicas: vec![ReplicaStatus::new(5002, 10, 10)],
+            ..Default::default()
+        };
+
+        // new follower is not reported yet
+        assert!(!is_synced(&spec, &status, 3));
+
+        status.replicas.push(ReplicaStatus::new(5003, 2, 4));
+        assert!(!is_synced(&spec, &status, 3));
+
+        status.replicas[1] = ReplicaStatus::new(5003, 10, 10);
+        assert!(is_synced(&spec, &status, 3));
+
+        // spec not yet updated by SC
+        assert!(!is_synced(&spec, &status, 4));
+    }
+}
@@ -0,0 +1,270 @@
+//!
+//! # Topic Statistics CLI
+//!
+//! CLI to report statistics of Topic aggregated from leaders of its partitions.
+//! Rates are measured by fetching statistics twice, `--window` apart.
+//!
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+use clap::Parser;
+use futures::future::try_join_all;
+use serde::Serialize;
+use tracing::debug;
+
+use fluvio::{Fluvio, PartitionId, PartitionStats};
+use fluvio::metadata::topic::TopicSpec;
+use fluvio_future::timer::sleep;
+use fluvio_protocol::record::NO_TIMESTAMP;
+
+use crate::common::output::{Terminal, set_raw_units};
+use crate::common::OutputFormat;
+
+#[derive(Debug, Parser)]
+pub struct TopicStatsOpt {
+    /// The name of the Topic
+    #[arg(value_name = "name")]
+    topic: String,
+
+    /// Window over which produce and consume rates are measured, ex: 10s
+    #[arg(long, value_name = "DURATION", default_value = "5s")]
+    window: humantime::Duration,
+
+    #[clap(flatten)]
+    output: OutputFormat,
+
+    /// Show sizes in bytes and durations in seconds, instead of human readable units
+    #[arg(long)]
+    raw: bool,
+}
+
+impl TopicStatsOpt {
+    pub async fn process<O: Terminal>(self, out: Arc<O>, fluvio: &Fluvio) -> Result<()> {
+        set_raw_units(self.raw);
+        let admin = fluvio.admin().await;
+        let topic = admin
+            .list::<TopicSpec, _>(vec![self.topic.clone()])
+            .await?
+            .into_iter()
+            .find(|topic| topic.name == self.topic)
+            .ok_or_else(|| anyhow!("topic '{}' not found", self.topic))?;
+        let partitions = topic.spec.partitions();
+        debug!(topic = %self.topic, partitions, window = %self.window, "topic stats");
+
+        let before = fetch_stats(fluvio, &self.topic, partitions).await?;
+        sleep(*self.window).await;
+        let after = fetch_stats(fluvio, &self.topic, partitions).await?;
+
+        let rows: Vec<StatsRow> = before
+            .iter()
+            .zip(after.iter())
+            .zip(0..)
+            .map(|((before, after), partition)| {
+                StatsRow::new(partition, before, after, *self.window)
+            })
+            .collect();
+        let stats = display::TopicStats {
+            topic: self.topic,
+            window_secs: self.window.as_secs_f64(),
+            total: StatsRow::total(&rows),
+            partitions: rows,
+        };
+
+        out.render_list(&stats, self.output.format)?;
+        Ok(())
+    }
+}
+
+async fn fetch_stats(
+    fluvio: &Fluvio,
+    topic: &str,
+    partitions: PartitionId,
+) -> Result<Vec<PartitionStats>> {
+    try_join_all((0..partitions).map(|partition| fluvio.partition_stats(topic, partition))).await
+}
+
+/// statistics of partition or whole topic if partition is not set
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub(crate) struct StatsRow {
+    partition: Option<PartitionId>,
+    records: u64,
+    size: u64,
+    produce_records_per_sec: f64,
+    produce_bytes_per_sec: f64,
+    consume_records_per_sec: f64,
+    consume_bytes_per_sec: f64,
+    earliest_timestamp: Option<i64>,
+    latest_timestamp: Option<i64>,
+    active_consumers: u32,
+}
+
+impl StatsRow {
+    fn new(
+        partition: PartitionId,
+        before: &PartitionStats,
+        after: &PartitionStats,
+        window: Duration,
+    ) -> Self {
+        let secs = window.as_secs_f64().max(f64::EPSILON);
+        // counters restart when partition leader changes, rate is unknown then
+        let rate = |before: u64, after: u64| after.saturating_sub(before) as f64 / secs;
+        Self {
+            partition: Some(partition),
+            records: after.records(),
+            size: after.size,
+            produce_records_per_sec: rate(before.produced_records, after.produced_records),
+            produce_bytes_per_sec: rate(before.produced_bytes, after.produced_bytes),
+            consume_records_per_sec: rate(before.consumed_records, after.consumed_records),
+            consume_bytes_per_sec: rate(before.consumed_bytes, after.consumed_bytes),
+            earliest_timestamp: known_timestamp(after.earliest_timestamp),
+            latest_timestamp: known_timestamp(after.latest_timestamp),
+            active_consumers: after.active_consumers,
+        }
+    }
+
+    fn total(rows: &[StatsRow]) -> Self {
+        rows.iter().fold(Self::default(), |total, row| Self {
+            partition: None,
+            records: total.records + row.records,
+            size: total.size + row.size,
+            produce_records_per_sec: total.produce_records_per_sec + row.produce_records_per_sec,
+            produce_bytes_per_sec: total.produce_bytes_per_sec + row.produce_bytes_per_sec,
+            consume_records_per_sec: total.consume_records_per_sec + row.consume_records_per_sec,
+            consume_bytes_per_sec: total.consume_bytes_per_sec + row.consume_bytes_per_sec,
+            earliest_timestamp: min_known(total.earliest_timestamp, row.earliest_timestamp),
+            latest_timestamp: total.latest_timestamp.max(row.latest_timestamp),
+            active_consumers: total.active_consumers + row.active_consumers,
+        })
+    }
+}
+
+fn known_timestamp(timestamp: i64) -> Option<i64> {
+    (timestamp != NO_TIMESTAMP).then_some(timestamp)
+}
+
+fn min_known(a: Option<i64>, b: Option<i64>) -> Option<i64> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+mod display {
+
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use comfy_table::Row;
+    use serde::Serialize;
+
+    use crate::common::output::{TableOutputHandler, format_size};
+
+    use super::StatsRow;
+
+    #[derive(Serialize)]
+    pub(crate) struct TopicStats {
+        pub topic: String,
+        pub window_secs: f64,
+        pub partitions: Vec<StatsRow>,
+        pub total: StatsRow,
+    }
+
+    impl TableOutputHandler for TopicStats {
+        fn header(&self) -> Row {
+            Row::from([
+                "PARTITION",
+                "RECORDS",
+                "SIZE",
+                "PRODUCE RATE",
+                "CONSUME RATE",
+                "EARLIEST",
+                "LATEST",
+                "CONSUMERS",
+            ])
+        }
+
+        fn errors(&self) -> Vec<String> {
+            vec![]
+        }
+
+        fn content(&self) -> Vec<Row> {
+            self.partitions
+                .iter()
+                .chain(std::iter::once(&self.total))
+                .map(|row| {
+                    Row::from([
+                        row.partition
+                            .map_or_else(|| "total".to_owned(), |p| p.to_string()),
+                        row.records.to_string(),
+                        format_size(row.size),
+                        format_rate(row.produce_records_per_sec, row.produce_bytes_per_sec),
+                        format_rate(row.consume_records_per_sec, row.consume_bytes_per_sec),
+                        format_timestamp(row.earliest_timestamp),
+                        format_timestamp(row.latest_timestamp),
+                        row.active_consumers.to_string(),
+                    ])
+                })
+                .collect()
+        }
+    }
+
+    fn format_rate(records: f64, bytes: f64) -> String {
+        format!("{records:.1} rec/s ({}/s)", format_size(bytes as u64))
+    }
+
+    fn format_timestamp(timestamp: Option<i64>) -> String {
+        match timestamp.and_then(|ms| u64::try_from(ms).ok()) {
+            Some(ms) => humantime::format_rfc3339_seconds(UNIX_EPOCH + Duration::from_millis(ms))
+                .to_string(),
+            None => "-".to_owned(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    fn stats(produced: u64, consumed: u64, earliest: i64, latest: i64) -> PartitionStats {
+        PartitionStats {
+            start_offset: 0,
+            high_watermark: produced as i64,
+            size: produced * 10,
+            earliest_timestamp: earliest,
+            latest_timestamp: latest,
+            produced_records: produced,
+            produced_bytes: produced * 10,
+            consumed_records: consumed,
+            consumed_bytes: consumed * 10,
+            active_consumers: 1,
+        }
+    }
+
+    #[test]
+    fn test_stats_rates_and_total() {
+        let window = Duration::from_secs(2);
+        let first = StatsRow::new(0, &stats(10, 0, 100, 200), &stats(30, 10, 100, 300), window);
+        assert_eq!(first.records, 30);
+        assert_eq!(first.produce_records_per_sec, 10.0);
+        assert_eq!(first.consume_bytes_per_sec, 50.0);
+
+        // leader changed, counters restarted
+        let second = StatsRow::new(
+            1,
+            &stats(50, 0, NO_TIMESTAMP, NO_TIMESTAMP),
+            &stats(4, 0, 50, 400),
+            window,
+        );
+        assert_eq!(second.produce_records_per_sec, 0.0);
+
+        let total = StatsRow::total(&[first, second]);
+        assert_eq!(total.partition, None);
+        assert_eq!(total.records, 34);
+        assert_eq!(total.produce_records_per_sec, 10.0);
+        assert_eq!(total.earliest_timestamp, Some(50));
+        assert_eq!(total.latest_timestamp, Some(400));
+        assert_eq!(total.active_consumers, 2);
+    }
+}
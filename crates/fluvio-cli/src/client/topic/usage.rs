@@ -0,0 +1,305 @@
+//!
+//! # Topic Usage CLI
+//!
+//! Report growth of Topic size from history sampled by SC, with projections of
+//! when oldest records reach retention and when partitions reach their size limit.
+//!
+
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Result, anyhow};
+use clap::Parser;
+use futures::future::join_all;
+use serde::Serialize;
+use tracing::debug;
+
+use fluvio::Fluvio;
+use fluvio::metadata::topic::{TopicSpec, TopicUsageSample};
+use fluvio_protocol::record::NO_TIMESTAMP;
+use fluvio_types::defaults::SPU_PARTITION_MAX_BYTES;
+
+use crate::common::output::{Terminal, set_raw_units};
+use crate::common::OutputFormat;
+
+const MS_PER_DAY: f64 = 86_400_000.0;
+
+#[derive(Debug, Parser)]
+pub struct TopicUsageOpt {
+    /// The name of the Topic
+    #[arg(value_name = "name")]
+    topic: String,
+
+    #[clap(flatten)]
+    output: OutputFormat,
+
+    /// Show sizes in bytes and durations in seconds, instead of human readable units
+    #[arg(long)]
+    raw: bool,
+}
+
+impl TopicUsageOpt {
+    pub async fn process<O: Terminal>(self, out: Arc<O>, fluvio: &Fluvio) -> Result<()> {
+        set_raw_units(self.raw);
+        let admin = fluvio.admin().await;
+        let topic = admin
+            .list::<TopicSpec, _>(vec![self.topic.clone()])
+            .await?
+            .into_iter()
+            .find(|topic| topic.name == self.topic)
+            .ok_or_else(|| anyhow!("topic '{}' not found", self.topic))?;
+        let samples = admin.topic_usage(&self.topic).await?;
+        debug!(topic = %self.topic, samples = samples.len(), "topic usage");
+
+        let partitions = topic.spec.partitions();
+        let topic_name = &self.topic;
+        let results = join_all((0..partitions).map(|partition| async move {
+            fluvio
+                .partition_stats(topic_name, partition)
+                .await
+                .inspect_err(|err| debug!(partition, %err, "partition stats not available"))
+                .map_err(|err| format!("stats of partition {partition} not available: {err}"))
+        }))
+        .await;
+        let mut stats = vec![];
+        let mut errors = vec![];
+        for result in results {
+            match result {
+                Ok(partition_stats) => stats.push(partition_stats),
+                Err(err) => errors.push(err),
+            }
+        }
+        // oldest record may be in a partition without stats, retention is unknown
+        let earliest_timestamp = stats
+            .iter()
+            .map(|stats| stats.earliest_timestamp)
+            .filter(|timestamp| *timestamp != NO_TIMESTAMP)
+            .min()
+            .filter(|_| errors.is_empty());
+
+        let max_partition_size = topic
+            .spec
+            .get_storage()
+            .and_then(|storage| storage.max_partition_size)
+            .unwrap_or(SPU_PARTITION_MAX_BYTES);
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |now| now.as_millis() as i64);
+
+        let mut report = TopicUsage::new(
+            self.topic,
+            &samples,
+            earliest_timestamp,
+            now,
+            Duration::from_secs(topic.spec.retention_secs() as u64),
+            max_partition_size * partitions as u64,
+        );
+        report.errors = errors;
+        out.render_list(&report, self.output.format)?;
+        Ok(())
+    }
+}
+
+/// bytes per day, slope of least squares line through samples
+fn growth_per_day(samples: &[TopicUsageSample]) -> Option<f64> {
+    if samples.len() < 2 {
+        return None;
+    }
+    let count = samples.len() as f64;
+    let origin = samples[0].timestamp;
+    let days = |sample: &TopicUsageSample| (sample.timestamp - origin) as f64 / MS_PER_DAY;
+    let mean_day = samples.iter().map(days).sum::<f64>() / count;
+    let mean_size = samples.iter().map(|sample| sample.size as f64).sum::<f64>() / count;
+    let (covariance, variance) = samples.iter().fold((0.0, 0.0), |(cov, var), sample| {
+        let day = days(sample) - mean_day;
+        (
+            cov + day * (sample.size as f64 - mean_size),
+            var + day * day,
+        )
+    });
+    (variance > 0.0).then(|| covariance / variance)
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub(crate) struct TopicUsage {
+    topic: String,
+    size: u64,
+    samples: usize,
+    history_secs: u64,
+    growth_bytes_per_day: Option<f64>,
+    /// time until oldest records reach retention and start being removed
+    retention_in_secs: Option<u64>,
+    /// projected size when oldest records reach retention
+    size_at_retention: Option<u64>,
+    size_limit: u64,
+    /// projected time until partitions reach their max size
+    limit_in_secs: Option<u64>,
+    #[serde(skip)]
+    errors: Vec<String>,
+}
+
+impl TopicUsage {
+    fn new(
+        topic: String,
+        samples: &[TopicUsageSample],
+        earliest_timestamp: Option<i64>,
+        now: i64,
+        retention: Duration,
+        size_limit: u64,
+    ) -> Self {
+        let size = samples.last().map(|sample| sample.size).unwrap_or_default();
+        let history_secs = match (samples.first(), samples.last()) {
+            (Some(first), Some(last)) => ((last.timestamp - first.timestamp) / 1000) as u64,
+            _ => 0,
+        };
+        let growth = growth_per_day(samples);
+        let retention_in = earliest_timestamp.map(|earliest| {
+            let age = Duration::from_millis(now.saturating_sub(earliest).max(0) as u64);
+            retention.saturating_sub(age)
+        });
+        let project = |days: f64| (size as f64 + growth.unwrap_or_default() * days).max(0.0) as u64;
+        let limit_in_secs = match growth {
+            _ if size >= size_limit => Some(0),
+            Some(growth) if growth > 0.0 => {
+                Some(((size_limit - size) as f64 / growth * 86_400.0) as u64)
+            }
+            _ => None,
+        };
+        Self {
+            topic,
+            size,
+            samples: samples.len(),
+            history_secs,
+            growth_bytes_per_day: growth,
+            retention_in_secs: retention_in.map(|left| left.as_secs()),
+            size_at_retention: retention_in
+                .filter(|_| growth.is_some())
+                .map(|left| project(left.as_secs_f64() / 86_400.0).min(size_limit)),
+            size_limit,
+            limit_in_secs,
+            errors: vec![],
+        }
+    }
+}
+
+mod display {
+
+    use std::time::Duration;
+
+    use comfy_table::Row;
+
+    use crate::common::output::{TableOutputHandler, format_duration, format_size};
+
+    use super::TopicUsage;
+
+    impl TableOutputHandler for TopicUsage {
+        fn header(&self) -> Row {
+            Row::from([
+                "SIZE",
+                "GROWTH",
+                "HISTORY",
+                "RETENTION IN",
+                "SIZE AT RETENTION",
+                "LIMIT",
+                "LIMIT IN",
+            ])
+        }
+
+        fn errors(&self) -> Vec<String> {
+            self.errors.clone()
+        }
+
+        fn content(&self) -> Vec<Row> {
+            let growth = match self.growth_bytes_per_day {
+                Some(growth) if growth < 0.0 => format!("-{}/day", format_size(-growth as u64)),
+                Some(growth) => format!("{}/day", format_size(growth as u64)),
+                None => "-".to_owned(),
+            };
+            let history = format!(
+                "{} ({} samples)",
+                format_duration(Duration::from_secs(self.history_secs)),
+                self.samples
+            );
+            vec![Row::from([
+                format_size(self.size),
+                growth,
+                history,
+                format_eta(self.retention_in_secs),
+                self.size_at_retention
+                    .map_or_else(|| "-".to_owned(), format_size),
+                format_size(self.size_limit),
+                format_eta(self.limit_in_secs),
+            ])]
+        }
+    }
+
+    fn format_eta(secs: Option<u64>) -> String {
+        match secs {
+            Some(0) => "reached".to_owned(),
+            Some(secs) => format_duration(Duration::from_secs(secs)),
+            None => "-".to_owned(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    const DAY_MS: i64 = 86_400_000;
+
+    fn sample(day: i64, size: u64) -> TopicUsageSample {
+        TopicUsageSample {
+            timestamp: day * DAY_MS,
+            size,
+        }
+    }
+
+    #[test]
+    fn test_growth_per_day() {
+        assert_eq!(growth_per_day(&[]), None);
+        assert_eq!(growth_per_day(&[sample(1, 100)]), None);
+        assert_eq!(growth_per_day(&[sample(1, 100), sample(1, 200)]), None);
+        assert_eq!(
+            growth_per_day(&[sample(0, 100), sample(1, 300), sample(2, 500)]),
+            Some(200.0)
+        );
+        assert_eq!(
+            growth_per_day(&[sample(0, 500), sample(2, 300)]),
+            Some(-100.0)
+        );
+    }
+
+    #[test]
+    fn test_usage_projections() {
+        let samples = [sample(0, 1000), sample(1, 2000), sample(2, 3000)];
+        let usage = TopicUsage::new(
+            "orders".to_owned(),
+            &samples,
+            Some(-DAY_MS),
+            2 * DAY_MS,
+            Duration::from_secs(7 * 86_400),
+            10_000,
+        );
+        assert_eq!(usage.size, 3000);
+        assert_eq!(usage.history_secs, 2 * 86_400);
+        assert_eq!(usage.growth_bytes_per_day, Some(1000.0));
+        // oldest record is 3 days old
+        assert_eq!(usage.retention_in_secs, Some(4 * 86_400));
+        assert_eq!(usage.size_at_retention, Some(7000));
+        assert_eq!(usage.limit_in_secs, Some(7 * 86_400));
+
+        let full = TopicUsage::new(
+            "orders".to_owned(),
+            &samples,
+            None,
+            2 * DAY_MS,
+            Duration::from_secs(86_400),
+            3000,
+        );
+        assert_eq!(full.retention_in_secs, None);
+        assert_eq!(full.size_at_retention, None);
+        assert_eq!(full.limit_in_secs, Some(0));
+    }
+}
@@ -0,0 +1,267 @@
+//!
+//! # Record trace CLI
+//!
+//! Follows a record produced with `--trace` across topics it was copied, transformed or
+//! mirrored into. Records carrying the same trace origin id are searched among the last
+//! records of partitions of the given topics, or topics in hops of the record,
+//! and printed in order of their hops.
+//!
+
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use clap::Parser;
+use anyhow::Result;
+use futures::StreamExt;
+use serde::Serialize;
+use tracing::debug;
+
+use fluvio::{Fluvio, Offset, PartitionId};
+use fluvio::consumer::ConsumerConfigExt;
+use fluvio::metadata::topic::TopicSpec;
+use fluvio::trace::RecordTrace;
+use fluvio_protocol::record::{ConsumerRecord, NO_TIMESTAMP};
+
+use crate::client::cmd::ClientCmd;
+use crate::common::output::Terminal;
+use crate::common::OutputFormat;
+use crate::CliError;
+
+/// records searched at the end of each partition by default
+const DEFAULT_SCAN_RECORDS: u32 = 10_000;
+
+/// bytes of value shown for each record
+const VALUE_PREVIEW: usize = 40;
+
+/// Follow a record across topics it was copied, transformed or mirrored into
+#[derive(Debug, Parser)]
+pub struct TraceOpt {
+    /// Record as <TOPIC>:<PARTITION>:<OFFSET>, or its trace origin id
+    #[arg(value_name = "RECORD_ID")]
+    record_id: String,
+
+    /// Topics to search, topics in hops of the record by default.
+    /// Required when tracing by origin id
+    #[arg(short, long = "topic", value_name = "TOPIC")]
+    topics: Vec<String>,
+
+    /// Number of last records searched in each partition
+    #[arg(long, value_name = "RECORDS", default_value_t = DEFAULT_SCAN_RECORDS)]
+    scan: u32,
+
+    #[clap(flatten)]
+    output: OutputFormat,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct TracedRecord {
+    topic: String,
+    partition: PartitionId,
+    offset: i64,
+    timestamp: Option<i64>,
+    hops: Vec<String>,
+    value: String,
+}
+
+#[async_trait]
+impl ClientCmd for TraceOpt {
+    async fn process_client<O: Terminal + Debug + Send + Sync>(
+        self,
+        out: Arc<O>,
+        fluvio: &Fluvio,
+    ) -> Result<()> {
+        let (origin_id, hops) = match parse_record_id(&self.record_id) {
+            Some((topic, partition, offset)) => {
+                let record = read_record(fluvio, topic, partition, offset).await?;
+                let trace = RecordTrace::from_record(record.inner()).ok_or_else(|| {
+                    CliError::Other(format!(
+                        "record {} has no trace, produce it with `--trace`",
+                        self.record_id
+                    ))
+                })?;
+                (trace.origin_id, trace.hops)
+            }
+            None => (self.record_id.clone(), vec![]),
+        };
+        let search = if self.topics.is_empty() {
+            hops
+        } else {
+            self.topics.clone()
+        };
+        if search.is_empty() {
+            return Err(CliError::InvalidArg(
+                "topics to search must be given with `--topic`".to_owned(),
+            )
+            .into());
+        }
+        debug!(origin_id, ?search, "tracing record");
+
+        let admin = fluvio.admin().await;
+        let topics = admin
+            .all::<TopicSpec>()
+            .await?
+            .into_iter()
+            .filter(|topic| search.contains(&topic.name))
+            .collect::<Vec<_>>();
+
+        let mut traced = vec![];
+        for topic in topics {
+            // record is produced once to each topic, other partitions don't have it
+            for partition in 0..topic.spec.partitions() {
+                let found = self
+                    .search_partition(fluvio, &topic.name, partition, &origin_id)
+                    .await?;
+                if !found.is_empty() {
+                    traced.extend(found);
+                    break;
+                }
+            }
+        }
+        if traced.is_empty() {
+            return Err(CliError::Other(format!(
+                "no records with trace {origin_id} in last {} records of partitions of searched topics",
+                self.scan
+            ))
+            .into());
+        }
+
+        // records are produced to topics in order of hops, ties only with mirrors
+        traced.sort_by_key(|record| (record.hops.len(), record.timestamp));
+        out.render_list(&display::TracedRecords(traced), self.output.format)?;
+        Ok(())
+    }
+}
+
+impl TraceOpt {
+    async fn search_partition(
+        &self,
+        fluvio: &Fluvio,
+        topic: &str,
+        partition: PartitionId,
+        origin_id: &str,
+    ) -> Result<Vec<TracedRecord>> {
+        let mut builder = ConsumerConfigExt::builder();
+        builder
+            .topic(topic)
+            .partition(partition)
+            .offset_start(Offset::from_end(self.scan))
+            .disable_continuous(true);
+        let mut stream = fluvio.consumer_with_config(builder.build()?).await?;
+
+        let mut traced = vec![];
+        while let Some(record) = stream.next().await {
+            let record = record?;
+            let Some(trace) = RecordTrace::from_record(record.inner()) else {
+                continue;
+            };
+            if trace.origin_id != origin_id {
+                continue;
+            }
+            traced.push(TracedRecord {
+                topic: topic.to_owned(),
+                partition,
+                offset: record.offset(),
+                timestamp: (record.timestamp() != NO_TIMESTAMP).then_some(record.timestamp()),
+                hops: trace.hops,
+                value: preview(record.value()),
+            });
+        }
+        Ok(traced)
+    }
+}
+
+/// topic, partition and offset of record id, topic names can't contain ':'
+fn parse_record_id(record_id: &str) -> Option<(&str, PartitionId, i64)> {
+    let mut parts = record_id.split(':');
+    let (Some(topic), Some(partition), Some(offset), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return None;
+    };
+    Some((topic, partition.parse().ok()?, offset.parse().ok()?))
+}
+
+async fn read_record(
+    fluvio: &Fluvio,
+    topic: &str,
+    partition: PartitionId,
+    offset: i64,
+) -> Result<ConsumerRecord> {
+    let mut builder = ConsumerConfigExt::builder();
+    builder
+        .topic(topic)
+        .partition(partition)
+        .offset_start(Offset::absolute(offset)?)
+        .disable_continuous(true);
+    let mut stream = fluvio.consumer_with_config(builder.build()?).await?;
+    match stream.next().await {
+        Some(record) => Ok(record?),
+        None => {
+            Err(CliError::Other(format!("record {topic}:{partition}:{offset} not found")).into())
+        }
+    }
+}
+
+fn preview(value: &[u8]) -> String {
+    let value = String::from_utf8_lossy(value);
+    match value.char_indices().nth(VALUE_PREVIEW) {
+        Some((end, _)) => format!("{}...", &value[..end]),
+        None => value.into_owned(),
+    }
+}
+
+mod display {
+
+    use comfy_table::Row;
+    use serde::Serialize;
+
+    use crate::common::output::TableOutputHandler;
+
+    use super::TracedRecord;
+
+    #[derive(Serialize)]
+    pub(crate) struct TracedRecords(pub Vec<TracedRecord>);
+
+    impl TableOutputHandler for TracedRecords {
+        fn header(&self) -> Row {
+            Row::from(["TOPIC", "PARTITION", "OFFSET", "TIMESTAMP", "HOPS", "VALUE"])
+        }
+
+        fn errors(&self) -> Vec<String> {
+            vec![]
+        }
+
+        fn content(&self) -> Vec<Row> {
+            self.0
+                .iter()
+                .map(|record| {
+                    Row::from([
+                        record.topic.clone(),
+                        record.partition.to_string(),
+                        record.offset.to_string(),
+                        record
+                            .timestamp
+                            .map_or_else(|| "-".to_owned(), |timestamp| timestamp.to_string()),
+                        record.hops.join(" -> "),
+                        record.value.clone(),
+                    ])
+                })
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_parse_record_id() {
+        assert_eq!(parse_record_id("orders:1:42"), Some(("orders", 1, 42)));
+        assert_eq!(parse_record_id("3f2a9c0d4e5b6a71"), None);
+        assert_eq!(parse_record_id("orders:x:42"), None);
+        assert_eq!(parse_record_id("orders:1:42:0"), None);
+    }
+}
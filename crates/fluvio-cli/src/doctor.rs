@@ -0,0 +1,459 @@
+//! # Doctor
+//!
+//! Checks local setup and connectivity to the cluster, printing how to fix problems found
+
+use std::fmt;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use clap::Parser;
+use colored::Colorize;
+use anyhow::{Result, anyhow};
+
+use fluvio::{Fluvio, FluvioClusterConfig};
+use fluvio::config::{TlsConfig, TlsPolicy};
+use fluvio::metadata::spu::SpuSpec;
+use fluvio_cli_common::install::{fluvio_bin_dir, fluvio_extensions_dir};
+use fluvio_extension_common::cert::CertExpiry;
+use fluvio_extension_common::target::ClusterTarget;
+use fluvio_future::future::timeout;
+use fluvio_socket::{ClientConfig, VersionedSocket};
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Parser)]
+pub struct DoctorOpt {
+    /// Warn about certificates expiring within this period. Ex: '30days', '12h'
+    #[arg(long, value_parser = humantime::parse_duration, default_value = "30days")]
+    cert_expiry_warning: Duration,
+
+    /// Max tolerated difference between local and cluster clock. Ex: '5s'
+    #[arg(long, value_parser = humantime::parse_duration, default_value = "5s")]
+    max_clock_skew: Duration,
+}
+
+impl DoctorOpt {
+    pub async fn process(self, target: ClusterTarget) -> Result<()> {
+        let mut checks = vec![check_extensions_dir(), check_bin_in_path()];
+
+        match target.load() {
+            Ok(cluster) => {
+                checks.push(Check::pass(
+                    "profile",
+                    format!("cluster endpoint: {}", cluster.endpoint),
+                ));
+                checks.extend(self.check_tls(&cluster.tls));
+                checks.extend(self.check_cluster(&cluster).await);
+            }
+            Err(err) => checks.push(Check::fail(
+                "profile",
+                format!("unable to load cluster config: {err}"),
+                "list profiles with `fluvio profile list`, select one with \
+                 `fluvio profile switch <name>` or create one with `fluvio cluster start`",
+            )),
+        }
+
+        for check in &checks {
+            println!("{check}");
+        }
+
+        let failed = checks
+            .iter()
+            .filter(|check| check.status == Status::Fail)
+            .count();
+        if failed > 0 {
+            return Err(anyhow!("{failed} check(s) failed"));
+        }
+        Ok(())
+    }
+
+    fn check_tls(&self, tls: &TlsPolicy) -> Vec<Check> {
+        let TlsPolicy::Verified(config) = tls else {
+            return vec![];
+        };
+
+        let certs: Vec<(&str, Result<Vec<u8>>)> = match config {
+            TlsConfig::Files(paths) => vec![
+                ("client cert", read_file(&paths.cert)),
+                ("ca cert", read_file(&paths.ca_cert)),
+            ],
+            TlsConfig::Inline(certs) => vec![
+                ("client cert", Ok(certs.cert.as_bytes().to_vec())),
+                ("ca cert", Ok(certs.ca_cert.as_bytes().to_vec())),
+            ],
+        };
+
+        let mut checks = vec![];
+        if let TlsConfig::Files(paths) = config
+            && let Err(err) = read_file(&paths.key)
+        {
+            checks.push(Check::fail(
+                "client key",
+                err.to_string(),
+                "update key path of cluster in ~/.fluvio/config",
+            ));
+        }
+        for (name, pem) in certs {
+            checks.push(self.check_cert(name, pem));
+        }
+        checks
+    }
+
+    fn check_cert(&self, name: &'static str, pem: Result<Vec<u8>>) -> Check {
+        // bundle is only as good as its first expiring certificate
+        let cert = match pem.and_then(|pem| CertExpiry::earliest(&pem)) {
+            Ok(cert) => cert,
+            Err(err) => {
+                return Check::fail(
+                    name,
+                    err.to_string(),
+                    "update certificate of cluster in ~/.fluvio/config",
+                );
+            }
+        };
+
+        let not_after = humantime::format_rfc3339_seconds(cert.not_after);
+        match cert.remaining() {
+            None => Check::fail(
+                name,
+                format!("{} expired at {not_after}", cert.subject),
+                "renew certificate and update cluster in ~/.fluvio/config",
+            ),
+            Some(_) if cert.expires_within(self.cert_expiry_warning) => Check::warn(
+                name,
+                format!("{} expires at {not_after}", cert.subject),
+                "renew certificate before it expires",
+            ),
+            Some(_) => Check::pass(name, format!("valid until {not_after}")),
+        }
+    }
+
+    async fn check_cluster(&self, cluster: &FluvioClusterConfig) -> Vec<Check> {
+        let client_config = match ClientConfig::try_from(cluster.clone()) {
+            Ok(config) => config,
+            Err(err) => {
+                return vec![Check::fail(
+                    "sc",
+                    err.to_string(),
+                    "check TLS settings of cluster in ~/.fluvio/config",
+                )];
+            }
+        };
+
+        let sent = SystemTime::now();
+        let socket = match connect(client_config.recreate()).await {
+            Ok(socket) => socket,
+            Err(err) => {
+                return vec![Check::fail(
+                    "sc",
+                    format!("unable to connect to {}: {err}", cluster.endpoint),
+                    "make sure cluster is running with `fluvio cluster status` \
+                     and endpoint is reachable",
+                )];
+            }
+        };
+        let received = SystemTime::now();
+        let (_, _, versions) = socket.split();
+
+        let mut checks = vec![Check::pass(
+            "sc",
+            format!("connected to {}", cluster.endpoint),
+        )];
+        checks.push(self.check_clock_skew(versions.server_time(), sent, received));
+        checks.push(check_version(versions.platform_version()));
+        checks.extend(check_spus(cluster, &client_config).await);
+        checks
+    }
+
+    fn check_clock_skew(
+        &self,
+        server_time: Option<i64>,
+        sent: SystemTime,
+        received: SystemTime,
+    ) -> Check {
+        let Some(server_time) = server_time else {
+            return Check::warn(
+                "clock",
+                "cluster doesn't report its time",
+                "upgrade cluster to check clock skew",
+            );
+        };
+        let millis = |time: SystemTime| {
+            time.duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as i64
+        };
+        // server time was taken somewhere between request and response
+        let round_trip = millis(received) - millis(sent);
+        let skew = server_time - (millis(sent) + round_trip / 2);
+        let tolerance = self.max_clock_skew.as_millis() as i64 + round_trip / 2;
+        if skew.abs() > tolerance {
+            Check::warn(
+                "clock",
+                format!("local clock differs from cluster by {skew}ms"),
+                "synchronize clocks with NTP, ex: `sudo timedatectl set-ntp true`",
+            )
+        } else {
+            Check::pass("clock", format!("skew {skew}ms"))
+        }
+    }
+}
+
+fn read_file(path: &Path) -> Result<Vec<u8>> {
+    std::fs::read(path).map_err(|err| anyhow!("unable to read {}: {err}", path.display()))
+}
+
+async fn connect(config: ClientConfig) -> Result<VersionedSocket> {
+    timeout(CONNECT_TIMEOUT, config.connect())
+        .await
+        .map_err(|_| anyhow!("timed out after {}s", CONNECT_TIMEOUT.as_secs()))?
+        .map_err(|err| err.into())
+}
+
+fn check_extensions_dir() -> Check {
+    match fluvio_extensions_dir() {
+        Ok(dir) => Check::pass("extensions", format!("{}", dir.display())),
+        Err(err) => Check::fail(
+            "extensions",
+            err.to_string(),
+            "create ~/.fluvio/extensions or set FLUVIO_EXTENSIONS_DIR",
+        ),
+    }
+}
+
+fn check_bin_in_path() -> Check {
+    let bin_dir = match fluvio_bin_dir() {
+        Ok(dir) => dir,
+        Err(err) => return Check::fail("path", err.to_string(), "set FLUVIO_DIR"),
+    };
+    let paths: Vec<_> = std::env::var_os("PATH")
+        .map(|path| std::env::split_paths(&path).collect())
+        .unwrap_or_default();
+    if !paths.contains(&bin_dir) {
+        return Check::warn(
+            "path",
+            format!("{} is not in PATH", bin_dir.display()),
+            format!(
+                "add `export PATH=\"{}:$PATH\"` to your shell profile",
+                bin_dir.display()
+            ),
+        );
+    }
+
+    // `fluvio` in PATH may be a frontend launching channel binary, compare directories only
+    let resolved = which::which("fluvio").ok();
+    let current = std::env::current_exe().ok();
+    let resolved_dir = resolved.as_deref().and_then(Path::parent);
+    let current_dir = current.as_deref().and_then(Path::parent);
+    match (resolved_dir, current_dir) {
+        (Some(resolved_dir), Some(current_dir)) if resolved_dir != current_dir => Check::warn(
+            "path",
+            format!(
+                "`fluvio` in PATH is in {}, but running from {}",
+                resolved_dir.display(),
+                current_dir.display()
+            ),
+            "remove stale fluvio binaries or reorder PATH",
+        ),
+        _ => Check::pass("path", format!("{} is in PATH", bin_dir.display())),
+    }
+}
+
+fn check_version(platform: &semver::Version) -> Check {
+    let Ok(cli) = semver::Version::parse(crate::VERSION.trim()) else {
+        return Check::warn("version", "unable to parse CLI version", "reinstall CLI");
+    };
+    if (cli.major, cli.minor) == (platform.major, platform.minor) {
+        return Check::pass("version", format!("CLI {cli}, cluster {platform}"));
+    }
+    let fix = if cli < *platform {
+        format!("install matching CLI with `fvm install {platform}`")
+    } else {
+        "upgrade cluster with `fluvio cluster upgrade`".to_owned()
+    };
+    Check::warn(
+        "version",
+        format!("CLI {cli} doesn't match cluster {platform}"),
+        fix,
+    )
+}
+
+async fn check_spus(cluster: &FluvioClusterConfig, client_config: &ClientConfig) -> Vec<Check> {
+    let spus = match Fluvio::connect_with_config(cluster).await {
+        Ok(fluvio) => match fluvio.admin().await.all::<SpuSpec>().await {
+            Ok(spus) => spus,
+            Err(err) => {
+                return vec![Check::fail(
+                    "spu",
+                    format!("unable to list SPUs: {err}"),
+                    "check SC logs",
+                )];
+            }
+        },
+        Err(err) => {
+            return vec![Check::fail(
+                "spu",
+                format!("unable to connect: {err}"),
+                "make sure cluster is running with `fluvio cluster status`",
+            )];
+        }
+    };
+    if spus.is_empty() {
+        return vec![Check::fail(
+            "spu",
+            "no SPU registered",
+            "start SPUs, for local cluster run `fluvio cluster resume`",
+        )];
+    }
+
+    let mut checks = vec![];
+    for spu in spus {
+        let addr = match &spu.spec.public_endpoint_local {
+            Some(local) if cluster.use_spu_local_address => {
+                format!("{}:{}", local.host, local.port)
+            }
+            _ => spu.spec.public_endpoint.addr(),
+        };
+        let mut config = client_config.with_prefix_sni_domain(&spu.name);
+        config.set_addr(addr.clone());
+        let check = match connect(config).await {
+            Ok(_) => Check::pass("spu", format!("{} reachable at {addr}", spu.spec.id)),
+            Err(err) => Check::fail(
+                "spu",
+                format!("{} unreachable at {addr}: {err}", spu.spec.id),
+                "make sure SPU public address is reachable from this host, \
+                 check advertised address with `fluvio cluster spu list`",
+            ),
+        };
+        checks.push(check);
+    }
+    checks
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Pass,
+    Warn,
+    Fail,
+}
+
+#[derive(Debug)]
+struct Check {
+    name: &'static str,
+    status: Status,
+    message: String,
+    fix: Option<String>,
+}
+
+impl Check {
+    fn pass(name: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: Status::Pass,
+            message: message.into(),
+            fix: None,
+        }
+    }
+
+    fn warn(name: &'static str, message: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: Status::Warn,
+            message: message.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    fn fail(name: &'static str, message: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: Status::Fail,
+            message: message.into(),
+            fix: Some(fix.into()),
+        }
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let icon = match self.status {
+            Status::Pass => "✅",
+            Status::Warn => "⚠️ ",
+            Status::Fail => "❌",
+        };
+        write!(f, "{icon} {}: {}", self.name.bold(), self.message)?;
+        if let Some(fix) = &self.fix {
+            write!(f, "\n   fix: {fix}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use fluvio_extension_common::cert::generate_self_signed;
+
+    use super::*;
+
+    fn opt() -> DoctorOpt {
+        DoctorOpt {
+            cert_expiry_warning: Duration::from_secs(30 * 24 * 3600),
+            max_clock_skew: Duration::from_secs(5),
+        }
+    }
+
+    #[test]
+    fn test_check_clock_skew() {
+        let sent = UNIX_EPOCH + Duration::from_secs(1000);
+        let received = sent + Duration::from_millis(200);
+        let server = |offset: i64| Some(1_000_100 + offset);
+
+        assert_eq!(
+            opt().check_clock_skew(server(0), sent, received).status,
+            Status::Pass
+        );
+        assert_eq!(
+            opt().check_clock_skew(server(5_050), sent, received).status,
+            Status::Pass
+        );
+        assert_eq!(
+            opt()
+                .check_clock_skew(server(-6_000), sent, received)
+                .status,
+            Status::Warn
+        );
+        assert_eq!(
+            opt().check_clock_skew(None, sent, received).status,
+            Status::Warn
+        );
+    }
+
+    #[test]
+    fn test_check_version() {
+        let current = semver::Version::parse(crate::VERSION.trim()).expect("version");
+        assert_eq!(check_version(&current).status, Status::Pass);
+
+        let newer = semver::Version::new(current.major + 1, 0, 0);
+        let check = check_version(&newer);
+        assert_eq!(check.status, Status::Warn);
+        assert!(check.fix.expect("fix").contains("fvm install"));
+    }
+
+    #[test]
+    fn test_check_cert() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let (cert, _) = generate_self_signed(dir.path(), "ca", 60).expect("generate");
+        let ca = std::fs::read(cert).expect("read");
+        let check = opt().check_cert("ca cert", Ok(ca.clone()));
+        assert_eq!(check.status, Status::Pass);
+
+        // expiring within window
+        let mut opt = opt();
+        opt.cert_expiry_warning = Duration::from_secs(90 * 24 * 3600);
+        assert_eq!(opt.check_cert("ca cert", Ok(ca)).status, Status::Warn);
+
+        let check = opt.check_cert("ca cert", Err(anyhow!("missing")));
+        assert_eq!(check.status, Status::Fail);
+    }
+}
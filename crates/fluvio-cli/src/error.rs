@@ -44,6 +44,8 @@ pub enum CliError {
 
     #[error("Invalid argument: {0}")]
     InvalidArg(String),
+    #[error("Invalid CLI config: {0}")]
+    CliConfig(String),
     #[error("Unknown error: {0}")]
     Other(String),
     #[error("{0}")]
@@ -67,4 +69,6 @@ pub enum CliError {
     SmartModuleConfigBuilder(#[from] fluvio_smartengine::SmartModuleConfigBuilderError),
     #[error("Hub error: {0}")]
     HubError(String),
+    #[error("Timed out after {}", humantime::format_duration(*.0))]
+    Timeout(std::time::Duration),
 }
@@ -0,0 +1,153 @@
+//!
+//! # Release notes
+//!
+//! Summarizes entries of the project changelog for versions newer than the installed one.
+//! Changelog sections are headed by `## Platform Version <version> - <date>`, with one
+//! `### <category>` subsection per kind of change.
+//!
+
+use semver::Version;
+use serde::Serialize;
+
+pub const CHANGELOG_URL: &str =
+    "https://raw.githubusercontent.com/infinyon/fluvio/master/CHANGELOG.md";
+
+const VERSION_HEADING: &str = "## Platform Version ";
+const CATEGORY_HEADING: &str = "### ";
+
+/// Changes of one released version
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReleaseNotes {
+    pub version: String,
+    pub date: String,
+    pub categories: Vec<ReleaseCategory>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReleaseCategory {
+    pub name: String,
+    pub entries: Vec<String>,
+}
+
+/// Notes of versions after `installed` up to and including `latest`, newest first
+pub async fn fetch_release_notes(
+    installed: &Version,
+    latest: &Version,
+) -> anyhow::Result<Vec<ReleaseNotes>> {
+    let changelog = fluvio_cli_common::http::get_simple(CHANGELOG_URL).await?;
+    Ok(notes_between(&changelog, installed, latest))
+}
+
+fn notes_between(changelog: &str, installed: &Version, latest: &Version) -> Vec<ReleaseNotes> {
+    parse_changelog(changelog)
+        .into_iter()
+        .filter(|notes| {
+            Version::parse(&notes.version)
+                .is_ok_and(|version| version > *installed && version <= *latest)
+        })
+        .collect()
+}
+
+fn parse_changelog(changelog: &str) -> Vec<ReleaseNotes> {
+    let mut releases: Vec<ReleaseNotes> = vec![];
+    for line in changelog.lines().map(str::trim_end) {
+        if let Some(heading) = line.strip_prefix(VERSION_HEADING) {
+            let (version, date) = heading.split_once(" - ").unwrap_or((heading, ""));
+            releases.push(ReleaseNotes {
+                version: version.trim().to_owned(),
+                date: date.trim().to_owned(),
+                categories: vec![],
+            });
+        } else if let Some(release) = releases.last_mut() {
+            if let Some(category) = line.strip_prefix(CATEGORY_HEADING) {
+                release.categories.push(ReleaseCategory {
+                    name: category.trim().to_owned(),
+                    entries: vec![],
+                });
+            } else if let Some(entry) = line.strip_prefix("* ").or(line.strip_prefix("- "))
+                && let Some(category) = release.categories.last_mut()
+            {
+                category.entries.push(summarize_entry(entry));
+            }
+        }
+    }
+    releases
+}
+
+/// entry without markdown links, `Fix leak ([#12](https://..))` becomes `Fix leak (#12)`
+fn summarize_entry(entry: &str) -> String {
+    let mut summary = String::with_capacity(entry.len());
+    let mut rest = entry;
+    while let Some(start) = rest.find('[') {
+        let Some(end) = rest[start..].find("](").map(|end| start + end) else {
+            break;
+        };
+        let Some(close) = rest[end..].find(')').map(|close| end + close) else {
+            break;
+        };
+        summary.push_str(&rest[..start]);
+        summary.push_str(&rest[start + 1..end]);
+        rest = &rest[close + 1..];
+    }
+    summary.push_str(rest);
+    summary.trim().to_owned()
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    const CHANGELOG: &str = r#"# Release Notes
+
+## Platform Version 0.18.2 - UNRELEASED
+
+## Platform Version 0.18.1 - 2025-06-30
+
+### Added
+* Use rustls instead openssl ([#4569](https://github.com/infinyon/fluvio/issues/4569))
+
+### Fixed
+
+* Produce only on available partitions ([#4559](https://github.com/infinyon/fluvio/issues/4559))
+
+## Platform Version 0.18.0 - 2025-06-09
+
+### Added
+
+* Improve metrics granularity around smartmodules ([#4509](https://github.com/infinyon/fluvio/issues/4509))
+"#;
+
+    #[test]
+    fn test_notes_between() {
+        let notes = notes_between(CHANGELOG, &Version::new(0, 18, 0), &Version::new(0, 18, 1));
+
+        assert_eq!(
+            notes,
+            vec![ReleaseNotes {
+                version: "0.18.1".to_owned(),
+                date: "2025-06-30".to_owned(),
+                categories: vec![
+                    ReleaseCategory {
+                        name: "Added".to_owned(),
+                        entries: vec!["Use rustls instead openssl (#4569)".to_owned()],
+                    },
+                    ReleaseCategory {
+                        name: "Fixed".to_owned(),
+                        entries: vec!["Produce only on available partitions (#4559)".to_owned()],
+                    },
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_summarize_entry() {
+        assert_eq!(summarize_entry("Plain entry"), "Plain entry");
+        assert_eq!(
+            summarize_entry("See [docs](https://fluvio.io) and [#1](x)"),
+            "See docs and #1"
+        );
+        assert_eq!(summarize_entry("Unclosed [link"), "Unclosed [link");
+    }
+}
@@ -1,2 +1,3 @@
 pub mod opts;
 pub mod update;
+pub mod changelog;
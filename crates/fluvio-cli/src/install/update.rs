@@ -286,6 +286,31 @@ pub async fn check_update_available(
     }
 }
 
+/// Versions of the Fluvio CLI published for this target, oldest first.
+/// Prereleases are included only if `prerelease` is set
+#[instrument(
+    skip(agent),
+    fields(prefix = agent.base_url())
+)]
+pub async fn fetch_available_versions(agent: &HttpAgent, prerelease: bool) -> Result<Vec<Version>> {
+    let target = fluvio_index::package_target()?;
+    let id: PackageId = FLUVIO_CLI_PACKAGE_ID.parse()?;
+
+    let request = agent.request_package(&id)?;
+    let body = fluvio_cli_common::http::get_bytes_req(&request).await?;
+    let package = agent.package_from_response(&body).await?;
+
+    Ok(package
+        .releases_for_target(&target)
+        .into_iter()
+        .filter(|release| !release.yanked)
+        .filter(|release| {
+            prerelease || (release.version.pre.is_empty() && release.version.build.is_empty())
+        })
+        .map(|release| release.version.clone())
+        .collect())
+}
+
 /// Prompt the user about a new required version of the Fluvio CLI
 #[instrument(
     skip(agent),
@@ -3,6 +3,11 @@
 //! CLI configurations at the top of the tree
 
 mod error;
+mod cli_config;
+mod doctor;
+mod telemetry;
+#[cfg(feature = "consumer")]
+mod shell;
 pub mod client;
 pub mod install;
 mod profile;
@@ -23,6 +28,17 @@ use install::update::{
 pub use root::{Root, HelpOpt};
 pub use client::TableFormatConfig;
 
+/// exit code of command stopped by `--timeout`, same as coreutils `timeout`
+pub const TIMEOUT_EXIT_CODE: i32 = 124;
+
+/// process exit code for error returned by command
+pub fn exit_code(err: &anyhow::Error) -> i32 {
+    match err.downcast_ref::<CliError>() {
+        Some(CliError::Timeout(_)) => TIMEOUT_EXIT_CODE,
+        _ => 1,
+    }
+}
+
 // Checks for an update if channel is latest or ALWAYS_CHECK is set
 async fn check_for_channel_update() {
     if should_always_print_available_update() {
@@ -68,8 +84,9 @@ mod root {
     use std::sync::Arc;
     use std::path::PathBuf;
     use std::process::Command;
+    use std::time::Duration;
 
-    use clap::{Parser, Command as ClapCommand, CommandFactory};
+    use clap::{Parser, Command as ClapCommand, CommandFactory, FromArgMatches};
     use clap_complete::{generate, Shell};
     use fluvio_benchmark::cli::BenchmarkOpt;
     use tracing::debug;
@@ -79,7 +96,13 @@ mod root {
     use fluvio_cluster::cli::ClusterCmd;
     use fluvio_cli_common::install::fluvio_extensions_dir;
     use fluvio_channel::{FLUVIO_RELEASE_CHANNEL, LATEST_CHANNEL_NAME};
+    use fluvio_future::future::timeout;
 
+    use crate::cli_config::{AliasCmd, CliConfig};
+    use crate::doctor::DoctorOpt;
+    use crate::telemetry::{self, TelemetryCmd};
+    #[cfg(feature = "consumer")]
+    use crate::shell::ShellOpt;
     use crate::profile::ProfileOpt;
     use crate::install::opts::InstallOpt;
     use crate::client::FluvioCmd;
@@ -88,6 +111,7 @@ mod root {
     use crate::common::target::ClusterTarget;
     use crate::common::COMMAND_TEMPLATE;
     use crate::common::PrintTerminal;
+    use crate::CliError;
 
     /// Fluvio Command Line Interface
     #[derive(Parser, Debug)]
@@ -96,17 +120,62 @@ mod root {
         opts: RootOpt,
         #[clap(subcommand)]
         command: RootCmd,
+        /// name of invoked command, reported by telemetry
+        #[clap(skip)]
+        command_name: String,
     }
 
     impl Root {
+        /// Parse command line, using defaults from CLI config file for flags not given
+        pub fn parse_with_config() -> Result<Self> {
+            let config = CliConfig::load()?;
+            config.apply_env();
+            let command = config.apply_defaults(Self::command());
+            let args = config.expand_aliases(&command, std::env::args_os().collect())?;
+            let matches = command.clone().get_matches_from(args);
+            let mut root = Self::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+            root.command_name = telemetry::command_name(&command, &matches);
+            Ok(root)
+        }
+
         pub async fn process(self) -> Result<()> {
+            let Some(duration) = self.opts.timeout else {
+                return self.process_command().await;
+            };
+
+            // dropping command future cancels its in-flight requests
+            match timeout(duration, self.process_command()).await {
+                Ok(result) => result,
+                Err(_) => {
+                    restore_terminal();
+                    Err(CliError::Timeout(duration).into())
+                }
+            }
+        }
+
+        async fn process_command(self) -> Result<()> {
             if command_triggers_update_check(&self.command) {
                 tracing::info!("Triggered a Fluvio Update Check");
                 check_for_channel_update().await;
             }
 
-            self.command.process(self.opts).await?;
-            Ok(())
+            let result = self.command.process(self.opts).await;
+            telemetry::record(&self.command_name, result.is_ok());
+            result
+        }
+    }
+
+    /// leave full screen mode of interrupted command, if any
+    fn restore_terminal() {
+        use crossterm::{execute, terminal};
+
+        if terminal::is_raw_mode_enabled().unwrap_or(false) {
+            let _ = terminal::disable_raw_mode();
+            let _ = execute!(
+                std::io::stdout(),
+                terminal::LeaveAlternateScreen,
+                crossterm::cursor::Show
+            );
         }
     }
 
@@ -114,6 +183,21 @@ mod root {
     struct RootOpt {
         #[clap(flatten)]
         pub target: ClusterTarget,
+
+        /// Abort command if not completed within duration, ex: '30s', '5m'.
+        ///
+        /// In-flight requests are cancelled and command exits with code 124.
+        /// Defaults to `timeout` of CLI config.
+        /// Limits the whole command, including waits bounded by options of subcommands
+        /// such as `--wait-timeout`, so the deadline which is reached first stops the command.
+        #[arg(
+            long,
+            global = true,
+            env = "FLUVIO_TIMEOUT",
+            value_name = "DURATION",
+            value_parser = humantime::parse_duration
+        )]
+        pub timeout: Option<Duration>,
     }
 
     #[derive(Debug, Parser)]
@@ -134,6 +218,15 @@ mod root {
         #[cfg(feature = "consumer")]
         Fluvio(FluvioCmd),
 
+        /// Interactive shell reusing one connection to the cluster
+        ///
+        /// Runs fluvio commands without the `fluvio` prefix, with command history,
+        /// tab completion of commands, topics and profiles, and shorthands such as
+        /// `ls topics` or `tail <topic>`. Type `help` in the shell for details.
+        #[command(name = "shell")]
+        #[cfg(feature = "consumer")]
+        Shell(ShellOpt),
+
         /// Run Fluvio benchmarks
         #[command(name = "benchmark", alias = "bench")]
         Benchmark(BenchmarkOpt),
@@ -167,6 +260,24 @@ mod root {
         #[command(name = "version")]
         Version(VersionOpt),
 
+        /// Check local setup and connectivity to the cluster
+        ///
+        /// Validates extensions directory and PATH, the active profile, TLS certificates,
+        /// connectivity to SC and every SPU, clock skew and version alignment.
+        #[command(name = "doctor")]
+        Doctor(DoctorOpt),
+
+        /// Manage anonymous usage telemetry, off by default
+        ///
+        /// When turned on, each command sends its name, outcome, CLI version and platform.
+        /// Use `fluvio telemetry preview` to see exactly what is sent.
+        #[command(subcommand, name = "telemetry")]
+        Telemetry(TelemetryCmd),
+
+        /// Manage command aliases defined in the `[alias]` table of CLI config
+        #[command(subcommand, name = "alias")]
+        Alias(AliasCmd),
+
         /// Generate command-line completions for Fluvio
         ///
         /// Run the following two commands to enable fluvio command completions.
@@ -194,6 +305,10 @@ mod root {
                 Self::Fluvio(fluvio_cmd) => {
                     fluvio_cmd.process(out, root.target).await?;
                 }
+                #[cfg(feature = "consumer")]
+                Self::Shell(shell) => {
+                    shell.process(root.target).await?;
+                }
                 Self::Profile(profile) => {
                     profile.process(out).await?;
                 }
@@ -220,6 +335,15 @@ mod root {
                 Self::Version(version) => {
                     version.process(root.target).await?;
                 }
+                Self::Doctor(doctor) => {
+                    doctor.process(root.target).await?;
+                }
+                Self::Telemetry(telemetry) => {
+                    telemetry.process()?;
+                }
+                Self::Alias(alias) => {
+                    alias.process()?;
+                }
                 Self::Completions(completion) => {
                     completion.process()?;
                 }
@@ -18,13 +18,7 @@ pub struct ManualAddOpt {
 
 impl ManualAddOpt {
     pub fn process(self) -> Result<()> {
-        let mut config_file = match ConfigFile::load(None) {
-            Ok(config_file) => config_file,
-            Err(_) => {
-                println!("Creating default fluvio config file");
-                ConfigFile::default_config()?
-            }
-        };
+        let mut config_file = ConfigFile::load_default_or_new_for_update()?;
 
         let def_tls = TlsPolicy::Disabled;
         config_file.add_or_replace_profile(&self.profile_name, &self.cluster_address, &def_tls)?;
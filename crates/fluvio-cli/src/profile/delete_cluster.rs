@@ -17,7 +17,7 @@ impl DeleteClusterOpt {
     pub async fn process(self) -> Result<()> {
         let cluster_name = self.cluster_name;
 
-        let mut config_file = match ConfigFile::load(None) {
+        let mut config_file = match ConfigFile::load_for_update(None) {
             Ok(config_file) => config_file,
             Err(e) => {
                 println!("No config can be found: {e}");
@@ -1,9 +1,18 @@
+//!
+//! # Export profile
+//!
+//! Exports a profile with its cluster as a single document, which is imported
+//! on another machine with `fluvio profile import`.
+//! TLS certs referenced by files are inlined, unless references are kept on request.
+//!
+
+use std::convert::TryFrom;
 use std::sync::Arc;
 
 use clap::Parser;
-use anyhow::{anyhow, Result};
+use anyhow::{Context, Result};
 
-use fluvio::config::{ConfigFile, TlsConfig, TlsPolicy};
+use fluvio::config::{ConfigFile, TlsCerts, TlsConfig, TlsPolicy};
 use fluvio_extension_common::Terminal;
 use fluvio_extension_common::output::OutputType;
 
@@ -21,6 +30,11 @@ pub struct ExportOpt {
         ignore_case = true
     )]
     pub output_format: OutputType,
+
+    /// Keep paths of TLS certs referenced by files instead of inlining them,
+    /// certs must be at same paths where profile is imported
+    #[arg(long)]
+    pub tls_reference: bool,
 }
 
 impl ExportOpt {
@@ -41,39 +55,43 @@ impl ExportOpt {
             }
         };
 
-        let cluster_name = if let Some(ref profile_name) = self.profile_name {
+        let (profile_name, profile) = if let Some(ref profile_name) = self.profile_name {
             if let Some(profile) = config_file.config().profile(profile_name) {
-                profile.cluster.clone()
+                (profile_name.clone(), profile.clone())
             } else {
                 return Err(CliError::ProfileNotFoundInConfig(profile_name.to_owned()).into());
             }
-        } else if let Ok(profile) = config_file.config().current_profile() {
-            profile.cluster.clone()
+        } else if let (Ok(profile), Some(profile_name)) = (
+            config_file.config().current_profile(),
+            config_file.config().current_profile_name(),
+        ) {
+            (profile_name.to_owned(), profile.clone())
         } else {
             return Err(CliError::NoActiveProfileInConfig.into());
         };
-        let profile_export = if let Some(fluvio_config) =
-            config_file.config().cluster(&cluster_name)
+        let cluster_name = profile.cluster.clone();
+        let mut profile_export =
+            if let Some(fluvio_config) = config_file.config().cluster(&cluster_name) {
+                fluvio_config.to_owned()
+            } else {
+                return Err(CliError::ClusterNotFoundInConfig(cluster_name.to_owned()).into());
+            };
+
+        if !self.tls_reference
+            && let TlsPolicy::Verified(TlsConfig::Files(paths)) = &profile_export.tls
         {
-            if let TlsPolicy::Verified(TlsConfig::Files(_)) = fluvio_config.tls {
-                return Err(anyhow!(
-                    "Cluster {cluster_name} uses externals TLS certs. Only inline TLS certs are supported."
-                ));
-            }
-            fluvio_config
-        } else {
-            return Err(CliError::ClusterNotFoundInConfig(cluster_name.to_owned()).into());
-        };
+            let certs = TlsCerts::try_from(paths.clone())
+                .with_context(|| format!("unable to inline TLS certs of cluster {cluster_name}"))?;
+            profile_export.tls = certs.into();
+        }
 
         if output_format == OutputType::toml {
-            use fluvio::config::{Config, Profile};
+            use fluvio::config::Config;
 
             // add cluster to a new config export
-            let profile_name = cluster_name.clone();
             let mut config_export = Config::new();
 
-            config_export.add_cluster(profile_export.to_owned(), cluster_name.clone());
-            let profile = Profile::new(cluster_name.clone());
+            config_export.add_cluster(profile_export, cluster_name);
             config_export.add_profile(profile, profile_name.clone());
             config_export.set_current_profile(&profile_name);
             Ok(out.render_serde(&config_export, output_format.into())?)
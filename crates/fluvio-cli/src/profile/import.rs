@@ -0,0 +1,188 @@
+//!
+//! # Import profile
+//!
+//! Imports profiles exported with `fluvio profile export`, in TOML or JSON.
+//! Exported config documents keep their profile names, exported cluster configs
+//! are imported as profile of given name.
+//!
+
+use std::fs;
+use std::path::PathBuf;
+
+use clap::Parser;
+use anyhow::{Context, Result, anyhow};
+
+use fluvio::config::{Config, ConfigFile, FluvioClusterConfig, Profile};
+
+#[derive(Debug, Parser)]
+pub struct ImportOpt {
+    /// Exported profile, TOML or JSON
+    #[arg(value_name = "FILE")]
+    file: PathBuf,
+
+    /// Name of imported profile and its cluster, required for exported cluster configs
+    #[arg(long, value_name = "NAME")]
+    name: Option<String>,
+
+    /// Replace profile and cluster with same names
+    #[arg(long)]
+    force: bool,
+
+    /// Switch to imported profile
+    #[arg(long)]
+    switch: bool,
+}
+
+impl ImportOpt {
+    pub fn process(self) -> Result<()> {
+        let content = fs::read_to_string(&self.file)
+            .with_context(|| format!("unable to read {}", self.file.display()))?;
+        let profiles = parse_export(&content, self.name.as_deref())
+            .with_context(|| format!("invalid profile export {}", self.file.display()))?;
+
+        let mut config_file = ConfigFile::load_default_or_new_for_update()?;
+
+        let config = config_file.mut_config();
+        for (profile_name, profile, cluster) in &profiles {
+            if !self.force {
+                if config.profile(profile_name).is_some() {
+                    return Err(anyhow!(
+                        "profile {profile_name} already exists, use --force to replace it"
+                    ));
+                }
+                if config.cluster(&profile.cluster).is_some() {
+                    return Err(anyhow!(
+                        "cluster {} already exists, use --force to replace it",
+                        profile.cluster
+                    ));
+                }
+            }
+            config.add_cluster(cluster.clone(), profile.cluster.clone());
+            config.add_profile(profile.clone(), profile_name.clone());
+            println!("Imported profile {profile_name}");
+        }
+        if self.switch
+            && let Some((profile_name, _, _)) = profiles.first()
+        {
+            config.set_current_profile(profile_name);
+            println!("Switched to profile {profile_name}");
+        }
+        config_file.save()?;
+
+        Ok(())
+    }
+}
+
+/// profiles with their clusters from exported config or cluster config
+fn parse_export(
+    content: &str,
+    name: Option<&str>,
+) -> Result<Vec<(String, Profile, FluvioClusterConfig)>> {
+    let trimmed = content.trim_start();
+    let is_json = trimmed.starts_with('{');
+
+    let config: Result<Config> = if is_json {
+        serde_json::from_str(content).map_err(Into::into)
+    } else {
+        toml::from_str(content).map_err(Into::into)
+    };
+    match config {
+        Ok(config) if !config.profile.is_empty() => {
+            let mut profiles = Vec::with_capacity(config.profile.len());
+            let mut exported: Vec<_> = config.profile.iter().collect();
+            exported.sort_by(|(a, _), (b, _)| a.cmp(b));
+            for (profile_name, profile) in exported {
+                let cluster = config.cluster(&profile.cluster).ok_or_else(|| {
+                    anyhow!(
+                        "cluster {} of profile {profile_name} is missing",
+                        profile.cluster
+                    )
+                })?;
+                let mut profile = profile.clone();
+                let profile_name = match name {
+                    Some(name) => {
+                        profile.cluster = name.to_owned();
+                        name.to_owned()
+                    }
+                    None => profile_name.clone(),
+                };
+                profiles.push((profile_name, profile, cluster.clone()));
+            }
+            if name.is_some() && profiles.len() > 1 {
+                return Err(anyhow!(
+                    "--name can't be used with multiple exported profiles"
+                ));
+            }
+            Ok(profiles)
+        }
+        _ => {
+            let cluster: FluvioClusterConfig = if is_json {
+                serde_json::from_str(content)?
+            } else {
+                toml::from_str(content)?
+            };
+            let name =
+                name.ok_or_else(|| anyhow!("--name is required to import cluster config"))?;
+            Ok(vec![(
+                name.to_owned(),
+                Profile::new(name.to_owned()),
+                cluster,
+            )])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use fluvio::config::TlsPolicy;
+
+    use super::*;
+
+    const EXPORTED_CONFIG: &str = r#"
+version = "2.0"
+current_profile = "prod"
+
+[profile.prod]
+cluster = "prod-cluster"
+topic = "events"
+
+[cluster.prod-cluster]
+endpoint = "fluvio.example.com:9003"
+
+[cluster.prod-cluster.tls]
+tls_policy = "anonymous"
+
+[cluster.prod-cluster.metadata.installation]
+type = "k8"
+"#;
+
+    #[test]
+    fn test_parse_exported_config() {
+        let profiles = parse_export(EXPORTED_CONFIG, None).expect("parse");
+        assert_eq!(profiles.len(), 1);
+        let (profile_name, profile, cluster) = &profiles[0];
+        assert_eq!(profile_name, "prod");
+        assert_eq!(profile.cluster, "prod-cluster");
+        assert_eq!(profile.topic.as_deref(), Some("events"));
+        assert_eq!(cluster.endpoint, "fluvio.example.com:9003");
+        assert_eq!(cluster.tls, TlsPolicy::Anonymous);
+        assert!(cluster.has_metadata("installation"));
+
+        let profiles = parse_export(EXPORTED_CONFIG, Some("staging")).expect("parse");
+        assert_eq!(profiles[0].0, "staging");
+        assert_eq!(profiles[0].1.cluster, "staging");
+    }
+
+    #[test]
+    fn test_parse_exported_cluster() {
+        let exported = r#"{"endpoint": "127.0.0.1:9003", "tls": {"tls_policy": "disabled"}}"#;
+        assert!(parse_export(exported, None).is_err());
+
+        let profiles = parse_export(exported, Some("dev")).expect("parse");
+        let (profile_name, profile, cluster) = &profiles[0];
+        assert_eq!(profile_name, "dev");
+        assert_eq!(profile.cluster, "dev");
+        assert_eq!(cluster.endpoint, "127.0.0.1:9003");
+    }
+}
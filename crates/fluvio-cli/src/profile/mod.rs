@@ -13,6 +13,7 @@ mod delete_profile;
 mod delete_cluster;
 mod list;
 mod export;
+mod import;
 
 use std::sync::Arc;
 
@@ -29,6 +30,7 @@ use crate::profile::sync::SyncCmd;
 use crate::profile::list::ListOpt;
 use crate::profile::rename::RenameOpt;
 use crate::profile::export::ExportOpt;
+use crate::profile::import::ImportOpt;
 
 #[derive(Debug, Parser)]
 pub struct ProfileOpt {
@@ -82,6 +84,10 @@ pub enum ProfileCmd {
     #[command(name = "export")]
     Export(ExportOpt),
 
+    /// Import a profile exported with `fluvio profile export`
+    #[command(name = "import")]
+    Import(ImportOpt),
+
     /// Manually add a profile (advanced)
     #[command(name = "add")]
     ManualAdd(ManualAddOpt),
@@ -114,6 +120,9 @@ impl ProfileCmd {
             Self::Export(export) => {
                 export.process(out)?;
             }
+            Self::Import(import) => {
+                import.process()?;
+            }
             Self::ManualAdd(add) => {
                 add.process()?;
             }
@@ -13,7 +13,7 @@ pub struct RenameOpt {
 
 impl RenameOpt {
     pub fn process(self) -> Result<()> {
-        let mut config_file = match ConfigFile::load(None) {
+        let mut config_file = match ConfigFile::load_for_update(None) {
             Ok(config) => config,
             Err(e) => {
                 eprintln!("unable to find Fluvio config file");
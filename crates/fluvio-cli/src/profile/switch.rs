@@ -17,7 +17,7 @@ pub struct SwitchOpt {
 impl SwitchOpt {
     pub async fn process<O: Terminal>(self, out: Arc<O>) -> Result<()> {
         let profile_name = self.profile_name;
-        match ConfigFile::load(None) {
+        match ConfigFile::load_for_update(None) {
             Ok(mut config_file) => {
                 if !config_file.mut_config().set_current_profile(&profile_name) {
                     println!("profile {} not found", &profile_name);
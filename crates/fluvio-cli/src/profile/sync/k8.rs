@@ -66,7 +66,7 @@ fn compute_profile_name() -> Result<String> {
 
 /// create new k8 cluster and profile
 pub async fn set_k8_context(opt: K8Opt, external_addr: String) -> Result<Profile> {
-    let mut config_file = ConfigFile::load_default_or_new()?;
+    let mut config_file = ConfigFile::load_default_or_new_for_update()?;
     let config = config_file.mut_config();
 
     let profile_name = if let Some(name) = &opt.name {
@@ -34,7 +34,7 @@ impl LocalOpt {
 /// create new local cluster and profile
 pub fn set_local_context(local_config: LocalOpt) -> Result<String> {
     let local_addr = local_config.local;
-    let mut config_file = ConfigFile::load_default_or_new()?;
+    let mut config_file = ConfigFile::load_default_or_new_for_update()?;
 
     let config = config_file.mut_config();
 
@@ -0,0 +1,399 @@
+//! # Shell
+//!
+//! Interactive REPL running fluvio commands over a single connection to the cluster,
+//! with command history, completion of commands, topics and profiles, and shorthand commands
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use clap::{CommandFactory, Parser};
+use anyhow::Result;
+use rustyline::{Config, Context, Editor, Helper};
+use rustyline::completion::Completer;
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::FileHistory;
+use rustyline::validate::Validator;
+use tracing::debug;
+
+use fluvio::Fluvio;
+use fluvio::config::ConfigFile;
+use fluvio::metadata::topic::TopicSpec;
+use fluvio_cli_common::install::fluvio_base_dir;
+use fluvio_extension_common::target::ClusterTarget;
+
+use crate::client::{FluvioCmd, connect};
+use crate::common::PrintTerminal;
+
+const HISTORY_FILE: &str = "shell_history";
+
+/// records shown by `tail` if count is not given
+const DEFAULT_TAIL: u32 = 10;
+
+/// commands handled by the shell itself
+const BUILTINS: &[&str] = &["exit", "quit", "help", "profile", "ls", "tail", "cat"];
+
+/// objects which can be listed with `ls`
+const LS_TARGETS: &[&str] = &[
+    "topics",
+    "partitions",
+    "smartmodules",
+    "consumers",
+    "tableformats",
+    "remotes",
+];
+
+const SHORTHAND_HELP: &str = "\
+Shell commands:
+  ls [topics|partitions|smartmodules|consumers|tableformats|remotes]   list objects, topics by default
+  tail <topic> [count] [options]   consume last records of topic (10 by default) and wait for new ones
+  cat <topic> [options]      print all records of topic and exit
+  profile [name]             show current profile or reconnect using another one
+  help                       show this help
+  exit, quit                 leave the shell (or Ctrl-D)
+
+Any other line is run as a fluvio command without the `fluvio` prefix, ex: `topic list`
+";
+
+#[derive(Debug, Parser)]
+pub struct ShellOpt {
+    /// File keeping command history [default: ~/.fluvio/shell_history]
+    #[arg(long, value_name = "path")]
+    history_file: Option<PathBuf>,
+}
+
+impl ShellOpt {
+    pub async fn process(self, target: ClusterTarget) -> Result<()> {
+        let history_file = match self.history_file {
+            Some(path) => path,
+            None => fluvio_base_dir()?.join(HISTORY_FILE),
+        };
+
+        let mut target = target;
+        let mut fluvio = connect(&target).await?;
+        let mut name = prompt_name(&target);
+        let out = Arc::new(PrintTerminal::new());
+
+        let config = Config::builder().auto_add_history(false).build();
+        let mut editor: Editor<ShellHelper, FileHistory> = Editor::with_config(config)?;
+        let mut helper = ShellHelper::new();
+        helper.profiles = profile_names();
+        helper.topics = topic_names(&fluvio).await;
+        editor.set_helper(Some(helper));
+        if let Err(err) = editor.load_history(&history_file) {
+            debug!(%err, "no shell history loaded");
+        }
+
+        println!("Connected to {name}. Type `help` for shell commands, `exit` to quit.");
+        loop {
+            // readline blocks this thread only, connection tasks keep running on the executor
+            let line = match editor.readline(&format!("{name}> ")) {
+                Ok(line) => line,
+                Err(ReadlineError::Interrupted) => continue,
+                Err(ReadlineError::Eof) => break,
+                Err(err) => return Err(err.into()),
+            };
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            editor.add_history_entry(line)?;
+
+            let words = match shell_words::split(line) {
+                Ok(words) => words,
+                Err(err) => {
+                    eprintln!("{err}");
+                    continue;
+                }
+            };
+
+            match words.first().map(String::as_str) {
+                Some("exit" | "quit") => break,
+                Some("help") => {
+                    let _ = FluvioCmd::command().print_help();
+                    println!("\n{SHORTHAND_HELP}");
+                    continue;
+                }
+                Some("profile") => match words.get(1) {
+                    Some(profile) => {
+                        let new_target = ClusterTarget {
+                            profile: Some(profile.to_owned()),
+                            ..Default::default()
+                        };
+                        match connect(&new_target).await {
+                            Ok(client) => {
+                                fluvio = client;
+                                target = new_target;
+                                name = prompt_name(&target);
+                            }
+                            Err(err) => eprintln!("{err}"),
+                        }
+                    }
+                    None => println!("{name}"),
+                },
+                _ => {
+                    let args = std::iter::once("fluvio".to_owned()).chain(expand_shorthand(words));
+                    match FluvioCmd::try_parse_from(args) {
+                        Ok(cmd) => {
+                            if let Err(err) = cmd
+                                .process_client(out.clone(), &fluvio, target.clone())
+                                .await
+                            {
+                                eprintln!("{err}");
+                            }
+                        }
+                        Err(err) => {
+                            let _ = err.print();
+                        }
+                    }
+                }
+            }
+
+            if let Some(helper) = editor.helper_mut() {
+                helper.topics = topic_names(&fluvio).await;
+                helper.profiles = profile_names();
+            }
+        }
+
+        if let Err(err) = editor.save_history(&history_file) {
+            debug!(%err, "unable to save shell history");
+        }
+        Ok(())
+    }
+}
+
+/// profile or cluster the shell is connected to
+fn prompt_name(target: &ClusterTarget) -> String {
+    if let Some(profile) = &target.profile {
+        return profile.to_owned();
+    }
+    if let Some(cluster) = &target.cluster {
+        return cluster.to_owned();
+    }
+    ConfigFile::load_default_or_new()
+        .ok()
+        .and_then(|config| config.config().current_profile_name().map(str::to_owned))
+        .unwrap_or_else(|| "fluvio".to_owned())
+}
+
+async fn topic_names(fluvio: &Fluvio) -> Vec<String> {
+    let topics = match fluvio.admin().await.all::<TopicSpec>().await {
+        Ok(topics) => topics,
+        Err(err) => {
+            debug!(%err, "unable to list topics for completion");
+            return vec![];
+        }
+    };
+    topics.into_iter().map(|topic| topic.name).collect()
+}
+
+fn profile_names() -> Vec<String> {
+    ConfigFile::load_default_or_new()
+        .map(|config| config.config().profile.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// expand shorthand commands of the shell into fluvio commands
+fn expand_shorthand(words: Vec<String>) -> Vec<String> {
+    let command: &[&str] = match words.first().map(String::as_str) {
+        Some("ls") => {
+            let object = match words.get(1).map(String::as_str) {
+                None | Some("topics") => "topic",
+                Some("partitions") => "partition",
+                Some("smartmodules" | "sm") => "smartmodule",
+                Some("consumers") => "consumer",
+                Some("tableformats" | "tf") => "table-format",
+                Some("remotes") => "remote",
+                Some(_) => return words,
+            };
+            let options = words.iter().skip(2).cloned();
+            return [object.to_owned(), "list".to_owned()]
+                .into_iter()
+                .chain(options)
+                .collect();
+        }
+        Some("tail") if words.len() > 1 => {
+            let (count, skip) = match words.get(2).filter(|arg| arg.parse::<u32>().is_ok()) {
+                Some(count) => (count.to_owned(), 3),
+                None => (DEFAULT_TAIL.to_string(), 2),
+            };
+            return [
+                "consume".to_owned(),
+                words[1].to_owned(),
+                "-T".to_owned(),
+                count,
+            ]
+            .into_iter()
+            .chain(words.into_iter().skip(skip))
+            .collect();
+        }
+        Some("cat") if words.len() > 1 => &["consume", "-B", "-d"],
+        _ => return words,
+    };
+
+    // topic followed by the expanded options, then options given by user
+    let topic = words[1].to_owned();
+    std::iter::once(command[0].to_owned())
+        .chain(std::iter::once(topic))
+        .chain(command[1..].iter().map(|option| (*option).to_owned()))
+        .chain(words.into_iter().skip(2))
+        .collect()
+}
+
+/// completion of commands, topics and profiles
+struct ShellHelper {
+    /// top level commands with their subcommands
+    commands: BTreeMap<String, Vec<String>>,
+    topics: Vec<String>,
+    profiles: Vec<String>,
+}
+
+impl ShellHelper {
+    fn new() -> Self {
+        let commands = FluvioCmd::command()
+            .get_subcommands()
+            .filter(|command| !command.is_hide_set())
+            .map(|command| {
+                let subcommands = command
+                    .get_subcommands()
+                    .filter(|subcommand| !subcommand.is_hide_set())
+                    .map(|subcommand| subcommand.get_name().to_owned())
+                    .collect();
+                (command.get_name().to_owned(), subcommands)
+            })
+            .chain(
+                BUILTINS
+                    .iter()
+                    .map(|builtin| ((*builtin).to_owned(), vec![])),
+            )
+            .collect();
+        Self {
+            commands,
+            topics: vec![],
+            profiles: vec![],
+        }
+    }
+
+    /// candidates for the word being typed, given the words before it
+    fn candidates(&self, previous: &[&str], word: &str) -> Vec<String> {
+        let options: Vec<&String> = match previous {
+            [] => self.commands.keys().collect(),
+            ["profile"] => self.profiles.iter().collect(),
+            ["ls"] => return matching(LS_TARGETS.iter().copied(), word),
+            [command] => match self.commands.get(*command) {
+                Some(subcommands) if !subcommands.is_empty() => subcommands.iter().collect(),
+                _ => self.topics.iter().collect(),
+            },
+            _ => self.topics.iter().collect(),
+        };
+        matching(options.into_iter().map(String::as_str), word)
+    }
+}
+
+fn matching<'a>(options: impl Iterator<Item = &'a str>, word: &str) -> Vec<String> {
+    let mut candidates: Vec<String> = options
+        .filter(|option| option.starts_with(word))
+        .map(str::to_owned)
+        .collect();
+    candidates.sort();
+    candidates.dedup();
+    candidates
+}
+
+impl Completer for ShellHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        let line = &line[..pos];
+        let mut previous: Vec<&str> = line.split_whitespace().collect();
+        let word = if line.ends_with(char::is_whitespace) {
+            ""
+        } else {
+            previous.pop().unwrap_or_default()
+        };
+        Ok((pos - word.len(), self.candidates(&previous, word)))
+    }
+}
+
+impl Hinter for ShellHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ShellHelper {}
+
+impl Validator for ShellHelper {}
+
+impl Helper for ShellHelper {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn words(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_owned).collect()
+    }
+
+    #[test]
+    fn test_expand_shorthand() {
+        assert_eq!(expand_shorthand(words("ls")), words("topic list"));
+        assert_eq!(
+            expand_shorthand(words("ls consumers -O json")),
+            words("consumer list -O json")
+        );
+        assert_eq!(expand_shorthand(words("ls foo")), words("ls foo"));
+        assert_eq!(
+            expand_shorthand(words("tail events -k")),
+            words("consume events -T 10 -k")
+        );
+        assert_eq!(
+            expand_shorthand(words("tail events 5")),
+            words("consume events -T 5")
+        );
+        assert_eq!(
+            expand_shorthand(words("cat events")),
+            words("consume events -B -d")
+        );
+        assert_eq!(expand_shorthand(words("topic list")), words("topic list"));
+        assert_eq!(expand_shorthand(words("tail")), words("tail"));
+    }
+
+    #[test]
+    fn test_candidates() {
+        let mut helper = ShellHelper::new();
+        helper.topics = words("events orders");
+        helper.profiles = words("local cloud");
+
+        assert!(helper.candidates(&[], "to").contains(&"topic".to_owned()));
+        assert!(helper.candidates(&[], "ta").contains(&"tail".to_owned()));
+        assert!(
+            helper
+                .candidates(&["topic"], "")
+                .contains(&"describe".to_owned())
+        );
+        assert_eq!(helper.candidates(&["consume"], "ev"), words("events"));
+        assert_eq!(
+            helper.candidates(&["topic", "describe"], ""),
+            words("events orders")
+        );
+        assert_eq!(helper.candidates(&["profile"], "l"), words("local"));
+        assert_eq!(
+            helper.candidates(&["ls"], "t"),
+            words("tableformats topics")
+        );
+    }
+
+    #[test]
+    fn test_cli_parses_expanded_commands() {
+        for line in ["ls", "ls partitions", "tail events", "cat events -O json"] {
+            let args = std::iter::once("fluvio".to_owned()).chain(expand_shorthand(words(line)));
+            assert!(FluvioCmd::try_parse_from(args).is_ok(), "{line}");
+        }
+    }
+}
@@ -0,0 +1,387 @@
+//! # Telemetry
+//!
+//! Opt-in anonymous usage reporting, nothing is collected unless enabled with
+//! `fluvio telemetry on --endpoint <url>`.
+//! Each command sends one event with its name, outcome, CLI version and platform.
+//! Events are sent by a background process, so commands never wait for the endpoint.
+//! Arguments, topic names, profiles and cluster addresses are never included.
+//! `fluvio telemetry preview` prints the event exactly as it would be sent.
+//!
+//! `FLUVIO_TELEMETRY=off` or `DO_NOT_TRACK=1` turn telemetry off regardless of settings,
+//! which are stored in `~/.fluvio/telemetry.toml`.
+
+use std::path::PathBuf;
+use std::process::{Command as Process, Stdio};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use clap::{ArgMatches, Command, Parser};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+use anyhow::{anyhow, Result};
+
+use fluvio_channel::FLUVIO_RELEASE_CHANNEL;
+use fluvio_cli_common::install::fluvio_base_dir;
+
+pub const FLUVIO_TELEMETRY: &str = "FLUVIO_TELEMETRY";
+const DO_NOT_TRACK: &str = "DO_NOT_TRACK";
+const TELEMETRY_FILE: &str = "telemetry.toml";
+
+/// background process doesn't wait longer than this for endpoint
+const SEND_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// name of commands not built into the CLI, so plugin names are not reported
+const PLUGIN_COMMAND: &str = "plugin";
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TelemetrySettings {
+    #[serde(default)]
+    pub enabled: bool,
+    /// random id generated when telemetry is turned on, discarded when turned off
+    pub install_id: Option<String>,
+    /// where events are sent, set when telemetry is turned on
+    pub endpoint: Option<String>,
+}
+
+impl TelemetrySettings {
+    pub fn path() -> Result<PathBuf> {
+        Ok(fluvio_base_dir()?.join(TELEMETRY_FILE))
+    }
+
+    /// settings from default location, disabled if file doesn't exist or can't be read
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| Ok(std::fs::read_to_string(path)?))
+            .ok()
+            .and_then(|content| toml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        std::fs::write(Self::path()?, toml::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// true if events are sent: enabled with endpoint and not turned off by environment
+    pub fn is_active(&self) -> bool {
+        self.enabled && self.endpoint.is_some() && !disabled_by_env()
+    }
+
+    pub fn endpoint(&self) -> &str {
+        self.endpoint.as_deref().unwrap_or("<not set>")
+    }
+}
+
+fn disabled_by_env() -> bool {
+    let set = |var: &str, values: &[&str]| {
+        std::env::var(var).is_ok_and(|value| values.contains(&value.to_lowercase().as_str()))
+    };
+    set(FLUVIO_TELEMETRY, &["off", "0", "false"]) || set(DO_NOT_TRACK, &["1", "true"])
+}
+
+/// everything sent about one command
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TelemetryEvent {
+    pub install_id: String,
+    pub command: String,
+    pub success: bool,
+    pub cli_version: String,
+    pub release_channel: Option<String>,
+    pub os: String,
+    pub arch: String,
+}
+
+impl TelemetryEvent {
+    pub fn new(install_id: impl Into<String>, command: impl Into<String>, success: bool) -> Self {
+        Self {
+            install_id: install_id.into(),
+            command: command.into(),
+            success,
+            cli_version: crate::VERSION.trim().to_owned(),
+            release_channel: std::env::var(FLUVIO_RELEASE_CHANNEL).ok(),
+            os: std::env::consts::OS.to_owned(),
+            arch: std::env::consts::ARCH.to_owned(),
+        }
+    }
+}
+
+/// name of subcommands invoked, ex: `topic create`, without any argument
+pub fn command_name(cmd: &Command, matches: &ArgMatches) -> String {
+    let mut names = vec![];
+    let (mut cmd, mut matches) = (cmd, matches);
+    while let Some((name, sub_matches)) = matches.subcommand() {
+        let Some(sub_cmd) = cmd.find_subcommand(name) else {
+            names.push(PLUGIN_COMMAND);
+            break;
+        };
+        names.push(sub_cmd.get_name());
+        cmd = sub_cmd;
+        matches = sub_matches;
+    }
+    names.join(" ")
+}
+
+/// send event of command if telemetry is active, failures are ignored.
+/// Event is sent by detached `fluvio telemetry send` process, command doesn't wait for it
+pub fn record(command: &str, success: bool) {
+    // telemetry commands are not reported, sending process would report itself
+    if command.split(' ').next() == Some("telemetry") {
+        return;
+    }
+    let settings = TelemetrySettings::load();
+    if !settings.is_active() {
+        return;
+    }
+    let (Some(install_id), Some(endpoint)) = (&settings.install_id, &settings.endpoint) else {
+        return;
+    };
+    let event = TelemetryEvent::new(install_id, command, success);
+    let Ok(event) = serde_json::to_string(&event) else {
+        return;
+    };
+
+    let spawned = std::env::current_exe().and_then(|exe| {
+        Process::new(exe)
+            .args([
+                "telemetry",
+                "send",
+                "--endpoint",
+                endpoint,
+                "--event",
+                &event,
+            ])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+    });
+    match spawned {
+        Ok(_) => debug!("telemetry event handed to background process"),
+        Err(err) => debug!(%err, "telemetry event not sent"),
+    }
+}
+
+/// send event and wait for endpoint to accept it
+fn send(endpoint: &str, event: &TelemetryEvent) -> Result<()> {
+    let request = fluvio_hub_util::http::Request::post(endpoint)
+        .header("Content-Type", "application/json")
+        .body(serde_json::to_vec(event)?)?;
+
+    // request is blocking, send it on its own thread so it can time out
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        let _ = sender.send(futures::executor::block_on(
+            fluvio_hub_util::htclient::send(request),
+        ));
+    });
+    let response = receiver
+        .recv_timeout(SEND_TIMEOUT)
+        .map_err(|_| anyhow!("{endpoint} didn't answer in {}s", SEND_TIMEOUT.as_secs()))??;
+    if !response.status().is_success() {
+        return Err(anyhow!(
+            "{endpoint} failed with status {}",
+            response.status()
+        ));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Parser)]
+pub enum TelemetryCmd {
+    /// Send anonymous usage events to endpoint
+    #[command(name = "on")]
+    On(TelemetryOnOpt),
+    /// Stop sending usage events and discard the install id
+    #[command(name = "off")]
+    Off,
+    /// Show whether usage events are sent
+    #[command(name = "status")]
+    Status,
+    /// Print the event which would be sent for a command, without sending it
+    #[command(name = "preview")]
+    Preview(TelemetryPreviewOpt),
+    /// Send event of a command, run in background after commands
+    #[command(name = "send", hide = true)]
+    Send(TelemetrySendOpt),
+}
+
+#[derive(Debug, Parser)]
+pub struct TelemetryOnOpt {
+    /// URL events are POSTed to as JSON, previous endpoint is kept if not given
+    #[arg(long, value_name = "url")]
+    endpoint: Option<String>,
+}
+
+#[derive(Debug, Parser)]
+pub struct TelemetrySendOpt {
+    #[arg(long)]
+    endpoint: String,
+
+    /// event as JSON
+    #[arg(long)]
+    event: String,
+}
+
+#[derive(Debug, Parser)]
+pub struct TelemetryPreviewOpt {
+    /// Command to preview event of, ex: topic create
+    #[arg(default_values_t = ["telemetry".to_owned(), "preview".to_owned()])]
+    command: Vec<String>,
+}
+
+impl TelemetryCmd {
+    pub fn process(self) -> Result<()> {
+        let mut settings = TelemetrySettings::load();
+        match self {
+            Self::On(opt) => {
+                if let Some(endpoint) = opt.endpoint {
+                    settings.endpoint = Some(endpoint);
+                }
+                if settings.endpoint.is_none() {
+                    return Err(anyhow!(
+                        "telemetry endpoint is required: fluvio telemetry on --endpoint <url>"
+                    ));
+                }
+                settings.enabled = true;
+                settings
+                    .install_id
+                    .get_or_insert_with(|| uuid::Uuid::new_v4().to_string());
+                settings.save()?;
+                println!("telemetry is on, turn it off with `fluvio telemetry off`");
+                if disabled_by_env() {
+                    println!(
+                        "no events are sent while {FLUVIO_TELEMETRY} or {DO_NOT_TRACK} turn it off"
+                    );
+                }
+            }
+            Self::Off => {
+                settings.enabled = false;
+                settings.install_id = None;
+                settings.save()?;
+                println!("telemetry is off");
+            }
+            Self::Status => {
+                let status = if settings.is_active() {
+                    "on"
+                } else if settings.enabled {
+                    "off (disabled by environment)"
+                } else {
+                    "off"
+                };
+                println!("telemetry: {status}");
+                println!("endpoint:  {}", settings.endpoint());
+            }
+            Self::Preview(opt) => {
+                let install_id = settings
+                    .install_id
+                    .clone()
+                    .unwrap_or_else(|| "<generated when turned on>".to_owned());
+                let event = TelemetryEvent::new(install_id, opt.command.join(" "), true);
+                println!("POST {}", settings.endpoint());
+                println!("{}", serde_json::to_string_pretty(&event)?);
+                if !settings.is_active() {
+                    println!("telemetry is off, nothing is sent");
+                }
+            }
+            Self::Send(opt) => {
+                let event: TelemetryEvent = serde_json::from_str(&opt.event)?;
+                match send(&opt.endpoint, &event) {
+                    Ok(()) => debug!("telemetry event sent"),
+                    Err(err) => debug!(%err, "telemetry event not sent"),
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use clap::CommandFactory;
+
+    use crate::Root;
+
+    use super::*;
+
+    #[test]
+    fn test_command_name() {
+        let cmd = Root::command();
+        let name = |args: &[&str]| {
+            let matches = cmd.clone().try_get_matches_from(args).expect("parse");
+            command_name(&cmd, &matches)
+        };
+
+        assert_eq!(
+            name(&["fluvio", "topic", "create", "secret-topic"]),
+            "topic create"
+        );
+        assert_eq!(name(&["fluvio", "consume", "orders", "-B"]), "consume");
+        assert_eq!(
+            name(&["fluvio", "my-plugin", "--token", "x"]),
+            PLUGIN_COMMAND
+        );
+    }
+
+    #[test]
+    fn test_active_requires_endpoint() {
+        let mut settings = TelemetrySettings {
+            enabled: true,
+            install_id: Some("id".to_owned()),
+            endpoint: None,
+        };
+        assert!(!settings.is_active());
+        settings.endpoint = Some("http://localhost:8080/events".to_owned());
+        assert_eq!(settings.is_active(), !disabled_by_env());
+    }
+
+    #[test]
+    fn test_send_checks_status() {
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+        let endpoint = format!("http://{}/events", listener.local_addr().expect("addr"));
+        let server = std::thread::spawn(move || {
+            for status in ["404 Not Found", "204 No Content"] {
+                let (mut stream, _) = listener.accept().expect("accept");
+                // read whole request, JSON body ends with its closing brace
+                let mut request = vec![];
+                let mut buf = [0u8; 1024];
+                while !request.ends_with(b"}") {
+                    let read = stream.read(&mut buf).expect("read");
+                    if read == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&buf[..read]);
+                }
+                let reply = format!("HTTP/1.1 {status}\r\nContent-Length: 0\r\n\r\n");
+                stream.write_all(reply.as_bytes()).expect("write");
+            }
+        });
+
+        let event = TelemetryEvent::new("id", "topic list", true);
+        assert!(send(&endpoint, &event).is_err());
+        assert!(send(&endpoint, &event).is_ok());
+        server.join().expect("server");
+    }
+
+    #[test]
+    fn test_settings_roundtrip() {
+        let settings = TelemetrySettings {
+            enabled: true,
+            install_id: Some("id".to_owned()),
+            endpoint: None,
+        };
+        let content = toml::to_string(&settings).expect("serialize");
+
+        assert_eq!(
+            toml::from_str::<TelemetrySettings>(&content).expect("parse"),
+            settings
+        );
+        assert_eq!(
+            toml::from_str::<TelemetrySettings>("").expect("parse"),
+            TelemetrySettings::default()
+        );
+    }
+}
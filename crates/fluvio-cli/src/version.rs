@@ -1,24 +1,53 @@
+use std::time::Duration;
+
 use sha2::{Digest, Sha256};
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use anyhow::Result;
+use semver::Version;
+use serde::Serialize;
 
-use fluvio::Fluvio;
-use fluvio::config::ConfigFile;
+use fluvio::{Fluvio, FluvioClusterConfig};
+use fluvio::config::{ConfigFile, TlsConfig, TlsPolicy};
 use fluvio_cli_common::version_cmd::{FluvioVersionPrinter, os_info};
+use fluvio_extension_common::cert::CertExpiry;
 use fluvio_extension_common::target::ClusterTarget;
-use fluvio_channel::FLUVIO_RELEASE_CHANNEL;
+use fluvio_channel::{FLUVIO_RELEASE_CHANNEL, LATEST_CHANNEL_NAME, STABLE_CHANNEL_NAME};
+use fluvio_index::HttpAgent;
 
+use crate::install::changelog::{ReleaseNotes, fetch_release_notes};
+use crate::install::update::fetch_available_versions;
 use crate::metadata::subcommand_metadata;
 
+/// number of newest available versions listed by `version check`
+const AVAILABLE_VERSIONS_SHOWN: usize = 5;
+
 #[derive(Debug, Parser)]
 pub struct VersionOpt {
     #[clap(short, long)]
     /// Output in JSON format
     pub json: bool,
+
+    /// Flag TLS certificates expiring within this period. Ex: '30days', '12h'
+    #[arg(long, value_parser = humantime::parse_duration, default_value = "30days")]
+    pub cert_expiry_warning: Duration,
+
+    #[command(subcommand)]
+    pub cmd: Option<VersionCmd>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum VersionCmd {
+    /// Check whether CLI and cluster are outdated on the release channel,
+    /// with changes of newer versions
+    Check(VersionCheckOpt),
 }
 
 impl VersionOpt {
-    pub async fn process(self, target: ClusterTarget) -> Result<()> {
+    pub async fn process(mut self, target: ClusterTarget) -> Result<()> {
+        if let Some(VersionCmd::Check(check)) = self.cmd.take() {
+            return check.process(target).await;
+        }
+
         let mut version_printer = FluvioVersionPrinter::new("Fluvio CLI", crate::VERSION.trim());
 
         if let Ok(channel_name) = std::env::var(FLUVIO_RELEASE_CHANNEL) {
@@ -29,9 +58,24 @@ impl VersionOpt {
             version_printer.append_extra("Fluvio Channel Frontend SHA256", sha);
         }
 
-        let platform = self.format_platform_version(target).await;
+        let cluster_config = target.load().ok();
+        let platform = self.format_platform_version(cluster_config.as_ref()).await;
         version_printer.append_extra("Fluvio Platform", platform);
 
+        if let Some(expiry) = cluster_config
+            .as_ref()
+            .and_then(|config| self.format_cert_expiry(&config.tls))
+        {
+            version_printer.append_extra("Profile Client Cert Expiry", expiry);
+        }
+
+        #[cfg(feature = "k8s")]
+        if let Some(expiry) = fluvio_cluster::LocalConfig::load_local()
+            .and_then(|local| self.format_cert_expiry(local.server_tls_policy()))
+        {
+            version_printer.append_extra("Local SC/SPU Server Cert Expiry", expiry);
+        }
+
         version_printer.append_extra("Git Commit", env!("GIT_HASH"));
 
         if let Some(info) = os_info() {
@@ -76,12 +120,15 @@ impl VersionOpt {
         Some(format!("{:x}", &fluvio_cli_bin_sha256))
     }
 
-    async fn format_platform_version(&self, target: ClusterTarget) -> String {
+    async fn format_platform_version(
+        &self,
+        cluster_config: Option<&FluvioClusterConfig>,
+    ) -> String {
         // Attempt to connect to a Fluvio cluster to get platform version
         // Even if we fail to connect, we should not fail the other printouts
         let mut platform_version = String::from("Not available");
-        if let Ok(fluvio_config) = target.load()
-            && let Ok(fluvio) = Fluvio::connect_with_config(&fluvio_config).await
+        if let Some(fluvio_config) = cluster_config
+            && let Ok(fluvio) = Fluvio::connect_with_config(fluvio_config).await
         {
             let version = fluvio.platform_version();
             platform_version = version.to_string();
@@ -99,6 +146,30 @@ impl VersionOpt {
         format!("{platform_version}{profile_name}")
     }
 
+    // Expiry of certificate used by TLS policy, flagged if expired or expiring soon
+    fn format_cert_expiry(&self, policy: &TlsPolicy) -> Option<String> {
+        let TlsPolicy::Verified(config) = policy else {
+            return None;
+        };
+        let pem = match config {
+            TlsConfig::Files(paths) => std::fs::read(&paths.cert).ok(),
+            TlsConfig::Inline(certs) => Some(certs.cert.as_bytes().to_vec()),
+        };
+        let Some(cert) = pem.and_then(|pem| CertExpiry::earliest(&pem).ok()) else {
+            return Some("Not available".to_string());
+        };
+
+        let not_after = humantime::format_rfc3339_seconds(cert.not_after);
+        let expiry = match cert.remaining() {
+            None => format!("{not_after} (expired)"),
+            Some(_) if cert.expires_within(self.cert_expiry_warning) => {
+                format!("{not_after} (expires soon, renew it)")
+            }
+            Some(_) => not_after.to_string(),
+        };
+        Some(expiry)
+    }
+
     fn format_subcommand_metadata(&self) -> Option<Vec<(String, String)>> {
         let metadata = subcommand_metadata().ok()?;
         let mut formats = Vec::new();
@@ -114,3 +185,156 @@ impl VersionOpt {
         Some(formats)
     }
 }
+
+#[derive(Debug, Parser)]
+pub struct VersionCheckOpt {
+    /// Release channel to check, the active channel by default
+    #[arg(long)]
+    channel: Option<String>,
+
+    /// Don't print changes of newer versions
+    #[arg(long)]
+    no_changelog: bool,
+
+    /// Output in JSON format
+    #[arg(short, long)]
+    json: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct VersionCheck {
+    channel: String,
+    latest: String,
+    available: Vec<String>,
+    cli: ComponentVersion,
+    platform: Option<ComponentVersion>,
+    changelog: Vec<ReleaseNotes>,
+}
+
+#[derive(Debug, Serialize)]
+struct ComponentVersion {
+    version: String,
+    outdated: bool,
+}
+
+impl ComponentVersion {
+    fn new(version: &Version, latest: &Version) -> Self {
+        Self {
+            version: version.to_string(),
+            outdated: version < latest,
+        }
+    }
+
+    fn describe(&self, latest: &str) -> String {
+        if self.outdated {
+            format!("{} (outdated, latest is {latest})", self.version)
+        } else {
+            format!("{} (up to date)", self.version)
+        }
+    }
+}
+
+impl VersionCheckOpt {
+    pub async fn process(self, target: ClusterTarget) -> Result<()> {
+        let channel = self
+            .channel
+            .clone()
+            .or_else(|| std::env::var(FLUVIO_RELEASE_CHANNEL).ok())
+            .unwrap_or_else(|| STABLE_CHANNEL_NAME.to_owned());
+        let prerelease = channel == LATEST_CHANNEL_NAME;
+
+        let agent = HttpAgent::default();
+        let available = fetch_available_versions(&agent, prerelease).await?;
+        let installed = Version::parse(crate::VERSION.trim())?;
+        let latest = available
+            .last()
+            .cloned()
+            .unwrap_or_else(|| installed.clone());
+
+        let platform = match target.load() {
+            Ok(config) => Fluvio::connect_with_config(&config)
+                .await
+                .ok()
+                .map(|fluvio| fluvio.platform_version().clone()),
+            Err(_) => None,
+        };
+
+        // changes since the oldest installed component
+        let oldest = platform
+            .iter()
+            .chain([&installed])
+            .min()
+            .unwrap_or(&installed);
+        let changelog = if self.no_changelog || *oldest >= latest {
+            vec![]
+        } else {
+            fetch_release_notes(oldest, &latest)
+                .await
+                .inspect_err(|err| tracing::debug!(%err, "changelog not available"))
+                .unwrap_or_default()
+        };
+
+        let check = VersionCheck {
+            channel,
+            latest: latest.to_string(),
+            available: available
+                .iter()
+                .rev()
+                .take(AVAILABLE_VERSIONS_SHOWN)
+                .map(ToString::to_string)
+                .collect(),
+            cli: ComponentVersion::new(&installed, &latest),
+            platform: platform
+                .as_ref()
+                .map(|platform| ComponentVersion::new(platform, &latest)),
+            changelog,
+        };
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&check)?);
+        } else {
+            print_check(&check);
+        }
+        Ok(())
+    }
+}
+
+fn print_check(check: &VersionCheck) {
+    let width = 20;
+    println!("{:width$} : {}", "Release Channel", check.channel);
+    println!(
+        "{:width$} : {}",
+        "Available Versions",
+        check.available.join(", ")
+    );
+    println!(
+        "{:width$} : {}",
+        "Fluvio CLI",
+        check.cli.describe(&check.latest)
+    );
+    let platform = check
+        .platform
+        .as_ref()
+        .map_or_else(|| "Not available".to_owned(), |p| p.describe(&check.latest));
+    println!("{:width$} : {}", "Fluvio Platform", platform);
+
+    for notes in &check.changelog {
+        println!();
+        println!("=== What's new in {} ({}) ===", notes.version, notes.date);
+        for category in &notes.categories {
+            if category.entries.is_empty() {
+                continue;
+            }
+            println!("{}:", category.name);
+            for entry in &category.entries {
+                println!("  * {entry}");
+            }
+        }
+    }
+
+    if check.cli.outdated {
+        crate::install::update::prompt_available_update(
+            &Version::parse(&check.latest).expect("latest is a valid version"),
+        );
+    }
+}
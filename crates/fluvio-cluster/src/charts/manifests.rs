@@ -0,0 +1,100 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::Result;
+use tracing::{info, debug, instrument};
+use fluvio_command::CommandExt;
+
+use super::ChartInstallError;
+
+/// File names recognized by kustomize as the root of an overlay
+const KUSTOMIZATION_FILES: [&str; 3] = ["kustomization.yaml", "kustomization.yml", "Kustomization"];
+
+/// Installs Fluvio from pre-rendered Kubernetes manifests
+///
+/// This is an alternative to [`ChartInstaller`] for environments where
+/// helm is not allowed. The manifests are applied with `kubectl`, so the
+/// only runtime dependency is `kubectl` itself. If the directory contains
+/// a kustomization file, it is applied as a kustomize overlay (`kubectl apply -k`),
+/// otherwise every manifest in the directory is applied recursively.
+///
+/// [`ChartInstaller`]: super::ChartInstaller
+#[derive(Debug, Clone)]
+pub struct ManifestsInstaller {
+    namespace: String,
+    dir: PathBuf,
+}
+
+impl ManifestsInstaller {
+    /// Creates an installer for the manifests found in `dir`
+    pub fn new(namespace: impl Into<String>, dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        if !dir.is_dir() {
+            return Err(ChartInstallError::Other(format!(
+                "manifests directory not found: {}",
+                dir.display()
+            ))
+            .into());
+        }
+
+        Ok(Self {
+            namespace: namespace.into(),
+            dir,
+        })
+    }
+
+    /// Directory holding the manifests
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns true if the manifests directory is a kustomize overlay
+    pub fn is_kustomize(&self) -> bool {
+        KUSTOMIZATION_FILES
+            .iter()
+            .any(|name| self.dir.join(name).is_file())
+    }
+
+    /// Applies the manifests to the configured cluster.
+    ///
+    /// `kubectl apply` is idempotent, so the same call is used for install and upgrade.
+    #[instrument(skip(self))]
+    pub fn apply(&self) -> Result<()> {
+        let mut cmd = Command::new("kubectl");
+        cmd.arg("apply").args(["--namespace", &self.namespace]);
+
+        if self.is_kustomize() {
+            debug!(dir = %self.dir.display(), "applying kustomize overlay");
+            cmd.arg("-k").arg(&self.dir);
+        } else {
+            debug!(dir = %self.dir.display(), "applying raw manifests");
+            cmd.arg("--recursive").arg("-f").arg(&self.dir);
+        }
+
+        cmd.inherit().result()?;
+
+        info!(dir = %self.dir.display(), "manifests have been applied");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_detect_kustomize() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let installer = ManifestsInstaller::new("default", dir.path()).expect("installer");
+        assert!(!installer.is_kustomize());
+
+        std::fs::write(dir.path().join("kustomization.yaml"), "resources: []").expect("write");
+        assert!(installer.is_kustomize());
+    }
+
+    #[test]
+    fn test_missing_dir() {
+        assert!(ManifestsInstaller::new("default", "/does/not/exist").is_err());
+    }
+}
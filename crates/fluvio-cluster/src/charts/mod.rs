@@ -1,7 +1,9 @@
 mod chart;
 mod location;
+mod manifests;
 
 pub use chart::*;
+pub use manifests::*;
 pub use error::*;
 pub use location::*;
 
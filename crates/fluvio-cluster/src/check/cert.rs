@@ -0,0 +1,193 @@
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use tracing::debug;
+
+use fluvio::config::{ConfigFile, TlsConfig, TlsPolicy};
+use fluvio_extension_common::cert::CertExpiry;
+
+use crate::LocalConfig;
+use crate::render::ProgressRenderer;
+
+use super::{CheckResult, CheckStatus, ClusterCheck, UnrecoverableCheckStatus};
+
+/// name of certificate and its PEM content
+type NamedCert = (String, Result<Vec<u8>>);
+
+/// Check expiry of certificates used by current profile and by local cluster servers
+#[derive(Debug)]
+pub(crate) struct TlsCertExpiryCheck {
+    warning_window: Duration,
+}
+
+impl TlsCertExpiryCheck {
+    pub(crate) fn new(warning_window: Duration) -> Self {
+        Self { warning_window }
+    }
+
+    fn certificates(&self) -> Vec<NamedCert> {
+        let mut certs = vec![];
+        if let Ok(config) = ConfigFile::load_default_or_new()
+            && let Ok(cluster) = config.config().current_cluster()
+        {
+            certs.extend(policy_certs("profile client", &cluster.tls));
+        }
+
+        // server certs are only known for clusters started from this machine
+        if let Some(local) = LocalConfig::load_local() {
+            certs.extend(policy_certs(
+                "local SC/SPU server",
+                local.server_tls_policy(),
+            ));
+        }
+        certs
+    }
+
+    fn evaluate(&self, certs: Vec<NamedCert>) -> CheckStatus {
+        if certs.is_empty() {
+            return CheckStatus::pass("TLS is not enabled");
+        }
+
+        let count = certs.len();
+        let mut expiring = vec![];
+        let mut earliest: Option<SystemTime> = None;
+        for (name, pem) in certs {
+            let cert = match pem.and_then(|pem| CertExpiry::earliest(&pem)) {
+                Ok(cert) => cert,
+                Err(err) => {
+                    return CheckStatus::Unrecoverable(
+                        UnrecoverableCheckStatus::InvalidCertificate {
+                            name,
+                            reason: format!("{err:#}"),
+                        },
+                    );
+                }
+            };
+
+            let not_after = humantime::format_rfc3339_seconds(cert.not_after).to_string();
+            debug!(name, subject = %cert.subject, not_after, "certificate expiry");
+            if cert.remaining().is_none() {
+                return CheckStatus::Unrecoverable(UnrecoverableCheckStatus::CertificateExpired {
+                    name,
+                    subject: cert.subject,
+                    not_after,
+                });
+            }
+            if cert.expires_within(self.warning_window) {
+                expiring.push(format!("{name} cert expires at {not_after}"));
+            }
+            earliest = Some(earliest.map_or(cert.not_after, |time| time.min(cert.not_after)));
+        }
+
+        if !expiring.is_empty() {
+            return CheckStatus::warn(format!(
+                "{}. Renew before expiry, for local clusters use 'fluvio cluster renew-certs'",
+                expiring.join(", ")
+            ));
+        }
+
+        let earliest = earliest
+            .map(|time| humantime::format_rfc3339_seconds(time).to_string())
+            .unwrap_or_default();
+        CheckStatus::pass(format!("{count} TLS certificates valid until {earliest}"))
+    }
+}
+
+#[async_trait]
+impl ClusterCheck for TlsCertExpiryCheck {
+    async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+        Ok(self.evaluate(self.certificates()))
+    }
+
+    fn label(&self) -> &str {
+        "TLS Certificate Expiry"
+    }
+}
+
+/// certificate and CA certificate of verified TLS policy
+fn policy_certs(owner: &str, policy: &TlsPolicy) -> Vec<NamedCert> {
+    let TlsPolicy::Verified(config) = policy else {
+        return vec![];
+    };
+
+    match config {
+        TlsConfig::Files(paths) => vec![
+            (owner.to_string(), read_pem(&paths.cert)),
+            (format!("{owner} CA"), read_pem(&paths.ca_cert)),
+        ],
+        TlsConfig::Inline(certs) => vec![
+            (owner.to_string(), Ok(certs.cert.as_bytes().to_vec())),
+            (format!("{owner} CA"), Ok(certs.ca_cert.as_bytes().to_vec())),
+        ],
+    }
+}
+
+fn read_pem(path: &Path) -> Result<Vec<u8>> {
+    std::fs::read(path).with_context(|| format!("unable to read {}", path.display()))
+}
+
+#[cfg(test)]
+mod test {
+
+    use anyhow::anyhow;
+
+    use fluvio::config::TlsPaths;
+    use fluvio_extension_common::cert::generate_self_signed;
+
+    use super::*;
+
+    const DAY: Duration = Duration::from_secs(24 * 3600);
+
+    #[test]
+    fn test_cert_expiry_status() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let (ca_cert, _) = generate_self_signed(dir.path(), "ca", 30).expect("generate");
+        let certs = || {
+            vec![(
+                "profile client CA".to_string(),
+                std::fs::read(&ca_cert).map_err(Into::into),
+            )]
+        };
+
+        let check = TlsCertExpiryCheck::new(DAY);
+        assert!(matches!(check.evaluate(vec![]), CheckStatus::Pass(_)));
+        assert!(matches!(check.evaluate(certs()), CheckStatus::Pass(_)));
+
+        // CA cert expires within 30 days
+        let check = TlsCertExpiryCheck::new(60 * DAY);
+        assert!(matches!(check.evaluate(certs()), CheckStatus::Warn(_)));
+
+        let status = check.evaluate(vec![(
+            "profile client".to_string(),
+            Err(anyhow!("missing")),
+        )]);
+        assert!(matches!(
+            status,
+            CheckStatus::Unrecoverable(UnrecoverableCheckStatus::InvalidCertificate { .. })
+        ));
+    }
+
+    #[test]
+    fn test_policy_certs() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let (ca_cert, _) = generate_self_signed(dir.path(), "ca", 30).expect("generate");
+
+        assert!(policy_certs("profile client", &TlsPolicy::Disabled).is_empty());
+        assert!(policy_certs("profile client", &TlsPolicy::Anonymous).is_empty());
+
+        let policy = TlsPolicy::from(TlsPaths {
+            domain: "fluvio.local".to_string(),
+            key: "missing.key".into(),
+            cert: "missing.crt".into(),
+            ca_cert,
+        });
+        let certs = policy_certs("profile client", &policy);
+        assert_eq!(certs.len(), 2);
+        assert_eq!(certs[0].0, "profile client");
+        assert!(certs[0].1.is_err());
+        assert_eq!(certs[1].0, "profile client CA");
+        assert!(certs[1].1.is_ok());
+    }
+}
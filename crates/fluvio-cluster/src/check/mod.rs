@@ -6,6 +6,7 @@ use std::process::Command;
 use std::time::Duration;
 
 pub mod render;
+mod cert;
 
 use anyhow::Result;
 use colored::Colorize;
@@ -28,6 +29,8 @@ use crate::render::ProgressRenderer;
 use crate::charts::{ChartConfig, ChartInstaller, ChartInstallError, SYS_CHART_NAME};
 use crate::LocalConfig;
 
+use self::cert::TlsCertExpiryCheck;
+
 const KUBE_VERSION: &str = "1.7.0";
 const RESOURCE_SERVICE: &str = "service";
 const RESOURCE_CRD: &str = "customresourcedefinitions";
@@ -131,6 +134,8 @@ pub type CheckStatuses = Vec<CheckStatus>;
 pub enum CheckStatus {
     /// This check has passed and has the given success message
     Pass(CheckSucceeded),
+    /// This check has passed but found something that needs attention
+    Warn(CheckWarning),
     /// This check has failed but can be recovered
     AutoFixableError {
         message: String,
@@ -145,11 +150,19 @@ impl CheckStatus {
     pub(crate) fn pass(msg: impl Into<String>) -> Self {
         Self::Pass(msg.into())
     }
+
+    /// Creates a passing check status with a warning message
+    pub(crate) fn warn(msg: impl Into<String>) -> Self {
+        Self::Warn(msg.into())
+    }
 }
 
 /// A successful check yields a success message
 pub type CheckSucceeded = String;
 
+/// A check that passed with a warning yields a warning message
+pub type CheckWarning = String;
+
 /// A type of check failure which may be automatically recovered from
 #[derive(thiserror::Error, Debug)]
 pub enum RecoverableCheck {
@@ -252,6 +265,28 @@ pub enum UnrecoverableCheckStatus {
     #[error("Helm client error")]
     HelmClientError,
 
+    /// A TLS certificate has expired
+    #[error(
+        "{name} {subject} expired at {not_after}. Renew it, for local clusters use 'fluvio cluster renew-certs'"
+    )]
+    CertificateExpired {
+        /// Which certificate expired
+        name: String,
+        /// Subject of the certificate
+        subject: String,
+        /// Expiry time
+        not_after: String,
+    },
+
+    /// A TLS certificate could not be read
+    #[error("Invalid {name}: {reason}")]
+    InvalidCertificate {
+        /// Which certificate is invalid
+        name: String,
+        /// Why it could not be read
+        reason: String,
+    },
+
     /// Other misc
     #[error("Other failure: {0}")]
     Other(String),
@@ -845,6 +880,12 @@ impl ClusterChecker {
         self.with_check(LocalClusterVersionCheck(version))
     }
 
+    /// Adds check for expiry of profile and local cluster TLS certificates,
+    /// which warns about certificates expiring within `warning_window`
+    pub fn with_cert_expiry_check(self, warning_window: Duration) -> Self {
+        self.with_check(TlsCertExpiryCheck::new(warning_window))
+    }
+
     /// Adds all checks required for starting a cluster on minikube.
     ///
     /// Note that no checks are run until the [`run`] method is invoked.
@@ -860,6 +901,21 @@ impl ClusterChecker {
         self
     }
 
+    /// Adds all checks required for installing from raw manifests.
+    ///
+    /// Unlike [`with_k8_checks`], this does not require helm to be installed.
+    ///
+    /// [`with_k8_checks`]: ClusterChecker::with_k8_checks
+    pub fn with_manifests_checks(mut self) -> Self {
+        let checks: Vec<Box<(dyn ClusterCheck)>> = vec![
+            Box::new(ActiveKubernetesCluster),
+            Box::new(K8Version),
+            Box::new(CreateCrdPermission),
+        ];
+        self.checks.extend(checks);
+        self
+    }
+
     /// Adds all checks required for starting a local cluster.
     ///
     /// Note that no checks are run until the [`run`] method is invoked.
@@ -947,6 +1003,10 @@ impl ClusterChecker {
                         passed = true;
                         pb.println(pad_format!(format!("{} {}", "✅".bold(), status)));
                     }
+                    CheckStatus::Warn(warning) => {
+                        passed = true;
+                        pb.println(pad_format!(format!("{} {}", "⚠️".bold(), warning.yellow())));
+                    }
                     CheckStatus::Unrecoverable(err) => {
                         debug!("failed: {}", err);
 
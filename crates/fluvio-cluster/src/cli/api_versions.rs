@@ -0,0 +1,374 @@
+//!
+//! # API Versions CLI
+//!
+//! Report API key version ranges supported by SC and each SPU, to spot
+//! mixed-version clusters before using new features.
+//!
+
+use std::sync::Arc;
+
+use clap::Parser;
+use anyhow::Result;
+use serde::Serialize;
+use tracing::debug;
+
+use fluvio::Fluvio;
+use fluvio::dataplane::link::versions::ApiVersionKey;
+use fluvio::metadata::spu::SpuSpec;
+use fluvio_sc_schema::AdminPublicApiKey;
+use fluvio_spu_schema::server::SpuServerApiKey;
+
+use crate::cli::common::output::Terminal;
+use crate::cli::common::OutputFormat;
+
+#[derive(Debug, Parser)]
+pub struct ApiVersionsOpt {
+    #[clap(flatten)]
+    output: OutputFormat,
+}
+
+impl ApiVersionsOpt {
+    pub async fn process<O: Terminal>(self, out: Arc<O>, fluvio: &Fluvio) -> Result<()> {
+        let sc_versions = fluvio.versions();
+        let mut nodes = vec![ApiNode {
+            name: "sc".to_owned(),
+            kind: NodeKind::Sc,
+            platform_version: Some(sc_versions.platform_version().to_string()),
+            api_versions: sc_versions.api_versions().clone(),
+            error: None,
+        }];
+
+        let mut spus = fluvio.admin().await.all::<SpuSpec>().await?;
+        spus.sort_by_key(|spu| spu.spec.id);
+        for spu in spus {
+            let name = format!("spu-{}", spu.spec.id);
+            let node = match fluvio.spu_versions(spu.spec.id).await {
+                Ok(versions) => ApiNode {
+                    name,
+                    kind: NodeKind::Spu,
+                    platform_version: Some(versions.platform_version().to_string()),
+                    api_versions: versions.api_versions().clone(),
+                    error: None,
+                },
+                Err(err) => {
+                    debug!(spu = spu.spec.id, %err, "failed to query api versions");
+                    ApiNode {
+                        name,
+                        kind: NodeKind::Spu,
+                        platform_version: None,
+                        api_versions: vec![],
+                        error: Some(err.to_string()),
+                    }
+                }
+            };
+            nodes.push(node);
+        }
+
+        let matrix = ApiVersionsMatrix::new(nodes);
+        out.render_list(&matrix, self.output.format)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum NodeKind {
+    Sc,
+    Spu,
+}
+
+/// API versions reported by a single SC or SPU
+#[derive(Debug)]
+struct ApiNode {
+    name: String,
+    kind: NodeKind,
+    platform_version: Option<String>,
+    api_versions: Vec<ApiVersionKey>,
+    /// set if node couldn't be queried
+    error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+struct ApiRange {
+    min: i16,
+    max: i16,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct NodeInfo {
+    name: String,
+    kind: NodeKind,
+    platform_version: Option<String>,
+    error: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct ApiRow {
+    kind: NodeKind,
+    api: String,
+    api_key: i16,
+    /// range of each node in order of nodes, None if node doesn't support api or is of other kind
+    versions: Vec<Option<ApiRange>>,
+    /// reachable nodes of same kind disagree on range
+    mixed: bool,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct ApiVersionsMatrix {
+    nodes: Vec<NodeInfo>,
+    apis: Vec<ApiRow>,
+    /// reachable nodes report different platform versions
+    mixed_platform: bool,
+}
+
+impl ApiVersionsMatrix {
+    fn new(nodes: Vec<ApiNode>) -> Self {
+        let mut keys: Vec<(NodeKind, i16)> = nodes
+            .iter()
+            .flat_map(|node| {
+                node.api_versions
+                    .iter()
+                    .map(|version| (node.kind, version.api_key))
+            })
+            .collect();
+        keys.sort_unstable();
+        keys.dedup();
+
+        let apis = keys
+            .into_iter()
+            .map(|(kind, api_key)| {
+                let versions: Vec<Option<ApiRange>> = nodes
+                    .iter()
+                    .map(|node| {
+                        node.api_versions
+                            .iter()
+                            .find(|version| node.kind == kind && version.api_key == api_key)
+                            .map(|version| ApiRange {
+                                min: version.min_version,
+                                max: version.max_version,
+                            })
+                    })
+                    .collect();
+                let mut ranges = nodes
+                    .iter()
+                    .zip(versions.iter())
+                    .filter(|(node, _)| node.kind == kind && node.error.is_none())
+                    .map(|(_, range)| range);
+                let mixed = match ranges.next() {
+                    Some(first) => ranges.any(|range| range != first),
+                    None => false,
+                };
+                ApiRow {
+                    kind,
+                    api: api_name(kind, api_key),
+                    api_key,
+                    versions,
+                    mixed,
+                }
+            })
+            .collect();
+
+        let mut platform_versions = nodes
+            .iter()
+            .filter_map(|node| node.platform_version.as_ref());
+        let mixed_platform = match platform_versions.next() {
+            Some(first) => platform_versions.any(|version| version != first),
+            None => false,
+        };
+
+        Self {
+            nodes: nodes
+                .into_iter()
+                .map(|node| NodeInfo {
+                    name: node.name,
+                    kind: node.kind,
+                    platform_version: node.platform_version,
+                    error: node.error,
+                })
+                .collect(),
+            apis,
+            mixed_platform,
+        }
+    }
+}
+
+fn api_name(kind: NodeKind, api_key: i16) -> String {
+    let name = match kind {
+        NodeKind::Sc => AdminPublicApiKey::try_from(api_key as u16).map(|key| format!("{key:?}")),
+        NodeKind::Spu => SpuServerApiKey::try_from(api_key as u16).map(|key| format!("{key:?}")),
+    };
+    name.unwrap_or_else(|_| "Unknown".to_owned())
+}
+
+mod display {
+
+    use comfy_table::Row;
+
+    use crate::cli::common::output::TableOutputHandler;
+
+    use super::{ApiVersionsMatrix, NodeKind};
+
+    impl TableOutputHandler for ApiVersionsMatrix {
+        fn header(&self) -> Row {
+            let mut header = vec!["API".to_owned(), "KEY".to_owned()];
+            header.extend(self.nodes.iter().map(|node| node.name.to_uppercase()));
+            header.push("MIXED".to_owned());
+            Row::from(header)
+        }
+
+        fn errors(&self) -> Vec<String> {
+            self.nodes
+                .iter()
+                .filter_map(|node| {
+                    node.error
+                        .as_ref()
+                        .map(|err| format!("{} unreachable: {err}", node.name))
+                })
+                .collect()
+        }
+
+        fn content(&self) -> Vec<Row> {
+            let mut platform = vec!["Platform Version".to_owned(), String::new()];
+            platform.extend(
+                self.nodes
+                    .iter()
+                    .map(|node| node.platform_version.clone().unwrap_or_default()),
+            );
+            platform.push(mixed_label(self.mixed_platform));
+
+            let mut rows = vec![Row::from(platform)];
+            rows.extend(self.apis.iter().map(|api| {
+                let label = match api.kind {
+                    NodeKind::Sc => format!("SC {}", api.api),
+                    NodeKind::Spu => format!("SPU {}", api.api),
+                };
+                let mut row = vec![label, api.api_key.to_string()];
+                row.extend(
+                    self.nodes
+                        .iter()
+                        .zip(api.versions.iter())
+                        .map(|(node, range)| match range {
+                            _ if node.kind != api.kind || node.error.is_some() => String::new(),
+                            Some(range) => format!("{}-{}", range.min, range.max),
+                            None => "-".to_owned(),
+                        }),
+                );
+                row.push(mixed_label(api.mixed));
+                Row::from(row)
+            }));
+            rows
+        }
+    }
+
+    fn mixed_label(mixed: bool) -> String {
+        if mixed { "yes" } else { "" }.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    fn key(api_key: i16, min_version: i16, max_version: i16) -> ApiVersionKey {
+        ApiVersionKey {
+            api_key,
+            min_version,
+            max_version,
+        }
+    }
+
+    fn node(
+        name: &str,
+        kind: NodeKind,
+        version: &str,
+        api_versions: Vec<ApiVersionKey>,
+    ) -> ApiNode {
+        ApiNode {
+            name: name.to_owned(),
+            kind,
+            platform_version: Some(version.to_owned()),
+            api_versions,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_api_versions_matrix() {
+        let produce = SpuServerApiKey::Produce as i16;
+        let stats = SpuServerApiKey::FetchPartitionStats as i16;
+        let create = AdminPublicApiKey::Create as i16;
+        let matrix = ApiVersionsMatrix::new(vec![
+            node("sc", NodeKind::Sc, "0.14.0", vec![key(create, 0, 25)]),
+            node(
+                "spu-5001",
+                NodeKind::Spu,
+                "0.14.0",
+                vec![key(produce, 0, 29), key(stats, 0, 29)],
+            ),
+            node(
+                "spu-5002",
+                NodeKind::Spu,
+                "0.13.0",
+                vec![key(produce, 0, 25)],
+            ),
+            ApiNode {
+                name: "spu-5003".to_owned(),
+                kind: NodeKind::Spu,
+                platform_version: None,
+                api_versions: vec![],
+                error: Some("connection refused".to_owned()),
+            },
+        ]);
+
+        assert!(matrix.mixed_platform);
+        assert_eq!(matrix.apis.len(), 3);
+
+        let sc_create = &matrix.apis[0];
+        assert_eq!(sc_create.kind, NodeKind::Sc);
+        assert_eq!(sc_create.api, "Create");
+        assert_eq!(sc_create.versions[0], Some(ApiRange { min: 0, max: 25 }));
+        assert_eq!(sc_create.versions[1], None);
+        assert!(!sc_create.mixed);
+
+        let spu_produce = &matrix.apis[1];
+        assert_eq!(spu_produce.api, "Produce");
+        assert_eq!(
+            spu_produce.versions,
+            vec![
+                None,
+                Some(ApiRange { min: 0, max: 29 }),
+                Some(ApiRange { min: 0, max: 25 }),
+                None
+            ]
+        );
+        assert!(spu_produce.mixed);
+
+        // missing on older spu
+        let spu_stats = &matrix.apis[2];
+        assert_eq!(spu_stats.api, "FetchPartitionStats");
+        assert_eq!(spu_stats.versions[2], None);
+        assert!(spu_stats.mixed);
+    }
+
+    #[test]
+    fn test_api_versions_matrix_uniform() {
+        let produce = SpuServerApiKey::Produce as i16;
+        let matrix = ApiVersionsMatrix::new(vec![
+            node(
+                "spu-5001",
+                NodeKind::Spu,
+                "0.14.0",
+                vec![key(produce, 0, 29)],
+            ),
+            node(
+                "spu-5002",
+                NodeKind::Spu,
+                "0.14.0",
+                vec![key(produce, 0, 29)],
+            ),
+        ]);
+        assert!(!matrix.mixed_platform);
+        assert!(!matrix.apis[0].mixed);
+    }
+}
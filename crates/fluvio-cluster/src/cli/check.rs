@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use anyhow::bail;
 use anyhow::Result;
 use fluvio_extension_common::installation::InstallationType;
@@ -15,6 +17,10 @@ pub struct CheckOpt {
     /// Attempt to fix recoverable errors
     #[arg(long)]
     fix: bool,
+
+    /// Warn about TLS certificates expiring within this period. Ex: '30days', '12h'
+    #[arg(long, value_parser = humantime::parse_duration, default_value = "30days")]
+    cert_expiry_warning: Duration,
 }
 
 impl CheckOpt {
@@ -54,6 +60,7 @@ impl CheckOpt {
             _other => ClusterChecker::empty(),
         };
 
+        let checker = checker.with_cert_expiry_check(self.cert_expiry_warning);
         let pb = ProgressBarFactory::new(false);
 
         checker.run(&pb, self.fix).await?;
@@ -0,0 +1,185 @@
+//!
+//! # Cluster config CLI
+//!
+//! Show or change defaults applied by SC to topics created afterwards.
+//!
+
+use std::time::Duration;
+
+use anyhow::Result;
+use clap::Parser;
+use humantime::parse_duration;
+
+use fluvio::{Fluvio, FluvioAdmin};
+use fluvio_sc_schema::clusterconfig::{
+    ClusterConfigSpec, CLUSTER_CONFIG_NAME, DEFAULT_TOPIC_REPLICATION,
+};
+use fluvio_sc_schema::topic::CompressionAlgorithm;
+use fluvio_types::ReplicationFactor;
+use fluvio_types::defaults::STORAGE_RETENTION_SECONDS;
+
+#[derive(Debug, Parser)]
+pub enum ClusterConfigCmd {
+    /// Show cluster config
+    #[command(name = "get")]
+    Get,
+
+    /// Change cluster config, options not given are kept
+    #[command(name = "set")]
+    Set(SetClusterConfigOpt),
+}
+
+impl ClusterConfigCmd {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+        let current = current_config(&admin).await?;
+
+        let config = match self {
+            Self::Get => current,
+            Self::Set(opt) => {
+                let config = opt.apply(current);
+                admin
+                    .create(CLUSTER_CONFIG_NAME.to_owned(), false, config.clone())
+                    .await?;
+                config
+            }
+        };
+        print_config(&config);
+        Ok(())
+    }
+}
+
+/// cluster config of SC, default if it was never set
+pub(crate) async fn current_config(admin: &FluvioAdmin) -> Result<ClusterConfigSpec> {
+    Ok(admin
+        .list::<ClusterConfigSpec, String>(vec![CLUSTER_CONFIG_NAME.to_owned()])
+        .await?
+        .into_iter()
+        .next()
+        .map(|config| config.spec)
+        .unwrap_or_default())
+}
+
+#[derive(Debug, Parser)]
+pub struct SetClusterConfigOpt {
+    /// Retention time of topics created without retention time
+    /// Ex: '1h', '2d 10s', '7 days'
+    #[arg(long, value_name = "time", value_parser = parse_duration)]
+    default_retention_time: Option<Duration>,
+
+    /// Replication factor of topics created without replication factor
+    #[arg(long, value_name = "integer")]
+    default_replication: Option<ReplicationFactor>,
+
+    /// Compression of topics created without compression type
+    #[arg(long, value_name = "compression")]
+    default_compression: Option<CompressionAlgorithm>,
+
+    /// Create topic with single partition when producing to unknown topic
+    #[arg(long, value_name = "bool")]
+    auto_create_topics: Option<bool>,
+
+    /// Start from built-in defaults instead of current config
+    #[arg(long)]
+    reset: bool,
+}
+
+impl SetClusterConfigOpt {
+    fn apply(self, current: ClusterConfigSpec) -> ClusterConfigSpec {
+        let mut config = if self.reset {
+            // maintenance is not a default, it's kept
+            ClusterConfigSpec {
+                maintenance: current.maintenance,
+                ..Default::default()
+            }
+        } else {
+            current
+        };
+        if let Some(retention) = self.default_retention_time {
+            config.default_retention_secs = Some(retention.as_secs() as u32);
+        }
+        if let Some(replication) = self.default_replication {
+            config.default_replication = Some(replication);
+        }
+        if let Some(compression) = self.default_compression {
+            config.default_compression = Some(compression);
+        }
+        if let Some(auto_create_topics) = self.auto_create_topics {
+            config.auto_create_topics = auto_create_topics;
+        }
+        config
+    }
+}
+
+pub(crate) fn print_config(config: &ClusterConfigSpec) {
+    let retention = Duration::from_secs(
+        config
+            .default_retention_secs
+            .unwrap_or(STORAGE_RETENTION_SECONDS) as u64,
+    );
+    println!(
+        "default retention:   {}",
+        humantime::format_duration(retention)
+    );
+    println!(
+        "default replication: {}",
+        config
+            .default_replication
+            .unwrap_or(DEFAULT_TOPIC_REPLICATION)
+    );
+    println!(
+        "default compression: {}",
+        config
+            .default_compression
+            .as_ref()
+            .unwrap_or(&CompressionAlgorithm::Any)
+    );
+    println!("auto create topics:  {}", config.auto_create_topics);
+    let maintenance = match &config.maintenance {
+        None => "off",
+        Some(maintenance) if maintenance.reject_produce => "on, produce is rejected",
+        Some(_) => "on",
+    };
+    println!("maintenance:         {maintenance}");
+}
+
+#[cfg(test)]
+mod test {
+
+    use clap::Parser;
+
+    use super::*;
+
+    #[test]
+    fn test_set_keeps_current_config() {
+        let current = ClusterConfigSpec {
+            default_replication: Some(3),
+            auto_create_topics: true,
+            ..Default::default()
+        };
+
+        let opt = SetClusterConfigOpt::parse_from([
+            "set",
+            "--default-retention-time",
+            "1h",
+            "--default-compression",
+            "zstd",
+        ]);
+        let config = opt.apply(current.clone());
+        assert_eq!(config.default_retention_secs, Some(3600));
+        assert_eq!(config.default_replication, Some(3));
+        assert_eq!(config.default_compression, Some(CompressionAlgorithm::Zstd));
+        assert!(config.auto_create_topics);
+
+        let opt =
+            SetClusterConfigOpt::parse_from(["set", "--reset", "--auto-create-topics", "true"]);
+        let config = opt.apply(current);
+        assert_eq!(
+            config,
+            ClusterConfigSpec {
+                auto_create_topics: true,
+                ..Default::default()
+            }
+        );
+    }
+}
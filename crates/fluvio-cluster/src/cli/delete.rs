@@ -5,7 +5,7 @@ use dialoguer::Input;
 use tracing::debug;
 use dialoguer::theme::ColorfulTheme;
 
-use crate::{InstallationType, cli::get_installation_type};
+use crate::{InstallationType, DockerConfig, DockerInstaller, cli::get_installation_type};
 use crate::delete::ClusterUninstallConfig;
 use crate::cli::{ClusterCliError, ConfigFile};
 
@@ -89,6 +89,17 @@ impl DeleteOpt {
                     builder.uninstall_k8(false);
                     builder.uninstall_sys(false);
                 }
+                InstallationType::Docker => {
+                    let platform_version = semver::Version::parse(crate::cli::VERSION.trim())?;
+                    let cluster = config.config().current_cluster()?;
+                    let installer = DockerInstaller::from_config(
+                        DockerConfig::builder(platform_version)
+                            .with_cluster_config(cluster)
+                            .build()?,
+                    );
+                    installer.uninstall()?;
+                    return Ok(());
+                }
                 InstallationType::Cloud => {
                     let profile = config.config().current_profile_name().unwrap_or("none");
                     bail!(
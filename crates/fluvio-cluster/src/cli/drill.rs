@@ -0,0 +1,527 @@
+//!
+//! # Failure drills
+//!
+//! Deliberately take down an SPU and measure how the cluster recovers: time until partitions
+//! it led have new leaders online, and produce errors observed by a canary producer running
+//! through the drill. Records acknowledged to the canary are read back afterwards, none must be lost.
+//!
+
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, anyhow, bail};
+use clap::Parser;
+use futures_util::StreamExt;
+use tracing::debug;
+
+use fluvio::{Fluvio, Offset, RecordKey};
+use fluvio::consumer::ConsumerConfigExt;
+use fluvio_command::CommandExt;
+use fluvio_controlplane_metadata::partition::{PartitionResolution, PartitionSpec};
+use fluvio_controlplane_metadata::spg::SpuGroupSpec;
+use fluvio_controlplane_metadata::spu::SpuSpec;
+use fluvio_controlplane_metadata::topic::TopicSpec;
+use fluvio_future::timer::sleep;
+use fluvio_types::{PartitionId, SpuId};
+
+use crate::cli::get_installation_type;
+use crate::runtime::spu::SpuClusterManager;
+use crate::{DEFAULT_NAMESPACE, InstallationType, LocalConfig};
+
+/// period of metadata polls while waiting for failover
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// period of canary records
+const CANARY_INTERVAL: Duration = Duration::from_millis(100);
+
+/// canary runs this long before SPU is killed and after failover
+const CANARY_SETTLE: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Parser)]
+pub enum DrillCmd {
+    /// Kill an SPU, then report time to failover and produce errors seen by a canary
+    ///
+    /// SPUs of local clusters are signaled and restarted after failover.
+    /// SPU pods on Kubernetes are deleted and recreated by their StatefulSet.
+    #[command(name = "kill-spu")]
+    KillSpu(KillSpuOpt),
+}
+
+impl DrillCmd {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        match self {
+            Self::KillSpu(opt) => opt.process(fluvio).await,
+        }
+    }
+}
+
+#[derive(Debug, Parser)]
+pub struct KillSpuOpt {
+    /// Id of SPU to kill
+    #[arg(value_name = "SPU_ID")]
+    spu: SpuId,
+
+    /// Topic the canary produces to. By default a temporary topic replicated
+    /// across SPUs is created and deleted after the drill
+    #[arg(long, value_name = "TOPIC")]
+    topic: Option<String>,
+
+    /// Stop SPU with SIGTERM, or delete its pod with grace period, instead of killing it
+    #[arg(long)]
+    graceful: bool,
+
+    /// Leave SPU of local cluster down after the drill
+    #[arg(long)]
+    no_restart: bool,
+
+    /// Give up waiting for failover and SPU restart after this long
+    #[arg(long, value_name = "DURATION", default_value = "60s")]
+    failover_timeout: humantime::Duration,
+
+    /// Kubernetes namespace of cluster
+    #[arg(short, long, default_value = DEFAULT_NAMESPACE)]
+    namespace: String,
+}
+
+/// record sent by canary, with its outcome
+#[derive(Debug, Clone)]
+struct CanaryAttempt {
+    sent: Instant,
+    result: Result<(PartitionId, i64), String>,
+}
+
+/// produce errors seen by canary
+#[derive(Debug, Default, PartialEq, Eq)]
+struct CanarySummary {
+    sent: usize,
+    failed: usize,
+    first_error: Option<String>,
+    /// longest time from a failed record to next acknowledged one
+    longest_outage: Duration,
+}
+
+impl CanarySummary {
+    fn new(attempts: &[CanaryAttempt]) -> Self {
+        let mut summary = Self {
+            sent: attempts.len(),
+            ..Default::default()
+        };
+        let mut outage_start: Option<Instant> = None;
+        for attempt in attempts {
+            match &attempt.result {
+                Ok(_) => {
+                    if let Some(start) = outage_start.take() {
+                        summary.longest_outage = summary
+                            .longest_outage
+                            .max(attempt.sent.duration_since(start));
+                    }
+                }
+                Err(err) => {
+                    summary.failed += 1;
+                    summary.first_error.get_or_insert_with(|| err.clone());
+                    outage_start.get_or_insert(attempt.sent);
+                }
+            }
+        }
+        if let Some(start) = outage_start
+            && let Some(last) = attempts.last()
+        {
+            summary.longest_outage = summary.longest_outage.max(last.sent.duration_since(start));
+        }
+        summary
+    }
+}
+
+impl KillSpuOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let (installation_type, _) = get_installation_type()?;
+        debug!(?installation_type);
+        let local = match installation_type {
+            InstallationType::Local | InstallationType::LocalK8 | InstallationType::ReadOnly => {
+                Some(
+                    LocalConfig::load_local()
+                        .context("Configuration file for local cluster not found")?,
+                )
+            }
+            InstallationType::K8 => None,
+            other => bail!("SPU drills are not supported on {other:?} clusters"),
+        };
+
+        let admin = fluvio.admin().await;
+        let spus = admin.all::<SpuSpec>().await?;
+        let online = spus
+            .iter()
+            .filter(|spu| spu.status.is_online())
+            .map(|spu| spu.spec.id)
+            .collect::<Vec<_>>();
+        if !online.contains(&self.spu) {
+            bail!("SPU {} is not online", self.spu);
+        }
+        if online.len() < 2 {
+            bail!("failover requires at least 2 online SPUs");
+        }
+
+        let (topic, temporary) = match &self.topic {
+            Some(topic) => (topic.clone(), false),
+            None => {
+                let topic = format!("fluvio-drill-{:08x}", rand::random::<u32>());
+                let replication = online.len().min(3) as u32;
+                admin
+                    .create(
+                        topic.clone(),
+                        false,
+                        TopicSpec::new_computed(online.len() as u32, replication, None),
+                    )
+                    .await?;
+                println!("Created canary topic {topic} with replication {replication}");
+                (topic, true)
+            }
+        };
+
+        let result = self.drill(fluvio, local.as_ref(), &topic).await;
+        if temporary && let Err(err) = admin.delete::<TopicSpec>(topic.clone()).await {
+            println!("Unable to delete canary topic {topic}: {err}");
+        }
+        result
+    }
+
+    async fn drill(&self, fluvio: &Fluvio, local: Option<&LocalConfig>, topic: &str) -> Result<()> {
+        self.wait_for(|| async move { Ok(self.led_partitions(fluvio, Some(topic)).await?.1 == 0) })
+            .await
+            .context("canary topic partitions are not online")?;
+
+        let run_id = format!("{:08x}", rand::random::<u32>());
+        let stop = AtomicBool::new(false);
+        let canary = canary(fluvio, topic, &run_id, &stop);
+
+        let drill = async {
+            sleep(CANARY_SETTLE).await;
+            let result = self.kill_and_failover(fluvio, local).await;
+            if result.is_ok() {
+                sleep(CANARY_SETTLE).await;
+            }
+            // canary must stop even if SPU couldn't be killed
+            stop.store(true, Ordering::Relaxed);
+            result
+        };
+
+        let (attempts, drill) = futures_util::future::join(canary, drill).await;
+        let (led, failover) = drill?;
+
+        match failover {
+            Ok(elapsed) if led > 0 => println!(
+                "✅ Failover of {led} partitions took {}",
+                humantime::format_duration(round_millis(elapsed))
+            ),
+            Ok(_) => println!("SPU {} was not leading replicated partitions", self.spu),
+            Err(err) => println!("❌ Failover didn't complete: {err}"),
+        }
+
+        let summary = CanarySummary::new(&attempts);
+        println!(
+            "Canary sent {} records, {} failed",
+            summary.sent, summary.failed
+        );
+        if let Some(err) = &summary.first_error {
+            println!(
+                "  first error: {err}\n  longest produce outage: {}",
+                humantime::format_duration(round_millis(summary.longest_outage))
+            );
+        }
+
+        let lost = lost_records(fluvio, topic, &run_id, &attempts).await?;
+        if lost.is_empty() {
+            println!("✅ All acknowledged canary records were read back");
+        } else {
+            println!("❌ {} acknowledged canary records were lost", lost.len());
+        }
+
+        self.restart(fluvio, local).await
+    }
+
+    /// kill SPU and wait for partitions it led to fail over.
+    /// returns number of partitions led, and time to failover
+    async fn kill_and_failover(
+        &self,
+        fluvio: &Fluvio,
+        local: Option<&LocalConfig>,
+    ) -> Result<(usize, Result<Duration>)> {
+        let (led, _) = self.led_partitions(fluvio, None).await?;
+        println!(
+            "Killing SPU {}, leader of {} replicated partitions",
+            self.spu,
+            led.len()
+        );
+        let killed = Instant::now();
+        self.kill(fluvio, local).await?;
+
+        let failover = self
+            .wait_for(|| async move {
+                let (still_led, offline) = self.led_partitions(fluvio, None).await?;
+                Ok(still_led.is_empty() && offline == 0)
+            })
+            .await
+            .map(|()| killed.elapsed());
+        Ok((led.len(), failover))
+    }
+
+    /// partitions led by drilled SPU with replicas to fail over to, and number of offline partitions
+    async fn led_partitions(
+        &self,
+        fluvio: &Fluvio,
+        topic: Option<&str>,
+    ) -> Result<(Vec<String>, usize)> {
+        let partitions = fluvio.admin().await.all::<PartitionSpec>().await?;
+        let partitions = partitions.into_iter().filter(|partition| {
+            topic.is_none_or(|topic| {
+                partition
+                    .name
+                    .rsplit_once('-')
+                    .is_some_and(|(name, _)| name == topic)
+            })
+        });
+        let mut led = vec![];
+        let mut offline = 0;
+        for partition in partitions.filter(|partition| partition.spec.replicas.len() > 1) {
+            if partition.spec.leader == self.spu {
+                led.push(partition.name.clone());
+            }
+            if partition.status.resolution != PartitionResolution::Online {
+                offline += 1;
+            }
+        }
+        Ok((led, offline))
+    }
+
+    async fn kill(&self, fluvio: &Fluvio, local: Option<&LocalConfig>) -> Result<()> {
+        match local {
+            Some(local) => {
+                let signal = if self.graceful { "TERM" } else { "KILL" };
+                local.as_spu_cluster_manager().signal_spu(self.spu, signal)
+            }
+            None => {
+                let pod = self.pod_name(fluvio).await?;
+                let mut cmd = self.kubectl();
+                cmd.args(["delete", "pod", pod.as_str(), "--wait=false"]);
+                if !self.graceful {
+                    cmd.args(["--grace-period=0", "--force"]);
+                }
+                cmd.result()
+                    .with_context(|| format!("unable to delete SPU pod {pod}"))?;
+                Ok(())
+            }
+        }
+    }
+
+    /// start SPU of local cluster again, and wait for SPU to be online
+    async fn restart(&self, fluvio: &Fluvio, local: Option<&LocalConfig>) -> Result<()> {
+        let started = Instant::now();
+        if let Some(local) = local {
+            if self.no_restart {
+                println!("SPU {} left down", self.spu);
+                return Ok(());
+            }
+            let id = u16::try_from(self.spu).context("SPU id out of range of local SPUs")?;
+            local
+                .as_spu_cluster_manager()
+                .create_spu_absolute(id)?
+                .start()?;
+        }
+
+        self.wait_for(|| async move {
+            Ok(fluvio
+                .admin()
+                .await
+                .all::<SpuSpec>()
+                .await?
+                .iter()
+                .any(|spu| spu.spec.id == self.spu && spu.status.is_online()))
+        })
+        .await
+        .with_context(|| format!("SPU {} didn't come back online", self.spu))?;
+        println!(
+            "✅ SPU {} back online after {}",
+            self.spu,
+            humantime::format_duration(round_millis(started.elapsed()))
+        );
+        Ok(())
+    }
+
+    /// pod of SPU in its SPU group
+    async fn pod_name(&self, fluvio: &Fluvio) -> Result<String> {
+        let groups = fluvio.admin().await.all::<SpuGroupSpec>().await?;
+        groups
+            .iter()
+            .find_map(|group| {
+                let index = self.spu - group.spec.min_id;
+                (index >= 0 && index < group.spec.replicas as i32)
+                    .then(|| format!("fluvio-spg-{}-{index}", group.name))
+            })
+            .ok_or_else(|| anyhow!("SPU {} is not managed by a SPU group", self.spu))
+    }
+
+    /// poll condition until it holds or timeout expires
+    async fn wait_for<F, Fut>(&self, condition: F) -> Result<()>
+    where
+        F: Fn() -> Fut,
+        Fut: std::future::Future<Output = Result<bool>>,
+    {
+        let start = Instant::now();
+        loop {
+            match condition().await {
+                Ok(true) => return Ok(()),
+                Ok(false) => {}
+                Err(err) => debug!(%err, "drill poll failed"),
+            }
+            if start.elapsed() >= *self.failover_timeout {
+                bail!(
+                    "timed out after {}",
+                    humantime::format_duration(*self.failover_timeout)
+                );
+            }
+            sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    fn kubectl(&self) -> Command {
+        let mut cmd = Command::new("kubectl");
+        cmd.args(["--namespace", self.namespace.as_str()]);
+        cmd
+    }
+}
+
+/// produce records until stopped, waiting for each to be acknowledged
+async fn canary(
+    fluvio: &Fluvio,
+    topic: &str,
+    run_id: &str,
+    stop: &AtomicBool,
+) -> Vec<CanaryAttempt> {
+    let producer = match fluvio.topic_producer(topic.to_owned()).await {
+        Ok(producer) => producer,
+        Err(err) => {
+            return vec![CanaryAttempt {
+                sent: Instant::now(),
+                result: Err(format!("unable to create producer: {err}")),
+            }];
+        }
+    };
+
+    let mut attempts = vec![];
+    while !stop.load(Ordering::Relaxed) {
+        let sent = Instant::now();
+        let value = format!("drill-{run_id}-{}", attempts.len());
+        let result = match producer.send(RecordKey::NULL, value).await {
+            Ok(output) => output
+                .wait()
+                .await
+                .map(|metadata| (metadata.partition_id(), metadata.offset()))
+                .map_err(|err| err.to_string()),
+            Err(err) => Err(err.to_string()),
+        };
+        if let Err(err) = &result {
+            debug!(%err, "canary produce failed");
+        }
+        attempts.push(CanaryAttempt { sent, result });
+        sleep(CANARY_INTERVAL.saturating_sub(sent.elapsed())).await;
+    }
+    attempts
+}
+
+/// acknowledged canary records not found in their partitions
+async fn lost_records(
+    fluvio: &Fluvio,
+    topic: &str,
+    run_id: &str,
+    attempts: &[CanaryAttempt],
+) -> Result<Vec<usize>> {
+    let prefix = format!("drill-{run_id}-");
+    let mut acked: Vec<(usize, PartitionId, i64)> = attempts
+        .iter()
+        .enumerate()
+        .filter_map(|(index, attempt)| {
+            attempt
+                .result
+                .as_ref()
+                .ok()
+                .map(|(partition, offset)| (index, *partition, *offset))
+        })
+        .collect();
+
+    let mut partitions = acked
+        .iter()
+        .map(|(_, partition, _)| *partition)
+        .collect::<Vec<_>>();
+    partitions.sort_unstable();
+    partitions.dedup();
+
+    let mut found = std::collections::HashSet::new();
+    for partition in partitions {
+        let Some(first) = acked
+            .iter()
+            .filter(|(_, acked_partition, _)| *acked_partition == partition)
+            .map(|(_, _, offset)| *offset)
+            .min()
+        else {
+            continue;
+        };
+        let mut builder = ConsumerConfigExt::builder();
+        builder
+            .topic(topic)
+            .partition(partition)
+            .offset_start(Offset::absolute(first)?)
+            .disable_continuous(true);
+        let mut stream = fluvio.consumer_with_config(builder.build()?).await?;
+        while let Some(record) = stream.next().await {
+            let record = record?;
+            if let Some(index) = std::str::from_utf8(record.value())
+                .ok()
+                .and_then(|value| value.strip_prefix(&prefix))
+                .and_then(|index| index.parse::<usize>().ok())
+            {
+                found.insert(index);
+            }
+        }
+    }
+
+    acked.retain(|(index, _, _)| !found.contains(index));
+    Ok(acked.into_iter().map(|(index, _, _)| index).collect())
+}
+
+fn round_millis(duration: Duration) -> Duration {
+    Duration::from_millis(duration.as_millis() as u64)
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_canary_summary() {
+        let start = Instant::now();
+        let attempt = |millis: u64, result: Result<(PartitionId, i64), &str>| CanaryAttempt {
+            sent: start + Duration::from_millis(millis),
+            result: result.map_err(str::to_owned),
+        };
+        let attempts = vec![
+            attempt(0, Ok((0, 0))),
+            attempt(100, Err("timeout")),
+            attempt(1600, Err("not leader")),
+            attempt(2100, Ok((0, 1))),
+            attempt(2200, Ok((1, 0))),
+        ];
+
+        assert_eq!(
+            CanarySummary::new(&attempts),
+            CanarySummary {
+                sent: 5,
+                failed: 2,
+                first_error: Some("timeout".to_owned()),
+                longest_outage: Duration::from_millis(2000),
+            }
+        );
+        assert_eq!(CanarySummary::new(&[]), CanarySummary::default());
+    }
+}
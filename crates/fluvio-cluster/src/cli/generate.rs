@@ -0,0 +1,257 @@
+//!
+//! # Generate deployment artifacts
+//!
+//! Emits files for running a Fluvio cluster without Kubernetes
+//!
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+
+use fluvio_types::defaults::{SC_PUBLIC_PORT, SC_PRIVATE_PORT};
+
+use crate::runtime::local::{BASE_SPU, spu_ports};
+
+#[derive(Debug, Parser)]
+pub enum GenerateCmd {
+    /// Generate systemd units and config files for running SC and SPUs on VMs
+    #[command(name = "systemd")]
+    Systemd(SystemdOpt),
+}
+
+impl GenerateCmd {
+    pub async fn process(self) -> Result<()> {
+        match self {
+            Self::Systemd(opt) => opt.process(),
+        }
+    }
+}
+
+#[derive(Debug, Parser)]
+pub struct SystemdOpt {
+    /// number of SPUs to generate
+    #[arg(long, default_value = "1")]
+    spus: u16,
+
+    /// directory where generated files are written
+    #[arg(long, default_value = "fluvio-systemd")]
+    output_dir: PathBuf,
+
+    /// data dir on the target hosts
+    #[arg(long, default_value = "/var/lib/fluvio")]
+    data_dir: PathBuf,
+
+    /// path to fluvio-run on the target hosts
+    #[arg(long, default_value = "/usr/local/bin/fluvio-run")]
+    launcher: PathBuf,
+
+    /// user that runs the services
+    #[arg(long, default_value = "fluvio")]
+    user: String,
+
+    /// host name where SC is reachable by SPUs and clients
+    #[arg(long, default_value = "localhost")]
+    sc_host: String,
+
+    /// host name advertised by SPUs
+    #[arg(long, default_value = "localhost")]
+    spu_host: String,
+
+    /// RUST_LOG options
+    #[arg(long, default_value = "info")]
+    rust_log: String,
+}
+
+impl SystemdOpt {
+    fn process(self) -> Result<()> {
+        let files = self.render()?;
+
+        fs::create_dir_all(&self.output_dir)
+            .with_context(|| format!("unable to create {}", self.output_dir.display()))?;
+        for (name, content) in &files {
+            let path = self.output_dir.join(name);
+            fs::write(&path, content)
+                .with_context(|| format!("unable to write {}", path.display()))?;
+        }
+
+        println!(
+            "Generated {} files in {}",
+            files.len(),
+            self.output_dir.display()
+        );
+        println!("Copy the directory to each host and run setup.sh as root to install the units.");
+        Ok(())
+    }
+
+    fn spu_ids(&self) -> impl Iterator<Item = u16> {
+        BASE_SPU..BASE_SPU.saturating_add(self.spus)
+    }
+
+    fn sc_metadata_dir(&self) -> PathBuf {
+        self.data_dir.join("sc").join("metadata")
+    }
+
+    fn spu_data_dir(&self, id: u16) -> PathBuf {
+        self.data_dir.join(format!("spu-{id}"))
+    }
+
+    /// returns list of (file name, content)
+    fn render(&self) -> Result<Vec<(String, String)>> {
+        let mut files = vec![
+            ("sc.env".to_owned(), format!("RUST_LOG={}\n", self.rust_log)),
+            ("fluvio-sc.service".to_owned(), self.render_sc_unit()),
+        ];
+
+        for id in self.spu_ids() {
+            files.push((format!("spu-{id}.env"), self.render_spu_env(id)));
+            files.push((
+                format!("fluvio-spu-{id}.service"),
+                self.render_spu_unit(id)?,
+            ));
+        }
+
+        files.push(("setup.sh".to_owned(), self.render_setup()?));
+        Ok(files)
+    }
+
+    fn render_sc_unit(&self) -> String {
+        let exec = format!(
+            "{} run sc --local {} --bind-public 0.0.0.0:{SC_PUBLIC_PORT} --bind-private 0.0.0.0:{SC_PRIVATE_PORT}",
+            self.launcher.display(),
+            self.sc_metadata_dir().display(),
+        );
+        self.render_unit("Fluvio Streaming Controller", "sc.env", &exec)
+    }
+
+    fn render_spu_env(&self, id: u16) -> String {
+        format!(
+            "RUST_LOG={}\nFLV_SPU_ID={id}\nFLV_SC_PRIVATE_HOST={}:{SC_PRIVATE_PORT}\nFLV_LOG_BASE_DIR={}\n",
+            self.rust_log,
+            self.sc_host,
+            self.spu_data_dir(id).display(),
+        )
+    }
+
+    fn render_spu_unit(&self, id: u16) -> Result<String> {
+        let (public_port, private_port) = spu_ports(id)?;
+        let exec = format!(
+            "{} run spu --public-server 0.0.0.0:{public_port} --private-server 0.0.0.0:{private_port}",
+            self.launcher.display(),
+        );
+        Ok(self.render_unit(
+            &format!("Fluvio Streaming Processing Unit {id}"),
+            &format!("spu-{id}.env"),
+            &exec,
+        ))
+    }
+
+    fn render_unit(&self, description: &str, env_file: &str, exec: &str) -> String {
+        format!(
+            r#"[Unit]
+Description={description}
+After=network-online.target
+Wants=network-online.target
+
+[Service]
+Type=simple
+User={user}
+EnvironmentFile=/etc/fluvio/{env_file}
+ExecStart={exec}
+Restart=on-failure
+RestartSec=5
+LimitNOFILE=65536
+
+[Install]
+WantedBy=multi-user.target
+"#,
+            user = self.user,
+        )
+    }
+
+    fn render_setup(&self) -> Result<String> {
+        let mut script =
+            String::from("#!/bin/sh\n# Generated by `fluvio cluster generate systemd`\nset -e\n\n");
+        script.push_str("DIR=$(cd \"$(dirname \"$0\")\" && pwd)\n\n");
+        script.push_str(&format!(
+            "id -u {user} >/dev/null 2>&1 || useradd --system --no-create-home {user}\n",
+            user = self.user
+        ));
+        script.push_str("mkdir -p /etc/fluvio\n");
+
+        let mut dirs = vec![self.sc_metadata_dir()];
+        dirs.extend(self.spu_ids().map(|id| self.spu_data_dir(id)));
+        for dir in &dirs {
+            script.push_str(&format!("mkdir -p {}\n", dir.display()));
+        }
+        script.push_str(&format!(
+            "chown -R {user} {}\n\n",
+            self.data_dir.display(),
+            user = self.user
+        ));
+
+        script.push_str("cp \"$DIR\"/*.env /etc/fluvio/\n");
+        script.push_str("cp \"$DIR\"/*.service /etc/systemd/system/\n");
+        script.push_str("systemctl daemon-reload\n\n");
+
+        script.push_str("# On the SC host:\n");
+        script.push_str("#   systemctl enable --now fluvio-sc\n");
+        script.push_str("# On each SPU host, start the SPU and register it with the SC:\n");
+        for id in self.spu_ids() {
+            let (public_port, private_port) = spu_ports(id)?;
+            script.push_str(&format!(
+                "#   systemctl enable --now fluvio-spu-{id} && fluvio cluster join --id {id} --public-server {host}:{public_port} --private-server {host}:{private_port}\n",
+                host = self.spu_host,
+            ));
+        }
+
+        Ok(script)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn opt(spus: u16) -> SystemdOpt {
+        SystemdOpt {
+            spus,
+            output_dir: PathBuf::from("out"),
+            data_dir: PathBuf::from("/var/lib/fluvio"),
+            launcher: PathBuf::from("/usr/local/bin/fluvio-run"),
+            user: "fluvio".to_owned(),
+            sc_host: "sc.internal".to_owned(),
+            spu_host: "localhost".to_owned(),
+            rust_log: "info".to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_render_systemd_files() {
+        let files = opt(3).render().expect("render");
+        let names: Vec<&str> = files.iter().map(|(name, _)| name.as_str()).collect();
+        assert!(names.contains(&"fluvio-sc.service"));
+        assert!(names.contains(&"fluvio-spu-5001.service"));
+        assert!(names.contains(&"fluvio-spu-5003.service"));
+        assert!(names.contains(&"setup.sh"));
+        assert_eq!(files.len(), 2 + 3 * 2 + 1);
+
+        let (_, env) = files
+            .iter()
+            .find(|(name, _)| name == "spu-5002.env")
+            .expect("spu env");
+        assert!(env.contains("FLV_SPU_ID=5002"));
+        assert!(env.contains("FLV_SC_PRIVATE_HOST=sc.internal:9004"));
+        assert!(env.contains("FLV_LOG_BASE_DIR=/var/lib/fluvio/spu-5002"));
+    }
+
+    #[test]
+    fn test_spu_ports() {
+        assert_eq!(spu_ports(5001).expect("ports"), (9010, 9011));
+        assert_eq!(spu_ports(5003).expect("ports"), (9030, 9031));
+        assert!(spu_ports(5000).is_err());
+        assert!(spu_ports(u16::MAX).is_err());
+        assert!(opt(10_000).render().is_err());
+    }
+}
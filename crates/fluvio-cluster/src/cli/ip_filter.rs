@@ -0,0 +1,75 @@
+//!
+//! # IP filter CLI
+//!
+//! Show or replace networks accepted by SC public service.
+//! Changes are not persisted, SC startup options apply after restart.
+//! SPU networks are changed with `fluvio cluster spu config set`.
+//!
+
+use anyhow::Result;
+use clap::Parser;
+
+use fluvio::Fluvio;
+use fluvio_sc_schema::ip_filter::IpFilterRules;
+
+#[derive(Debug, Parser)]
+pub enum IpFilterCmd {
+    /// Show networks accepted by SC
+    #[command(name = "get")]
+    Get,
+
+    /// Replace networks accepted by SC, existing connections are not affected
+    #[command(name = "set")]
+    Set(SetIpFilterOpt),
+
+    /// Accept connections from all networks
+    #[command(name = "clear")]
+    Clear,
+}
+
+impl IpFilterCmd {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+        let rules = match self {
+            Self::Get => admin.ip_filter().await?,
+            Self::Set(opt) => {
+                admin
+                    .set_ip_filter(IpFilterRules {
+                        allow: opt.allow,
+                        deny: opt.deny,
+                    })
+                    .await?
+            }
+            Self::Clear => admin.set_ip_filter(IpFilterRules::default()).await?,
+        };
+        print_rules(&rules);
+        Ok(())
+    }
+}
+
+#[derive(Debug, Parser)]
+pub struct SetIpFilterOpt {
+    /// Only accept connections from these networks, in CIDR notation
+    #[arg(long, value_name = "cidr", value_delimiter = ',')]
+    allow: Vec<String>,
+
+    /// Reject connections from these networks, takes precedence over allow list
+    #[arg(long, value_name = "cidr", value_delimiter = ',')]
+    deny: Vec<String>,
+}
+
+fn print_rules(rules: &IpFilterRules) {
+    let networks = |networks: &[String]| {
+        if networks.is_empty() {
+            "-".to_owned()
+        } else {
+            networks.join(", ")
+        }
+    };
+    if rules.allow.is_empty() {
+        println!("allow: all networks");
+    } else {
+        println!("allow: {}", networks(&rules.allow));
+    }
+    println!("deny:  {}", networks(&rules.deny));
+}
@@ -0,0 +1,109 @@
+//!
+//! # Join SPU to cluster
+//!
+//! Registers an SPU running outside Kubernetes with the SC
+//!
+
+use std::convert::TryFrom;
+
+use anyhow::{Result, anyhow};
+use clap::Parser;
+
+use fluvio::Fluvio;
+use fluvio::config::TlsPolicy;
+use fluvio::metadata::customspu::{CustomSpuSpec, CustomSpuKey};
+use fluvio_extension_common::target::ClusterTarget;
+use flv_util::socket_helpers::ServerAddress;
+
+#[derive(Debug, Parser)]
+pub struct JoinOpt {
+    /// SPU id
+    #[arg(short = 'i', long = "id", env = "FLV_SPU_ID")]
+    id: i32,
+
+    /// Public server::port advertised to clients
+    #[arg(short = 'p', long = "public-server", value_name = "host:port")]
+    public_server: String,
+
+    /// Private server::port advertised to other SPUs
+    #[arg(short = 'v', long = "private-server", value_name = "host:port")]
+    private_server: String,
+
+    /// Rack name
+    #[arg(short = 'r', long = "rack", value_name = "string")]
+    rack: Option<String>,
+
+    /// Replace an existing registration of the same SPU id with different endpoints
+    #[arg(long)]
+    force: bool,
+
+    /// Allow joining over a connection without TLS
+    #[arg(long)]
+    insecure: bool,
+}
+
+impl JoinOpt {
+    pub async fn process(self, target: ClusterTarget) -> Result<()> {
+        let config = target.load()?;
+        if matches!(config.tls, TlsPolicy::Disabled) && !self.insecure {
+            return Err(anyhow!(
+                "refusing to join SPU {} over a connection without TLS, use --insecure to override",
+                self.id
+            ));
+        }
+
+        let fluvio = Fluvio::connect_with_config(&config).await?;
+        self.join(&fluvio).await
+    }
+
+    async fn join(self, fluvio: &Fluvio) -> Result<()> {
+        let spec = self.as_spec()?;
+        let name = format!("custom-spu-{}", self.id);
+        let admin = fluvio.admin().await;
+
+        let existing = admin
+            .all::<CustomSpuSpec>()
+            .await?
+            .into_iter()
+            .find(|spu| spu.spec.id == self.id);
+
+        if let Some(existing) = existing {
+            if is_same_registration(&existing.spec, &spec) {
+                println!("SPU {} already joined the cluster", self.id);
+                return Ok(());
+            }
+
+            if !self.force {
+                return Err(anyhow!(
+                    "SPU {} is already registered as \"{}\" with different endpoints, use --force to replace it",
+                    self.id,
+                    existing.name
+                ));
+            }
+
+            admin
+                .delete::<CustomSpuSpec>(CustomSpuKey::Name(existing.name))
+                .await?;
+        }
+
+        admin.create(name, false, spec).await?;
+        println!("SPU {} joined the cluster", self.id);
+        Ok(())
+    }
+
+    fn as_spec(&self) -> Result<CustomSpuSpec> {
+        Ok(CustomSpuSpec {
+            id: self.id,
+            public_endpoint: ServerAddress::try_from(self.public_server.clone())?.into(),
+            public_endpoint_local: None,
+            private_endpoint: ServerAddress::try_from(self.private_server.clone())?.into(),
+            rack: self.rack.clone(),
+        })
+    }
+}
+
+fn is_same_registration(existing: &CustomSpuSpec, new: &CustomSpuSpec) -> bool {
+    existing.public_endpoint == new.public_endpoint
+        && existing.private_endpoint == new.private_endpoint
+        && existing.rack == new.rack
+}
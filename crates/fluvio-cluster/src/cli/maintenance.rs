@@ -0,0 +1,79 @@
+//!
+//! # Maintenance mode CLI
+//!
+//! While cluster is in maintenance, SC rejects creation of topics and doesn't
+//! reassign replicas. SPUs optionally reject produce requests.
+//!
+
+use anyhow::Result;
+use clap::Parser;
+
+use fluvio::Fluvio;
+use fluvio_sc_schema::clusterconfig::{MaintenanceSpec, CLUSTER_CONFIG_NAME};
+
+use super::config::{current_config, print_config};
+
+#[derive(Debug, Parser)]
+pub enum MaintenanceCmd {
+    /// Put cluster in maintenance
+    #[command(name = "on")]
+    On(MaintenanceOnOpt),
+
+    /// Take cluster out of maintenance
+    #[command(name = "off")]
+    Off,
+}
+
+#[derive(Debug, Parser)]
+pub struct MaintenanceOnOpt {
+    /// SPUs reject produce requests while in maintenance
+    #[arg(long)]
+    reject_produce: bool,
+}
+
+impl MaintenanceCmd {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+        let mut config = current_config(&admin).await?;
+        config.maintenance = self.maintenance();
+        admin
+            .create(CLUSTER_CONFIG_NAME.to_owned(), false, config.clone())
+            .await?;
+        print_config(&config);
+        Ok(())
+    }
+
+    fn maintenance(self) -> Option<MaintenanceSpec> {
+        match self {
+            Self::On(opt) => Some(MaintenanceSpec {
+                reject_produce: opt.reject_produce,
+            }),
+            Self::Off => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use clap::Parser;
+
+    use super::*;
+
+    #[test]
+    fn test_maintenance() {
+        let cmd = MaintenanceCmd::parse_from(["maintenance", "on"]);
+        assert_eq!(cmd.maintenance(), Some(MaintenanceSpec::default()));
+
+        let cmd = MaintenanceCmd::parse_from(["maintenance", "on", "--reject-produce"]);
+        assert_eq!(
+            cmd.maintenance(),
+            Some(MaintenanceSpec {
+                reject_produce: true
+            })
+        );
+
+        let cmd = MaintenanceCmd::parse_from(["maintenance", "off"]);
+        assert_eq!(cmd.maintenance(), None);
+    }
+}
@@ -19,6 +19,15 @@ mod diagnostics;
 mod status;
 mod shutdown;
 mod upgrade;
+mod generate;
+mod join;
+mod renew_certs;
+mod package_images;
+mod api_versions;
+mod ip_filter;
+mod config;
+mod maintenance;
+mod drill;
 
 use start::StartOpt;
 use resume::ResumeOpt;
@@ -30,6 +39,15 @@ use diagnostics::DiagnosticsOpt;
 use status::StatusOpt;
 use shutdown::ShutdownOpt;
 use upgrade::UpgradeOpt;
+use generate::GenerateCmd;
+use join::JoinOpt;
+use renew_certs::RenewCertsOpt;
+use package_images::PackageImagesOpt;
+use api_versions::ApiVersionsOpt;
+use ip_filter::IpFilterCmd;
+use config::ClusterConfigCmd;
+use maintenance::MaintenanceCmd;
+use drill::DrillCmd;
 
 pub use self::error::ClusterCliError;
 
@@ -94,6 +112,67 @@ pub enum ClusterCmd {
     /// Shutdown cluster processes without deleting data
     #[command(name = "shutdown")]
     Shutdown(ShutdownOpt),
+
+    /// Generate deployment files for running a cluster without Kubernetes
+    #[command(subcommand, name = "generate")]
+    Generate(GenerateCmd),
+
+    /// Register an SPU running outside Kubernetes with the cluster
+    ///
+    /// This is run on the SPU host after the SPU is started. The registration
+    /// is idempotent, so it is safe to run on every SPU start.
+    #[command(name = "join")]
+    Join(JoinOpt),
+
+    /// Renew TLS certificates of a local cluster
+    ///
+    /// Server and client certificates are re-signed by the CA with the same key,
+    /// subject and extensions. Previous certificates are kept with `.bak` extension.
+    #[command(name = "renew-certs")]
+    RenewCerts(RenewCertsOpt),
+
+    /// Export images required by the cluster for air-gapped installs
+    ///
+    /// Images are saved to a tarball, which can then be pushed to a registry mirror
+    /// with `--input` and `--mirror`. Install from the mirror with `--image-registry`.
+    #[command(name = "package-images")]
+    PackageImages(PackageImagesOpt),
+
+    /// Report API versions supported by SC and each SPU
+    ///
+    /// Prints a matrix of API key version ranges per node, flagging APIs and
+    /// platform versions which differ across the cluster.
+    #[command(name = "api-versions")]
+    ApiVersions(ApiVersionsOpt),
+
+    /// Show or change networks accepted by the SC public service
+    ///
+    /// Rules are CIDR allow and deny lists, deny takes precedence.
+    /// Changes apply to new connections and last until SC restart.
+    #[command(subcommand, name = "ip-filter")]
+    IpFilter(IpFilterCmd),
+
+    /// Show or change defaults of new topics
+    ///
+    /// Retention, replication and compression defaults are applied by SC
+    /// to topics created without them. Producers create unknown topics
+    /// when auto creation is enabled.
+    #[command(subcommand, name = "config")]
+    Config(ClusterConfigCmd),
+
+    /// Turn maintenance mode on or off
+    ///
+    /// While in maintenance, SC rejects creation of topics and doesn't
+    /// reassign replicas. With `--reject-produce` SPUs reject produce requests.
+    #[command(subcommand, name = "maintenance")]
+    Maintenance(MaintenanceCmd),
+
+    /// Run failure drills against the cluster
+    ///
+    /// Drills take down cluster components on purpose and report how
+    /// the cluster recovered. Run them against test clusters.
+    #[command(subcommand, name = "drill")]
+    Drill(DrillCmd),
 }
 
 impl ClusterCmd {
@@ -116,7 +195,7 @@ impl ClusterCmd {
                         ImageTagStrategy::VersionGit => {
                             let image_version = format!("{}-{}", VERSION, env!("GIT_HASH"));
                             debug!("Using image version: {:?}", &image_version);
-                            start.k8_config.image_version = Some(image_version);
+                            start.k8_config.image_tag = Some(image_version);
                         }
                         ImageTagStrategy::Git => {
                             debug!("Using developer image version: {}", env!("GIT_HASH"));
@@ -138,7 +217,7 @@ impl ClusterCmd {
                         ImageTagStrategy::Version => {}
                         ImageTagStrategy::VersionGit => {
                             let image_version = format!("{}-{}", VERSION, env!("GIT_HASH"));
-                            upgrade.start.k8_config.image_version = Some(image_version);
+                            upgrade.start.k8_config.image_tag = Some(image_version);
                         }
                         ImageTagStrategy::Git => upgrade.start.develop = true,
                     }
@@ -169,6 +248,38 @@ impl ClusterCmd {
             Self::Shutdown(opt) => {
                 opt.process().await?;
             }
+            Self::Generate(generate) => {
+                generate.process().await?;
+            }
+            Self::Join(join) => {
+                join.process(target).await?;
+            }
+            Self::RenewCerts(opt) => {
+                opt.process().await?;
+            }
+            Self::PackageImages(opt) => {
+                opt.process(platform_version).await?;
+            }
+            Self::ApiVersions(opt) => {
+                let fluvio = target.connect().await?;
+                opt.process(out, &fluvio).await?;
+            }
+            Self::IpFilter(cmd) => {
+                let fluvio = target.connect().await?;
+                cmd.process(&fluvio).await?;
+            }
+            Self::Config(cmd) => {
+                let fluvio = target.connect().await?;
+                cmd.process(&fluvio).await?;
+            }
+            Self::Maintenance(cmd) => {
+                let fluvio = target.connect().await?;
+                cmd.process(&fluvio).await?;
+            }
+            Self::Drill(cmd) => {
+                let fluvio = target.connect().await?;
+                cmd.process(&fluvio).await?;
+            }
         }
 
         Ok(())
@@ -0,0 +1,160 @@
+//!
+//! # Package images for air-gapped installs
+//!
+//! Exports images required by the cluster to a tarball on a connected machine,
+//! and pushes them from the tarball to a registry mirror reachable by the cluster.
+//!
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result, anyhow};
+use clap::Parser;
+use semver::Version;
+
+use fluvio_command::CommandExt;
+
+use crate::ClusterConfig;
+
+#[derive(Debug, Parser)]
+pub struct PackageImagesOpt {
+    /// Registry to pull images from
+    #[arg(long, visible_alias = "registry")]
+    image_registry: Option<String>,
+
+    /// Image tag, defaults to platform version
+    #[arg(long)]
+    image_tag: Option<String>,
+
+    /// Tarball images are exported to
+    #[arg(short, long, conflicts_with = "input")]
+    output: Option<PathBuf>,
+
+    /// Push images from tarball created by this command to mirror registry instead of exporting
+    #[arg(long, requires = "mirror")]
+    input: Option<PathBuf>,
+
+    /// Registry mirror images are pushed to, ex: registry.local:5000/infinyon.
+    /// Install with `fluvio cluster start --image-registry <MIRROR>` afterwards
+    #[arg(long, requires = "input")]
+    mirror: Option<String>,
+
+    /// Container CLI used to pull, save and push images
+    #[arg(long, default_value = "docker")]
+    container_cli: String,
+}
+
+impl PackageImagesOpt {
+    pub async fn process(self, platform_version: Version) -> Result<()> {
+        let mut builder = ClusterConfig::builder(platform_version.clone());
+        if let Some(registry) = &self.image_registry {
+            builder.image_registry(registry);
+        }
+        if let Some(tag) = &self.image_tag {
+            builder.image_tag(tag.trim());
+        }
+        let images = builder.build()?.images();
+
+        match (&self.input, &self.mirror) {
+            (Some(input), Some(mirror)) => self.push_to_mirror(&images, input, mirror),
+            _ => {
+                let output = self
+                    .output
+                    .clone()
+                    .unwrap_or_else(|| format!("fluvio-images-{platform_version}.tar").into());
+                self.export(&images, output)
+            }
+        }
+    }
+
+    fn export(&self, images: &[String], output: PathBuf) -> Result<()> {
+        for image in images {
+            println!("pulling {image}");
+            self.container_cmd()
+                .args(["pull", image.as_str()])
+                .inherit()
+                .result()
+                .with_context(|| format!("unable to pull {image}"))?;
+        }
+
+        self.container_cmd()
+            .arg("save")
+            .arg("-o")
+            .arg(&output)
+            .args(images)
+            .inherit()
+            .result()
+            .context("unable to save images")?;
+
+        println!("✅ Exported {} to {}", images.join(", "), output.display());
+        println!(
+            "Copy it to a machine with access to the registry mirror and run \
+             'fluvio cluster package-images --input {} --mirror <REGISTRY>'",
+            output.display()
+        );
+        Ok(())
+    }
+
+    fn push_to_mirror(&self, images: &[String], input: &Path, mirror: &str) -> Result<()> {
+        self.container_cmd()
+            .arg("load")
+            .arg("-i")
+            .arg(input)
+            .inherit()
+            .result()
+            .with_context(|| format!("unable to load images from {}", input.display()))?;
+
+        let mirror = mirror.trim_end_matches('/');
+        for image in images {
+            let mirrored = mirror_image(image, mirror)?;
+            println!("pushing {mirrored}");
+            self.container_cmd()
+                .args(["tag", image.as_str(), mirrored.as_str()])
+                .inherit()
+                .result()
+                .with_context(|| format!("unable to tag {image}"))?;
+            self.container_cmd()
+                .args(["push", mirrored.as_str()])
+                .inherit()
+                .result()
+                .with_context(|| format!("unable to push {mirrored}"))?;
+        }
+
+        println!("✅ Images pushed to {mirror}");
+        println!("Install with 'fluvio cluster start --image-registry {mirror}'");
+        Ok(())
+    }
+
+    fn container_cmd(&self) -> Command {
+        Command::new(&self.container_cli)
+    }
+}
+
+/// name of `image` in `mirror` registry, keeping image name and tag
+fn mirror_image(image: &str, mirror: &str) -> Result<String> {
+    let name = image
+        .rsplit('/')
+        .next()
+        .filter(|name| !name.is_empty())
+        .ok_or_else(|| anyhow!("invalid image name: {image}"))?;
+    Ok(format!("{mirror}/{name}"))
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_mirror_image() {
+        assert_eq!(
+            mirror_image("infinyon/fluvio:0.11.0", "registry.local:5000/infinyon").unwrap(),
+            "registry.local:5000/infinyon/fluvio:0.11.0"
+        );
+        assert_eq!(
+            mirror_image("localhost:5000/infinyon/fluvio:0.11.0", "mirror").unwrap(),
+            "mirror/fluvio:0.11.0"
+        );
+        assert!(mirror_image("infinyon/", "mirror").is_err());
+    }
+}
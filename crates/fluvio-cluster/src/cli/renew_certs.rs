@@ -0,0 +1,149 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result, bail};
+use clap::Parser;
+use colored::Colorize;
+use tracing::debug;
+
+use fluvio::config::{TlsConfig, TlsPolicy};
+use fluvio_command::CommandExt;
+use fluvio_extension_common::cert::{CertExpiry, subject_alt_names};
+use fluvio_types::config_file::SaveLoadConfig;
+
+use crate::LocalConfig;
+use crate::start::local::LOCAL_CONFIG_PATH;
+use crate::{InstallationType, cli::get_installation_type};
+
+#[derive(Debug, Parser)]
+pub struct RenewCertsOpt {
+    /// Private key of Certificate Authority which issued the cluster certificates
+    #[arg(long, value_name = "PATH")]
+    ca_key: PathBuf,
+
+    /// Number of days renewed certificates are valid
+    #[arg(long, default_value_t = 365)]
+    days: u32,
+}
+
+impl RenewCertsOpt {
+    pub async fn process(self) -> Result<()> {
+        let (installation_type, config) = get_installation_type()?;
+        debug!(?installation_type);
+
+        match installation_type {
+            InstallationType::Local | InstallationType::ReadOnly => {}
+            InstallationType::K8 | InstallationType::LocalK8 => bail!(
+                "'fluvio cluster renew-certs' is only for local clusters, update the 'fluvio-tls' and 'fluvio-ca' secrets of Kubernetes cluster instead"
+            ),
+            _ => {
+                let profile = config.config().current_profile_name().unwrap_or("none");
+                bail!(
+                    "'fluvio cluster renew-certs' is only for local clusters, cluster \"{profile}\" is not managed by this machine"
+                );
+            }
+        }
+
+        let local_config_path = LOCAL_CONFIG_PATH
+            .as_ref()
+            .filter(|path| path.is_file())
+            .context("Configuration file for local cluster not found")?;
+        let local_config = LocalConfig::load_from(local_config_path)
+            .context("Unable to load configuration file for local cluster")?;
+
+        let TlsPolicy::Verified(TlsConfig::Files(server)) = local_config.server_tls_policy() else {
+            bail!("local cluster was not started with TLS certificate files, nothing to renew");
+        };
+        let mut certs = vec![("server", &server.cert, &server.ca_cert)];
+        if let TlsPolicy::Verified(TlsConfig::Files(client)) = local_config.client_tls_policy()
+            && client.cert != server.cert
+        {
+            certs.push(("client", &client.cert, &client.ca_cert));
+        }
+
+        for (name, cert, ca_cert) in certs {
+            self.renew(cert, ca_cert)
+                .with_context(|| format!("failed to renew {name} certificate"))?;
+            let renewed = CertExpiry::earliest(&std::fs::read(cert)?)?;
+            println!(
+                "✅ Renewed {name} certificate {}, valid until {}",
+                cert.display(),
+                humantime::format_rfc3339_seconds(renewed.not_after)
+            );
+        }
+
+        println!(
+            "{}",
+            "Restart cluster to use renewed certificates: 'fluvio cluster shutdown' and 'fluvio cluster resume'"
+                .bold()
+        );
+        Ok(())
+    }
+
+    /// re-sign existing certificate with CA, keeping its key, subject and alternative names.
+    /// previous certificate is kept with `.bak` extension
+    fn renew(&self, cert: &Path, ca_cert: &Path) -> Result<()> {
+        let backup = cert.with_extension("crt.bak");
+        std::fs::copy(cert, &backup)
+            .with_context(|| format!("unable to back up {}", cert.display()))?;
+
+        // extensions are not carried over by re-signing, hostname checks need alternative names
+        let sans = subject_alt_names(&std::fs::read(&backup)?)?;
+        let extensions = tempfile::NamedTempFile::new()?;
+        if !sans.is_empty() {
+            std::fs::write(
+                extensions.path(),
+                format!("subjectAltName = {}\n", sans.join(",")),
+            )?;
+        }
+
+        Command::new("openssl")
+            .arg("x509")
+            .arg("-in")
+            .arg(&backup)
+            .arg("-CA")
+            .arg(ca_cert)
+            .arg("-CAkey")
+            .arg(&self.ca_key)
+            .arg("-set_serial")
+            .arg(rand::random::<u32>().to_string())
+            .arg("-days")
+            .arg(self.days.to_string())
+            .arg("-extfile")
+            .arg(extensions.path())
+            .arg("-out")
+            .arg(cert)
+            .result()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::time::Duration;
+
+    use fluvio_extension_common::cert::generate_self_signed;
+
+    use super::*;
+
+    #[test]
+    fn test_renew_keeps_alt_names() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let (ca_cert, ca_key) = generate_self_signed(dir.path(), "ca", 400).expect("ca");
+        let (cert, _) = generate_self_signed(dir.path(), "fluvio.local", 1).expect("cert");
+
+        let opt = RenewCertsOpt { ca_key, days: 90 };
+        opt.renew(&cert, &ca_cert).expect("renew");
+
+        let pem = std::fs::read(&cert).expect("read");
+        let renewed = CertExpiry::earliest(&pem).expect("expiry");
+        assert!(renewed.subject.contains("CN=fluvio.local"));
+        assert!(!renewed.expires_within(Duration::from_secs(89 * 24 * 3600)));
+        assert_eq!(
+            subject_alt_names(&pem).expect("sans"),
+            vec!["DNS:fluvio.local".to_owned(), "IP:127.0.0.1".to_owned()]
+        );
+        assert!(cert.with_extension("crt.bak").is_file());
+    }
+}
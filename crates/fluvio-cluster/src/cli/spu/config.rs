@@ -0,0 +1,169 @@
+//!
+//! # SPU runtime config
+//!
+//! Changes SPU settings without restarting the SPU. The config is stored in the
+//! SPU spec and propagated by the SC to the SPU.
+//! Configuration in effect, with runtime settings applied, is fetched from the SPU itself.
+//!
+
+use anyhow::{Result, anyhow};
+use clap::Parser;
+
+use fluvio::Fluvio;
+use fluvio::metadata::spu::{SpuSpec, SpuRuntimeConfig, UpdateSpuAction};
+use fluvio_service::ip_filter::IpCidr;
+
+#[derive(Debug, Parser)]
+pub enum SpuConfigCmd {
+    /// Change runtime settings of a running SPU
+    #[command(name = "set")]
+    Set(SetSpuConfigOpt),
+
+    /// Clear runtime settings, SPU falls back to its startup configuration
+    #[command(name = "reset")]
+    Reset(ResetSpuConfigOpt),
+
+    /// Print configuration in effect on a running SPU as JSON
+    #[command(name = "show")]
+    Show(ShowSpuConfigOpt),
+}
+
+impl SpuConfigCmd {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        match self {
+            Self::Set(opt) => opt.process(fluvio).await,
+            Self::Reset(opt) => opt.process(fluvio).await,
+            Self::Show(opt) => opt.process(fluvio).await,
+        }
+    }
+}
+
+#[derive(Debug, Parser)]
+pub struct SetSpuConfigOpt {
+    /// SPU id
+    #[arg(short = 'i', long = "id")]
+    id: i32,
+
+    /// Log filter, same format as RUST_LOG
+    #[arg(long, value_name = "filter")]
+    log_level: Option<String>,
+
+    /// Number of writes before log is flushed
+    #[arg(long, value_name = "integer")]
+    flush_write_count: Option<u32>,
+
+    /// Idle time in milliseconds before log is flushed
+    #[arg(long, value_name = "integer")]
+    flush_idle_msec: Option<u32>,
+
+    /// Max bytes transferred between leader and follower per request
+    #[arg(long, value_name = "integer")]
+    peer_max_bytes: Option<u32>,
+
+    /// Log every request to the public service with client identity
+    #[arg(long, value_name = "bool")]
+    access_log: Option<bool>,
+
+    /// Only accept connections from these networks, in CIDR notation.
+    /// Empty value clears the list
+    #[arg(long, value_name = "cidr", value_delimiter = ',')]
+    ip_allow: Option<Vec<String>>,
+
+    /// Reject connections from these networks, in CIDR notation.
+    /// Empty value clears the list
+    #[arg(long, value_name = "cidr", value_delimiter = ',')]
+    ip_deny: Option<Vec<String>>,
+
+    /// Max bytes per second produced by each client connection, 0 removes the quota
+    #[arg(long, value_name = "integer")]
+    produce_byte_rate: Option<u32>,
+
+    /// Max bytes per second fetched by each client connection, 0 removes the quota
+    #[arg(long, value_name = "integer")]
+    fetch_byte_rate: Option<u32>,
+}
+
+impl SetSpuConfigOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let config = SpuRuntimeConfig {
+            log_level: self.log_level,
+            flush_write_count: self.flush_write_count,
+            flush_idle_msec: self.flush_idle_msec,
+            peer_max_bytes: self.peer_max_bytes,
+            access_log: self.access_log,
+            ip_allow: self.ip_allow.map(parse_networks).transpose()?,
+            ip_deny: self.ip_deny.map(parse_networks).transpose()?,
+            produce_byte_rate: self.produce_byte_rate,
+            fetch_byte_rate: self.fetch_byte_rate,
+        };
+        if config.is_empty() {
+            return Err(anyhow!("no settings to change"));
+        }
+
+        let name = find_spu_name(fluvio, self.id).await?;
+        fluvio
+            .admin()
+            .await
+            .update::<SpuSpec>(name, UpdateSpuAction::SetConfig(config.clone()))
+            .await?;
+        println!("SPU {} config updated: {config}", self.id);
+        Ok(())
+    }
+}
+
+#[derive(Debug, Parser)]
+pub struct ResetSpuConfigOpt {
+    /// SPU id
+    #[arg(short = 'i', long = "id")]
+    id: i32,
+}
+
+impl ResetSpuConfigOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let name = find_spu_name(fluvio, self.id).await?;
+        fluvio
+            .admin()
+            .await
+            .update::<SpuSpec>(name, UpdateSpuAction::ResetConfig)
+            .await?;
+        println!("SPU {} config reset", self.id);
+        Ok(())
+    }
+}
+
+#[derive(Debug, Parser)]
+pub struct ShowSpuConfigOpt {
+    /// SPU id
+    #[arg(short = 'i', long = "id")]
+    id: i32,
+}
+
+impl ShowSpuConfigOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let config = fluvio.spu_config(self.id).await?;
+        let config: serde_json::Value = serde_json::from_str(&config)?;
+        println!("{}", serde_json::to_string_pretty(&config)?);
+        Ok(())
+    }
+}
+
+/// validate networks, so SPU doesn't get ones it can't apply
+fn parse_networks(networks: Vec<String>) -> Result<Vec<String>> {
+    networks
+        .iter()
+        .filter(|network| !network.trim().is_empty())
+        .map(|network| Ok(network.parse::<IpCidr>()?.to_string()))
+        .collect()
+}
+
+async fn find_spu_name(fluvio: &Fluvio, id: i32) -> Result<String> {
+    fluvio
+        .admin()
+        .await
+        .all::<SpuSpec>()
+        .await?
+        .into_iter()
+        .find(|spu| spu.spec.id == id)
+        .map(|spu| spu.name)
+        .ok_or_else(|| anyhow!("SPU {id} not found"))
+}
@@ -5,6 +5,8 @@ mod list;
 mod display;
 mod register;
 mod unregister;
+mod config;
+mod resize;
 
 use anyhow::Result;
 
@@ -12,6 +14,8 @@ use fluvio::Fluvio;
 use list::ListSpusOpt;
 use register::RegisterCustomSpuOpt;
 use unregister::UnregisterCustomSpuOpt;
+use config::SpuConfigCmd;
+use resize::ResizeStorageOpt;
 
 use super::common::COMMAND_TEMPLATE;
 use super::common::output::Terminal;
@@ -38,6 +42,17 @@ pub enum SpuCmd {
         help_template = COMMAND_TEMPLATE,
     )]
     List(ListSpusOpt),
+
+    /// Change SPU settings at runtime, without restart
+    #[command(subcommand, name = "config")]
+    Config(SpuConfigCmd),
+
+    /// Expand persistent volumes of SPUs running on Kubernetes
+    #[command(
+        name = "resize-storage",
+        help_template = COMMAND_TEMPLATE,
+    )]
+    ResizeStorage(ResizeStorageOpt),
 }
 
 impl SpuCmd {
@@ -52,6 +67,12 @@ impl SpuCmd {
             Self::List(list) => {
                 list.process(out, fluvio).await?;
             }
+            Self::Config(config) => {
+                config.process(fluvio).await?;
+            }
+            Self::ResizeStorage(resize) => {
+                resize.process(fluvio).await?;
+            }
         }
         Ok(())
     }
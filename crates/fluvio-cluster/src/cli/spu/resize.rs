@@ -0,0 +1,247 @@
+//!
+//! # Resize SPU storage
+//!
+//! Expands persistent volumes of a managed SPU group running on Kubernetes.
+//! Volume claim templates of a StatefulSet can't be changed, so existing claims are
+//! expanded in place, the SPU group is updated with the new size and only then
+//! the StatefulSet is deleted without deleting its pods, so SC recreates it from the SPU group.
+//! Each step can be repeated, a failed step stops the command with instructions to recover.
+//!
+
+use std::process::Command;
+
+use anyhow::{Context, Result, anyhow, bail};
+use clap::Parser;
+use tracing::debug;
+
+use fluvio::Fluvio;
+use fluvio_command::CommandExt;
+use fluvio_controlplane_metadata::spg::SpuGroupSpec;
+
+use crate::cli::get_installation_type;
+use crate::{DEFAULT_NAMESPACE, DEFAULT_SPU_GROUP_NAME, InstallationType};
+
+#[derive(Debug, Parser)]
+pub struct ResizeStorageOpt {
+    /// New storage size of each SPU, ex: 50Gi. Volumes can only grow
+    #[arg(long, value_name = "SIZE")]
+    size: String,
+
+    /// SPU group to resize
+    #[arg(long, default_value = DEFAULT_SPU_GROUP_NAME)]
+    group: String,
+
+    /// Kubernetes namespace of cluster
+    #[arg(short, long, default_value = DEFAULT_NAMESPACE)]
+    namespace: String,
+}
+
+impl ResizeStorageOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let (installation_type, _) = get_installation_type()?;
+        debug!(?installation_type);
+        if !matches!(
+            installation_type,
+            InstallationType::K8 | InstallationType::LocalK8
+        ) {
+            bail!("SPU storage can only be resized for SPU groups running on Kubernetes");
+        }
+
+        let new_bytes = parse_quantity(&self.size)?;
+
+        let admin = fluvio.admin().await;
+        let group = admin
+            .all::<SpuGroupSpec>()
+            .await?
+            .into_iter()
+            .find(|group| group.name == self.group)
+            .ok_or_else(|| anyhow!("SPU group '{}' not found", self.group))?;
+
+        let current = group.spec.spu_config.real_storage_config().size;
+        let current_bytes = parse_quantity(&current)?;
+        if new_bytes == current_bytes {
+            println!("SPU group '{}' already has {current} storage", self.group);
+            return Ok(());
+        }
+        if new_bytes < current_bytes {
+            bail!(
+                "storage can't shrink from {current} to {}, persistent volumes only support expansion",
+                self.size
+            );
+        }
+
+        let claims: Vec<String> = (0..group.spec.replicas)
+            .map(|index| format!("data-fluvio-spg-{}-{index}", self.group))
+            .collect();
+        self.check_expandable(&claims)?;
+
+        let claim_patch = serde_json::json!({
+            "spec": { "resources": { "requests": { "storage": self.size } } }
+        });
+        for claim in &claims {
+            self.kubectl()
+                .args(["patch", "pvc", claim.as_str()])
+                .arg("-p")
+                .arg(claim_patch.to_string())
+                .result()
+                .with_context(|| {
+                    format!(
+                        "unable to resize volume claim {claim}. SPU group is unchanged, \
+                         fix the claim and run this command again"
+                    )
+                })?;
+            println!("✅ Requested {} for volume claim {claim}", self.size);
+        }
+
+        let group_patch = serde_json::json!({
+            "spec": { "spuConfig": { "storage": { "size": self.size } } }
+        });
+        self.kubectl()
+            .args(["patch", "spugroup", self.group.as_str(), "--type", "merge"])
+            .arg("-p")
+            .arg(group_patch.to_string())
+            .result()
+            .with_context(|| {
+                format!(
+                    "unable to update storage size of SPU group. Volume claims are already expanded, \
+                     run this command again to update SPU group '{}'",
+                    self.group
+                )
+            })?;
+
+        let updated = admin
+            .all::<SpuGroupSpec>()
+            .await?
+            .into_iter()
+            .find(|group| group.name == self.group)
+            .map(|group| group.spec.spu_config.real_storage_config().size);
+        match updated {
+            Some(size) if parse_quantity(&size).ok() == Some(new_bytes) => {}
+            other => bail!(
+                "SPU group '{}' storage size is {} after update, expected {}. \
+                 StatefulSet is unchanged, run this command again",
+                self.group,
+                other.as_deref().unwrap_or("unknown"),
+                self.size
+            ),
+        }
+
+        // pods are kept, recreated StatefulSet adopts them and rolls them with the new size
+        let statefulset = format!("fluvio-spg-{}", self.group);
+        self.kubectl()
+            .args(["delete", "statefulset", statefulset.as_str()])
+            .arg("--cascade=orphan")
+            .result()
+            .with_context(|| {
+                format!(
+                    "unable to detach SPU pods from StatefulSet. SPU group is updated, \
+                     finish with 'kubectl delete statefulset {statefulset} --cascade=orphan -n {}'",
+                    self.namespace
+                )
+            })?;
+
+        println!(
+            "✅ SPU group '{}' storage resized from {current} to {}",
+            self.group, self.size
+        );
+        println!(
+            "Volumes expand in the background, some storage drivers require SPU pod restart to finish file system resize. \
+             Track progress with 'kubectl get pvc -n {}'",
+            self.namespace
+        );
+        Ok(())
+    }
+
+    /// volumes can be expanded only if their storage class allows it
+    fn check_expandable(&self, claims: &[String]) -> Result<()> {
+        let Some(claim) = claims.first() else {
+            bail!("SPU group '{}' has no replicas", self.group);
+        };
+        let class = self.query(&[
+            "get",
+            "pvc",
+            claim.as_str(),
+            "-o",
+            "jsonpath={.spec.storageClassName}",
+        ])?;
+        if class.is_empty() {
+            bail!(
+                "volume claim {claim} has no storage class, expand its persistent volume manually"
+            );
+        }
+        let expandable = self.query(&[
+            "get",
+            "storageclass",
+            class.as_str(),
+            "-o",
+            "jsonpath={.allowVolumeExpansion}",
+        ])?;
+        if expandable != "true" {
+            bail!(
+                "storage class '{class}' does not allow volume expansion. \
+                 Set 'allowVolumeExpansion: true' on it if its provisioner supports expansion and run this command again"
+            );
+        }
+        Ok(())
+    }
+
+    fn query(&self, args: &[&str]) -> Result<String> {
+        let output = self
+            .kubectl()
+            .args(args)
+            .result()
+            .with_context(|| format!("kubectl {} failed", args.join(" ")))?;
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_owned())
+    }
+
+    fn kubectl(&self) -> Command {
+        let mut cmd = Command::new("kubectl");
+        cmd.args(["--namespace", self.namespace.as_str()]);
+        cmd
+    }
+}
+
+/// bytes of Kubernetes quantity such as `10Gi` or `500M`
+fn parse_quantity(quantity: &str) -> Result<u64> {
+    const SUFFIXES: [(&str, u64); 8] = [
+        ("Ki", 1 << 10),
+        ("Mi", 1 << 20),
+        ("Gi", 1 << 30),
+        ("Ti", 1 << 40),
+        ("K", 1_000),
+        ("M", 1_000_000),
+        ("G", 1_000_000_000),
+        ("T", 1_000_000_000_000),
+    ];
+
+    let quantity = quantity.trim();
+    let (number, multiplier) = SUFFIXES
+        .iter()
+        .find_map(|(suffix, multiplier)| {
+            quantity
+                .strip_suffix(suffix)
+                .map(|number| (number, *multiplier))
+        })
+        .unwrap_or((quantity, 1));
+    let number: u64 = number
+        .parse()
+        .map_err(|_| anyhow!("invalid storage size '{quantity}', expected value such as 50Gi"))?;
+    number
+        .checked_mul(multiplier)
+        .ok_or_else(|| anyhow!("storage size '{quantity}' is too large"))
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_parse_quantity() {
+        assert_eq!(parse_quantity("10Gi").unwrap(), 10 * 1024 * 1024 * 1024);
+        assert_eq!(parse_quantity("500M").unwrap(), 500_000_000);
+        assert_eq!(parse_quantity("1024").unwrap(), 1024);
+        assert!(parse_quantity("10GB").is_err());
+        assert!(parse_quantity("Gi").is_err());
+    }
+}
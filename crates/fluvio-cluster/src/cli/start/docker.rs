@@ -0,0 +1,41 @@
+use anyhow::Result;
+use semver::Version;
+
+use crate::{DockerConfig, DockerInstaller};
+
+use super::StartOpt;
+
+/// Starts SC and SPUs as a docker compose stack and creates a matching profile
+pub async fn process_docker(opt: StartOpt, platform_version: Version) -> Result<()> {
+    let mut builder = DockerConfig::builder(platform_version);
+
+    builder
+        .spu_replicas(opt.spu)
+        .save_profile(!opt.skip_profile_creation)
+        .hide_spinner(false);
+
+    if let Some(registry) = opt.k8_config.image_registry {
+        builder.image_registry(registry);
+    }
+
+    if let Some(image_tag) = opt.k8_config.image_tag {
+        builder.image_tag(image_tag.trim());
+    }
+
+    if let Some(rust_log) = opt.rust_log {
+        builder.rust_log(rust_log);
+    }
+
+    if let Some(proxy_addr) = opt.proxy_addr {
+        builder.external_host(proxy_addr);
+    }
+
+    if let Some(data_dir) = opt.data_dir {
+        builder.compose_dir(data_dir);
+    }
+
+    let installer = DockerInstaller::from_config(builder.build()?);
+    installer.install().await?;
+
+    Ok(())
+}
@@ -34,7 +34,14 @@ pub async fn process_k8(opt: StartOpt, platform_version: Version, upgrade: bool)
         .upgrade(upgrade)
         .with_if(opt.skip_checks, |b| b.skip_checks(true))
         .use_k8_port_forwarding(opt.k8_config.use_k8_port_forwarding)
-        .use_cluster_ip(opt.k8_config.use_cluster_ip);
+        .use_cluster_ip(opt.k8_config.use_cluster_ip)
+        .validate_external_addrs(!opt.k8_config.skip_addr_validation)
+        .spu_anti_affinity(opt.k8_config.spu_anti_affinity)
+        .spu_topology_key(opt.k8_config.spu_topology_key);
+
+    if let Some(max_unavailable) = opt.k8_config.spu_max_unavailable {
+        builder.spu_max_unavailable(max_unavailable);
+    }
 
     if cfg!(target_os = "macos") {
         builder.proxy_addr(opt.proxy_addr.unwrap_or_else(|| String::from("localhost")));
@@ -46,7 +53,11 @@ pub async fn process_k8(opt: StartOpt, platform_version: Version, upgrade: bool)
         builder.local_chart(chart_location);
     }
 
-    if let Some(registry) = opt.k8_config.registry {
+    if let Some(manifests_dir) = opt.k8_config.manifests_dir {
+        builder.manifests_dir(manifests_dir);
+    }
+
+    if let Some(registry) = opt.k8_config.image_registry {
         builder.image_registry(registry);
     }
 
@@ -58,7 +69,7 @@ pub async fn process_k8(opt: StartOpt, platform_version: Version, upgrade: bool)
         builder.authorization_config_map(map);
     }
 
-    if let Some(image_tag) = opt.k8_config.image_version {
+    if let Some(image_tag) = opt.k8_config.image_tag {
         builder.image_tag(image_tag.trim());
     }
 
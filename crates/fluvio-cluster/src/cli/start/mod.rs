@@ -11,12 +11,13 @@ use fluvio_types::defaults::{TLS_SERVER_SECRET_NAME, TLS_CLIENT_SECRET_NAME};
 
 mod local;
 mod k8;
+mod docker;
 mod sys;
 mod tls;
 
 use tls::TlsOpt;
 
-use crate::InstallationType;
+use crate::{InstallationType, SpuAntiAffinity};
 
 pub fn default_log_directory() -> PathBuf {
     let base = fluvio_cli_common::install::fluvio_base_dir().unwrap_or(std::env::temp_dir());
@@ -79,13 +80,13 @@ pub struct K8Install {
     #[arg(long)]
     pub chart_version: Option<semver::Version>,
 
-    /// k8: use specific image version
-    #[arg(long)]
-    pub image_version: Option<String>,
+    /// k8: use specific image tag
+    #[arg(long, visible_alias = "image-version")]
+    pub image_tag: Option<String>,
 
-    /// k8: use custom docker registry
-    #[arg(long)]
-    pub registry: Option<String>,
+    /// k8: use custom docker registry, such as a local mirror for air-gapped installs
+    #[arg(long, visible_alias = "registry")]
+    pub image_registry: Option<String>,
 
     /// k8 namespace
     #[arg(long, default_value = "default")]
@@ -107,6 +108,13 @@ pub struct K8Install {
     #[arg(long)]
     pub chart_values: Vec<PathBuf>,
 
+    /// Install from pre-rendered manifests in this directory instead of helm charts.
+    ///
+    /// If the directory contains a kustomization.yaml, it is applied as a kustomize overlay.
+    /// Helm is not required when this option is used.
+    #[arg(long, conflicts_with_all = ["chart_location", "chart_values", "chart_version"])]
+    pub manifests_dir: Option<PathBuf>,
+
     /// Uses port forwarding for connecting to SC (only during install)
     ///
     /// For connecting to a cluster during and after install, --proxy-addr <IP or DNS> is recommended
@@ -117,6 +125,22 @@ pub struct K8Install {
     #[arg(long, hide = true)]
     use_cluster_ip: bool,
 
+    /// Skip verifying that SC and SPU external addresses resolve and accept connections after install
+    #[arg(long)]
+    skip_addr_validation: bool,
+
+    /// Spread SPU pods across topology domains: none, preferred or required
+    #[arg(long, default_value = "none")]
+    spu_anti_affinity: SpuAntiAffinity,
+
+    /// Node label defining topology domains SPUs are spread across, ex: topology.kubernetes.io/zone
+    #[arg(long, default_value = "kubernetes.io/hostname")]
+    spu_topology_key: String,
+
+    /// Create PodDisruptionBudget allowing at most this many SPUs to be evicted at once by voluntary disruptions
+    #[arg(long, value_name = "COUNT")]
+    spu_max_unavailable: Option<u16>,
+
     /// TLS: Client secret name while adding to Kubernetes
     #[arg(long, default_value = TLS_CLIENT_SECRET_NAME)]
     tls_client_secret_name: String,
@@ -212,6 +236,10 @@ pub struct IntallationTypeOpt {
     /// Start SC in read only mode
     #[arg(long, value_name = "config path")]
     read_only: Option<PathBuf>,
+
+    /// run SC and SPUs as a docker compose stack
+    #[arg(long)]
+    docker: bool,
 }
 
 impl StartOpt {
@@ -219,9 +247,12 @@ impl StartOpt {
         use crate::cli::start::local::process_local;
         use crate::cli::start::sys::process_sys;
         use crate::cli::start::k8::process_k8;
+        use crate::cli::start::docker::process_docker;
 
         if self.sys_only {
             process_sys(&self, upgrade)?;
+        } else if self.installation_type.docker {
+            process_docker(self, platform_version).await?;
         } else if self.installation_type.is_local_group() {
             process_local(self, platform_version).await?;
         } else {
@@ -238,28 +269,36 @@ impl IntallationTypeOpt {
     }
 
     pub fn get(&self) -> Option<InstallationType> {
-        match (self.local, self.local_k8, &self.read_only, &self.k8) {
-            (true, _, _, _) => Some(InstallationType::Local),
-            (_, true, _, _) => Some(InstallationType::LocalK8),
-            (_, _, Some(_), _) => Some(InstallationType::ReadOnly),
-            (_, _, _, true) => Some(InstallationType::K8),
+        match (
+            self.local,
+            self.local_k8,
+            &self.read_only,
+            &self.k8,
+            self.docker,
+        ) {
+            (true, _, _, _, _) => Some(InstallationType::Local),
+            (_, true, _, _, _) => Some(InstallationType::LocalK8),
+            (_, _, Some(_), _, _) => Some(InstallationType::ReadOnly),
+            (_, _, _, true, _) => Some(InstallationType::K8),
+            (_, _, _, _, true) => Some(InstallationType::Docker),
             _ => None,
         }
     }
 
     pub fn set(&mut self, installation_type: InstallationType) {
-        let (local, local_k8, k8, read_only) = match installation_type {
-            InstallationType::K8 => (false, false, true, None),
-            InstallationType::Local => (true, false, false, None),
-            InstallationType::LocalK8 => (false, true, false, None),
-            InstallationType::ReadOnly => (false, false, false, Some(Default::default())),
-            InstallationType::Docker => (false, false, false, None),
-            InstallationType::Cloud => (false, false, false, None),
+        let (local, local_k8, k8, read_only, docker) = match installation_type {
+            InstallationType::K8 => (false, false, true, None, false),
+            InstallationType::Local => (true, false, false, None, false),
+            InstallationType::LocalK8 => (false, true, false, None, false),
+            InstallationType::ReadOnly => (false, false, false, Some(Default::default()), false),
+            InstallationType::Docker => (false, false, false, None, true),
+            InstallationType::Cloud => (false, false, false, None, false),
         };
         self.local = local;
         self.local_k8 = local_k8;
         self.k8 = k8;
         self.read_only = read_only;
+        self.docker = docker;
     }
 
     pub fn get_or_default(&self) -> InstallationType {
@@ -279,6 +318,7 @@ mod tests {
             local_k8: Default::default(),
             k8: Default::default(),
             read_only: Default::default(),
+            docker: Default::default(),
         };
 
         //when
@@ -295,5 +335,8 @@ mod tests {
 
         opt.set(InstallationType::ReadOnly);
         assert_eq!(opt.get(), Some(InstallationType::ReadOnly));
+
+        opt.set(InstallationType::Docker);
+        assert_eq!(opt.get(), Some(InstallationType::Docker));
     }
 }
@@ -1,13 +1,19 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
 use clap::Parser;
 use colored::Colorize;
 use anyhow::{Result, anyhow};
+use futures_util::StreamExt;
 
-use fluvio::{Fluvio, FluvioAdmin, FluvioClusterConfig};
+use fluvio::{Fluvio, FluvioAdmin, FluvioClusterConfig, Offset};
 use fluvio::config::ConfigFile;
+use fluvio::consumer::ConsumerConfigExt;
 use fluvio_controlplane_metadata::partition::PartitionSpec;
 use fluvio_controlplane_metadata::{spu::SpuSpec, topic::TopicSpec};
 use fluvio_extension_common::installation::InstallationType;
-use fluvio_sc_schema::objects::Metadata;
+use fluvio_future::future::timeout;
+use fluvio_sc_schema::objects::{ListRequest, Metadata};
+use fluvio_types::canary::{CanaryRecord, canary_spu};
 use tracing::debug;
 
 use crate::CheckStatus;
@@ -16,6 +22,9 @@ use crate::render::ProgressRenderer;
 use crate::{cli::ClusterCliError, cli::ClusterTarget};
 use crate::progress::ProgressBarFactory;
 
+/// max wait for last record of canary topic, SPU may be unreachable
+const CANARY_READ_TIMEOUT: Duration = Duration::from_secs(5);
+
 #[derive(Debug, Parser)]
 pub struct StatusOpt;
 
@@ -50,6 +59,7 @@ impl StatusOpt {
         Self::check_sc(&pb, &fluvio_config, &config_file).await?;
         Self::check_spus(&pb, &fluvio_config).await?;
         Self::check_topics(&pb, &fluvio_config).await?;
+        Self::check_canary(&pb, &fluvio_config).await?;
 
         pb.finish_and_clear();
 
@@ -204,6 +214,100 @@ impl StatusOpt {
         }
     }
 
+    /// health of SPUs from last records of canary topics, nothing is reported if canary is disabled
+    async fn check_canary(
+        pb: &ProgressRenderer,
+        fluvio_config: &FluvioClusterConfig,
+    ) -> Result<()> {
+        pb.set_message(pad_format!(format!(
+            "{} Checking {}",
+            "📝".bold(),
+            "Canary"
+        )));
+
+        let admin = FluvioAdmin::connect_with_config(fluvio_config).await?;
+        let mut spus = admin
+            .list_with_config::<TopicSpec, String>(ListRequest::default().system(true))
+            .await?
+            .into_iter()
+            .filter_map(|topic| canary_spu(&topic.name).map(|spu| (spu, topic.name)))
+            .collect::<Vec<_>>();
+        if spus.is_empty() {
+            return Ok(());
+        }
+        spus.sort();
+
+        let fluvio = Fluvio::connect_with_config(fluvio_config).await?;
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_millis() as i64);
+        let mut healthy = 0;
+        let mut max_latency_ms = 0;
+        for (spu, topic) in &spus {
+            match Self::last_canary_record(&fluvio, topic).await {
+                Ok(Some(record)) if !record.is_stale(now_ms) => {
+                    healthy += 1;
+                    max_latency_ms = max_latency_ms.max(record.previous_latency_ms.unwrap_or(0));
+                }
+                Ok(Some(record)) => pb.println(pad_format!(format!(
+                    "{} Canary of SPU {} last succeeded {}s ago",
+                    "❌".bold(),
+                    spu,
+                    (now_ms - record.sent_ms) / 1000
+                ))),
+                Ok(None) => pb.println(pad_format!(format!(
+                    "{} Canary of SPU {} has no probes yet",
+                    "🟡".yellow(),
+                    spu
+                ))),
+                Err(err) => {
+                    debug!(spu, %err, "unable to read canary record");
+                    pb.println(pad_format!(format!(
+                        "{} Unable to read canary of SPU {}",
+                        "❌".bold(),
+                        spu
+                    )))
+                }
+            }
+        }
+
+        if healthy == spus.len() {
+            pb.println(pad_format!(format!(
+                "{} Canary healthy on {} SPU{}, latency up to {}ms",
+                "✅".bold(),
+                healthy,
+                if healthy == 1 { "" } else { "s" },
+                max_latency_ms
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn last_canary_record(fluvio: &Fluvio, topic: &str) -> Result<Option<CanaryRecord>> {
+        timeout(
+            CANARY_READ_TIMEOUT,
+            Self::read_last_canary_record(fluvio, topic),
+        )
+        .await
+        .map_err(|_| anyhow!("timed out after {CANARY_READ_TIMEOUT:?}"))?
+    }
+
+    async fn read_last_canary_record(fluvio: &Fluvio, topic: &str) -> Result<Option<CanaryRecord>> {
+        let mut builder = ConsumerConfigExt::builder();
+        builder
+            .topic(topic)
+            .partition(0)
+            .offset_start(Offset::from_end(1))
+            .disable_continuous(true);
+        let mut stream = fluvio.consumer_with_config(builder.build()?).await?;
+        let mut last = None;
+        while let Some(record) = stream.next().await {
+            last = Some(serde_json::from_slice(record?.value())?);
+        }
+        Ok(last)
+    }
+
     async fn total_cluster_storage(partitions: &Vec<Metadata<PartitionSpec>>) -> Result<i64> {
         let mut cluster_total = 0;
         for partition in partitions {
@@ -74,6 +74,9 @@ pub enum K8InstallError {
     /// Timed out when waiting for SPU.
     #[error("Timed out when waiting for SPU")]
     SPUTimeout,
+    /// Address advertised to clients outside Kubernetes is not usable
+    #[error("External address validation failed: {0}")]
+    ExternalAddressInvalid(String),
     /// Unable to find Fluvio SC service in Kubernetes
     #[error("Unable to detect Fluvio SC K8 service")]
     UnableToDetectService,
@@ -41,8 +41,11 @@ mod process;
 pub mod cli;
 use fluvio_helm as helm;
 
-pub use start::k8::{ClusterInstaller, ClusterConfig, ClusterConfigBuilder, DEFAULT_SPU_GROUP_NAME};
+pub use start::k8::{
+    ClusterInstaller, ClusterConfig, ClusterConfigBuilder, SpuAntiAffinity, DEFAULT_SPU_GROUP_NAME,
+};
 pub use start::local::{LocalInstaller, LocalConfig, LocalConfigBuilder};
+pub use start::docker::{DockerInstaller, DockerConfig, DockerConfigBuilder};
 pub use error::{ClusterError, K8InstallError, LocalInstallError, UninstallError};
 pub use helm::HelmError;
 pub use check::{ClusterChecker, CheckStatus, CheckStatuses, CheckResult, CheckResults};
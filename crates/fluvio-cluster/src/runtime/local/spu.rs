@@ -79,8 +79,17 @@ impl SpuTarget for LocalSpuProcess {
     }
 }
 
-const BASE_PORT: u16 = 9010;
-const BASE_SPU: u16 = 5001;
+pub(crate) const BASE_PORT: u16 = 9010;
+pub(crate) const BASE_SPU: u16 = 5001;
+
+/// public and private port of SPU, error if they are out of port range
+pub(crate) fn spu_ports(id: u16) -> AnyResult<(u16, u16)> {
+    id.checked_sub(BASE_SPU)
+        .and_then(|index| index.checked_mul(10))
+        .and_then(|offset| offset.checked_add(BASE_PORT))
+        .and_then(|public_port| Some((public_port, public_port.checked_add(1)?)))
+        .ok_or_else(|| anyhow!("no ports available for SPU {id}, too many SPUs"))
+}
 
 /// Manage SPU Process Cluster
 pub struct LocalSpuProcessClusterManager {
@@ -92,14 +101,15 @@ pub struct LocalSpuProcessClusterManager {
 }
 
 impl SpuClusterManager for LocalSpuProcessClusterManager {
-    fn create_spu_relative(&self, relative_id: u16) -> Box<dyn SpuTarget> {
-        self.create_spu_absolute(relative_id + BASE_SPU)
+    fn create_spu_relative(&self, relative_id: u16) -> AnyResult<Box<dyn SpuTarget>> {
+        let id = relative_id
+            .checked_add(BASE_SPU)
+            .ok_or_else(|| anyhow!("SPU index {relative_id} is out of range"))?;
+        self.create_spu_absolute(id)
     }
 
-    fn create_spu_absolute(&self, id: u16) -> Box<dyn SpuTarget> {
-        let spu_index = id - BASE_SPU;
-        let public_port = BASE_PORT + spu_index * 10;
-        let private_port = public_port + 1;
+    fn create_spu_absolute(&self, id: u16) -> AnyResult<Box<dyn SpuTarget>> {
+        let (public_port, private_port) = spu_ports(id)?;
         let spu_spec = SpuSpec {
             id: id as i32,
             spu_type: SpuType::Custom,
@@ -121,7 +131,7 @@ impl SpuClusterManager for LocalSpuProcessClusterManager {
 
         let spu_log_dir = format!("{}/spu_log_{}.log", self.log_dir.display(), id);
 
-        Box::new(LocalSpuProcess {
+        Ok(Box::new(LocalSpuProcess {
             id: spu_spec.id,
             spec: spu_spec,
             log_dir: spu_log_dir,
@@ -129,12 +139,20 @@ impl SpuClusterManager for LocalSpuProcessClusterManager {
             launcher: self.launcher.clone(),
             tls_policy: self.tls_policy.clone(),
             data_dir: self.data_dir.clone(),
-        })
+        }))
     }
 
     fn terminate_spu(&self, id: SpuId) -> AnyResult<()> {
+        self.signal_spu(id, "TERM")
+    }
+}
+
+impl LocalSpuProcessClusterManager {
+    /// send signal, such as `TERM` or `KILL`, to SPU process
+    pub fn signal_spu(&self, id: SpuId, signal: &str) -> AnyResult<()> {
         let kill_arg = format!("fluvio-run spu -i {id}");
         Command::new("pkill")
+            .arg(format!("-{signal}"))
             .arg("-f")
             .arg(kill_arg)
             .output()
@@ -21,11 +21,11 @@ pub mod spu {
 
     /// manages spu
     pub trait SpuClusterManager {
-        /// create new spu target
-        fn create_spu_absolute(&self, id: u16) -> Box<dyn SpuTarget>;
+        /// create new spu target, error if id is out of range of this manager
+        fn create_spu_absolute(&self, id: u16) -> Result<Box<dyn SpuTarget>>;
 
         /// create spu with relative (0) from some base
-        fn create_spu_relative(&self, id: u16) -> Box<dyn SpuTarget>;
+        fn create_spu_relative(&self, id: u16) -> Result<Box<dyn SpuTarget>>;
 
         fn terminate_spu(&self, id: SpuId) -> Result<()>;
     }
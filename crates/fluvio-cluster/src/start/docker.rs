@@ -0,0 +1,411 @@
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Result, anyhow};
+use derive_builder::Builder;
+use once_cell::sync::Lazy;
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, instrument};
+
+use fluvio::{Fluvio, FluvioClusterConfig};
+use fluvio::config::{ConfigFile, TlsPolicy};
+use fluvio_command::CommandExt;
+use fluvio_controlplane_metadata::spu::{CustomSpuSpec, Endpoint, IngressAddr, IngressPort};
+use fluvio_future::timer::sleep;
+use fluvio_types::defaults::{SC_PUBLIC_PORT, SC_PRIVATE_PORT};
+
+use crate::{InstallationType, LocalInstallError, StartStatus};
+use crate::progress::{InstallProgressMessage, ProgressBarFactory};
+use crate::render::{ProgressRenderedText, ProgressRenderer};
+use crate::runtime::local::{BASE_SPU, spu_ports};
+
+use super::common::try_connect_to_sc;
+use super::constants::MAX_PROVISION_TIME_SEC;
+
+/// Profile created for docker installations
+pub const DOCKER_PROFILE: &str = "docker";
+
+pub static DEFAULT_COMPOSE_DIR: Lazy<Option<PathBuf>> =
+    Lazy::new(|| directories::BaseDirs::new().map(|it| it.home_dir().join(".fluvio/docker")));
+
+const COMPOSE_FILE: &str = "docker-compose.yml";
+const DEFAULT_REGISTRY: &str = "infinyon";
+const DEFAULT_PROJECT: &str = "fluvio";
+const DEFAULT_RUST_LOG: &str = "info";
+const DOCKER_METADATA_NAME: &str = "docker";
+
+/// Location of the compose stack, saved in the profile so delete can find it
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DockerMetadata {
+    compose_dir: PathBuf,
+    project_name: String,
+}
+
+/// Describes how to run Fluvio with docker compose
+#[derive(Builder, Debug, Clone)]
+#[builder(build_fn(private, name = "build_impl"))]
+pub struct DockerConfig {
+    /// Platform version
+    #[builder(setter(into))]
+    platform_version: Version,
+
+    /// Docker registry to pull Fluvio images from
+    #[builder(setter(into), default = "DEFAULT_REGISTRY.to_string()")]
+    image_registry: String,
+
+    /// Image tag, defaults to the platform version
+    #[builder(setter(into, strip_option), default)]
+    image_tag: Option<String>,
+
+    /// Number of SPUs to run
+    #[builder(default = "1")]
+    spu_replicas: u16,
+
+    /// Host name under which published ports are reachable by clients
+    #[builder(setter(into), default = "\"localhost\".to_string()")]
+    external_host: String,
+
+    /// Directory where the compose file is written
+    #[builder(
+        setter(into),
+        default = "DEFAULT_COMPOSE_DIR.clone().unwrap_or_default()"
+    )]
+    compose_dir: PathBuf,
+
+    /// Docker compose project name
+    #[builder(setter(into), default = "DEFAULT_PROJECT.to_string()")]
+    project_name: String,
+
+    /// Sets the [`RUST_LOG`] environment variable for SC and SPUs
+    #[builder(setter(into), default = "DEFAULT_RUST_LOG.to_string()")]
+    rust_log: String,
+
+    /// Whether to save a `docker` profile of this installation to `~/.fluvio/config`
+    #[builder(default = "true")]
+    save_profile: bool,
+
+    /// Used to hide spinner animation for progress updates
+    #[builder(default = "true")]
+    hide_spinner: bool,
+}
+
+impl DockerConfig {
+    pub fn builder(platform_version: Version) -> DockerConfigBuilder {
+        let mut builder = DockerConfigBuilder::default();
+        builder.platform_version(platform_version);
+        builder
+    }
+
+    fn image(&self) -> String {
+        let tag = self
+            .image_tag
+            .clone()
+            .unwrap_or_else(|| self.platform_version.to_string());
+        format!("{}/fluvio:{}", self.image_registry, tag)
+    }
+
+    fn spu_ids(&self) -> impl Iterator<Item = u16> {
+        BASE_SPU..BASE_SPU.saturating_add(self.spu_replicas)
+    }
+
+    fn sc_pub_addr(&self) -> String {
+        format!("{}:{SC_PUBLIC_PORT}", self.external_host)
+    }
+
+    fn compose_path(&self) -> PathBuf {
+        self.compose_dir.join(COMPOSE_FILE)
+    }
+
+    /// Renders the docker compose stack for SC and SPUs
+    pub fn render_compose(&self) -> Result<String> {
+        let image = self.image();
+        let mut out = String::new();
+        let _ = writeln!(out, "name: {}", self.project_name);
+        let _ = writeln!(out, "services:");
+        let _ = writeln!(out, "  sc:");
+        let _ = writeln!(out, "    image: {image}");
+        let _ = writeln!(
+            out,
+            "    command: [\"./fluvio-run\", \"sc\", \"--local\", \"/fluvio/metadata\"]"
+        );
+        let _ = writeln!(out, "    environment:");
+        let _ = writeln!(out, "      RUST_LOG: {}", self.rust_log);
+        let _ = writeln!(out, "    ports:");
+        let _ = writeln!(out, "      - \"{SC_PUBLIC_PORT}:{SC_PUBLIC_PORT}\"");
+        let _ = writeln!(out, "    volumes:");
+        let _ = writeln!(out, "      - sc-metadata:/fluvio/metadata");
+
+        for id in self.spu_ids() {
+            let (public_port, private_port) = spu_ports(id)?;
+            let _ = writeln!(out, "  spu-{id}:");
+            let _ = writeln!(out, "    image: {image}");
+            let _ = writeln!(
+                out,
+                "    command: [\"./fluvio-run\", \"spu\", \"-i\", \"{id}\", \"-p\", \"0.0.0.0:{public_port}\", \"-v\", \"0.0.0.0:{private_port}\", \"--sc-addr\", \"sc:{SC_PRIVATE_PORT}\", \"--log-base-dir\", \"/fluvio/data\"]"
+            );
+            let _ = writeln!(out, "    environment:");
+            let _ = writeln!(out, "      RUST_LOG: {}", self.rust_log);
+            let _ = writeln!(out, "    ports:");
+            let _ = writeln!(out, "      - \"{public_port}:{public_port}\"");
+            let _ = writeln!(out, "    volumes:");
+            let _ = writeln!(out, "      - spu-{id}-data:/fluvio/data");
+            let _ = writeln!(out, "    depends_on:");
+            let _ = writeln!(out, "      - sc");
+        }
+
+        let _ = writeln!(out, "volumes:");
+        let _ = writeln!(out, "  sc-metadata:");
+        for id in self.spu_ids() {
+            let _ = writeln!(out, "  spu-{id}-data:");
+        }
+        Ok(out)
+    }
+
+    /// Custom SPU registrations matching the compose stack
+    fn spu_specs(&self) -> Result<Vec<(String, CustomSpuSpec)>> {
+        self.spu_ids()
+            .map(|id| {
+                let (public_port, private_port) = spu_ports(id)?;
+                let spec = CustomSpuSpec {
+                    id: id as i32,
+                    public_endpoint: IngressPort {
+                        port: public_port,
+                        ingress: vec![IngressAddr::from_host(self.external_host.clone())],
+                        ..Default::default()
+                    },
+                    private_endpoint: Endpoint {
+                        port: private_port,
+                        host: format!("spu-{id}"),
+                        ..Default::default()
+                    },
+                    public_endpoint_local: Some(Endpoint {
+                        port: public_port,
+                        host: format!("spu-{id}"),
+                        ..Default::default()
+                    }),
+                    rack: None,
+                };
+                Ok((format!("custom-spu-{id}"), spec))
+            })
+            .collect()
+    }
+}
+
+impl DockerConfigBuilder {
+    pub fn build(&self) -> Result<DockerConfig> {
+        let config = self
+            .build_impl()
+            .map_err(|err| LocalInstallError::MissingRequiredConfig(err.to_string()))?;
+        Ok(config)
+    }
+
+    /// Applies the compose location saved in the profile by a previous install
+    pub fn with_cluster_config(&mut self, cluster: &FluvioClusterConfig) -> &mut Self {
+        if let Some(metadata) =
+            cluster.query_metadata_by_name::<DockerMetadata>(DOCKER_METADATA_NAME)
+        {
+            self.compose_dir(metadata.compose_dir);
+            self.project_name(metadata.project_name);
+        }
+        self
+    }
+}
+
+/// Runs a Fluvio cluster with docker compose, for users without Kubernetes
+#[derive(Debug)]
+pub struct DockerInstaller {
+    config: DockerConfig,
+    pb_factory: ProgressBarFactory,
+}
+
+impl DockerInstaller {
+    pub fn from_config(config: DockerConfig) -> Self {
+        Self {
+            pb_factory: ProgressBarFactory::new(config.hide_spinner),
+            config,
+        }
+    }
+
+    /// Writes the compose file, starts the stack and registers SPUs
+    #[instrument(skip(self))]
+    pub async fn install(&self) -> Result<StartStatus> {
+        let pb = self.pb_factory.create()?;
+
+        pb.set_message("Writing docker compose file");
+        std::fs::create_dir_all(&self.config.compose_dir)?;
+        let compose_path = self.config.compose_path();
+        std::fs::write(&compose_path, self.config.render_compose()?)?;
+        debug!(path = %compose_path.display(), "compose file written");
+
+        pb.set_message(InstallProgressMessage::LaunchingSC.msg());
+        self.compose(&["up", "-d"])?;
+        pb.println(format!(
+            "✅ Docker compose stack started: {}",
+            compose_path.display()
+        ));
+
+        if self.config.save_profile {
+            self.set_profile()?;
+        }
+
+        let cluster_config = FluvioClusterConfig::new(self.config.sc_pub_addr());
+        let fluvio = try_connect_to_sc(&cluster_config, &self.config.platform_version, &pb)
+            .await
+            .ok_or(LocalInstallError::SCServiceTimeout)?;
+        pb.println(InstallProgressMessage::ScLaunched.msg());
+
+        self.register_spus(&fluvio, &pb).await?;
+        self.confirm_spu(&fluvio, &pb).await?;
+        pb.println(format!("✅ {} SPU launched", self.config.spu_replicas));
+        pb.finish_and_clear();
+
+        self.pb_factory
+            .println("🎯 Successfully installed Fluvio cluster on docker");
+
+        Ok(StartStatus {
+            address: self.config.external_host.clone(),
+            port: SC_PUBLIC_PORT,
+        })
+    }
+
+    /// Stops the stack and removes its volumes
+    pub fn uninstall(&self) -> Result<()> {
+        self.compose(&["down", "--volumes"])
+    }
+
+    fn compose(&self, args: &[&str]) -> Result<()> {
+        Command::new("docker")
+            .arg("compose")
+            .arg("-p")
+            .arg(&self.config.project_name)
+            .arg("-f")
+            .arg(self.config.compose_path())
+            .args(args)
+            .inherit()
+            .result()
+            .map_err(|err| anyhow!("docker compose failed: {err}"))?;
+        Ok(())
+    }
+
+    fn set_profile(&self) -> Result<()> {
+        let mut config_file = ConfigFile::load_default_or_new_for_update()?;
+        config_file.add_or_replace_profile(
+            DOCKER_PROFILE,
+            &self.config.sc_pub_addr(),
+            &TlsPolicy::Disabled,
+        )?;
+        let config = config_file.mut_config().current_cluster_mut()?;
+        InstallationType::Docker.save_to(config)?;
+        config.update_metadata_by_name(
+            DOCKER_METADATA_NAME,
+            DockerMetadata {
+                compose_dir: self.config.compose_dir.clone(),
+                project_name: self.config.project_name.clone(),
+            },
+        )?;
+        config_file.save()?;
+
+        self.pb_factory
+            .println(InstallProgressMessage::ProfileSet.msg());
+        Ok(())
+    }
+
+    async fn register_spus(&self, fluvio: &Fluvio, pb: &ProgressRenderer) -> Result<()> {
+        let admin = fluvio.admin().await;
+        let specs = self.config.spu_specs()?;
+        let total = specs.len() as u16;
+        for (index, (name, spec)) in specs.into_iter().enumerate() {
+            pb.set_message(InstallProgressMessage::StartSPU(index as u16 + 1, total).msg());
+            if admin
+                .list::<CustomSpuSpec, _>(vec![name.clone()])
+                .await?
+                .is_empty()
+            {
+                debug!(name, "create custom spu");
+                admin.create(name, false, spec).await?;
+            } else {
+                debug!(name, "custom spu already exists");
+            }
+        }
+        Ok(())
+    }
+
+    async fn confirm_spu(&self, fluvio: &Fluvio, pb: &ProgressRenderer) -> Result<()> {
+        use fluvio_controlplane_metadata::spu::SpuSpec;
+
+        let admin = fluvio.admin().await;
+        let timeout_duration = Duration::from_secs(*MAX_PROVISION_TIME_SEC as u64);
+        let time = SystemTime::now();
+        let expected = self.config.spu_replicas as usize;
+
+        while time.elapsed()? < timeout_duration {
+            let online = admin
+                .all::<SpuSpec>()
+                .await?
+                .iter()
+                .filter(|spu| spu.status.is_online())
+                .count();
+
+            pb.set_message(format!("🖥️ {online}/{expected} SPU confirmed"));
+            if online >= expected {
+                return Ok(());
+            }
+            sleep(Duration::from_secs(1)).await;
+        }
+
+        Err(LocalInstallError::SPUTimeout.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_compose() {
+        let config = DockerConfig::builder(Version::parse("0.11.0").unwrap())
+            .spu_replicas(2)
+            .compose_dir("/tmp/fluvio-docker")
+            .build()
+            .expect("config");
+
+        let compose = config.render_compose().expect("compose");
+        assert!(compose.contains("image: infinyon/fluvio:0.11.0"));
+        assert!(compose.contains("  spu-5001:"));
+        assert!(compose.contains("  spu-5002:"));
+        assert!(compose.contains("\"9020:9020\""));
+        assert!(compose.contains("\"--sc-addr\", \"sc:9004\""));
+
+        let specs = config.spu_specs().expect("specs");
+        assert_eq!(specs.len(), 2);
+        assert_eq!(specs[1].1.private_endpoint.host, "spu-5002");
+    }
+
+    #[test]
+    fn test_compose_location_from_profile() {
+        let mut cluster = FluvioClusterConfig::new("localhost:9003");
+        cluster
+            .update_metadata_by_name(
+                DOCKER_METADATA_NAME,
+                DockerMetadata {
+                    compose_dir: PathBuf::from("/opt/fluvio-docker"),
+                    project_name: "custom".to_string(),
+                },
+            )
+            .expect("metadata");
+
+        let config = DockerConfig::builder(Version::parse("0.11.0").unwrap())
+            .with_cluster_config(&cluster)
+            .build()
+            .expect("config");
+        assert_eq!(
+            config.compose_path(),
+            PathBuf::from("/opt/fluvio-docker/docker-compose.yml")
+        );
+        assert_eq!(config.project_name, "custom");
+    }
+}
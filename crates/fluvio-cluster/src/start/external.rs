@@ -0,0 +1,273 @@
+//! Validation of SC and SPU addresses advertised to clients outside Kubernetes
+
+use std::env;
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+use tracing::{debug, instrument, warn};
+
+use fluvio_future::retry::{retry, ExponentialBackoff, RetryExt};
+use fluvio_socket::{ClientConfig, FluvioSocket};
+
+use crate::render::ProgressRenderer;
+
+/// maximum number of attempts to validate an external address
+static MAX_ADDR_CHECK_LOOP: Lazy<usize> = Lazy::new(|| {
+    let var_value = env::var("FLV_CLUSTER_MAX_ADDR_CHECK_LOOP").unwrap_or_default();
+    var_value.parse().unwrap_or(30)
+});
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Why clients can't use an advertised address
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum AddrProblem {
+    /// not in `host:port` form
+    Malformed,
+    /// host doesn't resolve
+    Unresolved(String),
+    /// host resolves, but no TCP connection can be made
+    Unreachable {
+        resolved: Vec<SocketAddr>,
+        reason: String,
+    },
+    /// TCP connection is accepted, but TLS or Fluvio handshake fails
+    Handshake(String),
+}
+
+/// Install settings which are likely cause of a bad external address
+#[derive(Debug)]
+pub(crate) struct AddrHints<'a> {
+    pub proxy_addr: Option<&'a str>,
+    pub service_type: &'a str,
+    /// domain server certs are verified against, None if TLS is not verified
+    pub tls_domain: Option<&'a str>,
+}
+
+impl AddrHints<'_> {
+    /// explain what is wrong with `addr` advertised by `name` and how to fix it.
+    /// `sni_prefix` is prepended to TLS domain, as done for SPUs
+    pub(crate) fn diagnose(
+        &self,
+        name: &str,
+        addr: &str,
+        sni_prefix: Option<&str>,
+        problem: &AddrProblem,
+    ) -> String {
+        match problem {
+            AddrProblem::Malformed => {
+                format!("{name} advertises '{addr}' which is not a valid host:port address")
+            }
+            AddrProblem::Unresolved(reason) => {
+                let fix = match self.proxy_addr {
+                    Some(proxy) => format!(
+                        "check that --proxy-addr '{proxy}' is a DNS name or IP address resolvable from this machine"
+                    ),
+                    None => "wait for DNS record of load balancer to propagate, \
+                             or pass --proxy-addr <IP or DNS>"
+                        .to_string(),
+                };
+                format!(
+                    "{name} advertises '{addr}' but its host does not resolve: {reason}. Fix: {fix}"
+                )
+            }
+            AddrProblem::Unreachable { resolved, reason } => {
+                let ips: Vec<String> = resolved.iter().map(|ip| ip.to_string()).collect();
+                let fix = if self.service_type == "NodePort" {
+                    "check that --proxy-addr points to a Kubernetes node \
+                     and firewall allows NodePort range"
+                        .to_string()
+                } else {
+                    format!(
+                        "check firewall rules and that {} service exposes the port",
+                        self.service_type
+                    )
+                };
+                format!(
+                    "{name} advertises '{addr}' which resolves to {} but refuses connections: {reason}. Fix: {fix}",
+                    ips.join(", ")
+                )
+            }
+            AddrProblem::Handshake(reason) => match self.tls_domain {
+                Some(domain) => {
+                    let domain = match sni_prefix {
+                        Some(prefix) => format!("{prefix}.{domain}"),
+                        None => domain.to_string(),
+                    };
+                    format!(
+                        "{name} at '{addr}' accepts connections but TLS handshake failed: {reason}. Fix: server certificate must be valid for '{domain}' and signed by CA of client certificate"
+                    )
+                }
+                None => format!(
+                    "{name} at '{addr}' accepts connections but is not responding as Fluvio server: {reason}. Fix: check that address forwards to Fluvio service port and TLS settings match server"
+                ),
+            },
+        }
+    }
+}
+
+/// Checks that `addr` resolves, accepts connections and completes TLS and Fluvio handshake,
+/// retrying with exponential backoff while DNS and load balancers converge
+#[instrument(skip(config, pb))]
+pub(crate) async fn validate_external_addr(
+    name: &str,
+    addr: &str,
+    config: &ClientConfig,
+    pb: &ProgressRenderer,
+) -> Result<(), AddrProblem> {
+    let (host, port) = split_host_port(addr).ok_or(AddrProblem::Malformed)?;
+
+    let mut attempt = 0u16;
+    let operation = || {
+        attempt += 1;
+        pb.set_message(format!(
+            "🔎 Validating {name} external address: {addr}, attempt: {attempt}"
+        ));
+        async move {
+            let result = check_addr(host, port, addr, config).await;
+            if let Err(problem) = &result {
+                warn!(addr, ?problem, "external address not usable yet");
+            }
+            result
+        }
+    };
+
+    retry(
+        ExponentialBackoff::from_millis(2)
+            .max_delay(Duration::from_secs(10))
+            .take(*MAX_ADDR_CHECK_LOOP),
+        operation,
+    )
+    .await
+}
+
+async fn check_addr(
+    host: &str,
+    port: u16,
+    addr: &str,
+    config: &ClientConfig,
+) -> Result<(), AddrProblem> {
+    let resolved: Vec<SocketAddr> = (host, port)
+        .to_socket_addrs()
+        .map_err(|err| AddrProblem::Unresolved(err.to_string()))?
+        .collect();
+    if resolved.is_empty() {
+        return Err(AddrProblem::Unresolved("no address records".to_string()));
+    }
+    debug!(addr, ?resolved, "resolved external address");
+
+    let mut reason = String::new();
+    let mut reachable = false;
+    for socket_addr in &resolved {
+        match FluvioSocket::connect(&socket_addr.to_string())
+            .timeout(CONNECT_TIMEOUT)
+            .await
+        {
+            Ok(Ok(_)) => {
+                reachable = true;
+                break;
+            }
+            Ok(Err(err)) => reason = err.to_string(),
+            Err(_) => reason = format!("timed out after {}s", CONNECT_TIMEOUT.as_secs()),
+        }
+    }
+    if !reachable {
+        return Err(AddrProblem::Unreachable { resolved, reason });
+    }
+
+    let mut config = config.recreate();
+    config.set_addr(addr.to_owned());
+    match config.connect().timeout(CONNECT_TIMEOUT).await {
+        Ok(Ok(_)) => Ok(()),
+        Ok(Err(err)) => Err(AddrProblem::Handshake(err.to_string())),
+        Err(_) => Err(AddrProblem::Handshake(format!(
+            "timed out after {}s",
+            CONNECT_TIMEOUT.as_secs()
+        ))),
+    }
+}
+
+fn split_host_port(addr: &str) -> Option<(&str, u16)> {
+    let (host, port) = addr.rsplit_once(':')?;
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    if host.is_empty() {
+        return None;
+    }
+    Some((host, port.parse().ok()?))
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_split_host_port() {
+        assert_eq!(
+            split_host_port("fluvio.example.com:9003"),
+            Some(("fluvio.example.com", 9003))
+        );
+        assert_eq!(split_host_port("[::1]:9005"), Some(("::1", 9005)));
+        assert_eq!(split_host_port("fluvio.example.com"), None);
+        assert_eq!(split_host_port(":9003"), None);
+        assert_eq!(split_host_port("localhost:port"), None);
+    }
+
+    #[test]
+    fn test_diagnose() {
+        let hints = AddrHints {
+            proxy_addr: Some("fluvio.example.com"),
+            service_type: "NodePort",
+            tls_domain: Some("fluvio.local"),
+        };
+
+        let msg = hints.diagnose(
+            "SPU 0",
+            "fluvio.example.com:30004",
+            Some("fluvio-spg-main-0"),
+            &AddrProblem::Unresolved("not found".to_string()),
+        );
+        assert!(msg.contains("--proxy-addr 'fluvio.example.com'"));
+
+        let msg = hints.diagnose(
+            "SPU 0",
+            "fluvio.example.com:30004",
+            Some("fluvio-spg-main-0"),
+            &AddrProblem::Unreachable {
+                resolved: vec!["10.0.0.1:30004".parse().unwrap()],
+                reason: "connection refused".to_string(),
+            },
+        );
+        assert!(msg.contains("10.0.0.1:30004"));
+        assert!(msg.contains("NodePort range"));
+
+        let msg = hints.diagnose(
+            "SPU 0",
+            "fluvio.example.com:30004",
+            Some("fluvio-spg-main-0"),
+            &AddrProblem::Handshake("certificate verify failed".to_string()),
+        );
+        assert!(msg.contains("'fluvio-spg-main-0.fluvio.local'"));
+
+        let hints = AddrHints {
+            proxy_addr: None,
+            service_type: "LoadBalancer",
+            tls_domain: None,
+        };
+        let msg = hints.diagnose(
+            "SC",
+            "lb.example.com:9003",
+            None,
+            &AddrProblem::Unresolved("not found".to_string()),
+        );
+        assert!(msg.contains("pass --proxy-addr"));
+        let msg = hints.diagnose(
+            "SC",
+            "lb.example.com:9003",
+            None,
+            &AddrProblem::Handshake("unexpected eof".to_string()),
+        );
+        assert!(msg.contains("not responding as Fluvio server"));
+    }
+}
@@ -4,7 +4,7 @@ use std::io::ErrorKind;
 use std::collections::BTreeMap;
 use std::io::Read;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::borrow::Cow;
 use std::process::Child;
 use std::process::Command;
@@ -25,6 +25,7 @@ use fluvio_controlplane_metadata::spg::SpuConfig;
 use fluvio_sc_schema::objects::CommonCreateRequest;
 use fluvio_types::defaults::TLS_CLIENT_SECRET_NAME;
 use fluvio_types::defaults::TLS_SERVER_SECRET_NAME;
+use fluvio_types::defaults::SPU_DEFAULT_NAME;
 use k8_client::SharedK8Client;
 use k8_client::load_and_share;
 use k8_types::K8Obj;
@@ -39,6 +40,7 @@ use k8_client::meta_client::MetadataClient;
 use k8_types::core::service::{LoadBalancerType, ServiceSpec, TargetPort};
 use k8_types::core::node::{NodeSpec, NodeAddress};
 use fluvio_command::CommandExt;
+use fluvio_socket::ClientConfig;
 
 use crate::InstallationType;
 use crate::check::{AlreadyInstalled, SysChartCheck};
@@ -49,16 +51,18 @@ use crate::render::ProgressRenderer;
 use crate::start::common::check_crd;
 use crate::tls_config_to_cert_paths;
 use crate::{StartStatus, DEFAULT_NAMESPACE, ClusterChecker};
-use crate::charts::{ChartConfig, ChartInstaller};
+use crate::charts::{ChartConfig, ChartInstaller, ManifestsInstaller};
 use crate::UserChartLocation;
 use crate::progress::InstallProgressMessage;
 
 use super::constants::*;
 use super::common::try_connect_to_sc;
+use super::external::{AddrHints, validate_external_addr};
 
 pub const DEFAULT_SPU_GROUP_NAME: &str = "main";
 const DEFAULT_REGISTRY: &str = "infinyon";
 const DEFAULT_SERVICE_TYPE: &str = "NodePort";
+const DEFAULT_SPU_TOPOLOGY_KEY: &str = "kubernetes.io/hostname";
 
 const FLUVIO_SC_SERVICE: &str = "fluvio-sc-public";
 /// maximum time waiting for sc service to come up
@@ -277,6 +281,13 @@ pub struct ClusterConfig {
     #[builder(setter(into), default)]
     proxy_addr: Option<String>,
 
+    /// Whether to verify that SC and SPU external addresses resolve and accept
+    /// connections after install. Defaults to `true`.
+    ///
+    /// Addresses are not validated when port forwarding or cluster IP is used.
+    #[builder(default = "true")]
+    validate_external_addrs: bool,
+
     #[builder(setter(into), default = "TLS_SERVER_SECRET_NAME.to_string()")]
     tls_server_secret_name: String,
 
@@ -285,6 +296,83 @@ pub struct ClusterConfig {
 
     #[builder(setter(into), default)]
     default_spu_group: Option<DefaultSpuGroup>,
+
+    /// Whether SPU pods avoid being scheduled in the same topology domain. Defaults to none.
+    ///
+    /// # Example
+    ///
+    /// Spread SPUs across availability zones, so a zone outage does not take out all replicas
+    ///
+    /// ```
+    /// # use fluvio_cluster::{ClusterConfig, ClusterConfigBuilder, SpuAntiAffinity};
+    /// # fn example(builder: &mut ClusterConfigBuilder) -> anyhow::Result<()> {
+    /// let config = builder
+    ///     .spu_anti_affinity(SpuAntiAffinity::Required)
+    ///     .spu_topology_key("topology.kubernetes.io/zone")
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[builder(default)]
+    spu_anti_affinity: SpuAntiAffinity,
+
+    /// Node label defining topology domains SPUs are spread across.
+    /// Defaults to `kubernetes.io/hostname`, one domain per node.
+    #[builder(setter(into), default = "DEFAULT_SPU_TOPOLOGY_KEY.to_string()")]
+    spu_topology_key: String,
+
+    /// Creates a PodDisruptionBudget allowing at most this many SPUs to be
+    /// voluntarily evicted at once, such as during node drains.
+    #[builder(setter(into, strip_option), default)]
+    spu_max_unavailable: Option<u16>,
+
+    /// Installs from pre-rendered manifests in this directory instead of helm charts.
+    ///
+    /// If the directory contains a `kustomization.yaml`, it is applied as a kustomize
+    /// overlay. Helm is not required at runtime when this is set, and chart-specific
+    /// options such as `chart_values` or `image_registry` are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use fluvio_cluster::{ClusterConfig, ClusterConfigBuilder, ClusterError};
+    /// # fn example(builder: &mut ClusterConfigBuilder) -> anyhow::Result<()> {
+    /// let config = builder
+    ///     .manifests_dir("./k8-util/helm/manifests")
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[builder(setter(into, strip_option), default)]
+    manifests_dir: Option<PathBuf>,
+}
+
+/// Anti-affinity between SPU pods
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SpuAntiAffinity {
+    /// SPUs may be scheduled in same topology domain
+    #[default]
+    None,
+    /// scheduler spreads SPUs across topology domains when possible
+    Preferred,
+    /// SPUs are never scheduled in same topology domain,
+    /// SPUs stay pending if there are fewer domains than SPUs
+    Required,
+}
+
+impl std::str::FromStr for SpuAntiAffinity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Self::None),
+            "preferred" => Ok(Self::Preferred),
+            "required" => Ok(Self::Required),
+            other => Err(format!(
+                "invalid anti-affinity '{other}', expected one of: none, preferred, required"
+            )),
+        }
+    }
 }
 
 /// Controls SPG creation during installation
@@ -316,6 +404,70 @@ impl ClusterConfig {
         builder.platform_version(platform_version);
         builder
     }
+
+    /// Container images pulled by the cluster, with registry and tag applied.
+    ///
+    /// These must be available in the registry for air-gapped installs.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use fluvio_cluster::ClusterConfig;
+    /// use semver::Version;
+    /// let config = ClusterConfig::builder(Version::parse("0.11.0").unwrap())
+    ///     .image_registry("registry.local:5000/infinyon")
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(config.images(), vec!["registry.local:5000/infinyon/fluvio:0.11.0"]);
+    /// ```
+    pub fn images(&self) -> Vec<String> {
+        let tag = self
+            .image_tag
+            .clone()
+            .unwrap_or_else(|| self.platform_version.to_string());
+        // SC and SPUs run from the same image
+        vec![format!("{}/fluvio:{}", self.image_registry, tag)]
+    }
+
+    /// helm values for SPU pod anti-affinity and disruption budget, None if neither is set
+    fn spu_scheduling_values(&self) -> Option<serde_json::Value> {
+        let term = serde_json::json!({
+            "labelSelector": { "matchLabels": { "app": SPU_DEFAULT_NAME } },
+            "topologyKey": self.spu_topology_key,
+        });
+        let affinity = match self.spu_anti_affinity {
+            SpuAntiAffinity::None => None,
+            SpuAntiAffinity::Preferred => Some(serde_json::json!({
+                "podAntiAffinity": {
+                    "preferredDuringSchedulingIgnoredDuringExecution": [
+                        { "weight": 100, "podAffinityTerm": term }
+                    ]
+                }
+            })),
+            SpuAntiAffinity::Required => Some(serde_json::json!({
+                "podAntiAffinity": {
+                    "requiredDuringSchedulingIgnoredDuringExecution": [term]
+                }
+            })),
+        };
+
+        let mut spu_pod = serde_json::Map::new();
+        if let Some(affinity) = affinity {
+            spu_pod.insert("affinity".to_owned(), affinity);
+        }
+        if let Some(max_unavailable) = self.spu_max_unavailable {
+            spu_pod.insert(
+                "disruptionBudget".to_owned(),
+                serde_json::json!({ "maxUnavailable": max_unavailable }),
+            );
+        }
+
+        if spu_pod.is_empty() {
+            None
+        } else {
+            Some(serde_json::json!({ "spuPod": spu_pod }))
+        }
+    }
 }
 
 impl ClusterConfigBuilder {
@@ -604,6 +756,17 @@ impl ClusterInstaller {
             }
         }
 
+        if self.config.manifests_dir.is_some() {
+            // manifests carry their own CRDs and there is no helm release to check
+            self.pb_factory
+                .println(InstallProgressMessage::PreFlightCheck.msg());
+            ClusterChecker::empty()
+                .with_manifests_checks()
+                .run(&self.pb_factory, fix)
+                .await?;
+            return Ok(());
+        }
+
         let mut checker = ClusterChecker::empty().with_k8_checks();
 
         if self.config.install_sys {
@@ -646,7 +809,11 @@ impl ClusterInstaller {
             self.preflight_check(true).await?;
         }
 
-        self.install_app().await?;
+        if let Some(manifests_dir) = &self.config.manifests_dir {
+            self.install_manifests(manifests_dir)?;
+        } else {
+            self.install_app().await?;
+        }
 
         // before we do let's try make sure SPU are installed.
         check_crd(self.kube_client.clone()).await?;
@@ -673,6 +840,23 @@ impl ClusterInstaller {
             (external_host_and_port.clone(), None)
         };
 
+        // addresses clients will use after install, only reachable without port forwarding
+        let external_client_config: Option<ClientConfig> = if self.config.validate_external_addrs
+            && !self.config.use_k8_port_forwarding
+            && !self.config.use_cluster_ip
+        {
+            let config = FluvioClusterConfig::new(external_host_and_port.clone())
+                .with_tls(self.config.client_tls_policy.clone())
+                .try_into()?;
+            Some(config)
+        } else {
+            None
+        };
+        if let Some(config) = &external_client_config {
+            self.check_external_addr("SC", &external_host_and_port, None, config, &pb)
+                .await?;
+        }
+
         let cluster_config = FluvioClusterConfig::new(install_host_and_port.clone())
             .with_tls(self.config.client_tls_policy.clone());
         pb.set_message("🔎 Discovering Fluvio SC");
@@ -688,6 +872,21 @@ impl ClusterInstaller {
             Self::create_managed_spu_group(default_spu_group, &fluvio, &pb).await?;
         }
 
+        if let Some(external_client_config) = &external_client_config {
+            let spus = fluvio.admin().await.all::<SpuSpec>().await?;
+            for spu in spus {
+                let config = external_client_config.with_prefix_sni_domain(&spu.name);
+                self.check_external_addr(
+                    &format!("SPU {}", spu.spec.id),
+                    &spu.spec.public_endpoint.addr(),
+                    Some(&spu.name),
+                    &config,
+                    &pb,
+                )
+                .await?;
+            }
+        }
+
         if let Some(mut pf_process) = pf_process {
             match pf_process.kill() {
                 Ok(_) => info!("Port forwarding process exited normally"),
@@ -705,6 +904,37 @@ impl ClusterInstaller {
         })
     }
 
+    /// Validates address advertised to clients, failing with diagnosis of misconfiguration
+    async fn check_external_addr(
+        &self,
+        name: &str,
+        addr: &str,
+        sni_prefix: Option<&str>,
+        config: &ClientConfig,
+        pb: &ProgressRenderer,
+    ) -> Result<()> {
+        match validate_external_addr(name, addr, config, pb).await {
+            Ok(()) => {
+                pb.println(format!("✅ {name} reachable at external address: {addr}"));
+                Ok(())
+            }
+            Err(problem) => {
+                let tls_domain = match &self.config.client_tls_policy {
+                    TlsPolicy::Verified(tls) => Some(tls.domain()),
+                    _ => None,
+                };
+                let hints = AddrHints {
+                    proxy_addr: self.config.proxy_addr.as_deref(),
+                    service_type: &self.config.service_type,
+                    tls_domain,
+                };
+                let diagnosis = hints.diagnose(name, addr, sni_prefix, &problem);
+                pb.println(format!("❌ {diagnosis}"));
+                Err(K8InstallError::ExternalAddressInvalid(diagnosis).into())
+            }
+        }
+    }
+
     /// Install Fluvio Core chart on the configured cluster
     #[instrument(skip(self))]
     async fn install_app(&self) -> Result<()> {
@@ -840,6 +1070,20 @@ impl ClusterInstaller {
 
         debug!("Using helm install settings: {:#?}", &install_settings);
 
+        // written to a values file, since helm --set can't express lists of objects
+        let _scheduling_file = if let Some(values) = self.config.spu_scheduling_values() {
+            let (file, path) = NamedTempFile::new()?.into_parts();
+            let values = serde_yaml::to_string(&values)
+                .map_err(|e| anyhow!("couldn't serialize helm yaml {e:?}"))?;
+            debug!(%values, "spu scheduling values");
+            write!(&file, "{values}")
+                .map_err(|e| anyhow!("Error writing helm values file\n{e}"))?;
+            chart_values.push(path.to_path_buf());
+            Some((file, path))
+        } else {
+            None
+        };
+
         chart_values.append(&mut self.config.chart_values.clone());
 
         let mut config = ChartConfig::app_builder()
@@ -874,6 +1118,44 @@ impl ClusterInstaller {
         Ok(())
     }
 
+    /// Install Fluvio from raw manifests, without helm
+    #[instrument(skip(self))]
+    fn install_manifests(&self, manifests_dir: &Path) -> Result<()> {
+        let pb = self.pb_factory.create()?;
+
+        let installer = ManifestsInstaller::new(&self.config.namespace, manifests_dir)?;
+        pb.set_message(format!(
+            "📊 Applying Fluvio manifests from {}",
+            manifests_dir.display()
+        ));
+
+        // If configured with TLS, copy certs to server
+        if let (TlsPolicy::Verified(server_tls), TlsPolicy::Verified(client_tls)) = (
+            &self.config.server_tls_policy,
+            &self.config.client_tls_policy,
+        ) {
+            self.upload_tls_secrets(server_tls, client_tls)?;
+        }
+
+        installer.apply()?;
+
+        if installer.is_kustomize() {
+            pb.println(format!(
+                "✅ Applied Fluvio kustomize overlay: {}",
+                manifests_dir.display()
+            ));
+        } else {
+            pb.println(format!(
+                "✅ Applied Fluvio manifests: {}",
+                manifests_dir.display()
+            ));
+        }
+
+        pb.finish_and_clear();
+
+        Ok(())
+    }
+
     /// Uploads TLS secrets to Kubernetes
     fn upload_tls_secrets(&self, server_tls: &TlsConfig, client_tls: &TlsConfig) -> Result<()> {
         let server_paths: Cow<TlsPaths> = tls_config_to_cert_paths(server_tls)?;
@@ -1278,7 +1560,7 @@ impl ClusterInstaller {
         pb.set_message(format!("Creating K8 profile for: {external_addr}"));
 
         let profile_name = self.compute_profile_name()?;
-        let mut config_file = ConfigFile::load_default_or_new()?;
+        let mut config_file = ConfigFile::load_default_or_new_for_update()?;
         config_file.add_or_replace_profile(
             &profile_name,
             external_addr,
@@ -1370,6 +1652,65 @@ impl ClusterInstaller {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_images() {
+        let version = semver::Version::parse("0.11.0").unwrap();
+        let config = ClusterConfig::builder(version.clone()).build().unwrap();
+        assert_eq!(config.images(), vec!["infinyon/fluvio:0.11.0"]);
+
+        let config = ClusterConfig::builder(version)
+            .image_registry("localhost:5000/infinyon")
+            .image_tag("0.11.0-dev")
+            .build()
+            .unwrap();
+        assert_eq!(
+            config.images(),
+            vec!["localhost:5000/infinyon/fluvio:0.11.0-dev"]
+        );
+    }
+
+    #[test]
+    fn test_spu_scheduling_values() {
+        let version = semver::Version::parse("0.11.0").unwrap();
+        let config = ClusterConfig::builder(version.clone()).build().unwrap();
+        assert!(config.spu_scheduling_values().is_none());
+
+        let config = ClusterConfig::builder(version.clone())
+            .spu_anti_affinity(SpuAntiAffinity::Required)
+            .spu_topology_key("topology.kubernetes.io/zone")
+            .spu_max_unavailable(1u16)
+            .build()
+            .unwrap();
+        let values = config.spu_scheduling_values().unwrap();
+        let term = &values["spuPod"]["affinity"]["podAntiAffinity"]["requiredDuringSchedulingIgnoredDuringExecution"]
+            [0];
+        assert_eq!(term["topologyKey"], "topology.kubernetes.io/zone");
+        assert_eq!(term["labelSelector"]["matchLabels"]["app"], "spu");
+        assert_eq!(values["spuPod"]["disruptionBudget"]["maxUnavailable"], 1);
+
+        let config = ClusterConfig::builder(version)
+            .spu_anti_affinity(SpuAntiAffinity::Preferred)
+            .build()
+            .unwrap();
+        let values = config.spu_scheduling_values().unwrap();
+        let term = &values["spuPod"]["affinity"]["podAntiAffinity"]["preferredDuringSchedulingIgnoredDuringExecution"]
+            [0];
+        assert_eq!(
+            term["podAffinityTerm"]["topologyKey"],
+            "kubernetes.io/hostname"
+        );
+        assert!(values["spuPod"].get("disruptionBudget").is_none());
+    }
+
+    #[test]
+    fn test_anti_affinity_from_str() {
+        assert_eq!(
+            "preferred".parse::<SpuAntiAffinity>(),
+            Ok(SpuAntiAffinity::Preferred)
+        );
+        assert!("zone".parse::<SpuAntiAffinity>().is_err());
+    }
+
     #[test]
     fn test_build_config() {
         let config: ClusterConfig =
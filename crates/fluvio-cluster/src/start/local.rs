@@ -206,6 +206,14 @@ impl LocalConfig {
         builder
     }
 
+    /// Loads config of local cluster started from this machine, if there is one
+    pub fn load_local() -> Option<Self> {
+        LOCAL_CONFIG_PATH
+            .as_ref()
+            .filter(|path| path.is_file())
+            .and_then(|path| Self::load_from(path).ok())
+    }
+
     pub fn platform_version(&self) -> &Version {
         &self.platform_version
     }
@@ -214,6 +222,16 @@ impl LocalConfig {
         self.launcher.as_deref()
     }
 
+    /// TLS policy of the SC and SPU servers
+    pub fn server_tls_policy(&self) -> &TlsPolicy {
+        &self.server_tls_policy
+    }
+
+    /// TLS policy of the client profile
+    pub fn client_tls_policy(&self) -> &TlsPolicy {
+        &self.client_tls_policy
+    }
+
     pub fn as_spu_cluster_manager(&self) -> LocalSpuProcessClusterManager {
         LocalSpuProcessClusterManager {
             log_dir: self.log_dir.to_owned(),
@@ -596,7 +614,7 @@ impl LocalInstaller {
             self.config.sc_pub_addr
         ));
 
-        let mut config_file = ConfigFile::load_default_or_new()?;
+        let mut config_file = ConfigFile::load_default_or_new_for_update()?;
         config_file.add_or_replace_profile(
             LOCAL_PROFILE,
             &self.config.sc_pub_addr,
@@ -640,7 +658,7 @@ impl LocalInstaller {
         use k8_client::meta_client::MetadataClient;
         use crate::runtime::spu::SpuClusterManager;
 
-        let spu_process = cluster_manager.create_spu_relative(spu_index);
+        let spu_process = cluster_manager.create_spu_relative(spu_index)?;
 
         let input = InputK8Obj::new(
             spu_process.spec().clone(),
@@ -688,7 +706,7 @@ impl LocalInstaller {
     ) -> Result<()> {
         use crate::runtime::spu::SpuClusterManager;
 
-        let spu_process = cluster_manager.create_spu_relative(spu_index);
+        let spu_process = cluster_manager.create_spu_relative(spu_index)?;
         let spec = spu_process.spec();
         let admin = fluvio.admin().await;
         let name = format!("custom-spu-{}", spu_process.id());
@@ -1,6 +1,8 @@
 pub mod k8;
 pub mod local;
+pub mod docker;
 mod common;
+mod external;
 
 mod constants {
 
@@ -93,6 +93,21 @@ impl std::fmt::Display for Compression {
 }
 
 impl Compression {
+    /// Compression algorithms enabled in this build
+    pub fn supported() -> Vec<Compression> {
+        vec![
+            Compression::None,
+            #[cfg(feature = "gzip")]
+            Compression::Gzip,
+            #[cfg(feature = "snap")]
+            Compression::Snappy,
+            #[cfg(feature = "lz4")]
+            Compression::Lz4,
+            #[cfg(feature = "zstd")]
+            Compression::Zstd,
+        ]
+    }
+
     /// Compress the given data, returning the compressed data
     pub fn compress(&self, src: &[u8]) -> Result<Bytes, CompressionError> {
         match *self {
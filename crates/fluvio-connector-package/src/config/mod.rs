@@ -750,6 +750,7 @@ mod tests {
                     partition: PartitionConfig {
                         count: Some(3),
                         max_size: Some(bytesize::ByteSize(1000)),
+                        max_batch_size: None,
                         replication: Some(2),
                         ignore_rack_assignment: Some(true),
                         maps: None,
@@ -757,6 +758,7 @@ mod tests {
                     retention: RetentionConfig {
                         time: Some(Duration::from_secs(120)),
                         segment_size: Some(bytesize::ByteSize(2000)),
+                        local_time: None,
                     },
                     compression: CompressionConfig {
                         type_: CompressionAlgorithm::Lz4,
@@ -774,6 +776,9 @@ mod tests {
                             },
                         },
                     }),
+                    timestamp: None,
+                    format_version: None,
+                    transforms: Default::default(),
                 },
                 version: "0.1.0".to_string(),
                 producer: Some(ProducerParameters {
@@ -0,0 +1,24 @@
+use fluvio_stream_model::k8_types::{Crd, GROUP, V1, CrdNames, Spec, Status, DefaultHeader};
+
+use super::ClusterConfigSpec;
+use super::ClusterConfigStatus;
+
+const CLUSTER_CONFIG_API: Crd = Crd {
+    group: GROUP,
+    version: V1,
+    names: CrdNames {
+        kind: "ClusterConfig",
+        plural: "clusterconfigs",
+        singular: "clusterconfig",
+    },
+};
+
+impl Spec for ClusterConfigSpec {
+    type Header = DefaultHeader;
+    type Status = ClusterConfigStatus;
+    fn metadata() -> &'static Crd {
+        &CLUSTER_CONFIG_API
+    }
+}
+
+impl Status for ClusterConfigStatus {}
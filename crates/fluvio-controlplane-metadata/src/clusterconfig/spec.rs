@@ -0,0 +1,218 @@
+//!
+//! # Cluster Config Spec
+//!
+//! Cluster wide defaults applied to new topics, and maintenance mode.
+//! There is a single instance, named [`CLUSTER_CONFIG_NAME`].
+//!
+
+use fluvio_protocol::{Encoder, Decoder};
+use fluvio_types::ReplicationFactor;
+use fluvio_types::defaults::STORAGE_RETENTION_SECONDS_MIN;
+
+use crate::topic::{CleanupPolicy, CompressionAlgorithm, ReplicaSpec, SegmentBasedPolicy, TopicSpec};
+
+/// name of cluster config instance
+pub const CLUSTER_CONFIG_NAME: &str = "default";
+
+/// replication factor of computed topics when neither topic nor cluster config set it
+pub const DEFAULT_TOPIC_REPLICATION: ReplicationFactor = 1;
+
+#[derive(Encoder, Decoder, Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct ClusterConfigSpec {
+    /// retention of topics created without cleanup policy, in seconds
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub default_retention_secs: Option<u32>,
+    /// replication factor of computed topics created without replication factor
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub default_replication: Option<ReplicationFactor>,
+    /// compression of topics created with `any` compression
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub default_compression: Option<CompressionAlgorithm>,
+    /// create topics with single partition when producer sends to unknown topic
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    pub auto_create_topics: bool,
+    /// cluster is in maintenance while set
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub maintenance: Option<MaintenanceSpec>,
+}
+
+/// While cluster is in maintenance, SC rejects creation of topics
+/// and doesn't reassign replicas of existing topics
+#[derive(Encoder, Decoder, Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct MaintenanceSpec {
+    /// SPUs reject produce requests
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    pub reject_produce: bool,
+}
+
+impl ClusterConfigSpec {
+    pub fn is_in_maintenance(&self) -> bool {
+        self.maintenance.is_some()
+    }
+
+    /// produce requests are rejected by SPUs
+    pub fn rejects_produce(&self) -> bool {
+        self.maintenance
+            .as_ref()
+            .is_some_and(|maintenance| maintenance.reject_produce)
+    }
+
+    /// validate configuration, return string with errors
+    pub fn validate_config(&self) -> Option<String> {
+        if let Some(retention) = self.default_retention_secs
+            && retention < STORAGE_RETENTION_SECONDS_MIN
+        {
+            return Some(format!(
+                "default retention {retention} secs is less than minimum {STORAGE_RETENTION_SECONDS_MIN}"
+            ));
+        }
+
+        if self.default_replication == Some(0) {
+            return Some("default replication factor must be greater than 0".to_owned());
+        }
+
+        None
+    }
+
+    /// Fill topic settings which were not set on creation, returns true if topic changed.
+    /// Replication factor 0 of computed topic means not set.
+    pub fn apply_topic_defaults(&self, topic: &mut TopicSpec) -> bool {
+        let mut changed = false;
+
+        if let ReplicaSpec::Computed(param) = topic.replicas()
+            && param.replication_factor == 0
+        {
+            let mut param = param.clone();
+            param.replication_factor = self
+                .default_replication
+                .unwrap_or(DEFAULT_TOPIC_REPLICATION);
+            topic.set_replicas(ReplicaSpec::Computed(param));
+            changed = true;
+        }
+
+        if let Some(retention) = self.default_retention_secs
+            && topic.get_clean_policy().is_none()
+        {
+            topic.set_cleanup_policy(CleanupPolicy::Segment(SegmentBasedPolicy {
+                time_in_seconds: retention,
+            }));
+            changed = true;
+        }
+
+        if let Some(compression) = &self.default_compression
+            && *compression != CompressionAlgorithm::Any
+            && *topic.get_compression_type() == CompressionAlgorithm::Any
+        {
+            topic.set_compression_type(compression.clone());
+            changed = true;
+        }
+
+        changed
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_apply_topic_defaults() {
+        let config = ClusterConfigSpec {
+            default_retention_secs: Some(3600),
+            default_replication: Some(3),
+            default_compression: Some(CompressionAlgorithm::Zstd),
+            auto_create_topics: false,
+            maintenance: None,
+        };
+
+        let mut topic = TopicSpec::new_computed(2, 0, None);
+        assert!(config.apply_topic_defaults(&mut topic));
+        assert_eq!(topic.replication_factor(), Some(3));
+        assert_eq!(topic.retention_secs(), 3600);
+        assert_eq!(topic.get_compression_type(), &CompressionAlgorithm::Zstd);
+
+        // settings of topic are kept
+        let mut topic = TopicSpec::new_computed(2, 2, None);
+        topic.set_cleanup_policy(CleanupPolicy::Segment(SegmentBasedPolicy {
+            time_in_seconds: 60,
+        }));
+        topic.set_compression_type(CompressionAlgorithm::Gzip);
+        let expected = topic.clone();
+        assert!(!config.apply_topic_defaults(&mut topic));
+        assert_eq!(topic, expected);
+    }
+
+    #[test]
+    fn test_validate_config() {
+        assert!(ClusterConfigSpec::default().validate_config().is_none());
+        let config = ClusterConfigSpec {
+            default_retention_secs: Some(1),
+            ..Default::default()
+        };
+        assert!(config.validate_config().is_some());
+        let config = ClusterConfigSpec {
+            default_replication: Some(0),
+            ..Default::default()
+        };
+        assert!(config.validate_config().is_some());
+    }
+
+    #[test]
+    fn test_maintenance() {
+        let config = ClusterConfigSpec::default();
+        assert!(!config.is_in_maintenance());
+        assert!(!config.rejects_produce());
+
+        let config = ClusterConfigSpec {
+            maintenance: Some(MaintenanceSpec::default()),
+            ..Default::default()
+        };
+        assert!(config.is_in_maintenance());
+        assert!(!config.rejects_produce());
+
+        let config = ClusterConfigSpec {
+            maintenance: Some(MaintenanceSpec {
+                reject_produce: true,
+            }),
+            ..Default::default()
+        };
+        assert!(config.rejects_produce());
+    }
+
+    #[test]
+    fn test_apply_topic_defaults_without_config() {
+        let config = ClusterConfigSpec::default();
+
+        let mut topic = TopicSpec::new_computed(1, 0, None);
+        assert!(config.apply_topic_defaults(&mut topic));
+        assert_eq!(topic.replication_factor(), Some(DEFAULT_TOPIC_REPLICATION));
+        assert!(topic.get_clean_policy().is_none());
+        assert_eq!(topic.get_compression_type(), &CompressionAlgorithm::Any);
+
+        let mut topic = TopicSpec::new_computed(1, 1, None);
+        assert!(!config.apply_topic_defaults(&mut topic));
+    }
+}
@@ -7,6 +7,7 @@ pub mod tableformat;
 pub mod message;
 pub mod mirror;
 pub mod mirroring;
+pub mod clusterconfig;
 
 pub use fluvio_stream_model::core;
 
@@ -39,6 +40,7 @@ pub mod extended {
         TableFormat,
         DerivedStream,
         Mirror,
+        ClusterConfig,
     }
 
     pub trait SpecExt: Spec {
@@ -3,7 +3,10 @@
 use fluvio_types::SpuId;
 use fluvio_protocol::{link::ErrorCode, Decoder, Encoder};
 
-use crate::topic::{CleanupPolicy, CompressionAlgorithm, Deduplication, TopicSpec, TopicStorageConfig};
+use crate::topic::{
+    CleanupPolicy, CompressionAlgorithm, Deduplication, TimestampPolicy, TopicSpec,
+    TopicStorageConfig,
+};
 
 /// Spec for Partition
 /// Each partition has replicas spread among SPU
@@ -33,6 +36,13 @@ pub struct PartitionSpec {
     #[cfg_attr(feature = "use_serde", serde(default))]
     #[fluvio(min_version = 14)]
     pub mirror: Option<PartitionMirrorConfig>,
+    /// incremented on every leader change, used to fence deposed leaders
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 21)]
+    pub leader_epoch: i32,
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 25)]
+    pub timestamp_policy: TimestampPolicy,
 }
 
 impl PartitionSpec {
@@ -61,13 +71,29 @@ impl PartitionSpec {
             compression_type: topic.get_compression_type().clone(),
             deduplication: topic.get_deduplication().cloned(),
             system: topic.is_system(),
+            leader_epoch: 0,
+            timestamp_policy: topic.get_timestamp_policy().clone(),
         }
     }
 
+    /// apply settings of topic which can be changed on existing partitions
+    pub fn update_topic_config(&mut self, topic: &TopicSpec) {
+        self.cleanup_policy = topic.get_clean_policy().cloned();
+        self.storage = topic.get_storage().cloned();
+        self.compression_type = topic.get_compression_type().clone();
+        self.deduplication = topic.get_deduplication().cloned();
+    }
+
     pub fn has_spu(&self, spu: &SpuId) -> bool {
         self.replicas.contains(spu)
     }
 
+    /// switch to new leader, advancing leader epoch
+    pub fn change_leader(&mut self, leader: SpuId) {
+        self.leader = leader;
+        self.leader_epoch += 1;
+    }
+
     /// follower replicas
     pub fn followers(&self) -> Vec<SpuId> {
         self.replicas
@@ -7,6 +7,7 @@ mod spec_v1;
 pub use self::spec::*;
 pub use self::status::*;
 pub use self::package::*;
+pub use self::params::*;
 
 #[cfg(feature = "k8")]
 mod k8;
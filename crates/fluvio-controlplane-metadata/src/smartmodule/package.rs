@@ -485,7 +485,7 @@ mod package_test {
 #[cfg(all(test, feature = "smartmodule"))]
 mod test {
 
-    use crate::smartmodule::params::{SmartModuleParams, SmartModuleParam};
+    use crate::smartmodule::params::{SmartModuleParams, SmartModuleParam, SmartModuleParamType};
 
     use super::{FluvioSemVersion, SmartModulePackage};
 
@@ -501,6 +501,7 @@ mod test {
         let param = SmartModuleParam {
             optional: true,
             description: Some("fluvio".to_owned()),
+            ..Default::default()
         };
         let mut params = SmartModuleParams::default();
         params.insert_param("param1".to_owned(), param);
@@ -539,5 +540,7 @@ mod test {
         let input1 = &params.get_param("multiplier").unwrap();
         assert_eq!(input1.description.as_ref().unwrap(), "multiply input");
         assert!(!input1.optional);
+        assert_eq!(input1.kind, SmartModuleParamType::Int);
+        assert_eq!(input1.default.as_deref(), Some("1"));
     }
 }
@@ -2,6 +2,8 @@ use std::{
     collections::{BTreeMap},
 };
 
+use thiserror::Error;
+
 use fluvio_protocol::{Encoder, Decoder};
 
 #[derive(Debug, Default, Clone, PartialEq, Eq, Encoder, Decoder)]
@@ -23,6 +25,10 @@ impl SmartModuleParams {
     pub fn insert_param(&mut self, name: String, param: SmartModuleParam) {
         self.0.insert(name, param);
     }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &SmartModuleParam)> {
+        self.0.iter()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Encoder, Default, Decoder)]
@@ -31,6 +37,105 @@ pub struct SmartModuleParam {
     pub description: Option<String>,
     #[cfg_attr(feature = "serde", serde(default))]
     pub optional: bool,
+    /// type of value, checked when SmartModule is invoked
+    #[fluvio(min_version = 22)]
+    #[cfg_attr(feature = "use_serde", serde(default, rename = "type"))]
+    pub kind: SmartModuleParamType,
+    /// allowed values of `enum` parameter
+    #[fluvio(min_version = 22)]
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub values: Vec<String>,
+    /// value used when parameter is not given
+    #[fluvio(min_version = 22)]
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub default: Option<String>,
+}
+
+impl SmartModuleParam {
+    /// Checks value given to the parameter when SmartModule is invoked.
+    /// Returns default value to use if parameter is not given
+    pub fn bind(
+        &self,
+        name: &str,
+        value: Option<&str>,
+    ) -> Result<Option<String>, SmartModuleParamError> {
+        let Some(value) = value else {
+            return match &self.default {
+                Some(default) => Ok(Some(default.clone())),
+                None if self.optional => Ok(None),
+                None => Err(SmartModuleParamError::Missing {
+                    name: name.to_owned(),
+                    description: self
+                        .description
+                        .as_ref()
+                        .map(|description| format!(" ({description})"))
+                        .unwrap_or_default(),
+                }),
+            };
+        };
+
+        let valid = match self.kind {
+            SmartModuleParamType::String => true,
+            SmartModuleParamType::Int => value.parse::<i64>().is_ok(),
+            SmartModuleParamType::Bool => value.parse::<bool>().is_ok(),
+            SmartModuleParamType::Enum => self.values.iter().any(|allowed| allowed == value),
+        };
+        if valid {
+            Ok(None)
+        } else {
+            Err(SmartModuleParamError::InvalidValue {
+                name: name.to_owned(),
+                value: value.to_owned(),
+                expected: self.expected(),
+            })
+        }
+    }
+
+    /// description of valid values
+    fn expected(&self) -> String {
+        match self.kind {
+            SmartModuleParamType::String => "string".to_owned(),
+            SmartModuleParamType::Int => "integer".to_owned(),
+            SmartModuleParamType::Bool => "true or false".to_owned(),
+            SmartModuleParamType::Enum => format!("one of: {}", self.values.join(", ")),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Encoder, Decoder)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum SmartModuleParamType {
+    #[default]
+    #[fluvio(tag = 0)]
+    String,
+    #[fluvio(tag = 1)]
+    Int,
+    #[fluvio(tag = 2)]
+    Bool,
+    #[fluvio(tag = 3)]
+    Enum,
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum SmartModuleParamError {
+    #[error("missing value of required parameter `{name}`{description}")]
+    Missing { name: String, description: String },
+    #[error("invalid value `{value}` of parameter `{name}`, expected {expected}")]
+    InvalidValue {
+        name: String,
+        value: String,
+        expected: String,
+    },
 }
 
 /// map parameters from list to map and vice versa
@@ -82,3 +187,48 @@ mod map_init_params {
         param: SmartModuleParam,
     }
 }
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_bind_param() {
+        let count = SmartModuleParam {
+            description: Some("max records".to_owned()),
+            kind: SmartModuleParamType::Int,
+            ..Default::default()
+        };
+        assert_eq!(count.bind("count", Some("10")), Ok(None));
+        assert_eq!(
+            count.bind("count", Some("ten")).unwrap_err().to_string(),
+            "invalid value `ten` of parameter `count`, expected integer"
+        );
+        assert_eq!(
+            count.bind("count", None).unwrap_err().to_string(),
+            "missing value of required parameter `count` (max records)"
+        );
+
+        let mode = SmartModuleParam {
+            kind: SmartModuleParamType::Enum,
+            values: vec!["fast".to_owned(), "safe".to_owned()],
+            default: Some("safe".to_owned()),
+            ..Default::default()
+        };
+        assert_eq!(mode.bind("mode", None), Ok(Some("safe".to_owned())));
+        assert_eq!(mode.bind("mode", Some("fast")), Ok(None));
+        assert_eq!(
+            mode.bind("mode", Some("slow")).unwrap_err().to_string(),
+            "invalid value `slow` of parameter `mode`, expected one of: fast, safe"
+        );
+
+        let verbose = SmartModuleParam {
+            kind: SmartModuleParamType::Bool,
+            optional: true,
+            ..Default::default()
+        };
+        assert_eq!(verbose.bind("verbose", None), Ok(None));
+        assert!(verbose.bind("verbose", Some("yes")).is_err());
+    }
+}
@@ -38,6 +38,14 @@ pub struct SpuSpec {
     #[fluvio(min_version = 1)]
     #[cfg_attr(feature = "use_serde", serde(skip_serializing_if = "Option::is_none"))]
     pub public_endpoint_local: Option<Endpoint>,
+
+    /// settings applied by the SPU at runtime, without restart
+    #[fluvio(min_version = 20)]
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "SpuRuntimeConfig::is_empty")
+    )]
+    pub config: SpuRuntimeConfig,
 }
 
 impl fmt::Display for SpuSpec {
@@ -154,6 +162,7 @@ impl From<CustomSpuSpec> for SpuSpec {
             rack: spec.rack,
             spu_type: SpuType::Custom,
             public_endpoint_local: spec.public_endpoint_local,
+            config: SpuRuntimeConfig::default(),
         }
     }
 }
@@ -173,6 +182,118 @@ impl From<SpuSpec> for CustomSpuSpec {
     }
 }
 
+/// SPU settings which can be changed while the SPU is running.
+/// Unset values fall back to the SPU startup configuration.
+#[derive(Decoder, Encoder, Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase", default)
+)]
+pub struct SpuRuntimeConfig {
+    /// log filter directives, same format as `RUST_LOG`
+    #[cfg_attr(feature = "use_serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub log_level: Option<String>,
+    #[cfg_attr(feature = "use_serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub flush_write_count: Option<u32>,
+    #[cfg_attr(feature = "use_serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub flush_idle_msec: Option<u32>,
+    /// max bytes transferred between leader and follower per request
+    #[cfg_attr(feature = "use_serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub peer_max_bytes: Option<u32>,
+    /// log every request of public service with client identity
+    #[fluvio(min_version = 26)]
+    #[cfg_attr(feature = "use_serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub access_log: Option<bool>,
+    /// networks accepted by public service, in CIDR notation
+    #[fluvio(min_version = 27)]
+    #[cfg_attr(feature = "use_serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub ip_allow: Option<Vec<String>>,
+    /// networks rejected by public service, in CIDR notation
+    #[fluvio(min_version = 27)]
+    #[cfg_attr(feature = "use_serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub ip_deny: Option<Vec<String>>,
+    /// max bytes per second produced by each client connection
+    #[fluvio(min_version = 31)]
+    #[cfg_attr(feature = "use_serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub produce_byte_rate: Option<u32>,
+    /// max bytes per second fetched by each client connection
+    #[fluvio(min_version = 31)]
+    #[cfg_attr(feature = "use_serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub fetch_byte_rate: Option<u32>,
+}
+
+impl SpuRuntimeConfig {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// overwrite values which are set in `other`
+    pub fn merge(&mut self, other: Self) {
+        if other.log_level.is_some() {
+            self.log_level = other.log_level;
+        }
+        if other.flush_write_count.is_some() {
+            self.flush_write_count = other.flush_write_count;
+        }
+        if other.flush_idle_msec.is_some() {
+            self.flush_idle_msec = other.flush_idle_msec;
+        }
+        if other.peer_max_bytes.is_some() {
+            self.peer_max_bytes = other.peer_max_bytes;
+        }
+        if other.access_log.is_some() {
+            self.access_log = other.access_log;
+        }
+        if other.ip_allow.is_some() {
+            self.ip_allow = other.ip_allow;
+        }
+        if other.ip_deny.is_some() {
+            self.ip_deny = other.ip_deny;
+        }
+        if other.produce_byte_rate.is_some() {
+            self.produce_byte_rate = other.produce_byte_rate;
+        }
+        if other.fetch_byte_rate.is_some() {
+            self.fetch_byte_rate = other.fetch_byte_rate;
+        }
+    }
+}
+
+impl fmt::Display for SpuRuntimeConfig {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut settings = vec![];
+        if let Some(log_level) = &self.log_level {
+            settings.push(format!("log-level={log_level}"));
+        }
+        if let Some(count) = self.flush_write_count {
+            settings.push(format!("flush-write-count={count}"));
+        }
+        if let Some(msec) = self.flush_idle_msec {
+            settings.push(format!("flush-idle-msec={msec}"));
+        }
+        if let Some(bytes) = self.peer_max_bytes {
+            settings.push(format!("peer-max-bytes={bytes}"));
+        }
+        if let Some(access_log) = self.access_log {
+            settings.push(format!("access-log={access_log}"));
+        }
+        if let Some(networks) = &self.ip_allow {
+            settings.push(format!("ip-allow=[{}]", networks.join(" ")));
+        }
+        if let Some(networks) = &self.ip_deny {
+            settings.push(format!("ip-deny=[{}]", networks.join(" ")));
+        }
+        if let Some(rate) = self.produce_byte_rate {
+            settings.push(format!("produce-byte-rate={rate}"));
+        }
+        if let Some(rate) = self.fetch_byte_rate {
+            settings.push(format!("fetch-byte-rate={rate}"));
+        }
+        write!(f, "{}", settings.join(","))
+    }
+}
+
 #[derive(Decoder, Encoder, Default, Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(
     feature = "use_serde",
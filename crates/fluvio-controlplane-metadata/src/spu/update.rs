@@ -0,0 +1,19 @@
+use fluvio_protocol::{Decoder, Encoder};
+
+use super::SpuRuntimeConfig;
+
+#[derive(Debug, Encoder, Decoder, Clone)]
+pub enum UpdateSpuAction {
+    /// merge settings into the SPU runtime config
+    #[fluvio(tag = 0)]
+    SetConfig(SpuRuntimeConfig),
+    /// clear runtime config, SPU falls back to its startup configuration
+    #[fluvio(tag = 1)]
+    ResetConfig,
+}
+
+impl Default for UpdateSpuAction {
+    fn default() -> Self {
+        Self::SetConfig(SpuRuntimeConfig::default())
+    }
+}
@@ -1,6 +1,12 @@
 #![allow(clippy::assign_op_pattern)]
 
-use fluvio_protocol::{Encoder, Decoder};
+use std::io::Error as IoError;
+
+use fluvio_protocol::bytes::BufMut;
+use fluvio_protocol::{Encoder, Decoder, Version};
+
+/// version of computed columns, value formats and color rules
+pub const COLOR_RULES_VERSION: Version = 24;
 
 #[derive(Encoder, Decoder, Default, Debug, Eq, PartialEq, Clone)]
 #[cfg_attr(
@@ -70,6 +76,33 @@ pub struct TableFormatColumnConfig {
     pub primary_key: Option<bool>,
     pub header_bg_color: Option<Color>,
     pub header_text_color: Option<Color>,
+    /// computes value of the column from fields of the record, ex: `price * quantity`
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = COLOR_RULES_VERSION)]
+    pub expression: Option<String>,
+    /// longer values are truncated
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = COLOR_RULES_VERSION)]
+    pub max_length: Option<u32>,
+    /// color of the value given by the first rule whose condition holds for the record
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    #[fluvio(min_version = COLOR_RULES_VERSION)]
+    pub color_rules: Vec<TableFormatColorRule>,
+}
+
+/// Colors value when condition holds, ex: `status == "error"`
+#[derive(Encoder, Decoder, Default, Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct TableFormatColorRule {
+    pub when: String,
+    pub color: Color,
 }
 
 #[cfg_attr(
@@ -130,6 +163,11 @@ impl TableFormatColumnConfig {
         self.header_bg_color = header_bg_color;
         self
     }
+
+    pub fn with_expression(mut self, expression: Option<String>) -> Self {
+        self.expression = expression;
+        self
+    }
 }
 
 #[cfg_attr(
@@ -137,7 +175,7 @@ impl TableFormatColumnConfig {
     derive(serde::Serialize, serde::Deserialize),
     serde(rename_all = "UPPERCASE")
 )]
-#[derive(Encoder, Decoder, Debug, Eq, PartialEq, Clone)]
+#[derive(Decoder, Debug, Eq, PartialEq, Clone)]
 pub enum Color {
     #[fluvio(tag = 0)]
     Blue,
@@ -145,6 +183,12 @@ pub enum Color {
     Yellow,
     #[fluvio(tag = 2)]
     Green,
+    #[fluvio(tag = 3)]
+    Red,
+    #[fluvio(tag = 4)]
+    Cyan,
+    #[fluvio(tag = 5)]
+    Magenta,
 }
 
 impl Default for Color {
@@ -152,3 +196,69 @@ impl Default for Color {
         Self::Blue
     }
 }
+
+impl Color {
+    fn tag(&self) -> u8 {
+        match self {
+            Self::Blue => 0,
+            Self::Yellow => 1,
+            Self::Green => 2,
+            Self::Red => 3,
+            Self::Cyan => 4,
+            Self::Magenta => 5,
+        }
+    }
+}
+
+impl Encoder for Color {
+    fn write_size(&self, version: Version) -> usize {
+        self.tag().write_size(version)
+    }
+
+    fn encode<T>(&self, dest: &mut T, version: Version) -> Result<(), IoError>
+    where
+        T: BufMut,
+    {
+        // older versions can't decode colors added with color rules, send default instead
+        let tag = match self.tag() {
+            tag if tag > 2 && version < COLOR_RULES_VERSION => Self::default().tag(),
+            tag => tag,
+        };
+        tag.encode(dest, version)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn test_color_versions() {
+        let column = TableFormatColumnConfig {
+            key_path: "status".to_owned(),
+            header_bg_color: Some(Color::Red),
+            ..Default::default()
+        };
+
+        let mut old = vec![];
+        column
+            .encode(&mut old, COLOR_RULES_VERSION - 1)
+            .expect("encode");
+        let decoded =
+            TableFormatColumnConfig::decode_from(&mut Cursor::new(old), COLOR_RULES_VERSION - 1)
+                .expect("decode");
+        assert_eq!(decoded.header_bg_color, Some(Color::Blue));
+
+        let mut current = vec![];
+        column
+            .encode(&mut current, COLOR_RULES_VERSION)
+            .expect("encode");
+        let decoded =
+            TableFormatColumnConfig::decode_from(&mut Cursor::new(current), COLOR_RULES_VERSION)
+                .expect("decode");
+        assert_eq!(decoded, column);
+    }
+}
@@ -7,7 +7,11 @@ use crate::topic::{
     ReplicaSpec, TopicReplicaParam, SegmentBasedPolicy, CleanupPolicy, TopicStorageConfig,
 };
 
-use super::{TopicSpec, PartitionMap, CompressionAlgorithm, deduplication::Deduplication};
+use super::{
+    TopicSpec, PartitionMap, CompressionAlgorithm,
+    deduplication::{Deduplication, Transform},
+    timestamp::TimestampPolicy,
+};
 
 const DEFAULT_PARTITION_COUNT: PartitionCount = 1;
 const DEFAULT_REPLICATION_FACTOR: ReplicationFactor = 1;
@@ -46,6 +50,30 @@ pub struct TopicConfig {
         serde(default, skip_serializing_if = "Option::is_none")
     )]
     pub deduplication: Option<Deduplication>,
+
+    /// timestamp assignment and validation of produced records
+    #[builder(default)]
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub timestamp: Option<TimestampPolicy>,
+
+    /// version of record payloads, consumers upcast records of older versions
+    #[builder(default)]
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub format_version: Option<u32>,
+
+    /// default transforms of consumers
+    #[builder(default)]
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub transforms: Vec<Transform>,
 }
 
 #[derive(Debug, Default, Builder, Clone, PartialEq, Eq)]
@@ -80,6 +108,14 @@ pub struct PartitionConfig {
     )]
     pub max_size: Option<bytesize::ByteSize>,
 
+    /// max size of batch accepted by SPU, which bounds size of records
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(skip_serializing_if = "Option::is_none", default),
+        schemars(with = "Option::<String>")
+    )]
+    pub max_batch_size: Option<bytesize::ByteSize>,
+
     #[cfg_attr(
         feature = "use_serde",
         serde(skip_serializing_if = "Option::is_none", default)
@@ -124,6 +160,18 @@ pub struct RetentionConfig {
         schemars(with = "Option::<String>")
     )]
     pub segment_size: Option<bytesize::ByteSize>,
+
+    /// time segments stay on SPU disk before offloading to tiered storage
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(
+            skip_serializing_if = "Option::is_none",
+            with = "humantime_serde",
+            default
+        ),
+        schemars(with = "Option::<String>")
+    )]
+    pub local_time: Option<Duration>,
 }
 
 #[derive(Debug, Default, Builder, Clone, PartialEq, Eq)]
@@ -161,6 +209,7 @@ impl Default for PartitionConfig {
             replication: Some(DEFAULT_REPLICATION_FACTOR),
             ignore_rack_assignment: Some(DEFAULT_IGNORE_RACK_ASSIGMENT),
             max_size: Default::default(),
+            max_batch_size: Default::default(),
             maps: Default::default(),
         }
     }
@@ -170,6 +219,8 @@ impl From<TopicConfig> for TopicSpec {
     fn from(config: TopicConfig) -> Self {
         let segment_size = config.retention.segment_size.map(|s| s.as_u64() as u32);
         let max_partition_size = config.partition.max_size.map(|s| s.as_u64());
+        let max_batch_size = config.partition.max_batch_size.map(|s| s.as_u64() as u32);
+        let local_retention_seconds = config.retention.local_time.map(|t| t.as_secs() as u32);
 
         let replica_spec = match config.partition.maps {
             Some(maps) => ReplicaSpec::Assigned(maps.into()),
@@ -194,11 +245,20 @@ impl From<TopicConfig> for TopicSpec {
 
         topic_spec.set_compression_type(config.compression.type_);
         topic_spec.set_deduplication(config.deduplication);
-
-        if segment_size.is_some() || max_partition_size.is_some() {
+        topic_spec.set_timestamp_policy(config.timestamp.unwrap_or_default());
+        topic_spec.set_format_version(config.format_version.unwrap_or_default());
+        topic_spec.set_transforms(config.transforms);
+
+        if segment_size.is_some()
+            || max_partition_size.is_some()
+            || local_retention_seconds.is_some()
+            || max_batch_size.is_some()
+        {
             topic_spec.set_storage(TopicStorageConfig {
                 segment_size,
                 max_partition_size,
+                local_retention_seconds,
+                max_batch_size,
             });
         }
 
@@ -213,6 +273,7 @@ fn default_version() -> String {
 #[cfg(test)]
 mod tests {
     use crate::topic::deduplication::{Bounds, Filter, Transform};
+    use crate::topic::timestamp::TimestampType;
 
     use super::*;
 
@@ -226,6 +287,7 @@ meta:
 partition:
   count: 3
   max-size: 1.0 KB
+  max-batch-size: 500 B
   replication: 2
   ignore-rack-assignment: true
   maps:
@@ -236,6 +298,7 @@ partition:
 retention:
   time: 2m
   segment-size: 2.0 KB
+  local-time: 1m
 compression:
   type: Lz4
 deduplication:
@@ -245,6 +308,14 @@ deduplication:
   filter:
     transform:
       uses: fluvio/dedup-bloom-filter@0.1.0
+timestamp:
+  type: log-append-time
+format-version: 2
+transforms:
+- uses: fluvio/decompress@0.1.0
+- uses: fluvio/decode@0.1.0
+  with:
+    format: json
 "#;
 
         //when
@@ -339,8 +410,16 @@ compression:
         test_spec.set_storage(TopicStorageConfig {
             segment_size: Some(2000),
             max_partition_size: Some(1000),
+            local_retention_seconds: Some(60),
+            max_batch_size: Some(500),
         });
         test_spec.set_deduplication(Some(test_deduplication()));
+        test_spec.set_timestamp_policy(TimestampPolicy {
+            type_: TimestampType::LogAppendTime,
+            max_skew: None,
+        });
+        test_spec.set_format_version(2);
+        test_spec.set_transforms(test_transforms());
 
         assert_eq!(spec, test_spec);
     }
@@ -354,6 +433,7 @@ compression:
             partition: PartitionConfig {
                 count: Some(3),
                 max_size: Some(bytesize::ByteSize(1000)),
+                max_batch_size: Some(bytesize::ByteSize(500)),
                 replication: Some(2),
                 ignore_rack_assignment: Some(true),
                 maps: Some(vec![PartitionMap {
@@ -365,14 +445,34 @@ compression:
             retention: RetentionConfig {
                 time: Some(Duration::from_secs(120)),
                 segment_size: Some(bytesize::ByteSize(2000)),
+                local_time: Some(Duration::from_secs(60)),
             },
             compression: CompressionConfig {
                 type_: CompressionAlgorithm::Lz4,
             },
             deduplication: Some(test_deduplication()),
+            timestamp: Some(TimestampPolicy {
+                type_: TimestampType::LogAppendTime,
+                max_skew: None,
+            }),
+            format_version: Some(2),
+            transforms: test_transforms(),
         }
     }
 
+    fn test_transforms() -> Vec<Transform> {
+        vec![
+            Transform {
+                uses: "fluvio/decompress@0.1.0".to_string(),
+                with: Default::default(),
+            },
+            Transform {
+                uses: "fluvio/decode@0.1.0".to_string(),
+                with: [("format".to_string(), "json".to_string())].into(),
+            },
+        ]
+    }
+
     fn test_deduplication() -> Deduplication {
         Deduplication {
             bounds: Bounds {
@@ -1,6 +1,7 @@
 mod spec;
 mod status;
 mod deduplication;
+mod timestamp;
 mod update;
 pub mod config;
 
@@ -8,6 +9,7 @@ pub use self::update::*;
 pub use self::spec::*;
 pub use self::status::*;
 pub use self::deduplication::*;
+pub use self::timestamp::*;
 
 pub const PENDING_REASON: &str = "waiting for live spus";
 
@@ -13,7 +13,8 @@ use fluvio_protocol::{Encoder, Decoder};
 
 use crate::partition::{HomePartitionConfig, PartitionMirrorConfig, RemotePartitionConfig};
 
-use super::deduplication::Deduplication;
+use super::deduplication::{Deduplication, Transform};
+use super::timestamp::TimestampPolicy;
 
 #[derive(Debug, Clone, PartialEq, Default, Encoder, Decoder)]
 #[cfg_attr(
@@ -39,6 +40,18 @@ pub struct TopicSpec {
     #[cfg_attr(feature = "use_serde", serde(default))]
     #[fluvio(min_version = 13)]
     system: bool,
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    #[fluvio(min_version = 23)]
+    transforms: Vec<Transform>,
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 25)]
+    timestamp_policy: TimestampPolicy,
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 29)]
+    format_version: u32,
 }
 
 impl From<ReplicaSpec> for TopicSpec {
@@ -139,6 +152,14 @@ impl TopicSpec {
         self.deduplication = deduplication;
     }
 
+    pub fn get_timestamp_policy(&self) -> &TimestampPolicy {
+        &self.timestamp_policy
+    }
+
+    pub fn set_timestamp_policy(&mut self, policy: TimestampPolicy) {
+        self.timestamp_policy = policy;
+    }
+
     pub fn is_system(&self) -> bool {
         self.system
     }
@@ -147,6 +168,24 @@ impl TopicSpec {
         self.system = system;
     }
 
+    /// transforms applied by consumers of the topic, unless they opt out
+    pub fn get_transforms(&self) -> &[Transform] {
+        &self.transforms
+    }
+
+    pub fn set_transforms(&mut self, transforms: Vec<Transform>) {
+        self.transforms = transforms;
+    }
+
+    /// version of record payloads written by producers, 0 if payloads are not versioned
+    pub fn get_format_version(&self) -> u32 {
+        self.format_version
+    }
+
+    pub fn set_format_version(&mut self, version: u32) {
+        self.format_version = version;
+    }
+
     /// get retention secs that can be displayed
     pub fn retention_secs(&self) -> u32 {
         self.get_clean_policy()
@@ -187,6 +226,22 @@ impl TopicSpec {
                     ));
                 }
             }
+            if let Some(max_batch_size) = storage.max_batch_size {
+                let segment_size = storage.segment_size.unwrap_or(SPU_LOG_SEGMENT_MAX_BYTES);
+                if max_batch_size == 0 || max_batch_size > segment_size {
+                    return Some(format!(
+                        "max_batch_size {max_batch_size} must be between 1 and segment size {segment_size}"
+                    ));
+                }
+            }
+            if let Some(local_retention_secs) = storage.local_retention_seconds
+                && local_retention_secs >= self.retention_secs()
+            {
+                return Some(format!(
+                    "local retention secs {local_retention_secs} must be less than retention secs {}",
+                    self.retention_secs()
+                ));
+            }
         }
 
         None
@@ -985,6 +1040,20 @@ impl SegmentBasedPolicy {
 pub struct TopicStorageConfig {
     pub segment_size: Option<u32>,       // segment size
     pub max_partition_size: Option<u64>, // max partition size
+    /// seconds segments stay on SPU disk before offloading to tiered storage
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[fluvio(min_version = 28)]
+    pub local_retention_seconds: Option<u32>,
+    /// max size of batch accepted by SPU, which bounds size of records
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[fluvio(min_version = 30)]
+    pub max_batch_size: Option<u32>,
 }
 
 #[derive(Decoder, Default, Encoder, Debug, Clone, Eq, PartialEq)]
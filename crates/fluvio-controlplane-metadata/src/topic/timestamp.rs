@@ -0,0 +1,126 @@
+use std::time::Duration;
+
+use fluvio_protocol::{Encoder, Decoder};
+
+/// Source of timestamps stored with records of the topic
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Encoder, Decoder)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    derive(schemars::JsonSchema),
+    serde(rename_all = "kebab-case")
+)]
+pub enum TimestampType {
+    /// keep timestamps assigned by producer
+    #[default]
+    #[fluvio(tag = 0)]
+    CreateTime,
+    /// overwrite timestamps with time of append to the log
+    #[fluvio(tag = 1)]
+    LogAppendTime,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Invalid timestamp type, expected create-time or log-append-time")]
+pub struct InvalidTimestampType;
+
+impl std::str::FromStr for TimestampType {
+    type Err = InvalidTimestampType;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "create-time" | "createtime" => Ok(Self::CreateTime),
+            "log-append-time" | "logappendtime" => Ok(Self::LogAppendTime),
+            _ => Err(InvalidTimestampType),
+        }
+    }
+}
+
+impl std::fmt::Display for TimestampType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CreateTime => write!(f, "create-time"),
+            Self::LogAppendTime => write!(f, "log-append-time"),
+        }
+    }
+}
+
+/// How SPU assigns and validates timestamps of produced records
+#[derive(Debug, Default, Clone, PartialEq, Eq, Encoder, Decoder)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    derive(schemars::JsonSchema),
+    serde(rename_all = "kebab-case")
+)]
+pub struct TimestampPolicy {
+    #[cfg_attr(feature = "use_serde", serde(default, rename = "type"))]
+    pub type_: TimestampType,
+    /// max difference between producer timestamp and SPU clock, only checked for create time
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "humantime_serde"
+        ),
+        schemars(with = "String")
+    )]
+    pub max_skew: Option<Duration>,
+}
+
+impl TimestampPolicy {
+    pub fn is_log_append_time(&self) -> bool {
+        self.type_ == TimestampType::LogAppendTime
+    }
+
+    /// true if producer timestamp in milliseconds is accepted at time `now`
+    pub fn accepts(&self, timestamp: i64, now: i64) -> bool {
+        match self.max_skew {
+            Some(max_skew) if self.type_ == TimestampType::CreateTime => {
+                timestamp.abs_diff(now) <= max_skew.as_millis() as u64
+            }
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_timestamp_skew() {
+        let policy = TimestampPolicy {
+            type_: TimestampType::CreateTime,
+            max_skew: Some(Duration::from_secs(60)),
+        };
+        assert!(policy.accepts(100_000, 100_000));
+        assert!(policy.accepts(40_000, 100_000));
+        assert!(policy.accepts(160_000, 100_000));
+        assert!(!policy.accepts(39_999, 100_000));
+        assert!(!policy.accepts(160_001, 100_000));
+
+        assert!(TimestampPolicy::default().accepts(0, 100_000));
+
+        let append = TimestampPolicy {
+            type_: TimestampType::LogAppendTime,
+            max_skew: Some(Duration::from_secs(60)),
+        };
+        assert!(append.accepts(0, 100_000));
+    }
+
+    #[test]
+    fn test_timestamp_type_from_str() {
+        assert_eq!(
+            "log-append-time".parse::<TimestampType>().unwrap(),
+            TimestampType::LogAppendTime
+        );
+        assert_eq!(
+            "CreateTime".parse::<TimestampType>().unwrap(),
+            TimestampType::CreateTime
+        );
+        assert!("now".parse::<TimestampType>().is_err());
+    }
+}
@@ -1,5 +1,7 @@
 use fluvio_protocol::{Decoder, Encoder};
 
+use super::{CleanupPolicy, CompressionAlgorithm, Deduplication, SegmentBasedPolicy, TopicSpec};
+
 #[derive(Debug, Default, Encoder, Decoder, Clone)]
 pub struct AddPartition {
     pub count: u32,
@@ -12,12 +14,67 @@ pub struct AddMirror {
     pub home_to_mirror: bool,
 }
 
+/// change number of replicas of each partition
+#[derive(Debug, Default, Encoder, Decoder, Clone)]
+pub struct SetReplication {
+    pub factor: u32,
+}
+
+/// change settings of existing topic, unset settings are kept
+#[derive(Debug, Default, Encoder, Decoder, Clone, PartialEq)]
+pub struct AlterTopicConfig {
+    pub retention_time_seconds: Option<u32>,
+    pub max_partition_size: Option<u64>,
+    pub compression_type: Option<CompressionAlgorithm>,
+    pub deduplication: Option<Deduplication>,
+    /// remove deduplication of topic
+    pub remove_deduplication: bool,
+    pub max_batch_size: Option<u32>,
+}
+
+impl AlterTopicConfig {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// apply changed settings to topic spec
+    pub fn apply(&self, spec: &mut TopicSpec) {
+        if let Some(time_in_seconds) = self.retention_time_seconds {
+            spec.set_cleanup_policy(CleanupPolicy::Segment(SegmentBasedPolicy {
+                time_in_seconds,
+            }));
+        }
+        if let Some(compression_type) = &self.compression_type {
+            spec.set_compression_type(compression_type.clone());
+        }
+        if self.remove_deduplication {
+            spec.set_deduplication(None);
+        } else if let Some(deduplication) = &self.deduplication {
+            spec.set_deduplication(Some(deduplication.clone()));
+        }
+        if self.max_partition_size.is_some() || self.max_batch_size.is_some() {
+            let mut storage = spec.get_storage().cloned().unwrap_or_default();
+            if let Some(max_partition_size) = self.max_partition_size {
+                storage.max_partition_size = Some(max_partition_size);
+            }
+            if let Some(max_batch_size) = self.max_batch_size {
+                storage.max_batch_size = Some(max_batch_size);
+            }
+            spec.set_storage(storage);
+        }
+    }
+}
+
 #[derive(Debug, Encoder, Decoder, Clone)]
 pub enum UpdateTopicAction {
     #[fluvio(tag = 0)]
     AddPartition(AddPartition),
     #[fluvio(tag = 1)]
     AddMirror(AddMirror),
+    #[fluvio(tag = 2)]
+    SetReplication(SetReplication),
+    #[fluvio(tag = 3)]
+    AlterConfig(AlterTopicConfig),
 }
 
 impl Default for UpdateTopicAction {
@@ -25,3 +82,47 @@ impl Default for UpdateTopicAction {
         Self::AddPartition(AddPartition::default())
     }
 }
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_alter_topic_config() {
+        let mut spec = TopicSpec::new_computed(1, 1, None);
+        spec.set_compression_type(CompressionAlgorithm::Gzip);
+        spec.set_deduplication(Some(Deduplication::default()));
+
+        let alter = AlterTopicConfig {
+            retention_time_seconds: Some(3600),
+            max_batch_size: Some(1024),
+            ..Default::default()
+        };
+        assert!(!alter.is_empty());
+        alter.apply(&mut spec);
+
+        assert_eq!(spec.retention_secs(), 3600);
+        let storage = spec.get_storage().expect("storage");
+        assert_eq!(storage.max_batch_size, Some(1024));
+        assert_eq!(storage.max_partition_size, None);
+        // unset settings are kept
+        assert_eq!(spec.get_compression_type(), &CompressionAlgorithm::Gzip);
+        assert!(spec.get_deduplication().is_some());
+
+        AlterTopicConfig {
+            remove_deduplication: true,
+            compression_type: Some(CompressionAlgorithm::Lz4),
+            ..Default::default()
+        }
+        .apply(&mut spec);
+        assert!(spec.get_deduplication().is_none());
+        assert_eq!(spec.get_compression_type(), &CompressionAlgorithm::Lz4);
+        assert_eq!(
+            spec.get_storage().expect("storage").max_batch_size,
+            Some(1024)
+        );
+
+        assert!(AlterTopicConfig::default().is_empty());
+    }
+}
@@ -3,7 +3,9 @@
 use std::fmt;
 
 use fluvio_controlplane_metadata::{
-    topic::{CleanupPolicy, TopicStorageConfig, CompressionAlgorithm, Deduplication},
+    topic::{
+        CleanupPolicy, TopicStorageConfig, CompressionAlgorithm, Deduplication, TimestampPolicy,
+    },
     core::MetadataItem,
     store::MetadataStoreObject,
     partition::{PartitionSpec, PartitionMirrorConfig},
@@ -25,6 +27,10 @@ pub struct Replica {
     pub storage: Option<TopicStorageConfig>,
     pub compression_type: CompressionAlgorithm,
     pub deduplication: Option<Deduplication>,
+    #[fluvio(min_version = 21)]
+    pub leader_epoch: i32,
+    #[fluvio(min_version = 22)]
+    pub timestamp_policy: TimestampPolicy,
 }
 
 impl Replica {
@@ -69,13 +75,19 @@ where
             storage: spec.storage,
             compression_type: spec.compression_type,
             deduplication: spec.deduplication,
+            leader_epoch: spec.leader_epoch,
+            timestamp_policy: spec.timestamp_policy,
         }
     }
 }
 
 impl fmt::Display for Replica {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{} leader: {} replicas: [", self.id, self.leader)?;
+        write!(
+            f,
+            "{} leader: {} epoch: {} replicas: [",
+            self.id, self.leader, self.leader_epoch
+        )?;
         for replica in &self.replicas {
             write!(f, "{replica},")?;
         }
@@ -4,11 +4,17 @@ use fluvio_protocol::{Encoder, Decoder};
 use fluvio_controlplane_metadata::message::Message;
 
 /// General control plane request
+///
+/// Either carries a snapshot of all objects in `all` or incremental `changes`.
+/// Incremental changes carry `prev_epoch`, the epoch of the previous request
+/// they build on, so the receiver can detect missed updates.
 #[derive(Decoder, Encoder, Debug, Default)]
 pub struct ControlPlaneRequest<S> {
     pub epoch: i64,
     pub changes: Vec<Message<S>>,
     pub all: Vec<S>,
+    #[fluvio(min_version = 21)]
+    pub prev_epoch: i64,
 }
 
 impl<S> ControlPlaneRequest<S>
@@ -20,6 +26,7 @@ where
             epoch,
             changes,
             all: vec![],
+            prev_epoch: 0,
         }
     }
 
@@ -28,6 +35,18 @@ where
             epoch,
             changes: vec![],
             all,
+            prev_epoch: 0,
         }
     }
+
+    /// set epoch of previous request these changes are based on
+    pub fn with_prev_epoch(mut self, prev_epoch: i64) -> Self {
+        self.prev_epoch = prev_epoch;
+        self
+    }
+
+    /// true if changes are relative to a previous request
+    pub fn is_incremental(&self) -> bool {
+        self.all.is_empty() && self.prev_epoch > 0
+    }
 }
@@ -17,6 +17,7 @@ use crate::sc_api::update_partition::UpdatePartitionStatRequest;
 use super::register_spu::RegisterSpuRequest;
 use super::update_lrs::UpdateLrsRequest;
 use super::remove::ReplicaRemovedRequest;
+use super::resync::ResyncRequest;
 
 #[repr(u16)]
 #[derive(Eq, PartialEq, Debug, Encoder, Decoder, Clone, Copy)]
@@ -29,6 +30,7 @@ pub enum InternalScKey {
     ReplicaRemoved = 2002,
     UpdateMirror = 2003,
     UpdatePartition = 2004,
+    Resync = 2005,
 }
 
 /// Request made to Spu from Sc
@@ -44,6 +46,8 @@ pub enum InternalScRequest {
     UpdateMirrorStatRequest(RequestMessage<UpdateMirrorStatRequest>),
     #[fluvio(tag = 4)]
     UpdatePartitionStatRequest(RequestMessage<UpdatePartitionStatRequest>),
+    #[fluvio(tag = 5)]
+    ResyncRequest(RequestMessage<ResyncRequest>),
 }
 
 impl Default for InternalScRequest {
@@ -77,6 +81,9 @@ impl ApiMessage for InternalScRequest {
             InternalScKey::UpdatePartition => {
                 api_decode!(InternalScRequest, UpdatePartitionStatRequest, src, header)
             }
+            InternalScKey::Resync => {
+                api_decode!(InternalScRequest, ResyncRequest, src, header)
+            }
         }
     }
 }
@@ -1,6 +1,7 @@
 pub mod api;
 pub mod register_spu;
 pub mod remove;
+pub mod resync;
 pub mod update_lrs;
 pub mod update_mirror;
 pub mod update_partition;
@@ -16,6 +16,12 @@ use fluvio_protocol::Decoder;
 use fluvio_protocol::Encoder;
 use fluvio_types::SpuId;
 
+use crate::spu_api::update_cluster_config::UpdateClusterConfigRequest;
+use crate::spu_api::update_mirror::UpdateMirrorRequest;
+use crate::spu_api::update_replica::UpdateReplicaRequest;
+use crate::spu_api::update_smartmodule::UpdateSmartModuleRequest;
+use crate::spu_api::update_spu::UpdateSpuRequest;
+
 use super::api::InternalScKey;
 
 // -----------------------------------
@@ -25,13 +31,70 @@ use super::api::InternalScKey;
 #[derive(Decoder, Encoder, Debug, Default)]
 pub struct RegisterSpuRequest {
     spu: SpuId,
+    /// versions of SC requests supported by SPU
+    #[fluvio(min_version = 1)]
+    api_versions: SpuApiVersions,
 }
 
 impl Request for RegisterSpuRequest {
     const API_KEY: u16 = InternalScKey::RegisterSpu as u16;
+    // version 1 carries versions of SC requests supported by SPU
+    const DEFAULT_API_VERSION: i16 = 1;
     type Response = RegisterSpuResponse;
 }
 
+/// Highest versions of SC requests which SPU can decode.
+/// Sent at registration, so SC only sends requests the SPU understands.
+#[derive(Decoder, Encoder, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpuApiVersions {
+    pub update_spu: i16,
+    pub update_replica: i16,
+    pub update_smartmodule: i16,
+    pub update_mirror: i16,
+    /// None if SPU doesn't accept cluster config
+    pub update_cluster_config: Option<i16>,
+}
+
+impl Default for SpuApiVersions {
+    /// versions of SPUs which don't send versions at registration
+    fn default() -> Self {
+        Self {
+            update_spu: 0,
+            update_replica: 18,
+            update_smartmodule: 10,
+            update_mirror: 0,
+            update_cluster_config: None,
+        }
+    }
+}
+
+impl SpuApiVersions {
+    /// versions of this SPU
+    pub fn current() -> Self {
+        Self {
+            update_spu: UpdateSpuRequest::DEFAULT_API_VERSION,
+            update_replica: UpdateReplicaRequest::DEFAULT_API_VERSION,
+            update_smartmodule: UpdateSmartModuleRequest::DEFAULT_API_VERSION,
+            update_mirror: UpdateMirrorRequest::DEFAULT_API_VERSION,
+            update_cluster_config: Some(UpdateClusterConfigRequest::DEFAULT_API_VERSION),
+        }
+    }
+
+    /// versions both SC and SPU support
+    pub fn negotiate(spu: &Self) -> Self {
+        let current = Self::current();
+        Self {
+            update_spu: current.update_spu.min(spu.update_spu),
+            update_replica: current.update_replica.min(spu.update_replica),
+            update_smartmodule: current.update_smartmodule.min(spu.update_smartmodule),
+            update_mirror: current.update_mirror.min(spu.update_mirror),
+            update_cluster_config: spu
+                .update_cluster_config
+                .map(|version| version.min(UpdateClusterConfigRequest::DEFAULT_API_VERSION)),
+        }
+    }
+}
+
 #[derive(Decoder, Encoder, Default, Debug)]
 pub struct RegisterSpuResponse {
     error_code: ErrorCode,
@@ -44,12 +107,19 @@ pub struct RegisterSpuResponse {
 
 impl RegisterSpuRequest {
     pub fn new(spu: SpuId) -> Self {
-        Self { spu }
+        Self {
+            spu,
+            api_versions: SpuApiVersions::current(),
+        }
     }
 
     pub fn spu(&self) -> SpuId {
         self.spu
     }
+
+    pub fn api_versions(&self) -> &SpuApiVersions {
+        &self.api_versions
+    }
 }
 
 // -----------------------------------
@@ -88,3 +158,148 @@ impl RegisterSpuResponse {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn test_register_from_old_spu() {
+        // registration of SPU before version exchange only has spu id
+        let mut bytes = vec![];
+        RegisterSpuRequest::new(5001)
+            .encode(&mut bytes, 0)
+            .expect("encode");
+
+        let request = RegisterSpuRequest::decode_from(&mut Cursor::new(bytes), 0).expect("decode");
+        assert_eq!(request.spu(), 5001);
+        assert_eq!(request.api_versions(), &SpuApiVersions::default());
+        assert_eq!(
+            SpuApiVersions::negotiate(request.api_versions()),
+            SpuApiVersions::default()
+        );
+    }
+
+    #[test]
+    fn test_register_versions_exchange() {
+        let mut bytes = vec![];
+        RegisterSpuRequest::new(5001)
+            .encode(&mut bytes, RegisterSpuRequest::DEFAULT_API_VERSION)
+            .expect("encode");
+
+        let request = RegisterSpuRequest::decode_from(
+            &mut Cursor::new(bytes),
+            RegisterSpuRequest::DEFAULT_API_VERSION,
+        )
+        .expect("decode");
+        assert_eq!(request.api_versions(), &SpuApiVersions::current());
+        assert_eq!(
+            SpuApiVersions::negotiate(request.api_versions()),
+            SpuApiVersions::current()
+        );
+    }
+
+    #[test]
+    fn test_spu_update_to_older_spu() {
+        use fluvio_controlplane_metadata::spu::{SpuRuntimeConfig, SpuSpec};
+
+        // SPU before ip filter of runtime config
+        let spu_versions = SpuApiVersions {
+            update_spu: 26,
+            ..SpuApiVersions::current()
+        };
+        let version = SpuApiVersions::negotiate(&spu_versions).update_spu;
+        assert_eq!(version, 26);
+
+        let mut spec = SpuSpec::new(5001);
+        spec.config = SpuRuntimeConfig {
+            access_log: Some(true),
+            ip_allow: Some(vec!["10.0.0.0/8".to_owned()]),
+            ..Default::default()
+        };
+        let mut bytes = vec![];
+        UpdateSpuRequest::with_all(1, vec![spec])
+            .encode(&mut bytes, version)
+            .expect("encode");
+
+        let request =
+            UpdateSpuRequest::decode_from(&mut Cursor::new(bytes), version).expect("decode");
+        let config = &request.all[0].config;
+        assert_eq!(config.access_log, Some(true));
+        assert_eq!(config.ip_allow, None);
+    }
+
+    #[test]
+    fn test_replica_update_to_older_spu() {
+        use fluvio_controlplane_metadata::topic::TopicStorageConfig;
+
+        use crate::replica::Replica;
+
+        // SPU before local retention of tiered storage
+        let spu_versions = SpuApiVersions {
+            update_replica: 22,
+            ..SpuApiVersions::current()
+        };
+        let version = SpuApiVersions::negotiate(&spu_versions).update_replica;
+        assert_eq!(version, 22);
+
+        let mut replica = Replica::new(("topic", 0), 5001, vec![5001, 5002]);
+        replica.storage = Some(TopicStorageConfig {
+            segment_size: Some(1000),
+            local_retention_seconds: Some(60),
+            ..Default::default()
+        });
+        let mut bytes = vec![];
+        UpdateReplicaRequest::with_all(1, vec![replica])
+            .encode(&mut bytes, version)
+            .expect("encode");
+
+        let request =
+            UpdateReplicaRequest::decode_from(&mut Cursor::new(bytes), version).expect("decode");
+        let storage = request.all[0].storage.as_ref().expect("storage");
+        assert_eq!(storage.segment_size, Some(1000));
+        assert_eq!(storage.local_retention_seconds, None);
+    }
+
+    #[test]
+    fn test_replica_max_batch_size() {
+        use fluvio_controlplane_metadata::topic::TopicStorageConfig;
+
+        use crate::replica::Replica;
+
+        let mut replica = Replica::new(("topic", 0), 5001, vec![5001]);
+        replica.storage = Some(TopicStorageConfig {
+            max_batch_size: Some(1024),
+            ..Default::default()
+        });
+
+        let max_batch_size = |spu_version: i16| {
+            let spu_versions = SpuApiVersions {
+                update_replica: spu_version,
+                ..SpuApiVersions::current()
+            };
+            let version = SpuApiVersions::negotiate(&spu_versions).update_replica;
+            let mut bytes = vec![];
+            UpdateReplicaRequest::with_all(1, vec![replica.clone()])
+                .encode(&mut bytes, version)
+                .expect("encode");
+            let request = UpdateReplicaRequest::decode_from(&mut Cursor::new(bytes), version)
+                .expect("decode");
+            request.all[0]
+                .storage
+                .as_ref()
+                .expect("storage")
+                .max_batch_size
+        };
+
+        assert_eq!(
+            max_batch_size(UpdateReplicaRequest::DEFAULT_API_VERSION),
+            Some(1024)
+        );
+        // SPU before max batch size
+        assert_eq!(max_batch_size(28), None);
+    }
+}
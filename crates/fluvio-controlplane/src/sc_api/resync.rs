@@ -0,0 +1,54 @@
+use std::fmt;
+
+use fluvio_protocol::api::Request;
+use fluvio_protocol::Decoder;
+use fluvio_protocol::Encoder;
+
+use super::api::InternalScKey;
+
+/// Metadata that SPU receives incrementally from SC
+#[derive(Decoder, Encoder, Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum MetadataSyncKind {
+    #[default]
+    #[fluvio(tag = 0)]
+    Spu,
+    #[fluvio(tag = 1)]
+    Replica,
+}
+
+impl fmt::Display for MetadataSyncKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Spu => write!(f, "spu"),
+            Self::Replica => write!(f, "replica"),
+        }
+    }
+}
+
+/// Request snapshot of metadata after SPU detected a gap in incremental changes
+#[derive(Decoder, Encoder, Debug, Default, Clone)]
+pub struct ResyncRequest {
+    pub kind: MetadataSyncKind,
+    /// last epoch applied by SPU
+    pub last_epoch: i64,
+}
+
+impl ResyncRequest {
+    pub fn new(kind: MetadataSyncKind, last_epoch: i64) -> Self {
+        Self { kind, last_epoch }
+    }
+}
+
+impl fmt::Display for ResyncRequest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "resync {} from epoch {}", self.kind, self.last_epoch)
+    }
+}
+
+impl Request for ResyncRequest {
+    const API_KEY: u16 = InternalScKey::Resync as u16;
+    type Response = ResyncResponse;
+}
+
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct ResyncResponse {}
@@ -0,0 +1,41 @@
+use fluvio_controlplane_metadata::{
+    clusterconfig::ClusterConfigSpec,
+    core::MetadataItem,
+    message::{Message, Messages},
+    store::MetadataStoreObject,
+};
+use fluvio_protocol::{Encoder, Decoder, api::Request};
+
+use crate::requests::ControlPlaneRequest;
+
+use super::api::InternalSpuApi;
+
+#[derive(Decoder, Encoder, Debug, Eq, PartialEq, Clone, Default)]
+pub struct ClusterConfig {
+    pub name: String,
+    pub spec: ClusterConfigSpec,
+}
+
+pub type UpdateClusterConfigRequest = ControlPlaneRequest<ClusterConfig>;
+
+impl Request for UpdateClusterConfigRequest {
+    const API_KEY: u16 = InternalSpuApi::UpdateClusterConfig as u16;
+    type Response = UpdateClusterConfigResponse;
+}
+
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct UpdateClusterConfigResponse {}
+
+pub type ClusterConfigMsg = Message<ClusterConfig>;
+pub type ClusterConfigMsgs = Messages<ClusterConfig>;
+
+impl<C> From<MetadataStoreObject<ClusterConfigSpec, C>> for ClusterConfig
+where
+    C: MetadataItem,
+{
+    fn from(mso: MetadataStoreObject<ClusterConfigSpec, C>) -> Self {
+        let name = mso.key;
+        let spec = mso.spec;
+        Self { name, spec }
+    }
+}
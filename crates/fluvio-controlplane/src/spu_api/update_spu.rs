@@ -11,6 +11,8 @@ pub type UpdateSpuRequest = ControlPlaneRequest<SpuSpec>;
 
 impl Request for UpdateSpuRequest {
     const API_KEY: u16 = InternalSpuApi::UpdateSpu as u16;
+    const DEFAULT_API_VERSION: i16 = 31; // includes byte rate quotas of runtime config
+    const MIN_API_VERSION: i16 = 0;
     type Response = UpdateSpuResponse;
 }
 
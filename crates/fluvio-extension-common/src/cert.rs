@@ -0,0 +1,171 @@
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Result, anyhow};
+use x509_parser::extensions::GeneralName;
+use x509_parser::pem::Pem;
+
+/// Validity of X.509 certificate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CertExpiry {
+    pub subject: String,
+    pub not_after: SystemTime,
+}
+
+impl CertExpiry {
+    /// expiry of every certificate in PEM bundle
+    pub fn from_pem(pem: &[u8]) -> Result<Vec<Self>> {
+        let mut certs = vec![];
+        for pem in Pem::iter_from_buffer(pem) {
+            let pem = pem.map_err(|err| anyhow!("invalid PEM: {err}"))?;
+            let cert = pem
+                .parse_x509()
+                .map_err(|err| anyhow!("invalid certificate: {err}"))?;
+            let not_after = cert.validity().not_after.timestamp();
+            certs.push(Self {
+                subject: cert.subject().to_string(),
+                not_after: UNIX_EPOCH + Duration::from_secs(not_after.max(0) as u64),
+            });
+        }
+        if certs.is_empty() {
+            return Err(anyhow!("no certificate found in PEM"));
+        }
+        Ok(certs)
+    }
+
+    /// first certificate to expire in PEM bundle, bundle is only valid until then
+    pub fn earliest(pem: &[u8]) -> Result<Self> {
+        Self::from_pem(pem)?
+            .into_iter()
+            .min_by_key(|cert| cert.not_after)
+            .ok_or_else(|| anyhow!("no certificate found in PEM"))
+    }
+
+    /// time left before certificate expires, None if already expired
+    pub fn remaining(&self) -> Option<Duration> {
+        self.not_after.duration_since(SystemTime::now()).ok()
+    }
+
+    /// true if certificate expires within `window`
+    pub fn expires_within(&self, window: Duration) -> bool {
+        self.remaining().is_none_or(|remaining| remaining < window)
+    }
+}
+
+/// Subject alternative names of first certificate in PEM, in openssl format
+/// such as `DNS:fluvio.local` or `IP:127.0.0.1`
+pub fn subject_alt_names(pem: &[u8]) -> Result<Vec<String>> {
+    let pem = Pem::iter_from_buffer(pem)
+        .next()
+        .ok_or_else(|| anyhow!("no certificate found in PEM"))?
+        .map_err(|err| anyhow!("invalid PEM: {err}"))?;
+    let cert = pem
+        .parse_x509()
+        .map_err(|err| anyhow!("invalid certificate: {err}"))?;
+    let Some(san) = cert
+        .subject_alternative_name()
+        .map_err(|err| anyhow!("invalid subject alternative name: {err}"))?
+    else {
+        return Ok(vec![]);
+    };
+
+    let mut names = vec![];
+    for name in &san.value.general_names {
+        match name {
+            GeneralName::DNSName(dns) => names.push(format!("DNS:{dns}")),
+            GeneralName::RFC822Name(email) => names.push(format!("email:{email}")),
+            GeneralName::URI(uri) => names.push(format!("URI:{uri}")),
+            GeneralName::IPAddress(bytes) => {
+                let ip = match bytes.len() {
+                    4 => IpAddr::from(<[u8; 4]>::try_from(*bytes)?),
+                    16 => IpAddr::from(<[u8; 16]>::try_from(*bytes)?),
+                    len => return Err(anyhow!("invalid IP address of {len} bytes")),
+                };
+                names.push(format!("IP:{ip}"));
+            }
+            other => return Err(anyhow!("unsupported subject alternative name: {other:?}")),
+        }
+    }
+    Ok(names)
+}
+
+/// Create self-signed certificate `{name}.crt` and key `{name}.key` in `dir` with `openssl`,
+/// valid for `days` from now, with `name` as subject and DNS alternative name.
+/// Lets tests use certificates which don't expire with time.
+#[doc(hidden)]
+pub fn generate_self_signed(dir: &Path, name: &str, days: u32) -> Result<(PathBuf, PathBuf)> {
+    let cert = dir.join(format!("{name}.crt"));
+    let key = dir.join(format!("{name}.key"));
+    let output = Command::new("openssl")
+        .args(["req", "-x509", "-newkey", "rsa:2048", "-nodes", "-subj"])
+        .arg(format!("/CN={name}"))
+        .arg("-addext")
+        .arg(format!("subjectAltName=DNS:{name},IP:127.0.0.1"))
+        .arg("-days")
+        .arg(days.to_string())
+        .arg("-keyout")
+        .arg(&key)
+        .arg("-out")
+        .arg(&cert)
+        .output()
+        .map_err(|err| anyhow!("unable to run openssl: {err}"))?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "openssl failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+    Ok((cert, key))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const DAY: Duration = Duration::from_secs(24 * 3600);
+
+    fn pem(dir: &Path, name: &str, days: u32) -> Vec<u8> {
+        let (cert, _) = generate_self_signed(dir, name, days).expect("generate");
+        std::fs::read(cert).expect("read")
+    }
+
+    #[test]
+    fn test_cert_expiry() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let certs = CertExpiry::from_pem(&pem(dir.path(), "fluvio.io", 10)).expect("parse");
+        assert_eq!(certs.len(), 1);
+
+        let cert = &certs[0];
+        assert!(cert.subject.contains("CN=fluvio.io"));
+        let remaining = cert.remaining().expect("not expired");
+        assert!(remaining > 9 * DAY && remaining <= 10 * DAY);
+        assert!(!cert.expires_within(9 * DAY));
+        assert!(cert.expires_within(11 * DAY));
+    }
+
+    #[test]
+    fn test_earliest_in_bundle() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let bundle = [pem(dir.path(), "late", 30), pem(dir.path(), "early", 10)].concat();
+        assert_eq!(CertExpiry::from_pem(&bundle).expect("parse").len(), 2);
+        let cert = CertExpiry::earliest(&bundle).expect("earliest");
+        assert!(cert.subject.contains("CN=early"));
+    }
+
+    #[test]
+    fn test_subject_alt_names() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        assert_eq!(
+            subject_alt_names(&pem(dir.path(), "fluvio.local", 1)).expect("sans"),
+            vec!["DNS:fluvio.local".to_owned(), "IP:127.0.0.1".to_owned()]
+        );
+    }
+
+    #[test]
+    fn test_invalid_pem() {
+        assert!(CertExpiry::from_pem(b"not a certificate").is_err());
+    }
+}
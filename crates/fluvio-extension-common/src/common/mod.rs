@@ -16,6 +16,7 @@ pub struct OutputFormat {
         short = 'O',
         long = "output",
         value_name = "type",
+        env = "FLUVIO_OUTPUT",
         value_enum,
         ignore_case = true
     )]
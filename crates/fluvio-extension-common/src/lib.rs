@@ -8,8 +8,12 @@ pub mod tls;
 #[cfg(feature = "installation")]
 pub mod installation;
 
+#[cfg(feature = "cert")]
+pub mod cert;
+
 pub use common::*;
 pub use crate::output::Terminal;
+use crate::output::theme;
 use fluvio_index::{PackageId, MaybeVersion};
 
 pub const COMMAND_TEMPLATE: &str = "{about}
@@ -73,11 +77,11 @@ impl Default for PrintTerminal {
 
 impl Terminal for PrintTerminal {
     fn print(&self, msg: &str) {
-        print!("{msg}");
+        print!("{}", theme().text(msg));
     }
 
     fn println(&self, msg: &str) {
-        println!("{msg}");
+        println!("{}", theme().text(msg));
     }
 }
 
@@ -117,13 +121,13 @@ pub mod target {
     #[derive(Debug, Parser, Default, Clone)]
     pub struct ClusterTarget {
         /// Address of cluster
-        #[arg(short = 'c', long, value_name = "host:port")]
+        #[arg(short = 'c', long, value_name = "host:port", env = "FLUVIO_CLUSTER")]
         pub cluster: Option<String>,
 
         #[clap(flatten)]
         pub tls: TlsClientOpt,
 
-        #[arg(short = 'P', long, value_name = "profile")]
+        #[arg(short = 'P', long, value_name = "profile", env = "FLUVIO_PROFILE")]
         pub profile: Option<String>,
     }
 
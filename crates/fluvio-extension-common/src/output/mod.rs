@@ -1,6 +1,8 @@
 mod table;
 mod serde;
 mod describe;
+mod theme;
+mod units;
 
 use comfy_table::Table;
 pub use output::Terminal;
@@ -17,6 +19,9 @@ pub use self::describe::DescribeObjectHandler;
 
 pub use context::RenderContext;
 
+pub use self::theme::{Theme, FLUVIO_THEME, set_theme, theme};
+pub use self::units::{set_raw_units, format_size, format_duration, human_size, human_duration};
+
 pub trait KeyValOutputHandler {
     fn key_values(&self) -> Vec<(String, Option<String>)>;
 }
@@ -68,7 +73,8 @@ pub trait DisplayTable {
 
 impl DisplayTable for Table {
     fn print_std(&self, indent: u8) {
-        for line in self.to_string().split('\n') {
+        let rendered = theme().render_table(self, self.header().is_some());
+        for line in rendered.split('\n') {
             println!("{}{}", " ".repeat(indent as usize), line);
         }
     }
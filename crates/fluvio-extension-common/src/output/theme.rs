@@ -0,0 +1,251 @@
+//!
+//! # Output Theme
+//!
+//! Styling of table and text output shared by all commands.
+//! Selected by `FLUVIO_THEME` environment variable or CLI config, `NO_COLOR` turns off colors
+//! of any theme.
+//!
+
+use std::borrow::Cow;
+use std::fmt;
+use std::io::IsTerminal;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use comfy_table::Table;
+use serde::Deserialize;
+
+pub const FLUVIO_THEME: &str = "FLUVIO_THEME";
+
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Theme {
+    /// colors readable on dark terminal background
+    #[default]
+    Dark,
+    /// colors readable on light terminal background
+    Light,
+    /// no colors
+    NoColor,
+    /// no colors and ASCII symbols, text of records and metadata is kept
+    Ascii,
+}
+
+impl Theme {
+    /// theme from environment, falling back to configured one
+    pub fn resolve(configured: Option<Theme>) -> Self {
+        let env_theme = std::env::var(FLUVIO_THEME)
+            .ok()
+            .filter(|theme| !theme.trim().is_empty())
+            .and_then(|theme| match theme.parse() {
+                Ok(theme) => Some(theme),
+                Err(err) => {
+                    eprintln!("warning: ignoring {FLUVIO_THEME}: {err}");
+                    None
+                }
+            });
+        let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        Self::select(env_theme, no_color, configured)
+    }
+
+    fn select(env_theme: Option<Theme>, no_color: bool, configured: Option<Theme>) -> Self {
+        let theme = env_theme.or(configured).unwrap_or_default();
+        if no_color && theme.is_colored() {
+            Self::NoColor
+        } else {
+            theme
+        }
+    }
+
+    pub fn is_colored(self) -> bool {
+        matches!(self, Self::Dark | Self::Light)
+    }
+
+    pub fn is_ascii(self) -> bool {
+        self == Self::Ascii
+    }
+
+    /// escape sequence of table header, if colors are shown
+    fn header_style(self) -> Option<&'static str> {
+        if !std::io::stdout().is_terminal() {
+            return None;
+        }
+        match self {
+            Self::Dark => Some("\x1B[1;36m"),
+            Self::Light => Some("\x1B[1;34m"),
+            Self::NoColor | Self::Ascii => None,
+        }
+    }
+
+    /// table as text, with styled header line
+    pub fn render_table(self, table: &Table, with_header: bool) -> String {
+        let rendered = table.to_string();
+        let rendered = self.text(&rendered);
+        match self.header_style().filter(|_| with_header) {
+            Some(style) => {
+                let (header, rest) = rendered.split_once('\n').unwrap_or((&rendered, ""));
+                format!("{style}{header}\x1B[0m\n{rest}")
+                    .trim_end_matches('\n')
+                    .to_owned()
+            }
+            None => rendered.into_owned(),
+        }
+    }
+
+    /// adapt text to theme, dropping colors and replacing symbols with ASCII if needed
+    pub fn text(self, text: &str) -> Cow<'_, str> {
+        let text = if self.is_colored() {
+            Cow::Borrowed(text)
+        } else {
+            strip_ansi(text)
+        };
+        if self.is_ascii() && !text.is_ascii() {
+            Cow::Owned(to_ascii(&text))
+        } else {
+            text
+        }
+    }
+}
+
+impl FromStr for Theme {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "dark" => Ok(Self::Dark),
+            "light" => Ok(Self::Light),
+            "no-color" | "nocolor" | "none" => Ok(Self::NoColor),
+            "ascii" => Ok(Self::Ascii),
+            other => Err(format!(
+                "unknown theme '{other}', expected dark, light, no-color or ascii"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Theme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Dark => "dark",
+            Self::Light => "light",
+            Self::NoColor => "no-color",
+            Self::Ascii => "ascii",
+        };
+        f.write_str(name)
+    }
+}
+
+/// set theme of the process, only first call has effect
+pub fn set_theme(theme: Theme) {
+    let _ = THEME.set(theme);
+}
+
+/// theme of the process, resolved from environment unless set
+pub fn theme() -> Theme {
+    *THEME.get_or_init(|| Theme::resolve(None))
+}
+
+/// remove ANSI escape sequences
+fn strip_ansi(text: &str) -> Cow<'_, str> {
+    if !text.contains('\x1B') {
+        return Cow::Borrowed(text);
+    }
+    let mut stripped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1B' {
+            // skip until final byte of control sequence
+            if chars.next() == Some('[') {
+                for c in chars.by_ref() {
+                    if c.is_ascii_alphabetic() {
+                        break;
+                    }
+                }
+            }
+        } else {
+            stripped.push(c);
+        }
+    }
+    Cow::Owned(stripped)
+}
+
+/// replace common symbols and drop other symbols and emoji,
+/// letters of any script are kept since they are part of names and records
+fn to_ascii(text: &str) -> String {
+    let mut ascii = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            c if c.is_ascii() => ascii.push(c),
+            '✅' | '✓' | '✔' | '👍' => ascii.push_str("[ok]"),
+            '❌' | '❎' | '✗' | '✘' => ascii.push_str("[x]"),
+            '⚠' | '❕' | '❗' | '💡' => ascii.push_str("[!]"),
+            '…' => ascii.push_str("..."),
+            '→' => ascii.push_str("->"),
+            '─' | '━' => ascii.push('-'),
+            '│' | '┃' => ascii.push('|'),
+            c if is_symbol(c) => {}
+            c => ascii.push(c),
+        }
+    }
+    // symbol without replacement at start of line leaves its separator
+    if !text.starts_with(' ') && ascii.starts_with(' ') {
+        ascii.trim_start().to_owned()
+    } else {
+        ascii
+    }
+}
+
+/// arrows, box drawing, dingbats and emoji, used to decorate messages
+fn is_symbol(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x2190..=0x21FF | 0x2500..=0x27BF | 0x2B00..=0x2BFF | 0xFE0F | 0x200D | 0x1F000..=0x1FAFF
+    )
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_select_theme() {
+        assert_eq!(Theme::select(None, false, None), Theme::Dark);
+        assert_eq!(Theme::select(None, false, Some(Theme::Light)), Theme::Light);
+        assert_eq!(
+            Theme::select(Some(Theme::Ascii), false, Some(Theme::Light)),
+            Theme::Ascii
+        );
+        assert_eq!(
+            Theme::select(None, true, Some(Theme::Light)),
+            Theme::NoColor
+        );
+        assert_eq!(Theme::select(Some(Theme::Ascii), true, None), Theme::Ascii);
+        assert!("solarized".parse::<Theme>().is_err());
+    }
+
+    #[test]
+    fn test_theme_text() {
+        let error = "\x1B[1;31merror:\x1B[0m topic not found";
+        assert_eq!(Theme::Dark.text(error), error);
+        assert_eq!(Theme::NoColor.text(error), "error: topic not found");
+        assert_eq!(
+            Theme::NoColor.text("✅ Successfully updated"),
+            "✅ Successfully updated"
+        );
+        assert_eq!(
+            Theme::Ascii.text("✅ Successfully updated"),
+            "[ok] Successfully updated"
+        );
+        assert_eq!(
+            Theme::Ascii.text("🎣 Fetching latest version…"),
+            "Fetching latest version..."
+        );
+        assert_eq!(
+            Theme::Ascii.text("✅ topic café-日本 created"),
+            "[ok] topic café-日本 created"
+        );
+    }
+}
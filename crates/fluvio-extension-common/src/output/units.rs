@@ -0,0 +1,117 @@
+//!
+//! # Units
+//!
+//! Human readable sizes and durations, ex: `1.2 GiB`, `3h 20m`.
+//! Commands with `--raw` show sizes in bytes and durations in seconds instead,
+//! so output can be processed by scripts.
+//!
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use bytesize::ByteSize;
+
+static RAW_UNITS: AtomicBool = AtomicBool::new(false);
+
+const DURATION_UNITS: [(&str, u128); 5] = [
+    ("d", 86_400_000),
+    ("h", 3_600_000),
+    ("m", 60_000),
+    ("s", 1_000),
+    ("ms", 1),
+];
+
+/// show sizes and durations of the process without units
+pub fn set_raw_units(raw: bool) {
+    RAW_UNITS.store(raw, Ordering::Relaxed);
+}
+
+/// size as shown in output, human readable unless raw units are set
+pub fn format_size(bytes: u64) -> String {
+    if RAW_UNITS.load(Ordering::Relaxed) {
+        bytes.to_string()
+    } else {
+        human_size(bytes)
+    }
+}
+
+/// duration as shown in output, human readable unless raw units are set
+pub fn format_duration(duration: Duration) -> String {
+    if RAW_UNITS.load(Ordering::Relaxed) {
+        raw_duration(duration)
+    } else {
+        human_duration(duration)
+    }
+}
+
+/// size in binary units with one decimal, ex: `1.2 GiB`
+pub fn human_size(bytes: u64) -> String {
+    ByteSize::b(bytes).to_string_as(true)
+}
+
+/// two most significant units of duration, ex: `3h 20m`, `1s 500ms`
+pub fn human_duration(duration: Duration) -> String {
+    let mut millis = duration.as_millis();
+    if millis == 0 {
+        return format!("{}ms", duration.as_micros() as f64 / 1000.0);
+    }
+    // at least one millisecond, so some unit matches
+    let first = DURATION_UNITS
+        .iter()
+        .position(|(_, unit_ms)| millis >= *unit_ms)
+        .unwrap_or_default();
+    let mut parts = Vec::with_capacity(2);
+    for (unit, unit_ms) in DURATION_UNITS.iter().skip(first).take(2) {
+        let count = millis / unit_ms;
+        millis %= unit_ms;
+        if count > 0 {
+            parts.push(format!("{count}{unit}"));
+        }
+    }
+    parts.join(" ")
+}
+
+/// seconds with millisecond precision, ex: `12000`, `1.5`
+fn raw_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match duration.subsec_millis() {
+        0 => secs.to_string(),
+        millis => format!("{secs}.{millis:03}")
+            .trim_end_matches('0')
+            .to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_human_size() {
+        assert_eq!(human_size(0), "0 B");
+        assert_eq!(human_size(1023), "1023 B");
+        assert_eq!(human_size(1024), "1.0 KiB");
+        assert_eq!(human_size(1536), "1.5 KiB");
+        assert_eq!(human_size(1_288_490_189), "1.2 GiB");
+        assert_eq!(human_size(u64::MAX), "16.0 EiB");
+    }
+
+    #[test]
+    fn test_human_duration() {
+        assert_eq!(human_duration(Duration::ZERO), "0ms");
+        assert_eq!(human_duration(Duration::from_micros(250)), "0.25ms");
+        assert_eq!(human_duration(Duration::from_millis(1500)), "1s 500ms");
+        assert_eq!(human_duration(Duration::from_secs(12_000)), "3h 20m");
+        assert_eq!(human_duration(Duration::from_secs(12_030)), "3h 20m");
+        assert_eq!(human_duration(Duration::from_secs(7 * 86_400)), "7d");
+        assert_eq!(human_duration(Duration::from_secs(86_400 + 60)), "1d");
+    }
+
+    #[test]
+    fn test_raw_duration() {
+        assert_eq!(raw_duration(Duration::from_secs(12_000)), "12000");
+        assert_eq!(raw_duration(Duration::from_millis(1500)), "1.5");
+        assert_eq!(raw_duration(Duration::from_millis(1025)), "1.025");
+    }
+}
@@ -11,27 +11,27 @@ use crate::target::TargetError;
 #[derive(Debug, Parser, Default, Clone)]
 pub struct TlsClientOpt {
     /// Enable TLS
-    #[arg(long)]
+    #[arg(long, env = "FLUVIO_TLS")]
     pub tls: bool,
 
     /// TLS: use client cert
-    #[arg(long)]
+    #[arg(long, env = "FLUVIO_TLS_ENABLE_CLIENT_CERT")]
     pub enable_client_cert: bool,
 
     /// Required if client cert is used
-    #[arg(long)]
+    #[arg(long, env = "FLUVIO_TLS_DOMAIN")]
     pub domain: Option<String>,
 
     /// Path to TLS ca cert, required when client cert is enabled
-    #[arg(long)]
+    #[arg(long, env = "FLUVIO_TLS_CA_CERT")]
     pub ca_cert: Option<PathBuf>,
 
     /// Path to TLS client certificate
-    #[arg(long)]
+    #[arg(long, env = "FLUVIO_TLS_CLIENT_CERT")]
     pub client_cert: Option<PathBuf>,
 
     /// Path to TLS client private key
-    #[arg(long)]
+    #[arg(long, env = "FLUVIO_TLS_CLIENT_KEY")]
     pub client_key: Option<PathBuf>,
 }
 
@@ -135,4 +135,15 @@ mod tests {
         let result: Result<TlsPolicy, _> = tls_opt.try_into();
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_env_for_all_opts() {
+        use clap::CommandFactory;
+
+        let cmd = TlsClientOpt::command();
+        for arg in cmd.get_arguments().filter(|arg| arg.get_id() != "help") {
+            let env = arg.get_env().expect("env var");
+            assert!(env.to_string_lossy().starts_with("FLUVIO_TLS"));
+        }
+    }
 }
@@ -88,15 +88,14 @@ impl<T: FluvioEncoder> Encoder<FluvioCodecData<T>> for FluvioCodec {
         let (src, version) = src;
 
         let size = src.write_size(version) as i32;
-        trace!(size = "encoding data with write size");
+        trace!(size, "encoding data with write size");
         buf.reserve(4 + size as usize);
 
         // First 4 bytes are the size of the message.
         // Then the message payload.
-        let mut len_slice = Vec::new();
-        size.encode(&mut len_slice, version)?;
-        buf.extend_from_slice(&len_slice);
-        buf.extend_from_slice(&src.as_bytes(version)?);
+        // Both are encoded directly into frame buffer to avoid intermediate allocations
+        size.encode(buf, version)?;
+        src.encode(buf, version)?;
 
         Ok(())
     }
@@ -218,6 +217,26 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_encode_length_prefixed() {
+        use bytes::BytesMut;
+        use tokio_util::codec::Encoder as _;
+
+        let data = "hello".to_owned();
+        let mut codec = FluvioCodec::new();
+        let mut buf = BytesMut::new();
+        codec.encode((data.clone(), 0), &mut buf).expect("encode");
+        codec.encode((data.clone(), 0), &mut buf).expect("encode");
+
+        let mut expected = vec![];
+        (data.write_size(0) as i32)
+            .encode(&mut expected, 0)
+            .expect("len");
+        expected.extend_from_slice(&Encoder::as_bytes(&data, 0).expect("bytes"));
+        expected.extend_from_slice(&expected.clone());
+        assert_eq!(&buf[..], &expected[..]);
+    }
+
     #[fluvio_future::test]
     async fn test_async_tcp_vec() {
         debug!("start running test");
@@ -63,6 +63,18 @@ pub enum ErrorCode {
     #[fluvio(tag = 73)]
     #[error("Partition is short-circuited")]
     PartitionShortCircuited,
+    #[fluvio(tag = 74)]
+    #[error("record timestamp {timestamp} differs from SPU clock by more than {max_skew_ms} ms")]
+    InvalidTimestamp { timestamp: i64, max_skew_ms: u64 },
+    #[fluvio(tag = 75)]
+    #[error("too many connections {scope}, limit is {max}")]
+    ConnectionLimitExceeded { scope: String, max: u32 },
+    #[fluvio(tag = 76)]
+    #[error("connections from {ip} are not allowed")]
+    ConnectionNotAllowed { ip: String },
+    #[fluvio(tag = 77)]
+    #[error("the cluster is in maintenance")]
+    ClusterInMaintenance,
 
     // Spu errors
     #[fluvio(tag = 1000)]
@@ -314,6 +326,22 @@ mod test {
         assert_tag!(ErrorCode::MessageTooLarge, 10, 0);
         assert_tag!(ErrorCode::PermissionDenied, 13, 0);
         assert_tag!(ErrorCode::StorageError, 56, 0);
+        assert_tag!(
+            ErrorCode::ConnectionLimitExceeded {
+                scope: "from 10.0.0.1".to_owned(),
+                max: 1
+            },
+            75,
+            0
+        );
+        assert_tag!(
+            ErrorCode::ConnectionNotAllowed {
+                ip: "10.0.0.1".to_owned()
+            },
+            76,
+            0
+        );
+        assert_tag!(ErrorCode::ClusterInMaintenance, 77, 0);
 
         // Spu errors
         assert_tag!(ErrorCode::SpuError, 1000, 0);
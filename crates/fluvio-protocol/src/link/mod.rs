@@ -1,5 +1,6 @@
 mod error_code;
 pub mod smartmodule;
+pub mod token;
 pub mod versions;
 
 pub use error_code::*;
@@ -0,0 +1,45 @@
+//!
+//! # Token authentication
+//!
+//! Clients of clusters authenticating with bearer tokens send token as first request
+//! of connection to SC. Connection is closed if token is rejected.
+//!
+
+use std::fmt;
+
+use crate::{Encoder, Decoder};
+use crate::api::Request;
+
+use super::ErrorCode;
+
+pub const TOKEN_AUTH_API_KEY: u16 = 9;
+
+#[derive(Decoder, Encoder, Default)]
+pub struct TokenAuthRequest {
+    pub token: String,
+}
+
+impl TokenAuthRequest {
+    pub fn new(token: impl Into<String>) -> Self {
+        Self {
+            token: token.into(),
+        }
+    }
+}
+
+/// token is credential, it is never logged
+impl fmt::Debug for TokenAuthRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("TokenAuthRequest")
+    }
+}
+
+impl Request for TokenAuthRequest {
+    const API_KEY: u16 = TOKEN_AUTH_API_KEY;
+    type Response = TokenAuthResponse;
+}
+
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct TokenAuthResponse {
+    pub error_code: ErrorCode,
+}
@@ -1,4 +1,5 @@
 use std::io::{Error as IoError, ErrorKind};
+use std::time::Duration;
 
 use crate::bytes::{BufMut, Buf};
 use crate::{Encoder, Version, Decoder};
@@ -8,6 +9,12 @@ use super::ErrorCode;
 
 pub const VERSIONS_API_KEY: u16 = 18;
 pub const V10_PLATFORM: i16 = 2;
+/// client sends how long it waits for responses
+pub const REQUEST_TIMEOUT_VERSION: i16 = 3;
+/// server sends its wall clock
+pub const SERVER_TIME_VERSION: i16 = 4;
+/// server sends optional features it handles
+pub const CAPABILITIES_VERSION: i16 = 5;
 
 // -----------------------------------
 // ApiVersionsRequest
@@ -21,11 +28,22 @@ pub struct ApiVersionsRequest {
     pub client_os: String,
     #[fluvio(min_version = 1)]
     pub client_arch: String,
+    /// how long client waits for each response on this connection, in milliseconds
+    #[fluvio(min_version = REQUEST_TIMEOUT_VERSION)]
+    pub request_timeout_ms: Option<u32>,
+}
+
+impl ApiVersionsRequest {
+    /// server abandons requests on this connection after timeout, client has stopped waiting
+    pub fn request_timeout(&self) -> Option<Duration> {
+        self.request_timeout_ms
+            .map(|ms| Duration::from_millis(ms as u64))
+    }
 }
 
 impl Request for ApiVersionsRequest {
     const API_KEY: u16 = VERSIONS_API_KEY;
-    const DEFAULT_API_VERSION: i16 = V10_PLATFORM;
+    const DEFAULT_API_VERSION: i16 = CAPABILITIES_VERSION;
     type Response = ApiVersionsResponse;
 }
 
@@ -40,6 +58,10 @@ pub struct ApiVersionsResponse {
     pub error_code: ErrorCode,
     pub api_keys: ApiVersions,
     pub platform_version: PlatformVersion,
+    #[fluvio(min_version = SERVER_TIME_VERSION)]
+    pub server_time: ServerTime,
+    #[fluvio(min_version = CAPABILITIES_VERSION)]
+    pub capabilities: ServerCapabilities,
 }
 
 #[derive(Decoder, Encoder, Default, Clone, Debug, Eq, PartialEq)]
@@ -119,6 +141,126 @@ impl Encoder for PlatformVersion {
     }
 }
 
+/// Wall clock of server when response was created, in milliseconds since UNIX epoch.
+///
+/// Sent from `SERVER_TIME_VERSION`. Older servers answer newer requests without it,
+/// so it's decoded only if bytes remain.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub struct ServerTime(Option<i64>);
+
+impl ServerTime {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn now() -> Self {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis() as i64)
+            .ok();
+        Self(now)
+    }
+
+    pub fn from_millis(millis: i64) -> Self {
+        Self(Some(millis))
+    }
+
+    pub fn millis(&self) -> Option<i64> {
+        self.0
+    }
+}
+
+impl Decoder for ServerTime {
+    fn decode<T>(&mut self, src: &mut T, version: Version) -> Result<(), IoError>
+    where
+        T: Buf,
+    {
+        if src.remaining() < 8 {
+            self.0 = None;
+            return Ok(());
+        }
+        let mut millis: i64 = 0;
+        millis.decode(src, version)?;
+        self.0 = Some(millis);
+        Ok(())
+    }
+}
+
+impl Encoder for ServerTime {
+    fn write_size(&self, version: Version) -> usize {
+        self.0.map_or(0, |millis| millis.write_size(version))
+    }
+
+    fn encode<T>(&self, dest: &mut T, version: Version) -> Result<(), IoError>
+    where
+        T: BufMut,
+    {
+        match self.0 {
+            Some(millis) => millis.encode(dest, version),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Optional features handled by the server itself
+#[derive(Decoder, Encoder, Default, Clone, Debug, Eq, PartialEq)]
+pub struct Capabilities {
+    /// names of compression codecs server can decode and encode
+    pub compression: Vec<String>,
+    /// SmartModule API version of engine, 0 if SmartModules are not supported
+    pub smartmodule_version: i16,
+    /// server takes part in mirroring between clusters
+    pub mirroring: bool,
+}
+
+/// Capabilities of server, written after server time.
+///
+/// Sent from `CAPABILITIES_VERSION`. Older servers answer newer requests without them,
+/// so they are decoded only if bytes remain.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct ServerCapabilities(Option<Capabilities>);
+
+impl ServerCapabilities {
+    pub fn new(capabilities: Capabilities) -> Self {
+        Self(Some(capabilities))
+    }
+
+    pub fn get(&self) -> Option<&Capabilities> {
+        self.0.as_ref()
+    }
+}
+
+impl Decoder for ServerCapabilities {
+    fn decode<T>(&mut self, src: &mut T, version: Version) -> Result<(), IoError>
+    where
+        T: Buf,
+    {
+        if !src.has_remaining() {
+            self.0 = None;
+            return Ok(());
+        }
+        let mut capabilities = Capabilities::default();
+        capabilities.decode(src, version)?;
+        self.0 = Some(capabilities);
+        Ok(())
+    }
+}
+
+impl Encoder for ServerCapabilities {
+    fn write_size(&self, version: Version) -> usize {
+        self.0
+            .as_ref()
+            .map_or(0, |capabilities| capabilities.write_size(version))
+    }
+
+    fn encode<T>(&self, dest: &mut T, version: Version) -> Result<(), IoError>
+    where
+        T: BufMut,
+    {
+        match &self.0 {
+            Some(capabilities) => capabilities.encode(dest, version),
+            None => Ok(()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -157,18 +299,121 @@ mod tests {
                 error_code: ErrorCode::None,
                 api_keys: vec![],
                 platform_version,
+                server_time: ServerTime::from_millis(1_700_000_000_000),
+                capabilities: ServerCapabilities::new(Capabilities {
+                    compression: vec!["none".to_owned(), "gzip".to_owned()],
+                    smartmodule_version: 22,
+                    mirroring: true,
+                }),
             }
         }
 
+        let version = ApiVersionsRequest::DEFAULT_API_VERSION;
         let api_version = api_versions();
         let mut api_versions_buffer: Vec<u8> = vec![];
-        api_version.encode(&mut api_versions_buffer, 0).unwrap();
+        api_version
+            .encode(&mut api_versions_buffer, version)
+            .unwrap();
 
         let mut decoded_api_version = ApiVersionsResponse::default();
         decoded_api_version
-            .decode(&mut (&*api_versions_buffer), 0)
+            .decode(&mut (&*api_versions_buffer), version)
             .unwrap();
 
         assert_eq!(api_version, decoded_api_version);
     }
+
+    #[test]
+    fn test_decode_response_without_server_time() {
+        let response = ApiVersionsResponse {
+            platform_version: PlatformVersion::from(semver::Version::new(0, 11, 0)),
+            server_time: ServerTime::from_millis(1_700_000_000_000),
+            ..Default::default()
+        };
+        // not written for older clients
+        let mut buffer: Vec<u8> = vec![];
+        response.encode(&mut buffer, V10_PLATFORM).unwrap();
+        assert_eq!(buffer.len(), response.write_size(V10_PLATFORM));
+
+        let mut decoded = ApiVersionsResponse::default();
+        decoded.decode(&mut (&*buffer), V10_PLATFORM).unwrap();
+        assert_eq!(decoded.server_time.millis(), None);
+        assert_eq!(decoded.platform_version, response.platform_version);
+
+        // older server answering newer request
+        let mut decoded = ApiVersionsResponse::default();
+        decoded
+            .decode(&mut (&*buffer), SERVER_TIME_VERSION)
+            .unwrap();
+        assert_eq!(decoded.server_time.millis(), None);
+    }
+
+    #[test]
+    fn test_decode_response_without_capabilities() {
+        let response = ApiVersionsResponse {
+            platform_version: PlatformVersion::from(semver::Version::new(0, 12, 0)),
+            server_time: ServerTime::from_millis(1_700_000_000_000),
+            ..Default::default()
+        };
+        let mut buffer: Vec<u8> = vec![];
+        response.encode(&mut buffer, SERVER_TIME_VERSION).unwrap();
+
+        // older server answering newer request
+        let mut decoded = ApiVersionsResponse::default();
+        decoded
+            .decode(&mut (&*buffer), CAPABILITIES_VERSION)
+            .unwrap();
+        assert_eq!(decoded.server_time.millis(), Some(1_700_000_000_000));
+        assert_eq!(decoded.capabilities.get(), None);
+    }
+
+    #[test]
+    fn test_capabilities_versioned() {
+        let response = ApiVersionsResponse {
+            platform_version: PlatformVersion::from(semver::Version::new(0, 12, 0)),
+            server_time: ServerTime::from_millis(1_700_000_000_000),
+            capabilities: ServerCapabilities::new(Capabilities {
+                compression: vec!["none".to_owned()],
+                smartmodule_version: 22,
+                mirroring: false,
+            }),
+            ..Default::default()
+        };
+
+        // not written for clients expecting only server time
+        let mut buffer: Vec<u8> = vec![];
+        response.encode(&mut buffer, SERVER_TIME_VERSION).unwrap();
+        assert_eq!(buffer.len(), response.write_size(SERVER_TIME_VERSION));
+
+        let mut decoded = ApiVersionsResponse::default();
+        decoded
+            .decode(&mut (&*buffer), SERVER_TIME_VERSION)
+            .unwrap();
+        assert_eq!(decoded.server_time.millis(), Some(1_700_000_000_000));
+        assert_eq!(decoded.capabilities.get(), None);
+    }
+
+    #[test]
+    fn test_request_timeout_versioned() {
+        let request = ApiVersionsRequest {
+            client_version: "0.12.0".to_owned(),
+            request_timeout_ms: Some(5000),
+            ..Default::default()
+        };
+
+        let mut buffer: Vec<u8> = vec![];
+        request
+            .encode(&mut buffer, REQUEST_TIMEOUT_VERSION)
+            .unwrap();
+        let decoded =
+            ApiVersionsRequest::decode_from(&mut (&*buffer), REQUEST_TIMEOUT_VERSION).unwrap();
+        assert_eq!(decoded.request_timeout(), Some(Duration::from_secs(5)));
+
+        // older versions don't carry timeout
+        let mut buffer: Vec<u8> = vec![];
+        request.encode(&mut buffer, V10_PLATFORM).unwrap();
+        let decoded = ApiVersionsRequest::decode_from(&mut (&*buffer), V10_PLATFORM).unwrap();
+        assert_eq!(decoded.client_version, "0.12.0");
+        assert_eq!(decoded.request_timeout(), None);
+    }
 }
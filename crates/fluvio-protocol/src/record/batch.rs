@@ -291,6 +291,7 @@ impl Batch {
     ) -> impl Iterator<Item = ConsumerRecord> {
         let base_offset = self.base_offset;
         let first_timestamp = self.header.first_timestamp;
+        let batch_last_offset = base_offset + self.records.len() as Offset - 1;
 
         self.records
             .into_iter()
@@ -299,6 +300,7 @@ impl Batch {
                 partition,
                 offset: base_offset + relative as Offset,
                 timestamp_base: first_timestamp,
+                batch_last_offset,
                 record,
             })
     }
@@ -337,6 +339,17 @@ impl Batch<RawRecords> {
 
         Ok(records)
     }
+
+    /// compress records of uncompressed batch, compressed batches are left as is
+    pub fn compress(&mut self, compression: Compression) -> Result<(), CompressionError> {
+        if self.get_compression()? != Compression::None {
+            return Ok(());
+        }
+        self.records = RawRecords(compression.compress(&self.records.0)?);
+        self.header.set_compression(compression);
+        self.batch_len = self.calc_batch_len();
+        Ok(())
+    }
 }
 
 impl<T: Into<MemoryRecords>> From<T> for Batch {
@@ -842,10 +855,13 @@ mod test {
         assert_eq!(consumer_records[0].offset(), 0);
         assert_eq!(consumer_records[1].offset(), 1);
         assert_eq!(consumer_records[2].offset(), 2);
+        assert!(!consumer_records[1].is_batch_end());
+        assert!(consumer_records[2].is_batch_end());
 
         consumer_records.iter().for_each(|record| {
             assert_eq!(record.timestamp(), 1_500_000_000);
             assert_eq!(record.partition, partition_id);
+            assert_eq!(record.batch_last_offset(), 2);
         });
     }
 
@@ -946,6 +962,26 @@ mod test {
         assert_eq!(batch.len(), 3);
     }
 
+    #[test]
+    fn test_compress_raw_records() {
+        let mem_records = vec![Record::new("a"), Record::new("b")];
+        let mut batch: Batch<RawRecords> = Batch::try_from(Batch::from(mem_records)).unwrap();
+
+        batch.compress(Compression::Gzip).expect("compress");
+        assert_eq!(batch.get_compression().unwrap(), Compression::Gzip);
+        assert!(batch.validate_decoding());
+        let compressed = batch.records.0.clone();
+
+        // already compressed batch is kept
+        batch.compress(Compression::Lz4).expect("compress");
+        assert_eq!(batch.get_compression().unwrap(), Compression::Gzip);
+        assert_eq!(batch.records.0, compressed);
+
+        let records = batch.memory_records().unwrap();
+        assert_eq!(records[0].value.as_ref(), b"a");
+        assert_eq!(records[1].value.as_ref(), b"b");
+    }
+
     #[test]
     fn test_truncate_incomplete_raw_records_in_memory_records() {
         // then
@@ -392,6 +392,10 @@ impl<R: Clone> Clone for RecordSet<R> {
     }
 }
 
+/// record attribute set when headers follow the value as key and value pairs.
+/// Records without headers are encoded as before headers were supported.
+const HEADERS_ATTR: i8 = 0x01;
+
 #[derive(Decoder, Default, Encoder, Debug, Clone)]
 pub struct RecordHeader {
     attributes: i8,
@@ -427,6 +431,54 @@ impl RecordHeader {
     pub fn get_timestamp_delta(&self) -> Timestamp {
         self.timestamp_delta
     }
+
+    fn has_headers(&self) -> bool {
+        self.attributes & HEADERS_ATTR != 0
+    }
+}
+
+/// Key and value pair attached to record, encoded same as Kafka record headers
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct HeaderEntry {
+    pub key: String,
+    pub value: RecordData,
+}
+
+impl HeaderEntry {
+    pub fn new(key: impl Into<String>, value: impl Into<RecordData>) -> Self {
+        Self {
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+}
+
+impl Encoder for HeaderEntry {
+    fn write_size(&self, version: Version) -> usize {
+        RecordData::from(self.key.as_bytes().to_vec()).write_size(version)
+            + self.value.write_size(version)
+    }
+
+    fn encode<T>(&self, dest: &mut T, version: Version) -> Result<(), Error>
+    where
+        T: BufMut,
+    {
+        RecordData::from(self.key.as_bytes().to_vec()).encode(dest, version)?;
+        self.value.encode(dest, version)
+    }
+}
+
+impl Decoder for HeaderEntry {
+    fn decode<T>(&mut self, src: &mut T, version: Version) -> Result<(), Error>
+    where
+        T: Buf,
+    {
+        let mut key = RecordData::default();
+        key.decode(src, version)?;
+        self.key = String::from_utf8(key.as_ref().to_vec())
+            .map_err(|err| Error::new(ErrorKind::InvalidData, err))?;
+        self.value.decode(src, version)
+    }
 }
 
 #[derive(Default, Clone)]
@@ -434,7 +486,7 @@ pub struct Record<B = RecordData> {
     pub preamble: RecordHeader,
     pub key: Option<B>,
     pub value: B,
-    pub headers: i64,
+    pub headers: Vec<HeaderEntry>,
 }
 
 impl<B: Default> Record<B> {
@@ -472,6 +524,21 @@ impl<B: Default> Record<B> {
     pub fn into_key(self) -> Option<B> {
         self.key
     }
+
+    /// value of header with the key, first one if there are many
+    pub fn header(&self, key: &str) -> Option<&RecordData> {
+        self.headers
+            .iter()
+            .find(|header| header.key == key)
+            .map(|header| &header.value)
+    }
+
+    /// set header, replacing headers with the same key
+    pub fn set_header(&mut self, key: impl Into<String>, value: impl Into<RecordData>) {
+        let header = HeaderEntry::new(key, value);
+        self.headers.retain(|existing| existing.key != header.key);
+        self.headers.push(header);
+    }
 }
 
 impl Record {
@@ -529,7 +596,12 @@ where
         let inner_size = self.preamble.write_size(version)
             + self.key.write_size(version)
             + self.value.write_size(version)
-            + self.headers.var_write_size();
+            + (self.headers.len() as i64).var_write_size()
+            + self
+                .headers
+                .iter()
+                .map(|header| header.write_size(version))
+                .sum::<usize>();
         let len: i64 = inner_size as i64;
         len.var_write_size() + inner_size
     }
@@ -539,10 +611,19 @@ where
         T: BufMut,
     {
         let mut out: Vec<u8> = Vec::new();
-        self.preamble.encode(&mut out, version)?;
+        let mut preamble = self.preamble.clone();
+        if self.headers.is_empty() {
+            preamble.attributes &= !HEADERS_ATTR;
+        } else {
+            preamble.attributes |= HEADERS_ATTR;
+        }
+        preamble.encode(&mut out, version)?;
         self.key.encode(&mut out, version)?;
         self.value.encode(&mut out, version)?;
-        self.headers.encode_varint(&mut out)?;
+        (self.headers.len() as i64).encode_varint(&mut out)?;
+        for header in &self.headers {
+            header.encode(&mut out, version)?;
+        }
         let len: i64 = out.len() as i64;
         trace!("record encode as {} bytes", len);
         len.encode_varint(dest)?;
@@ -575,7 +656,16 @@ where
         trace!("offset delta: {}", self.preamble.offset_delta);
         self.key.decode(src, version)?;
         self.value.decode(src, version)?;
-        self.headers.decode_varint(src)?;
+        let mut headers: i64 = 0;
+        headers.decode_varint(src)?;
+        // without attribute, count is not followed by headers
+        self.headers = if self.preamble.has_headers() {
+            (0..headers.max(0))
+                .map(|_| HeaderEntry::decode_from(src, version))
+                .collect::<Result<_, _>>()?
+        } else {
+            vec![]
+        };
 
         Ok(())
     }
@@ -591,6 +681,8 @@ pub struct ConsumerRecord {
     pub record: Record<RecordData>,
     /// Timestamp base of batch in which the records is present
     pub(crate) timestamp_base: Timestamp,
+    /// Offset of last record of batch in which the record is present
+    pub(crate) batch_last_offset: i64,
 }
 
 impl ConsumerRecord {
@@ -643,6 +735,21 @@ impl ConsumerRecord {
             self.timestamp_base + self.record.timestamp_delta()
         }
     }
+
+    /// Timestamp of first record of batch in which the record is present
+    pub fn batch_first_timestamp(&self) -> Timestamp {
+        self.timestamp_base
+    }
+
+    /// Offset of last record of batch in which the record is present
+    pub fn batch_last_offset(&self) -> i64 {
+        self.batch_last_offset
+    }
+
+    /// Returns true if this is the last record of its batch
+    pub fn is_batch_end(&self) -> bool {
+        self.offset >= self.batch_last_offset
+    }
 }
 
 impl AsRef<[u8]> for ConsumerRecord {
@@ -686,6 +793,56 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_encode_decode_record_headers() -> Result<(), IoError> {
+        let mut record = Record::new("dog");
+        record.set_header("origin", "cat");
+        record.set_header("hops", "a");
+        record.set_header("hops", "a,b");
+
+        let out = record.as_bytes(0)?;
+        assert_eq!(record.write_size(0), out.len());
+
+        let decoded = Record::<RecordData>::decode_from(&mut Cursor::new(&out), 0)?;
+        assert_eq!(decoded.value.as_ref(), b"dog");
+        assert_eq!(decoded.headers.len(), 2);
+        assert_eq!(
+            decoded.header("origin").map(|value| value.as_ref()),
+            Some(b"cat".as_ref())
+        );
+        assert_eq!(
+            decoded.header("hops").map(|value| value.as_ref()),
+            Some(b"a,b".as_ref())
+        );
+        assert!(decoded.header("unknown").is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_record_without_headers() -> Result<(), IoError> {
+        // encoding of record before headers, with header count as varint
+        let mut expected = vec![];
+        let mut inner = vec![];
+        RecordHeader::default().encode(&mut inner, 0)?;
+        None::<RecordData>.encode(&mut inner, 0)?;
+        RecordData::from("dog").encode(&mut inner, 0)?;
+        0i64.encode_varint(&mut inner)?;
+        (inner.len() as i64).encode_varint(&mut expected)?;
+        expected.extend(inner);
+
+        let mut record = Record::new("dog");
+        assert_eq!(record.as_bytes(0)?.as_ref(), expected.as_slice());
+
+        // headers removed, attribute is cleared too
+        record.set_header("origin", "cat");
+        assert_ne!(record.as_bytes(0)?.as_ref(), expected.as_slice());
+        record.headers.clear();
+        assert_eq!(record.as_bytes(0)?.as_ref(), expected.as_slice());
+
+        Ok(())
+    }
+
     /// test decoding of records when one of the batch was truncated
     #[test]
     fn test_decode_batch_truncation() {
@@ -791,6 +948,7 @@ mod test {
             offset: 0,
             partition: 0,
             record: Default::default(),
+            batch_last_offset: 0,
         };
 
         assert_eq!(record.timestamp(), NO_TIMESTAMP);
@@ -799,6 +957,7 @@ mod test {
             offset: 0,
             partition: 0,
             record: Default::default(),
+            batch_last_offset: 0,
         };
         assert_eq!(record.timestamp(), NO_TIMESTAMP);
     }
@@ -810,6 +969,7 @@ mod test {
             offset: 0,
             partition: 0,
             record: Default::default(),
+            batch_last_offset: 0,
         };
 
         assert_eq!(record.timestamp(), 1_000_000_000);
@@ -820,6 +980,7 @@ mod test {
             record: memory_record,
             offset: 0,
             partition: 0,
+            batch_last_offset: 0,
         };
         assert_eq!(record.timestamp(), 1_000_000_800);
     }
@@ -4,7 +4,11 @@ use fluvio_run::RunCmd;
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cmd: RunCmd = RunCmd::parse();
 
-    fluvio_future::subscriber::init_tracer(None);
+    match cmd {
+        // SPU log level can be changed at runtime
+        RunCmd::SPU(_) => fluvio_spu::init_tracer(),
+        _ => fluvio_future::subscriber::init_tracer(None),
+    }
 
     cmd.process()?;
     Ok(())
@@ -24,6 +24,8 @@ pub enum AdminPublicApiKey {
     Watch = 1004,
     Mirroring = 1005,
     Update = 1006,
+    TopicUsage = 1007,
+    IpFilter = 1008,
 }
 
 impl Default for AdminPublicApiKey {
@@ -0,0 +1,7 @@
+pub use fluvio_controlplane_metadata::clusterconfig::*;
+
+use crate::{AdminSpec, CreatableAdminSpec};
+
+impl AdminSpec for ClusterConfigSpec {}
+
+impl CreatableAdminSpec for ClusterConfigSpec {}
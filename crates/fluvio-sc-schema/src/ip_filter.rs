@@ -0,0 +1,62 @@
+//!
+//! # IP Filter
+//!
+//! Allow and deny lists of networks accepted by SC public service.
+//! Request without update returns current lists.
+//!
+
+use anyhow::Result;
+
+use fluvio_protocol::{Decoder, Encoder, Version};
+use fluvio_protocol::api::Request;
+use fluvio_protocol::link::ErrorCode;
+
+use crate::{AdminPublicApiKey, TryEncodableFrom};
+
+/// Networks in CIDR notation
+#[derive(Encoder, Decoder, Default, Debug, Clone, PartialEq, Eq)]
+pub struct IpFilterRules {
+    pub allow: Vec<String>,
+    pub deny: Vec<String>,
+}
+
+#[derive(Encoder, Decoder, Default, Debug, Clone)]
+pub struct IpFilterRequest {
+    /// replace current rules
+    pub update: Option<IpFilterRules>,
+}
+
+impl IpFilterRequest {
+    pub fn get() -> Self {
+        Self::default()
+    }
+
+    pub fn set(rules: IpFilterRules) -> Self {
+        Self {
+            update: Some(rules),
+        }
+    }
+}
+
+impl Request for IpFilterRequest {
+    const API_KEY: u16 = AdminPublicApiKey::IpFilter as u16;
+    const DEFAULT_API_VERSION: i16 = 0;
+    type Response = IpFilterResponse;
+}
+
+impl TryEncodableFrom<IpFilterRequest> for IpFilterRequest {
+    fn try_encode_from(input: IpFilterRequest, _version: Version) -> Result<Self> {
+        Ok(input)
+    }
+
+    fn downcast(&self) -> Result<Option<IpFilterRequest>> {
+        Ok(Some(self.clone()))
+    }
+}
+
+#[derive(Encoder, Decoder, Default, Debug, Clone, PartialEq)]
+pub struct IpFilterResponse {
+    pub error_code: ErrorCode,
+    /// rules in effect after request
+    pub rules: IpFilterRules,
+}
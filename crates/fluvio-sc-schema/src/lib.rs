@@ -10,8 +10,10 @@ pub mod shared;
 pub mod tableformat;
 pub mod mirror;
 pub mod mirroring;
+pub mod clusterconfig;
 
 pub mod remote_file;
+pub mod ip_filter;
 
 mod apis;
 mod request;
@@ -416,6 +416,7 @@ mod create {
 
     use anyhow::{anyhow, Result};
 
+    use fluvio_controlplane_metadata::clusterconfig::ClusterConfigSpec;
     use fluvio_controlplane_metadata::mirror::MirrorSpec;
     use fluvio_protocol::bytes::{BufMut, Buf};
     use fluvio_protocol::{Encoder, Decoder};
@@ -674,4 +675,7 @@ mod create {
             }
         }
     }
+
+    // only created with dynamic object protocol
+    impl ClassicCreatableAdminSpec for ClusterConfigSpec {}
 }
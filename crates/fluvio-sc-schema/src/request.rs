@@ -17,6 +17,8 @@ use fluvio_protocol::core::Decoder;
 use fluvio_protocol::link::versions::ApiVersionsRequest;
 
 use crate::mirroring::ObjectMirroringRequest;
+use crate::topic::TopicUsageRequest;
+use crate::ip_filter::IpFilterRequest;
 use crate::AdminPublicApiKey;
 use crate::objects::{
     ObjectApiCreateRequest, ObjectApiDeleteRequest, ObjectApiListRequest, ObjectApiUpdateRequest,
@@ -33,6 +35,8 @@ pub enum AdminPublicDecodedRequest {
     WatchRequest(RequestMessage<ObjectApiWatchRequest>),
     MirroringRequest(RequestMessage<ObjectMirroringRequest>),
     UpdateRequest(RequestMessage<ObjectApiUpdateRequest>),
+    TopicUsageRequest(RequestMessage<TopicUsageRequest>),
+    IpFilterRequest(RequestMessage<IpFilterRequest>),
 }
 
 impl Default for AdminPublicDecodedRequest {
@@ -93,6 +97,14 @@ impl ApiMessage for AdminPublicDecodedRequest {
                 header,
                 ObjectApiUpdateRequest::decode_from(src, version)?,
             ))),
+            AdminPublicApiKey::TopicUsage => Ok(Self::TopicUsageRequest(RequestMessage::new(
+                header,
+                TopicUsageRequest::decode_from(src, version)?,
+            ))),
+            AdminPublicApiKey::IpFilter => Ok(Self::IpFilterRequest(RequestMessage::new(
+                header,
+                IpFilterRequest::decode_from(src, version)?,
+            ))),
         }
     }
 }
@@ -1,4 +1,7 @@
 pub use fluvio_controlplane_metadata::topic::*;
+pub use usage::*;
+
+mod usage;
 
 pub mod validate {
     use crate::shared::validate_resource_name;
@@ -0,0 +1,57 @@
+//!
+//! # Topic Usage
+//!
+//! Size history of a topic, sampled periodically by SC from partition sizes reported by SPUs.
+//!
+
+use anyhow::Result;
+
+use fluvio_protocol::{Decoder, Encoder, Version};
+use fluvio_protocol::api::Request;
+use fluvio_protocol::link::ErrorCode;
+
+use crate::{AdminPublicApiKey, TryEncodableFrom};
+
+#[derive(Encoder, Decoder, Default, Debug, Clone)]
+pub struct TopicUsageRequest {
+    pub topic: String,
+}
+
+impl TopicUsageRequest {
+    pub fn new(topic: impl Into<String>) -> Self {
+        Self {
+            topic: topic.into(),
+        }
+    }
+}
+
+impl Request for TopicUsageRequest {
+    const API_KEY: u16 = AdminPublicApiKey::TopicUsage as u16;
+    const DEFAULT_API_VERSION: i16 = 0;
+    type Response = TopicUsageResponse;
+}
+
+impl TryEncodableFrom<TopicUsageRequest> for TopicUsageRequest {
+    fn try_encode_from(input: TopicUsageRequest, _version: Version) -> Result<Self> {
+        Ok(input)
+    }
+
+    fn downcast(&self) -> Result<Option<TopicUsageRequest>> {
+        Ok(Some(self.clone()))
+    }
+}
+
+#[derive(Encoder, Decoder, Default, Debug, Clone, PartialEq)]
+pub struct TopicUsageResponse {
+    pub error_code: ErrorCode,
+    /// samples in time order, oldest first
+    pub samples: Vec<TopicUsageSample>,
+}
+
+#[derive(Encoder, Decoder, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TopicUsageSample {
+    /// time of sample, in milliseconds since epoch
+    pub timestamp: i64,
+    /// total size of topic partitions, in bytes
+    pub size: u64,
+}
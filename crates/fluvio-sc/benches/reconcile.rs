@@ -0,0 +1,64 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use fluvio_future::task::run_block_on;
+use fluvio_sc::TopicReducer;
+use fluvio_sc::core::shard::DEFAULT_RECONCILE_SHARDS;
+use fluvio_sc::stores::clusterconfig::ClusterConfigLocalStore;
+use fluvio_sc::stores::partition::PartitionLocalStore;
+use fluvio_sc::stores::spu::{K8MetaItem, SpuLocalStore, SpuLocalStorePolicy};
+use fluvio_sc::stores::topic::{TopicAdminMd, TopicLocalStore, TopicResolution, TopicStatus};
+
+const TOPICS: usize = 1000;
+const PARTITIONS: u32 = 3;
+const REPLICATION: u32 = 2;
+const SPUS: i32 = 5;
+
+/// reducer with online SPUs and topics waiting for replica assignment
+fn setup(shards: usize) -> (TopicReducer, Vec<TopicAdminMd>) {
+    let spus =
+        SpuLocalStore::<K8MetaItem>::quick((0..SPUS).map(|id| (5001 + id, true, None)).collect());
+    let partitions = PartitionLocalStore::new_shared();
+    run_block_on(partitions.sync_all(vec![]));
+
+    let reducer = TopicReducer::new(
+        TopicLocalStore::new_shared(),
+        spus,
+        partitions,
+        ClusterConfigLocalStore::new_shared(),
+    )
+    .with_shards(shards);
+
+    let topics = (0..TOPICS)
+        .map(|i| {
+            let mut topic =
+                TopicAdminMd::with_spec(format!("topic-{i}"), (PARTITIONS, REPLICATION).into());
+            topic.set_status(TopicStatus::new(TopicResolution::Pending, vec![], ""));
+            topic
+        })
+        .collect();
+
+    (reducer, topics)
+}
+
+fn bench_reconcile(c: &mut Criterion, name: &str, shards: usize) {
+    let (reducer, topics) = setup(shards);
+    c.bench_function(name, |b| {
+        b.iter(|| run_block_on(reducer.process_requests(topics.clone())))
+    });
+}
+
+fn bench_serial_reconcile(c: &mut Criterion) {
+    bench_reconcile(c, "serial reconcile", 1);
+}
+
+fn bench_sharded_reconcile(c: &mut Criterion) {
+    bench_reconcile(c, "sharded reconcile", DEFAULT_RECONCILE_SHARDS);
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = bench_serial_reconcile, bench_sharded_reconcile
+}
+
+criterion_main!(benches);
@@ -8,10 +8,14 @@
 //!     3) cli parameters
 //!
 
+use std::collections::BTreeMap;
 use std::path::Path;
 use std::process;
+use std::str::FromStr;
 use std::path::PathBuf;
 use std::convert::TryFrom;
+use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::{anyhow, Result};
 use clap::Args;
@@ -19,14 +23,28 @@ use tracing::info;
 use tracing::debug;
 use clap::Parser;
 
+use fluvio::config::{TlsConfig as ClientTlsConfig, TlsPaths, TlsPolicy};
 use fluvio_types::print_cli_err;
 use fluvio_types::defaults::TLS_SERVER_SECRET_NAME;
 use fluvio_future::rust_tls::TlsAcceptor;
-
-use crate::services::auth::basic::BasicRbacPolicy;
+use fluvio_future::task::run_block_on;
+use fluvio_socket::SocketTuning;
+use fluvio_service::limits::ConnectionLimits;
+use fluvio_service::ip_filter::IpFilter;
+use fluvio_auth::x509::ScopeBindings;
+use fluvio_sc_schema::remote_file::RemoteMetadataFile;
+use k8_client::K8Config;
+
+use crate::services::auth::backend::{
+    HttpEndpoint, OpaBackend, SharedPolicyBackend, WebhookBackend, DEFAULT_BACKEND_TIMEOUT,
+};
+use crate::services::auth::basic::{BasicRbacPolicy, SecretRbacPolicy};
+use crate::services::auth::oidc::{DEFAULT_PRINCIPAL_CLAIM, DEFAULT_SCOPES_CLAIM, OidcConfig};
 use crate::config::ScConfig;
+use crate::secrets::{DEFAULT_SECRET_REFRESH, SecretRef, SecretResolver, SharedSecretResolver};
+use crate::validate::{ConfigReport, check_bind, check_metadata_dir};
 
-type Config = (ScConfig, Option<BasicRbacPolicy>);
+type Config = (ScConfig, Option<SharedPolicyBackend>);
 
 /// cli options
 #[derive(Debug, Parser)]
@@ -43,6 +61,34 @@ pub struct ScOpt {
     /// Address for internal service
     bind_private: Option<String>,
 
+    #[arg(long, value_name = "host:port", env = "FLV_SC_BIND_HEALTH")]
+    /// Address for HTTP liveness (/healthz) and readiness (/readyz) probes
+    bind_health: Option<String>,
+
+    #[arg(long, value_name = "host:port", env = "FLV_SC_METRICS_BIND")]
+    /// Address for HTTP server exposing Prometheus metrics on /metrics
+    metrics_bind: Option<String>,
+
+    /// Interval of canary probes producing and consuming records on a topic of each SPU,
+    /// ex: 30s. Canary is disabled if not set
+    #[arg(
+        long,
+        value_name = "duration",
+        value_parser = humantime::parse_duration,
+        env = "FLV_SC_CANARY_INTERVAL"
+    )]
+    canary_interval: Option<Duration>,
+
+    /// file of bearer token canary sends to OIDC authentication,
+    /// read again when canary reconnects so rotated tokens are picked up
+    #[arg(
+        long,
+        value_name = "path",
+        env = "FLV_SC_CANARY_TOKEN_FILE",
+        requires = "canary_interval"
+    )]
+    canary_token_file: Option<PathBuf>,
+
     // k8 namespace
     #[arg(short = 'n', long = "namespace", value_name = "namespace")]
     namespace: Option<String>,
@@ -50,23 +96,70 @@ pub struct ScOpt {
     #[clap(flatten)]
     tls: TlsConfig,
 
+    #[clap(flatten)]
+    socket: SocketOpt,
+
+    #[clap(flatten)]
+    connection_limits: ConnectionLimitOpt,
+
+    #[clap(flatten)]
+    ip_filter: IpFilterOpt,
+
+    #[clap(flatten)]
+    oidc: OidcOpt,
+
+    /// path or secret of scope bindings, ex: k8s://fluvio-auth/scopes.json
     #[arg(
         long = "authorization-scopes",
         value_name = "authorization scopes path",
         env
     )]
-    x509_auth_scopes: Option<PathBuf>,
+    x509_auth_scopes: Option<SecretRef>,
 
+    /// path or secret of basic policy, ex: vault://secret/data/fluvio#policy.
+    /// Policy of secret is reloaded when secret changes
     #[arg(
         long = "authorization-policy",
         value_name = "authorization policy path",
         env
     )]
-    auth_policy: Option<PathBuf>,
+    auth_policy: Option<SecretRef>,
+
+    /// authorize requests with webhook, decision requests are POSTed as JSON
+    /// and webhook answers with {"allowed": bool}
+    #[arg(
+        long = "authorization-webhook",
+        value_name = "url",
+        env,
+        conflicts_with_all = ["auth_policy", "auth_opa"]
+    )]
+    auth_webhook: Option<HttpEndpoint>,
+
+    /// authorize requests with OPA decision, requests are sent as input,
+    /// ex: http://localhost:8181/v1/data/fluvio/authz/allow
+    #[arg(
+        long = "authorization-opa",
+        value_name = "url",
+        env,
+        conflicts_with = "auth_policy"
+    )]
+    auth_opa: Option<HttpEndpoint>,
+
+    /// timeout of authorization webhook and OPA requests, requests are denied on timeout
+    #[arg(long = "authorization-timeout-ms", value_name = "ms", env)]
+    auth_timeout_ms: Option<u64>,
+
+    /// interval of refreshing secrets of providers
+    #[arg(long, value_name = "seconds", env = "FLV_SECRET_REFRESH_SECS")]
+    secret_refresh_secs: Option<u64>,
 
     /// only allow white list of controllers
     #[arg(long)]
     white_list: Vec<String>,
+
+    /// validate configuration, print report and exit without starting SC
+    #[arg(long)]
+    validate_config: bool,
 }
 
 #[derive(Debug, Args)]
@@ -76,6 +169,11 @@ pub struct ScOptRunMode {
     #[arg(long, value_name = "metadata path")]
     local: Option<PathBuf>,
 
+    /// run in local mode with metadata in database, ex: sqlite:/var/lib/fluvio/metadata.db.
+    /// Requires SC built with `sqlite` feature
+    #[arg(long, value_name = "store")]
+    metadata_store: Option<MetadataStore>,
+
     /// run on k8
     #[arg(long)]
     k8: bool,
@@ -85,9 +183,27 @@ pub struct ScOptRunMode {
     read_only: Option<PathBuf>,
 }
 
+/// database of local metadata
+#[derive(Debug, Clone)]
+pub enum MetadataStore {
+    Sqlite(PathBuf),
+}
+
+impl FromStr for MetadataStore {
+    type Err = anyhow::Error;
+
+    fn from_str(store: &str) -> Result<Self, Self::Err> {
+        match store.strip_prefix("sqlite:") {
+            Some(path) if !path.is_empty() => Ok(Self::Sqlite(path.into())),
+            _ => Err(anyhow!("{store}: expected sqlite:<path>")),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum RunMode<'a> {
     Local(&'a Path),
+    Sqlite(&'a Path),
     ReadOnly(&'a Path),
     K8s,
 }
@@ -96,12 +212,14 @@ impl ScOpt {
     pub fn mode(&self) -> RunMode<'_> {
         match (
             &self.run_mode.local,
+            &self.run_mode.metadata_store,
             &self.run_mode.read_only,
             self.run_mode.k8,
         ) {
-            (Some(metadata), None, false) => RunMode::Local(metadata),
-            (None, Some(path), false) => RunMode::ReadOnly(path),
-            (None, None, true) => RunMode::K8s,
+            (Some(metadata), None, None, false) => RunMode::Local(metadata),
+            (None, Some(MetadataStore::Sqlite(path)), None, false) => RunMode::Sqlite(path),
+            (None, None, Some(path), false) => RunMode::ReadOnly(path),
+            (None, None, None, true) => RunMode::K8s,
             _ => panic!("Params do not satisfy defined run modes"),
         }
     }
@@ -109,7 +227,14 @@ impl ScOpt {
     /// as sc configuration, 2nd part of tls configuration(proxy addr, tls config)
     /// 3rd part is path to read only metadata config
     #[allow(clippy::wrong_self_convention)]
-    fn as_sc_config(self) -> Result<(Config, Option<(String, TlsConfig)>)> {
+    /// configuration, and resolver of secrets which need refresh
+    fn as_sc_config(
+        self,
+    ) -> Result<(
+        Config,
+        Option<(String, TlsConfig)>,
+        Option<SharedSecretResolver>,
+    )> {
         let mut config = ScConfig::default();
 
         // apply our option
@@ -121,24 +246,86 @@ impl ScOpt {
             config.private_endpoint = private_addr;
         }
 
+        config.health_endpoint = self.bind_health;
+        config.metrics_endpoint = self.metrics_bind;
+        config.canary_interval = self.canary_interval;
+        config.canary_client.token_file = self.canary_token_file;
+
         if let Some(namespace) = self.namespace {
             config.namespace = namespace
         }
 
-        config.x509_auth_scopes = self.x509_auth_scopes;
         config.white_list = self.white_list.into_iter().collect();
         config.read_only_metadata = self.run_mode.read_only.is_some();
+        config.socket = self.socket.tuning();
+        config.connection_limits = self.connection_limits.limits();
+        config.ip_filter = self.ip_filter.filter()?;
+
+        // secrets of providers are resolved before SC starts, and refreshed while it runs
+        let refresh = self
+            .secret_refresh_secs
+            .map_or(DEFAULT_SECRET_REFRESH, Duration::from_secs);
+        let resolver = Arc::new(SecretResolver::new(
+            config.namespace.clone(),
+            refresh,
+            SecretResolver::process_dir(),
+        ));
+        let mut tls = self.tls;
+        let auth_policy = self.auth_policy;
+        let (x509_auth_scopes, secret_policy, tls_secrets, scope_mapping) = run_block_on(async {
+            let x509_auth_scopes = match &self.x509_auth_scopes {
+                Some(secret) => Some(resolver.resolve_file(secret).await?),
+                None => None,
+            };
+            let scope_mapping = match &self.oidc.auth_oidc_scope_mapping {
+                Some(secret) => Some(parse_scope_mapping(&resolver.resolve(secret).await?)?),
+                None => None,
+            };
+            let secret_policy = match &auth_policy {
+                Some(secret) if secret.as_file().is_none() => {
+                    Some(SecretRbacPolicy::load(secret.clone(), resolver.clone()).await?)
+                }
+                _ => None,
+            };
+            let tls_secrets = tls.resolve_secrets(&resolver).await?;
+            anyhow::Ok((x509_auth_scopes, secret_policy, tls_secrets, scope_mapping))
+        })?;
+        let scopes_secret = self
+            .x509_auth_scopes
+            .as_ref()
+            .is_some_and(|secret| secret.as_file().is_none());
+        let refreshed =
+            (scopes_secret || secret_policy.is_some() || tls_secrets).then(|| resolver.clone());
+        // proxy loads certificates and scopes again when their files change
+        if scopes_secret || tls_secrets {
+            tls.secrets = Some(resolver.clone());
+        }
+        config.x509_auth_scopes = x509_auth_scopes;
+        config.oidc = self.oidc.config(scope_mapping);
 
         // Set Configuration Authorization Policy
 
-        let policy = match self.auth_policy {
-            // Lookup a policy from a path
-            Some(p) => Some(BasicRbacPolicy::try_from(p)?),
-            // Use root-only default policy if no policy path is found;
-            None => None,
-        };
-
-        let mut tls = self.tls;
+        let timeout = self
+            .auth_timeout_ms
+            .map_or(DEFAULT_BACKEND_TIMEOUT, Duration::from_millis);
+        let policy: Option<SharedPolicyBackend> =
+            match (auth_policy, self.auth_webhook, self.auth_opa) {
+                // Lookup a policy from a path
+                (Some(SecretRef::File(p)), _, _) => Some(Arc::new(BasicRbacPolicy::try_from(p)?)),
+                // policy of secret is loaded with other secrets
+                (Some(_), _, _) => {
+                    secret_policy.map(|policy| Arc::new(policy) as SharedPolicyBackend)
+                }
+                (_, Some(endpoint), _) => Some(Arc::new(WebhookBackend::new(endpoint, timeout))),
+                (_, _, Some(endpoint)) => Some(Arc::new(OpaBackend::new(endpoint, timeout))),
+                // Use root-only default policy if no policy is configured;
+                _ => None,
+            };
+        if config.oidc.is_some() && policy.is_none() {
+            return Err(anyhow!(
+                "OIDC authentication requires authorization policy, webhook or OPA"
+            ));
+        }
 
         // if tls is on, we need to assign public service(internal) to another port
         // because public is used by proxy which forward traffic to internal public port
@@ -149,26 +336,254 @@ impl ScOpt {
                 .bind_non_tls_public
                 .clone()
                 .ok_or_else(|| anyhow!("non tls addr for public must be specified"))?;
+            if config.canary_interval.is_some() {
+                config.canary_client.endpoint = Some(proxy_addr.clone());
+                config.canary_client.tls = tls.canary_tls_policy()?;
+            }
             info!("TLS UPDATING");
             let _ = tls
                 .secret_name
                 .get_or_insert(TLS_SERVER_SECRET_NAME.to_string());
             info!("{:?}", tls);
 
-            Ok(((config, policy), Some((proxy_addr, tls))))
+            Ok(((config, policy), Some((proxy_addr, tls)), refreshed))
         } else {
-            Ok(((config, policy), None))
+            Ok(((config, policy), None, refreshed))
+        }
+    }
+
+    pub fn is_validate_config(&self) -> bool {
+        self.validate_config
+    }
+
+    /// check metadata, authorization, TLS material and addresses of configuration
+    pub fn validate(self) -> ConfigReport {
+        let mut report = ConfigReport::default();
+
+        match self.mode() {
+            RunMode::Local(metadata) => {
+                report.check("metadata path", check_metadata_dir(metadata));
+            }
+            RunMode::Sqlite(path) => {
+                let dir = path.parent().unwrap_or(Path::new("."));
+                report.check("metadata database", check_metadata_dir(dir));
+            }
+            RunMode::ReadOnly(path) => {
+                let result = RemoteMetadataFile::open(path).map(|metadata| {
+                    format!("{} has {} topics", path.display(), metadata.topics.len())
+                });
+                report.check("read only metadata", result);
+            }
+            RunMode::K8s => {
+                let result = K8Config::load()
+                    .map(|config| format!("namespace {}", config.namespace()))
+                    .map_err(|err| anyhow!("unable to load kubernetes config: {err}"));
+                report.check("kubernetes config", result);
+            }
+        }
+
+        if let Some(path) = self.auth_policy.as_ref().and_then(SecretRef::as_file) {
+            let result = BasicRbacPolicy::try_from(path.to_path_buf())
+                .map(|policy| format!("{} has {} roles", path.display(), policy.0.len()))
+                .map_err(|err| anyhow!("{}: {err}", path.display()));
+            report.check("authorization policy", result);
+        }
+
+        if let Some(path) = self.x509_auth_scopes.as_ref().and_then(SecretRef::as_file) {
+            let result = ScopeBindings::load(path)
+                .map(|_| format!("{} is valid", path.display()))
+                .map_err(|err| anyhow!("{}: {err}", path.display()));
+            report.check("authorization scopes", result);
+        }
+
+        if let Some(issuer) = &self.oidc.auth_oidc_issuer {
+            let result = if issuer.starts_with("https://") || issuer.starts_with("http://") {
+                Ok(format!("tokens of {issuer}"))
+            } else {
+                Err(anyhow!("{issuer} is not an http(s) URL"))
+            };
+            report.check("OIDC issuer", result);
+        }
+
+        match self.as_sc_config() {
+            Ok(((config, _), tls_option, _)) => {
+                if let Some((proxy_addr, tls)) = &tls_option {
+                    // rustls rejects private key which doesn't match certificate
+                    let result = tls
+                        .try_build_tls_acceptor()
+                        .map(|_| "certificate matches private key".to_owned());
+                    report.check("TLS certificate", result);
+                    report.check("TLS proxy address", check_bind(proxy_addr));
+                }
+                report.check("public address", check_bind(&config.public_endpoint));
+                report.check("private address", check_bind(&config.private_endpoint));
+                if let Some(health_endpoint) = &config.health_endpoint {
+                    report.check("health address", check_bind(health_endpoint));
+                }
+                if let Some(metrics_endpoint) = &config.metrics_endpoint {
+                    report.check("metrics address", check_bind(metrics_endpoint));
+                }
+            }
+            Err(err) => report.check("options", Err(err)),
         }
+
+        report
     }
 
     pub fn parse_cli_or_exit(self) -> (Config, Option<(String, TlsConfig)>) {
+        SecretResolver::remove_stale_dirs();
         match self.as_sc_config() {
             Err(err) => {
                 print_cli_err!(err);
                 process::exit(-1);
             }
-            Ok(config) => config,
+            Ok((config, tls_option, refreshed)) => {
+                if let Some(resolver) = refreshed {
+                    resolver.start_refresh();
+                }
+                (config, tls_option)
+            }
+        }
+    }
+}
+
+/// TCP options for accepted connections, unset options keep OS defaults
+#[derive(Debug, Args, Clone, Default)]
+pub struct SocketOpt {
+    /// enable or disable TCP_NODELAY
+    #[arg(long, value_name = "bool", env = "FLV_TCP_NODELAY")]
+    pub tcp_nodelay: Option<bool>,
+
+    /// socket send buffer size in bytes
+    #[arg(long, value_name = "bytes", env = "FLV_SOCKET_SEND_BUFFER_BYTES")]
+    pub socket_send_buffer_bytes: Option<usize>,
+
+    /// socket receive buffer size in bytes
+    #[arg(long, value_name = "bytes", env = "FLV_SOCKET_RECV_BUFFER_BYTES")]
+    pub socket_recv_buffer_bytes: Option<usize>,
+}
+
+impl SocketOpt {
+    pub fn tuning(&self) -> SocketTuning {
+        SocketTuning {
+            nodelay: self.tcp_nodelay,
+            send_buffer_size: self.socket_send_buffer_bytes,
+            recv_buffer_size: self.socket_recv_buffer_bytes,
+        }
+    }
+}
+
+/// Limits of concurrent connections to public service, unset means unlimited.
+/// Behind TLS proxy with authorization scopes, connections are counted from client address
+/// passed by proxy, otherwise from proxy address.
+#[derive(Debug, Args, Clone, Default)]
+pub struct ConnectionLimitOpt {
+    /// max concurrent connections from a source IP
+    #[arg(long, value_name = "integer", env = "FLV_MAX_CONNECTIONS_PER_IP")]
+    pub max_connections_per_ip: Option<u32>,
+
+    /// max concurrent connections of an authenticated principal
+    #[arg(
+        long,
+        value_name = "integer",
+        env = "FLV_MAX_CONNECTIONS_PER_PRINCIPAL"
+    )]
+    pub max_connections_per_principal: Option<u32>,
+}
+
+impl ConnectionLimitOpt {
+    pub fn limits(&self) -> ConnectionLimits {
+        ConnectionLimits {
+            max_per_ip: self.max_connections_per_ip,
+            max_per_principal: self.max_connections_per_principal,
+        }
+    }
+}
+
+/// Authentication of clients with bearer tokens of OIDC provider.
+/// Requests of token identities are authorized by policy, webhook or OPA
+#[derive(Debug, Args, Clone, Default)]
+pub struct OidcOpt {
+    /// issuer URL of OIDC provider, enables token authentication,
+    /// ex: https://sso.example.com/realms/fluvio
+    #[arg(
+        long,
+        value_name = "url",
+        env = "FLV_SC_AUTH_OIDC_ISSUER",
+        conflicts_with = "x509_auth_scopes"
+    )]
+    pub auth_oidc_issuer: Option<String>,
+
+    /// audience tokens must be issued for, not checked if not set
+    #[arg(
+        long,
+        value_name = "audience",
+        env = "FLV_SC_AUTH_OIDC_AUDIENCE",
+        requires = "auth_oidc_issuer"
+    )]
+    pub auth_oidc_audience: Option<String>,
+
+    /// claim of token with principal name
+    #[arg(long, value_name = "claim", default_value = DEFAULT_PRINCIPAL_CLAIM)]
+    pub auth_oidc_principal_claim: String,
+
+    /// claim of token with groups, roles or space separated scopes
+    #[arg(long, value_name = "claim", default_value = DEFAULT_SCOPES_CLAIM)]
+    pub auth_oidc_scopes_claim: String,
+
+    /// path or secret of JSON object mapping claim values to scopes, ex: {"admins": ["admin"]}.
+    /// Claim values are used as scopes if not set
+    #[arg(
+        long,
+        value_name = "scope mapping path",
+        env = "FLV_SC_AUTH_OIDC_SCOPE_MAPPING",
+        requires = "auth_oidc_issuer"
+    )]
+    pub auth_oidc_scope_mapping: Option<SecretRef>,
+
+    /// max age of cached keys of issuer, keys are also fetched for tokens of unknown keys
+    #[arg(long, value_name = "seconds", requires = "auth_oidc_issuer")]
+    pub auth_oidc_jwks_refresh_secs: Option<u64>,
+}
+
+impl OidcOpt {
+    pub fn config(
+        &self,
+        scope_mapping: Option<BTreeMap<String, Vec<String>>>,
+    ) -> Option<OidcConfig> {
+        let mut config = OidcConfig::new(self.auth_oidc_issuer.as_deref()?);
+        config.audience = self.auth_oidc_audience.clone();
+        config.principal_claim = self.auth_oidc_principal_claim.clone();
+        config.scopes_claim = self.auth_oidc_scopes_claim.clone();
+        config.scope_mapping = scope_mapping;
+        if let Some(secs) = self.auth_oidc_jwks_refresh_secs {
+            config.jwks_refresh = Duration::from_secs(secs);
         }
+        Some(config)
+    }
+}
+
+/// claim values and their scopes
+fn parse_scope_mapping(value: &[u8]) -> Result<BTreeMap<String, Vec<String>>> {
+    serde_json::from_slice(value).map_err(|err| anyhow!("invalid OIDC scope mapping: {err}"))
+}
+
+/// Networks accepted by public service, in CIDR notation.
+/// Behind TLS proxy, connections are filtered by proxy address.
+#[derive(Debug, Args, Clone, Default)]
+pub struct IpFilterOpt {
+    /// only accept connections from these networks
+    #[arg(long, value_name = "cidr", value_delimiter = ',', env = "FLV_IP_ALLOW")]
+    pub ip_allow: Vec<String>,
+
+    /// reject connections from these networks, takes precedence over allow list
+    #[arg(long, value_name = "cidr", value_delimiter = ',', env = "FLV_IP_DENY")]
+    pub ip_deny: Vec<String>,
+}
+
+impl IpFilterOpt {
+    pub fn filter(&self) -> Result<IpFilter> {
+        IpFilter::new(&self.ip_allow, &self.ip_deny)
     }
 }
 
@@ -178,19 +593,19 @@ pub struct TlsConfig {
     #[arg(long)]
     tls: bool,
 
-    /// TLS: path to server certificate
+    /// TLS: path to server certificate, or secret: k8s://, vault://, aws-sm://
     #[arg(long)]
     pub server_cert: Option<String>,
 
     #[arg(long)]
-    /// TLS: path to server private key
+    /// TLS: path to server private key, or secret: k8s://, vault://, aws-sm://
     pub server_key: Option<String>,
 
     /// TLS: enable client cert
     #[arg(long)]
     pub enable_client_cert: bool,
 
-    /// TLS: path to ca cert, required when client cert is enabled
+    /// TLS: path to ca cert, or secret, required when client cert is enabled
     #[arg(long)]
     pub ca_cert: Option<String>,
 
@@ -198,12 +613,68 @@ pub struct TlsConfig {
     /// TLS: address of non tls public service, required
     bind_non_tls_public: Option<String>,
 
+    /// TLS: domain of server certificate verified by canary,
+    /// required when client cert is enabled
+    #[arg(long)]
+    pub canary_tls_domain: Option<String>,
+
     #[arg(long)]
     /// Secret name used while adding to kubernetes
     pub secret_name: Option<String>,
+
+    /// resolver of certificates and scopes which are secrets, to restart proxy when they change
+    #[arg(skip)]
+    pub secrets: Option<SharedSecretResolver>,
 }
 
 impl TlsConfig {
+    /// replace secrets of certificates and key with files of their values,
+    /// returns whether any secret is resolved
+    async fn resolve_secrets(&mut self, resolver: &SecretResolver) -> Result<bool> {
+        let mut resolved = false;
+        for path in [
+            &mut self.server_cert,
+            &mut self.server_key,
+            &mut self.ca_cert,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            let secret: SecretRef = path.parse()?;
+            if secret.as_file().is_none() {
+                *path = resolver.resolve_file(&secret).await?.display().to_string();
+                resolved = true;
+            }
+        }
+        Ok(resolved)
+    }
+
+    /// TLS of canary connecting to proxy, server certificate is client certificate of canary
+    fn canary_tls_policy(&self) -> Result<TlsPolicy> {
+        let (Some(domain), Some(ca_cert)) = (&self.canary_tls_domain, &self.ca_cert) else {
+            if self.enable_client_cert {
+                return Err(anyhow!(
+                    "canary with client cert requires canary tls domain and ca cert"
+                ));
+            }
+            return Ok(TlsPolicy::Anonymous);
+        };
+        let cert = self
+            .server_cert
+            .as_ref()
+            .ok_or_else(|| anyhow!("missing server cert"))?;
+        let key = self
+            .server_key
+            .as_ref()
+            .ok_or_else(|| anyhow!("missing server key"))?;
+        Ok(TlsPolicy::Verified(ClientTlsConfig::Files(TlsPaths {
+            domain: domain.clone(),
+            key: key.into(),
+            cert: cert.into(),
+            ca_cert: ca_cert.into(),
+        })))
+    }
+
     pub fn try_build_tls_acceptor(&self) -> Result<TlsAcceptor> {
         let server_crt_path = self
             .server_cert
@@ -1,6 +1,7 @@
 mod sc_config;
 
 pub use self::sc_config::ScConfig;
+pub use self::sc_config::CanaryClientConfig;
 pub use self::sc_config::ScConfigBuilder;
 pub use self::sc_config::DEFAULT_NAMESPACE;
 
@@ -4,10 +4,17 @@
 //! Stores configuration parameter used by Streaming Controller module.
 //!
 use std::collections::HashSet;
+use std::time::Duration;
 use std::{io::Error as IoError, path::PathBuf};
 
 use fluvio_types::defaults::SC_PUBLIC_PORT;
 use fluvio_types::defaults::SC_PRIVATE_PORT;
+use fluvio_socket::SocketTuning;
+use fluvio_service::limits::ConnectionLimits;
+use fluvio_service::ip_filter::IpFilter;
+use fluvio::config::TlsPolicy;
+
+use crate::services::auth::oidc::OidcConfig;
 
 pub const DEFAULT_NAMESPACE: &str = "default";
 
@@ -28,7 +35,23 @@ pub struct ScConfig {
     pub private_endpoint: String,
     pub namespace: String,
     pub x509_auth_scopes: Option<PathBuf>,
+    /// authentication of clients with bearer tokens of OIDC issuer
+    pub oidc: Option<OidcConfig>,
     pub white_list: HashSet<String>,
+    /// TCP options for public and private servers
+    pub socket: SocketTuning,
+    /// address of HTTP server for liveness and readiness probes, disabled if not set
+    pub health_endpoint: Option<String>,
+    /// address of HTTP server exposing Prometheus metrics, disabled if not set
+    pub metrics_endpoint: Option<String>,
+    /// interval of canary probes of SPUs, disabled if not set
+    pub canary_interval: Option<Duration>,
+    /// connection of canary probes to public service
+    pub canary_client: CanaryClientConfig,
+    /// max concurrent connections to public service per source IP and principal
+    pub connection_limits: ConnectionLimits,
+    /// networks accepted by public service at startup, can be changed through admin API
+    pub ip_filter: IpFilter,
 }
 
 impl ::std::default::Default for ScConfig {
@@ -39,11 +62,29 @@ impl ::std::default::Default for ScConfig {
             private_endpoint: format!("0.0.0.0:{SC_PRIVATE_PORT}"),
             namespace: DEFAULT_NAMESPACE.to_owned(),
             x509_auth_scopes: None,
+            oidc: None,
             white_list: HashSet::new(),
+            socket: SocketTuning::default(),
+            health_endpoint: None,
+            metrics_endpoint: None,
+            canary_interval: None,
+            canary_client: CanaryClientConfig::default(),
+            connection_limits: ConnectionLimits::default(),
+            ip_filter: IpFilter::default(),
         }
     }
 }
 
+/// Connection of canary to public service, through TLS proxy if TLS is on
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct CanaryClientConfig {
+    /// address of TLS proxy, public endpoint is used if not set
+    pub endpoint: Option<String>,
+    pub tls: TlsPolicy,
+    /// file of bearer token for OIDC authentication, read again on every connection
+    pub token_file: Option<PathBuf>,
+}
+
 impl ScConfig {
     /// check if white list is enabled for controller name
     /// if white list is empty then everything is enabled,
@@ -0,0 +1,312 @@
+//!
+//! # Canary Controller
+//!
+//! Probes SPUs by producing and consuming tiny records on a system topic led by each SPU.
+//! End-to-end latency and failures are exported as metrics. Each record carries outcome of
+//! previous probe, so `fluvio cluster status` can read canary health from last records.
+//!
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Result, anyhow};
+use futures_util::StreamExt;
+use tracing::{debug, error, info, instrument, warn};
+
+use fluvio::{Fluvio, FluvioClusterConfig, Offset, RecordKey};
+use fluvio::consumer::ConsumerConfigExt;
+use fluvio_controlplane_metadata::spu::SpuSpec;
+use fluvio_controlplane_metadata::topic::{CleanupPolicy, SegmentBasedPolicy, TopicStorageConfig};
+use fluvio_future::future::timeout;
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+use fluvio_protocol::record::ReplicaKey;
+use fluvio_stream_dispatcher::actions::WSAction;
+use fluvio_stream_model::core::MetadataItem;
+use fluvio_stream_model::store::k8::K8MetaItem;
+use fluvio_types::SpuId;
+use fluvio_types::canary::{CANARY_TOPIC_PREFIX, CanaryRecord, canary_spu, canary_topic};
+
+use crate::config::CanaryClientConfig;
+use crate::core::{SharedContext, SharedScMetrics};
+use crate::stores::StoreContext;
+use crate::stores::partition::PartitionSpec;
+use crate::stores::topic::TopicSpec;
+
+const CANARY_SEGMENT_SIZE: u32 = 1_048_576; // 1MB
+const CANARY_PARTITION_SIZE: u64 = CANARY_SEGMENT_SIZE as u64 * 4;
+const CANARY_RETENTION_SEC: u32 = 3600;
+
+#[derive(Debug)]
+pub struct CanaryController<C: MetadataItem = K8MetaItem> {
+    spus: StoreContext<SpuSpec, C>,
+    topics: StoreContext<TopicSpec, C>,
+    partitions: StoreContext<PartitionSpec, C>,
+    metrics: SharedScMetrics,
+    endpoint: String,
+    client: CanaryClientConfig,
+    interval: Duration,
+    /// outcome of last probe of SPUs, carried by next record
+    last_probes: HashMap<SpuId, LastProbe>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+struct LastProbe {
+    latency: Option<Duration>,
+    failures: u64,
+}
+
+impl<C> CanaryController<C>
+where
+    C: MetadataItem + 'static,
+    C::UId: Send + Sync,
+{
+    /// start canary if probe interval is configured
+    pub fn start(ctx: SharedContext<C>) {
+        let config = ctx.config();
+        let Some(interval) = config.canary_interval else {
+            debug!("canary interval not configured, canary disabled");
+            return;
+        };
+        if config.read_only_metadata {
+            info!("canary disabled with read only metadata");
+            return;
+        }
+
+        let controller = Self {
+            spus: ctx.spus().clone(),
+            topics: ctx.topics().clone(),
+            partitions: ctx.partitions().clone(),
+            metrics: ctx.metrics().clone(),
+            endpoint: local_endpoint(
+                config
+                    .canary_client
+                    .endpoint
+                    .as_deref()
+                    .unwrap_or(&config.public_endpoint),
+            ),
+            client: config.canary_client.clone(),
+            interval,
+            last_probes: HashMap::new(),
+        };
+
+        spawn(controller.dispatch_loop());
+    }
+
+    #[instrument(name = "CanaryController", skip(self), fields(endpoint = %self.endpoint))]
+    async fn dispatch_loop(mut self) {
+        info!(interval = ?self.interval, "starting canary");
+        let mut fluvio = None;
+        loop {
+            sleep(self.interval).await;
+
+            let spus = self.ensure_canary_topics().await;
+            self.metrics.retain_canary(&spus);
+            self.last_probes.retain(|spu, _| spus.contains(spu));
+
+            if fluvio.is_none() {
+                match self.connect().await {
+                    Ok(client) => fluvio = Some(client),
+                    Err(err) => {
+                        error!(%err, "canary unable to connect to public service");
+                        continue;
+                    }
+                }
+            }
+            let Some(client) = &fluvio else {
+                continue;
+            };
+
+            let mut failed = false;
+            for spu in spus {
+                failed |= !self.probe(client, spu).await;
+            }
+            // connection may be closed by proxy or expired token, connect again with fresh token
+            if failed {
+                fluvio = None;
+            }
+        }
+    }
+
+    /// connect to public service with TLS and token of SC
+    async fn connect(&self) -> Result<Fluvio> {
+        let mut config =
+            FluvioClusterConfig::new(self.endpoint.clone()).with_tls(self.client.tls.clone());
+        if let Some(path) = &self.client.token_file {
+            config = config.with_auth_token_file(path);
+        }
+        Fluvio::connect_with_config(&config).await
+    }
+
+    /// create topics of online SPUs and delete topics of removed SPUs, return online SPUs
+    async fn ensure_canary_topics(&mut self) -> Vec<SpuId> {
+        let mut registered = vec![];
+        let mut online = vec![];
+        for spu in self.spus.store().read().await.values() {
+            let spu = spu.inner();
+            registered.push(spu.spec.id);
+            if spu.status.is_online() {
+                online.push(spu.spec.id);
+            }
+        }
+
+        let canary_topics = self
+            .topics
+            .store()
+            .clone_keys()
+            .await
+            .into_iter()
+            .filter(|topic| topic.starts_with(CANARY_TOPIC_PREFIX))
+            .collect::<Vec<_>>();
+
+        for topic in &canary_topics {
+            if canary_spu(topic).is_none_or(|spu| !registered.contains(&spu)) {
+                info!(topic, "deleting canary topic of removed SPU");
+                if let Err(err) = self.topics.delete(topic.clone()).await {
+                    error!(topic, %err, "unable to delete canary topic");
+                }
+            }
+        }
+
+        for spu in &online {
+            let topic = canary_topic(*spu);
+            if !canary_topics.contains(&topic) {
+                self.topics
+                    .send_action(WSAction::UpdateSpec((topic.clone(), canary_spec(*spu))))
+                    .await;
+                info!(topic, "canary topic created");
+            }
+        }
+
+        online
+    }
+
+    /// produce record and consume it back, record outcome, returns false if probe failed
+    async fn probe(&mut self, fluvio: &Fluvio, spu: SpuId) -> bool {
+        let topic = canary_topic(spu);
+        let Some(partition) = self
+            .partitions
+            .store()
+            .value(&ReplicaKey::new(topic.clone(), 0u32))
+            .await
+        else {
+            debug!(topic, "canary partition not yet provisioned");
+            return true;
+        };
+        if partition.inner().spec.leader != spu {
+            debug!(topic, "canary partition led by another SPU");
+            return true;
+        }
+
+        let last = self.last_probes.entry(spu).or_default();
+        let record = CanaryRecord {
+            sent_ms: now_millis(),
+            interval_ms: self.interval.as_millis() as u64,
+            previous_latency_ms: last.latency.map(|latency| latency.as_millis() as u64),
+            failures: last.failures,
+        };
+
+        let start = Instant::now();
+        let result = match timeout(self.interval, produce_consume(fluvio, &topic, &record)).await {
+            Ok(result) => result,
+            Err(_) => Err(anyhow!("timed out after {:?}", self.interval)),
+        };
+        match result {
+            Ok(()) => {
+                let latency = start.elapsed();
+                debug!(spu, ?latency, "canary probe succeeded");
+                *last = LastProbe {
+                    latency: Some(latency),
+                    failures: 0,
+                };
+                self.metrics.record_canary(spu, Some(latency));
+                true
+            }
+            Err(err) => {
+                warn!(spu, %err, "canary probe failed");
+                last.latency = None;
+                last.failures += 1;
+                self.metrics.record_canary(spu, None);
+                false
+            }
+        }
+    }
+}
+
+/// canary topic has single partition led by SPU, with only recent records kept
+fn canary_spec(spu: SpuId) -> TopicSpec {
+    let mut spec = TopicSpec::new_assigned(vec![(0, vec![spu])]);
+    spec.set_system(true);
+    spec.set_cleanup_policy(CleanupPolicy::Segment(SegmentBasedPolicy {
+        time_in_seconds: CANARY_RETENTION_SEC,
+    }));
+    spec.set_storage(TopicStorageConfig {
+        segment_size: Some(CANARY_SEGMENT_SIZE),
+        max_partition_size: Some(CANARY_PARTITION_SIZE),
+        ..Default::default()
+    });
+    spec
+}
+
+async fn produce_consume(fluvio: &Fluvio, topic: &str, record: &CanaryRecord) -> Result<()> {
+    let value = serde_json::to_vec(record)?;
+    let producer = fluvio.topic_producer(topic.to_owned()).await?;
+    let offset = producer
+        .send(RecordKey::NULL, value.clone())
+        .await?
+        .wait()
+        .await?
+        .offset();
+
+    let mut builder = ConsumerConfigExt::builder();
+    builder
+        .topic(topic)
+        .partition(0)
+        .offset_start(Offset::absolute(offset)?);
+    let mut stream = fluvio.consumer_with_config(builder.build()?).await?;
+    match stream.next().await {
+        Some(Ok(consumed)) if consumed.offset() == offset && consumed.value() == value => Ok(()),
+        Some(Ok(consumed)) => Err(anyhow!(
+            "consumed unexpected record at offset {}",
+            consumed.offset()
+        )),
+        Some(Err(err)) => Err(err.into()),
+        None => Err(anyhow!("consumer stream ended")),
+    }
+}
+
+/// public service bound to any address is reached on loopback
+fn local_endpoint(endpoint: &str) -> String {
+    match endpoint.rsplit_once(':') {
+        Some(("0.0.0.0" | "[::]" | "", port)) => format!("127.0.0.1:{port}"),
+        _ => endpoint.to_owned(),
+    }
+}
+
+/// milliseconds since epoch
+fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_millis() as i64)
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_local_endpoint() {
+        assert_eq!(local_endpoint("0.0.0.0:9003"), "127.0.0.1:9003");
+        assert_eq!(local_endpoint("[::]:9003"), "127.0.0.1:9003");
+        assert_eq!(local_endpoint(":9003"), "127.0.0.1:9003");
+        assert_eq!(local_endpoint("10.0.0.1:9003"), "10.0.0.1:9003");
+    }
+
+    #[test]
+    fn test_canary_spec() {
+        let spec = canary_spec(5001);
+        assert!(spec.is_system());
+        assert!(spec.validate_config().is_none());
+    }
+}
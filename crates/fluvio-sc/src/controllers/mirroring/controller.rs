@@ -266,6 +266,9 @@ impl<C: MetadataItem> RemoteMirrorController<C> {
         remote_topic.set_compression_type(topic.spec.get_compression_type().clone());
 
         remote_topic.set_deduplication(topic.spec.get_deduplication().cloned());
+        remote_topic.set_transforms(topic.spec.get_transforms().to_vec());
+        remote_topic.set_timestamp_policy(topic.spec.get_timestamp_policy().clone());
+        remote_topic.set_format_version(topic.spec.get_format_version());
 
         if let Some(storage) = topic.spec.get_storage() {
             remote_topic.set_storage(storage.clone());
@@ -12,8 +12,10 @@ use fluvio_future::task::spawn;
 use fluvio_controlplane_metadata::core::MetadataItem;
 use fluvio_controlplane_metadata::store::k8::K8MetaItem;
 
+use crate::core::SharedScMetrics;
 use crate::stores::StoreContext;
-use crate::stores::partition::PartitionSpec;
+use crate::stores::actions::WSAction;
+use crate::stores::partition::{PartitionSpec, PartitionResolution};
 use crate::stores::spu::SpuSpec;
 
 use super::reducer::PartitionReducer;
@@ -24,17 +26,23 @@ pub struct PartitionController<C: MetadataItem = K8MetaItem> {
     partitions: StoreContext<PartitionSpec, C>,
     spus: StoreContext<SpuSpec, C>,
     reducer: PartitionReducer<C>,
+    metrics: SharedScMetrics,
 }
 
 impl<C> PartitionController<C>
 where
     C: MetadataItem + 'static,
 {
-    pub fn start(partitions: StoreContext<PartitionSpec, C>, spus: StoreContext<SpuSpec, C>) {
+    pub fn start(
+        partitions: StoreContext<PartitionSpec, C>,
+        spus: StoreContext<SpuSpec, C>,
+        metrics: SharedScMetrics,
+    ) {
         let controller = Self {
             reducer: PartitionReducer::new(partitions.store().clone(), spus.store().clone()),
             partitions,
             spus,
+            metrics,
         };
 
         spawn(controller.dispatch_loop());
@@ -144,6 +152,20 @@ where
             .await;
 
         debug!("there were election actions: {}", actions.len());
+        // elections change leader in spec, failed elections set offline status
+        let elections = actions
+            .iter()
+            .filter(|action| matches!(action, WSAction::UpdateSpec(_)))
+            .count();
+        let leader_offline = actions
+            .iter()
+            .filter(|action| {
+                matches!(action, WSAction::UpdateStatus((_, status))
+                    if status.resolution == PartitionResolution::LeaderOffline)
+            })
+            .count();
+        self.metrics.record_elections(elections as u64);
+        self.metrics.record_leader_offline(leader_offline as u64);
         for action in actions.into_iter() {
             self.partitions.send_action(action).await;
         }
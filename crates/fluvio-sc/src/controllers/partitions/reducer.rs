@@ -159,7 +159,7 @@ impl<C: MetadataItem> PartitionReducer<C> {
                     partition_kv.status.candidate_leader(&spu_status, &policy)
                 {
                     let mut part_kv_change = partition_kv.clone();
-                    part_kv_change.spec.leader = candidate_leader;
+                    part_kv_change.spec.change_leader(candidate_leader);
 
                     // we only change leader, status happens next cycle
                     actions.push(PartitionWSAction::UpdateSpec((
@@ -225,7 +225,7 @@ impl<C: MetadataItem> PartitionReducer<C> {
                                 .is_suitable()
                         {
                             let mut part_kv_change = partition_kv.clone();
-                            part_kv_change.spec.leader = online_leader_spu_id;
+                            part_kv_change.spec.change_leader(online_leader_spu_id);
                             actions.push(PartitionWSAction::UpdateSpec((
                                 part_kv_change.key_owned(),
                                 part_kv_change.spec,
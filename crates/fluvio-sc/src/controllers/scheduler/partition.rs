@@ -132,13 +132,27 @@ where
             // ensure we don't change old partitions for no reason
             if let Some(actual_replica_map) = actual_replica_map
                 && let Some(replicas) = actual_replica_map.get(&(p_idx as PartitionId))
-                && replicas.len() == param.replication_factor as usize
+                && !replicas.is_empty()
             {
-                partition_map.insert(p_idx as PartitionId, replicas.clone());
-                continue;
+                if replicas.len() == param.replication_factor as usize {
+                    partition_map.insert(p_idx as PartitionId, replicas.clone());
+                    continue;
+                }
+                // replication factor changed, keep leader and existing followers in order
+                reserved_spus = replicas
+                    .iter()
+                    .take(param.replication_factor as usize)
+                    .copied()
+                    .collect();
+                debug!(
+                    partition = p_idx,
+                    ?replicas,
+                    replication_factor = param.replication_factor,
+                    "rescheduling replicas"
+                );
             }
 
-            for r_idx in 0..param.replication_factor {
+            for r_idx in reserved_spus.len() as ReplicationFactor..param.replication_factor {
                 // for each replica, they must be on different spu, anti-affinity
                 if let Some(spu) = self.scheduling_groups.find_suitable_spu(
                     &online_spus,
@@ -318,4 +332,33 @@ pub mod replica_map_test {
 
         assert_eq!(actual, expect);
     }
+
+    #[fluvio_future::test]
+    async fn generate_replica_change_replication_factor() {
+        let spus = DefaultSpuStore::quick(vec![(0, true, None), (1, true, None), (2, true, None)]);
+        let partitions = DefaultPartitionStore::bulk_load(vec![
+            (("t1", 0), vec![1]),
+            (("t1", 1), vec![2, 0, 1]),
+        ]);
+        let mut scheduler = PartitionScheduler::init(&spus, &partitions).await;
+
+        let param = TopicReplicaParam {
+            partitions: 2,
+            replication_factor: 2,
+            ignore_rack_assignment: false,
+        };
+        let actual_map: ReplicaPartitionMap = vec![(0, vec![1]), (1, vec![2, 0, 1])].into();
+        let actual = scheduler
+            .generate_partitions_without_rack(&param, Some(&actual_map))
+            .await;
+
+        // leader is kept and new follower is on other spu
+        let replicas = actual.get(&0).expect("partition 0");
+        assert_eq!(replicas.len(), 2);
+        assert_eq!(replicas[0], 1);
+        assert_ne!(replicas[1], 1);
+
+        // extra follower is removed
+        assert_eq!(actual.get(&1), Some(&vec![2, 0]));
+    }
 }
@@ -55,6 +55,7 @@ where
                 topics.store().clone(),
                 ctx.spus().store().clone(),
                 partitions.store().clone(),
+                ctx.cluster_configs().store().clone(),
             ),
             topics,
             partitions,
@@ -223,6 +224,7 @@ where
             spec.set_storage(TopicStorageConfig {
                 segment_size: Some(OFFSET_TOPIC_SEGMENT_SIZE),
                 max_partition_size: Some(OFFSET_TOPIC_PARTITION_SIZE),
+                ..Default::default()
             });
             self.topics
                 .send_action(WSAction::UpdateSpec((
@@ -1,4 +1,7 @@
 mod actions;
 mod reducer;
+
+pub use reducer::TopicReducer;
 pub(crate) mod controller;
 pub(crate) mod policy;
+pub(crate) mod usage;
@@ -16,11 +16,13 @@ use fluvio_stream_dispatcher::actions::WSAction;
 use fluvio_stream_model::core::MetadataItem;
 use tracing::{debug, trace, error, instrument};
 
+use crate::core::shard::{process_sharded, DEFAULT_RECONCILE_SHARDS};
 use crate::controllers::scheduler::PartitionScheduler;
 use crate::controllers::topics::policy::TopicNextState;
 use crate::stores::topic::*;
 use crate::stores::partition::*;
 use crate::stores::spu::*;
+use crate::stores::clusterconfig::*;
 use crate::controllers::partitions::PartitionWSAction;
 
 use super::actions::TopicActions;
@@ -43,6 +45,20 @@ pub struct TopicReducer<C: MetadataItem = K8MetaItem> {
     topic_store: Arc<TopicLocalStore<C>>,
     spu_store: Arc<SpuLocalStore<C>>,
     partition_store: Arc<PartitionLocalStore<C>>,
+    cluster_config_store: Arc<ClusterConfigLocalStore<C>>,
+    shards: usize,
+}
+
+impl<C: MetadataItem> Clone for TopicReducer<C> {
+    fn clone(&self) -> Self {
+        Self {
+            topic_store: self.topic_store.clone(),
+            spu_store: self.spu_store.clone(),
+            partition_store: self.partition_store.clone(),
+            cluster_config_store: self.cluster_config_store.clone(),
+            shards: self.shards,
+        }
+    }
 }
 
 impl<C: MetadataItem> TopicReducer<C> {
@@ -50,15 +66,23 @@ impl<C: MetadataItem> TopicReducer<C> {
         topic_store: impl Into<Arc<TopicLocalStore<C>>>,
         spu_store: impl Into<Arc<SpuLocalStore<C>>>,
         partition_store: impl Into<Arc<PartitionLocalStore<C>>>,
+        cluster_config_store: impl Into<Arc<ClusterConfigLocalStore<C>>>,
     ) -> Self {
         Self {
             topic_store: topic_store.into(),
             spu_store: spu_store.into(),
             partition_store: partition_store.into(),
+            cluster_config_store: cluster_config_store.into(),
+            shards: DEFAULT_RECONCILE_SHARDS,
         }
     }
 
-    #[allow(unused)]
+    /// number of tasks topics are reconciled by
+    pub fn with_shards(mut self, shards: usize) -> Self {
+        self.shards = shards;
+        self
+    }
+
     fn topic_store(&self) -> &TopicLocalStore<C> {
         &self.topic_store
     }
@@ -71,26 +95,49 @@ impl<C: MetadataItem> TopicReducer<C> {
         &self.partition_store
     }
 
+    async fn cluster_config(&self) -> ClusterConfigSpec {
+        self.cluster_config_store
+            .spec(CLUSTER_CONFIG_NAME)
+            .await
+            .unwrap_or_default()
+    }
+
     pub async fn process_requests(&self, topic_updates: Vec<TopicMetadata<C>>) -> TopicActions<C> {
         trace!(?topic_updates, "processing requests");
 
-        let mut actions = TopicActions::default();
-
-        for topic in topic_updates {
-            self.update_actions_next_state(&topic, &mut actions).await;
-        }
-
-        actions
+        self.process_topics(topic_updates).await
     }
 
     pub async fn process_spu_update(&self) -> TopicActions<C> {
-        let mut actions = TopicActions::default();
+        let topics = self.topic_store().clone_values().await;
+        self.process_topics(topics).await
+    }
 
-        let topics = self.topic_store().read().await;
-        for topic in topics.values() {
-            self.update_actions_next_state(topic, &mut actions).await;
-        }
+    /// compute actions for topics, sharded by topic name
+    async fn process_topics(&self, topics: Vec<TopicMetadata<C>>) -> TopicActions<C> {
+        let reducer = self.clone();
+        let shard_actions = process_sharded(
+            topics,
+            self.shards,
+            |topic| topic.key(),
+            move |topic| {
+                let reducer = reducer.clone();
+                async move {
+                    let mut actions = TopicActions::default();
+                    reducer
+                        .update_actions_next_state(&topic, &mut actions)
+                        .await;
+                    actions
+                }
+            },
+        )
+        .await;
 
+        let mut actions = TopicActions::default();
+        for shard in shard_actions {
+            actions.topics.extend(shard.topics);
+            actions.partitions.extend(shard.partitions);
+        }
         actions
     }
 
@@ -145,6 +192,19 @@ impl<C: MetadataItem> TopicReducer<C> {
             return;
         }
 
+        // settings not given on creation are taken from cluster config
+        if topic.status().is_resolution_initializing() {
+            let mut spec = topic.spec().clone();
+            if self.cluster_config().await.apply_topic_defaults(&mut spec) {
+                debug!(topic = %topic.key(), "applying cluster config defaults");
+                actions.topics.push(WSAction::<TopicSpec, C>::UpdateSpec((
+                    topic.key_owned(),
+                    spec,
+                )));
+                return;
+            }
+        }
+
         if topic.status().is_resolution_provisioned()
             && topic.spec().replicas().partitions() > topic.status().replica_map.len() as u32
         {
@@ -161,6 +221,32 @@ impl<C: MetadataItem> TopicReducer<C> {
             return;
         }
 
+        if topic.status().is_resolution_provisioned()
+            && let ReplicaSpec::Computed(param) = topic.spec().replicas()
+            && topic
+                .status()
+                .replica_map
+                .values()
+                .any(|replicas| replicas.len() != param.replication_factor as usize)
+        {
+            if self.cluster_config().await.is_in_maintenance() {
+                debug!(topic = %topic.key(), "cluster is in maintenance, replicas are not rescheduled");
+                return;
+            }
+            debug!(
+                topic = %topic.key(),
+                replication_factor = param.replication_factor,
+                "replication factor changed, rescheduling replicas"
+            );
+            let mut status = topic.status().clone();
+            status.resolution = TopicResolution::Pending;
+            actions.topics.push(WSAction::<TopicSpec, C>::UpdateStatus((
+                topic.key_owned(),
+                status,
+            )));
+            return;
+        }
+
         let mut scheduler =
             PartitionScheduler::init(self.spu_store(), self.partition_store()).await;
         let next_state = TopicNextState::compute_next_state(topic, &mut scheduler).await;
@@ -202,6 +288,7 @@ mod test2 {
 
     use fluvio_controlplane_metadata::topic::{TopicResolution, TopicStatus};
     use fluvio_controlplane_metadata::topic::PENDING_REASON;
+    use fluvio_stream_model::store::MetadataStoreObject;
 
     use super::*;
 
@@ -215,6 +302,7 @@ mod test2 {
             TopicLocalStore::new_shared(),
             SpuLocalStore::new_shared(),
             partition_store.clone(),
+            ClusterConfigLocalStore::new_shared(),
         );
         let topic_requests = vec![
             TopicAdminMd::with_spec("topic1", (1, 1).into()),
@@ -237,4 +325,85 @@ mod test2 {
         ];
         assert_eq!(actions.topics, expected_actions);
     }
+
+    // settings missing in new topic are filled from cluster config before provisioning
+    #[fluvio_future::test]
+    async fn test_topic_reducer_applies_cluster_defaults() {
+        let partition_store = PartitionLocalStore::new_shared();
+        let cluster_config_store = ClusterConfigLocalStore::new_shared();
+        let topic_reducer = TopicReducer::new(
+            TopicLocalStore::new_shared(),
+            SpuLocalStore::new_shared(),
+            partition_store.clone(),
+            cluster_config_store.clone(),
+        );
+        let cluster_config = ClusterConfigSpec {
+            default_replication: Some(2),
+            default_compression: Some(CompressionAlgorithm::Lz4),
+            ..Default::default()
+        };
+        partition_store.sync_all(vec![]).await;
+        cluster_config_store
+            .sync_all(vec![MetadataStoreObject::with_spec(
+                CLUSTER_CONFIG_NAME,
+                cluster_config,
+            )])
+            .await;
+
+        let actions = topic_reducer
+            .process_requests(vec![TopicAdminMd::with_spec("topic1", (1, 0).into())])
+            .await;
+
+        let mut expected_spec = TopicSpec::new_computed(1, 2, None);
+        expected_spec.set_compression_type(CompressionAlgorithm::Lz4);
+        assert_eq!(
+            actions.topics,
+            vec![TopicWSAction::UpdateSpec(("topic1".into(), expected_spec))]
+        );
+        assert!(actions.partitions.is_empty());
+    }
+
+    // replicas of topic with changed replication factor are rescheduled after maintenance
+    #[fluvio_future::test]
+    async fn test_topic_reducer_defers_rescheduling_in_maintenance() {
+        let partition_store = PartitionLocalStore::new_shared();
+        let cluster_config_store = ClusterConfigLocalStore::new_shared();
+        let topic_reducer = TopicReducer::new(
+            TopicLocalStore::new_shared(),
+            SpuLocalStore::new_shared(),
+            partition_store.clone(),
+            cluster_config_store.clone(),
+        );
+        partition_store.sync_all(vec![]).await;
+        let maintenance = ClusterConfigSpec {
+            maintenance: Some(MaintenanceSpec::default()),
+            ..Default::default()
+        };
+        cluster_config_store
+            .sync_all(vec![MetadataStoreObject::with_spec(
+                CLUSTER_CONFIG_NAME,
+                maintenance,
+            )])
+            .await;
+        let mut topic = TopicAdminMd::with_spec("topic1", (1, 2).into());
+        topic.set_status(TopicStatus::new(
+            TopicResolution::Provisioned,
+            vec![vec![0]],
+            "",
+        ));
+
+        let actions = topic_reducer.process_requests(vec![topic.clone()]).await;
+        assert!(actions.topics.is_empty());
+        assert!(actions.partitions.is_empty());
+
+        cluster_config_store.sync_all(vec![]).await;
+        let actions = topic_reducer.process_requests(vec![topic]).await;
+        assert_eq!(
+            actions.topics,
+            vec![TopicWSAction::UpdateStatus((
+                "topic1".into(),
+                TopicStatus::new(TopicResolution::Pending, vec![vec![0]], ""),
+            ))]
+        );
+    }
 }
@@ -0,0 +1,61 @@
+//!
+//! # Topic Usage Sampler
+//!
+//! Periodically records size of every topic, sum of sizes of its partitions
+//! as reported by leaders.
+//!
+
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+use tracing::{debug, instrument};
+
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+use fluvio_stream_model::core::MetadataItem;
+
+use crate::core::{SharedContext, SharedTopicUsage, TOPIC_USAGE_SAMPLE_INTERVAL};
+use crate::stores::partition::PartitionSpec;
+use crate::stores::StoreContext;
+
+#[derive(Debug)]
+pub struct TopicUsageSampler<C: MetadataItem> {
+    partitions: StoreContext<PartitionSpec, C>,
+    history: SharedTopicUsage,
+}
+
+impl<C> TopicUsageSampler<C>
+where
+    C: MetadataItem + 'static,
+{
+    pub fn start(ctx: SharedContext<C>) {
+        let sampler = Self {
+            partitions: ctx.partitions().clone(),
+            history: ctx.topic_usage().clone(),
+        };
+        spawn(sampler.dispatch_loop());
+    }
+
+    #[instrument(name = "TopicUsageSampler", skip(self))]
+    async fn dispatch_loop(self) {
+        debug!("starting topic usage sampler");
+        loop {
+            self.sample().await;
+            sleep(TOPIC_USAGE_SAMPLE_INTERVAL).await;
+        }
+    }
+
+    async fn sample(&self) {
+        let mut sizes: HashMap<String, u64> = HashMap::new();
+        for partition in self.partitions.store().read().await.values() {
+            let size = sizes.entry(partition.key().topic.clone()).or_default();
+            // negative sizes mean size is unknown
+            *size += u64::try_from(partition.inner().status().size).unwrap_or_default();
+        }
+        let timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |now| now.as_millis() as i64);
+        debug!(topics = sizes.len(), "sampled topic usage");
+        self.history.record(timestamp, sizes);
+    }
+}
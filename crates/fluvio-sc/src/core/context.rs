@@ -5,7 +5,9 @@
 //!
 use std::sync::Arc;
 
+use fluvio_sc_schema::clusterconfig::{ClusterConfigSpec, CLUSTER_CONFIG_NAME};
 use fluvio_sc_schema::mirror::MirrorSpec;
+use fluvio_service::ip_filter::SharedIpFilter;
 use fluvio_stream_model::core::MetadataItem;
 
 use crate::config::ScConfig;
@@ -17,6 +19,9 @@ use crate::stores::smartmodule::*;
 use crate::stores::tableformat::*;
 use crate::stores::*;
 
+use super::metrics::{ScMetrics, SharedScMetrics};
+use super::usage::{SharedTopicUsage, TopicUsageHistory};
+
 pub type SharedContext<C> = Arc<Context<C>>;
 pub type K8SharedContext = Arc<Context<K8MetaItem>>;
 
@@ -31,7 +36,11 @@ pub struct Context<C: MetadataItem> {
     smartmodules: StoreContext<SmartModuleSpec, C>,
     tableformats: StoreContext<TableFormatSpec, C>,
     mirrors: StoreContext<MirrorSpec, C>,
+    cluster_configs: StoreContext<ClusterConfigSpec, C>,
     health: SharedHealthCheck,
+    topic_usage: SharedTopicUsage,
+    ip_filter: SharedIpFilter,
+    metrics: SharedScMetrics,
     config: ScConfig,
 }
 
@@ -54,7 +63,11 @@ impl<C: MetadataItem> Context<C> {
             smartmodules: StoreContext::new(),
             tableformats: StoreContext::new(),
             mirrors: StoreContext::new(),
+            cluster_configs: StoreContext::new(),
             health: HealthCheck::shared(),
+            topic_usage: TopicUsageHistory::shared(),
+            ip_filter: SharedIpFilter::new(config.ip_filter.clone()),
+            metrics: ScMetrics::shared(),
             config,
         }
     }
@@ -90,11 +103,39 @@ impl<C: MetadataItem> Context<C> {
         &self.mirrors
     }
 
+    pub fn cluster_configs(&self) -> &StoreContext<ClusterConfigSpec, C> {
+        &self.cluster_configs
+    }
+
+    /// current cluster config, default if not set
+    pub async fn cluster_config(&self) -> ClusterConfigSpec {
+        self.cluster_configs
+            .store()
+            .spec(CLUSTER_CONFIG_NAME)
+            .await
+            .unwrap_or_default()
+    }
+
     /// spu health channel
     pub fn health(&self) -> &SharedHealthCheck {
         &self.health
     }
 
+    /// history of topic sizes
+    pub fn topic_usage(&self) -> &SharedTopicUsage {
+        &self.topic_usage
+    }
+
+    /// networks accepted by public service
+    pub fn ip_filter(&self) -> &SharedIpFilter {
+        &self.ip_filter
+    }
+
+    /// controller metrics
+    pub fn metrics(&self) -> &SharedScMetrics {
+        &self.metrics
+    }
+
     /// reference to config
     pub fn config(&self) -> &ScConfig {
         &self.config
@@ -0,0 +1,335 @@
+//!
+//! # Controller metrics
+//!
+//! Counters and latencies recorded by controllers and services.
+//! Gauges of metadata are computed from stores when scraped.
+//!
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use fluvio_types::SpuId;
+
+use crate::services::auth::cache::AuthDecisionCache;
+
+/// upper bounds in seconds of request latency histogram buckets
+const LATENCY_BUCKETS: [f64; 8] = [0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0];
+
+pub type SharedScMetrics = Arc<ScMetrics>;
+
+#[derive(Debug, Default)]
+pub struct ScMetrics {
+    leader_elections: AtomicU64,
+    leader_offline: AtomicU64,
+    requests: Mutex<BTreeMap<&'static str, Latency>>,
+    canary: Mutex<BTreeMap<SpuId, CanaryProbes>>,
+    auth_cache: OnceLock<Arc<AuthDecisionCache>>,
+}
+
+/// outcome of canary probes of SPU
+#[derive(Debug, Default, Clone, PartialEq)]
+struct CanaryProbes {
+    successes: u64,
+    failures: u64,
+    /// end-to-end latency of last successful probe
+    latency: Option<Duration>,
+    /// last probe succeeded
+    up: bool,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+struct Latency {
+    /// cumulative count of requests per bucket
+    buckets: [u64; LATENCY_BUCKETS.len()],
+    count: u64,
+    sum: f64,
+}
+
+impl Latency {
+    fn observe(&mut self, elapsed: Duration) {
+        let seconds = elapsed.as_secs_f64();
+        for (bucket, bound) in self.buckets.iter_mut().zip(LATENCY_BUCKETS) {
+            if seconds <= bound {
+                *bucket += 1;
+            }
+        }
+        self.count += 1;
+        self.sum += seconds;
+    }
+}
+
+impl ScMetrics {
+    pub fn shared() -> SharedScMetrics {
+        Arc::new(Self::default())
+    }
+
+    /// partitions whose leader was changed by election
+    pub fn record_elections(&self, count: u64) {
+        self.leader_elections.fetch_add(count, Ordering::Relaxed);
+    }
+
+    /// partitions set offline because no suitable leader was found
+    pub fn record_leader_offline(&self, count: u64) {
+        self.leader_offline.fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub fn record_request(&self, api: &'static str, elapsed: Duration) {
+        self.requests
+            .lock()
+            .expect("metrics lock poisoned")
+            .entry(api)
+            .or_default()
+            .observe(elapsed);
+    }
+
+    /// canary probe of SPU, with end-to-end latency if record was produced and consumed
+    pub fn record_canary(&self, spu: SpuId, latency: Option<Duration>) {
+        let mut canary = self.canary.lock().expect("metrics lock poisoned");
+        let probes = canary.entry(spu).or_default();
+        match latency {
+            Some(latency) => {
+                probes.successes += 1;
+                probes.latency = Some(latency);
+            }
+            None => probes.failures += 1,
+        }
+        probes.up = latency.is_some();
+    }
+
+    /// SPUs no longer probed
+    pub fn retain_canary(&self, spus: &[SpuId]) {
+        self.canary
+            .lock()
+            .expect("metrics lock poisoned")
+            .retain(|spu, _| spus.contains(spu));
+    }
+
+    /// authorization decision cache of public server, if policy is used
+    pub fn register_auth_cache(&self, cache: Arc<AuthDecisionCache>) {
+        let _ = self.auth_cache.set(cache);
+    }
+
+    /// run request handler and record its latency
+    pub async fn time<F: Future>(&self, api: &'static str, handler: F) -> F::Output {
+        let start = Instant::now();
+        let output = handler.await;
+        self.record_request(api, start.elapsed());
+        output
+    }
+
+    /// write counters and histograms in Prometheus text format
+    pub fn render(&self, out: &mut String) {
+        write_metric(
+            out,
+            "fluvio_sc_leader_elections_total",
+            "counter",
+            "Partition leaders changed by election",
+            &[("", self.leader_elections.load(Ordering::Relaxed) as f64)],
+        );
+        write_metric(
+            out,
+            "fluvio_sc_leader_offline_total",
+            "counter",
+            "Partitions set offline without suitable leader",
+            &[("", self.leader_offline.load(Ordering::Relaxed) as f64)],
+        );
+
+        let requests = self.requests.lock().expect("metrics lock poisoned");
+        let name = "fluvio_sc_request_duration_seconds";
+        let _ = writeln!(
+            out,
+            "# HELP {name} Latency of public API requests\n# TYPE {name} histogram"
+        );
+        for (api, latency) in requests.iter() {
+            for (count, bound) in latency.buckets.iter().zip(LATENCY_BUCKETS) {
+                let _ = writeln!(out, "{name}_bucket{{api=\"{api}\",le=\"{bound}\"}} {count}");
+            }
+            let _ = writeln!(
+                out,
+                "{name}_bucket{{api=\"{api}\",le=\"+Inf\"}} {}\n{name}_sum{{api=\"{api}\"}} {}\n{name}_count{{api=\"{api}\"}} {}",
+                latency.count, latency.sum, latency.count
+            );
+        }
+        drop(requests);
+
+        if let Some(cache) = self.auth_cache.get() {
+            let cache = cache.metrics();
+            write_metric(
+                out,
+                "fluvio_sc_auth_cache_hits_total",
+                "counter",
+                "Authorization decisions served from cache",
+                &[("", cache.hits as f64)],
+            );
+            write_metric(
+                out,
+                "fluvio_sc_auth_cache_misses_total",
+                "counter",
+                "Authorization decisions evaluated by policy",
+                &[("", cache.misses as f64)],
+            );
+            write_metric(
+                out,
+                "fluvio_sc_auth_cache_hit_ratio",
+                "gauge",
+                "Ratio of authorization decisions served from cache",
+                &[("", cache.hit_rate())],
+            );
+            write_metric(
+                out,
+                "fluvio_sc_auth_cache_entries",
+                "gauge",
+                "Authorization decisions in cache",
+                &[("", cache.entries as f64)],
+            );
+        }
+
+        let canary = self.canary.lock().expect("metrics lock poisoned");
+        if canary.is_empty() {
+            return;
+        }
+        let labels = canary
+            .iter()
+            .map(|(spu, probes)| (format!("spu=\"{spu}\""), probes))
+            .collect::<Vec<_>>();
+        let samples = |value: fn(&CanaryProbes) -> Option<f64>| {
+            labels
+                .iter()
+                .filter_map(|(labels, probes)| value(probes).map(|value| (labels.as_str(), value)))
+                .collect::<Vec<_>>()
+        };
+        write_metric(
+            out,
+            "fluvio_sc_canary_up",
+            "gauge",
+            "Whether last canary probe of SPU succeeded",
+            &samples(|probes| Some(if probes.up { 1.0 } else { 0.0 })),
+        );
+        write_metric(
+            out,
+            "fluvio_sc_canary_latency_seconds",
+            "gauge",
+            "End-to-end latency of last successful canary probe",
+            &samples(|probes| probes.latency.map(|latency| latency.as_secs_f64())),
+        );
+        write_metric(
+            out,
+            "fluvio_sc_canary_success_total",
+            "counter",
+            "Canary records produced and consumed",
+            &samples(|probes| Some(probes.successes as f64)),
+        );
+        write_metric(
+            out,
+            "fluvio_sc_canary_failures_total",
+            "counter",
+            "Canary probes failed",
+            &samples(|probes| Some(probes.failures as f64)),
+        );
+    }
+}
+
+/// write metric with help, type and samples, labels are given preformatted
+pub fn write_metric(out: &mut String, name: &str, ty: &str, help: &str, samples: &[(&str, f64)]) {
+    let _ = writeln!(out, "# HELP {name} {help}\n# TYPE {name} {ty}");
+    for (labels, value) in samples {
+        if labels.is_empty() {
+            let _ = writeln!(out, "{name} {value}");
+        } else {
+            let _ = writeln!(out, "{name}{{{labels}}} {value}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_render_metrics() {
+        let metrics = ScMetrics::default();
+        metrics.record_elections(2);
+        metrics.record_request("list", Duration::from_millis(3));
+        metrics.record_request("list", Duration::from_millis(200));
+
+        let mut out = String::new();
+        metrics.render(&mut out);
+
+        assert!(out.contains("fluvio_sc_leader_elections_total 2\n"));
+        assert!(out.contains("fluvio_sc_leader_offline_total 0\n"));
+        assert!(
+            out.contains(
+                "fluvio_sc_request_duration_seconds_bucket{api=\"list\",le=\"0.001\"} 0\n"
+            )
+        );
+        assert!(
+            out.contains(
+                "fluvio_sc_request_duration_seconds_bucket{api=\"list\",le=\"0.005\"} 1\n"
+            )
+        );
+        assert!(
+            out.contains("fluvio_sc_request_duration_seconds_bucket{api=\"list\",le=\"0.5\"} 2\n")
+        );
+        assert!(
+            out.contains("fluvio_sc_request_duration_seconds_bucket{api=\"list\",le=\"+Inf\"} 2\n")
+        );
+        assert!(out.contains("fluvio_sc_request_duration_seconds_count{api=\"list\"} 2\n"));
+        assert!(!out.contains("fluvio_sc_canary"));
+        assert!(!out.contains("fluvio_sc_auth_cache"));
+    }
+
+    #[test]
+    fn test_render_auth_cache() {
+        use crate::services::auth::basic::Action;
+        use crate::services::auth::cache::DecisionKey;
+        use fluvio_controlplane_metadata::extended::ObjectType;
+
+        let cache = Arc::new(AuthDecisionCache::default());
+        let key = DecisionKey {
+            principal: "alice".to_owned(),
+            scopes: vec![],
+            action: Action::Read,
+            object_type: ObjectType::Topic,
+            instance: None,
+        };
+        cache.get(&key);
+        cache.insert(key.clone(), true);
+        cache.get(&key);
+
+        let metrics = ScMetrics::default();
+        metrics.register_auth_cache(cache);
+        let mut out = String::new();
+        metrics.render(&mut out);
+
+        assert!(out.contains("fluvio_sc_auth_cache_hits_total 1\n"));
+        assert!(out.contains("fluvio_sc_auth_cache_misses_total 1\n"));
+        assert!(out.contains("fluvio_sc_auth_cache_hit_ratio 0.5\n"));
+        assert!(out.contains("fluvio_sc_auth_cache_entries 1\n"));
+    }
+
+    #[test]
+    fn test_render_canary() {
+        let metrics = ScMetrics::default();
+        metrics.record_canary(5001, Some(Duration::from_millis(20)));
+        metrics.record_canary(5001, None);
+        metrics.record_canary(5002, None);
+
+        let mut out = String::new();
+        metrics.render(&mut out);
+
+        assert!(out.contains("fluvio_sc_canary_up{spu=\"5001\"} 0\n"));
+        assert!(out.contains("fluvio_sc_canary_latency_seconds{spu=\"5001\"} 0.02\n"));
+        assert!(!out.contains("fluvio_sc_canary_latency_seconds{spu=\"5002\"}"));
+        assert!(out.contains("fluvio_sc_canary_success_total{spu=\"5001\"} 1\n"));
+        assert!(out.contains("fluvio_sc_canary_failures_total{spu=\"5002\"} 1\n"));
+
+        metrics.retain_canary(&[5002]);
+        let mut out = String::new();
+        metrics.render(&mut out);
+        assert!(!out.contains("spu=\"5001\""));
+    }
+}
@@ -1,2 +1,8 @@
 mod context;
+mod usage;
+mod metrics;
+pub mod shard;
+
 pub use self::context::*;
+pub use self::usage::*;
+pub use self::metrics::*;
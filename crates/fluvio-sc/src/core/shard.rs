@@ -0,0 +1,129 @@
+//!
+//! # Sharded reconciliation
+//!
+//! Controllers reconcile many objects on each change. Work is split into shards
+//! by object key and each shard runs as its own task, so shards are processed in
+//! parallel on the executor threads. All work for one key lands in the same shard,
+//! so per-object ordering is preserved.
+//!
+use std::future::Future;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use futures_util::future::join_all;
+use fluvio_future::task::spawn;
+
+/// Default number of shards used by controllers
+pub const DEFAULT_RECONCILE_SHARDS: usize = 16;
+
+/// Split items into `shards` groups by key, keeping relative order of items within a group
+pub fn shard_by_key<T, K, F>(
+    items: impl IntoIterator<Item = T>,
+    shards: usize,
+    key: F,
+) -> Vec<Vec<T>>
+where
+    K: Hash + ?Sized,
+    F: Fn(&T) -> &K,
+{
+    let shards = shards.max(1);
+    let mut groups: Vec<Vec<T>> = (0..shards).map(|_| Vec::new()).collect();
+    for item in items {
+        let mut hasher = DefaultHasher::new();
+        key(&item).hash(&mut hasher);
+        let index = (hasher.finish() % shards as u64) as usize;
+        groups[index].push(item);
+    }
+    groups.retain(|group| !group.is_empty());
+    groups
+}
+
+/// Process items in parallel, one task per shard.
+/// Items within a shard are processed in order. Outputs are returned in order of items.
+pub async fn process_sharded<T, K, O, F, Fut>(
+    items: impl IntoIterator<Item = T>,
+    shards: usize,
+    key: impl Fn(&T) -> &K,
+    process: F,
+) -> Vec<O>
+where
+    K: Hash + ?Sized,
+    T: Send + 'static,
+    O: Send + 'static,
+    F: Fn(T) -> Fut + Clone + Send + 'static,
+    Fut: Future<Output = O> + Send + 'static,
+{
+    let indexed = items.into_iter().enumerate();
+    let tasks = shard_by_key(indexed, shards, |(_, item)| key(item))
+        .into_iter()
+        .map(|shard| {
+            let process = process.clone();
+            spawn(async move {
+                let mut outputs = Vec::with_capacity(shard.len());
+                for (index, item) in shard {
+                    outputs.push((index, process(item).await));
+                }
+                outputs
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut outputs = join_all(tasks)
+        .await
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    outputs.sort_unstable_by_key(|(index, _)| *index);
+    outputs.into_iter().map(|(_, output)| output).collect()
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_shard_by_key_keeps_order() {
+        let items: Vec<(String, u32)> = (0..100).map(|i| (format!("topic-{}", i % 7), i)).collect();
+
+        let shards = shard_by_key(items, 4, |(key, _)| key);
+        assert!(shards.len() <= 4);
+        assert_eq!(shards.iter().map(Vec::len).sum::<usize>(), 100);
+
+        for shard in &shards {
+            for key in (0..7).map(|i| format!("topic-{i}")) {
+                let seq: Vec<u32> = shard
+                    .iter()
+                    .filter(|(k, _)| *k == key)
+                    .map(|(_, i)| *i)
+                    .collect();
+                assert!(seq.windows(2).all(|w| w[0] < w[1]));
+            }
+        }
+
+        // same key never ends up in two shards
+        for key in (0..7).map(|i| format!("topic-{i}")) {
+            let count = shards
+                .iter()
+                .filter(|shard| shard.iter().any(|(k, _)| *k == key))
+                .count();
+            assert_eq!(count, 1);
+        }
+    }
+
+    #[fluvio_future::test]
+    async fn test_process_sharded() {
+        let items: Vec<String> = (0..50).map(|i| format!("topic-{i}")).collect();
+        let outputs = process_sharded(
+            items.clone(),
+            8,
+            |item| item,
+            |item| async move { item.len() },
+        )
+        .await;
+        assert_eq!(
+            outputs,
+            items.iter().map(String::len).collect::<Vec<_>>(),
+            "outputs are in order of items"
+        );
+    }
+}
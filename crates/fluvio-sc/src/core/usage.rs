@@ -0,0 +1,102 @@
+//!
+//! # Topic Usage History
+//!
+//! Bounded in-memory history of topic sizes, used for capacity planning.
+//! History starts empty whenever SC starts.
+//!
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use fluvio_sc_schema::topic::TopicUsageSample;
+
+pub type SharedTopicUsage = Arc<TopicUsageHistory>;
+
+/// time between two samples of topic sizes
+pub const TOPIC_USAGE_SAMPLE_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// samples kept for each topic, 7 days of history
+const TOPIC_USAGE_MAX_SAMPLES: usize = 7 * 24 * 12;
+
+#[derive(Debug)]
+pub struct TopicUsageHistory {
+    max_samples: usize,
+    topics: Mutex<HashMap<String, VecDeque<TopicUsageSample>>>,
+}
+
+impl Default for TopicUsageHistory {
+    fn default() -> Self {
+        Self::new(TOPIC_USAGE_MAX_SAMPLES)
+    }
+}
+
+impl TopicUsageHistory {
+    pub fn new(max_samples: usize) -> Self {
+        Self {
+            max_samples: max_samples.max(1),
+            topics: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn shared() -> SharedTopicUsage {
+        Arc::new(Self::default())
+    }
+
+    /// add sample of every topic, forgetting topics which no longer exist
+    pub fn record(&self, timestamp: i64, sizes: HashMap<String, u64>) {
+        let mut topics = self.topics.lock().unwrap_or_else(|err| err.into_inner());
+        topics.retain(|topic, _| sizes.contains_key(topic));
+        for (topic, size) in sizes {
+            let samples = topics.entry(topic).or_default();
+            if samples.len() >= self.max_samples {
+                samples.pop_front();
+            }
+            samples.push_back(TopicUsageSample { timestamp, size });
+        }
+    }
+
+    /// samples of topic, oldest first
+    pub fn samples(&self, topic: &str) -> Option<Vec<TopicUsageSample>> {
+        let topics = self.topics.lock().unwrap_or_else(|err| err.into_inner());
+        topics
+            .get(topic)
+            .map(|samples| samples.iter().copied().collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_bounded_usage_history() {
+        let history = TopicUsageHistory::new(2);
+        history.record(
+            1,
+            HashMap::from([("a".to_owned(), 10), ("b".to_owned(), 5)]),
+        );
+        history.record(
+            2,
+            HashMap::from([("a".to_owned(), 20), ("b".to_owned(), 6)]),
+        );
+        history.record(3, HashMap::from([("a".to_owned(), 30)]));
+
+        assert_eq!(
+            history.samples("a"),
+            Some(vec![
+                TopicUsageSample {
+                    timestamp: 2,
+                    size: 20
+                },
+                TopicUsageSample {
+                    timestamp: 3,
+                    size: 30
+                },
+            ])
+        );
+        // deleted topic is forgotten
+        assert_eq!(history.samples("b"), None);
+    }
+}
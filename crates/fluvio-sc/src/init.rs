@@ -6,23 +6,28 @@
 //!
 use std::sync::Arc;
 
+use fluvio_sc_schema::clusterconfig::ClusterConfigSpec;
 use fluvio_sc_schema::mirror::MirrorSpec;
 use fluvio_stream_dispatcher::metadata::{SharedClient, MetadataClient};
 use fluvio_stream_model::core::MetadataItem;
 
 use crate::controllers::mirroring::controller::RemoteMirrorController;
+use crate::controllers::canary::CanaryController;
 use crate::core::Context;
 use crate::core::SharedContext;
 use crate::controllers::partitions::PartitionController;
 use crate::controllers::spus::SpuController;
 use crate::controllers::topics::controller::{TopicController, SystemTopicController};
+use crate::controllers::topics::usage::TopicUsageSampler;
 use crate::config::ScConfig;
 use crate::services::start_internal_server;
+use crate::services::start_probe_server;
+use crate::services::start_metrics_server;
 use crate::dispatcher::dispatcher::MetadataDispatcher;
-use crate::services::auth::basic::BasicRbacPolicy;
+use crate::services::auth::backend::SharedPolicyBackend;
 
 pub async fn start_main_loop<C, M>(
-    sc_config_policy: (ScConfig, Option<BasicRbacPolicy>),
+    sc_config_policy: (ScConfig, Option<SharedPolicyBackend>),
     metadata_client: SharedClient<C>,
 ) -> crate::core::SharedContext<M>
 where
@@ -84,13 +89,19 @@ where
         ctx.mirrors().clone(),
     );
 
+    MetadataDispatcher::<ClusterConfigSpec, C, M>::start(
+        namespace.clone(),
+        metadata_client.clone(),
+        ctx.cluster_configs().clone(),
+    );
+
     start_main_loop_services(ctx, auth_policy).await
 }
 
 /// start the main loop
 async fn start_main_loop_services<C>(
     ctx: Arc<Context<C>>,
-    auth_policy: Option<BasicRbacPolicy>,
+    auth_policy: Option<SharedPolicyBackend>,
 ) -> SharedContext<C>
 where
     C: MetadataItem + 'static,
@@ -101,12 +112,20 @@ where
     whitelist!(config, "spu", SpuController::start(ctx.clone()));
     whitelist!(config, "topic", TopicController::start(ctx.clone()));
     whitelist!(config, "topic", SystemTopicController::start(ctx.clone()));
+    whitelist!(config, "topic", TopicUsageSampler::start(ctx.clone()));
+    whitelist!(config, "canary", CanaryController::start(ctx.clone()));
     whitelist!(
         config,
         "partition",
-        PartitionController::start(ctx.partitions().clone(), ctx.spus().clone())
+        PartitionController::start(
+            ctx.partitions().clone(),
+            ctx.spus().clone(),
+            ctx.metrics().clone()
+        )
     );
 
+    let readiness = start_probe_server(ctx.clone());
+    start_metrics_server(ctx.clone(), readiness);
     whitelist!(config, "internal", start_internal_server(ctx.clone()));
     whitelist!(
         config,
@@ -130,19 +149,36 @@ where
 
         use fluvio_controlplane_metadata::core::MetadataItem;
         use crate::services::auth::{AuthGlobalContext, ReadOnlyAuthorization};
-        use crate::services::auth::basic::{BasicAuthorization, BasicRbacPolicy};
+        use crate::services::auth::backend::SharedPolicyBackend;
+        use crate::services::auth::basic::BasicAuthorization;
+        use crate::services::auth::oidc::{OidcAuthorization, OidcValidator};
 
-        pub fn start<C>(ctx: SharedContext<C>, auth_policy_option: Option<BasicRbacPolicy>)
+        pub fn start<C>(ctx: SharedContext<C>, auth_policy_option: Option<SharedPolicyBackend>)
         where
             C: MetadataItem + 'static,
             C::UId: Send + Sync,
         {
-            if let Some(policy) = auth_policy_option {
-                info!("using basic authorization");
+            let oidc = ctx.config().oidc.clone();
+            if let (Some(backend), Some(oidc)) = (&auth_policy_option, oidc) {
+                info!(
+                    issuer = %oidc.issuer,
+                    "using OIDC authentication with {} authorization",
+                    backend.kind()
+                );
+                let basic = BasicAuthorization::with_backend(backend.clone());
+                ctx.metrics().register_auth_cache(basic.cache());
                 start_public_server(AuthGlobalContext::new(
                     ctx,
-                    Arc::new(BasicAuthorization::new(policy)),
+                    Arc::new(OidcAuthorization::new(
+                        Arc::new(OidcValidator::new(oidc)),
+                        basic,
+                    )),
                 ));
+            } else if let Some(backend) = auth_policy_option {
+                info!("using {} authorization", backend.kind());
+                let basic = BasicAuthorization::with_backend(backend);
+                ctx.metrics().register_auth_cache(basic.cache());
+                start_public_server(AuthGlobalContext::new(ctx, Arc::new(basic)));
             } else if ctx.config().read_only_metadata {
                 info!("using read-only authorization");
 
@@ -164,7 +164,12 @@ impl K8SpuController {
             let spec = spg_obj.spec();
             let replicas = spec.replicas;
             for i in 0..replicas {
-                let (spu_name, spu) = spg_obj.as_spu(i, &services);
+                let (spu_name, mut spu) = spg_obj.as_spu(i, &services);
+
+                // keep runtime config set through the admin api
+                if let Some(existing) = self.spus.store().value(&spu_name).await {
+                    spu.spec.config = existing.spec().config.clone();
+                }
 
                 debug!(id=i,spu=?spu,"applying spu");
 
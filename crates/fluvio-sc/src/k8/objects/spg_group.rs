@@ -140,6 +140,7 @@ impl SpuGroupObj {
                 port: spu_public_ep.port,
                 encryption: spu_public_ep.encryption,
             }),
+            config: Default::default(),
         };
 
         /*
@@ -183,15 +184,17 @@ mod k8_convert {
     use fluvio_stream_model::k8_types::*;
     use fluvio_stream_model::k8_types::core::pod::{
         ContainerSpec, ContainerPortSpec, PodSpec, VolumeMount, VolumeSpec, SecretVolumeSpec,
+        Probe, HttpGetAction,
     };
     use fluvio_stream_model::k8_types::core::service::*;
     use fluvio_stream_model::k8_types::app::stateful::{
         PersistentVolumeClaim, VolumeAccessMode, ResourceRequirements, VolumeRequest,
     };
     use fluvio_types::defaults::{
-        SPU_DEFAULT_NAME, SPU_PUBLIC_PORT, SPU_PRIVATE_PORT, SC_PRIVATE_PORT, PRODUCT_NAME,
-        TLS_SERVER_SECRET_NAME,
+        SPU_DEFAULT_NAME, SPU_PUBLIC_PORT, SPU_PRIVATE_PORT, SPU_HEALTH_PORT, SC_PRIVATE_PORT,
+        PRODUCT_NAME, TLS_SERVER_SECRET_NAME,
     };
+    use fluvio_service::health::{LIVENESS_PATH, READINESS_PATH};
 
     use crate::stores::spg::SpuGroupSpec;
     use super::super::statefulset::K8StatefulSetSpec;
@@ -220,6 +223,12 @@ mod k8_convert {
         };
         private_port.name = Some("private".to_owned());
 
+        let mut health_port = ContainerPortSpec {
+            container_port: SPU_HEALTH_PORT,
+            ..Default::default()
+        };
+        health_port.name = Some("health".to_owned());
+
         // storage is special because defaults are explicit.
         let storage = spu_template.real_storage_config();
         let size = storage.size;
@@ -255,6 +264,8 @@ mod k8_convert {
             storage.log_dir,
             "--log-size".to_owned(),
             size.clone(),
+            "--bind-health".to_owned(),
+            format!("0.0.0.0:{SPU_HEALTH_PORT}"),
         ];
 
         if let Some(tls) = tls_config {
@@ -314,10 +325,12 @@ mod k8_convert {
             name: SPU_DEFAULT_NAME.to_owned(),
             image: Some(spu_k8_config.image.clone()),
             resources: spu_pod_config.resources.clone(),
-            ports: vec![public_port, private_port],
+            ports: vec![public_port, private_port, health_port],
             volume_mounts,
             env,
             args,
+            liveness_probe: Some(http_probe(LIVENESS_PATH)),
+            readiness_probe: Some(http_probe(READINESS_PATH)),
             ..Default::default()
         }];
 
@@ -335,6 +348,7 @@ mod k8_convert {
                 security_context: spu_k8_config.pod_security_context.clone(),
                 node_selector: Some(spu_pod_config.node_selector.clone()),
                 priority_class_name: spu_pod_config.priority_class_name.clone(),
+                affinity: spu_pod_config.affinity.clone(),
                 ..Default::default()
             },
         };
@@ -362,6 +376,20 @@ mod k8_convert {
         }
     }
 
+    /// probe against SPU health server
+    fn http_probe(path: &str) -> Probe {
+        Probe {
+            http_get: Some(HttpGetAction {
+                path: Some(path.to_owned()),
+                port: TargetPort::Number(SPU_HEALTH_PORT),
+                ..Default::default()
+            }),
+            period_seconds: Some(10),
+            failure_threshold: Some(3),
+            ..Default::default()
+        }
+    }
+
     /// generate headless service from SPG spec
     /// for now, we forgo port and env variable because it wasn't mapped from K8
     pub fn generate_service(_spg: &SpuGroupSpec, group_name: &str) -> ServiceSpec {
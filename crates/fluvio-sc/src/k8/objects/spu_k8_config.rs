@@ -9,7 +9,7 @@ use fluvio_controlplane_metadata::core::MetadataContext;
 use fluvio_types::defaults::SPU_PUBLIC_PORT;
 use fluvio_stream_model::k8_types::Env;
 use fluvio_stream_model::k8_types::core::pod::{
-    ResourceRequirements, PodSecurityContext, ContainerSpec, VolumeMount, VolumeSpec,
+    ResourceRequirements, PodSecurityContext, ContainerSpec, VolumeMount, VolumeSpec, Affinity,
 };
 use fluvio_stream_model::k8_types::core::config_map::{ConfigMapSpec, ConfigMapStatus};
 use fluvio_stream_model::k8_types::core::service::{
@@ -38,6 +38,8 @@ pub struct PodConfig {
     #[serde(default)]
     pub extra_volumes: Vec<VolumeSpec>,
     pub priority_class_name: Option<String>,
+    /// scheduling constraints of SPU pods, such as anti-affinity to spread them across nodes
+    pub affinity: Option<Affinity>,
 }
 
 #[derive(Debug, Eq, PartialEq, Default, Clone, Serialize, Deserialize)]
@@ -4,6 +4,8 @@ pub mod k8;
 pub mod cli;
 pub mod core;
 pub mod start;
+pub mod validate;
+pub mod secrets;
 
 pub mod stores;
 mod init;
@@ -11,6 +13,10 @@ mod error;
 mod services;
 mod controllers;
 
+/// topic reconciliation, exposed for benchmarks
+#[doc(hidden)]
+pub use controllers::topics::TopicReducer;
+
 const VERSION: &str = include_str!("../../../VERSION");
 
 pub mod dispatcher {
@@ -0,0 +1,256 @@
+use std::fmt;
+use std::time::SystemTime;
+
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+
+use fluvio_future::task::spawn_blocking;
+
+use super::{SecretProvider, SecretRef};
+
+const SERVICE: &str = "secretsmanager";
+const TARGET: &str = "secretsmanager.GetSecretValue";
+const CONTENT_TYPE: &str = "application/x-amz-json-1.1";
+
+/// Reads secret strings of AWS Secrets Manager. Region and credentials are read from
+/// `AWS_REGION`, `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` and optional `AWS_SESSION_TOKEN`,
+/// endpoint can be changed with `AWS_ENDPOINT_URL_SECRETS_MANAGER`
+pub(crate) struct AwsSecretsManager {
+    endpoint: String,
+    host: String,
+    region: String,
+    credentials: Credentials,
+}
+
+struct Credentials {
+    access_key_id: String,
+    secret_access_key: String,
+    session_token: Option<String>,
+}
+
+impl fmt::Debug for AwsSecretsManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "AwsSecretsManager {{ endpoint: {} }}", self.endpoint)
+    }
+}
+
+impl AwsSecretsManager {
+    pub(crate) fn from_env() -> Result<Self> {
+        let env = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+        let region = env("AWS_REGION")
+            .or_else(|| env("AWS_DEFAULT_REGION"))
+            .ok_or_else(|| anyhow!("AWS_REGION is required for AWS secrets"))?;
+        let credentials = Credentials {
+            access_key_id: env("AWS_ACCESS_KEY_ID")
+                .ok_or_else(|| anyhow!("AWS_ACCESS_KEY_ID is required for AWS secrets"))?,
+            secret_access_key: env("AWS_SECRET_ACCESS_KEY")
+                .ok_or_else(|| anyhow!("AWS_SECRET_ACCESS_KEY is required for AWS secrets"))?,
+            session_token: env("AWS_SESSION_TOKEN"),
+        };
+        let endpoint = env("AWS_ENDPOINT_URL_SECRETS_MANAGER")
+            .unwrap_or_else(|| format!("https://{SERVICE}.{region}.amazonaws.com"));
+        let endpoint = endpoint.trim_end_matches('/').to_owned();
+        let host = endpoint
+            .split_once("://")
+            .map_or(endpoint.as_str(), |(_, host)| host)
+            .to_owned();
+        Ok(Self {
+            endpoint,
+            host,
+            region,
+            credentials,
+        })
+    }
+}
+
+#[async_trait]
+impl SecretProvider for AwsSecretsManager {
+    async fn fetch(&self, secret: &SecretRef) -> Result<Vec<u8>> {
+        let SecretRef::Aws { secret_id, field } = secret else {
+            return Err(anyhow!("{secret} is not an AWS secret"));
+        };
+        let payload = json!({ "SecretId": secret_id }).to_string();
+        let amz_date = amz_date(SystemTime::now());
+        let authorization = authorization(
+            &self.credentials,
+            &self.region,
+            &self.host,
+            &amz_date,
+            &payload,
+        );
+        let mut request = ureq::post(&format!("{}/", self.endpoint))
+            .set("Content-Type", CONTENT_TYPE)
+            .set("X-Amz-Date", &amz_date)
+            .set("X-Amz-Target", TARGET)
+            .set("Authorization", &authorization);
+        if let Some(token) = &self.credentials.session_token {
+            request = request.set("X-Amz-Security-Token", token);
+        }
+        let body = spawn_blocking(move || -> Result<String> {
+            Ok(request
+                .send_string(&payload)
+                .map_err(|err| anyhow!("AWS Secrets Manager request failed: {err}"))?
+                .into_string()?)
+        })
+        .await?;
+
+        let body: Value = serde_json::from_str(&body)?;
+        let secret_string = body["SecretString"]
+            .as_str()
+            .ok_or_else(|| anyhow!("{secret} has no secret string"))?;
+        match field {
+            None => Ok(secret_string.as_bytes().to_vec()),
+            Some(field) => {
+                let fields: Value = serde_json::from_str(secret_string)
+                    .map_err(|err| anyhow!("{secret} is not JSON: {err}"))?;
+                fields[field]
+                    .as_str()
+                    .map(|value| value.as_bytes().to_vec())
+                    .ok_or_else(|| anyhow!("{secret} has no string field {field}"))
+            }
+        }
+    }
+}
+
+/// `YYYYMMDDTHHMMSSZ` time of request
+fn amz_date(time: SystemTime) -> String {
+    humantime::format_rfc3339_seconds(time)
+        .to_string()
+        .replace(['-', ':'], "")
+}
+
+/// signature version 4 `Authorization` header of GetSecretValue request
+fn authorization(
+    credentials: &Credentials,
+    region: &str,
+    host: &str,
+    amz_date: &str,
+    payload: &str,
+) -> String {
+    let date = &amz_date[..8];
+    let mut headers = vec![
+        ("content-type", CONTENT_TYPE),
+        ("host", host),
+        ("x-amz-date", amz_date),
+        ("x-amz-target", TARGET),
+    ];
+    if let Some(token) = &credentials.session_token {
+        headers.push(("x-amz-security-token", token.as_str()));
+    }
+    headers.sort();
+    let canonical_headers: String = headers
+        .iter()
+        .map(|(name, value)| format!("{name}:{}\n", value.trim()))
+        .collect();
+    let signed_headers = headers
+        .iter()
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>()
+        .join(";");
+    let canonical_request = format!(
+        "POST\n/\n\n{canonical_headers}\n{signed_headers}\n{}",
+        hex::encode(Sha256::digest(payload.as_bytes()))
+    );
+
+    let scope = format!("{date}/{region}/{SERVICE}/aws4_request");
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{amz_date}\n{scope}\n{}",
+        hex::encode(Sha256::digest(canonical_request.as_bytes()))
+    );
+    let key = signing_key(&credentials.secret_access_key, date, region, SERVICE);
+    let signature = hex::encode(hmac_sha256(&key, string_to_sign.as_bytes()));
+    format!(
+        "AWS4-HMAC-SHA256 Credential={}/{scope}, SignedHeaders={signed_headers}, Signature={signature}",
+        credentials.access_key_id
+    )
+}
+
+fn signing_key(secret_access_key: &str, date: &str, region: &str, service: &str) -> [u8; 32] {
+    let key = hmac_sha256(
+        format!("AWS4{secret_access_key}").as_bytes(),
+        date.as_bytes(),
+    );
+    let key = hmac_sha256(&key, region.as_bytes());
+    let key = hmac_sha256(&key, service.as_bytes());
+    hmac_sha256(&key, b"aws4_request")
+}
+
+/// HMAC-SHA256, RFC 2104
+fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; 32] {
+    const BLOCK_SIZE: usize = 64;
+
+    let mut block = [0u8; BLOCK_SIZE];
+    if key.len() > BLOCK_SIZE {
+        block[..32].copy_from_slice(&Sha256::digest(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+    let mut inner = Sha256::new();
+    inner.update(block.map(|byte| byte ^ 0x36));
+    inner.update(data);
+    let mut outer = Sha256::new();
+    outer.update(block.map(|byte| byte ^ 0x5c));
+    outer.update(inner.finalize());
+    outer.finalize().into()
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::*;
+
+    #[test]
+    fn test_hmac_sha256() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            hex::encode(hmac_sha256(b"Jefe", b"what do ya want for nothing?")),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn test_signing_key() {
+        // example of AWS signature version 4 documentation
+        assert_eq!(
+            hex::encode(signing_key(
+                "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+                "20150830",
+                "us-east-1",
+                "iam"
+            )),
+            "c4afb1cc5771d871763a393e44b703571b55cc28424d1a5e86da6ed3c154a4b9"
+        );
+    }
+
+    #[test]
+    fn test_authorization() {
+        let credentials = Credentials {
+            access_key_id: "AKIDEXAMPLE".to_owned(),
+            secret_access_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".to_owned(),
+            session_token: None,
+        };
+        let payload = json!({ "SecretId": "prod/fluvio" }).to_string();
+        assert_eq!(
+            authorization(
+                &credentials,
+                "us-east-1",
+                "secretsmanager.us-east-1.amazonaws.com",
+                "20150830T123600Z",
+                &payload
+            ),
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/secretsmanager/aws4_request, \
+             SignedHeaders=content-type;host;x-amz-date;x-amz-target, \
+             Signature=2273b201cdb3c7b24c3cead6000d78ea934bbb93cf6ec9aee507b209f359cd78"
+        );
+    }
+
+    #[test]
+    fn test_amz_date() {
+        let time = UNIX_EPOCH + Duration::from_secs(1_440_938_160);
+        assert_eq!(amz_date(time), "20150830T123600Z");
+    }
+}
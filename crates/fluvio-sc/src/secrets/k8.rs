@@ -0,0 +1,61 @@
+use std::fmt;
+
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+
+use fluvio_stream_model::k8_types::InputObjectMeta;
+use fluvio_stream_model::k8_types::core::secret::SecretSpec;
+use k8_client::meta_client::MetadataClient;
+use k8_client::{K8Config, SharedK8Client};
+
+use crate::config::DEFAULT_NAMESPACE;
+
+use super::{SecretProvider, SecretRef};
+
+/// Reads keys of Kubernetes secrets in SC namespace with SC service account
+pub(crate) struct K8Secrets {
+    client: SharedK8Client,
+    namespace: String,
+}
+
+impl fmt::Debug for K8Secrets {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "K8Secrets {{ namespace: {} }}", self.namespace)
+    }
+}
+
+impl K8Secrets {
+    pub(crate) fn from_env(namespace: &str) -> Result<Self> {
+        let config =
+            K8Config::load().map_err(|err| anyhow!("unable to load kubernetes config: {err}"))?;
+        // same as SC, default namespace is replaced with one from k8 config
+        let namespace = if namespace == DEFAULT_NAMESPACE {
+            config.namespace().to_owned()
+        } else {
+            namespace.to_owned()
+        };
+        Ok(Self {
+            client: k8_client::new_shared(config)?,
+            namespace,
+        })
+    }
+}
+
+#[async_trait]
+impl SecretProvider for K8Secrets {
+    async fn fetch(&self, secret: &SecretRef) -> Result<Vec<u8>> {
+        let SecretRef::K8s { name, key } = secret else {
+            return Err(anyhow!("{secret} is not a kubernetes secret"));
+        };
+        let meta = InputObjectMeta::named(name, &self.namespace);
+        let item = self.client.retrieve_item::<SecretSpec, _>(&meta).await?;
+        let encoded = item
+            .header
+            .data
+            .get(key)
+            .ok_or_else(|| anyhow!("secret {name} has no key {key}"))?;
+        Ok(STANDARD.decode(encoded)?)
+    }
+}
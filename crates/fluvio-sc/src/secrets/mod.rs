@@ -0,0 +1,551 @@
+//!
+//! # Secrets
+//!
+//! TLS and authorization material can be read from files or from secret providers:
+//! Kubernetes secrets, Vault and AWS Secrets Manager.
+//! Secrets are referenced by URI:
+//!
+//! - `k8s://<secret>/<key>`: key of Kubernetes secret in SC namespace
+//! - `vault://<path>#<field>`: field of Vault secret, ex: `vault://secret/data/fluvio/tls#cert`
+//! - `aws-sm://<secret id>[#<field>]`: AWS secret string, or field of JSON secret string
+//! - `file://<path>` or plain path: file
+//!
+//! Resolved secrets are cached and refreshed periodically. Consumers which need
+//! a file, such as TLS acceptor, get a copy of secret which is updated on refresh,
+//! and are notified to load it again. Copies are readable only by SC and are removed
+//! with resolver, or by next SC when SC was killed.
+//!
+
+mod aws;
+mod k8;
+mod vault;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use anyhow::{Context, Result, anyhow};
+use async_trait::async_trait;
+use once_cell::sync::OnceCell;
+use tracing::{debug, info, warn};
+
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+use fluvio_types::event::offsets::{OffsetChangeListener, OffsetPublisher, SharedOffsetPublisher};
+
+use self::aws::AwsSecretsManager;
+use self::k8::K8Secrets;
+use self::vault::VaultSecrets;
+
+pub const DEFAULT_SECRET_REFRESH: Duration = Duration::from_secs(300);
+
+const SECRETS_DIR_PREFIX: &str = "fluvio-sc-secrets-";
+
+pub type SharedSecretResolver = Arc<SecretResolver>;
+
+/// Reference to secret of provider
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SecretRef {
+    File(PathBuf),
+    K8s {
+        name: String,
+        key: String,
+    },
+    Vault {
+        path: String,
+        field: String,
+    },
+    Aws {
+        secret_id: String,
+        field: Option<String>,
+    },
+}
+
+impl SecretRef {
+    /// secret is a plain file, which doesn't need resolution
+    pub fn as_file(&self) -> Option<&Path> {
+        match self {
+            Self::File(path) => Some(path),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for SecretRef {
+    type Err = anyhow::Error;
+
+    fn from_str(uri: &str) -> Result<Self, Self::Err> {
+        if let Some(rest) = uri.strip_prefix("k8s://") {
+            let (name, key) = rest
+                .split_once('/')
+                .filter(|(name, key)| !name.is_empty() && !key.is_empty())
+                .ok_or_else(|| anyhow!("{uri}: expected k8s://<secret>/<key>"))?;
+            Ok(Self::K8s {
+                name: name.to_owned(),
+                key: key.to_owned(),
+            })
+        } else if let Some(rest) = uri.strip_prefix("vault://") {
+            let (path, field) = rest
+                .split_once('#')
+                .filter(|(path, field)| !path.is_empty() && !field.is_empty())
+                .ok_or_else(|| anyhow!("{uri}: expected vault://<path>#<field>"))?;
+            Ok(Self::Vault {
+                path: path.trim_start_matches('/').to_owned(),
+                field: field.to_owned(),
+            })
+        } else if let Some(rest) = uri.strip_prefix("aws-sm://") {
+            let (secret_id, field) = match rest.split_once('#') {
+                Some((secret_id, field)) => (secret_id, Some(field.to_owned())),
+                None => (rest, None),
+            };
+            if secret_id.is_empty() {
+                return Err(anyhow!("{uri}: expected aws-sm://<secret id>[#<field>]"));
+            }
+            Ok(Self::Aws {
+                secret_id: secret_id.to_owned(),
+                field,
+            })
+        } else if let Some(path) = uri.strip_prefix("file://") {
+            Ok(Self::File(path.into()))
+        } else if uri.is_empty() {
+            Err(anyhow!("empty secret reference"))
+        } else {
+            Ok(Self::File(uri.into()))
+        }
+    }
+}
+
+impl fmt::Display for SecretRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::File(path) => write!(f, "{}", path.display()),
+            Self::K8s { name, key } => write!(f, "k8s://{name}/{key}"),
+            Self::Vault { path, field } => write!(f, "vault://{path}#{field}"),
+            Self::Aws {
+                secret_id,
+                field: Some(field),
+            } => write!(f, "aws-sm://{secret_id}#{field}"),
+            Self::Aws {
+                secret_id,
+                field: None,
+            } => write!(f, "aws-sm://{secret_id}"),
+        }
+    }
+}
+
+/// Reads secrets from a store
+#[async_trait]
+pub trait SecretProvider: fmt::Debug + Send + Sync + 'static {
+    async fn fetch(&self, secret: &SecretRef) -> Result<Vec<u8>>;
+}
+
+#[derive(Debug)]
+struct FileSecrets;
+
+#[async_trait]
+impl SecretProvider for FileSecrets {
+    async fn fetch(&self, secret: &SecretRef) -> Result<Vec<u8>> {
+        let path = secret
+            .as_file()
+            .ok_or_else(|| anyhow!("{secret} is not a file"))?;
+        fs::read(path).with_context(|| format!("unable to read {}", path.display()))
+    }
+}
+
+/// Resolves secrets with provider of their scheme.
+/// Providers are configured from environment when first used.
+#[derive(Debug)]
+pub struct SecretResolver {
+    namespace: String,
+    refresh: Duration,
+    dir: PathBuf,
+    k8: OnceCell<Arc<dyn SecretProvider>>,
+    vault: OnceCell<Arc<dyn SecretProvider>>,
+    aws: OnceCell<Arc<dyn SecretProvider>>,
+    cache: RwLock<HashMap<SecretRef, Arc<[u8]>>>,
+    /// files with copies of secrets
+    files: RwLock<HashMap<SecretRef, PathBuf>>,
+    /// incremented when secret with file changes
+    file_changes: SharedOffsetPublisher,
+}
+
+impl Drop for SecretResolver {
+    fn drop(&mut self) {
+        if !self.files.get_mut().expect("secret files lock").is_empty() {
+            let _ = fs::remove_dir_all(&self.dir);
+        }
+    }
+}
+
+impl SecretResolver {
+    /// resolver of secrets in namespace, secret files are written to `dir`
+    pub fn new(namespace: impl Into<String>, refresh: Duration, dir: PathBuf) -> Self {
+        Self {
+            namespace: namespace.into(),
+            refresh,
+            dir,
+            k8: OnceCell::new(),
+            vault: OnceCell::new(),
+            aws: OnceCell::new(),
+            cache: RwLock::new(HashMap::new()),
+            files: RwLock::new(HashMap::new()),
+            file_changes: OffsetPublisher::shared(0),
+        }
+    }
+
+    /// directory of secret files of this SC process
+    pub fn process_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("{SECRETS_DIR_PREFIX}{}", std::process::id()))
+    }
+
+    /// remove secret files of SC processes which are not running, they are left
+    /// when SC is killed
+    pub fn remove_stale_dirs() {
+        use sysinfo::{Pid, ProcessesToUpdate, System};
+
+        let Ok(entries) = fs::read_dir(std::env::temp_dir()) else {
+            return;
+        };
+        let mut system = System::new();
+        for entry in entries.flatten() {
+            let name = entry.file_name();
+            let Some(pid) = name
+                .to_str()
+                .and_then(|name| name.strip_prefix(SECRETS_DIR_PREFIX))
+                .and_then(|pid| pid.parse::<u32>().ok())
+            else {
+                continue;
+            };
+            let pid = Pid::from_u32(pid);
+            system.refresh_processes(ProcessesToUpdate::Some(&[pid]), true);
+            if system.process(pid).is_none() {
+                debug!(path = %entry.path().display(), "removing stale secrets");
+                let _ = fs::remove_dir_all(entry.path());
+            }
+        }
+    }
+
+    /// notified when secret copied to file changes, consumers of files should load them again
+    pub fn file_changes(&self) -> OffsetChangeListener {
+        self.file_changes.change_listener()
+    }
+
+    fn provider(&self, secret: &SecretRef) -> Result<Arc<dyn SecretProvider>> {
+        let provider = match secret {
+            SecretRef::File(_) => return Ok(Arc::new(FileSecrets)),
+            SecretRef::K8s { .. } => self.k8.get_or_try_init(|| {
+                let provider: Arc<dyn SecretProvider> =
+                    Arc::new(K8Secrets::from_env(&self.namespace)?);
+                anyhow::Ok(provider)
+            })?,
+            SecretRef::Vault { .. } => self.vault.get_or_try_init(|| {
+                let provider: Arc<dyn SecretProvider> = Arc::new(VaultSecrets::from_env()?);
+                anyhow::Ok(provider)
+            })?,
+            SecretRef::Aws { .. } => self.aws.get_or_try_init(|| {
+                let provider: Arc<dyn SecretProvider> = Arc::new(AwsSecretsManager::from_env()?);
+                anyhow::Ok(provider)
+            })?,
+        };
+        Ok(provider.clone())
+    }
+
+    async fn fetch(&self, secret: &SecretRef) -> Result<Vec<u8>> {
+        self.provider(secret)?
+            .fetch(secret)
+            .await
+            .with_context(|| format!("unable to resolve secret {secret}"))
+    }
+
+    /// value of secret, fetched from provider if not cached
+    pub async fn resolve(&self, secret: &SecretRef) -> Result<Arc<[u8]>> {
+        if let Some(value) = self.cached(secret) {
+            return Ok(value);
+        }
+        let value: Arc<[u8]> = self.fetch(secret).await?.into();
+        debug!(%secret, "secret resolved");
+        self.cache
+            .write()
+            .expect("secret cache lock")
+            .insert(secret.clone(), value.clone());
+        Ok(value)
+    }
+
+    /// cached value of secret, value is replaced when secret changes
+    pub fn cached(&self, secret: &SecretRef) -> Option<Arc<[u8]>> {
+        self.cache
+            .read()
+            .expect("secret cache lock")
+            .get(secret)
+            .cloned()
+    }
+
+    /// path of file with secret, plain files are used as they are
+    pub async fn resolve_file(&self, secret: &SecretRef) -> Result<PathBuf> {
+        if let Some(path) = secret.as_file() {
+            return Ok(path.to_owned());
+        }
+        let value = self.resolve(secret).await?;
+        let path = self.dir.join(file_name(secret));
+        write_private(&self.dir, &path, &value)?;
+        self.files
+            .write()
+            .expect("secret files lock")
+            .insert(secret.clone(), path.clone());
+        Ok(path)
+    }
+
+    /// fetch cached secrets again, changed secrets replace cached values and their files.
+    /// Secrets which can't be fetched keep their last value
+    pub async fn refresh(&self) {
+        let secrets: Vec<SecretRef> = self
+            .cache
+            .read()
+            .expect("secret cache lock")
+            .keys()
+            .cloned()
+            .collect();
+        for secret in secrets {
+            let value = match self.fetch(&secret).await {
+                Ok(value) => value,
+                Err(err) => {
+                    warn!(%secret, "secret refresh failed, keeping last value: {err:#}");
+                    continue;
+                }
+            };
+            if self.cached(&secret).as_deref() == Some(value.as_slice()) {
+                continue;
+            }
+            info!(%secret, "secret changed");
+            let value: Arc<[u8]> = value.into();
+            let path = self
+                .files
+                .read()
+                .expect("secret files lock")
+                .get(&secret)
+                .cloned();
+            let file_changed = match path {
+                Some(path) => match write_private(&self.dir, &path, &value) {
+                    Ok(()) => true,
+                    Err(err) => {
+                        warn!(%secret, "unable to update secret file: {err:#}");
+                        false
+                    }
+                },
+                None => false,
+            };
+            self.cache
+                .write()
+                .expect("secret cache lock")
+                .insert(secret, value);
+            if file_changed {
+                self.file_changes.update_increment();
+            }
+        }
+    }
+
+    /// refresh secrets periodically in background
+    pub fn start_refresh(self: &Arc<Self>) {
+        info!(refresh_secs = self.refresh.as_secs(), "refreshing secrets");
+        let resolver = self.clone();
+        spawn(async move {
+            loop {
+                sleep(resolver.refresh).await;
+                resolver.refresh().await;
+            }
+        });
+    }
+}
+
+/// name of secret file, unique for secret
+fn file_name(secret: &SecretRef) -> String {
+    secret
+        .to_string()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// write file readable only by SC, file is created with its mode so secret is never exposed
+fn write_private(dir: &Path, path: &Path, value: &[u8]) -> Result<()> {
+    let mut dir_builder = fs::DirBuilder::new();
+    dir_builder.recursive(true);
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt};
+        dir_builder.mode(0o700);
+        options.mode(0o600);
+    }
+    dir_builder
+        .create(dir)
+        .with_context(|| format!("unable to create {}", dir.display()))?;
+
+    // write to temporary file and rename, so readers never see partial secret
+    let tmp = path.with_extension("tmp");
+    let _ = fs::remove_file(&tmp);
+    options
+        .open(&tmp)
+        .and_then(|mut file| file.write_all(value))
+        .with_context(|| format!("unable to write {}", tmp.display()))?;
+    fs::rename(&tmp, path).with_context(|| format!("unable to write {}", path.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_parse_secret_ref() {
+        assert_eq!(
+            "k8s://fluvio-tls/tls.crt"
+                .parse::<SecretRef>()
+                .expect("parse"),
+            SecretRef::K8s {
+                name: "fluvio-tls".to_owned(),
+                key: "tls.crt".to_owned()
+            }
+        );
+        assert_eq!(
+            "vault://secret/data/fluvio/tls#cert"
+                .parse::<SecretRef>()
+                .expect("parse"),
+            SecretRef::Vault {
+                path: "secret/data/fluvio/tls".to_owned(),
+                field: "cert".to_owned()
+            }
+        );
+        assert_eq!(
+            "aws-sm://prod/fluvio#policy"
+                .parse::<SecretRef>()
+                .expect("parse"),
+            SecretRef::Aws {
+                secret_id: "prod/fluvio".to_owned(),
+                field: Some("policy".to_owned())
+            }
+        );
+        assert_eq!(
+            "/etc/fluvio/tls.crt".parse::<SecretRef>().expect("parse"),
+            SecretRef::File("/etc/fluvio/tls.crt".into())
+        );
+        assert_eq!(
+            "file:///etc/fluvio/tls.crt"
+                .parse::<SecretRef>()
+                .expect("parse"),
+            SecretRef::File("/etc/fluvio/tls.crt".into())
+        );
+        assert!("k8s://fluvio-tls".parse::<SecretRef>().is_err());
+        assert!("vault://secret/data/fluvio".parse::<SecretRef>().is_err());
+        assert!("aws-sm://".parse::<SecretRef>().is_err());
+
+        for uri in [
+            "k8s://fluvio-tls/tls.crt",
+            "vault://secret/data/fluvio/tls#cert",
+            "aws-sm://prod/fluvio",
+        ] {
+            assert_eq!(uri.parse::<SecretRef>().expect("parse").to_string(), uri);
+        }
+    }
+
+    #[fluvio_future::test]
+    async fn test_resolve_and_refresh() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let dir = temp.path().to_owned();
+        let source = dir.join("policy.json");
+        fs::write(&source, b"v1").expect("write");
+
+        let resolver = SecretResolver::new("default", DEFAULT_SECRET_REFRESH, dir.join("secrets"));
+        let secret = SecretRef::File(source.clone());
+        let first = resolver.resolve(&secret).await.expect("resolve");
+        assert_eq!(&*first, b"v1");
+        assert_eq!(
+            resolver.resolve_file(&secret).await.expect("file"),
+            source,
+            "plain files are not copied"
+        );
+
+        // unchanged secret keeps value
+        resolver.refresh().await;
+        let cached = resolver.cached(&secret).expect("cached");
+        assert!(Arc::ptr_eq(&first, &cached));
+
+        fs::write(&source, b"v2").expect("write");
+        resolver.refresh().await;
+        assert_eq!(&*resolver.cached(&secret).expect("cached"), b"v2");
+
+        // failed refresh keeps last value
+        fs::remove_file(&source).expect("remove");
+        resolver.refresh().await;
+        assert_eq!(&*resolver.cached(&secret).expect("cached"), b"v2");
+    }
+
+    /// secret value which can be changed by test
+    #[derive(Debug, Default)]
+    struct MemorySecret(std::sync::Mutex<Vec<u8>>);
+
+    #[async_trait]
+    impl SecretProvider for MemorySecret {
+        async fn fetch(&self, _secret: &SecretRef) -> Result<Vec<u8>> {
+            Ok(self.0.lock().expect("lock").clone())
+        }
+    }
+
+    #[fluvio_future::test]
+    async fn test_refresh_secret_file() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let secrets_dir = temp.path().join("secrets");
+        let resolver = SecretResolver::new("default", DEFAULT_SECRET_REFRESH, secrets_dir.clone());
+        let provider = Arc::new(MemorySecret::default());
+        *provider.0.lock().expect("lock") = b"v1".to_vec();
+        resolver
+            .k8
+            .set(provider.clone() as Arc<dyn SecretProvider>)
+            .expect("provider is not set");
+        let mut changes = resolver.file_changes();
+
+        let secret: SecretRef = "k8s://fluvio-tls/tls.key".parse().expect("parse");
+        let path = resolver.resolve_file(&secret).await.expect("file");
+        assert_eq!(path, secrets_dir.join(file_name(&secret)));
+        assert_eq!(fs::read(&path).expect("read"), b"v1");
+
+        *provider.0.lock().expect("lock") = b"v2".to_vec();
+        resolver.refresh().await;
+        assert_eq!(fs::read(&path).expect("read"), b"v2");
+        assert_eq!(changes.listen().await, 1);
+
+        drop(resolver);
+        assert!(
+            !secrets_dir.exists(),
+            "secret files are removed with resolver"
+        );
+    }
+
+    #[test]
+    fn test_write_private() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let dir = temp.path().join("secrets");
+        let secret: SecretRef = "k8s://fluvio-tls/tls.key".parse().expect("parse");
+        let path = dir.join(file_name(&secret));
+        assert!(path.ends_with("k8s___fluvio_tls_tls_key"));
+
+        write_private(&dir, &path, b"key").expect("write");
+        write_private(&dir, &path, b"new key").expect("write");
+        assert_eq!(fs::read(&path).expect("read"), b"new key");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).expect("metadata").permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+            let mode = fs::metadata(&dir).expect("metadata").permissions().mode();
+            assert_eq!(mode & 0o777, 0o700);
+        }
+    }
+}
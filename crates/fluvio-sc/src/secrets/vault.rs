@@ -0,0 +1,100 @@
+use std::fmt;
+
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use serde_json::Value;
+
+use fluvio_future::task::spawn_blocking;
+
+use super::{SecretProvider, SecretRef};
+
+const DEFAULT_VAULT_ADDR: &str = "http://127.0.0.1:8200";
+
+/// Reads fields of Vault KV secrets, address and token are read from
+/// `VAULT_ADDR`, `VAULT_TOKEN` and optional `VAULT_NAMESPACE`
+pub(crate) struct VaultSecrets {
+    addr: String,
+    token: String,
+    namespace: Option<String>,
+}
+
+impl fmt::Debug for VaultSecrets {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "VaultSecrets {{ addr: {} }}", self.addr)
+    }
+}
+
+impl VaultSecrets {
+    pub(crate) fn from_env() -> Result<Self> {
+        let addr = std::env::var("VAULT_ADDR").unwrap_or_else(|_| DEFAULT_VAULT_ADDR.to_owned());
+        let token = std::env::var("VAULT_TOKEN")
+            .map_err(|_| anyhow!("VAULT_TOKEN is required for vault secrets"))?;
+        Ok(Self {
+            addr: addr.trim_end_matches('/').to_owned(),
+            token,
+            namespace: std::env::var("VAULT_NAMESPACE").ok(),
+        })
+    }
+}
+
+#[async_trait]
+impl SecretProvider for VaultSecrets {
+    async fn fetch(&self, secret: &SecretRef) -> Result<Vec<u8>> {
+        let SecretRef::Vault { path, field } = secret else {
+            return Err(anyhow!("{secret} is not a vault secret"));
+        };
+        let mut request =
+            ureq::get(&format!("{}/v1/{path}", self.addr)).set("X-Vault-Token", &self.token);
+        if let Some(namespace) = &self.namespace {
+            request = request.set("X-Vault-Namespace", namespace);
+        }
+        let body = spawn_blocking(move || -> Result<String> {
+            Ok(request
+                .call()
+                .map_err(|err| anyhow!("vault request failed: {err}"))?
+                .into_string()?)
+        })
+        .await?;
+        let body: Value = serde_json::from_str(&body)?;
+        field_value(&body, field).map(|value| value.as_bytes().to_vec())
+    }
+}
+
+/// field of KV v2 secret, or of KV v1 secret
+fn field_value<'a>(body: &'a Value, field: &str) -> Result<&'a str> {
+    let data = &body["data"];
+    let data = if data["data"].is_object() && data["metadata"].is_object() {
+        &data["data"]
+    } else {
+        data
+    };
+    data[field]
+        .as_str()
+        .ok_or_else(|| anyhow!("vault secret has no string field {field}"))
+}
+
+#[cfg(test)]
+mod test {
+
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_field_value() {
+        let kv2 = json!({
+            "data": {
+                "data": {"cert": "-----BEGIN CERTIFICATE-----"},
+                "metadata": {"version": 3}
+            }
+        });
+        assert_eq!(
+            field_value(&kv2, "cert").expect("field"),
+            "-----BEGIN CERTIFICATE-----"
+        );
+
+        let kv1 = json!({"data": {"policy": "{}"}});
+        assert_eq!(field_value(&kv1, "policy").expect("field"), "{}");
+        assert!(field_value(&kv1, "cert").is_err());
+    }
+}
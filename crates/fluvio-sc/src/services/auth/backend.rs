@@ -0,0 +1,344 @@
+//!
+//! # Authorization backends
+//!
+//! Authorization decisions of authenticated principals are made by a policy backend:
+//! the basic RBAC policy file, an external webhook, or an OPA server.
+//! Webhook and OPA are called over plain HTTP, they are expected to run as sidecar
+//! or inside cluster network. Errors and timeouts of external backends deny the request.
+//!
+
+use std::fmt::Debug;
+use std::io::Error as IoError;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use tracing::{debug, instrument};
+
+use fluvio_auth::AuthError;
+use fluvio_future::task::spawn_blocking;
+
+use super::cache::DecisionKey;
+
+pub const DEFAULT_BACKEND_TIMEOUT: Duration = Duration::from_secs(1);
+
+pub type SharedPolicyBackend = Arc<dyn PolicyBackend>;
+
+/// Makes authorization decisions
+#[async_trait]
+pub trait PolicyBackend: Debug + Send + Sync + 'static {
+    /// name of backend for logs
+    fn kind(&self) -> &'static str;
+
+    /// whether principal of request is allowed to take its action
+    async fn decide(&self, request: &DecisionKey) -> Result<bool, AuthError>;
+
+    /// changes when policy is reloaded, cached decisions of previous policy are dropped
+    fn policy_version(&self) -> u64 {
+        0
+    }
+}
+
+/// Sends decision requests to webhook, webhook answers with `{"allowed": bool}`
+#[derive(Debug)]
+pub struct WebhookBackend {
+    endpoint: HttpEndpoint,
+    timeout: Duration,
+}
+
+impl WebhookBackend {
+    pub fn new(endpoint: HttpEndpoint, timeout: Duration) -> Self {
+        Self { endpoint, timeout }
+    }
+}
+
+#[async_trait]
+impl PolicyBackend for WebhookBackend {
+    fn kind(&self) -> &'static str {
+        "webhook"
+    }
+
+    #[instrument(skip(self))]
+    async fn decide(&self, request: &DecisionKey) -> Result<bool, AuthError> {
+        #[derive(Deserialize)]
+        struct WebhookDecision {
+            allowed: bool,
+        }
+
+        let response = post_json(&self.endpoint, &json!(request), self.timeout).await?;
+        let decision: WebhookDecision = serde_json::from_value(response)
+            .map_err(|err| backend_error(anyhow!("invalid webhook decision: {err}")))?;
+        Ok(decision.allowed)
+    }
+}
+
+/// Queries decision of OPA data API with request as input,
+/// ex: `http://localhost:8181/v1/data/fluvio/authz/allow`.
+/// Undefined decision denies request
+#[derive(Debug)]
+pub struct OpaBackend {
+    endpoint: HttpEndpoint,
+    timeout: Duration,
+}
+
+impl OpaBackend {
+    pub fn new(endpoint: HttpEndpoint, timeout: Duration) -> Self {
+        Self { endpoint, timeout }
+    }
+}
+
+#[async_trait]
+impl PolicyBackend for OpaBackend {
+    fn kind(&self) -> &'static str {
+        "opa"
+    }
+
+    #[instrument(skip(self))]
+    async fn decide(&self, request: &DecisionKey) -> Result<bool, AuthError> {
+        let response =
+            post_json(&self.endpoint, &json!({ "input": request }), self.timeout).await?;
+        match response.get("result") {
+            None => Ok(false),
+            Some(Value::Bool(allowed)) => Ok(*allowed),
+            Some(result) => Err(backend_error(anyhow!(
+                "OPA decision is not boolean: {result}"
+            ))),
+        }
+    }
+}
+
+/// Plain HTTP endpoint, `http://host[:port][/path]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpEndpoint {
+    host: String,
+    port: u16,
+    path: String,
+}
+
+impl FromStr for HttpEndpoint {
+    type Err = anyhow::Error;
+
+    fn from_str(url: &str) -> Result<Self, Self::Err> {
+        let rest = url
+            .strip_prefix("http://")
+            .ok_or_else(|| anyhow!("{url}: only http:// endpoints are supported"))?;
+        let (authority, path) = match rest.find('/') {
+            Some(index) => rest.split_at(index),
+            None => (rest, "/"),
+        };
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (
+                host,
+                port.parse()
+                    .map_err(|_| anyhow!("{url}: invalid port {port}"))?,
+            ),
+            None => (authority, 80),
+        };
+        if host.is_empty() {
+            return Err(anyhow!("{url}: missing host"));
+        }
+        Ok(Self {
+            host: host.to_owned(),
+            port,
+            path: path.to_owned(),
+        })
+    }
+}
+
+impl std::fmt::Display for HttpEndpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "http://{}:{}{}", self.host, self.port, self.path)
+    }
+}
+
+fn backend_error(err: anyhow::Error) -> AuthError {
+    AuthError::IoError(IoError::other(err.to_string()))
+}
+
+/// POST JSON body and parse JSON response of successful request,
+/// timeout covers connect, request and response
+async fn post_json(
+    endpoint: &HttpEndpoint,
+    body: &Value,
+    timeout: Duration,
+) -> Result<Value, AuthError> {
+    let url = endpoint.to_string();
+    let body = body.to_string();
+    let response = spawn_blocking(move || {
+        ureq::post(&url)
+            .timeout(timeout)
+            .set("Content-Type", "application/json")
+            .send_string(&body)
+    })
+    .await;
+
+    let body = match response {
+        Ok(response) => {
+            debug!(%endpoint, status = response.status(), "authorization backend response");
+            response.into_string()?
+        }
+        Err(ureq::Error::Status(status, response)) => {
+            return Err(backend_error(anyhow!(
+                "{endpoint} failed with status {status}: {}",
+                response.into_string().unwrap_or_default()
+            )));
+        }
+        Err(err) => return Err(backend_error(anyhow!("{endpoint}: {err}"))),
+    };
+    serde_json::from_str(&body)
+        .map_err(|err| backend_error(anyhow!("{endpoint} returned invalid JSON: {err}")))
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::sync::Mutex;
+
+    use futures_util::{AsyncReadExt, AsyncWriteExt};
+
+    use fluvio_controlplane_metadata::extended::ObjectType;
+    use fluvio_future::net::TcpListener;
+    use fluvio_future::task::spawn;
+
+    use super::*;
+    use super::super::basic::Action;
+
+    fn request() -> DecisionKey {
+        DecisionKey {
+            principal: "alice".to_owned(),
+            scopes: vec!["Default".to_owned()],
+            action: Action::Delete,
+            object_type: ObjectType::Topic,
+            instance: Some("orders".to_owned()),
+        }
+    }
+
+    /// serve one request with response body, returns endpoint and body of request,
+    /// which is set before response is sent
+    async fn serve_once(response: &'static str) -> (HttpEndpoint, Arc<Mutex<Option<Value>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("addr");
+        let received = Arc::new(Mutex::new(None));
+        let request_body = received.clone();
+        spawn(async move {
+            let (mut stream, _) = listener.accept().await.expect("accept");
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            loop {
+                let read = stream.read(&mut buf).await.expect("read");
+                if read == 0 {
+                    return;
+                }
+                request.extend_from_slice(&buf[..read]);
+                if let Ok((_, body)) = parse_request(&request)
+                    && let Ok(body) = serde_json::from_slice::<Value>(body)
+                {
+                    *request_body.lock().expect("lock") = Some(body);
+                    break;
+                }
+            }
+            let reply =
+                format!("HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\r\n{response}");
+            stream.write_all(reply.as_bytes()).await.expect("write");
+        });
+        let endpoint = format!("http://{addr}/v1/data/fluvio/allow")
+            .parse()
+            .expect("endpoint");
+        (endpoint, received)
+    }
+
+    fn parse_request(request: &[u8]) -> anyhow::Result<(&str, &[u8])> {
+        let head_end = request
+            .windows(4)
+            .position(|window| window == b"\r\n\r\n")
+            .ok_or_else(|| anyhow!("incomplete"))?;
+        let head = std::str::from_utf8(&request[..head_end])?;
+        Ok((head, &request[head_end + 4..]))
+    }
+
+    #[test]
+    fn test_parse_endpoint() {
+        assert_eq!(
+            "http://opa:8181/v1/data/fluvio/allow"
+                .parse::<HttpEndpoint>()
+                .expect("endpoint"),
+            HttpEndpoint {
+                host: "opa".to_owned(),
+                port: 8181,
+                path: "/v1/data/fluvio/allow".to_owned(),
+            }
+        );
+        assert_eq!(
+            "http://authz".parse::<HttpEndpoint>().expect("endpoint"),
+            HttpEndpoint {
+                host: "authz".to_owned(),
+                port: 80,
+                path: "/".to_owned(),
+            }
+        );
+        assert!("https://authz".parse::<HttpEndpoint>().is_err());
+        assert!("http://authz:port".parse::<HttpEndpoint>().is_err());
+    }
+
+    #[fluvio_future::test]
+    async fn test_webhook_decision() {
+        let (endpoint, received) = serve_once(r#"{"allowed": true}"#).await;
+        let backend = WebhookBackend::new(endpoint, DEFAULT_BACKEND_TIMEOUT);
+        assert!(backend.decide(&request()).await.expect("decide"));
+        assert_eq!(
+            received.lock().expect("lock").take().expect("request"),
+            json!({
+                "principal": "alice",
+                "scopes": ["Default"],
+                "action": "Delete",
+                "object_type": "Topic",
+                "instance": "orders",
+            })
+        );
+    }
+
+    #[fluvio_future::test]
+    async fn test_opa_decision() {
+        let (endpoint, received) = serve_once(r#"{"result": false}"#).await;
+        let backend = OpaBackend::new(endpoint, DEFAULT_BACKEND_TIMEOUT);
+        assert!(!backend.decide(&request()).await.expect("decide"));
+        assert_eq!(
+            received.lock().expect("lock").take().expect("request")["input"]["principal"],
+            "alice"
+        );
+
+        // undefined decision denies
+        let (endpoint, _received) = serve_once("{}").await;
+        let backend = OpaBackend::new(endpoint, DEFAULT_BACKEND_TIMEOUT);
+        assert!(!backend.decide(&request()).await.expect("decide"));
+    }
+
+    #[fluvio_future::test]
+    async fn test_backend_errors() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("addr");
+        spawn(async move {
+            let (mut stream, _) = listener.accept().await.expect("accept");
+            let reply = "HTTP/1.1 403 Forbidden\r\nContent-Length: 2\r\n\r\n{}";
+            stream.write_all(reply.as_bytes()).await.expect("write");
+        });
+        let endpoint: HttpEndpoint = format!("http://{addr}/allow").parse().expect("endpoint");
+        let backend = WebhookBackend::new(endpoint, DEFAULT_BACKEND_TIMEOUT);
+        assert!(backend.decide(&request()).await.is_err());
+
+        // backend accepting connection but not answering
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("addr");
+        spawn(async move {
+            let (_stream, _) = listener.accept().await.expect("accept");
+            fluvio_future::timer::sleep(Duration::from_secs(5)).await;
+        });
+        let endpoint: HttpEndpoint = format!("http://{addr}/allow").parse().expect("endpoint");
+        let backend = WebhookBackend::new(endpoint, Duration::from_millis(100));
+        assert!(backend.decide(&request()).await.is_err());
+    }
+}
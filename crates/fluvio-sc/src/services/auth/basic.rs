@@ -1,22 +1,51 @@
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::SystemTime;
 
-use tracing::instrument;
+use tracing::{info, instrument, warn};
 use async_trait::async_trait;
-pub use policy::BasicRbacPolicy;
+pub use policy::{Action, BasicRbacPolicy};
 
 use fluvio_auth::{AuthContext, Authorization, TypeAction, InstanceAction, AuthError};
 use fluvio_controlplane_metadata::extended::ObjectType;
 use fluvio_auth::x509::X509Identity;
 
+use crate::secrets::{SecretRef, SharedSecretResolver};
+
+use super::backend::{PolicyBackend, SharedPolicyBackend};
+use super::cache::{AuthDecisionCache, DecisionKey};
+
+/// Authorization of x509 identities, decisions are made by policy backend
 #[derive(Debug, Clone)]
 pub struct BasicAuthorization {
-    policy: Arc<BasicRbacPolicy>,
+    backend: SharedPolicyBackend,
+    cache: Arc<AuthDecisionCache>,
 }
 
 impl BasicAuthorization {
     pub fn new(policy: BasicRbacPolicy) -> Self {
+        Self::with_backend(Arc::new(policy))
+    }
+
+    pub fn with_backend(backend: SharedPolicyBackend) -> Self {
         Self {
-            policy: Arc::new(policy),
+            backend,
+            cache: Arc::new(AuthDecisionCache::default()),
+        }
+    }
+
+    /// decision cache shared by contexts, for metrics
+    pub fn cache(&self) -> Arc<AuthDecisionCache> {
+        self.cache.clone()
+    }
+
+    /// context of authenticated identity
+    pub fn context(&self, identity: X509Identity) -> BasicAuthContext {
+        BasicAuthContext {
+            identity,
+            backend: self.backend.clone(),
+            cache: self.cache.clone(),
+            expires_at: None,
         }
     }
 }
@@ -36,17 +65,37 @@ impl Authorization for BasicAuthorization {
                 tracing::error!(%err, "failed to create x509 identity");
                 err
             })?;
-        Ok(BasicAuthContext {
-            identity,
-            policy: self.policy.clone(),
-        })
+
+        Ok(self.context(identity))
     }
 }
 
 #[derive(Debug)]
 pub struct BasicAuthContext {
     identity: X509Identity,
-    policy: Arc<BasicRbacPolicy>,
+    backend: SharedPolicyBackend,
+    cache: Arc<AuthDecisionCache>,
+    expires_at: Option<SystemTime>,
+}
+
+impl BasicAuthContext {
+    /// context of identity which expires, such as identity of token
+    pub fn expiring(mut self, at: SystemTime) -> Self {
+        self.expires_at = Some(at);
+        self
+    }
+
+    async fn decide(&self, key: DecisionKey) -> Result<bool, AuthError> {
+        self.cache
+            .sync_policy_version(self.backend.policy_version());
+        if let Some(allowed) = self.cache.get(&key) {
+            return Ok(allowed);
+        }
+
+        let allowed = self.backend.decide(&key).await?;
+        self.cache.insert(key, allowed);
+        Ok(allowed)
+    }
 }
 
 #[async_trait]
@@ -56,19 +105,138 @@ impl AuthContext for BasicAuthContext {
         ty: ObjectType,
         action: TypeAction,
     ) -> Result<bool, AuthError> {
-        self.policy
-            .evaluate(action.into(), ty, None, &self.identity)
-            .await
+        self.decide(DecisionKey {
+            principal: self.identity.principal.clone(),
+            scopes: self.identity.scopes().clone(),
+            action: action.into(),
+            object_type: ty,
+            instance: None,
+        })
+        .await
     }
 
     /// check if specific instance of spec can be deleted
     async fn allow_instance_action(
         &self,
-        _ty: ObjectType,
-        _action: InstanceAction,
-        _key: &str,
+        ty: ObjectType,
+        action: InstanceAction,
+        key: &str,
     ) -> Result<bool, AuthError> {
-        Ok(true)
+        self.decide(DecisionKey {
+            principal: self.identity.principal.clone(),
+            scopes: self.identity.scopes().clone(),
+            action: action.into(),
+            object_type: ty,
+            instance: Some(key.to_owned()),
+        })
+        .await
+    }
+
+    fn principal(&self) -> Option<&str> {
+        Some(&self.identity.principal)
+    }
+
+    fn client_addr(&self) -> Option<&str> {
+        self.identity.client_addr.as_deref()
+    }
+
+    fn expires_at(&self) -> Option<SystemTime> {
+        self.expires_at
+    }
+}
+
+#[async_trait]
+impl PolicyBackend for BasicRbacPolicy {
+    fn kind(&self) -> &'static str {
+        "basic"
+    }
+
+    async fn decide(&self, request: &DecisionKey) -> Result<bool, AuthError> {
+        // basic policy authorizes by object type, instance actions are allowed
+        if request.instance.is_some() {
+            return Ok(true);
+        }
+        let identity = X509Identity::new(request.principal.clone(), request.scopes.clone());
+        self.evaluate(
+            request.action.clone(),
+            request.object_type.clone(),
+            None,
+            &identity,
+        )
+        .await
+    }
+}
+
+/// Basic policy read from secret, policy is replaced when refreshed secret changes.
+/// Cached decisions of previous policy expire with their ttl
+#[derive(Debug)]
+pub struct SecretRbacPolicy {
+    secret: SecretRef,
+    resolver: SharedSecretResolver,
+    /// policy and secret value it was parsed from
+    current: RwLock<(Arc<[u8]>, Arc<BasicRbacPolicy>)>,
+    /// incremented on each reload
+    version: AtomicU64,
+}
+
+impl SecretRbacPolicy {
+    pub async fn load(secret: SecretRef, resolver: SharedSecretResolver) -> anyhow::Result<Self> {
+        let value = resolver.resolve(&secret).await?;
+        let policy: BasicRbacPolicy = serde_json::from_slice(&value)
+            .map_err(|err| anyhow::anyhow!("invalid policy {secret}: {err}"))?;
+        Ok(Self {
+            secret,
+            resolver,
+            current: RwLock::new((value, Arc::new(policy))),
+            version: AtomicU64::new(0),
+        })
+    }
+
+    /// current policy, parsed again if secret changed
+    fn policy(&self) -> Arc<BasicRbacPolicy> {
+        let Some(latest) = self.resolver.cached(&self.secret) else {
+            return self.current.read().expect("policy lock").1.clone();
+        };
+        {
+            let current = self.current.read().expect("policy lock");
+            if Arc::ptr_eq(&current.0, &latest) {
+                return current.1.clone();
+            }
+        }
+
+        let mut current = self.current.write().expect("policy lock");
+        if !Arc::ptr_eq(&current.0, &latest) {
+            match serde_json::from_slice::<BasicRbacPolicy>(&latest) {
+                Ok(policy) => {
+                    info!(secret = %self.secret, "authorization policy reloaded");
+                    current.1 = Arc::new(policy);
+                    self.version.fetch_add(1, Ordering::AcqRel);
+                }
+                Err(err) => {
+                    warn!(secret = %self.secret, %err, "invalid policy, keeping previous policy")
+                }
+            }
+            current.0 = latest;
+        }
+        current.1.clone()
+    }
+}
+
+#[async_trait]
+impl PolicyBackend for SecretRbacPolicy {
+    fn kind(&self) -> &'static str {
+        "basic"
+    }
+
+    async fn decide(&self, request: &DecisionKey) -> Result<bool, AuthError> {
+        let policy = self.policy();
+        policy.decide(request).await
+    }
+
+    fn policy_version(&self) -> u64 {
+        // reloads policy if secret changed
+        self.policy();
+        self.version.load(Ordering::Acquire)
     }
 }
 
@@ -282,6 +450,8 @@ mod test {
 
     use super::policy::*;
     use super::ObjectType;
+    use super::super::backend::PolicyBackend;
+    use super::super::cache::DecisionKey;
 
     #[test]
     fn test_action_urn_serialization() {
@@ -410,4 +580,85 @@ mod test {
                 .expect("eval")
         );
     }
+
+    #[fluvio_future::test]
+    async fn test_policy_backend() {
+        let policy = BasicRbacPolicy::default();
+        let request = |principal: &str, scope: &str, instance: Option<&str>| DecisionKey {
+            principal: principal.to_owned(),
+            scopes: vec![scope.to_owned()],
+            action: Action::Create,
+            object_type: ObjectType::Topic,
+            instance: instance.map(str::to_owned),
+        };
+
+        assert!(
+            policy
+                .decide(&request("admin", "Root", None))
+                .await
+                .expect("decide")
+        );
+        assert!(
+            !policy
+                .decide(&request("user", "Default", None))
+                .await
+                .expect("decide")
+        );
+        // instance actions are not enforced by basic policy
+        assert!(
+            policy
+                .decide(&request("user", "Default", Some("orders")))
+                .await
+                .expect("decide")
+        );
+    }
+
+    #[fluvio_future::test]
+    async fn test_secret_policy_reload() {
+        use std::sync::Arc;
+
+        use crate::secrets::{DEFAULT_SECRET_REFRESH, SecretRef, SecretResolver};
+        use super::SecretRbacPolicy;
+
+        let dir = std::env::temp_dir().join("fluvio_sc_secret_policy");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).expect("create");
+        let path = dir.join("policy.json");
+        let write_policy = |action: Action| {
+            let mut policy = BasicRbacPolicy::default();
+            let mut role = HashMap::new();
+            role.insert(ObjectType::Topic, vec![ActionUrn::new(action, None)]);
+            policy.0.insert(String::from("Default"), role);
+            std::fs::write(&path, serde_json::to_vec(&policy).expect("json")).expect("write");
+        };
+        let request = DecisionKey {
+            principal: "user".to_owned(),
+            scopes: vec!["Default".to_owned()],
+            action: Action::Create,
+            object_type: ObjectType::Topic,
+            instance: None,
+        };
+
+        write_policy(Action::Read);
+        let resolver = Arc::new(SecretResolver::new(
+            "default",
+            DEFAULT_SECRET_REFRESH,
+            dir.join("secrets"),
+        ));
+        let policy = SecretRbacPolicy::load(SecretRef::File(path.clone()), resolver.clone())
+            .await
+            .expect("load");
+        assert!(!policy.decide(&request).await.expect("decide"));
+
+        write_policy(Action::Create);
+        resolver.refresh().await;
+        assert!(policy.decide(&request).await.expect("decide"));
+
+        // invalid policy keeps previous policy
+        std::fs::write(&path, b"not json").expect("write");
+        resolver.refresh().await;
+        assert!(policy.decide(&request).await.expect("decide"));
+
+        std::fs::remove_dir_all(&dir).expect("remove");
+    }
 }
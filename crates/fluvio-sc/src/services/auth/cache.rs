@@ -0,0 +1,247 @@
+//!
+//! # Authorization decision cache
+//!
+//! Policy evaluation is done for every admin and consumer request.
+//! Decisions are cached per (principal, action, object) so high-rate APIs
+//! don't pay for evaluation on each call.
+//!
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tracing::{debug, trace};
+
+use fluvio_controlplane_metadata::extended::ObjectType;
+
+use super::basic::Action;
+
+const DEFAULT_CAPACITY: usize = 10_000;
+const DEFAULT_TTL: Duration = Duration::from_secs(60);
+
+/// Authorization request, also sent to external policy backends
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct DecisionKey {
+    pub principal: String,
+    pub scopes: Vec<String>,
+    pub action: Action,
+    pub object_type: ObjectType,
+    pub instance: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Decision {
+    allowed: bool,
+    created: Instant,
+    generation: u64,
+}
+
+/// Bounded cache of authorization decisions.
+///
+/// Entries expire after ttl, and all entries are dropped by [`AuthDecisionCache::invalidate`]
+/// when the policy changes.
+#[derive(Debug)]
+pub struct AuthDecisionCache {
+    capacity: usize,
+    ttl: Duration,
+    generation: AtomicU64,
+    policy_version: AtomicU64,
+    decisions: Mutex<HashMap<DecisionKey, Decision>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl Default for AuthDecisionCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY, DEFAULT_TTL)
+    }
+}
+
+impl AuthDecisionCache {
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            capacity,
+            ttl,
+            generation: AtomicU64::new(0),
+            policy_version: AtomicU64::new(0),
+            decisions: Mutex::new(HashMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// returns cached decision, counts hit or miss
+    pub fn get(&self, key: &DecisionKey) -> Option<bool> {
+        let generation = self.generation.load(Ordering::Acquire);
+        let decision = self.decisions.lock().ok().and_then(|decisions| {
+            decisions
+                .get(key)
+                .filter(|decision| self.is_valid(decision, generation))
+                .map(|decision| decision.allowed)
+        });
+
+        if decision.is_some() {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+        }
+        trace!(?key, ?decision, "auth cache lookup");
+        decision
+    }
+
+    pub fn insert(&self, key: DecisionKey, allowed: bool) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let generation = self.generation.load(Ordering::Acquire);
+        let Ok(mut decisions) = self.decisions.lock() else {
+            return;
+        };
+
+        if decisions.len() >= self.capacity && !decisions.contains_key(&key) {
+            decisions.retain(|_, decision| self.is_valid(decision, generation));
+            if decisions.len() >= self.capacity
+                && let Some(oldest) = decisions
+                    .iter()
+                    .min_by_key(|(_, decision)| decision.created)
+                    .map(|(key, _)| key.clone())
+            {
+                decisions.remove(&oldest);
+            }
+        }
+
+        decisions.insert(
+            key,
+            Decision {
+                allowed,
+                created: Instant::now(),
+                generation,
+            },
+        );
+    }
+
+    /// invalidate cache if policy was reloaded since last call
+    pub fn sync_policy_version(&self, version: u64) {
+        if self.policy_version.swap(version, Ordering::AcqRel) != version {
+            debug!(version, "policy changed, dropping cached decisions");
+            self.invalidate();
+        }
+    }
+
+    /// drop all cached decisions, must be called when policy changes
+    pub fn invalidate(&self) {
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        if let Ok(mut decisions) = self.decisions.lock() {
+            decisions.clear();
+        }
+    }
+
+    pub fn metrics(&self) -> AuthCacheMetrics {
+        AuthCacheMetrics {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self
+                .decisions
+                .lock()
+                .map(|decisions| decisions.len())
+                .unwrap_or_default(),
+        }
+    }
+
+    fn is_valid(&self, decision: &Decision, generation: u64) -> bool {
+        decision.generation == generation && decision.created.elapsed() < self.ttl
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize)]
+pub struct AuthCacheMetrics {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+}
+
+impl AuthCacheMetrics {
+    /// ratio of lookups served from cache
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    fn key(principal: &str, action: Action) -> DecisionKey {
+        DecisionKey {
+            principal: principal.to_owned(),
+            scopes: vec!["Default".to_owned()],
+            action,
+            object_type: ObjectType::Topic,
+            instance: None,
+        }
+    }
+
+    #[test]
+    fn test_cache_hit_and_miss() {
+        let cache = AuthDecisionCache::default();
+        assert_eq!(cache.get(&key("alice", Action::Read)), None);
+
+        cache.insert(key("alice", Action::Read), true);
+        cache.insert(key("alice", Action::Create), false);
+        assert_eq!(cache.get(&key("alice", Action::Read)), Some(true));
+        assert_eq!(cache.get(&key("alice", Action::Create)), Some(false));
+        assert_eq!(cache.get(&key("bob", Action::Read)), None);
+
+        let metrics = cache.metrics();
+        assert_eq!(metrics.hits, 2);
+        assert_eq!(metrics.misses, 2);
+        assert_eq!(metrics.entries, 2);
+        assert_eq!(metrics.hit_rate(), 0.5);
+    }
+
+    #[test]
+    fn test_cache_invalidate() {
+        let cache = AuthDecisionCache::default();
+        cache.insert(key("alice", Action::Read), true);
+        cache.invalidate();
+        assert_eq!(cache.get(&key("alice", Action::Read)), None);
+        assert_eq!(cache.metrics().entries, 0);
+    }
+
+    #[test]
+    fn test_cache_policy_version() {
+        let cache = AuthDecisionCache::default();
+        cache.insert(key("alice", Action::Read), true);
+        cache.sync_policy_version(0);
+        assert_eq!(cache.get(&key("alice", Action::Read)), Some(true));
+
+        cache.sync_policy_version(1);
+        assert_eq!(cache.get(&key("alice", Action::Read)), None);
+    }
+
+    #[test]
+    fn test_cache_bounded() {
+        let cache = AuthDecisionCache::new(2, DEFAULT_TTL);
+        cache.insert(key("a", Action::Read), true);
+        cache.insert(key("b", Action::Read), true);
+        cache.insert(key("c", Action::Read), true);
+        assert_eq!(cache.metrics().entries, 2);
+        assert_eq!(cache.get(&key("c", Action::Read)), Some(true));
+    }
+
+    #[test]
+    fn test_cache_expired() {
+        let cache = AuthDecisionCache::new(10, Duration::ZERO);
+        cache.insert(key("alice", Action::Read), true);
+        assert_eq!(cache.get(&key("alice", Action::Read)), None);
+    }
+}
@@ -1,4 +1,7 @@
+pub mod backend;
 pub mod basic;
+pub mod cache;
+pub mod oidc;
 
 pub use common::*;
 
@@ -0,0 +1,498 @@
+//!
+//! # OIDC authentication
+//!
+//! Clients authenticate with bearer token of OIDC provider, sent as first request of connection.
+//! Tokens are JWTs signed by keys of issuer's JWKS, keys are cached and fetched again
+//! when they expire or token is signed by unknown key.
+//! Principal and scopes are read from claims, requests are then authorized by policy backend
+//! as requests of x509 identities are. Connection is closed when its token expires.
+//!
+
+use std::collections::BTreeMap;
+use std::io::{Error as IoError, ErrorKind};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use ring::signature::{self, RsaPublicKeyComponents, UnparsedPublicKey};
+use serde::Deserialize;
+use serde_json::Value;
+use tracing::{debug, info, instrument, warn};
+
+use fluvio_auth::{AuthError, Authorization};
+use fluvio_auth::x509::X509Identity;
+use fluvio_future::task::spawn_blocking;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::link::token::{TOKEN_AUTH_API_KEY, TokenAuthRequest, TokenAuthResponse};
+use fluvio_socket::FluvioSocket;
+
+use super::basic::{BasicAuthContext, BasicAuthorization};
+
+pub const DEFAULT_PRINCIPAL_CLAIM: &str = "sub";
+pub const DEFAULT_SCOPES_CLAIM: &str = "groups";
+pub const DEFAULT_JWKS_REFRESH: Duration = Duration::from_secs(3600);
+
+/// keys are not fetched for unknown key ids more often than this
+const MIN_JWKS_FETCH_INTERVAL: Duration = Duration::from_secs(30);
+/// allowed difference between clocks of SC and issuer
+const CLOCK_SKEW_SECS: i64 = 60;
+
+/// Validation of tokens of OIDC issuer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OidcConfig {
+    /// issuer URL, keys are discovered from `{issuer}/.well-known/openid-configuration`
+    pub issuer: String,
+    /// audience tokens must be issued for, not checked if not set
+    pub audience: Option<String>,
+    /// claim with name of principal
+    pub principal_claim: String,
+    /// claim with list of groups or roles, or space separated scopes
+    pub scopes_claim: String,
+    /// scopes of claim values, claim values are scopes if not set
+    pub scope_mapping: Option<BTreeMap<String, Vec<String>>>,
+    /// max age of cached keys
+    pub jwks_refresh: Duration,
+}
+
+impl OidcConfig {
+    pub fn new(issuer: impl Into<String>) -> Self {
+        Self {
+            issuer: issuer.into().trim_end_matches('/').to_owned(),
+            audience: None,
+            principal_claim: DEFAULT_PRINCIPAL_CLAIM.to_owned(),
+            scopes_claim: DEFAULT_SCOPES_CLAIM.to_owned(),
+            scope_mapping: None,
+            jwks_refresh: DEFAULT_JWKS_REFRESH,
+        }
+    }
+
+    /// scopes of claim values
+    fn scopes(&self, claim: Option<&Value>) -> Vec<String> {
+        let values: Vec<&str> = match claim {
+            Some(Value::String(scopes)) => scopes.split_whitespace().collect(),
+            Some(Value::Array(values)) => values.iter().filter_map(Value::as_str).collect(),
+            _ => vec![],
+        };
+        let mut scopes: Vec<String> = match &self.scope_mapping {
+            Some(mapping) => values
+                .iter()
+                .filter_map(|value| mapping.get(*value))
+                .flatten()
+                .cloned()
+                .collect(),
+            None => values.into_iter().map(str::to_owned).collect(),
+        };
+        scopes.sort();
+        scopes.dedup();
+        scopes
+    }
+}
+
+/// Public key of JWKS
+#[derive(Debug, Clone, Deserialize)]
+struct Jwk {
+    kty: String,
+    #[serde(default)]
+    kid: Option<String>,
+    /// RSA modulus and exponent
+    #[serde(default)]
+    n: Option<String>,
+    #[serde(default)]
+    e: Option<String>,
+    /// EC curve and coordinates
+    #[serde(default)]
+    crv: Option<String>,
+    #[serde(default)]
+    x: Option<String>,
+    #[serde(default)]
+    y: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Jwks {
+    keys: Vec<Jwk>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JwtHeader {
+    alg: String,
+    #[serde(default)]
+    kid: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct KeyCache {
+    keys: Vec<Jwk>,
+    fetched: Option<Instant>,
+}
+
+/// Validates tokens with cached keys of issuer
+#[derive(Debug)]
+pub struct OidcValidator {
+    config: OidcConfig,
+    cache: Mutex<KeyCache>,
+}
+
+impl OidcValidator {
+    pub fn new(config: OidcConfig) -> Self {
+        Self {
+            config,
+            cache: Mutex::new(KeyCache::default()),
+        }
+    }
+
+    /// identity of valid token, and time it expires
+    #[instrument(skip(self, token))]
+    pub async fn validate(&self, token: &str) -> Result<(X509Identity, SystemTime)> {
+        let mut parts = token.split('.');
+        let (Some(header), Some(payload), Some(signature), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(anyhow!("token is not a JWT"));
+        };
+        let jwt_header: JwtHeader = serde_json::from_slice(&decode(header)?)?;
+        let signature = decode(signature)?;
+        let signed = &token[..header.len() + 1 + payload.len()];
+
+        let key = self.key(jwt_header.kid.as_deref()).await?;
+        verify(&jwt_header.alg, &key, signed.as_bytes(), &signature)?;
+
+        let claims: Value = serde_json::from_slice(&decode(payload)?)?;
+        self.identity(&claims, now_secs())
+    }
+
+    /// check registered claims, identity from principal and scopes claims
+    fn identity(&self, claims: &Value, now: i64) -> Result<(X509Identity, SystemTime)> {
+        if claims["iss"].as_str().map(|iss| iss.trim_end_matches('/'))
+            != Some(self.config.issuer.as_str())
+        {
+            return Err(anyhow!("token is not issued by {}", self.config.issuer));
+        }
+        let expires = match claims["exp"].as_i64() {
+            Some(exp) if exp + CLOCK_SKEW_SECS > now => exp + CLOCK_SKEW_SECS,
+            Some(_) => return Err(anyhow!("token expired")),
+            None => return Err(anyhow!("token has no expiration")),
+        };
+        if claims["nbf"]
+            .as_i64()
+            .is_some_and(|nbf| nbf - CLOCK_SKEW_SECS > now)
+        {
+            return Err(anyhow!("token is not yet valid"));
+        }
+        if let Some(audience) = &self.config.audience {
+            let issued_for = match &claims["aud"] {
+                Value::String(aud) => aud == audience,
+                Value::Array(auds) => auds
+                    .iter()
+                    .any(|aud| aud.as_str() == Some(audience.as_str())),
+                _ => false,
+            };
+            if !issued_for {
+                return Err(anyhow!("token is not issued for {audience}"));
+            }
+        }
+
+        let principal = claims[&self.config.principal_claim]
+            .as_str()
+            .ok_or_else(|| anyhow!("token has no {} claim", self.config.principal_claim))?;
+        let scopes = self.config.scopes(claims.get(&self.config.scopes_claim));
+        Ok((
+            X509Identity::new(principal.to_owned(), scopes),
+            UNIX_EPOCH + Duration::from_secs(expires as u64),
+        ))
+    }
+
+    /// key with id, keys are fetched if expired or key is unknown
+    async fn key(&self, kid: Option<&str>) -> Result<Jwk> {
+        let (key, fetch) = {
+            let cache = self.cache.lock().expect("key cache lock");
+            let age = cache.fetched.map(|fetched| fetched.elapsed());
+            let key = find_key(&cache.keys, kid);
+            let fetch = match (&key, age) {
+                (_, None) => true,
+                (Some(_), Some(age)) => age > self.config.jwks_refresh,
+                (None, Some(age)) => age > MIN_JWKS_FETCH_INTERVAL,
+            };
+            (key, fetch)
+        };
+        if !fetch {
+            return key.ok_or_else(|| anyhow!("token is signed by unknown key"));
+        }
+
+        match self.fetch_keys().await {
+            Ok(keys) => {
+                let key = find_key(&keys, kid);
+                info!(
+                    issuer = self.config.issuer,
+                    keys = keys.len(),
+                    "JWKS fetched"
+                );
+                *self.cache.lock().expect("key cache lock") = KeyCache {
+                    keys,
+                    fetched: Some(Instant::now()),
+                };
+                key.ok_or_else(|| anyhow!("token is signed by unknown key"))
+            }
+            // cached key is used until issuer is reachable again
+            Err(err) => {
+                warn!(issuer = self.config.issuer, %err, "unable to fetch JWKS");
+                key.ok_or(err)
+            }
+        }
+    }
+
+    async fn fetch_keys(&self) -> Result<Vec<Jwk>> {
+        let discovery = get_json(format!(
+            "{}/.well-known/openid-configuration",
+            self.config.issuer
+        ))
+        .await?;
+        let jwks_uri = discovery["jwks_uri"]
+            .as_str()
+            .ok_or_else(|| anyhow!("issuer has no jwks_uri"))?;
+        let jwks: Jwks = serde_json::from_value(get_json(jwks_uri.to_owned()).await?)?;
+        Ok(jwks.keys)
+    }
+}
+
+/// key with id, only key is used for tokens without id
+fn find_key(keys: &[Jwk], kid: Option<&str>) -> Option<Jwk> {
+    match kid {
+        Some(kid) => keys.iter().find(|key| key.kid.as_deref() == Some(kid)),
+        None if keys.len() == 1 => keys.first(),
+        None => None,
+    }
+    .cloned()
+}
+
+fn verify(alg: &str, key: &Jwk, signed: &[u8], signature: &[u8]) -> Result<()> {
+    let field = |value: &Option<String>, name: &str| {
+        value
+            .as_deref()
+            .ok_or_else(|| anyhow!("key has no {name}"))
+            .and_then(decode)
+    };
+    let verified = match (alg, key.kty.as_str()) {
+        ("RS256" | "RS384" | "RS512", "RSA") => {
+            let params = match alg {
+                "RS256" => &signature::RSA_PKCS1_2048_8192_SHA256,
+                "RS384" => &signature::RSA_PKCS1_2048_8192_SHA384,
+                _ => &signature::RSA_PKCS1_2048_8192_SHA512,
+            };
+            RsaPublicKeyComponents {
+                n: field(&key.n, "n")?,
+                e: field(&key.e, "e")?,
+            }
+            .verify(params, signed, signature)
+        }
+        ("ES256" | "ES384", "EC") => {
+            let (params, crv) = match alg {
+                "ES256" => (&signature::ECDSA_P256_SHA256_FIXED, "P-256"),
+                _ => (&signature::ECDSA_P384_SHA384_FIXED, "P-384"),
+            };
+            if key.crv.as_deref() != Some(crv) {
+                return Err(anyhow!("{alg} token is signed by key of other curve"));
+            }
+            // uncompressed point
+            let mut point = vec![4];
+            point.extend(field(&key.x, "x")?);
+            point.extend(field(&key.y, "y")?);
+            UnparsedPublicKey::new(params, point).verify(signed, signature)
+        }
+        _ => return Err(anyhow!("unsupported token algorithm {alg}")),
+    };
+    verified.map_err(|_| anyhow!("invalid token signature"))
+}
+
+fn decode(part: &str) -> Result<Vec<u8>> {
+    URL_SAFE_NO_PAD
+        .decode(part.trim_end_matches('='))
+        .map_err(|err| anyhow!("invalid base64url: {err}"))
+}
+
+async fn get_json(url: String) -> Result<Value> {
+    let body = spawn_blocking(move || -> Result<String> {
+        Ok(ureq::get(&url)
+            .timeout(Duration::from_secs(10))
+            .call()
+            .map_err(|err| anyhow!("{url}: {err}"))?
+            .into_string()?)
+    })
+    .await?;
+    Ok(serde_json::from_str(&body)?)
+}
+
+fn now_secs() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs() as i64)
+}
+
+/// Authorization of OIDC identities, decisions are made by policy backend
+#[derive(Debug, Clone)]
+pub struct OidcAuthorization {
+    validator: Arc<OidcValidator>,
+    basic: BasicAuthorization,
+}
+
+impl OidcAuthorization {
+    pub fn new(validator: Arc<OidcValidator>, basic: BasicAuthorization) -> Self {
+        Self { validator, basic }
+    }
+}
+
+#[async_trait]
+impl Authorization for OidcAuthorization {
+    type Context = BasicAuthContext;
+
+    #[instrument(level = "trace", skip(self, socket))]
+    async fn create_auth_context(
+        &self,
+        socket: &mut FluvioSocket,
+    ) -> Result<Self::Context, AuthError> {
+        let closed = || IoError::new(ErrorKind::Interrupted, "connection closed");
+        let req_msg = socket
+            .get_mut_stream()
+            .next_request_item::<TokenAuthRequest>()
+            .await
+            .ok_or_else(closed)?
+            .map_err(|_| closed())?;
+
+        let identity = if req_msg.header.api_key() == TOKEN_AUTH_API_KEY {
+            self.validator.validate(&req_msg.request.token).await
+        } else {
+            Err(anyhow!("bearer token is required"))
+        };
+        let error_code = match &identity {
+            Ok((identity, _)) => {
+                debug!(principal = identity.principal, "token authenticated");
+                ErrorCode::None
+            }
+            Err(err) => {
+                warn!(%err, "token rejected");
+                ErrorCode::Other(format!("authentication failed: {err}"))
+            }
+        };
+        socket
+            .get_mut_sink()
+            .send_response(
+                &req_msg.new_response(TokenAuthResponse { error_code }),
+                req_msg.header.api_version(),
+            )
+            .await
+            .map_err(|_| closed())?;
+
+        let (identity, expires_at) =
+            identity.map_err(|err| IoError::new(ErrorKind::PermissionDenied, err.to_string()))?;
+        // session is closed when token expires, client reconnects with refreshed token
+        Ok(self.basic.context(identity).expiring(expires_at))
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use ring::rand::SystemRandom;
+    use ring::signature::{ECDSA_P256_SHA256_FIXED_SIGNING, EcdsaKeyPair, KeyPair};
+    use serde_json::json;
+
+    use super::*;
+
+    const ISSUER: &str = "https://sso.example.com/realms/fluvio";
+
+    /// validator with key of signer, and function signing claims
+    fn signer() -> (OidcValidator, impl Fn(&Value) -> String) {
+        let rng = SystemRandom::new();
+        let pkcs8 =
+            EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &rng).expect("key");
+        let key_pair =
+            EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, pkcs8.as_ref(), &rng)
+                .expect("key pair");
+        let point = key_pair.public_key().as_ref().to_vec();
+        let jwk = Jwk {
+            kty: "EC".to_owned(),
+            kid: Some("key-1".to_owned()),
+            n: None,
+            e: None,
+            crv: Some("P-256".to_owned()),
+            x: Some(URL_SAFE_NO_PAD.encode(&point[1..33])),
+            y: Some(URL_SAFE_NO_PAD.encode(&point[33..])),
+        };
+
+        let mut config = OidcConfig::new(format!("{ISSUER}/"));
+        config.audience = Some("fluvio".to_owned());
+        config.scope_mapping = Some(BTreeMap::from([(
+            "platform-admins".to_owned(),
+            vec!["admin".to_owned()],
+        )]));
+        let validator = OidcValidator::new(config);
+        *validator.cache.lock().expect("lock") = KeyCache {
+            keys: vec![jwk],
+            fetched: Some(Instant::now()),
+        };
+
+        let sign = move |claims: &Value| {
+            let header = URL_SAFE_NO_PAD.encode(r#"{"alg":"ES256","kid":"key-1"}"#);
+            let payload = URL_SAFE_NO_PAD.encode(claims.to_string());
+            let signed = format!("{header}.{payload}");
+            let signature = key_pair
+                .sign(&SystemRandom::new(), signed.as_bytes())
+                .expect("sign");
+            format!("{signed}.{}", URL_SAFE_NO_PAD.encode(signature.as_ref()))
+        };
+        (validator, sign)
+    }
+
+    #[fluvio_future::test]
+    async fn test_validate_token() {
+        let (validator, sign) = signer();
+        let claims = json!({
+            "iss": ISSUER,
+            "aud": ["fluvio", "other"],
+            "sub": "alice",
+            "exp": now_secs() + 300,
+            "groups": ["platform-admins", "developers"],
+        });
+
+        let (identity, expires_at) = validator.validate(&sign(&claims)).await.expect("valid");
+        assert_eq!(identity.principal, "alice");
+        assert_eq!(identity.scopes, vec!["admin".to_owned()]);
+        let exp = claims["exp"].as_i64().expect("exp") + CLOCK_SKEW_SECS;
+        assert_eq!(expires_at, UNIX_EPOCH + Duration::from_secs(exp as u64));
+
+        // signature of other claims
+        let token = sign(&claims);
+        let forged = json!({ "sub": "mallory" }).to_string();
+        let mut parts = token.split('.').collect::<Vec<_>>();
+        let payload = URL_SAFE_NO_PAD.encode(forged);
+        parts[1] = &payload;
+        assert!(validator.validate(&parts.join(".")).await.is_err());
+
+        let mut expired = claims.clone();
+        expired["exp"] = json!(now_secs() - 3600);
+        assert!(validator.validate(&sign(&expired)).await.is_err());
+
+        let mut other_audience = claims.clone();
+        other_audience["aud"] = json!("billing");
+        assert!(validator.validate(&sign(&other_audience)).await.is_err());
+
+        assert!(validator.validate("not-a-token").await.is_err());
+    }
+
+    #[test]
+    fn test_scopes_of_claim() {
+        let config = OidcConfig::new(ISSUER);
+        assert_eq!(
+            config.scopes(Some(&json!("read write read"))),
+            vec!["read".to_owned(), "write".to_owned()]
+        );
+        assert_eq!(
+            config.scopes(Some(&json!(["b", "a", 1]))),
+            vec!["a".to_owned(), "b".to_owned()]
+        );
+        assert!(config.scopes(None).is_empty());
+    }
+}
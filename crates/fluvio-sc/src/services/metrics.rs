@@ -0,0 +1,126 @@
+//!
+//! # Prometheus metrics endpoint
+//!
+//! Serves controller metrics on `/metrics` in Prometheus text format.
+//! Metadata gauges are computed from stores on each scrape, counters and
+//! request latencies are recorded by controllers and public service.
+//!
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tracing::debug;
+
+use fluvio_service::health::{HealthServer, MetricsSource, Readiness};
+use fluvio_stream_model::core::MetadataItem;
+
+use crate::core::{SharedContext, write_metric};
+use crate::stores::partition::PartitionResolution;
+
+/// start metrics server if endpoint is configured.
+/// Probes are served on the same endpoint with readiness of SC.
+pub fn start_metrics_server<C>(ctx: SharedContext<C>, readiness: Arc<Readiness>)
+where
+    C: MetadataItem + 'static,
+    C::UId: Send + Sync,
+{
+    let Some(addr) = ctx.config().metrics_endpoint.clone() else {
+        debug!("metrics endpoint not configured, metrics disabled");
+        return;
+    };
+
+    HealthServer::new(addr, readiness)
+        .with_metrics(Arc::new(ScMetrics { ctx }))
+        .run();
+}
+
+struct ScMetrics<C: MetadataItem> {
+    ctx: SharedContext<C>,
+}
+
+#[async_trait]
+impl<C> MetricsSource for ScMetrics<C>
+where
+    C: MetadataItem + 'static,
+    C::UId: Send + Sync,
+{
+    async fn render(&self) -> String {
+        render(&self.ctx).await
+    }
+}
+
+/// metadata gauges followed by recorded metrics
+async fn render<C>(ctx: &SharedContext<C>) -> String
+where
+    C: MetadataItem + 'static,
+{
+    let mut out = String::new();
+
+    let topics = ctx.topics().store().count().await;
+    write_metric(
+        &mut out,
+        "fluvio_sc_topics",
+        "gauge",
+        "Number of topics",
+        &[("", topics as f64)],
+    );
+
+    let mut partitions: BTreeMap<&str, usize> = BTreeMap::new();
+    for partition in ctx.partitions().store().read().await.values() {
+        *partitions
+            .entry(resolution_label(&partition.inner().status.resolution))
+            .or_default() += 1;
+    }
+    let labels = partitions
+        .iter()
+        .map(|(resolution, count)| (format!("status=\"{resolution}\""), *count as f64))
+        .collect::<Vec<_>>();
+    write_metric(
+        &mut out,
+        "fluvio_sc_partitions",
+        "gauge",
+        "Number of partitions by status",
+        &labels
+            .iter()
+            .map(|(labels, count)| (labels.as_str(), *count))
+            .collect::<Vec<_>>(),
+    );
+
+    let spus = ctx
+        .spus()
+        .store()
+        .read()
+        .await
+        .values()
+        .map(|spu| {
+            let spu = spu.inner();
+            (
+                format!("spu=\"{}\"", spu.spec.id),
+                if spu.status.is_online() { 1.0 } else { 0.0 },
+            )
+        })
+        .collect::<Vec<_>>();
+    write_metric(
+        &mut out,
+        "fluvio_sc_spu_up",
+        "gauge",
+        "Whether SPU is online",
+        &spus
+            .iter()
+            .map(|(labels, up)| (labels.as_str(), *up))
+            .collect::<Vec<_>>(),
+    );
+
+    ctx.metrics().render(&mut out);
+    out
+}
+
+fn resolution_label(resolution: &PartitionResolution) -> &'static str {
+    match resolution {
+        PartitionResolution::Offline => "offline",
+        PartitionResolution::Online => "online",
+        PartitionResolution::LeaderOffline => "leader_offline",
+        PartitionResolution::ElectionLeaderFound => "election_leader_found",
+        PartitionResolution::OutOfStorage => "out_of_storage",
+    }
+}
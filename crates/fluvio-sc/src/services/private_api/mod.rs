@@ -22,6 +22,8 @@ where
     info!("starting internal services");
 
     let addr = ctx.config().private_endpoint.clone();
-    let server = FluvioApiServer::new(addr, ctx, ScInternalService::new());
+    let tuning = ctx.config().socket;
+    let server =
+        FluvioApiServer::new(addr, ctx, ScInternalService::new()).with_socket_tuning(tuning);
     server.run();
 }
@@ -10,17 +10,21 @@ use fluvio_controlplane::message::SpuMsg;
 use fluvio_controlplane::replica::Replica;
 use fluvio_controlplane::sc_api::api::InternalScKey;
 use fluvio_controlplane::sc_api::api::InternalScRequest;
-use fluvio_controlplane::sc_api::register_spu::RegisterSpuResponse;
+use fluvio_controlplane::sc_api::register_spu::{RegisterSpuResponse, SpuApiVersions};
 use fluvio_controlplane::sc_api::remove::ReplicaRemovedRequest;
+use fluvio_controlplane::sc_api::resync::{MetadataSyncKind, ResyncRequest};
 use fluvio_controlplane::sc_api::update_lrs::UpdateLrsRequest;
 use fluvio_controlplane::sc_api::update_mirror::UpdateMirrorStatRequest;
 use fluvio_controlplane::sc_api::update_partition::UpdatePartitionStatRequest;
+use fluvio_controlplane::spu_api::update_cluster_config::ClusterConfigMsg;
+use fluvio_controlplane::spu_api::update_cluster_config::UpdateClusterConfigRequest;
 use fluvio_controlplane::spu_api::update_mirror::MirrorMsg;
 use fluvio_controlplane::spu_api::update_mirror::UpdateMirrorRequest;
 use fluvio_controlplane::spu_api::update_replica::UpdateReplicaRequest;
 use fluvio_controlplane::spu_api::update_smartmodule::UpdateSmartModuleRequest;
 use fluvio_controlplane::spu_api::update_spu::UpdateSpuRequest;
 use fluvio_controlplane_metadata::message::Message;
+use fluvio_sc_schema::clusterconfig::ClusterConfigSpec;
 use fluvio_sc_schema::mirror::MirrorSpec;
 use fluvio_stream_model::core::MetadataItem;
 use fluvio_stream_model::store::ChangeListener;
@@ -77,9 +81,10 @@ where
         let mut api_stream = stream.api_stream::<InternalScRequest, InternalScKey>();
 
         // every SPU need to be validated and registered
-        let spu_id = wait_for_request!(api_stream,
+        let (spu_id, versions) = wait_for_request!(api_stream,
             InternalScRequest::RegisterSpuRequest(req_msg) => {
                 let spu_id = req_msg.request.spu();
+                let versions = SpuApiVersions::negotiate(req_msg.request.api_versions());
                 let mut status = true;
                 debug!(spu_id,"registration req");
 
@@ -98,17 +103,17 @@ where
                     return Ok(())
                 }
 
-                spu_id
+                (spu_id, versions)
             }
         );
 
-        info!(spu_id, "SPU connected");
+        info!(spu_id, ?versions, "SPU connected");
 
         let health_check = context.health().clone();
 
         health_check.update(spu_id, true).await;
 
-        if let Err(err) = dispatch_loop(context, spu_id, api_stream, sink).await {
+        if let Err(err) = dispatch_loop(context, spu_id, versions, api_stream, sink).await {
             error!("error with SPU <{}>, error: {}", spu_id, err);
         }
 
@@ -125,6 +130,7 @@ where
 async fn dispatch_loop<C>(
     context: SharedContext<C>,
     spu_id: SpuId,
+    versions: SpuApiVersions,
     mut api_stream: impl Stream<Item = Result<InternalScRequest, SocketError>> + Unpin,
     mut sink: FluvioSink,
 ) -> Result<(), SocketError>
@@ -135,6 +141,11 @@ where
     let mut partition_spec_listener = context.partitions().change_listener();
     let mut sm_spec_listener = context.smartmodules().change_listener();
     let mut mirror_spec_listener = context.mirrors().change_listener();
+    let mut cluster_config_listener = context.cluster_configs().change_listener();
+
+    // epoch of last update sent to SPU, incremental changes refer to it
+    let mut spu_sent_epoch: i64 = 0;
+    let mut replica_sent_epoch: i64 = 0;
 
     // send initial changes
 
@@ -144,10 +155,43 @@ where
         use tokio::select;
         use futures_util::stream::StreamExt;
 
-        send_spu_spec_changes(&mut spu_spec_listener, &mut sink, spu_id).await?;
-        send_smartmodule_changes(&mut sm_spec_listener, &mut sink, spu_id).await?;
-        send_replica_spec_changes(&mut partition_spec_listener, &mut sink, spu_id).await?;
-        send_mirror_changes(&mut mirror_spec_listener, &mut sink, spu_id).await?;
+        send_spu_spec_changes(
+            &mut spu_spec_listener,
+            &mut sink,
+            spu_id,
+            versions.update_spu,
+            &mut spu_sent_epoch,
+        )
+        .await?;
+        send_smartmodule_changes(
+            &mut sm_spec_listener,
+            &mut sink,
+            spu_id,
+            versions.update_smartmodule,
+        )
+        .await?;
+        send_replica_spec_changes(
+            &mut partition_spec_listener,
+            &mut sink,
+            spu_id,
+            versions.update_replica,
+            &mut replica_sent_epoch,
+        )
+        .await?;
+        send_mirror_changes(
+            &mut mirror_spec_listener,
+            &mut sink,
+            spu_id,
+            versions.update_mirror,
+        )
+        .await?;
+        send_cluster_config_changes(
+            &mut cluster_config_listener,
+            &mut sink,
+            spu_id,
+            versions.update_cluster_config,
+        )
+        .await?;
 
         trace!(spu_id, "waiting for SPU channel");
 
@@ -180,6 +224,14 @@ where
                             },
                             InternalScRequest::UpdatePartitionStatRequest(msg) => {
                                 receive_partition_status_update(&context, msg.request).await;
+                            },
+                            InternalScRequest::ResyncRequest(msg) => {
+                                let ResyncRequest { kind, last_epoch } = msg.request;
+                                info!(spu_id, %kind, last_epoch, "SPU requested metadata snapshot");
+                                match kind {
+                                    MetadataSyncKind::Spu => spu_spec_listener.reset_to_sync_all(),
+                                    MetadataSyncKind::Replica => partition_spec_listener.reset_to_sync_all(),
+                                }
                             }
                         }
                         // reset timer
@@ -211,6 +263,10 @@ where
                 debug!("mirror lister changed");
             }
 
+            _ = cluster_config_listener.listen() => {
+                debug!("cluster config lister changed");
+            }
+
         }
     }
 
@@ -385,6 +441,8 @@ async fn send_spu_spec_changes<C: MetadataItem>(
     listener: &mut ChangeListener<SpuSpec, C>,
     sink: &mut FluvioSink,
     spu_id: SpuId,
+    version: i16,
+    sent_epoch: &mut i64,
 ) -> Result<(), SocketError> {
     if !listener.has_change() {
         return Ok(());
@@ -410,19 +468,23 @@ async fn send_spu_spec_changes<C: MetadataItem>(
             .map(|d| Message::delete(d.spec))
             .collect();
         changes.append(&mut deletes);
-        UpdateSpuRequest::with_changes(epoch, changes)
+        UpdateSpuRequest::with_changes(epoch, changes).with_prev_epoch(*sent_epoch)
     };
 
     let mut message = RequestMessage::new_request(request);
     message.get_mut_header().set_client_id("sc");
+    message.get_mut_header().set_api_version(version);
 
     debug!(
         spu_id,
+        version,
         all = message.request.all.len(),
         changes = message.request.changes.len(),
+        prev_epoch = message.request.prev_epoch,
         "sending to spu",
     );
     sink.send_request(&message).await?;
+    *sent_epoch = epoch;
     Ok(())
 }
 
@@ -431,6 +493,8 @@ async fn send_replica_spec_changes<C: MetadataItem>(
     listener: &mut ChangeListener<PartitionSpec, C>,
     sink: &mut FluvioSink,
     spu_id: SpuId,
+    version: i16,
+    sent_epoch: &mut i64,
 ) -> Result<(), SocketError> {
     use crate::stores::ChangeFlag;
 
@@ -485,15 +549,17 @@ async fn send_replica_spec_changes<C: MetadataItem>(
             })
             .collect();
         changes.append(&mut deletes);
-        UpdateReplicaRequest::with_changes(epoch, changes)
+        UpdateReplicaRequest::with_changes(epoch, changes).with_prev_epoch(*sent_epoch)
     };
 
-    debug!(?request, "sending replica to spu");
+    debug!(?request, version, "sending replica to spu");
 
     let mut message = RequestMessage::new_request(request);
     message.get_mut_header().set_client_id("sc");
+    message.get_mut_header().set_api_version(version);
 
     sink.send_request(&message).await?;
+    *sent_epoch = epoch;
     Ok(())
 }
 
@@ -502,6 +568,7 @@ async fn send_smartmodule_changes<C: MetadataItem>(
     listener: &mut ChangeListener<SmartModuleSpec, C>,
     sink: &mut FluvioSink,
     spu_id: SpuId,
+    version: i16,
 ) -> Result<(), SocketError> {
     use crate::stores::ChangeFlag;
 
@@ -542,10 +609,11 @@ async fn send_smartmodule_changes<C: MetadataItem>(
         UpdateSmartModuleRequest::with_changes(epoch, changes)
     };
 
-    debug!(?request, "sending sm to spu");
+    debug!(?request, version, "sending sm to spu");
 
     let mut message = RequestMessage::new_request(request);
     message.get_mut_header().set_client_id("sc");
+    message.get_mut_header().set_api_version(version);
 
     sink.send_request(&message).await?;
     Ok(())
@@ -556,6 +624,7 @@ async fn send_mirror_changes<C: MetadataItem>(
     listener: &mut ChangeListener<MirrorSpec, C>,
     sink: &mut FluvioSink,
     spu_id: SpuId,
+    version: i16,
 ) -> Result<(), SocketError> {
     use crate::stores::ChangeFlag;
 
@@ -596,10 +665,75 @@ async fn send_mirror_changes<C: MetadataItem>(
         UpdateMirrorRequest::with_changes(epoch, changes)
     };
 
-    debug!(?request, "sending mirror to spu");
+    debug!(?request, version, "sending mirror to spu");
+
+    let mut message = RequestMessage::new_request(request);
+    message.get_mut_header().set_client_id("sc");
+    message.get_mut_header().set_api_version(version);
+
+    sink.send_request(&message).await?;
+    Ok(())
+}
+
+#[instrument(level = "trace", skip(sink))]
+/// changes are consumed even if SPU doesn't accept cluster config, so listener is not woken again
+async fn send_cluster_config_changes<C: MetadataItem>(
+    listener: &mut ChangeListener<ClusterConfigSpec, C>,
+    sink: &mut FluvioSink,
+    spu_id: SpuId,
+    version: Option<i16>,
+) -> Result<(), SocketError> {
+    use crate::stores::ChangeFlag;
+
+    if !listener.has_change() {
+        trace!("changes is empty, skipping");
+        return Ok(());
+    }
+
+    let changes = listener
+        .sync_changes_with_filter(&ChangeFlag {
+            spec: true,
+            status: false,
+            meta: false,
+        })
+        .await;
+    if changes.is_empty() {
+        trace!("spec changes is empty, skipping");
+        return Ok(());
+    }
+    let Some(version) = version else {
+        debug!("SPU doesn't accept cluster config, skipping");
+        return Ok(());
+    };
+
+    let epoch = changes.epoch;
+
+    let is_sync_all = changes.is_sync_all();
+    let (updates, deletes) = changes.parts();
+
+    let request = if is_sync_all {
+        UpdateClusterConfigRequest::with_all(
+            epoch,
+            updates.into_iter().map(|config| config.into()).collect(),
+        )
+    } else {
+        let mut changes: Vec<ClusterConfigMsg> = updates
+            .into_iter()
+            .map(|config| Message::update(config.into()))
+            .collect();
+        let mut deletes = deletes
+            .into_iter()
+            .map(|config| Message::delete(config.into()))
+            .collect();
+        changes.append(&mut deletes);
+        UpdateClusterConfigRequest::with_changes(epoch, changes)
+    };
+
+    debug!(?request, version, "sending cluster config to spu");
 
     let mut message = RequestMessage::new_request(request);
     message.get_mut_header().set_client_id("sc");
+    message.get_mut_header().set_api_version(version);
 
     sink.send_request(&message).await?;
     Ok(())
@@ -0,0 +1,59 @@
+//!
+//! # Liveness and readiness probes
+//!
+//! SC is ready once every metadata store has been loaded from the metadata backend,
+//! before that controllers and public API would work on partial metadata.
+//!
+use std::sync::Arc;
+use std::time::Duration;
+
+use tracing::{debug, info};
+
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+use fluvio_service::health::{HealthServer, Readiness};
+use fluvio_stream_model::core::MetadataItem;
+
+use crate::core::SharedContext;
+
+const METADATA_SYNC: &str = "metadata sync";
+
+const SYNC_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// start health server if endpoint is configured, returns readiness of SC
+pub fn start_probe_server<C>(ctx: SharedContext<C>) -> Arc<Readiness>
+where
+    C: MetadataItem + 'static,
+    C::UId: Send + Sync,
+{
+    let readiness = Readiness::shared(&[METADATA_SYNC]);
+    match ctx.config().health_endpoint.clone() {
+        Some(addr) => HealthServer::new(addr, readiness.clone()).run(),
+        None => debug!("health endpoint not configured, probes disabled"),
+    }
+
+    let sync_readiness = readiness.clone();
+    spawn(async move {
+        // stores start at epoch 0, first sync from metadata backend bumps it
+        while !metadata_synced(&ctx).await {
+            sleep(SYNC_POLL_INTERVAL).await;
+        }
+        info!("all metadata stores synced");
+        sync_readiness.set_ready(METADATA_SYNC);
+    });
+    readiness
+}
+
+/// cluster config is not waited for, clusters without it use default config
+async fn metadata_synced<C>(ctx: &SharedContext<C>) -> bool
+where
+    C: MetadataItem + 'static,
+{
+    ctx.spus().store().epoch().await > 0
+        && ctx.topics().store().epoch().await > 0
+        && ctx.partitions().store().epoch().await > 0
+        && ctx.spgs().store().epoch().await > 0
+        && ctx.tableformats().store().epoch().await > 0
+        && ctx.smartmodules().store().epoch().await > 0
+        && ctx.mirrors().store().epoch().await > 0
+}
@@ -6,12 +6,15 @@ use anyhow::Result;
 
 use fluvio_protocol::api::{RequestMessage, ResponseMessage, Request};
 use fluvio_protocol::link::versions::{
-    ApiVersionKey, ApiVersionsRequest, ApiVersionsResponse, PlatformVersion,
+    ApiVersionKey, ApiVersionsRequest, ApiVersionsResponse, Capabilities, PlatformVersion,
+    ServerCapabilities, ServerTime,
 };
 use fluvio_sc_schema::objects::{
     ObjectApiCreateRequest, ObjectApiDeleteRequest, ObjectApiListRequest, ObjectApiUpdateRequest,
     ObjectApiWatchRequest,
 };
+use fluvio_sc_schema::topic::TopicUsageRequest;
+use fluvio_sc_schema::ip_filter::IpFilterRequest;
 use fluvio_sc_schema::AdminPublicApiKey;
 
 // Fluvi Client version 0.14.0 corresponds to Platform version 10.0.0
@@ -24,6 +27,12 @@ pub async fn handle_api_versions_request(
 ) -> Result<ResponseMessage<ApiVersionsResponse>> {
     let mut response = ApiVersionsResponse {
         platform_version: PlatformVersion::new(&PLATFORM_VER),
+        server_time: ServerTime::now(),
+        // records and SmartModules are handled by SPUs
+        capabilities: ServerCapabilities::new(Capabilities {
+            mirroring: true,
+            ..Default::default()
+        }),
         ..Default::default()
     };
 
@@ -66,6 +75,18 @@ pub async fn handle_api_versions_request(
         ObjectApiUpdateRequest::MAX_API_VERSION,
     ));
 
+    response.api_keys.push(make_version_key(
+        AdminPublicApiKey::TopicUsage,
+        TopicUsageRequest::MIN_API_VERSION,
+        TopicUsageRequest::MAX_API_VERSION,
+    ));
+
+    response.api_keys.push(make_version_key(
+        AdminPublicApiKey::IpFilter,
+        IpFilterRequest::MIN_API_VERSION,
+        IpFilterRequest::MAX_API_VERSION,
+    ));
+
     trace!("flv api versions response: {:#?}", response);
 
     Ok(request.new_response(response))
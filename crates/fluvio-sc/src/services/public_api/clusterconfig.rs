@@ -0,0 +1,88 @@
+//!
+//! # Set Cluster Config Request
+//!
+//! Replaces cluster config, which is applied to topics created afterwards.
+//!
+
+use tracing::{info, trace, instrument};
+use anyhow::{anyhow, Result};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::Status;
+use fluvio_sc_schema::objects::CreateRequest;
+use fluvio_sc_schema::clusterconfig::{ClusterConfigSpec, CLUSTER_CONFIG_NAME};
+use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_stream_model::core::MetadataItem;
+use fluvio_auth::{AuthContext, TypeAction};
+
+use crate::services::auth::AuthServiceContext;
+
+/// Handler for cluster config request, existing config is replaced
+#[instrument(skip(req, auth_ctx))]
+pub async fn handle_set_cluster_config_request<AC: AuthContext, C: MetadataItem>(
+    req: CreateRequest<ClusterConfigSpec>,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status> {
+    let (create, spec) = req.parts();
+    let name = create.name;
+
+    info!(?spec, "setting cluster config");
+
+    if let Ok(authorized) = auth_ctx
+        .auth
+        .allow_type_action(ClusterConfigSpec::OBJECT_TYPE, TypeAction::Create)
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(Status::new(
+                name,
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+    } else {
+        return Err(anyhow!("authorization io error"));
+    }
+
+    if name != CLUSTER_CONFIG_NAME {
+        return Ok(Status::new(
+            name.clone(),
+            ErrorCode::Other(format!(
+                "cluster config must be named '{CLUSTER_CONFIG_NAME}'"
+            )),
+            Some(format!("invalid cluster config name: '{name}'")),
+        ));
+    }
+
+    if let Some(error) = spec.validate_config() {
+        return Ok(Status::new(
+            name,
+            ErrorCode::Other(error.clone()),
+            Some(error),
+        ));
+    }
+
+    if create.dry_run {
+        return Ok(Status::new_ok(name));
+    }
+
+    let status = match auth_ctx
+        .global_ctx
+        .cluster_configs()
+        .create_spec(name.clone(), spec)
+        .await
+    {
+        Ok(_) => {
+            info!("cluster config updated");
+            Status::new_ok(name)
+        }
+        Err(err) => Status::new(
+            name,
+            ErrorCode::Other(err.to_string()),
+            Some(err.to_string()),
+        ),
+    };
+
+    Ok(status)
+}
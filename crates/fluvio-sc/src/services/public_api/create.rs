@@ -1,4 +1,5 @@
 use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::clusterconfig::ClusterConfigSpec;
 use fluvio_sc_schema::mirror::MirrorSpec;
 use fluvio_stream_model::core::MetadataItem;
 use tracing::{instrument, debug, error};
@@ -17,7 +18,7 @@ use fluvio_auth::AuthContext;
 use crate::services::auth::AuthServiceContext;
 
 /// Handler for create topic request
-#[instrument(skip(request, auth_context))]
+#[instrument(skip(request, auth_context), fields(client_id = %request.header.client_id()))]
 pub async fn handle_create_request<AC: AuthContext, C: MetadataItem>(
     request: Box<RequestMessage<ObjectApiCreateRequest>>,
     auth_context: &AuthServiceContext<AC, C>,
@@ -38,6 +39,8 @@ pub async fn handle_create_request<AC: AuthContext, C: MetadataItem>(
         super::tableformat::handle_create_tableformat_request(create, auth_context).await?
     } else if let Some(create) = req.downcast()? as Option<CreateRequest<MirrorSpec>> {
         super::mirror::handle_register_mirror(create, auth_context).await?
+    } else if let Some(create) = req.downcast()? as Option<CreateRequest<ClusterConfigSpec>> {
+        super::clusterconfig::handle_set_cluster_config_request(create, auth_context).await?
     } else {
         error!("unknown create request: {:#?}", req);
         Status::new(
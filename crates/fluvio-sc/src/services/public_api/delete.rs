@@ -24,7 +24,7 @@ use fluvio_auth::AuthContext;
 use crate::services::auth::AuthServiceContext;
 
 /// Handler for delete topic request
-#[instrument(skip(request, auth_ctx))]
+#[instrument(skip(request, auth_ctx), fields(client_id = %request.header.client_id()))]
 pub async fn handle_delete_request<AC: AuthContext, C: MetadataItem>(
     request: RequestMessage<ObjectApiDeleteRequest>,
     auth_ctx: &AuthServiceContext<AC, C>,
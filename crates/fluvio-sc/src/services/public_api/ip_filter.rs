@@ -0,0 +1,76 @@
+//!
+//! # IP Filter Request
+//!
+//! Returns or replaces networks accepted by public service.
+//! Changes apply to new connections and are not persisted, startup options are used after restart.
+//!
+
+use anyhow::Result;
+use tracing::{debug, info, instrument};
+
+use fluvio_auth::{AuthContext, TypeAction};
+use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_controlplane_metadata::spu::SpuSpec;
+use fluvio_protocol::api::{RequestMessage, ResponseMessage};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::ip_filter::{IpFilterRequest, IpFilterResponse, IpFilterRules};
+use fluvio_service::ip_filter::{IpCidr, IpFilter};
+use fluvio_stream_model::core::MetadataItem;
+
+use crate::services::auth::AuthServiceContext;
+
+#[instrument(skip(request, auth_ctx))]
+pub async fn handle_ip_filter_request<AC: AuthContext, C: MetadataItem>(
+    request: RequestMessage<IpFilterRequest>,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<ResponseMessage<IpFilterResponse>> {
+    let (header, req) = request.get_header_request();
+    debug!(?req, "ip filter request");
+
+    // filter is part of cluster infrastructure, same as SPUs
+    let action = if req.update.is_some() {
+        TypeAction::Create
+    } else {
+        TypeAction::Read
+    };
+    let authorized = auth_ctx
+        .auth
+        .allow_type_action(SpuSpec::OBJECT_TYPE, action)
+        .await?;
+    let shared_filter = auth_ctx.global_ctx.ip_filter();
+
+    let error_code = if !authorized {
+        ErrorCode::PermissionDenied
+    } else if let Some(update) = req.update {
+        match IpFilter::new(&update.allow, &update.deny) {
+            Ok(filter) => {
+                info!(allow = ?update.allow, deny = ?update.deny, "ip filter updated");
+                shared_filter.set(filter);
+                ErrorCode::None
+            }
+            Err(err) => ErrorCode::Other(err.to_string()),
+        }
+    } else {
+        ErrorCode::None
+    };
+
+    let response = IpFilterResponse {
+        rules: if authorized {
+            filter_rules(&shared_filter.get())
+        } else {
+            IpFilterRules::default()
+        },
+        error_code,
+    };
+
+    Ok(ResponseMessage::from_header(&header, response))
+}
+
+fn filter_rules(filter: &IpFilter) -> IpFilterRules {
+    let to_strings =
+        |networks: &[IpCidr]| networks.iter().map(ToString::to_string).collect::<Vec<_>>();
+    IpFilterRules {
+        allow: to_strings(filter.allow()),
+        deny: to_strings(filter.deny()),
+    }
+}
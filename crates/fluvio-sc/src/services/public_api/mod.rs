@@ -14,6 +14,8 @@ mod tableformat;
 mod derivedstream;
 mod mirror;
 mod mirroring;
+mod ip_filter;
+mod clusterconfig;
 
 pub use server::start_public_server;
 
@@ -40,8 +42,17 @@ mod server {
         <A as Authorization>::Context: Send + Sync,
     {
         let addr = ctx.global_ctx.config().public_endpoint.clone();
+        let tuning = ctx.global_ctx.config().socket;
+        let limits = ctx.global_ctx.config().connection_limits;
+        let ip_filter = ctx.global_ctx.ip_filter().clone();
+        // TLS proxy passes client address only with authenticator of scopes
+        let proxied = ctx.global_ctx.config().x509_auth_scopes.is_some();
         debug!("starting public api service");
-        let server = FluvioApiServer::new(addr, ctx, PublicService::new());
+        let server = FluvioApiServer::new(addr, ctx, PublicService::new())
+            .with_socket_tuning(tuning)
+            .with_connection_limits(limits)
+            .with_ip_filter(ip_filter)
+            .with_tls_proxy(proxied);
         server.run();
     }
 }
@@ -10,15 +10,18 @@ use std::sync::Arc;
 use std::marker::PhantomData;
 use std::fmt::Debug;
 use std::io::Error as IoError;
+use std::time::SystemTime;
 
 use tracing::debug;
+use tracing::info;
 use tracing::instrument;
 use async_trait::async_trait;
 use anyhow::Result;
 
 use fluvio_service::ConnectInfo;
 use fluvio_types::event::StickyEvent;
-use fluvio_auth::Authorization;
+use fluvio_auth::{AuthContext, Authorization};
+use fluvio_service::limits::reject_connection;
 use fluvio_stream_model::core::MetadataItem;
 use fluvio_service::api_loop;
 use fluvio_service::call_service;
@@ -56,7 +59,7 @@ where
         self: Arc<Self>,
         ctx: Self::Context,
         mut socket: FluvioSocket,
-        _connection: ConnectInfo,
+        connection: ConnectInfo,
     ) -> Result<()> {
         let auth_context = ctx
             .auth
@@ -67,7 +70,18 @@ where
                 io_error
             })?;
 
+        // counted against limits until connection is closed
+        let permits =
+            match connection.acquire_client(auth_context.principal(), auth_context.client_addr()) {
+                Ok(permits) => permits,
+                Err(err) => {
+                    reject_connection(socket, err).await;
+                    return Ok(());
+                }
+            };
+
         debug!(?auth_context);
+        let expires_at = auth_context.expires_at();
         let service_context = Arc::new(AuthServiceContext::new(
             ctx.global_ctx.clone(),
             auth_context,
@@ -78,57 +92,102 @@ where
         let mut shared_sink = sink.as_shared();
 
         let end_event = StickyEvent::shared();
-
-        api_loop!(
-            api_stream,
-            "PublicAPI",
-
-            AdminPublicDecodedRequest::ApiVersionsRequest(request) => call_service!(
-                request,
-                super::api_version::handle_api_versions_request(request),
-                shared_sink,
-                "ApiVersionRequest"
-            ),
-
-            AdminPublicDecodedRequest::CreateRequest(request) => call_service!(
-                request,
-                super::create::handle_create_request(request, &service_context),
-                shared_sink,
-                "create  handler"
-            ),
-            AdminPublicDecodedRequest::UpdateRequest(request) => call_service!(
-                request,
-                super::update::handle_update_request(request, &service_context),
-                shared_sink,
-                "update handler"
-            ),
-            AdminPublicDecodedRequest::DeleteRequest(request) => call_service!(
-                request,
-                super::delete::handle_delete_request(request, &service_context),
-                shared_sink,
-                "delete  handler"
-            ),
-
-            AdminPublicDecodedRequest::ListRequest(request) => call_service!(
-                request,
-                super::list::handle_list_request(request, &service_context),
-                shared_sink,
-                "list handler"
-            ),
-            AdminPublicDecodedRequest::MirroringRequest(request) =>
-                super::mirroring::handle_mirroring_request(request, service_context.clone(), shared_sink.clone(), end_event.clone())?,
-            AdminPublicDecodedRequest::WatchRequest(request) =>
-                super::watch::handle_watch_request(
+        // latency of streaming requests is not recorded, they last as long as connection
+        let metrics = ctx.global_ctx.metrics().clone();
+        // how long client waits for responses, from api versions request
+        let mut timeout = None;
+
+        let session = async {
+            api_loop!(
+                api_stream,
+                "PublicAPI",
+
+                AdminPublicDecodedRequest::ApiVersionsRequest(request) => {
+                    timeout = request.request.request_timeout();
+                    call_service!(
+                        request,
+                        metrics.time("api_versions", super::api_version::handle_api_versions_request(request)),
+                        shared_sink,
+                        "ApiVersionRequest"
+                    )
+                },
+
+                AdminPublicDecodedRequest::CreateRequest(request) => call_service!(
+                    request,
+                    metrics.time("create", super::create::handle_create_request(request, &service_context)),
+                    shared_sink,
+                    "create  handler",
+                    timeout
+                ),
+                AdminPublicDecodedRequest::UpdateRequest(request) => call_service!(
+                    request,
+                    metrics.time("update", super::update::handle_update_request(request, &service_context)),
+                    shared_sink,
+                    "update handler",
+                    timeout
+                ),
+                AdminPublicDecodedRequest::DeleteRequest(request) => call_service!(
                     request,
-                    &service_context,
-                    shared_sink.clone(),
-                    end_event.clone(),
-                )?
+                    metrics.time("delete", super::delete::handle_delete_request(request, &service_context)),
+                    shared_sink,
+                    "delete  handler",
+                    timeout
+                ),
 
-        );
+                AdminPublicDecodedRequest::ListRequest(request) => call_service!(
+                    request,
+                    metrics.time("list", super::list::handle_list_request(request, &service_context)),
+                    shared_sink,
+                    "list handler",
+                    timeout
+                ),
+                AdminPublicDecodedRequest::TopicUsageRequest(request) => call_service!(
+                    request,
+                    metrics.time("topic_usage", super::topic::handle_topic_usage_request(request, &service_context)),
+                    shared_sink,
+                    "topic usage handler",
+                    timeout
+                ),
+                AdminPublicDecodedRequest::IpFilterRequest(request) => call_service!(
+                    request,
+                    metrics.time("ip_filter", super::ip_filter::handle_ip_filter_request(request, &service_context)),
+                    shared_sink,
+                    "ip filter handler",
+                    timeout
+                ),
+                AdminPublicDecodedRequest::MirroringRequest(request) =>
+                    super::mirroring::handle_mirroring_request(request, service_context.clone(), shared_sink.clone(), end_event.clone())?,
+                AdminPublicDecodedRequest::WatchRequest(request) =>
+                    super::watch::handle_watch_request(
+                        request,
+                        &service_context,
+                        shared_sink.clone(),
+                        end_event.clone(),
+                    )?
+
+            );
+            anyhow::Ok(())
+        };
+
+        // session of expiring identity is closed when identity expires
+        match expires_at {
+            Some(at) => {
+                let remaining = at.duration_since(SystemTime::now()).unwrap_or_default();
+                if let Ok(result) = fluvio_future::future::timeout(remaining, session).await {
+                    result?;
+                } else {
+                    info!(
+                        principal = ?service_context.auth.principal(),
+                        "authentication expired, closing connection"
+                    );
+                }
+            }
+            None => session.await?,
+        }
 
         // we are done with this tcp stream, notify any controllers use this strep
         end_event.notify();
+        drop(permits);
 
         Ok(())
     }
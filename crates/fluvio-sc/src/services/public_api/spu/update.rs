@@ -0,0 +1,67 @@
+//!
+//! # Update Spu Request
+//!
+//! Changes runtime config of an SPU. The new spec is propagated to the SPU,
+//! which applies the settings without restart.
+//!
+use std::io::{Error, ErrorKind};
+
+use tracing::{info, trace, instrument};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::Status;
+use fluvio_sc_schema::spu::{SpuSpec, UpdateSpuAction};
+use fluvio_auth::{AuthContext, InstanceAction};
+use fluvio_controlplane_metadata::extended::SpecExt;
+
+use crate::dispatcher::core::MetadataItem;
+use crate::services::auth::AuthServiceContext;
+
+/// Handler for update spu request
+#[instrument(skip(spu_name, action, auth_ctx))]
+pub async fn handle_spu_update_request<AC: AuthContext, C: MetadataItem>(
+    spu_name: String,
+    action: UpdateSpuAction,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status, Error> {
+    info!(%spu_name, ?action, "Updating spu");
+
+    if let Ok(authorized) = auth_ctx
+        .auth
+        .allow_instance_action(SpuSpec::OBJECT_TYPE, InstanceAction::Update, &spu_name)
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(Status::new(
+                spu_name,
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+    } else {
+        return Err(Error::new(ErrorKind::Interrupted, "authorization io error"));
+    }
+
+    let Some(spu) = auth_ctx.global_ctx.spus().store().value(&spu_name).await else {
+        return Ok(Status::new(
+            spu_name,
+            ErrorCode::SpuNotFound,
+            Some("not found".to_owned()),
+        ));
+    };
+
+    let mut spec = spu.spec().clone();
+    match action {
+        UpdateSpuAction::SetConfig(config) => spec.config.merge(config),
+        UpdateSpuAction::ResetConfig => spec.config = Default::default(),
+    }
+
+    auth_ctx
+        .global_ctx
+        .spus()
+        .create_spec(spu_name.clone(), spec)
+        .await?;
+
+    Ok(Status::new_ok(spu_name))
+}
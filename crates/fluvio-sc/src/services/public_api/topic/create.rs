@@ -15,7 +15,7 @@ use tracing::{info, debug, trace, instrument};
 use anyhow::{anyhow, Result};
 
 use fluvio_protocol::link::ErrorCode;
-use fluvio_controlplane_metadata::topic::ReplicaSpec;
+use fluvio_controlplane_metadata::topic::{Deduplication, ReplicaSpec};
 use fluvio_sc_schema::objects::CreateRequest;
 use fluvio_sc_schema::shared::validate_resource_name;
 use fluvio_sc_schema::Status;
@@ -60,8 +60,22 @@ pub(crate) async fn handle_create_topics_request<AC: AuthContext, C: MetadataIte
         return Err(anyhow!("authorization io error"));
     }
 
-    // validate topic request
-    let mut status = validate_topic_request::<C>(&name, &topic, &auth_ctx.global_ctx).await;
+    let cluster_config = auth_ctx.global_ctx.cluster_config().await;
+    if cluster_config.is_in_maintenance() {
+        debug!(topic = %name, "cluster is in maintenance");
+        return Ok(Status::new(
+            name,
+            ErrorCode::ClusterInMaintenance,
+            Some(String::from(
+                "cluster is in maintenance, topics can't be created",
+            )),
+        ));
+    }
+
+    // validate topic as provisioned, cluster config defaults are applied by topic controller
+    let mut provisioned = topic.clone();
+    cluster_config.apply_topic_defaults(&mut provisioned);
+    let mut status = validate_topic_request::<C>(&name, &provisioned, &auth_ctx.global_ctx).await;
     if status.is_error() {
         return Ok(status);
     }
@@ -75,6 +89,35 @@ pub(crate) async fn handle_create_topics_request<AC: AuthContext, C: MetadataIte
     Ok(status)
 }
 
+/// Validate SmartModule of deduplication is loaded, error status otherwise
+pub(crate) async fn validate_deduplication<C: MetadataItem>(
+    deduplication: &Deduplication,
+    metadata: &Context<C>,
+) -> Option<Status> {
+    let sm_name = deduplication.filter.transform.uses.as_str();
+    let sm_fqdn = match SmartModulePackageKey::from_qualified_name(sm_name) {
+        Ok(fqdn) => fqdn.store_id(),
+        Err(err) => {
+            return Some(Status::new(
+                sm_name.to_string(),
+                ErrorCode::DeduplicationSmartModuleNameInvalid(err.to_string()),
+                Some(err.to_string()),
+            ));
+        }
+    };
+    if !metadata.smartmodules().store().contains_key(&sm_fqdn).await {
+        return Some(Status::new(
+            sm_name.to_string(),
+            ErrorCode::DeduplicationSmartModuleNotLoaded,
+            Some(format!(
+                "{}\nHint: try `fluvio hub sm download {sm_name}` and repeat this operation",
+                ErrorCode::DeduplicationSmartModuleNotLoaded
+            )),
+        ));
+    }
+    None
+}
+
 /// Validate topic, takes advantage of the validation routines inside topic action workflow
 async fn validate_topic_request<C: MetadataItem>(
     name: &str,
@@ -113,25 +156,35 @@ async fn validate_topic_request<C: MetadataItem>(
     }
 
     // check if deduplication filter is present
-    if let Some(deduplication) = topic_spec.get_deduplication() {
-        let sm_name = deduplication.filter.transform.uses.as_str();
+    if let Some(deduplication) = topic_spec.get_deduplication()
+        && let Some(status) = validate_deduplication(deduplication, metadata).await
+    {
+        return status;
+    }
+
+    // check if default transforms are present
+    for transform in topic_spec.get_transforms() {
+        let sm_name = transform.uses.as_str();
         let sm_fqdn = match SmartModulePackageKey::from_qualified_name(sm_name) {
             Ok(fqdn) => fqdn.store_id(),
             Err(err) => {
                 return Status::new(
                     sm_name.to_string(),
-                    ErrorCode::DeduplicationSmartModuleNameInvalid(err.to_string()),
-                    Some(err.to_string()),
+                    ErrorCode::TopicInvalidConfiguration,
+                    Some(format!(
+                        "invalid transform SmartModule name {sm_name}: {err}"
+                    )),
                 );
             }
         };
         if !metadata.smartmodules().store().contains_key(&sm_fqdn).await {
             return Status::new(
                 sm_name.to_string(),
-                ErrorCode::DeduplicationSmartModuleNotLoaded,
+                ErrorCode::SmartModuleNotFound {
+                    name: sm_name.to_string(),
+                },
                 Some(format!(
-                    "{}\nHint: try `fluvio hub sm download {sm_name}` and repeat this operation",
-                    ErrorCode::DeduplicationSmartModuleNotLoaded
+                    "transform SmartModule {sm_name} not found\nHint: try `fluvio hub sm download {sm_name}` and repeat this operation"
                 )),
             );
         }
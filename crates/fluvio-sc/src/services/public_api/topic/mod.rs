@@ -1,8 +1,10 @@
 mod create;
 mod delete;
 mod fetch;
+mod usage;
 pub mod update;
 
 pub(crate) use create::*;
 pub(crate) use delete::*;
 pub(crate) use fetch::*;
+pub(crate) use usage::*;
@@ -0,0 +1,91 @@
+//!
+//! # Alter Config Request
+//!
+//! Changes mutable settings of existing topic, such as retention, compression and deduplication.
+//! Changed settings are propagated to partitions and applied by SPUs without restart.
+//!
+use std::io::Error;
+
+use tracing::{info, instrument};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::{topic::AlterTopicConfig, Status};
+use fluvio_stream_model::core::{MetadataItem, Spec};
+use fluvio_controlplane_metadata::topic::TopicSpec;
+use fluvio_auth::AuthContext;
+
+use crate::services::auth::AuthServiceContext;
+use crate::services::public_api::topic::validate_deduplication;
+
+/// Handler for alter topic config request
+#[instrument(skip(request, auth_ctx))]
+pub async fn handle_alter_config<AC: AuthContext, C: MetadataItem>(
+    topic_name: String,
+    request: AlterTopicConfig,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status, Error> {
+    let Some(topic) = auth_ctx
+        .global_ctx
+        .topics()
+        .store()
+        .value(&topic_name)
+        .await
+    else {
+        return Ok(Status::new(
+            topic_name,
+            ErrorCode::TopicNotFound,
+            Some("not found".to_owned()),
+        ));
+    };
+
+    let mut spec = topic.spec().clone();
+
+    if spec.is_system() {
+        return Ok(Status::new(
+            topic_name.clone(),
+            ErrorCode::SystemSpecUpdatingAttempt {
+                kind: TopicSpec::LABEL.to_lowercase(),
+                name: topic_name,
+            },
+            None,
+        ));
+    };
+
+    if request.is_empty() {
+        return Ok(Status::new(
+            topic_name,
+            ErrorCode::TopicInvalidConfiguration,
+            Some("no settings to change".to_owned()),
+        ));
+    }
+
+    request.apply(&mut spec);
+
+    if let Some(error) = spec.validate_config() {
+        return Ok(Status::new(
+            topic_name,
+            ErrorCode::TopicInvalidConfiguration,
+            Some(error),
+        ));
+    }
+
+    if let Some(deduplication) = request.deduplication.as_ref()
+        && !request.remove_deduplication
+        && let Some(status) = validate_deduplication(deduplication, &auth_ctx.global_ctx).await
+    {
+        return Ok(status);
+    }
+
+    if &spec == topic.spec() {
+        return Ok(Status::new_ok(topic_name));
+    }
+
+    info!(%topic_name, ?request, "altering topic config");
+    auth_ctx
+        .global_ctx
+        .topics()
+        .create_spec(topic.key.clone(), spec)
+        .await?;
+
+    Ok(Status::new_ok(topic_name))
+}
@@ -1,10 +1,12 @@
 mod add_partition;
 mod add_mirror;
+mod set_replication;
+mod alter_config;
 
 use std::io::{Error, ErrorKind};
 
 use anyhow::Result;
-use tracing::{info, instrument, trace};
+use tracing::{debug, info, instrument, trace};
 
 use fluvio_controlplane_metadata::extended::SpecExt;
 use fluvio_protocol::link::ErrorCode;
@@ -42,6 +44,23 @@ pub async fn handle_topic_update_request<AC: AuthContext, C: MetadataItem>(
         return Err(Error::new(ErrorKind::Interrupted, "authorization io error"));
     }
 
+    // same guard as topic creation, covers every update action including alter config
+    if auth_ctx
+        .global_ctx
+        .cluster_config()
+        .await
+        .is_in_maintenance()
+    {
+        debug!(%topic_name, "cluster is in maintenance");
+        return Ok(Status::new(
+            topic_name,
+            ErrorCode::ClusterInMaintenance,
+            Some(String::from(
+                "cluster is in maintenance, topics can't be updated",
+            )),
+        ));
+    }
+
     let status = match action {
         UpdateTopicAction::AddPartition(req) => {
             add_partition::handle_add_partition(topic_name, req, auth_ctx).await?
@@ -49,6 +68,12 @@ pub async fn handle_topic_update_request<AC: AuthContext, C: MetadataItem>(
         UpdateTopicAction::AddMirror(req) => {
             add_mirror::handle_add_mirror(topic_name, req, auth_ctx).await?
         }
+        UpdateTopicAction::SetReplication(req) => {
+            set_replication::handle_set_replication(topic_name, req, auth_ctx).await?
+        }
+        UpdateTopicAction::AlterConfig(req) => {
+            alter_config::handle_alter_config(topic_name, req, auth_ctx).await?
+        }
     };
 
     Ok(status)
@@ -0,0 +1,111 @@
+//!
+//! # Set Replication Request
+//!
+//! Changes replication factor of computed topic.
+//! Scheduler adds or removes followers of each partition, keeping its leader.
+//!
+use std::io::Error;
+
+use tracing::{info, instrument};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::{
+    topic::{ReplicaSpec, SetReplication},
+    Status,
+};
+use fluvio_stream_model::core::{MetadataItem, Spec};
+use fluvio_controlplane_metadata::topic::TopicSpec;
+use fluvio_auth::AuthContext;
+
+use crate::services::auth::AuthServiceContext;
+use crate::stores::spu::SpuLocalStorePolicy;
+
+/// Handler for set replication request
+#[instrument(skip(request, auth_ctx))]
+pub async fn handle_set_replication<AC: AuthContext, C: MetadataItem>(
+    topic_name: String,
+    request: SetReplication,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status, Error> {
+    let Some(topic) = auth_ctx
+        .global_ctx
+        .topics()
+        .store()
+        .value(&topic_name)
+        .await
+    else {
+        return Ok(Status::new(
+            topic_name,
+            ErrorCode::TopicNotFound,
+            Some("not found".to_owned()),
+        ));
+    };
+
+    let mut spec = topic.spec().clone();
+
+    if spec.is_system() {
+        return Ok(Status::new(
+            topic_name.clone(),
+            ErrorCode::SystemSpecUpdatingAttempt {
+                kind: TopicSpec::LABEL.to_lowercase(),
+                name: topic_name,
+            },
+            None,
+        ));
+    };
+
+    let ReplicaSpec::Computed(replica_param) = spec.replicas() else {
+        return Ok(Status::new(
+            topic_name,
+            ErrorCode::TopicInvalidReplicaType,
+            Some("replication factor can be changed for computed topics only".to_owned()),
+        ));
+    };
+
+    if let Err(err) = ReplicaSpec::valid_replication_factor(&request.factor) {
+        return Ok(Status::new(
+            topic_name,
+            ErrorCode::TopicInvalidConfiguration,
+            Some(err.to_string()),
+        ));
+    }
+
+    let spu_count = auth_ctx
+        .global_ctx
+        .spus()
+        .store()
+        .spu_used_for_replica()
+        .await;
+    if request.factor as usize > spu_count {
+        return Ok(Status::new(
+            topic_name,
+            ErrorCode::TopicInvalidConfiguration,
+            Some(format!(
+                "replication factor {} is greater than number of SPUs: {spu_count}",
+                request.factor
+            )),
+        ));
+    }
+
+    if replica_param.replication_factor == request.factor {
+        return Ok(Status::new_ok(topic_name));
+    }
+
+    info!(
+        %topic_name,
+        from = replica_param.replication_factor,
+        to = request.factor,
+        "changing replication factor"
+    );
+    let mut new_replica_param = replica_param.clone();
+    new_replica_param.replication_factor = request.factor;
+    spec.set_replicas(ReplicaSpec::Computed(new_replica_param));
+
+    auth_ctx
+        .global_ctx
+        .topics()
+        .create_spec(topic.key.clone(), spec)
+        .await?;
+
+    Ok(Status::new_ok(topic_name))
+}
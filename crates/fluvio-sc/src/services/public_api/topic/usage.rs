@@ -0,0 +1,59 @@
+//!
+//! # Topic Usage Request
+//!
+//! Returns size history of topic, recorded by topic usage sampler
+//!
+
+use anyhow::Result;
+use tracing::{debug, instrument};
+
+use fluvio_auth::{AuthContext, TypeAction};
+use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_protocol::api::{RequestMessage, ResponseMessage};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::topic::{TopicSpec, TopicUsageRequest, TopicUsageResponse};
+use fluvio_stream_model::core::MetadataItem;
+
+use crate::services::auth::AuthServiceContext;
+
+#[instrument(skip(request, auth_ctx))]
+pub async fn handle_topic_usage_request<AC: AuthContext, C: MetadataItem>(
+    request: RequestMessage<TopicUsageRequest>,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<ResponseMessage<TopicUsageResponse>> {
+    let (header, req) = request.get_header_request();
+    debug!(topic = %req.topic, "topic usage request");
+
+    let authorized = auth_ctx
+        .auth
+        .allow_type_action(TopicSpec::OBJECT_TYPE, TypeAction::Read)
+        .await?;
+    let response = if !authorized {
+        TopicUsageResponse {
+            error_code: ErrorCode::PermissionDenied,
+            ..Default::default()
+        }
+    } else if !auth_ctx
+        .global_ctx
+        .topics()
+        .store()
+        .contains_key(&req.topic)
+        .await
+    {
+        TopicUsageResponse {
+            error_code: ErrorCode::TopicNotFound,
+            ..Default::default()
+        }
+    } else {
+        TopicUsageResponse {
+            error_code: ErrorCode::None,
+            samples: auth_ctx
+                .global_ctx
+                .topic_usage()
+                .samples(&req.topic)
+                .unwrap_or_default(),
+        }
+    };
+
+    Ok(ResponseMessage::from_header(&header, response))
+}
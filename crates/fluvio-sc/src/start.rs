@@ -14,12 +14,18 @@ use k8_client::{K8Client, K8Config, memory::MemoryClient};
 
 use crate::{
     cli::{ScOpt, TlsConfig, RunMode},
-    services::auth::basic::BasicRbacPolicy,
+    services::auth::backend::SharedPolicyBackend,
     config::ScConfig,
     config::DEFAULT_NAMESPACE,
 };
 
 pub fn main_loop(opt: ScOpt) {
+    if opt.is_validate_config() {
+        let report = opt.validate();
+        println!("{report}");
+        std::process::exit(if report.is_valid() { 0 } else { 1 });
+    }
+
     // parse configuration (program exits on error)
     println!("CLI Option: {opt:#?}");
 
@@ -33,6 +39,13 @@ pub fn main_loop(opt: ScOpt) {
             let ((sc_config, auth_policy), tls_option) = opt.parse_cli_or_exit();
             local_main_loop(sc_config, client, auth_policy, tls_option)
         }
+        RunMode::Sqlite(db_path) => {
+            info!(?db_path, "Running in local mode with SQLite metadata");
+            let client = create_sqlite_metadata_store(db_path)
+                .expect("failed to open SQLite metadata store");
+            let ((sc_config, auth_policy), tls_option) = opt.parse_cli_or_exit();
+            local_main_loop(sc_config, client, auth_policy, tls_option)
+        }
         RunMode::ReadOnly(read_only_path) => {
             let read_only_path = read_only_path.to_path_buf();
             info!("Running in read only mode");
@@ -87,7 +100,7 @@ fn inspect_system() {
 fn k8_main_loop<C>(
     sc_config: ScConfig,
     client: SharedClient<C>,
-    auth_policy: Option<BasicRbacPolicy>,
+    auth_policy: Option<SharedPolicyBackend>,
     tls_option: Option<(String, TlsConfig)>,
 ) where
     C: MetadataClient<K8MetaItem> + 'static,
@@ -119,7 +132,7 @@ fn k8_main_loop<C>(
 fn local_main_loop<C, M>(
     sc_config: ScConfig,
     client: SharedClient<C>,
-    auth_policy: Option<BasicRbacPolicy>,
+    auth_policy: Option<SharedPolicyBackend>,
     tls_option: Option<(String, TlsConfig)>,
 ) where
     C: MetadataClient<M> + 'static,
@@ -142,7 +155,7 @@ fn local_main_loop<C, M>(
 
 mod proxy {
     use std::process;
-    use tracing::info;
+    use tracing::{error, info};
 
     use fluvio_types::print_cli_err;
     pub use fluvio_future::rust_tls::TlsAcceptor;
@@ -154,12 +167,41 @@ mod proxy {
 
     use crate::{config::ScConfig, cli::TlsConfig};
 
+    /// proxy is restarted when certificates or scopes from secrets change,
+    /// connections accepted before keep running
     pub async fn start_if(sc_config: ScConfig, tls_option: Option<(String, TlsConfig)>) {
-        if let Some((proxy_port, tls_config)) = tls_option {
-            let tls_acceptor = tls_config
-                .try_build_tls_acceptor()
-                .expect("can't build tls acceptor");
+        let Some((proxy_port, tls_config)) = tls_option else {
+            return;
+        };
+        let mut tls_acceptor = tls_config
+            .try_build_tls_acceptor()
+            .expect("can't build tls acceptor");
+        let Some(mut changes) = tls_config
+            .secrets
+            .as_ref()
+            .map(|secrets| secrets.file_changes())
+        else {
             start_proxy(sc_config, (tls_acceptor, proxy_port)).await;
+            return;
+        };
+
+        loop {
+            let mut proxy = Box::pin(start_proxy(
+                sc_config.clone(),
+                (tls_acceptor, proxy_port.clone()),
+            ));
+            tls_acceptor = loop {
+                tokio::select! {
+                    _ = &mut proxy => return,
+                    _ = changes.listen() => match tls_config.try_build_tls_acceptor() {
+                        Ok(acceptor) => {
+                            info!("TLS secrets changed, restarting TLS proxy");
+                            break acceptor;
+                        }
+                        Err(err) => error!("changed TLS secrets are invalid, keeping proxy: {err:#}"),
+                    },
+                }
+            };
         }
     }
 
@@ -207,3 +249,15 @@ fn create_k8_client(config: K8Config) -> Result<Arc<K8Client>> {
 fn create_local_metadata_store(path: &Path) -> Arc<LocalMetadataStorage> {
     Arc::new(LocalMetadataStorage::new(path))
 }
+
+#[cfg(feature = "sqlite")]
+fn create_sqlite_metadata_store(path: &Path) -> Result<Arc<LocalMetadataStorage>> {
+    Ok(Arc::new(LocalMetadataStorage::new_sqlite(path)?))
+}
+
+#[cfg(not(feature = "sqlite"))]
+fn create_sqlite_metadata_store(_path: &Path) -> Result<Arc<LocalMetadataStorage>> {
+    Err(anyhow::anyhow!(
+        "SQLite metadata store is not supported, SC is built without `sqlite` feature"
+    ))
+}
@@ -0,0 +1,5 @@
+pub use fluvio_controlplane_metadata::clusterconfig::*;
+
+use fluvio_stream_model::store::LocalStore;
+
+pub type ClusterConfigLocalStore<C> = LocalStore<ClusterConfigSpec, C>;
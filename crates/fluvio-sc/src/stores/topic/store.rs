@@ -1,6 +1,7 @@
 use fluvio_controlplane::PartitionMetadata;
 use fluvio_controlplane_metadata::partition::PartitionSpec;
 use fluvio_protocol::record::ReplicaKey;
+use fluvio_types::SpuId;
 use fluvio_stream_model::{
     store::{MetadataStoreObject, LocalStore},
     core::MetadataItem,
@@ -30,7 +31,9 @@ impl<C: MetadataItem> TopicMd<C> for TopicMetadata<C>
 where
     C: MetadataItem + Send + Sync,
 {
-    /// create new partitions from the replica map if it doesn't exists
+    /// create new partitions from the replica map if it doesn't exists.
+    /// Existing partitions are updated if their replicas differ from replica map,
+    /// which happens when replication factor is changed, or if topic config was altered
     async fn create_new_partitions(
         &self,
         partition_store: &PartitionLocalStore<C>,
@@ -51,8 +54,24 @@ where
                     MetadataStoreObject::with_spec(replica_key, partition_spec)
                         .with_context(self.ctx.create_child()),
                 )
-            } else {
-                debug!(?replica_key, "partition already exists");
+            } else if let Some(partition) = store.get(&replica_key) {
+                let mut updated = partition.inner().clone();
+                if let Some(new_replicas) = rescheduled_replicas(&partition.spec, replicas) {
+                    debug!(
+                        ?replica_key,
+                        old = ?partition.spec.replicas,
+                        new = ?new_replicas,
+                        "updating partition replicas"
+                    );
+                    updated.spec.replicas = new_replicas;
+                }
+                updated.spec.update_topic_config(&self.spec);
+                if updated.spec != partition.spec {
+                    debug!(?replica_key, "updating partition");
+                    partitions.push(updated);
+                } else {
+                    debug!(?replica_key, "partition already exists");
+                }
             }
         }
         drop(store);
@@ -60,6 +79,35 @@ where
     }
 }
 
+/// replicas of partition after applying scheduled replicas.
+/// Order of existing replicas is kept, and current leader is never removed since it may have been re-elected.
+/// Return None if there is no change
+fn rescheduled_replicas(spec: &PartitionSpec, scheduled: &[SpuId]) -> Option<Vec<SpuId>> {
+    let same_spus = spec.replicas.len() == scheduled.len()
+        && scheduled.iter().all(|spu| spec.replicas.contains(spu));
+    if scheduled.is_empty() || same_spus {
+        return None;
+    }
+
+    let mut replicas: Vec<SpuId> = spec
+        .replicas
+        .iter()
+        .filter(|spu| scheduled.contains(spu))
+        .chain(scheduled.iter().filter(|spu| !spec.replicas.contains(spu)))
+        .copied()
+        .collect();
+    if !replicas.contains(&spec.leader) {
+        replicas.insert(0, spec.leader);
+        replicas.truncate(scheduled.len());
+    }
+
+    if replicas == spec.replicas {
+        None
+    } else {
+        Some(replicas)
+    }
+}
+
 #[async_trait]
 pub trait TopicLocalStorePolicy<C>
 where
@@ -256,4 +304,91 @@ mod test {
         assert_eq!(partitions[0].key, ReplicaKey::new("topic-1", 1_u32));
         assert_eq!(partitions[0].spec.leader, 1);
     }
+
+    #[fluvio_future::test]
+    async fn test_partitions_with_changed_replication() {
+        let partition_stored = MetadataStoreObject::<PartitionSpec, u32>::new(
+            ReplicaKey::new("topic-1", 0_u32),
+            PartitionSpec::new(0, vec![0, 1]),
+            PartitionStatus::default(),
+        );
+        let status = TopicStatus::new(
+            TopicResolution::Provisioned,
+            vec![vec![0, 1, 2]],
+            "".to_owned(),
+        );
+        let topic =
+            MetadataStoreObject::<TopicSpec, u32>::new("topic-1", (1, 3, false).into(), status);
+        let partition_store = DefaultPartitionStore::bulk_new(vec![partition_stored]);
+
+        let partitions = topic.create_new_partitions(&partition_store).await;
+
+        assert_eq!(partitions.len(), 1);
+        assert_eq!(partitions[0].key, ReplicaKey::new("topic-1", 0_u32));
+        assert_eq!(partitions[0].spec.leader, 0);
+        assert_eq!(partitions[0].spec.replicas, vec![0, 1, 2]);
+    }
+
+    #[fluvio_future::test]
+    async fn test_partitions_with_altered_config() {
+        use fluvio_controlplane_metadata::topic::CompressionAlgorithm;
+
+        let partition_stored = MetadataStoreObject::<PartitionSpec, u32>::new(
+            ReplicaKey::new("topic-1", 0_u32),
+            PartitionSpec::new(0, vec![0, 1]),
+            PartitionStatus::default(),
+        );
+        let status = TopicStatus::new(
+            TopicResolution::Provisioned,
+            vec![vec![0, 1]],
+            "".to_owned(),
+        );
+        let mut spec: TopicSpec = (1, 2, false).into();
+        let mut topic = MetadataStoreObject::<TopicSpec, u32>::new("topic-1", spec.clone(), status);
+        let partition_store = DefaultPartitionStore::bulk_new(vec![partition_stored]);
+
+        // unchanged partition is not updated
+        assert!(
+            topic
+                .create_new_partitions(&partition_store)
+                .await
+                .is_empty()
+        );
+
+        spec.set_compression_type(CompressionAlgorithm::Lz4);
+        topic.set_spec(spec);
+        let partitions = topic.create_new_partitions(&partition_store).await;
+
+        assert_eq!(partitions.len(), 1);
+        assert_eq!(partitions[0].spec.replicas, vec![0, 1]);
+        assert_eq!(
+            partitions[0].spec.compression_type,
+            CompressionAlgorithm::Lz4
+        );
+    }
+
+    #[test]
+    fn test_rescheduled_replicas() {
+        use super::rescheduled_replicas;
+
+        // re-elected leader with same replicas is not changed
+        assert_eq!(
+            rescheduled_replicas(&PartitionSpec::new(1, vec![0, 1]), &[0, 1]),
+            None
+        );
+        // existing order is kept
+        assert_eq!(
+            rescheduled_replicas(&PartitionSpec::new(1, vec![0, 1]), &[0, 1, 2]),
+            Some(vec![0, 1, 2])
+        );
+        // leader is not removed
+        assert_eq!(
+            rescheduled_replicas(&PartitionSpec::new(1, vec![0, 1, 2]), &[0, 2]),
+            Some(vec![1, 0])
+        );
+        assert_eq!(
+            rescheduled_replicas(&PartitionSpec::new(1, vec![1, 0]), &[0, 2]),
+            None
+        );
+    }
 }
@@ -0,0 +1,166 @@
+//!
+//! # Configuration validation
+//!
+//! Checks configuration of SC without starting it, for validation of deployment configs in CI.
+//! Each check is reported, SC exits with error if any check fails.
+//!
+
+use std::fmt;
+use std::fs;
+use std::net::TcpListener;
+use std::path::Path;
+
+use anyhow::{Result, anyhow};
+
+/// Result of configuration checks
+#[derive(Debug, Default)]
+pub struct ConfigReport {
+    checks: Vec<Check>,
+}
+
+#[derive(Debug)]
+struct Check {
+    name: String,
+    result: Result<String, String>,
+}
+
+impl ConfigReport {
+    /// add result of a check, success contains description of what is validated
+    pub fn check(&mut self, name: impl Into<String>, result: Result<String>) {
+        self.checks.push(Check {
+            name: name.into(),
+            result: result.map_err(|err| format!("{err:#}")),
+        });
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.checks.iter().all(|check| check.result.is_ok())
+    }
+}
+
+impl fmt::Display for ConfigReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for check in &self.checks {
+            match &check.result {
+                Ok(detail) => writeln!(f, "OK     {}: {detail}", check.name)?,
+                Err(err) => writeln!(f, "ERROR  {}: {err}", check.name)?,
+            }
+        }
+        let failed = self
+            .checks
+            .iter()
+            .filter(|check| check.result.is_err())
+            .count();
+        if failed == 0 {
+            write!(f, "configuration is valid")
+        } else {
+            write!(f, "configuration is invalid, {failed} checks failed")
+        }
+    }
+}
+
+/// address can be bound by server
+pub fn check_bind(addr: &str) -> Result<String> {
+    let listener =
+        TcpListener::bind(addr).map_err(|err| anyhow!("{addr} is not available: {err}"))?;
+    drop(listener);
+    Ok(format!("{addr} is available"))
+}
+
+/// metadata directory is writable, or can be created
+pub fn check_metadata_dir(path: &Path) -> Result<String> {
+    if !path.exists() {
+        let parent = path
+            .ancestors()
+            .skip(1)
+            .find(|ancestor| ancestor.exists())
+            .ok_or_else(|| anyhow!("{} can't be created", path.display()))?;
+        if !parent.is_dir() {
+            return Err(anyhow!(
+                "{} can't be created, {} is not a directory",
+                path.display(),
+                parent.display()
+            ));
+        }
+        check_writable(parent)
+            .map_err(|err| anyhow!("{} can't be created: {err}", path.display()))?;
+        return Ok(format!("{} will be created", path.display()));
+    }
+    if !path.is_dir() {
+        return Err(anyhow!("{} is not a directory", path.display()));
+    }
+    check_writable(path)?;
+    Ok(format!("{} is writable", path.display()))
+}
+
+/// write probe file with unique name, so concurrent validations don't interfere
+fn check_writable(dir: &Path) -> Result<()> {
+    let probe = dir.join(format!(".validate-config-{}", std::process::id()));
+    fs::write(&probe, b"").map_err(|err| anyhow!("{} is not writable: {err}", dir.display()))?;
+    let _ = fs::remove_file(&probe);
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_config_report() {
+        let mut report = ConfigReport::default();
+        report.check("public address", Ok("0.0.0.0:9003 is available".to_owned()));
+        assert!(report.is_valid());
+
+        report.check("authorization policy", Err(anyhow!("invalid json")));
+        assert!(!report.is_valid());
+        assert_eq!(
+            report.to_string(),
+            "OK     public address: 0.0.0.0:9003 is available\n\
+             ERROR  authorization policy: invalid json\n\
+             configuration is invalid, 1 checks failed"
+        );
+    }
+
+    #[test]
+    fn test_check_bind() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+        let addr = listener.local_addr().expect("addr").to_string();
+        assert!(check_bind(&addr).is_err());
+        drop(listener);
+        assert!(check_bind(&addr).is_ok());
+    }
+
+    #[test]
+    fn test_check_metadata_dir() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let dir = temp.path().join("validate");
+        assert!(check_metadata_dir(&dir.join("metadata")).is_ok());
+
+        fs::create_dir_all(&dir).expect("create");
+        assert!(check_metadata_dir(&dir).is_ok());
+
+        let file = dir.join("file");
+        fs::write(&file, b"").expect("write");
+        assert!(check_metadata_dir(&file).is_err());
+        assert!(check_metadata_dir(&file.join("metadata")).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_check_metadata_dir_read_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = tempfile::tempdir().expect("temp dir");
+        let dir = temp.path().join("read-only");
+        fs::create_dir(&dir).expect("create");
+        fs::set_permissions(&dir, fs::Permissions::from_mode(0o555)).expect("permissions");
+
+        // root can write regardless of mode
+        let writable = fs::write(dir.join("probe"), b"").is_ok();
+        assert_eq!(check_metadata_dir(&dir).is_ok(), writable);
+        assert_eq!(check_metadata_dir(&dir.join("metadata")).is_ok(), writable);
+
+        fs::set_permissions(&dir, fs::Permissions::from_mode(0o755)).expect("permissions");
+    }
+}
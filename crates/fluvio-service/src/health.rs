@@ -0,0 +1,289 @@
+//!
+//! # HTTP health endpoints
+//!
+//! Minimal HTTP/1.1 server answering Kubernetes liveness and readiness probes.
+//! `/healthz` succeeds as long as the process serves requests,
+//! `/readyz` succeeds once all readiness conditions are met.
+//! `/metrics` serves Prometheus metrics if a metrics source is set.
+//!
+use std::collections::BTreeSet;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures_util::{AsyncReadExt, AsyncWriteExt, StreamExt};
+use tracing::{debug, error, info, instrument};
+
+use fluvio_future::future::timeout;
+use fluvio_future::net::{TcpListener, TcpStream};
+use fluvio_future::task::spawn;
+
+pub const LIVENESS_PATH: &str = "/healthz";
+pub const READINESS_PATH: &str = "/readyz";
+pub const METRICS_PATH: &str = "/metrics";
+
+/// max size of request head we read, probes send only a request line and few headers
+const MAX_REQUEST_BYTES: usize = 4096;
+
+/// time to receive request line, so idle connections are not kept open
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Metrics in Prometheus text format, rendered on each scrape
+#[async_trait]
+pub trait MetricsSource: Send + Sync {
+    async fn render(&self) -> String;
+}
+
+/// Conditions which must be met before server accepts traffic
+#[derive(Debug, Default)]
+pub struct Readiness {
+    pending: Mutex<BTreeSet<&'static str>>,
+}
+
+impl Readiness {
+    pub fn shared(conditions: &[&'static str]) -> Arc<Self> {
+        Arc::new(Self {
+            pending: Mutex::new(conditions.iter().copied().collect()),
+        })
+    }
+
+    /// mark condition as met, unknown conditions are ignored
+    pub fn set_ready(&self, condition: &str) {
+        let mut pending = self.pending.lock().expect("readiness lock poisoned");
+        if pending.remove(condition) {
+            info!(
+                condition,
+                remaining = pending.len(),
+                "readiness condition met"
+            );
+        }
+    }
+
+    /// conditions not met yet
+    pub fn pending(&self) -> Vec<&'static str> {
+        self.pending
+            .lock()
+            .expect("readiness lock poisoned")
+            .iter()
+            .copied()
+            .collect()
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.pending
+            .lock()
+            .expect("readiness lock poisoned")
+            .is_empty()
+    }
+}
+
+/// Serves liveness and readiness probes, and metrics if source is set
+pub struct HealthServer {
+    addr: String,
+    readiness: Arc<Readiness>,
+    metrics: Option<Arc<dyn MetricsSource>>,
+}
+
+impl fmt::Debug for HealthServer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HealthServer")
+            .field("addr", &self.addr)
+            .field("readiness", &self.readiness)
+            .field("metrics", &self.metrics.is_some())
+            .finish()
+    }
+}
+
+impl HealthServer {
+    pub fn new(addr: String, readiness: Arc<Readiness>) -> Self {
+        Self {
+            addr,
+            readiness,
+            metrics: None,
+        }
+    }
+
+    /// serve metrics of source on `/metrics`
+    pub fn with_metrics(mut self, metrics: Arc<dyn MetricsSource>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    pub fn run(self) {
+        spawn(self.accept_incoming());
+    }
+
+    #[instrument(skip(self), fields(addr = %self.addr))]
+    async fn accept_incoming(self) {
+        let listener = match TcpListener::bind(&self.addr).await {
+            Ok(listener) => listener,
+            Err(err) => {
+                // probes will fail and orchestrator restarts us, no need to exit here
+                error!("Error binding health server: {}", err);
+                return;
+            }
+        };
+
+        info!("health server started");
+        let mut incoming = listener.incoming();
+        while let Some(stream) = incoming.next().await {
+            match stream {
+                Ok(stream) => {
+                    let readiness = self.readiness.clone();
+                    let metrics = self.metrics.clone();
+                    spawn(async move {
+                        if let Err(err) = handle_probe(stream, &readiness, metrics.as_deref()).await
+                        {
+                            debug!("error answering probe: {}", err);
+                        }
+                    });
+                }
+                Err(err) => error!("error accepting health connection: {}", err),
+            }
+        }
+    }
+}
+
+async fn handle_probe(
+    mut stream: TcpStream,
+    readiness: &Readiness,
+    metrics: Option<&dyn MetricsSource>,
+) -> std::io::Result<()> {
+    let mut buf = vec![0u8; MAX_REQUEST_BYTES];
+    let len = match timeout(REQUEST_TIMEOUT, read_request_line(&mut stream, &mut buf)).await {
+        Ok(len) => len?,
+        Err(_) => {
+            debug!("timed out reading request");
+            return Ok(());
+        }
+    };
+
+    let request = String::from_utf8_lossy(&buf[..len]);
+    let request_line = request.lines().next().unwrap_or_default();
+    let (status, content_type, body) = match metrics {
+        Some(metrics) if is_metrics_request(request_line) => (
+            "200 OK",
+            "text/plain; version=0.0.4",
+            metrics.render().await,
+        ),
+        _ => {
+            let (status, body) = respond(request_line, readiness);
+            (status, "text/plain", body)
+        }
+    };
+    debug!(request_line, status, "health probe");
+
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.flush().await
+}
+
+/// read until end of request line, which is all we need. Returns bytes read
+async fn read_request_line(stream: &mut TcpStream, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut len = 0;
+    while len < buf.len() && !buf[..len].contains(&b'\n') {
+        let read = stream.read(&mut buf[len..]).await?;
+        if read == 0 {
+            break;
+        }
+        len += read;
+    }
+    Ok(len)
+}
+
+fn is_metrics_request(request_line: &str) -> bool {
+    let mut parts = request_line.split_whitespace();
+    matches!(parts.next(), Some("GET" | "HEAD"))
+        && parts
+            .next()
+            .is_some_and(|target| target.split('?').next() == Some(METRICS_PATH))
+}
+
+/// status line and body for request
+fn respond(request_line: &str, readiness: &Readiness) -> (&'static str, String) {
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return ("400 Bad Request", "bad request\n".to_owned());
+    };
+    if method != "GET" && method != "HEAD" {
+        return ("405 Method Not Allowed", "method not allowed\n".to_owned());
+    }
+
+    let path = target.split('?').next().unwrap_or_default();
+    match path {
+        LIVENESS_PATH => ("200 OK", "ok\n".to_owned()),
+        READINESS_PATH => {
+            let pending = readiness.pending();
+            if pending.is_empty() {
+                ("200 OK", "ready\n".to_owned())
+            } else {
+                (
+                    "503 Service Unavailable",
+                    format!("not ready: {}\n", pending.join(", ")),
+                )
+            }
+        }
+        _ => ("404 Not Found", "not found\n".to_owned()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_readiness() {
+        let readiness = Readiness::shared(&["metadata", "storage"]);
+        assert!(!readiness.is_ready());
+
+        readiness.set_ready("storage");
+        readiness.set_ready("unknown");
+        assert_eq!(readiness.pending(), vec!["metadata"]);
+
+        readiness.set_ready("metadata");
+        assert!(readiness.is_ready());
+    }
+
+    #[test]
+    fn test_respond() {
+        let readiness = Readiness::shared(&["metadata"]);
+
+        assert_eq!(respond("GET /healthz HTTP/1.1", &readiness).0, "200 OK");
+        assert_eq!(
+            respond("GET /readyz HTTP/1.1", &readiness),
+            (
+                "503 Service Unavailable",
+                "not ready: metadata\n".to_owned()
+            )
+        );
+
+        readiness.set_ready("metadata");
+        assert_eq!(
+            respond("GET /readyz?verbose HTTP/1.1", &readiness).0,
+            "200 OK"
+        );
+        assert_eq!(
+            respond("GET /metrics HTTP/1.1", &readiness).0,
+            "404 Not Found"
+        );
+        assert_eq!(
+            respond("POST /readyz HTTP/1.1", &readiness).0,
+            "405 Method Not Allowed"
+        );
+        assert_eq!(respond("", &readiness).0, "400 Bad Request");
+    }
+
+    #[test]
+    fn test_metrics_request() {
+        assert!(is_metrics_request("GET /metrics HTTP/1.1"));
+        assert!(is_metrics_request("GET /metrics?name=x HTTP/1.1"));
+        assert!(!is_metrics_request("GET /healthz HTTP/1.1"));
+        assert!(!is_metrics_request("POST /metrics HTTP/1.1"));
+        assert!(!is_metrics_request(""));
+    }
+}
@@ -0,0 +1,203 @@
+//!
+//! # IP filter
+//!
+//! CIDR based allow and deny lists for accepted connections.
+//! The filter is shared, so it can be replaced while the server is running.
+//!
+
+use std::fmt;
+use std::net::IpAddr;
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+
+use anyhow::{Result, anyhow};
+
+/// Network in CIDR notation, address without prefix length is single host
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpCidr {
+    addr: IpAddr,
+    prefix: u8,
+}
+
+impl IpCidr {
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        match (self.addr, ip) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => prefix_match(
+                u32::from(net).into(),
+                u32::from(*ip).into(),
+                self.prefix,
+                32,
+            ),
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                prefix_match(u128::from(net), u128::from(*ip), self.prefix, 128)
+            }
+            // dual stack listeners report IPv4 clients as mapped addresses
+            (IpAddr::V4(_), IpAddr::V6(ip)) => ip
+                .to_ipv4_mapped()
+                .is_some_and(|ip| self.contains(&IpAddr::V4(ip))),
+            (IpAddr::V6(_), IpAddr::V4(_)) => false,
+        }
+    }
+}
+
+fn prefix_match(net: u128, ip: u128, prefix: u8, bits: u8) -> bool {
+    if prefix == 0 {
+        return true;
+    }
+    let shift = bits - prefix;
+    (net >> shift) == (ip >> shift)
+}
+
+impl FromStr for IpCidr {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let (addr, prefix) = match s.split_once('/') {
+            Some((addr, prefix)) => (addr, Some(prefix)),
+            None => (s, None),
+        };
+        let addr: IpAddr = addr
+            .parse()
+            .map_err(|err| anyhow!("invalid address in \"{s}\": {err}"))?;
+        let max_prefix = if addr.is_ipv4() { 32 } else { 128 };
+        let prefix = match prefix {
+            Some(prefix) => prefix
+                .parse::<u8>()
+                .ok()
+                .filter(|prefix| *prefix <= max_prefix)
+                .ok_or_else(|| anyhow!("invalid prefix length in \"{s}\""))?,
+            None => max_prefix,
+        };
+        Ok(Self { addr, prefix })
+    }
+}
+
+impl fmt::Display for IpCidr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix)
+    }
+}
+
+/// Connections from denied networks are rejected.
+/// If allow list is not empty, only connections from allowed networks are accepted.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct IpFilter {
+    allow: Vec<IpCidr>,
+    deny: Vec<IpCidr>,
+}
+
+impl IpFilter {
+    /// parse networks in CIDR notation
+    pub fn new<S: AsRef<str>>(allow: &[S], deny: &[S]) -> Result<Self> {
+        let parse = |networks: &[S]| {
+            networks
+                .iter()
+                .map(|network| network.as_ref().parse())
+                .collect::<Result<Vec<IpCidr>>>()
+        };
+        Ok(Self {
+            allow: parse(allow)?,
+            deny: parse(deny)?,
+        })
+    }
+
+    pub fn from_networks(allow: Vec<IpCidr>, deny: Vec<IpCidr>) -> Self {
+        Self { allow, deny }
+    }
+
+    pub fn allow(&self) -> &[IpCidr] {
+        &self.allow
+    }
+
+    pub fn deny(&self) -> &[IpCidr] {
+        &self.deny
+    }
+
+    pub fn is_allowed(&self, ip: &IpAddr) -> bool {
+        if self.deny.iter().any(|network| network.contains(ip)) {
+            return false;
+        }
+        self.allow.is_empty() || self.allow.iter().any(|network| network.contains(ip))
+    }
+}
+
+/// IP filter of a running server
+#[derive(Debug, Default, Clone)]
+pub struct SharedIpFilter(Arc<RwLock<IpFilter>>);
+
+impl SharedIpFilter {
+    pub fn new(filter: IpFilter) -> Self {
+        Self(Arc::new(RwLock::new(filter)))
+    }
+
+    pub fn get(&self) -> IpFilter {
+        self.0
+            .read()
+            .map(|filter| filter.clone())
+            .unwrap_or_default()
+    }
+
+    /// replace filter, applies to new connections
+    pub fn set(&self, filter: IpFilter) {
+        let mut current = self.0.write().unwrap_or_else(|err| err.into_inner());
+        *current = filter;
+    }
+
+    pub fn is_allowed(&self, ip: &IpAddr) -> bool {
+        self.0
+            .read()
+            .map(|filter| filter.is_allowed(ip))
+            .unwrap_or(true)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    fn ip(ip: &str) -> IpAddr {
+        ip.parse().unwrap()
+    }
+
+    #[test]
+    fn test_cidr() {
+        let network: IpCidr = "10.1.0.0/16".parse().expect("parse");
+        assert!(network.contains(&ip("10.1.2.3")));
+        assert!(!network.contains(&ip("10.2.0.1")));
+        assert!(network.contains(&ip("::ffff:10.1.0.1")));
+        assert_eq!(network.to_string(), "10.1.0.0/16");
+
+        let host: IpCidr = "192.168.1.5".parse().expect("parse");
+        assert!(host.contains(&ip("192.168.1.5")));
+        assert!(!host.contains(&ip("192.168.1.6")));
+
+        let any: IpCidr = "0.0.0.0/0".parse().expect("parse");
+        assert!(any.contains(&ip("8.8.8.8")));
+
+        let v6: IpCidr = "fd00::/8".parse().expect("parse");
+        assert!(v6.contains(&ip("fd12::1")));
+        assert!(!v6.contains(&ip("10.0.0.1")));
+
+        assert!("10.0.0.0/33".parse::<IpCidr>().is_err());
+        assert!("10.0.0/8".parse::<IpCidr>().is_err());
+    }
+
+    #[test]
+    fn test_ip_filter() {
+        let filter = IpFilter::new(&["10.0.0.0/8"], &["10.0.5.0/24"]).expect("filter");
+        assert!(filter.is_allowed(&ip("10.1.1.1")));
+        assert!(!filter.is_allowed(&ip("10.0.5.1")));
+        assert!(!filter.is_allowed(&ip("192.168.1.1")));
+
+        let deny_only = IpFilter::new(&[], &["192.168.0.0/16"]).expect("filter");
+        assert!(deny_only.is_allowed(&ip("10.1.1.1")));
+        assert!(!deny_only.is_allowed(&ip("192.168.1.1")));
+
+        let shared = SharedIpFilter::default();
+        assert!(shared.is_allowed(&ip("192.168.1.1")));
+        shared.set(deny_only);
+        assert!(!shared.is_allowed(&ip("192.168.1.1")));
+    }
+}
@@ -1,5 +1,8 @@
 #[cfg(unix)]
 mod server;
+pub mod health;
+pub mod limits;
+pub mod ip_filter;
 
 #[cfg(test)]
 pub mod test_request;
@@ -10,20 +13,38 @@ pub use fluvio_protocol::codec::FluvioCodec;
 #[macro_export]
 macro_rules! call_service {
     ($req:expr,$handler:expr,$sink:expr,$msg:expr) => {{
+        $crate::call_service!($req, $handler, $sink, $msg, None)
+    }};
+
+    // timeout is how long client waits for response, as sent in api versions request
+    ($req:expr,$handler:expr,$sink:expr,$msg:expr,$timeout:expr) => {{
         {
             let version = $req.header.api_version();
-            tracing::debug!(api = $msg, "invoking handler");
-            let response = $handler.await?;
-            tracing::trace!("send back response: {:#?}", &response);
-            // we do not fast return here because there could be incoming requests to read
-            // even if the socket is closed to write.
-            if let Err(err) = $sink.send_response(&response, version).await {
-                tracing::warn!(
-                    "sending response failed: {}. Client could gave up waiting for the response",
-                    err
-                );
+            let timeout: Option<std::time::Duration> = $timeout;
+            tracing::debug!(api = $msg, ?timeout, "invoking handler");
+            let handler = $handler;
+            // client stops waiting after timeout, there is no point to keep working on it
+            let response = match timeout {
+                Some(timeout) => match fluvio_future::future::timeout(timeout, handler).await {
+                    Ok(response) => Some(response?),
+                    Err(_) => None,
+                },
+                None => Some(handler.await?),
+            };
+            if let Some(response) = response {
+                tracing::trace!("send back response: {:#?}", &response);
+                // we do not fast return here because there could be incoming requests to read
+                // even if the socket is closed to write.
+                if let Err(err) = $sink.send_response(&response, version).await {
+                    tracing::warn!(
+                        "sending response failed: {}. Client could gave up waiting for the response",
+                        err
+                    );
+                }
+                tracing::debug!(api = $msg, "finished");
+            } else {
+                tracing::info!(api = $msg, ?timeout, "client deadline passed, request abandoned");
             }
-            tracing::debug!(api = $msg, "finished");
         }
     }};
 
@@ -0,0 +1,231 @@
+//!
+//! # Connection limits
+//!
+//! Caps concurrent connections per source IP and per authenticated principal,
+//! so a single misbehaving client can't exhaust a shared server.
+//!
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+
+use tracing::{debug, warn};
+
+use fluvio_protocol::api::RequestMessage;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::link::versions::{ApiVersionsRequest, ApiVersionsResponse, VERSIONS_API_KEY};
+use fluvio_socket::FluvioSocket;
+
+/// Max concurrent connections, unset means unlimited
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ConnectionLimits {
+    pub max_per_ip: Option<u32>,
+    pub max_per_principal: Option<u32>,
+}
+
+/// Tracks open connections of a server against its limits
+#[derive(Debug, Default)]
+pub struct ConnectionTracker {
+    limits: ConnectionLimits,
+    by_ip: Mutex<HashMap<IpAddr, u32>>,
+    by_principal: Mutex<HashMap<String, u32>>,
+}
+
+impl ConnectionTracker {
+    pub fn new(limits: ConnectionLimits) -> Self {
+        Self {
+            limits,
+            ..Default::default()
+        }
+    }
+
+    pub fn limits(&self) -> &ConnectionLimits {
+        &self.limits
+    }
+
+    /// Count connection from `ip`, released when permit is dropped
+    pub fn acquire_ip(self: &Arc<Self>, ip: IpAddr) -> Result<ConnectionPermit, ErrorCode> {
+        let Some(max) = self.limits.max_per_ip else {
+            return Ok(ConnectionPermit::unlimited());
+        };
+        acquire(&self.by_ip, ip, max, || format!("from {ip}"))?;
+        Ok(ConnectionPermit {
+            tracker: Some(self.clone()),
+            key: Some(PermitKey::Ip(ip)),
+        })
+    }
+
+    /// Count connection of authenticated `principal`, released when permit is dropped
+    pub fn acquire_principal(
+        self: &Arc<Self>,
+        principal: &str,
+    ) -> Result<ConnectionPermit, ErrorCode> {
+        let Some(max) = self.limits.max_per_principal else {
+            return Ok(ConnectionPermit::unlimited());
+        };
+        acquire(&self.by_principal, principal.to_owned(), max, || {
+            format!("of principal {principal}")
+        })?;
+        Ok(ConnectionPermit {
+            tracker: Some(self.clone()),
+            key: Some(PermitKey::Principal(principal.to_owned())),
+        })
+    }
+
+    /// open connections from `ip`
+    pub fn ip_connections(&self, ip: &IpAddr) -> u32 {
+        count(&self.by_ip, ip)
+    }
+
+    /// open connections of `principal`
+    pub fn principal_connections(&self, principal: &str) -> u32 {
+        count(&self.by_principal, principal)
+    }
+}
+
+fn acquire<K: Eq + Hash>(
+    counts: &Mutex<HashMap<K, u32>>,
+    key: K,
+    max: u32,
+    scope: impl FnOnce() -> String,
+) -> Result<(), ErrorCode> {
+    let mut counts = counts.lock().unwrap_or_else(|err| err.into_inner());
+    let open = counts.get(&key).copied().unwrap_or_default();
+    if open >= max {
+        let scope = scope();
+        warn!(%scope, max, "connection limit reached, rejecting connection");
+        return Err(ErrorCode::ConnectionLimitExceeded { scope, max });
+    }
+    counts.insert(key, open + 1);
+    Ok(())
+}
+
+fn release<K: Eq + Hash>(counts: &Mutex<HashMap<K, u32>>, key: &K) {
+    let mut counts = counts.lock().unwrap_or_else(|err| err.into_inner());
+    if let Some(open) = counts.get_mut(key) {
+        *open = open.saturating_sub(1);
+        if *open == 0 {
+            counts.remove(key);
+        }
+    }
+}
+
+fn count<K, Q>(counts: &Mutex<HashMap<K, u32>>, key: &Q) -> u32
+where
+    K: Eq + Hash + std::borrow::Borrow<Q>,
+    Q: Eq + Hash + ?Sized,
+{
+    counts
+        .lock()
+        .map(|counts| counts.get(key).copied().unwrap_or_default())
+        .unwrap_or_default()
+}
+
+#[derive(Debug)]
+enum PermitKey {
+    Ip(IpAddr),
+    Principal(String),
+}
+
+/// Open connection counted against limits
+#[derive(Debug)]
+pub struct ConnectionPermit {
+    tracker: Option<Arc<ConnectionTracker>>,
+    key: Option<PermitKey>,
+}
+
+impl ConnectionPermit {
+    fn unlimited() -> Self {
+        Self {
+            tracker: None,
+            key: None,
+        }
+    }
+}
+
+impl Drop for ConnectionPermit {
+    fn drop(&mut self) {
+        let (Some(tracker), Some(key)) = (&self.tracker, &self.key) else {
+            return;
+        };
+        match key {
+            PermitKey::Ip(ip) => release(&tracker.by_ip, ip),
+            PermitKey::Principal(principal) => release(&tracker.by_principal, principal),
+        }
+    }
+}
+
+/// Reply to version request of rejected client with `error`, so client gets the reason
+/// instead of closed connection
+pub async fn reject_connection(mut socket: FluvioSocket, error: ErrorCode) {
+    let request: RequestMessage<ApiVersionsRequest> =
+        match socket.get_mut_stream().next_request_item().await {
+            Some(Ok(request)) if request.header.api_key() == VERSIONS_API_KEY => request,
+            _ => {
+                debug!("rejected client didn't request versions, closing connection");
+                return;
+            }
+        };
+    let version = request.header.api_version();
+    let response = request.new_response(ApiVersionsResponse {
+        error_code: error,
+        ..Default::default()
+    });
+    if let Err(err) = socket
+        .get_mut_sink()
+        .send_response(&response, version)
+        .await
+    {
+        debug!(%err, "unable to send rejection");
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_connection_limits() {
+        let tracker = Arc::new(ConnectionTracker::new(ConnectionLimits {
+            max_per_ip: Some(2),
+            max_per_principal: Some(1),
+        }));
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+
+        let first = tracker.acquire_ip(ip).expect("first");
+        let _second = tracker.acquire_ip(ip).expect("second");
+        let err = tracker.acquire_ip(ip).unwrap_err();
+        assert_eq!(
+            err,
+            ErrorCode::ConnectionLimitExceeded {
+                scope: "from 10.0.0.1".to_owned(),
+                max: 2
+            }
+        );
+        // other ips are not affected
+        assert!(tracker.acquire_ip("10.0.0.2".parse().unwrap()).is_ok());
+
+        drop(first);
+        assert_eq!(tracker.ip_connections(&ip), 1);
+        assert!(tracker.acquire_ip(ip).is_ok());
+
+        let alice = tracker.acquire_principal("alice").expect("alice");
+        assert!(tracker.acquire_principal("alice").is_err());
+        assert!(tracker.acquire_principal("bob").is_ok());
+        drop(alice);
+        assert_eq!(tracker.principal_connections("alice"), 0);
+    }
+
+    #[test]
+    fn test_unlimited() {
+        let tracker = Arc::new(ConnectionTracker::default());
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let permits: Vec<_> = (0..10)
+            .map(|_| tracker.acquire_ip(ip).expect("permit"))
+            .collect();
+        assert_eq!(permits.len(), 10);
+        assert_eq!(tracker.ip_connections(&ip), 0);
+    }
+}
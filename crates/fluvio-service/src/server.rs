@@ -1,24 +1,58 @@
 use std::fmt;
 use std::fmt::Debug;
 use std::marker::PhantomData;
+use std::net::SocketAddr;
 use std::process;
 use std::sync::Arc;
 use std::os::unix::io::AsRawFd;
 
 use futures_util::StreamExt;
 use async_trait::async_trait;
-use tracing::{instrument, debug, error, info};
+use tracing::{instrument, debug, error, info, warn};
 use anyhow::Result;
 
 use fluvio_future::net::{TcpListener, TcpStream};
 use fluvio_future::task::spawn;
 use fluvio_protocol::api::ApiMessage;
 use fluvio_protocol::Decoder as FluvioDecoder;
-use fluvio_socket::FluvioSocket;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_socket::{FluvioSocket, SocketTuning};
 use fluvio_types::event::StickyEvent;
 
+use crate::ip_filter::SharedIpFilter;
+use crate::limits::{ConnectionLimits, ConnectionPermit, ConnectionTracker, reject_connection};
+
 pub struct ConnectInfo {
     peer: String,
+    tracker: Arc<ConnectionTracker>,
+    proxied: bool,
+}
+
+impl ConnectInfo {
+    /// address of connected peer
+    pub fn peer(&self) -> &str {
+        &self.peer
+    }
+
+    /// Count connection against limits of authenticated principal and, for server behind
+    /// TLS proxy, of client address passed by proxy.
+    /// Permits must be kept while connection is open.
+    pub fn acquire_client(
+        &self,
+        principal: Option<&str>,
+        client_addr: Option<&str>,
+    ) -> Result<Vec<ConnectionPermit>, ErrorCode> {
+        let mut permits = vec![];
+        if self.proxied
+            && let Ok(addr) = client_addr.unwrap_or(&self.peer).parse::<SocketAddr>()
+        {
+            permits.push(self.tracker.acquire_ip(addr.ip())?);
+        }
+        if let Some(principal) = principal {
+            permits.push(self.tracker.acquire_principal(principal)?);
+        }
+        Ok(permits)
+    }
 }
 
 impl fmt::Debug for ConnectInfo {
@@ -51,6 +85,10 @@ pub struct FluvioApiServer<R, A, C, S> {
     context: C,
     service: Arc<S>,
     addr: String,
+    tuning: SocketTuning,
+    tracker: Arc<ConnectionTracker>,
+    ip_filter: SharedIpFilter,
+    proxied: bool,
 }
 
 impl<R, A, C, S> fmt::Debug for FluvioApiServer<R, A, C, S> {
@@ -70,8 +108,37 @@ where
             service: Arc::new(service),
             context,
             addr,
+            tuning: SocketTuning::default(),
+            tracker: Arc::new(ConnectionTracker::default()),
+            ip_filter: SharedIpFilter::default(),
+            proxied: false,
         }
     }
+
+    /// TCP options applied to accepted connections
+    pub fn with_socket_tuning(mut self, tuning: SocketTuning) -> Self {
+        self.tuning = tuning;
+        self
+    }
+
+    /// Reject connections beyond limits per source IP and principal
+    pub fn with_connection_limits(mut self, limits: ConnectionLimits) -> Self {
+        self.tracker = Arc::new(ConnectionTracker::new(limits));
+        self
+    }
+
+    /// Reject connections from addresses not allowed by filter
+    pub fn with_ip_filter(mut self, ip_filter: SharedIpFilter) -> Self {
+        self.ip_filter = ip_filter;
+        self
+    }
+
+    /// Connections are forwarded by TLS proxy, limit per source IP is applied to
+    /// client address passed by proxy instead of address of proxy
+    pub fn with_tls_proxy(mut self, proxied: bool) -> Self {
+        self.proxied = proxied;
+        self
+    }
 }
 
 impl<R, A, C, S> FluvioApiServer<R, A, C, S>
@@ -109,7 +176,15 @@ where
                     let context = self.context.clone();
                     let service = self.service.clone();
                     let host = self.addr.clone();
-                    spawn(Self::handle_request(stream, context, service, host));
+                    let tracker = self.tracker.clone();
+                    let ip_filter = self.ip_filter.clone();
+                    let proxied = self.proxied;
+                    if let Err(err) = self.tuning.apply(stream.as_raw_fd()) {
+                        warn!(%err, "unable to apply socket options");
+                    }
+                    spawn(Self::handle_request(
+                        stream, context, service, host, tracker, ip_filter, proxied,
+                    ));
                 }
                 Err(e) => {
                     error!("Error from TCP Stream: {:?}", e);
@@ -120,12 +195,20 @@ where
         info!("Closed TcpListener");
     }
 
-    #[instrument(skip(stream, context, service))]
-    async fn handle_request(stream: TcpStream, context: C, service: Arc<S>, host: String) {
-        let peer_addr = stream
-            .peer_addr()
+    #[instrument(skip(stream, context, service, tracker, ip_filter))]
+    async fn handle_request(
+        stream: TcpStream,
+        context: C,
+        service: Arc<S>,
+        host: String,
+        tracker: Arc<ConnectionTracker>,
+        ip_filter: SharedIpFilter,
+        proxied: bool,
+    ) {
+        let peer = stream.peer_addr().ok();
+        let peer_addr = peer
             .map(|addr| addr.to_string())
-            .unwrap_or_else(|_| "".to_owned());
+            .unwrap_or_else(|| "".to_owned());
         debug!(%peer_addr, "Handling request");
 
         let socket = {
@@ -133,8 +216,35 @@ where
             FluvioSocket::from_stream(Box::new(stream.clone()), Box::new(stream), fd)
         };
 
+        if let Some(addr) = peer
+            && !ip_filter.is_allowed(&addr.ip())
+        {
+            info!(%host, %peer_addr, "connection rejected by IP filter");
+            let err = ErrorCode::ConnectionNotAllowed {
+                ip: addr.ip().to_string(),
+            };
+            reject_connection(socket, err).await;
+            return;
+        }
+
+        // held until connection is closed, behind proxy client is counted by service
+        let _permit: Option<ConnectionPermit> = match peer
+            .filter(|_| !proxied)
+            .map(|addr| tracker.acquire_ip(addr.ip()))
+        {
+            Some(Ok(permit)) => Some(permit),
+            Some(Err(err)) => {
+                info!(%host, %peer_addr, %err, "connection rejected");
+                reject_connection(socket, err).await;
+                return;
+            }
+            None => None,
+        };
+
         let connection_info = ConnectInfo {
             peer: peer_addr.clone(),
+            tracker,
+            proxied,
         };
 
         let result = service.respond(context, socket, connection_info).await;
@@ -247,4 +357,48 @@ mod test {
         assert_eq!(service.processed_requests.load(Ordering::SeqCst), 4);
         shutdown.notify();
     }
+    #[test]
+    fn test_limits_of_proxied_client() {
+        let tracker = Arc::new(ConnectionTracker::new(ConnectionLimits {
+            max_per_ip: Some(1),
+            max_per_principal: Some(2),
+        }));
+        let connection = |proxied| ConnectInfo {
+            peer: "127.0.0.1:9000".to_owned(),
+            tracker: tracker.clone(),
+            proxied,
+        };
+
+        // clients behind proxy are counted by their own address
+        let first = connection(true)
+            .acquire_client(Some("alice"), Some("10.0.0.1:5000"))
+            .expect("first client");
+        assert_eq!(first.len(), 2);
+        let second = connection(true)
+            .acquire_client(Some("alice"), Some("10.0.0.2:5000"))
+            .expect("other client");
+        assert!(
+            connection(true)
+                .acquire_client(None, Some("10.0.0.1:5001"))
+                .is_err()
+        );
+        // principal limit is enforced
+        drop(second);
+        let _third = connection(true)
+            .acquire_client(Some("alice"), Some("10.0.0.2:5000"))
+            .expect("released client");
+        assert!(
+            connection(true)
+                .acquire_client(Some("alice"), Some("10.0.0.3:5000"))
+                .is_err()
+        );
+
+        // without proxy, address was counted when connection was accepted
+        let direct = connection(false)
+            .acquire_client(None, Some("10.0.0.1:5000"))
+            .expect("direct");
+        assert!(direct.is_empty());
+        drop(first);
+        assert_eq!(tracker.ip_connections(&"10.0.0.1".parse().unwrap()), 0);
+    }
 }
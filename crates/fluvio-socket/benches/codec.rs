@@ -0,0 +1,137 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use bytes::BytesMut;
+use criterion::{criterion_group, criterion_main, Criterion};
+use tokio_util::codec::Encoder as _;
+
+use fluvio_protocol::Encoder;
+use fluvio_protocol::codec::FluvioCodec;
+use fluvio_socket::BufferPool;
+
+/// counts allocations so benchmark can report allocations per message
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+const MESSAGES: usize = 1000;
+const PAYLOAD_SIZE: usize = 1024;
+const READ_SIZE: usize = 64 * 1024;
+
+fn payload() -> Vec<u8> {
+    vec![0xab; PAYLOAD_SIZE]
+}
+
+/// encoding as done before messages were written directly into frame buffer
+fn encode_with_copies<T: Encoder>(data: &T, buf: &mut BytesMut) {
+    let size = data.write_size(0) as i32;
+    buf.reserve(4 + size as usize);
+    let mut len_slice = Vec::new();
+    size.encode(&mut len_slice, 0).expect("len");
+    buf.extend_from_slice(&len_slice);
+    buf.extend_from_slice(&data.as_bytes(0).expect("bytes"));
+}
+
+fn encode_with_codec<T: Encoder>(data: &T, codec: &mut FluvioCodec, buf: &mut BytesMut) {
+    codec.encode((data, 0), buf).expect("encode");
+}
+
+fn allocations_per_message(mut f: impl FnMut()) -> f64 {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    for _ in 0..MESSAGES {
+        f();
+    }
+    (ALLOCATIONS.load(Ordering::Relaxed) - before) as f64 / MESSAGES as f64
+}
+
+fn report_allocations() {
+    let data = payload();
+    let mut buf = BytesMut::with_capacity(PAYLOAD_SIZE * 2);
+    let copies = allocations_per_message(|| {
+        buf.clear();
+        encode_with_copies(&data, &mut buf);
+    });
+
+    let mut codec = FluvioCodec::new();
+    let direct = allocations_per_message(|| {
+        buf.clear();
+        encode_with_codec(&data, &mut codec, &mut buf);
+    });
+
+    let fresh = allocations_per_message(|| {
+        let mut buf = BytesMut::with_capacity(READ_SIZE);
+        buf.resize(READ_SIZE, 0);
+        black_box(buf);
+    });
+
+    let pool = BufferPool::default();
+    let pooled = allocations_per_message(|| {
+        let mut buf = pool.get(READ_SIZE);
+        buf.resize(READ_SIZE, 0);
+        pool.put(buf);
+    });
+
+    println!("allocations per message: encode with copies {copies:.2}, codec encode {direct:.2}");
+    println!("allocations per read buffer: fresh {fresh:.2}, pooled {pooled:.2}");
+}
+
+fn bench_encode(c: &mut Criterion) {
+    report_allocations();
+
+    let data = payload();
+    let mut group = c.benchmark_group("encode");
+    group.bench_function("with copies", |b| {
+        let mut buf = BytesMut::with_capacity(PAYLOAD_SIZE * 2);
+        b.iter(|| {
+            buf.clear();
+            encode_with_copies(&data, &mut buf);
+        })
+    });
+    group.bench_function("codec", |b| {
+        let mut codec = FluvioCodec::new();
+        let mut buf = BytesMut::with_capacity(PAYLOAD_SIZE * 2);
+        b.iter(|| {
+            buf.clear();
+            encode_with_codec(&data, &mut codec, &mut buf);
+        })
+    });
+    group.finish();
+}
+
+fn bench_read_buffer(c: &mut Criterion) {
+    let mut group = c.benchmark_group("read buffer");
+    group.bench_function("fresh", |b| {
+        b.iter(|| {
+            let mut buf = BytesMut::with_capacity(READ_SIZE);
+            buf.resize(READ_SIZE, 0);
+            buf
+        })
+    });
+    group.bench_function("pooled", |b| {
+        let pool = BufferPool::default();
+        b.iter(|| {
+            let mut buf = pool.get(READ_SIZE);
+            buf.resize(READ_SIZE, 0);
+            pool.put(buf);
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_encode, bench_read_buffer);
+criterion_main!(benches);
@@ -0,0 +1,105 @@
+use std::sync::Mutex;
+
+use bytes::BytesMut;
+
+/// max buffers kept by default pool
+const DEFAULT_MAX_BUFFERS: usize = 64;
+
+/// buffers larger than this are not returned to default pool so single large read doesn't pin memory
+const DEFAULT_MAX_CAPACITY: usize = 1024 * 1024;
+
+/// Pool of reusable byte buffers.
+///
+/// Buffers are handed out empty. Buffers returned to a full pool, or larger than
+/// the pool's max capacity, are dropped.
+#[derive(Debug)]
+pub struct BufferPool {
+    buffers: Mutex<Vec<BytesMut>>,
+    max_buffers: usize,
+    max_capacity: usize,
+}
+
+impl Default for BufferPool {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_BUFFERS, DEFAULT_MAX_CAPACITY)
+    }
+}
+
+impl BufferPool {
+    pub fn new(max_buffers: usize, max_capacity: usize) -> Self {
+        Self {
+            buffers: Mutex::new(Vec::with_capacity(max_buffers)),
+            max_buffers,
+            max_capacity,
+        }
+    }
+
+    /// take empty buffer with at least `capacity` bytes of capacity
+    pub fn get(&self, capacity: usize) -> BytesMut {
+        let pooled = self.buffers.lock().unwrap().pop();
+        match pooled {
+            Some(mut buf) => {
+                buf.clear();
+                buf.reserve(capacity);
+                buf
+            }
+            None => BytesMut::with_capacity(capacity),
+        }
+    }
+
+    /// return buffer to pool
+    pub fn put(&self, buf: BytesMut) {
+        if buf.capacity() > self.max_capacity {
+            return;
+        }
+        let mut buffers = self.buffers.lock().unwrap();
+        if buffers.len() < self.max_buffers {
+            buffers.push(buf);
+        }
+    }
+
+    /// number of idle buffers in pool
+    pub fn len(&self) -> usize {
+        self.buffers.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_buffer_pool_reuse() {
+        let pool = BufferPool::new(2, 1024);
+        assert!(pool.is_empty());
+
+        let mut buf = pool.get(100);
+        buf.extend_from_slice(b"hello");
+        let ptr = buf.as_ptr();
+        pool.put(buf);
+        assert_eq!(pool.len(), 1);
+
+        let buf = pool.get(50);
+        assert!(buf.is_empty());
+        assert!(buf.capacity() >= 100);
+        assert_eq!(buf.as_ptr(), ptr);
+        assert!(pool.is_empty());
+    }
+
+    #[test]
+    fn test_buffer_pool_limits() {
+        let pool = BufferPool::new(1, 1024);
+
+        pool.put(BytesMut::with_capacity(4096));
+        assert!(pool.is_empty());
+
+        pool.put(BytesMut::with_capacity(10));
+        pool.put(BytesMut::with_capacity(10));
+        assert_eq!(pool.len(), 1);
+    }
+}
@@ -1,5 +1,7 @@
 use std::io::Error as IoError;
 
+use fluvio_protocol::link::ErrorCode;
+
 #[derive(thiserror::Error, Debug)]
 pub enum SocketError {
     #[error("Socket io {msg}")]
@@ -8,6 +10,8 @@ pub enum SocketError {
     SocketClosed,
     #[error("Socket is stale")]
     SocketStale,
+    #[error("Connection rejected by server: {0}")]
+    Rejected(ErrorCode),
 }
 
 impl From<IoError> for SocketError {
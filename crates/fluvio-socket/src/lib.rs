@@ -1,3 +1,4 @@
+mod buffer;
 mod error;
 mod multiplexing;
 mod sink;
@@ -5,13 +6,16 @@ mod socket;
 mod stream;
 mod versioned;
 mod stream_socket;
+mod tuning;
 
 #[cfg(test)]
 pub mod test_request;
 
 pub use fluvio_future::net::{BoxConnection, Connection};
+pub use self::buffer::BufferPool;
 pub use self::error::SocketError;
 pub use self::socket::FluvioSocket;
+pub use self::tuning::SocketTuning;
 pub use multiplexing::*;
 pub use sink::*;
 
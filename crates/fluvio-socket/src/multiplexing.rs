@@ -41,6 +41,21 @@ use crate::FluvioStream;
 
 pub type SharedMultiplexerSocket = Arc<MultiplexerSocket>;
 
+/// how long `send_and_receive` waits for response, configured by `FLV_SOCKET_WAIT` in seconds
+pub(crate) fn request_wait_time() -> Duration {
+    use once_cell::sync::Lazy;
+
+    static MAX_WAIT_TIME: Lazy<u64> = Lazy::new(|| {
+        use std::env;
+
+        let var_value = env::var("FLV_SOCKET_WAIT").unwrap_or_default();
+        let wait_time: u64 = var_value.parse().unwrap_or(60);
+        wait_time
+    });
+
+    Duration::from_secs(*MAX_WAIT_TIME)
+}
+
 #[derive(Clone)]
 struct SharedMsg(Arc<Mutex<Option<Bytes>>>, Arc<Event>);
 
@@ -132,16 +147,6 @@ impl MultiplexerSocket {
     where
         R: Request,
     {
-        use once_cell::sync::Lazy;
-
-        static MAX_WAIT_TIME: Lazy<u64> = Lazy::new(|| {
-            use std::env;
-
-            let var_value = env::var("FLV_SOCKET_WAIT").unwrap_or_default();
-            let wait_time: u64 = var_value.parse().unwrap_or(60);
-            wait_time
-        });
-
         let correlation_id = self.next_correlation_id();
         let bytes_lock = SharedMsg(Arc::new(Mutex::new(None)), Arc::new(Event::new()));
 
@@ -162,7 +167,7 @@ impl MultiplexerSocket {
 
         select! {
 
-            _ = sleep(Duration::from_secs(*MAX_WAIT_TIME)) => {
+            _ = sleep(request_wait_time()) => {
 
                 trace!("serial socket for: {}  timeout happen, id: {}", R::API_KEY, correlation_id);
                 // clean channel
@@ -174,7 +179,7 @@ impl MultiplexerSocket {
 
                 Err(IoError::new(
                     ErrorKind::TimedOut,
-                    format!("Timed out: {} secs waiting for response. API_KEY={}, CorrelationId={}", *MAX_WAIT_TIME,R::API_KEY, correlation_id),
+                    format!("Timed out: {} secs waiting for response. API_KEY={}, CorrelationId={}", request_wait_time().as_secs(),R::API_KEY, correlation_id),
                 ).into())
             },
 
@@ -23,6 +23,9 @@ pub struct FluvioSink {
     inner: SinkFrame,
     fd: ConnectionFd,
     enable_zero_copy: bool,
+    /// reused for encoding file slice messages
+    #[cfg(feature = "file")]
+    encode_buf: bytes::BytesMut,
 }
 
 impl fmt::Debug for FluvioSink {
@@ -52,6 +55,8 @@ impl FluvioSink {
             fd,
             enable_zero_copy: true,
             inner: SinkFrame::new(sink.compat_write(), FluvioCodec::new()),
+            #[cfg(feature = "file")]
+            encode_buf: bytes::BytesMut::new(),
         }
     }
 
@@ -103,18 +108,28 @@ mod fd {
 
 #[cfg(feature = "file")]
 mod file {
+    use std::io::IoSlice;
     use std::os::fd::BorrowedFd;
 
-    use bytes::BytesMut;
+    use bytes::Bytes;
+    use once_cell::sync::Lazy;
     use fluvio_future::task::spawn_blocking;
-    use futures_util::AsyncWriteExt;
+    use futures_util::{AsyncWrite, AsyncWriteExt};
     use nix::sys::uio::pread;
 
     use fluvio_protocol::store::{FileWrite, StoreValue};
     use fluvio_future::zero_copy::ZeroCopy;
 
+    use crate::BufferPool;
+
     use super::*;
 
+    /// initial capacity of buffer used to encode message around file slices
+    const ENCODE_BUF_CAPACITY: usize = 1000;
+
+    /// buffers for reading file slices when zero copy is disabled
+    static READ_BUFFERS: Lazy<BufferPool> = Lazy::new(BufferPool::default);
+
     impl FluvioSink {
         /// write
         pub async fn encode_file_slices<T>(
@@ -126,12 +141,18 @@ mod file {
             T: FileWrite,
         {
             trace!("encoding file slices version: {}", version);
-            let mut buf = BytesMut::with_capacity(1000);
+            // encoded bytes are split off this buffer, once they are written and dropped
+            // the allocation is reclaimed by the next reserve
+            let mut buf = std::mem::take(&mut self.encode_buf);
+            buf.clear();
+            buf.reserve(ENCODE_BUF_CAPACITY);
             let mut data: Vec<StoreValue> = vec![];
-            msg.file_encode(&mut buf, &mut data, version)?;
+            let encoded = msg.file_encode(&mut buf, &mut data, version);
             trace!("encoded buffer len: {}", buf.len());
             // add remainder
-            data.push(StoreValue::Bytes(buf.freeze()));
+            data.push(StoreValue::Bytes(buf.split().freeze()));
+            self.encode_buf = buf;
+            encoded?;
             self.write_store_values(data).await
         }
 
@@ -143,21 +164,19 @@ mod file {
             trace!("writing store values to socket values: {}", values.len());
 
             let mut total_bytes_written = 0usize;
+            // consecutive bytes are gathered and written with single vectored write
+            let mut pending_bytes: Vec<Bytes> = vec![];
 
             for value in values {
                 match value {
                     StoreValue::Bytes(bytes) => {
-                        trace!("writing store bytes to socket len: {}", bytes.len());
-                        // These bytes should be already encoded so don't need to pass
-                        // through the FluvioCodec
-                        self.get_mut_tcp_sink()
-                            .get_mut()
-                            .get_mut()
-                            .write_all(&bytes)
-                            .await?;
-                        total_bytes_written += bytes.len();
+                        trace!("queueing store bytes len: {}", bytes.len());
+                        if !bytes.is_empty() {
+                            pending_bytes.push(bytes);
+                        }
                     }
                     StoreValue::FileSlice(f_slice) => {
+                        total_bytes_written += self.write_pending_bytes(&mut pending_bytes).await?;
                         if f_slice.is_empty() {
                             trace!("empty slice, skipping");
                         } else {
@@ -187,8 +206,8 @@ mod file {
                                     len = f_slice.len(),
                                     "reading from file slice"
                                 );
+                                let mut buf = READ_BUFFERS.get(f_slice.len() as usize);
                                 let (read_result, mut buf) = spawn_blocking(move || {
-                                    let mut buf = BytesMut::with_capacity(f_slice.len() as usize);
                                     buf.resize(f_slice.len() as usize, 0);
                                     let fd = unsafe { BorrowedFd::borrow_raw(in_fd) };
                                     let read_size = pread(fd, &mut buf, offset).map_err(|err| {
@@ -209,6 +228,7 @@ mod file {
                                     .get_mut()
                                     .write_all(&buf)
                                     .await?;
+                                READ_BUFFERS.put(buf);
 
                                 total_bytes_written += read;
                             }
@@ -216,10 +236,45 @@ mod file {
                     }
                 }
             }
+            total_bytes_written += self.write_pending_bytes(&mut pending_bytes).await?;
 
             trace!(total_bytes_written, "finish writing store values");
             Ok(total_bytes_written)
         }
+
+        /// write queued bytes to socket and clear queue.
+        /// These bytes should be already encoded so don't need to pass through the FluvioCodec
+        async fn write_pending_bytes(
+            &mut self,
+            pending: &mut Vec<Bytes>,
+        ) -> Result<usize, SocketError> {
+            if pending.is_empty() {
+                return Ok(0);
+            }
+            let mut slices: Vec<IoSlice<'_>> = pending.iter().map(|b| IoSlice::new(b)).collect();
+            trace!(buffers = slices.len(), "writing store bytes to socket");
+            let writer = self.get_mut_tcp_sink().get_mut().get_mut();
+            let written = write_all_vectored(writer, &mut slices).await?;
+            pending.clear();
+            Ok(written)
+        }
+    }
+
+    /// write all slices, retrying partial writes
+    async fn write_all_vectored<W: AsyncWrite + Unpin + ?Sized>(
+        writer: &mut W,
+        mut slices: &mut [IoSlice<'_>],
+    ) -> Result<usize, std::io::Error> {
+        let mut total = 0;
+        while !slices.is_empty() {
+            let written = writer.write_vectored(slices).await?;
+            if written == 0 {
+                return Err(std::io::ErrorKind::WriteZero.into());
+            }
+            total += written;
+            IoSlice::advance_slices(&mut slices, written);
+        }
+        Ok(total)
     }
 
     #[cfg(test)]
@@ -13,6 +13,7 @@ use fluvio_future::net::{
 use super::SocketError;
 use crate::FluvioSink;
 use crate::FluvioStream;
+use crate::SocketTuning;
 
 /// Socket abstract that can send and receive fluvio objects
 pub struct FluvioSocket {
@@ -61,6 +62,11 @@ impl FluvioSocket {
         self.sink.id()
     }
 
+    /// apply TCP options to underlying connection
+    pub fn apply_tuning(&self, tuning: &SocketTuning) -> Result<(), std::io::Error> {
+        tuning.apply(self.id())
+    }
+
     /// as client, send request and wait for reply from server
     pub async fn send<R>(
         &mut self,
@@ -37,6 +37,10 @@ impl StreamSocket {
         )
     }
 
+    pub fn versions(&self) -> &Versions {
+        &self.versions
+    }
+
     pub fn is_stale(&self) -> bool {
         self.socket.is_stale()
     }
@@ -0,0 +1,92 @@
+use std::io::Error as IoError;
+
+use fluvio_future::net::ConnectionFd;
+
+/// TCP options applied to connection after it is established.
+/// Options which are not set keep operating system defaults.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SocketTuning {
+    /// disable Nagle's algorithm
+    pub nodelay: Option<bool>,
+    /// SO_SNDBUF in bytes
+    pub send_buffer_size: Option<usize>,
+    /// SO_RCVBUF in bytes
+    pub recv_buffer_size: Option<usize>,
+}
+
+impl SocketTuning {
+    pub fn with_nodelay(mut self, nodelay: bool) -> Self {
+        self.nodelay = Some(nodelay);
+        self
+    }
+
+    pub fn with_send_buffer_size(mut self, bytes: usize) -> Self {
+        self.send_buffer_size = Some(bytes);
+        self
+    }
+
+    pub fn with_recv_buffer_size(mut self, bytes: usize) -> Self {
+        self.recv_buffer_size = Some(bytes);
+        self
+    }
+
+    /// true if no option is set
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// apply options to connection
+    #[cfg(unix)]
+    pub fn apply(&self, fd: ConnectionFd) -> Result<(), IoError> {
+        use std::os::fd::BorrowedFd;
+
+        use nix::sys::socket::{setsockopt, sockopt};
+
+        if self.is_default() {
+            return Ok(());
+        }
+
+        // connection owns the fd and outlives this call
+        let fd = unsafe { BorrowedFd::borrow_raw(fd) };
+        if let Some(nodelay) = self.nodelay {
+            setsockopt(&fd, sockopt::TcpNoDelay, &nodelay)?;
+        }
+        if let Some(size) = self.send_buffer_size {
+            setsockopt(&fd, sockopt::SndBuf, &size)?;
+        }
+        if let Some(size) = self.recv_buffer_size {
+            setsockopt(&fd, sockopt::RcvBuf, &size)?;
+        }
+        Ok(())
+    }
+
+    /// socket options are not supported on this platform
+    #[cfg(not(unix))]
+    pub fn apply(&self, _fd: ConnectionFd) -> Result<(), IoError> {
+        Ok(())
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+
+    use std::net::{TcpListener, TcpStream};
+    use std::os::fd::AsRawFd;
+
+    use super::*;
+
+    #[test]
+    fn test_apply_socket_tuning() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+        let stream = TcpStream::connect(listener.local_addr().expect("addr")).expect("connect");
+
+        let tuning = SocketTuning::default()
+            .with_nodelay(true)
+            .with_send_buffer_size(64 * 1024)
+            .with_recv_buffer_size(64 * 1024);
+        assert!(!tuning.is_default());
+        tuning.apply(stream.as_raw_fd()).expect("apply");
+
+        assert!(stream.nodelay().expect("nodelay"));
+    }
+}
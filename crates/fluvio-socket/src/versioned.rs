@@ -6,15 +6,19 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use fluvio_protocol::Version;
-use tracing::{debug, instrument, info};
+use tracing::{debug, instrument, info, warn};
 
 use fluvio_protocol::api::RequestMessage;
 use fluvio_protocol::api::Request;
-use fluvio_protocol::link::versions::{ApiVersions, ApiVersionsRequest, ApiVersionsResponse};
+use fluvio_protocol::link::token::TokenAuthRequest;
+use fluvio_protocol::link::versions::{
+    ApiVersions, ApiVersionsRequest, ApiVersionsResponse, Capabilities,
+};
 use fluvio_future::net::{DomainConnector, DefaultDomainConnector};
 use fluvio_future::retry::retry_if;
 
-use crate::{SocketError, FluvioSocket, SharedMultiplexerSocket, AsyncResponse};
+use crate::{SocketError, FluvioSocket, SharedMultiplexerSocket, AsyncResponse, SocketTuning};
+use crate::multiplexing::request_wait_time;
 
 /// Frame with request and response
 pub trait SerialFrame: Display {
@@ -56,6 +60,8 @@ impl VersionedSocket {
             client_version: crate::built_info::PKG_VERSION.into(),
             client_os: crate::built_info::CFG_OS.into(),
             client_arch: crate::built_info::CFG_TARGET_ARCH.into(),
+            // lets server give up on requests we stopped waiting for
+            request_timeout_ms: Some(request_wait_time().as_millis().min(u32::MAX as u128) as u32),
         };
 
         debug!(client_version = %version.client_version, "querying versions");
@@ -63,6 +69,9 @@ impl VersionedSocket {
         req_msg.get_mut_header().set_client_id(&config.client_id);
 
         let response: ApiVersionsResponse = (socket.send(&req_msg).await?).response;
+        if response.error_code.is_error() {
+            return Err(SocketError::Rejected(response.error_code));
+        }
         let versions = Versions::new(response);
 
         debug!("versions: {:#?}", versions);
@@ -79,6 +88,9 @@ impl VersionedSocket {
     }
 }
 
+/// Source of bearer token, asked again for every connection so reconnects send refreshed token
+pub type AuthTokenSource = Arc<dyn Fn() -> Option<String> + Send + Sync>;
+
 /// Low level configuration option to directly connect to Fluvio
 /// This can bypass higher level validation required for CLI and end user application
 pub struct ClientConfig {
@@ -86,6 +98,9 @@ pub struct ClientConfig {
     client_id: String,
     connector: DomainConnector,
     use_spu_local_address: bool,
+    tuning: SocketTuning,
+    /// bearer token sent before any request, authenticates connections to SC only
+    auth_token: Option<AuthTokenSource>,
 }
 
 impl Debug for ClientConfig {
@@ -121,6 +136,8 @@ impl ClientConfig {
             client_id: "fluvio".to_owned(),
             connector,
             use_spu_local_address,
+            tuning: SocketTuning::default(),
+            auth_token: None,
         }
     }
 
@@ -153,12 +170,44 @@ impl ClientConfig {
         self.addr = domain
     }
 
+    pub fn socket_tuning(&self) -> &SocketTuning {
+        &self.tuning
+    }
+
+    /// TCP options applied to every connection created from this config
+    pub fn set_socket_tuning(&mut self, tuning: SocketTuning) {
+        self.tuning = tuning;
+    }
+
+    /// Bearer token authenticating connection, configs for SPUs derived from this one don't have it
+    pub fn set_auth_token(&mut self, token: Option<String>) {
+        self.auth_token =
+            token.map(|token| Arc::new(move || Some(token.clone())) as AuthTokenSource);
+    }
+
+    /// Source of bearer token, asked on every connection including ones of [`Self::recreate`]
+    pub fn set_auth_token_source(&mut self, source: AuthTokenSource) {
+        self.auth_token = Some(source);
+    }
+
     #[instrument(skip(self))]
     pub async fn connect(self) -> Result<VersionedSocket, SocketError> {
         debug!(add = %self.addr, "try connection to");
-        let socket =
+        let mut socket =
             FluvioSocket::connect_with_connector(&self.addr, self.connector.as_ref()).await?;
         info!(add = %self.addr, "connect to socket");
+        if let Err(err) = socket.apply_tuning(&self.tuning) {
+            warn!(add = %self.addr, %err, "unable to apply socket options");
+        }
+        if let Some(token) = self.auth_token.as_ref().and_then(|source| source()) {
+            let mut req_msg = RequestMessage::new_request(TokenAuthRequest::new(token.as_str()));
+            req_msg.get_mut_header().set_client_id(&self.client_id);
+            let response = socket.send(&req_msg).await?.response;
+            if response.error_code.is_error() {
+                return Err(SocketError::Rejected(response.error_code));
+            }
+            debug!(add = %self.addr, "authenticated with token");
+        }
         VersionedSocket::connect(socket, Arc::new(self)).await
     }
 
@@ -174,6 +223,8 @@ impl ClientConfig {
             client_id: self.client_id.clone(),
             connector,
             use_spu_local_address: self.use_spu_local_address,
+            tuning: self.tuning,
+            auth_token: None,
         }
     }
 
@@ -185,6 +236,8 @@ impl ClientConfig {
                 .connector
                 .new_domain(self.connector.domain().to_owned()),
             use_spu_local_address: self.use_spu_local_address,
+            tuning: self.tuning,
+            auth_token: self.auth_token.clone(),
         }
     }
 }
@@ -194,6 +247,8 @@ impl ClientConfig {
 pub struct Versions {
     api_versions: ApiVersions,
     platform_version: semver::Version,
+    server_time: Option<i64>,
+    capabilities: Option<Capabilities>,
 }
 
 impl Versions {
@@ -201,6 +256,8 @@ impl Versions {
         Self {
             api_versions: version_response.api_keys,
             platform_version: version_response.platform_version.to_semver(),
+            server_time: version_response.server_time.millis(),
+            capabilities: version_response.capabilities.get().cloned(),
         }
     }
 
@@ -212,6 +269,22 @@ impl Versions {
         &self.platform_version
     }
 
+    /// API keys and their version ranges supported by the server
+    pub fn api_versions(&self) -> &ApiVersions {
+        &self.api_versions
+    }
+
+    /// Optional features of server. None if server doesn't report them
+    pub fn capabilities(&self) -> Option<&Capabilities> {
+        self.capabilities.as_ref()
+    }
+
+    /// Server clock at connection time, in milliseconds since UNIX epoch.
+    /// None if server doesn't report it
+    pub fn server_time(&self) -> Option<i64> {
+        self.server_time
+    }
+
     /// Given an API key, it returns maximum compatible version. None if not found
     pub fn lookup_version<R: Request>(&self) -> Option<i16> {
         for version in &self.api_versions {
@@ -386,7 +459,7 @@ fn is_retryable(err: &SocketError) -> bool {
                 | ErrorKind::Interrupted
         ),
 
-        SocketError::SocketClosed | SocketError::SocketStale => false,
+        SocketError::SocketClosed | SocketError::SocketStale | SocketError::Rejected(_) => false,
     }
 }
 
@@ -23,7 +23,12 @@ use super::fetch_offset::FetchOffsetsRequest;
 use super::stream_fetch::FileStreamFetchRequest;
 use super::consumer_offset::{
     UpdateConsumerOffsetRequest, DeleteConsumerOffsetRequest, FetchConsumerOffsetsRequest,
+    ResetConsumerOffsetRequest,
 };
+use super::partition_stats::FetchPartitionStatsRequest;
+use super::replication_status::FetchReplicationStatusRequest;
+use super::delete_records::DeleteRecordsRequest;
+use super::spu_config::FetchSpuConfigRequest;
 use super::update_offset::UpdateOffsetsRequest;
 use super::mirror::StartMirrorRequest;
 
@@ -41,9 +46,36 @@ pub enum SpuServerRequest {
     UpdateConsumerOffsetRequest(RequestMessage<UpdateConsumerOffsetRequest>),
     DeleteConsumerOffsetRequest(RequestMessage<DeleteConsumerOffsetRequest>),
     FetchConsumerOffsetsRequest(RequestMessage<FetchConsumerOffsetsRequest>),
+    ResetConsumerOffsetRequest(RequestMessage<ResetConsumerOffsetRequest>),
+    FetchPartitionStatsRequest(RequestMessage<FetchPartitionStatsRequest>),
+    FetchReplicationStatusRequest(RequestMessage<FetchReplicationStatusRequest>),
+    DeleteRecordsRequest(RequestMessage<DeleteRecordsRequest>),
+    FetchSpuConfigRequest(RequestMessage<FetchSpuConfigRequest>),
     StartMirrorRequest(RequestMessage<StartMirrorRequest>),
 }
 
+impl SpuServerRequest {
+    pub fn header(&self) -> &RequestHeader {
+        match self {
+            Self::ApiVersionsRequest(request) => &request.header,
+            Self::ProduceRequest(request) => &request.header,
+            Self::FileFetchRequest(request) => &request.header,
+            Self::FetchOffsetsRequest(request) => &request.header,
+            Self::FileStreamFetchRequest(request) => &request.header,
+            Self::UpdateOffsetsRequest(request) => &request.header,
+            Self::UpdateConsumerOffsetRequest(request) => &request.header,
+            Self::DeleteConsumerOffsetRequest(request) => &request.header,
+            Self::FetchConsumerOffsetsRequest(request) => &request.header,
+            Self::ResetConsumerOffsetRequest(request) => &request.header,
+            Self::FetchPartitionStatsRequest(request) => &request.header,
+            Self::FetchReplicationStatusRequest(request) => &request.header,
+            Self::DeleteRecordsRequest(request) => &request.header,
+            Self::FetchSpuConfigRequest(request) => &request.header,
+            Self::StartMirrorRequest(request) => &request.header,
+        }
+    }
+}
+
 impl fmt::Display for SpuServerRequest {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -56,6 +88,11 @@ impl fmt::Display for SpuServerRequest {
             Self::UpdateConsumerOffsetRequest(_) => write!(f, "UpdateConsumerOffsetRequest"),
             Self::DeleteConsumerOffsetRequest(_) => write!(f, "DeleteConsumerOffsetRequest"),
             Self::FetchConsumerOffsetsRequest(_) => write!(f, "FetchConsumerOffsetsRequest"),
+            Self::ResetConsumerOffsetRequest(_) => write!(f, "ResetConsumerOffsetRequest"),
+            Self::FetchPartitionStatsRequest(_) => write!(f, "FetchPartitionStatsRequest"),
+            Self::FetchReplicationStatusRequest(_) => write!(f, "FetchReplicationStatusRequest"),
+            Self::DeleteRecordsRequest(_) => write!(f, "DeleteRecordsRequest"),
+            Self::FetchSpuConfigRequest(_) => write!(f, "FetchSpuConfigRequest"),
             Self::StartMirrorRequest(_) => write!(f, "StartMirrorRequest"),
         }
     }
@@ -98,6 +135,19 @@ impl ApiMessage for SpuServerRequest {
             SpuServerApiKey::FetchConsumerOffsets => {
                 api_decode!(Self, FetchConsumerOffsetsRequest, src, header)
             }
+            SpuServerApiKey::ResetConsumerOffset => {
+                api_decode!(Self, ResetConsumerOffsetRequest, src, header)
+            }
+            SpuServerApiKey::FetchPartitionStats => {
+                api_decode!(Self, FetchPartitionStatsRequest, src, header)
+            }
+            SpuServerApiKey::FetchReplicationStatus => {
+                api_decode!(Self, FetchReplicationStatusRequest, src, header)
+            }
+            SpuServerApiKey::DeleteRecords => api_decode!(Self, DeleteRecordsRequest, src, header),
+            SpuServerApiKey::FetchSpuConfig => {
+                api_decode!(Self, FetchSpuConfigRequest, src, header)
+            }
             SpuServerApiKey::StartMirror => api_decode!(Self, StartMirrorRequest, src, header),
         }
     }
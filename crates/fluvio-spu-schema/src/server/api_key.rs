@@ -23,6 +23,11 @@ pub enum SpuServerApiKey {
     UpdateConsumerOffset = 1006,
     DeleteConsumerOffset = 1007,
     FetchConsumerOffsets = 1008,
+    FetchPartitionStats = 1009,
+    DeleteRecords = 1010,
+    FetchSpuConfig = 1011,
+    ResetConsumerOffset = 1012,
+    FetchReplicationStatus = 1013,
 
     StartMirror = 2000,
 }
@@ -62,6 +62,40 @@ pub struct DeleteConsumerOffsetResponse {
     pub error_code: ErrorCode,
 }
 
+/// Set offset of consumer on replica, creating the consumer if it doesn't exist.
+/// Offset is of the last consumed record, consumer resumes reading after it
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct ResetConsumerOffsetRequest {
+    pub replica_id: ReplicaKey,
+    pub consumer_id: String,
+    pub offset: Offset,
+}
+
+impl ResetConsumerOffsetRequest {
+    pub fn new(
+        replica_id: impl Into<ReplicaKey>,
+        consumer_id: impl Into<String>,
+        offset: Offset,
+    ) -> Self {
+        Self {
+            replica_id: replica_id.into(),
+            consumer_id: consumer_id.into(),
+            offset,
+        }
+    }
+}
+
+impl Request for ResetConsumerOffsetRequest {
+    const API_KEY: u16 = SpuServerApiKey::ResetConsumerOffset as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = ResetConsumerOffsetResponse;
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct ResetConsumerOffsetResponse {
+    pub error_code: ErrorCode,
+}
+
 #[derive(Encoder, Decoder, Default, Debug)]
 pub struct FilterOpts {
     pub replica_id: Option<ReplicaKey>,
@@ -0,0 +1,62 @@
+//!
+//! # Delete Records
+//!
+//! API that allows admin to delete records of partition before an offset.
+//! Records are deleted on leader and its followers.
+//!
+use fluvio_protocol::api::Request;
+use fluvio_protocol::record::{Offset, ReplicaKey};
+use fluvio_protocol::{Encoder, Decoder};
+
+use crate::COMMON_VERSION;
+use crate::errors::ErrorCode;
+use super::SpuServerApiKey;
+
+#[derive(Decoder, Encoder, Default, Debug, Clone, PartialEq, Eq)]
+pub struct DeleteRecordsRequest {
+    pub replica_id: ReplicaKey,
+    /// records with offset lower than this are deleted, must not be above high watermark
+    pub before_offset: Offset,
+}
+
+impl DeleteRecordsRequest {
+    pub fn new(replica_id: impl Into<ReplicaKey>, before_offset: Offset) -> Self {
+        Self {
+            replica_id: replica_id.into(),
+            before_offset,
+        }
+    }
+}
+
+impl Request for DeleteRecordsRequest {
+    const API_KEY: u16 = SpuServerApiKey::DeleteRecords as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = DeleteRecordsResponse;
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct DeleteRecordsResponse {
+    pub error_code: ErrorCode,
+    /// first readable offset after deletion
+    pub log_start_offset: Offset,
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_encode_decode_delete_records() {
+        let request = DeleteRecordsRequest::new(("orders", 2), 150);
+        let mut dest = Vec::new();
+        request.encode(&mut dest, COMMON_VERSION).expect("encode");
+
+        let mut decoded = DeleteRecordsRequest::default();
+        decoded
+            .decode(&mut std::io::Cursor::new(dest), COMMON_VERSION)
+            .expect("decode");
+        assert_eq!(decoded, request);
+        assert_eq!(decoded.replica_id, ReplicaKey::new("orders", 2u32));
+    }
+}
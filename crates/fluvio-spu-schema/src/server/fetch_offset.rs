@@ -115,14 +115,30 @@ pub struct FetchOffsetPartitionResponse {
 
     /// Last readable offset
     pub last_stable_offset: i64,
+
+    /// Log end offset, including records not yet committed by replicas.
+    /// Older SPUs don't report it, see [`FetchOffsetPartitionResponse::end_offset`]
+    #[fluvio(min_version = 29)]
+    pub log_end_offset: i64,
+}
+
+impl FetchOffsetPartitionResponse {
+    /// offset after last record in the log, falls back to last stable offset for older SPUs
+    pub fn end_offset(&self) -> i64 {
+        self.log_end_offset.max(self.last_stable_offset)
+    }
 }
 
 impl fmt::Display for FetchOffsetPartitionResponse {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "error: {:#?}, partition: {}, start: {}, last: {}",
-            self.error_code, self.partition_index, self.start_offset, self.last_stable_offset
+            "error: {:#?}, partition: {}, start: {}, last: {}, end: {}",
+            self.error_code,
+            self.partition_index,
+            self.start_offset,
+            self.last_stable_offset,
+            self.log_end_offset
         )
     }
 }
@@ -136,3 +152,34 @@ impl PartitionOffset for FetchOffsetPartitionResponse {
         self.start_offset
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use fluvio_protocol::{Decoder, Encoder};
+
+    use super::FetchOffsetPartitionResponse;
+
+    #[test]
+    fn test_log_end_offset_versions() {
+        let response = FetchOffsetPartitionResponse {
+            start_offset: 1,
+            last_stable_offset: 5,
+            log_end_offset: 7,
+            ..Default::default()
+        };
+
+        let mut bytes = vec![];
+        response.encode(&mut bytes, 28).expect("encode");
+        let decoded =
+            FetchOffsetPartitionResponse::decode_from(&mut bytes.as_slice(), 28).expect("decode");
+        assert_eq!(decoded.log_end_offset, 0);
+        assert_eq!(decoded.end_offset(), 5);
+
+        let mut bytes = vec![];
+        response.encode(&mut bytes, 29).expect("encode");
+        let decoded =
+            FetchOffsetPartitionResponse::decode_from(&mut bytes.as_slice(), 29).expect("decode");
+        assert_eq!(decoded.end_offset(), 7);
+    }
+}
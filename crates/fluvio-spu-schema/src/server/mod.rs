@@ -7,6 +7,10 @@ pub mod fetch_offset;
 pub mod stream_fetch;
 pub mod update_offset;
 pub mod consumer_offset;
+pub mod partition_stats;
+pub mod replication_status;
+pub mod delete_records;
+pub mod spu_config;
 pub mod mirror;
 
 pub use self::api_key::*;
@@ -0,0 +1,115 @@
+//!
+//! # Fetch Partition Statistics
+//!
+//! API that allows CLI to fetch statistics of partition from its leader.
+//!
+use fluvio_protocol::api::Request;
+use fluvio_protocol::record::{Offset, ReplicaKey, NO_TIMESTAMP};
+use fluvio_protocol::{Encoder, Decoder};
+
+use crate::COMMON_VERSION;
+use crate::errors::ErrorCode;
+use super::SpuServerApiKey;
+
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct FetchPartitionStatsRequest {
+    pub replica_id: ReplicaKey,
+}
+
+impl FetchPartitionStatsRequest {
+    pub fn new(replica_id: impl Into<ReplicaKey>) -> Self {
+        Self {
+            replica_id: replica_id.into(),
+        }
+    }
+}
+
+impl Request for FetchPartitionStatsRequest {
+    const API_KEY: u16 = SpuServerApiKey::FetchPartitionStats as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = FetchPartitionStatsResponse;
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct FetchPartitionStatsResponse {
+    pub error_code: ErrorCode,
+    pub stats: PartitionStats,
+}
+
+/// Statistics of partition as seen by its leader.
+/// Traffic counters start from zero when SPU becomes leader of partition
+#[derive(Encoder, Decoder, Debug, Clone, PartialEq, Eq)]
+pub struct PartitionStats {
+    /// first readable offset
+    pub start_offset: Offset,
+    /// last readable offset
+    pub high_watermark: Offset,
+    /// bytes of partition in storage
+    pub size: u64,
+    /// timestamp of first readable record, NO_TIMESTAMP if unknown
+    pub earliest_timestamp: i64,
+    /// timestamp of last readable record, NO_TIMESTAMP if unknown
+    pub latest_timestamp: i64,
+    pub produced_records: u64,
+    pub produced_bytes: u64,
+    /// offsets sent to consumers
+    pub consumed_records: u64,
+    pub consumed_bytes: u64,
+    /// streams currently consuming from partition
+    pub active_consumers: u32,
+}
+
+impl Default for PartitionStats {
+    fn default() -> Self {
+        Self {
+            start_offset: 0,
+            high_watermark: 0,
+            size: 0,
+            earliest_timestamp: NO_TIMESTAMP,
+            latest_timestamp: NO_TIMESTAMP,
+            produced_records: 0,
+            produced_bytes: 0,
+            consumed_records: 0,
+            consumed_bytes: 0,
+            active_consumers: 0,
+        }
+    }
+}
+
+impl PartitionStats {
+    /// number of readable records
+    pub fn records(&self) -> u64 {
+        (self.high_watermark - self.start_offset).max(0) as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_encode_decode_partition_stats() {
+        let stats = PartitionStats {
+            start_offset: 10,
+            high_watermark: 25,
+            size: 4096,
+            earliest_timestamp: 1_000,
+            latest_timestamp: 2_000,
+            produced_records: 15,
+            produced_bytes: 300,
+            consumed_records: 30,
+            consumed_bytes: 600,
+            active_consumers: 2,
+        };
+        let mut dest = Vec::new();
+        stats.encode(&mut dest, COMMON_VERSION).expect("encode");
+
+        let mut decoded = PartitionStats::default();
+        decoded
+            .decode(&mut std::io::Cursor::new(dest), COMMON_VERSION)
+            .expect("decode");
+        assert_eq!(decoded, stats);
+        assert_eq!(decoded.records(), 15);
+    }
+}
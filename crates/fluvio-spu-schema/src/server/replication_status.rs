@@ -0,0 +1,144 @@
+//!
+//! # Fetch Replication Status
+//!
+//! API that allows CLI to fetch replication progress of followers from partition leader,
+//! to diagnose under-replicated partitions.
+//!
+use fluvio_protocol::api::Request;
+use fluvio_protocol::record::{Offset, ReplicaKey};
+use fluvio_protocol::{Encoder, Decoder};
+use fluvio_types::SpuId;
+
+use crate::COMMON_VERSION;
+use crate::errors::ErrorCode;
+use super::SpuServerApiKey;
+
+/// time fields with no value, as follower which never fetched
+pub const NO_TIME: i64 = -1;
+
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct FetchReplicationStatusRequest {
+    pub replica_id: ReplicaKey,
+}
+
+impl FetchReplicationStatusRequest {
+    pub fn new(replica_id: impl Into<ReplicaKey>) -> Self {
+        Self {
+            replica_id: replica_id.into(),
+        }
+    }
+}
+
+impl Request for FetchReplicationStatusRequest {
+    const API_KEY: u16 = SpuServerApiKey::FetchReplicationStatus as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = FetchReplicationStatusResponse;
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct FetchReplicationStatusResponse {
+    pub error_code: ErrorCode,
+    pub status: ReplicationStatus,
+}
+
+/// Replication state of partition as seen by its leader
+#[derive(Encoder, Decoder, Default, Debug, Clone, PartialEq, Eq)]
+pub struct ReplicationStatus {
+    pub leader: SpuId,
+    pub leader_epoch: i32,
+    pub leo: Offset,
+    pub hw: Offset,
+    /// replicas which must have a record before it is committed
+    pub in_sync_replica: u16,
+    pub followers: Vec<FollowerStatus>,
+}
+
+impl ReplicationStatus {
+    /// followers not in sync with leader
+    pub fn out_of_sync(&self) -> impl Iterator<Item = &FollowerStatus> {
+        self.followers.iter().filter(|follower| !follower.in_sync)
+    }
+}
+
+/// Progress of follower replicating from leader
+#[derive(Encoder, Decoder, Default, Debug, Clone, PartialEq, Eq)]
+pub struct FollowerStatus {
+    pub spu: SpuId,
+    pub leo: Offset,
+    pub hw: Offset,
+    /// records of leader follower doesn't have
+    pub lag_records: i64,
+    /// milliseconds since follower last had all records of leader, NO_TIME if never
+    pub lag_ms: i64,
+    /// milliseconds since follower last reported its offsets, NO_TIME if never
+    pub last_fetch_ms: i64,
+    /// follower has all committed records
+    pub in_sync: bool,
+    /// latest changes of in sync state, oldest first
+    pub transitions: Vec<SyncTransition>,
+}
+
+/// Change of follower in sync state
+#[derive(Encoder, Decoder, Default, Debug, Clone, PartialEq, Eq)]
+pub struct SyncTransition {
+    /// milliseconds since epoch
+    pub timestamp: i64,
+    pub in_sync: bool,
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_encode_decode_replication_status() {
+        let status = ReplicationStatus {
+            leader: 5001,
+            leader_epoch: 2,
+            leo: 100,
+            hw: 90,
+            in_sync_replica: 2,
+            followers: vec![
+                FollowerStatus {
+                    spu: 5002,
+                    leo: 100,
+                    hw: 90,
+                    lag_records: 0,
+                    lag_ms: 0,
+                    last_fetch_ms: 20,
+                    in_sync: true,
+                    transitions: vec![],
+                },
+                FollowerStatus {
+                    spu: 5003,
+                    leo: 40,
+                    hw: 40,
+                    lag_records: 60,
+                    lag_ms: 30_000,
+                    last_fetch_ms: NO_TIME,
+                    in_sync: false,
+                    transitions: vec![SyncTransition {
+                        timestamp: 1_000,
+                        in_sync: false,
+                    }],
+                },
+            ],
+        };
+        let mut dest = Vec::new();
+        status.encode(&mut dest, COMMON_VERSION).expect("encode");
+
+        let mut decoded = ReplicationStatus::default();
+        decoded
+            .decode(&mut std::io::Cursor::new(dest), COMMON_VERSION)
+            .expect("decode");
+        assert_eq!(decoded, status);
+        assert_eq!(
+            decoded
+                .out_of_sync()
+                .map(|follower| follower.spu)
+                .collect::<Vec<_>>(),
+            vec![5003]
+        );
+    }
+}
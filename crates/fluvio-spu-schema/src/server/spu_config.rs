@@ -0,0 +1,50 @@
+//!
+//! # Fetch SPU config
+//!
+//! API to retrieve configuration in effect on SPU, after defaults, environment, flags
+//! and runtime overrides are applied.
+//!
+use fluvio_protocol::api::Request;
+use fluvio_protocol::{Encoder, Decoder};
+
+use crate::COMMON_VERSION;
+use crate::errors::ErrorCode;
+use super::SpuServerApiKey;
+
+#[derive(Decoder, Encoder, Default, Debug, Clone, PartialEq, Eq)]
+pub struct FetchSpuConfigRequest {}
+
+impl Request for FetchSpuConfigRequest {
+    const API_KEY: u16 = SpuServerApiKey::FetchSpuConfig as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = FetchSpuConfigResponse;
+}
+
+#[derive(Encoder, Decoder, Default, Debug, Clone, PartialEq)]
+pub struct FetchSpuConfigResponse {
+    pub error_code: ErrorCode,
+    /// effective configuration as JSON
+    pub config: String,
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_encode_decode_spu_config_response() {
+        let response = FetchSpuConfigResponse {
+            error_code: ErrorCode::None,
+            config: r#"{"id":5001}"#.to_owned(),
+        };
+        let mut dest = Vec::new();
+        response.encode(&mut dest, COMMON_VERSION).expect("encode");
+
+        let mut decoded = FetchSpuConfigResponse::default();
+        decoded
+            .decode(&mut std::io::Cursor::new(dest), COMMON_VERSION)
+            .expect("decode");
+        assert_eq!(decoded, response);
+    }
+}
@@ -9,7 +9,7 @@ use std::marker::PhantomData;
 use educe::Educe;
 use derive_builder::Builder;
 
-use fluvio_protocol::record::RawRecords;
+use fluvio_protocol::record::{NO_TIMESTAMP, RawRecords};
 use fluvio_protocol::{Encoder, Decoder};
 use fluvio_protocol::api::Request;
 use fluvio_protocol::record::RecordSet;
@@ -54,6 +54,30 @@ pub const SMARTMODULE_TIMESTAMP: i16 = 22;
 
 pub const OFFSET_MANAGEMENT_API: i16 = 23;
 
+// version for credit based flow control
+pub const FLOW_CONTROL_API: i16 = 26;
+
+// version for decompressing batches on SPU
+pub const SERVER_DECOMPRESS_API: i16 = 27;
+
+// version for record sampling on SPU
+pub const SAMPLING_API: i16 = 28;
+
+// version for batch bounds evaluated on SPU
+pub const BATCH_BOUNDS_API: i16 = 30;
+
+// version for bypassing read cache of SPU
+pub const BYPASS_CACHE_API: i16 = 31;
+
+/// sampling ratio is expressed in basis points
+pub const SAMPLING_RATIO_SCALE: u16 = 10_000;
+
+/// responses SPU can send ahead of consumer acknowledgement by default
+pub const DEFAULT_STREAM_CREDITS: u32 = 1;
+
+/// max credits, bounded by client stream queue so a slow stream can't block its connection
+pub const MAX_STREAM_CREDITS: u32 = 10;
+
 /// Fetch records continuously
 /// Output will be send back as stream
 #[allow(deprecated)]
@@ -87,6 +111,27 @@ pub struct StreamFetchRequest<R> {
     #[builder(default)]
     #[fluvio(min_version = 23)]
     pub consumer_id: Option<String>,
+    /// number of responses which can be in flight before SPU waits for acknowledgement
+    #[builder(default = "DEFAULT_STREAM_CREDITS")]
+    #[fluvio(min_version = 26)]
+    pub credits: u32,
+    /// SPU sends uncompressed batches, for consumers which can't decompress topic codec.
+    /// Otherwise batches are sent as stored unless SmartModule is applied.
+    #[builder(default)]
+    #[fluvio(min_version = 27)]
+    pub server_decompress: bool,
+    /// SPU sends only sampled records, applied after SmartModules
+    #[builder(default)]
+    #[fluvio(min_version = 28)]
+    pub sampling: RecordSampling,
+    /// SPU skips batches without records in bounds, applied before SmartModules
+    #[builder(default)]
+    #[fluvio(min_version = 30)]
+    pub bounds: BatchBounds,
+    /// SPU reads records from segments instead of read cache, for large scans
+    #[builder(default)]
+    #[fluvio(min_version = 31)]
+    pub bypass_cache: bool,
     #[builder(setter(skip))]
     data: PhantomData<R>,
 }
@@ -109,6 +154,92 @@ where
     type Response = StreamFetchResponse<R>;
 }
 
+/// Selects records sent to consumer by their offset, so the same records are selected on every read
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Encoder, Decoder)]
+pub enum RecordSampling {
+    /// all records are sent
+    #[default]
+    #[fluvio(tag = 0)]
+    None,
+    /// every n-th record, starting at offset 0
+    #[fluvio(tag = 1)]
+    Every(u32),
+    /// fraction of records in basis points, 100 selects 1% of records
+    #[fluvio(tag = 2)]
+    Ratio(u16),
+}
+
+impl RecordSampling {
+    pub fn is_enabled(&self) -> bool {
+        !matches!(self, Self::None)
+    }
+
+    /// true if record at `offset` is sent to consumer
+    pub fn selects(&self, offset: i64) -> bool {
+        match self {
+            Self::None | Self::Every(0) => true,
+            Self::Every(n) => offset.rem_euclid(*n as i64) == 0,
+            Self::Ratio(ratio) => {
+                (mix(offset as u64) % SAMPLING_RATIO_SCALE as u64) < *ratio as u64
+            }
+        }
+    }
+}
+
+/// Bounds of records sent to consumer, evaluated by SPU on batch headers.
+/// Batches with any record in bounds are sent whole, so consumers check records themselves
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Encoder, Decoder)]
+pub struct BatchBounds {
+    /// min timestamp of records, in milliseconds since epoch
+    pub min_timestamp: Option<i64>,
+    /// max timestamp of records, in milliseconds since epoch
+    pub max_timestamp: Option<i64>,
+    /// max offset of records, inclusive
+    pub max_offset: Option<i64>,
+}
+
+impl BatchBounds {
+    pub fn is_enabled(&self) -> bool {
+        self.min_timestamp.is_some() || self.max_timestamp.is_some() || self.max_offset.is_some()
+    }
+
+    /// true if batch may have records in bounds, or is past bounds.
+    /// Batch starting after max timestamp is sent so consumer ends stream on it.
+    /// Timestamps of batches written without them are unknown, so they are kept
+    pub fn selects(&self, base_offset: i64, first_timestamp: i64, max_timestamp: i64) -> bool {
+        if self.max_offset.is_some_and(|max| base_offset > max) {
+            return false;
+        }
+        first_timestamp == NO_TIMESTAMP
+            || self.is_past(first_timestamp)
+            || self.min_timestamp.is_none_or(|min| max_timestamp >= min)
+    }
+
+    /// true if batch starts after max timestamp, later batches are out of bounds too
+    pub fn is_past(&self, first_timestamp: i64) -> bool {
+        first_timestamp != NO_TIMESTAMP
+            && self.max_timestamp.is_some_and(|max| first_timestamp > max)
+    }
+
+    /// true if record is in bounds, records without timestamp are only checked by offset
+    pub fn contains(&self, offset: i64, timestamp: i64) -> bool {
+        if self.max_offset.is_some_and(|max| offset > max) {
+            return false;
+        }
+        timestamp == NO_TIMESTAMP
+            || (self.min_timestamp.is_none_or(|min| timestamp >= min)
+                && self.max_timestamp.is_none_or(|max| timestamp <= max))
+    }
+}
+
+/// spreads consecutive offsets evenly, splitmix64 finalizer
+fn mix(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
 #[derive(Debug, Default, Clone, Encoder, Decoder)]
 pub(crate) struct DerivedStreamInvocation {
     pub stream: String,
@@ -164,7 +295,9 @@ mod tests {
 
     use fluvio_smartmodule::dataplane::smartmodule::Lookback;
 
-    use crate::server::smartmodule::{SmartModuleInvocationWasm, SmartModuleKind};
+    use crate::server::smartmodule::{
+        COMMON_VERSION_HAS_SM_NAME, SmartModuleInvocationWasm, SmartModuleKind,
+    };
 
     use super::*;
 
@@ -230,7 +363,8 @@ mod tests {
             0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x00,
             0x00, 0x00, 0x04, 0xde, 0xad, 0xbe, 0xef, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
             0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x01, 0, 10, 116, 101, 115, 116, 45, 97, 100, 104,
-            111, 99, 0,
+            111, 99, 0, // credits, server decompress, sampling, bounds, bypass cache
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
         ];
         assert_eq!(dest, expected);
     }
@@ -255,7 +389,7 @@ mod tests {
             ..Default::default()
         };
         value
-            .encode(&mut dest, DefaultStreamFetchRequest::MAX_API_VERSION - 1)
+            .encode(&mut dest, COMMON_VERSION_HAS_SM_NAME - 1)
             .expect("should encode");
         let expected = vec![
             // Pre sm name encoding
@@ -307,7 +441,8 @@ mod tests {
             0x00, 0x03, 0x6f, 0x6e, 0x65, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00,
             0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x00,
             0x00, 0x00, 0x04, 0xde, 0xad, 0xbe, 0xef, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
-            0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x02,
+            0x00, 0x64, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0xe8, 0x00, 0x00, 0x01,
         ];
         let mut value = DefaultStreamFetchRequest::default();
         value
@@ -330,6 +465,12 @@ mod tests {
         };
         assert_eq!(wasm, vec![0xde, 0xad, 0xbe, 0xef]);
         assert!(matches!(sm.kind, SmartModuleKind::Filter));
+        assert_eq!(value.credits, 1);
+        assert!(value.server_decompress);
+        assert_eq!(value.sampling, RecordSampling::Ratio(100));
+        assert_eq!(value.bounds.min_timestamp, Some(1000));
+        assert_eq!(value.bounds.max_timestamp, None);
+        assert!(value.bypass_cache);
     }
 
     #[test]
@@ -344,7 +485,7 @@ mod tests {
         value
             .decode(
                 &mut std::io::Cursor::new(bytes),
-                DefaultStreamFetchRequest::MAX_API_VERSION - 1,
+                COMMON_VERSION_HAS_SM_NAME - 1,
             )
             .unwrap();
         assert_eq!(value.topic, "one");
@@ -373,4 +514,51 @@ mod tests {
         let uncompressed = compressed.into_raw().expect("decompression failed");
         assert_eq!(orig, uncompressed);
     }
+
+    #[test]
+    fn test_sampling_every() {
+        let sampling = RecordSampling::Every(3);
+        let selected: Vec<i64> = (0..10).filter(|offset| sampling.selects(*offset)).collect();
+        assert_eq!(selected, vec![0, 3, 6, 9]);
+        assert!((0..10).all(|offset| RecordSampling::None.selects(offset)));
+    }
+
+    #[test]
+    fn test_batch_bounds() {
+        let bounds = BatchBounds {
+            min_timestamp: Some(1_000),
+            max_timestamp: Some(2_000),
+            max_offset: Some(100),
+        };
+        assert!(bounds.selects(0, 500, 1_000));
+        assert!(bounds.selects(0, 1_500, 2_500));
+        assert!(!bounds.selects(0, 500, 999));
+        // batch past bounds ends stream
+        assert!(bounds.selects(0, 2_001, 3_000));
+        assert!(bounds.is_past(2_001));
+        assert!(!bounds.is_past(2_000));
+        assert!(!bounds.is_past(NO_TIMESTAMP));
+        assert!(!bounds.selects(101, 1_500, 1_500));
+        assert!(bounds.selects(0, NO_TIMESTAMP, NO_TIMESTAMP));
+
+        assert!(bounds.contains(100, 1_000));
+        assert!(!bounds.contains(101, 1_000));
+        assert!(!bounds.contains(0, 999));
+        assert!(bounds.contains(0, NO_TIMESTAMP));
+        assert!(!BatchBounds::default().is_enabled());
+        assert!(BatchBounds::default().selects(1_000, 0, 0));
+    }
+
+    #[test]
+    fn test_sampling_ratio() {
+        let sampling = RecordSampling::Ratio(100);
+        let selected = (0..100_000)
+            .filter(|offset| sampling.selects(*offset))
+            .count();
+        assert!((800..1200).contains(&selected), "selected {selected}");
+        assert!(
+            (0..1000).all(|offset| RecordSampling::Ratio(SAMPLING_RATIO_SCALE).selects(offset))
+        );
+        assert!(!(0..1000).any(|offset| RecordSampling::Ratio(0).selects(offset)));
+    }
 }
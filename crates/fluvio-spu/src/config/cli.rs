@@ -9,12 +9,17 @@ use std::process;
 use anyhow::{anyhow, Result};
 use tracing::debug;
 use tracing::info;
-use clap::Parser;
+use clap::{Args, Parser, Subcommand};
 
 use fluvio_types::print_cli_err;
 use fluvio_types::SpuId;
 use fluvio_future::rust_tls::TlsAcceptor;
 use fluvio_types::defaults::SPU_PEER_MAX_BYTES;
+use fluvio_types::defaults::CONSUMER_OFFSET_TTL_SECONDS;
+use fluvio_socket::SocketTuning;
+use fluvio_service::limits::ConnectionLimits;
+use fluvio_service::ip_filter::IpFilter;
+use fluvio_storage::cache::BatchCache;
 
 use super::SpuConfig;
 
@@ -34,6 +39,10 @@ pub struct SpuOpt {
     /// Spu server for internal cluster communication
     pub bind_private: Option<String>,
 
+    /// Address for HTTP liveness (/healthz) and readiness (/readyz) probes
+    #[arg(long, value_name = "host:port", env = "FLV_SPU_BIND_HEALTH")]
+    pub bind_health: Option<String>,
+
     /// Address of the SC Server
     #[arg(long, value_name = "host:port", env = "FLV_SC_PRIVATE_HOST")]
     pub sc_addr: Option<String>,
@@ -50,6 +59,22 @@ pub struct SpuOpt {
     #[arg(long, value_name = "integer", env = "FLV_LOG_INDEX_MAX_INTERVAL_BYTES")]
     pub index_max_interval_bytes: Option<u32>,
 
+    /// Milliseconds to wait for more produce requests to the same partition
+    /// so they are appended in a single write, 0 disables coalescing
+    #[arg(long, value_name = "integer", env = "FLV_WRITE_LINGER_MS")]
+    pub write_linger_ms: Option<u32>,
+
+    /// Object store url for tiered storage, ex: `s3://bucket/prefix` or `file:///path`.
+    /// Segments of topics with local retention are offloaded there.
+    /// S3 credentials and endpoint (MinIO) are read from AWS_* environment variables
+    #[arg(long, value_name = "url", env = "FLV_TIERED_STORAGE")]
+    pub tiered_storage: Option<String>,
+
+    /// Memory for recently written batches, shared by all partitions of SPU.
+    /// Consumers reading at the tail of partitions are served from memory, 0 disables the cache
+    #[arg(long, value_name = "integer", env = "FLV_READ_CACHE_MAX_BYTES")]
+    pub read_cache_max_bytes: Option<u64>,
+
     /// max bytes to transfer between leader and follower
     #[arg(
         long,
@@ -66,8 +91,73 @@ pub struct SpuOpt {
     )]
     pub smart_engine_max_memory: Option<usize>,
 
+    /// Seconds after which offsets of consumers that stopped committing are removed,
+    /// 0 keeps them forever
+    #[arg(
+        long,
+        value_name = "integer",
+        env = "FLV_CONSUMER_OFFSET_TTL_SECS",
+        default_value_t = CONSUMER_OFFSET_TTL_SECONDS
+    )]
+    pub consumer_offset_ttl_secs: u64,
+
+    /// Run with the minimal profile for edge devices: disables the SmartModule engine,
+    /// reduces buffers and worker threads and caps memory
+    #[arg(long, env = "FLV_SPU_MINIMAL")]
+    pub minimal: bool,
+
+    /// Log every request to the public service with client identity, topic and latency.
+    /// Can be changed at runtime with SPU runtime config
+    #[arg(long, env = "FLV_SPU_ACCESS_LOG")]
+    pub access_log: bool,
+
+    /// Max bytes per second produced by each client connection, requests over it are delayed.
+    /// Can be changed at runtime with SPU runtime config
+    #[arg(long, value_name = "integer", env = "FLV_SPU_PRODUCE_BYTE_RATE")]
+    pub produce_byte_rate: Option<u32>,
+
+    /// Max bytes per second fetched by each client connection, responses over it are delayed.
+    /// Can be changed at runtime with SPU runtime config
+    #[arg(long, value_name = "integer", env = "FLV_SPU_FETCH_BYTE_RATE")]
+    pub fetch_byte_rate: Option<u32>,
+
     #[clap(flatten)]
     tls: TlsConfig,
+
+    #[clap(flatten)]
+    socket: SocketOpt,
+
+    #[clap(flatten)]
+    connection_limits: ConnectionLimitOpt,
+
+    #[clap(flatten)]
+    ip_filter: IpFilterOpt,
+
+    /// Print resolved configuration as JSON, after defaults, environment and flags, and exit
+    #[arg(long)]
+    pub dry_run: bool,
+
+    #[command(subcommand)]
+    pub command: Option<SpuCommand>,
+}
+
+/// Maintenance commands, run while SPU is stopped
+#[derive(Debug, Subcommand)]
+pub enum SpuCommand {
+    /// Regenerate offset indexes of a partition from its log segments,
+    /// reporting discrepancies found
+    RebuildIndex(RebuildIndexOpt),
+}
+
+#[derive(Debug, Args)]
+pub struct RebuildIndexOpt {
+    /// Partition to rebuild, ex: `topic-0`
+    #[arg(long, value_name = "topic-partition")]
+    pub partition: String,
+
+    /// Only report discrepancies, without writing indexes
+    #[arg(long)]
+    pub dry_run: bool,
 }
 
 impl SpuOpt {
@@ -119,6 +209,21 @@ impl SpuOpt {
             config.log.index_max_interval_bytes = index_max_interval_bytes;
         }
 
+        if let Some(write_linger_ms) = self.write_linger_ms {
+            info!("overriding write linger: {}ms", write_linger_ms);
+            config.log.write_linger_ms = write_linger_ms;
+        }
+
+        if let Some(tiered_storage) = self.tiered_storage {
+            info!("using tiered storage: {}", tiered_storage);
+            config.log.tiered_storage = Some(tiered_storage);
+        }
+
+        if let Some(read_cache_max_bytes) = self.read_cache_max_bytes {
+            info!("overriding read cache max bytes: {}", read_cache_max_bytes);
+            config.log.read_cache = BatchCache::shared(read_cache_max_bytes);
+        }
+
         if let Some(public_addr) = self.bind_public {
             info!("overriding public addr: {}", public_addr);
             config.public_endpoint = public_addr;
@@ -134,6 +239,7 @@ impl SpuOpt {
                 .tls
                 .bind_non_tls_public
                 .ok_or_else(|| anyhow!("non tls addr for public must be specified"))?;
+            config.admin_principals = self.tls.admin_principals;
         }
 
         if let Some(private_addr) = self.bind_private {
@@ -141,7 +247,15 @@ impl SpuOpt {
             config.private_endpoint = private_addr;
         }
 
+        config.health_endpoint = self.bind_health;
         config.peer_max_bytes = self.peer_max_bytes;
+        config.consumer_offset_ttl_secs = self.consumer_offset_ttl_secs;
+        config.socket = self.socket.tuning();
+        config.connection_limits = self.connection_limits.limits();
+        config.ip_filter = IpFilter::new(&self.ip_filter.ip_allow, &self.ip_filter.ip_deny)?;
+        config.access_log = self.access_log;
+        config.produce_byte_rate = self.produce_byte_rate;
+        config.fetch_byte_rate = self.fetch_byte_rate;
 
         if let Some(smart_engine_max_memory) = self.smart_engine_max_memory {
             info!(
@@ -151,6 +265,11 @@ impl SpuOpt {
             config.smart_engine.store_max_memory = smart_engine_max_memory;
         }
 
+        if self.minimal {
+            info!("using minimal runtime profile");
+            config.apply_minimal_profile();
+        }
+
         Ok((config, tls_port))
     }
 
@@ -238,7 +357,72 @@ fn find_spu_id_from_env() -> Result<SpuId> {
     }
 }
 
-/// same in the SC
+/// TCP options applied to public and internal server connections
+/// and to follower connections to leaders, unset options keep OS defaults
+#[derive(Debug, Args, Default)]
+struct SocketOpt {
+    /// enable or disable TCP_NODELAY
+    #[arg(long, value_name = "bool", env = "FLV_TCP_NODELAY")]
+    tcp_nodelay: Option<bool>,
+
+    /// socket send buffer size in bytes
+    #[arg(long, value_name = "bytes", env = "FLV_SOCKET_SEND_BUFFER_BYTES")]
+    socket_send_buffer_bytes: Option<usize>,
+
+    /// socket receive buffer size in bytes
+    #[arg(long, value_name = "bytes", env = "FLV_SOCKET_RECV_BUFFER_BYTES")]
+    socket_recv_buffer_bytes: Option<usize>,
+}
+
+impl SocketOpt {
+    fn tuning(&self) -> SocketTuning {
+        SocketTuning {
+            nodelay: self.tcp_nodelay,
+            send_buffer_size: self.socket_send_buffer_bytes,
+            recv_buffer_size: self.socket_recv_buffer_bytes,
+        }
+    }
+}
+
+/// Limits of concurrent connections to public service, unset means unlimited.
+/// Behind TLS proxy, connections are counted from client address passed by proxy.
+#[derive(Debug, Args, Default)]
+struct ConnectionLimitOpt {
+    /// max concurrent connections from a source IP
+    #[arg(long, value_name = "integer", env = "FLV_MAX_CONNECTIONS_PER_IP")]
+    max_connections_per_ip: Option<u32>,
+
+    /// max concurrent connections of an authenticated principal
+    #[arg(
+        long,
+        value_name = "integer",
+        env = "FLV_MAX_CONNECTIONS_PER_PRINCIPAL"
+    )]
+    max_connections_per_principal: Option<u32>,
+}
+
+impl ConnectionLimitOpt {
+    fn limits(&self) -> ConnectionLimits {
+        ConnectionLimits {
+            max_per_ip: self.max_connections_per_ip,
+            max_per_principal: self.max_connections_per_principal,
+        }
+    }
+}
+
+/// Networks accepted by public service, in CIDR notation.
+/// Can be changed at runtime with SPU runtime config
+#[derive(Debug, Args, Default)]
+struct IpFilterOpt {
+    /// only accept connections from these networks
+    #[arg(long, value_name = "cidr", value_delimiter = ',', env = "FLV_IP_ALLOW")]
+    ip_allow: Vec<String>,
+
+    /// reject connections from these networks, takes precedence over allow list
+    #[arg(long, value_name = "cidr", value_delimiter = ',', env = "FLV_IP_DENY")]
+    ip_deny: Vec<String>,
+}
+
 #[derive(Debug, Parser, Default)]
 struct TlsConfig {
     /// enable tls
@@ -261,4 +445,13 @@ struct TlsConfig {
     #[arg(long)]
     /// TLS: address of non tls public service, required
     pub bind_non_tls_public: Option<String>,
+
+    /// TLS: principals of client certificates allowed to read SPU configuration
+    #[arg(
+        long,
+        value_name = "principal",
+        value_delimiter = ',',
+        env = "FLV_SPU_ADMIN_PRINCIPALS"
+    )]
+    pub admin_principals: Vec<String>,
 }
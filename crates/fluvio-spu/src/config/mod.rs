@@ -1,6 +1,6 @@
 mod cli;
 mod spu_config;
 
-pub use self::cli::SpuOpt;
+pub use self::cli::{SpuOpt, SpuCommand, RebuildIndexOpt};
 
 pub use self::spu_config::{SpuConfig, ReplicationConfig};
@@ -11,10 +11,14 @@
 use std::env;
 use std::path::PathBuf;
 
+use serde_json::{Value, json};
+use tracing::warn;
+
 // defaults values
 use fluvio_types::defaults::SPU_PUBLIC_PORT;
 use fluvio_types::defaults::SPU_PRIVATE_PORT;
 use fluvio_types::defaults::SC_PRIVATE_PORT;
+use fluvio_types::defaults::CONSUMER_OFFSET_TTL_SECONDS;
 use fluvio_types::defaults::SPU_LOG_BASE_DIR;
 use fluvio_types::defaults::SPU_LOG_SIZE;
 use fluvio_types::defaults::SPU_LOG_INDEX_MAX_BYTES;
@@ -22,6 +26,7 @@ use fluvio_types::defaults::SPU_LOG_INDEX_MAX_INTERVAL_BYTES;
 use fluvio_types::defaults::SPU_LOG_SEGMENT_MAX_BYTES;
 use fluvio_types::defaults::SPU_RETRY_SC_TIMEOUT_MS;
 use fluvio_types::defaults::SPU_SMARTENGINE_STORE_MAX_BYTES;
+use fluvio_types::defaults::SPU_READ_CACHE_MAX_BYTES;
 
 // environment variables
 
@@ -30,6 +35,11 @@ use fluvio_types::defaults::FLV_LOG_BASE_DIR;
 use fluvio_types::defaults::FLV_LOG_SIZE;
 use fluvio_types::SpuId;
 use fluvio_storage::config::ReplicaConfig;
+use fluvio_storage::cache::{BatchCache, SharedBatchCache};
+use fluvio_controlplane_metadata::spu::SpuRuntimeConfig;
+use fluvio_socket::SocketTuning;
+use fluvio_service::limits::ConnectionLimits;
+use fluvio_service::ip_filter::{IpCidr, IpFilter};
 use fluvio_types::defaults::{
     STORAGE_FLUSH_IDLE_MSEC, STORAGE_FLUSH_WRITE_COUNT, STORAGE_MAX_BATCH_SIZE,
 };
@@ -57,6 +67,12 @@ pub struct Log {
     pub flush_write_count: u32,
     pub flush_idle_msec: u32,
     pub max_batch_size: u32,
+    /// time to wait for more produce requests to the same partition before appending, 0 disables coalescing
+    pub write_linger_ms: u32,
+    /// url of object store where segments of topics with local retention are offloaded
+    pub tiered_storage: Option<String>,
+    /// recently written batches, shared by replicas of SPU
+    pub read_cache: SharedBatchCache,
 }
 
 impl Default for Log {
@@ -72,23 +88,67 @@ impl Default for Log {
             flush_write_count: STORAGE_FLUSH_WRITE_COUNT,
             flush_idle_msec: STORAGE_FLUSH_IDLE_MSEC,
             max_batch_size: STORAGE_MAX_BATCH_SIZE,
+            write_linger_ms: 0,
+            tiered_storage: None,
+            read_cache: BatchCache::shared(SPU_READ_CACHE_MAX_BYTES),
         }
     }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct SmartEngineConfig {
+    pub enabled: bool,
     pub store_max_memory: usize,
 }
 
 impl Default for SmartEngineConfig {
     fn default() -> Self {
         Self {
+            enabled: true,
             store_max_memory: SPU_SMARTENGINE_STORE_MAX_BYTES,
         }
     }
 }
 
+// limits used by the minimal profile
+const MINIMAL_PEER_MAX_BYTES: u32 = 1_048_576; //1mb
+const MINIMAL_MAX_BATCH_SIZE: u32 = 262_144; //256kb
+const MINIMAL_SMARTENGINE_STORE_MAX_BYTES: usize = 16_777_216; //16mb
+const MINIMAL_READ_CACHE_MAX_BYTES: u64 = 16_777_216; //16mb
+
+/// Runtime profile of the SPU
+#[derive(Debug, Default, Eq, PartialEq, Clone, Copy)]
+pub enum RuntimeProfile {
+    #[default]
+    Standard,
+    /// For Raspberry Pi-class edge devices: no SmartModule engine,
+    /// smaller buffers and fewer worker threads
+    Minimal,
+}
+
+impl RuntimeProfile {
+    /// number of executor threads to use, `None` lets the runtime decide
+    pub fn worker_threads(&self) -> Option<usize> {
+        match self {
+            Self::Standard => None,
+            Self::Minimal => Some(1),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Standard => "standard",
+            Self::Minimal => "minimal",
+        }
+    }
+}
+
+impl std::fmt::Display for RuntimeProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 /// streaming processing unit configuration file
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct SpuConfig {
@@ -111,6 +171,35 @@ pub struct SpuConfig {
     pub peer_max_bytes: u32,
 
     pub smart_engine: SmartEngineConfig,
+
+    pub profile: RuntimeProfile,
+
+    /// TCP options for public and private servers and connections to leaders
+    pub socket: SocketTuning,
+
+    /// address of HTTP server for liveness and readiness probes, disabled if not set
+    pub health_endpoint: Option<String>,
+
+    /// seconds after which offsets of inactive consumers expire, 0 disables expiry
+    pub consumer_offset_ttl_secs: u64,
+
+    /// log every request of public service
+    pub access_log: bool,
+
+    /// max bytes per second produced by each client connection
+    pub produce_byte_rate: Option<u32>,
+
+    /// max bytes per second fetched by each client connection
+    pub fetch_byte_rate: Option<u32>,
+
+    /// max concurrent connections to public service per source IP and principal
+    pub connection_limits: ConnectionLimits,
+
+    /// networks accepted by public service
+    pub ip_filter: IpFilter,
+
+    /// principals allowed to administer SPU, when clients are authenticated by TLS proxy
+    pub admin_principals: Vec<String>,
 }
 
 impl Default for SpuConfig {
@@ -126,6 +215,16 @@ impl Default for SpuConfig {
             log: Log::default(),
             peer_max_bytes: fluvio_storage::FileReplica::PREFER_MAX_LEN,
             smart_engine: SmartEngineConfig::default(),
+            profile: RuntimeProfile::default(),
+            socket: SocketTuning::default(),
+            health_endpoint: None,
+            consumer_offset_ttl_secs: CONSUMER_OFFSET_TTL_SECONDS,
+            access_log: false,
+            produce_byte_rate: None,
+            fetch_byte_rate: None,
+            connection_limits: ConnectionLimits::default(),
+            ip_filter: IpFilter::default(),
+            admin_principals: vec![],
         }
     }
 }
@@ -161,6 +260,126 @@ impl SpuConfig {
     pub fn storage(&self) -> &Log {
         &self.log
     }
+
+    /// Returns copy of the config with runtime overrides applied
+    pub fn with_runtime_config(&self, runtime: &SpuRuntimeConfig) -> Self {
+        let mut config = self.clone();
+        if let Some(flush_write_count) = runtime.flush_write_count {
+            config.log.flush_write_count = flush_write_count;
+        }
+        if let Some(flush_idle_msec) = runtime.flush_idle_msec {
+            config.log.flush_idle_msec = flush_idle_msec;
+        }
+        if let Some(peer_max_bytes) = runtime.peer_max_bytes {
+            config.peer_max_bytes = peer_max_bytes;
+        }
+        if let Some(access_log) = runtime.access_log {
+            config.access_log = access_log;
+        }
+        // 0 removes quota set at startup
+        if let Some(rate) = runtime.produce_byte_rate {
+            config.produce_byte_rate = (rate > 0).then_some(rate);
+        }
+        if let Some(rate) = runtime.fetch_byte_rate {
+            config.fetch_byte_rate = (rate > 0).then_some(rate);
+        }
+        if runtime.ip_allow.is_some() || runtime.ip_deny.is_some() {
+            config.ip_filter = IpFilter::from_networks(
+                runtime_networks(&runtime.ip_allow, self.ip_filter.allow()),
+                runtime_networks(&runtime.ip_deny, self.ip_filter.deny()),
+            );
+        }
+        config
+    }
+
+    /// Resolved configuration as JSON, to check which values are in effect
+    pub fn to_json(&self) -> Value {
+        let networks = |networks: &[IpCidr]| -> Vec<String> {
+            networks.iter().map(ToString::to_string).collect()
+        };
+        json!({
+            "id": self.id,
+            "rack": self.rack,
+            "public_endpoint": self.public_endpoint,
+            "private_endpoint": self.private_endpoint,
+            "sc_endpoint": self.sc_endpoint,
+            "sc_retry_ms": self.sc_retry_ms,
+            "replication": {
+                "min_in_sync_replicas": self.replication.min_in_sync_replicas,
+            },
+            "log": {
+                "base_dir": self.log.base_dir,
+                "size": self.log.size,
+                "index_max_bytes": self.log.index_max_bytes,
+                "index_max_interval_bytes": self.log.index_max_interval_bytes,
+                "segment_max_bytes": self.log.segment_max_bytes,
+                "flush_write_count": self.log.flush_write_count,
+                "flush_idle_msec": self.log.flush_idle_msec,
+                "max_batch_size": self.log.max_batch_size,
+                "write_linger_ms": self.log.write_linger_ms,
+                "read_cache_max_bytes": self.log.read_cache.max_bytes(),
+            },
+            "peer_max_bytes": self.peer_max_bytes,
+            "smart_engine": {
+                "enabled": self.smart_engine.enabled,
+                "store_max_memory": self.smart_engine.store_max_memory,
+            },
+            "profile": self.profile.as_str(),
+            "socket": {
+                "nodelay": self.socket.nodelay,
+                "send_buffer_size": self.socket.send_buffer_size,
+                "recv_buffer_size": self.socket.recv_buffer_size,
+            },
+            "health_endpoint": self.health_endpoint,
+            "consumer_offset_ttl_secs": self.consumer_offset_ttl_secs,
+            "access_log": self.access_log,
+            "produce_byte_rate": self.produce_byte_rate,
+            "fetch_byte_rate": self.fetch_byte_rate,
+            "connection_limits": {
+                "max_per_ip": self.connection_limits.max_per_ip,
+                "max_per_principal": self.connection_limits.max_per_principal,
+            },
+            "ip_filter": {
+                "allow": networks(self.ip_filter.allow()),
+                "deny": networks(self.ip_filter.deny()),
+            },
+            "admin_principals": self.admin_principals,
+        })
+    }
+
+    /// Switch to the minimal profile, disabling the SmartModule engine and
+    /// capping buffer sizes. Limits lower than the profile ones are kept.
+    pub fn apply_minimal_profile(&mut self) {
+        self.profile = RuntimeProfile::Minimal;
+        self.smart_engine.enabled = false;
+        self.smart_engine.store_max_memory = self
+            .smart_engine
+            .store_max_memory
+            .min(MINIMAL_SMARTENGINE_STORE_MAX_BYTES);
+        self.peer_max_bytes = self.peer_max_bytes.min(MINIMAL_PEER_MAX_BYTES);
+        self.log.max_batch_size = self.log.max_batch_size.min(MINIMAL_MAX_BATCH_SIZE);
+        if self.log.read_cache.max_bytes() > MINIMAL_READ_CACHE_MAX_BYTES {
+            self.log.read_cache = BatchCache::shared(MINIMAL_READ_CACHE_MAX_BYTES);
+        }
+    }
+}
+
+/// invalid networks are rejected by CLI, keep startup networks if they got into runtime config anyway
+fn runtime_networks(networks: &Option<Vec<String>>, startup: &[IpCidr]) -> Vec<IpCidr> {
+    let Some(networks) = networks else {
+        return startup.to_vec();
+    };
+    match networks
+        .iter()
+        .map(|network| network.parse())
+        .collect::<anyhow::Result<Vec<IpCidr>>>()
+    {
+        Ok(networks) => networks,
+        Err(err) => {
+            warn!(%err, "ignoring invalid networks of runtime config");
+            startup.to_vec()
+        }
+    }
 }
 
 impl From<&SpuConfig> for ReplicaConfig {
@@ -174,6 +393,8 @@ impl From<&SpuConfig> for ReplicaConfig {
             .flush_write_count(log.flush_write_count)
             .flush_idle_msec(log.flush_idle_msec)
             .max_batch_size(log.max_batch_size)
+            .tiered_storage(log.tiered_storage.clone())
+            .read_cache(Some(log.read_cache.clone()))
             .build()
     }
 }
@@ -183,3 +404,101 @@ impl From<&SpuConfig> for ReplicationConfig {
         config.replication.clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_minimal_profile() {
+        let mut config = SpuConfig::default();
+        config.smart_engine.store_max_memory = 1024;
+        config.apply_minimal_profile();
+
+        assert_eq!(config.profile, RuntimeProfile::Minimal);
+        assert!(!config.smart_engine.enabled);
+        assert_eq!(config.smart_engine.store_max_memory, 1024);
+        assert_eq!(config.peer_max_bytes, MINIMAL_PEER_MAX_BYTES);
+        assert_eq!(config.log.max_batch_size, MINIMAL_MAX_BATCH_SIZE);
+        assert_eq!(
+            config.log.read_cache.max_bytes(),
+            MINIMAL_READ_CACHE_MAX_BYTES
+        );
+        assert_eq!(config.profile.worker_threads(), Some(1));
+    }
+
+    #[test]
+    fn test_runtime_config_overrides() {
+        let config = SpuConfig {
+            fetch_byte_rate: Some(1_000_000),
+            ..Default::default()
+        };
+        let runtime = SpuRuntimeConfig {
+            flush_idle_msec: Some(500),
+            peer_max_bytes: Some(2048),
+            access_log: Some(true),
+            produce_byte_rate: Some(500_000),
+            fetch_byte_rate: Some(0),
+            ..Default::default()
+        };
+
+        let effective = config.with_runtime_config(&runtime);
+        assert_eq!(effective.log.flush_idle_msec, 500);
+        assert_eq!(
+            effective.log.flush_write_count,
+            config.log.flush_write_count
+        );
+        assert_eq!(effective.peer_max_bytes, 2048);
+        assert!(effective.access_log);
+        assert_eq!(effective.produce_byte_rate, Some(500_000));
+        assert_eq!(effective.fetch_byte_rate, None);
+        assert_eq!(effective.ip_filter, config.ip_filter);
+
+        assert_eq!(
+            config.with_runtime_config(&SpuRuntimeConfig::default()),
+            config
+        );
+    }
+
+    #[test]
+    fn test_runtime_ip_filter() {
+        let config = SpuConfig {
+            ip_filter: IpFilter::new(&["10.0.0.0/8"], &["10.0.5.0/24"]).expect("filter"),
+            ..Default::default()
+        };
+        let runtime = SpuRuntimeConfig {
+            ip_deny: Some(vec!["10.0.6.0/24".to_owned()]),
+            ..Default::default()
+        };
+        let effective = config.with_runtime_config(&runtime);
+        assert_eq!(effective.ip_filter.allow(), config.ip_filter.allow());
+        assert!(effective.ip_filter.is_allowed(&"10.0.5.1".parse().unwrap()));
+        assert!(!effective.ip_filter.is_allowed(&"10.0.6.1".parse().unwrap()));
+
+        // invalid runtime networks keep startup list
+        let runtime = SpuRuntimeConfig {
+            ip_allow: Some(vec!["not a network".to_owned()]),
+            ..Default::default()
+        };
+        assert_eq!(config.with_runtime_config(&runtime), config);
+    }
+
+    #[test]
+    fn test_config_json() {
+        let config = SpuConfig {
+            id: 5001,
+            ip_filter: IpFilter::new(&["10.0.0.0/8"], &[]).expect("filter"),
+            ..Default::default()
+        };
+        let runtime = SpuRuntimeConfig {
+            peer_max_bytes: Some(1024),
+            ..Default::default()
+        };
+        let json = config.with_runtime_config(&runtime).to_json();
+        assert_eq!(json["id"], 5001);
+        assert_eq!(json["peer_max_bytes"], 1024);
+        assert_eq!(json["profile"], "standard");
+        assert_eq!(json["ip_filter"]["allow"], json!(["10.0.0.0/8"]));
+        assert_eq!(json["health_endpoint"], Value::Null);
+    }
+}
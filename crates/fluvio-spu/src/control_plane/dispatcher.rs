@@ -8,8 +8,10 @@ use futures_util::stream::StreamExt;
 use anyhow::{anyhow, Result};
 
 use fluvio_controlplane::sc_api::register_spu::RegisterSpuRequest;
+use fluvio_controlplane::sc_api::resync::{MetadataSyncKind, ResyncRequest};
 use fluvio_controlplane::sc_api::update_lrs::UpdateLrsRequest;
 use fluvio_controlplane::spu_api::api::{InternalSpuRequest, InternalSpuApi};
+use fluvio_controlplane::spu_api::update_cluster_config::UpdateClusterConfigRequest;
 use fluvio_controlplane::spu_api::update_replica::UpdateReplicaRequest;
 use fluvio_controlplane::spu_api::update_smartmodule::UpdateSmartModuleRequest;
 use fluvio_controlplane::spu_api::update_spu::UpdateSpuRequest;
@@ -23,10 +25,14 @@ use fluvio_storage::FileReplica;
 use fluvio_controlplane::sc_api::update_mirror::UpdateMirrorStatRequest;
 use fluvio_controlplane::spu_api::update_mirror::UpdateMirrorRequest;
 use fluvio_controlplane::sc_api::update_partition::UpdatePartitionStatRequest;
+use fluvio_controlplane_metadata::clusterconfig::{ClusterConfigSpec, CLUSTER_CONFIG_NAME};
+use fluvio_controlplane_metadata::message::MsgType;
 
 use crate::core::SharedGlobalContext;
+use crate::core::{METADATA_SYNC, STORAGE_INIT};
 
 use super::message_sink::SharedLrsStatusUpdate;
+use super::sync_epoch::SyncEpoch;
 use super::{SharedMirrorStatusUpdate, SharedPartitionStatusUpdate};
 
 // keep track of various internal state of dispatcher
@@ -37,6 +43,8 @@ struct DispatcherCounter {
     pub reconnect: u64,       // number of reconnect to sc
     pub smartmodule: u64,     // number of sm updates from sc
     pub mirror: u64,          // number of mirror updates from sc
+    pub cluster_config: u64,  // number of cluster config updates from sc
+    pub resync: u64,          // number of snapshots requested from sc
 }
 
 /// Controller for handling connection to SC
@@ -135,6 +143,10 @@ impl ScDispatcher<FileReplica> {
 
         let mut status_timer = Timer::interval(MIN_SC_SINK_TIME);
 
+        // sc starts each connection with full state
+        let mut replica_epoch = SyncEpoch::default();
+        let mut spu_epoch = SyncEpoch::default();
+
         loop {
             trace!("waiting");
 
@@ -151,10 +163,16 @@ impl ScDispatcher<FileReplica> {
                     match sc_request {
                         Some(Ok(InternalSpuRequest::UpdateReplicaRequest(request))) => {
                             self.counter.replica_changes += 1;
+                            if replica_epoch.advance(&request.request) {
+                                self.request_resync(MetadataSyncKind::Replica, &replica_epoch, &mut sink).await?;
+                            }
                             self.handle_update_replica_request(request,&mut sink).await;
                         },
                         Some(Ok(InternalSpuRequest::UpdateSpuRequest(request))) => {
                             self.counter.spu_changes += 1;
+                            if spu_epoch.advance(&request.request) {
+                                self.request_resync(MetadataSyncKind::Spu, &spu_epoch, &mut sink).await?;
+                            }
                             if let Err(err) = self.handle_update_spu_request(request).await {
                                 error!(%err, "error handling update spu request");
                                 break;
@@ -174,6 +192,10 @@ impl ScDispatcher<FileReplica> {
                                 break;
                             }
                         },
+                        Some(Ok(InternalSpuRequest::UpdateClusterConfigRequest(request))) => {
+                            self.counter.cluster_config += 1;
+                            self.handle_update_cluster_config_request(request);
+                        },
                         Some(Err(err)) => {
                             error!(%err, "Api error");
                             break;
@@ -193,6 +215,26 @@ impl ScDispatcher<FileReplica> {
         Ok(())
     }
 
+    /// ask sc for snapshot after gap in incremental changes
+    async fn request_resync(
+        &mut self,
+        kind: MetadataSyncKind,
+        sync: &SyncEpoch,
+        sc_sink: &mut FluvioSink,
+    ) -> Result<()> {
+        warn!(
+            %kind,
+            last_epoch = sync.last_epoch(),
+            "gap in metadata changes, requesting snapshot"
+        );
+        self.counter.resync += 1;
+        let message = RequestMessage::new_request(ResyncRequest::new(kind, sync.last_epoch()));
+        sc_sink
+            .send_request(&message)
+            .await
+            .map_err(|err| anyhow!("error requesting resync from sc: {}", err))
+    }
+
     /// send lrs status back to sc
     #[instrument(skip(self))]
     async fn send_lrs_status_back_to_sc(&mut self, sc_sink: &mut FluvioSink) -> Result<()> {
@@ -335,7 +377,15 @@ impl ScDispatcher<FileReplica> {
 
         debug!( message = ?request,"replica request");
 
-        for action in self.ctx.apply_replica_update(request).await.into_iter() {
+        let actions = self.ctx.apply_replica_update(request).await;
+        if !actions
+            .iter()
+            .any(|action| matches!(action, ReplicaChange::StorageError(_)))
+        {
+            self.ctx.readiness().set_ready(STORAGE_INIT);
+        }
+
+        for action in actions.into_iter() {
             match action {
                 ReplicaChange::Remove(remove) => {
                     let message = RequestMessage::new_request(remove);
@@ -380,7 +430,16 @@ impl ScDispatcher<FileReplica> {
             self.ctx.spu_localstore().apply_changes(request.changes)
         };
 
+        if let Some(local_spu) = self.ctx.spu_localstore().spec(&self.ctx.local_spu_id()) {
+            self.ctx.update_runtime_config(local_spu.config).await;
+        }
+
         self.ctx.sync_follower_update().await;
+        self.ctx.readiness().set_ready(METADATA_SYNC);
+        // SC sends replicas only if there are any, without them there is no storage to initialize
+        if self.ctx.replica_localstore().count() == 0 {
+            self.ctx.readiness().set_ready(STORAGE_INIT);
+        }
 
         trace!("finish spu update");
 
@@ -461,4 +520,37 @@ impl ScDispatcher<FileReplica> {
 
         Ok(())
     }
+
+    ///
+    /// Handle cluster config update sent by SC, only default instance is used
+    ///
+    #[instrument(skip(self, req_msg), name = "update_cluster_config_request")]
+    fn handle_update_cluster_config_request(
+        &mut self,
+        req_msg: RequestMessage<UpdateClusterConfigRequest>,
+    ) {
+        let (_, request) = req_msg.get_header_request();
+
+        debug!(message = ?request, "starting cluster config update");
+
+        let mut config = if request.all.is_empty() {
+            self.ctx.cluster_config()
+        } else {
+            ClusterConfigSpec::default()
+        };
+        for item in request.all {
+            if item.name == CLUSTER_CONFIG_NAME {
+                config = item.spec;
+            }
+        }
+        for change in request.changes {
+            if change.content.name == CLUSTER_CONFIG_NAME {
+                config = match change.header {
+                    MsgType::UPDATE => change.content.spec,
+                    MsgType::DELETE => ClusterConfigSpec::default(),
+                };
+            }
+        }
+        self.ctx.update_cluster_config(config);
+    }
 }
@@ -3,6 +3,7 @@
 mod dispatcher;
 mod action;
 mod message_sink;
+mod sync_epoch;
 
 pub use dispatcher::ScDispatcher;
 
@@ -0,0 +1,73 @@
+use std::fmt::Debug;
+
+use fluvio_controlplane::requests::ControlPlaneRequest;
+use fluvio_protocol::{Decoder, Encoder};
+
+/// Tracks epoch of metadata received from SC to detect gaps in incremental changes
+#[derive(Debug, Default)]
+pub(crate) struct SyncEpoch {
+    last: Option<i64>,
+    resync_pending: bool,
+}
+
+impl SyncEpoch {
+    /// last epoch applied without gap, 0 if unknown
+    pub fn last_epoch(&self) -> i64 {
+        self.last.unwrap_or_default()
+    }
+
+    /// record request from SC.
+    /// returns true if a gap was detected and snapshot should be requested
+    pub fn advance<S>(&mut self, request: &ControlPlaneRequest<S>) -> bool
+    where
+        S: Encoder + Decoder + Debug,
+    {
+        if !request.is_incremental() {
+            // snapshot or changes from beginning, becomes new base
+            self.last = Some(request.epoch);
+            self.resync_pending = false;
+            return false;
+        }
+
+        if self.last == Some(request.prev_epoch) {
+            self.last = Some(request.epoch);
+            return false;
+        }
+
+        // until snapshot arrives, further changes don't follow last applied epoch either
+        if self.resync_pending {
+            false
+        } else {
+            self.resync_pending = true;
+            true
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fluvio_controlplane::spu_api::update_replica::UpdateReplicaRequest;
+
+    use super::*;
+
+    #[test]
+    fn test_sync_epoch_gap() {
+        let mut sync = SyncEpoch::default();
+
+        assert!(!sync.advance(&UpdateReplicaRequest::with_all(5, vec![])));
+        assert_eq!(sync.last_epoch(), 5);
+        assert!(!sync.advance(&UpdateReplicaRequest::with_changes(7, vec![]).with_prev_epoch(5)));
+        assert_eq!(sync.last_epoch(), 7);
+
+        // missed changes between 7 and 9
+        assert!(sync.advance(&UpdateReplicaRequest::with_changes(10, vec![]).with_prev_epoch(9)));
+        // only request snapshot once
+        assert!(!sync.advance(&UpdateReplicaRequest::with_changes(11, vec![]).with_prev_epoch(10)));
+        assert_eq!(sync.last_epoch(), 7);
+
+        // snapshot resets
+        assert!(!sync.advance(&UpdateReplicaRequest::with_all(12, vec![])));
+        assert!(!sync.advance(&UpdateReplicaRequest::with_changes(13, vec![]).with_prev_epoch(12)));
+        assert_eq!(sync.last_epoch(), 13);
+    }
+}
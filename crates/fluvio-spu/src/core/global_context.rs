@@ -3,13 +3,17 @@
 //!
 //! Global Context maintains states need to be shared across in the SPU
 
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use std::fmt::Debug;
 
-use tracing::{debug, error, instrument};
+use tracing::{debug, error, info, instrument};
 
 use fluvio_types::SpuId;
 use fluvio_storage::ReplicaStorage;
+use fluvio_controlplane_metadata::spu::SpuRuntimeConfig;
+use fluvio_controlplane_metadata::clusterconfig::ClusterConfigSpec;
+use fluvio_service::health::Readiness;
+use fluvio_service::ip_filter::SharedIpFilter;
 
 use crate::config::SpuConfig;
 use crate::control_plane::SharedMirrorStatusUpdate;
@@ -39,9 +43,16 @@ use super::SharedSpuConfig;
 
 pub use file_replica::ReplicaChange;
 
+/// readiness condition met once first SPU metadata is received from SC
+pub(crate) const METADATA_SYNC: &str = "metadata sync";
+/// readiness condition met once replicas assigned by SC are initialized
+pub(crate) const STORAGE_INIT: &str = "storage init";
+
 #[derive(Debug)]
 pub struct GlobalContext<S> {
     config: SharedSpuConfig,
+    runtime_config: RwLock<SpuRuntimeConfig>,
+    cluster_config: RwLock<ClusterConfigSpec>,
     spu_localstore: SharedSpuLocalStore,
     replica_localstore: SharedReplicaLocalStore,
     smartmodule_localstore: SharedSmartModuleLocalStore,
@@ -56,6 +67,8 @@ pub struct GlobalContext<S> {
     mirrors: SharedMirrorLocalStore,
     metrics: Arc<SpuMetrics>,
     consumer_offset: SharedConsumerOffsetStorages,
+    readiness: Arc<Readiness>,
+    ip_filter: SharedIpFilter,
 }
 
 // -----------------------------------
@@ -73,13 +86,16 @@ where
     pub fn new(spu_config: SpuConfig) -> Self {
         let spus = SpuLocalStore::new_shared();
         let replicas = ReplicaStore::new_shared();
-        let metrics = Arc::new(SpuMetrics::new());
+        let metrics = Arc::new(SpuMetrics::new(spu_config.log.read_cache.clone()));
 
         GlobalContext {
+            ip_filter: SharedIpFilter::new(spu_config.ip_filter.clone()),
             spu_localstore: spus.clone(),
             replica_localstore: replicas.clone(),
             smartmodule_localstore: SmartModuleLocalStore::new_shared(),
             config: Arc::new(spu_config),
+            runtime_config: RwLock::new(SpuRuntimeConfig::default()),
+            cluster_config: RwLock::new(ClusterConfigSpec::default()),
             leaders_state: ReplicaLeadersState::new_shared(),
             followers_state: FollowersState::new_shared(),
             spu_followers: FollowerNotifier::shared(),
@@ -91,6 +107,7 @@ where
             mirrors: MirrorLocalStore::new_shared(),
             metrics,
             consumer_offset: SharedConsumerOffsetStorages::default(),
+            readiness: Readiness::shared(&[METADATA_SYNC, STORAGE_INIT]),
         }
     }
 
@@ -143,6 +160,152 @@ where
         self.config.clone()
     }
 
+    /// runtime config received from SC
+    pub fn runtime_config(&self) -> SpuRuntimeConfig {
+        self.runtime_config
+            .read()
+            .map(|config| config.clone())
+            .unwrap_or_default()
+    }
+
+    /// startup config with runtime overrides applied
+    pub fn effective_config(&self) -> SpuConfig {
+        self.config.with_runtime_config(&self.runtime_config())
+    }
+
+    /// whether requests of public service are logged, runtime config overrides startup flag
+    pub fn access_log_enabled(&self) -> bool {
+        self.runtime_config
+            .read()
+            .ok()
+            .and_then(|config| config.access_log)
+            .unwrap_or(self.config.access_log)
+    }
+
+    /// max bytes per second produced by each client connection, runtime config overrides startup flag
+    pub fn produce_byte_rate(&self) -> Option<u32> {
+        self.runtime_config
+            .read()
+            .ok()
+            .and_then(|config| config.produce_byte_rate)
+            .map_or(self.config.produce_byte_rate, |rate| {
+                (rate > 0).then_some(rate)
+            })
+    }
+
+    /// max bytes per second fetched by each client connection, runtime config overrides startup flag
+    pub fn fetch_byte_rate(&self) -> Option<u32> {
+        self.runtime_config
+            .read()
+            .ok()
+            .and_then(|config| config.fetch_byte_rate)
+            .map_or(self.config.fetch_byte_rate, |rate| {
+                (rate > 0).then_some(rate)
+            })
+    }
+
+    /// networks accepted by public service
+    pub fn ip_filter(&self) -> &SharedIpFilter {
+        &self.ip_filter
+    }
+
+    /// apply runtime config received from SC, without restart
+    #[instrument(skip(self))]
+    pub async fn update_runtime_config(&self, config: SpuRuntimeConfig) {
+        let flush_changed = {
+            let Ok(mut current) = self.runtime_config.write() else {
+                error!("runtime config lock poisoned");
+                return;
+            };
+
+            if *current == config {
+                return;
+            }
+
+            if current.log_level != config.log_level
+                && let Err(err) = crate::log_level::set_log_level(config.log_level.as_deref())
+            {
+                error!("unable to change log level: {err}");
+            }
+
+            if current.ip_allow != config.ip_allow || current.ip_deny != config.ip_deny {
+                self.ip_filter
+                    .set(self.config.with_runtime_config(&config).ip_filter);
+            }
+
+            let flush_changed = current.flush_write_count != config.flush_write_count
+                || current.flush_idle_msec != config.flush_idle_msec;
+
+            info!(%config, "runtime config updated");
+            *current = config;
+            flush_changed
+        };
+
+        if flush_changed {
+            self.update_flush_policy().await;
+        }
+    }
+
+    /// apply flush settings to running replicas, new replicas get them from effective config
+    async fn update_flush_policy(&self) {
+        let log = self.effective_config().log;
+        let leaders: Vec<_> = self.leaders_state.read().await.values().cloned().collect();
+        for leader in leaders {
+            leader
+                .update_flush_policy(log.flush_write_count, log.flush_idle_msec)
+                .await;
+        }
+        let followers: Vec<_> = self
+            .followers_state
+            .read()
+            .await
+            .values()
+            .cloned()
+            .collect();
+        for follower in followers {
+            follower
+                .update_flush_policy(log.flush_write_count, log.flush_idle_msec)
+                .await;
+        }
+        debug!(
+            flush_write_count = log.flush_write_count,
+            flush_idle_msec = log.flush_idle_msec,
+            "flush policy updated"
+        );
+    }
+
+    /// cluster config received from SC
+    pub fn cluster_config(&self) -> ClusterConfigSpec {
+        self.cluster_config
+            .read()
+            .map(|config| config.clone())
+            .unwrap_or_default()
+    }
+
+    /// whether produce requests are rejected because cluster is in maintenance
+    pub fn rejects_produce(&self) -> bool {
+        self.cluster_config
+            .read()
+            .is_ok_and(|config| config.rejects_produce())
+    }
+
+    #[instrument(skip(self))]
+    pub fn update_cluster_config(&self, config: ClusterConfigSpec) {
+        let Ok(mut current) = self.cluster_config.write() else {
+            error!("cluster config lock poisoned");
+            return;
+        };
+
+        if *current != config {
+            info!(
+                maintenance = config.is_in_maintenance(),
+                reject_produce = config.rejects_produce(),
+                "cluster config updated"
+            );
+            *current = config;
+        }
+    }
+
     pub fn follower_notifier(&self) -> &Arc<FollowerNotifier> {
         &self.spu_followers
     }
@@ -197,6 +360,11 @@ where
     pub(crate) fn consumer_offset(&self) -> &SharedConsumerOffsetStorages {
         &self.consumer_offset
     }
+
+    /// conditions gating readiness probe
+    pub(crate) fn readiness(&self) -> &Arc<Readiness> {
+        &self.readiness
+    }
 }
 
 mod file_replica {
@@ -211,6 +379,7 @@ mod file_replica {
     use flv_util::actions::Actions;
 
     use crate::core::SpecChange;
+    use crate::replication::leader::SharedFileLeaderState;
 
     use super::*;
 
@@ -361,12 +530,36 @@ mod file_replica {
                                     }
                                 }
                             } else if new_replica.leader == local_id {
-                                if self.leaders_state().get(&new_replica.id).await.is_some() {
+                                if let Some(leader) =
+                                    self.leaders_state().get(&new_replica.id).await
+                                {
+                                    leader.update_leader_epoch(new_replica.leader_epoch);
+                                    if new_replica.replicas != old_replica.replicas {
+                                        leader.update_followers(&new_replica.replicas).await;
+                                    }
+                                    if is_config_changed(&new_replica, &old_replica) {
+                                        self.reconfigure_leader(&leader, new_replica).await;
+                                    }
                                 } else {
                                     error!("leader controller was not found: {}", new_replica.id);
                                 }
                             } else {
-                                self.followers_state().update_replica(new_replica).await;
+                                // replication factor change may add or remove us as follower
+                                let is_follower = new_replica.replicas.contains(&local_id);
+                                let was_follower = old_replica.replicas.contains(&local_id);
+                                if is_follower && !was_follower {
+                                    if let Err(err) = self
+                                        .followers_state_owned()
+                                        .add_replica(self, new_replica)
+                                        .await
+                                    {
+                                        outputs.push(ReplicaChange::StorageError(err));
+                                    }
+                                } else if !is_follower && was_follower {
+                                    self.remove_follower_replica(new_replica).await;
+                                } else {
+                                    self.followers_state().update_replica(new_replica).await;
+                                }
                             }
                         }
                     }
@@ -376,6 +569,17 @@ mod file_replica {
             outputs
         }
 
+        /// apply changed topic settings to leader, replacing its state
+        async fn reconfigure_leader(&self, leader: &SharedFileLeaderState, replica: Replica) {
+            let id = replica.id.clone();
+            match leader.reconfigure(replica, self).await {
+                Ok(state) => {
+                    self.leaders_state().insert(id, state).await;
+                }
+                Err(err) => error!(%id, "unable to reconfigure leader: {err:#}"),
+            }
+        }
+
         async fn remove_replica(&self, outputs: &mut Vec<ReplicaChange>, replica: Replica) {
             if replica.leader == self.local_spu_id() {
                 outputs.push(ReplicaChange::Remove(
@@ -508,4 +712,12 @@ mod file_replica {
             Ok(())
         }
     }
+
+    /// topic settings of replica, which can be changed on running replica
+    fn is_config_changed(new: &Replica, old: &Replica) -> bool {
+        new.cleanup_policy != old.cleanup_policy
+            || new.storage != old.storage
+            || new.compression_type != old.compression_type
+            || new.deduplication != old.deduplication
+    }
 }
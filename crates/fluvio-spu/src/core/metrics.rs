@@ -5,6 +5,7 @@ use std::{
         RwLock,
     },
     ops::AddAssign,
+    time::Duration,
 };
 
 use fluvio_protocol::record::Batch;
@@ -14,22 +15,37 @@ use fluvio_smartengine::metrics::SmartModuleChainMetrics;
 use crate::smartengine::SmartModuleChainMetrics;
 
 use fluvio_spu_schema::fetch::FilePartitionResponse;
-use serde::Serialize;
+use fluvio_storage::cache::{BatchCache, SharedBatchCache};
+use serde::{Serialize, Serializer, ser::SerializeMap, ser::SerializeStruct};
+
+use crate::traffic::is_connector;
+
+/// Max number of clients whose traffic is counted separately
+const MAX_TRACKED_CLIENTS: usize = 1000;
+/// Client id under which traffic of clients beyond limit is counted
+const OTHER_CLIENTS: &str = "other";
 
 #[derive(Default, Debug, Serialize)]
 pub(crate) struct SpuMetrics {
     inbound: Activity,
     outbound: Activity,
+    write_coalesce: CoalesceMetrics,
+    stream_credits: CreditMetrics,
     #[serde(skip)] // Skip serializing the RwLock wrapper
     smartmodule_metrics: RwLock<HashMap<String, SmartModuleChainMetrics>>,
+    #[serde(skip)] // shared with storage, reported by monitoring
+    read_cache: SharedBatchCache,
 }
 
 impl SpuMetrics {
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(read_cache: SharedBatchCache) -> Self {
         Self {
             inbound: Activity::default(),
             outbound: Activity::default(),
+            write_coalesce: CoalesceMetrics::default(),
+            stream_credits: CreditMetrics::default(),
             smartmodule_metrics: RwLock::new(HashMap::new()),
+            read_cache,
         }
     }
 
@@ -41,6 +57,19 @@ impl SpuMetrics {
         &self.outbound
     }
 
+    pub fn write_coalesce(&self) -> &CoalesceMetrics {
+        &self.write_coalesce
+    }
+
+    pub fn stream_credits(&self) -> &CreditMetrics {
+        &self.stream_credits
+    }
+
+    /// usage, hits and evictions of read cache
+    pub fn read_cache(&self) -> &BatchCache {
+        &self.read_cache
+    }
+
     pub fn smartmodule_metrics(&self) -> HashMap<String, SmartModuleChainMetrics> {
         // Return a copy of the metrics to avoid holding the lock
         self.smartmodule_metrics.read().unwrap().clone()
@@ -80,12 +109,22 @@ impl Record {
         self.records.fetch_add(records, Ordering::SeqCst);
         self.bytes.fetch_add(bytes, Ordering::SeqCst);
     }
+
+    pub(crate) fn records(&self) -> u64 {
+        self.records.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn bytes(&self) -> u64 {
+        self.bytes.load(Ordering::SeqCst)
+    }
 }
 
 #[derive(Default, Debug, Serialize)]
 pub struct Activity {
     connector: Record,
     client: Record,
+    /// traffic by client id
+    clients: ClientsActivity,
 }
 
 #[derive(Default, Debug)]
@@ -95,17 +134,58 @@ pub(crate) struct IncreaseValue {
 }
 
 impl Activity {
-    pub(crate) fn increase(&self, connector: bool, records: u64, bytes: u64) {
-        if connector {
+    pub(crate) fn increase(&self, client_id: &str, records: u64, bytes: u64) {
+        if is_connector(client_id) {
             self.connector.increase(records, bytes);
         } else {
             self.client.increase(records, bytes);
         }
+        self.clients.increase(client_id, records, bytes);
     }
 
-    pub(crate) fn increase_by_value(&self, connector: bool, value: IncreaseValue) {
+    pub(crate) fn increase_by_value(&self, client_id: &str, value: IncreaseValue) {
         let IncreaseValue { records, bytes } = value;
-        self.increase(connector, records, bytes)
+        self.increase(client_id, records, bytes)
+    }
+}
+
+/// Traffic attributed to client ids sent in request headers
+#[derive(Default, Debug)]
+pub(crate) struct ClientsActivity {
+    clients: RwLock<HashMap<String, Record>>,
+}
+
+impl ClientsActivity {
+    fn increase(&self, client_id: &str, records: u64, bytes: u64) {
+        {
+            let clients = self.clients.read().unwrap();
+            if let Some(record) = clients.get(client_id) {
+                record.increase(records, bytes);
+                return;
+            }
+        }
+
+        let mut clients = self.clients.write().unwrap();
+        let client_id = if clients.len() < MAX_TRACKED_CLIENTS || clients.contains_key(client_id) {
+            client_id
+        } else {
+            OTHER_CLIENTS
+        };
+        clients
+            .entry(client_id.to_owned())
+            .or_default()
+            .increase(records, bytes);
+    }
+}
+
+impl Serialize for ClientsActivity {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let clients = self.clients.read().unwrap();
+        let mut map = serializer.serialize_map(Some(clients.len()))?;
+        for (client_id, record) in clients.iter() {
+            map.serialize_entry(client_id, record)?;
+        }
+        map.end()
     }
 }
 
@@ -125,10 +205,102 @@ impl Activity {
     }
 }
 
+/// Traffic of single partition since this SPU became its leader
+#[derive(Default, Debug)]
+pub(crate) struct PartitionActivity {
+    produced: Record,
+    consumed: Record,
+}
+
+impl PartitionActivity {
+    pub(crate) fn produced(&self) -> &Record {
+        &self.produced
+    }
+
+    pub(crate) fn consumed(&self) -> &Record {
+        &self.consumed
+    }
+}
+
+/// Produce write coalescing activity
+#[derive(Default, Debug)]
+pub(crate) struct CoalesceMetrics {
+    requests: AtomicU64,
+    writes: AtomicU64,
+    added_latency_us: AtomicU64,
+}
+
+impl CoalesceMetrics {
+    /// record single storage append covering `requests` produce requests
+    pub(crate) fn record_write(&self, requests: u64) {
+        self.requests.fetch_add(requests, Ordering::SeqCst);
+        self.writes.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// record time produce request waited for coalesced write
+    pub(crate) fn add_latency(&self, latency: Duration) {
+        self.added_latency_us
+            .fetch_add(latency.as_micros() as u64, Ordering::SeqCst);
+    }
+
+    /// average number of produce requests per storage append
+    pub(crate) fn ratio(&self) -> f64 {
+        let writes = self.writes.load(Ordering::SeqCst);
+        if writes == 0 {
+            return 0.0;
+        }
+        self.requests.load(Ordering::SeqCst) as f64 / writes as f64
+    }
+}
+
+impl Serialize for CoalesceMetrics {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("CoalesceMetrics", 4)?;
+        state.serialize_field("requests", &self.requests)?;
+        state.serialize_field("writes", &self.writes)?;
+        state.serialize_field("ratio", &self.ratio())?;
+        state.serialize_field("added_latency_us", &self.added_latency_us)?;
+        state.end()
+    }
+}
+
+/// Stream fetch flow control activity
+#[derive(Default, Debug, Serialize)]
+pub(crate) struct CreditMetrics {
+    /// responses waiting for consumer acknowledgement
+    inflight: AtomicU64,
+    /// responses sent ahead of acknowledgement
+    pipelined: AtomicU64,
+    /// times records were available but stream had no credit left
+    exhausted: AtomicU64,
+}
+
+impl CreditMetrics {
+    pub(crate) fn add_inflight(&self, responses: u64) {
+        self.inflight.fetch_add(responses, Ordering::SeqCst);
+    }
+
+    pub(crate) fn remove_inflight(&self, responses: u64) {
+        self.inflight.fetch_sub(responses, Ordering::SeqCst);
+    }
+
+    pub(crate) fn add_pipelined(&self) {
+        self.pipelined.fetch_add(1, Ordering::SeqCst);
+    }
+
+    pub(crate) fn add_exhausted(&self) {
+        self.exhausted.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
 impl IncreaseValue {
     pub(crate) fn new(records: u64, bytes: u64) -> Self {
         Self { records, bytes }
     }
+
+    pub(crate) fn bytes(&self) -> u64 {
+        self.bytes
+    }
 }
 
 // Measuring of serialized data. `bytes` is length of file slice, `records` is an offset's change
@@ -180,13 +352,59 @@ mod tests {
         let activity = Activity::default();
 
         //when
-        activity.increase_by_value(true, IncreaseValue::from(&batch));
+        activity.increase_by_value("fluvio_connector", IncreaseValue::from(&batch));
 
         //then
         assert_eq!(activity.connector.records.load(Ordering::SeqCst), 2);
         assert_eq!(activity.connector.bytes.load(Ordering::SeqCst), 33); // 10 + 11 + 12
     }
 
+    #[test]
+    fn test_increase_by_client() {
+        let activity = Activity::default();
+        activity.increase("app1", 2, 20);
+        activity.increase("app1", 1, 10);
+        activity.increase("fluvio_connector_sink", 4, 40);
+
+        assert_eq!(activity.client_records(), 3);
+        assert_eq!(activity.connector_records(), 4);
+        let value = serde_json::to_value(&activity).expect("json");
+        assert_eq!(value["clients"]["app1"]["records"], 3);
+        assert_eq!(value["clients"]["app1"]["bytes"], 30);
+        assert_eq!(value["clients"]["fluvio_connector_sink"]["records"], 4);
+    }
+
+    #[test]
+    fn test_clients_beyond_limit_counted_together() {
+        let activity = Activity::default();
+        for i in 0..MAX_TRACKED_CLIENTS {
+            activity.increase(&format!("app{i}"), 1, 1);
+        }
+        activity.increase("late1", 1, 1);
+        activity.increase("late2", 1, 1);
+        activity.increase("app0", 1, 1);
+
+        let clients = activity.clients.clients.read().unwrap();
+        assert_eq!(clients.len(), MAX_TRACKED_CLIENTS + 1);
+        assert_eq!(clients[OTHER_CLIENTS].records(), 2);
+        assert_eq!(clients["app0"].records(), 2);
+    }
+
+    #[test]
+    fn test_coalesce_ratio() {
+        let metrics = CoalesceMetrics::default();
+        assert_eq!(metrics.ratio(), 0.0);
+
+        metrics.record_write(3);
+        metrics.record_write(1);
+        metrics.add_latency(Duration::from_micros(150));
+
+        assert_eq!(metrics.ratio(), 2.0);
+        let value = serde_json::to_value(&metrics).expect("json");
+        assert_eq!(value["writes"], 2);
+        assert_eq!(value["added_latency_us"], 150);
+    }
+
     #[test]
     fn test_increase_from_file_partition_response() {
         //given
@@ -199,7 +417,7 @@ mod tests {
         let activity = Activity::default();
 
         //when
-        activity.increase_by_value(true, IncreaseValue::from(&response));
+        activity.increase_by_value("fluvio_connector", IncreaseValue::from(&response));
 
         //then
         assert_eq!(activity.connector.records.load(Ordering::SeqCst), 1);
@@ -9,6 +9,7 @@ pub mod metrics;
 pub mod mirror;
 
 pub use self::global_context::{GlobalContext, ReplicaChange};
+pub(crate) use self::global_context::{METADATA_SYNC, STORAGE_INIT};
 pub use self::store::Spec;
 pub use self::store::LocalStore;
 pub use self::store::SpecChange;
@@ -96,6 +96,30 @@ impl ConsumerOffsetStorage {
         }
         Ok(())
     }
+
+    /// delete offsets not modified since `cutoff`, returns number of deleted offsets
+    async fn expire(&mut self, cutoff: TimestampSecs) -> Result<usize> {
+        let expired: Vec<_> = self
+            .kv
+            .entries()
+            .await?
+            .into_iter()
+            .filter(|(_, offset)| offset.modified_time < cutoff)
+            .map(|(key, _)| key)
+            .collect();
+        if expired.is_empty() {
+            return Ok(0);
+        }
+
+        for key in &expired {
+            debug!(?key, "expired consumer offset");
+            self.kv.delete(key).await?;
+        }
+        // checkpoint right away, so expired entries are not replayed from log on restart
+        self.kv.flush().await?;
+        self.changes_since_flush = Default::default();
+        Ok(expired.len())
+    }
 }
 
 impl KVStorage<ConsumerOffsetKey, ConsumerOffset> for ConsumerOffsetStorage {
@@ -204,9 +228,13 @@ impl SharableConsumerOffsetStorage {
     pub async fn list(&self) -> Result<Vec<(ConsumerOffsetKey, ConsumerOffset)>> {
         self.0.read().await.entries().await
     }
+
+    pub(crate) async fn expire(&self, cutoff: TimestampSecs) -> Result<usize> {
+        self.0.write().await.expire(cutoff).await
+    }
 }
 
-fn now_timestamp() -> TimestampSecs {
+pub(crate) fn now_timestamp() -> TimestampSecs {
     SystemTime::now()
         .duration_since(SystemTime::UNIX_EPOCH)
         .unwrap_or_default()
@@ -312,6 +340,42 @@ mod tests {
         leader.remove().await.expect("removed");
     }
 
+    #[fluvio_future::test]
+    async fn test_expire() {
+        //given
+        let leader = create_offset_replica("test_expire_consumer_offsets").await;
+        let notifier = FollowerNotifier::shared();
+        let storage = SharedConsumerOffsetStorages::default();
+        let shared_storage = storage
+            .get_or_insert(&leader, &notifier)
+            .await
+            .expect("storage");
+
+        let now = now_timestamp();
+        let stale = ConsumerOffsetKey::new(("topic1", 0), "stale");
+        let active = ConsumerOffsetKey::new(("topic1", 0), "active");
+        shared_storage
+            .put(stale, ConsumerOffset::with(1, now - 1000))
+            .await
+            .expect("put stale");
+        shared_storage
+            .put(active.clone(), ConsumerOffset::with(2, now))
+            .await
+            .expect("put active");
+
+        //when
+        let expired = shared_storage.expire(now - 500).await.expect("expire");
+
+        //then
+        assert_eq!(expired, 1);
+        let list = shared_storage.list().await.expect("list");
+        assert_eq!(list.len(), 1);
+        assert_eq!(list[0].0, active);
+        assert_eq!(shared_storage.expire(now - 500).await.expect("expire"), 0);
+
+        leader.remove().await.expect("removed");
+    }
+
     async fn create_offset_replica(dir: impl AsRef<Path>) -> LeaderReplicaState<FileReplica> {
         let base_dir = temp_dir().join(dir);
         ensure_clean_dir(&base_dir);
@@ -0,0 +1,70 @@
+//! Periodic removal of offsets of consumers which stopped committing
+
+use std::time::Duration;
+
+use anyhow::Result;
+use tracing::{debug, error, info};
+
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+
+use crate::core::DefaultSharedGlobalContext;
+
+use super::consumer::now_timestamp;
+
+/// upper bound of time between expiry runs
+const MAX_EXPIRY_INTERVAL: Duration = Duration::from_secs(3600);
+
+pub(crate) fn start_consumer_offset_expiry(ctx: DefaultSharedGlobalContext) {
+    let ttl_secs = ctx.config().consumer_offset_ttl_secs;
+    if ttl_secs == 0 {
+        info!("consumer offset expiry disabled");
+        return;
+    }
+
+    spawn(async move {
+        let interval = expiry_interval(ttl_secs);
+        info!(ttl_secs, ?interval, "starting consumer offset expiry");
+        loop {
+            sleep(interval).await;
+            if let Err(err) = expire_offsets(&ctx, ttl_secs).await {
+                error!("error expiring consumer offsets: {:?}", err);
+            }
+        }
+    });
+}
+
+fn expiry_interval(ttl_secs: u64) -> Duration {
+    Duration::from_secs(ttl_secs).min(MAX_EXPIRY_INTERVAL)
+}
+
+async fn expire_offsets(ctx: &DefaultSharedGlobalContext, ttl_secs: u64) -> Result<()> {
+    // only leader writes offsets, followers receive deletions through replication
+    let Some(ref replica) = ctx.leaders_state().is_consumer_offset_leader().await else {
+        debug!("not consumer offset leader, skipping expiry");
+        return Ok(());
+    };
+
+    let storage = ctx
+        .consumer_offset()
+        .get_or_insert(replica, ctx.follower_notifier())
+        .await?;
+    let cutoff = now_timestamp().saturating_sub(ttl_secs);
+    let expired = storage.expire(cutoff).await?;
+    if expired > 0 {
+        info!(expired, cutoff, "expired offsets of inactive consumers");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_expiry_interval() {
+        assert_eq!(expiry_interval(60), Duration::from_secs(60));
+        assert_eq!(expiry_interval(7 * 24 * 3600), MAX_EXPIRY_INTERVAL);
+    }
+}
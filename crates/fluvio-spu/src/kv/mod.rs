@@ -1 +1,2 @@
 pub(crate) mod consumer;
+pub(crate) mod expiry;
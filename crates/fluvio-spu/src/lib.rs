@@ -1,6 +1,7 @@
 mod error;
 mod config;
 mod kv;
+mod log_level;
 
 cfg_if::cfg_if! {
     if #[cfg(unix)] {
@@ -12,26 +13,20 @@ cfg_if::cfg_if! {
         mod storage;
         mod smartengine;
         mod monitoring;
+        mod rebuild_index;
         pub(crate) mod mirroring;
         pub use start::main_loop;
     }
 }
 
 pub use config::SpuOpt;
+pub use log_level::init_tracer;
 
 const VERSION: &str = include_str!("../../../VERSION");
 
 pub(crate) mod traffic {
-    use fluvio_protocol::api::RequestHeader;
-
-    pub(crate) trait TrafficType {
-        // check if traffic is connector
-        fn is_connector(&self) -> bool;
-    }
-
-    impl TrafficType for RequestHeader {
-        fn is_connector(&self) -> bool {
-            self.client_id().starts_with("fluvio_connector")
-        }
+    /// check if traffic of client is connector
+    pub(crate) fn is_connector(client_id: &str) -> bool {
+        client_id.starts_with("fluvio_connector")
     }
 }
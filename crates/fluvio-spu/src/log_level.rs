@@ -0,0 +1,49 @@
+//!
+//! # Log level
+//!
+//! Tracing subscriber whose filter can be changed while the SPU is running
+//!
+use std::sync::OnceLock;
+
+use anyhow::{Result, anyhow};
+use tracing_subscriber::{EnvFilter, Registry, fmt, reload};
+use tracing_subscriber::prelude::*;
+
+const DEFAULT_LOG_FILTER: &str = "info";
+
+struct LogFilter {
+    startup: String,
+    handle: reload::Handle<EnvFilter, Registry>,
+}
+
+static LOG_FILTER: OnceLock<LogFilter> = OnceLock::new();
+
+/// Initialize tracing with filter from `RUST_LOG`, which can be replaced later by runtime config
+pub fn init_tracer() {
+    let startup =
+        std::env::var(EnvFilter::DEFAULT_ENV).unwrap_or_else(|_| DEFAULT_LOG_FILTER.to_owned());
+    let filter =
+        EnvFilter::try_new(&startup).unwrap_or_else(|_| EnvFilter::new(DEFAULT_LOG_FILTER));
+    let (filter, handle) = reload::Layer::new(filter);
+
+    if tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt::layer())
+        .try_init()
+        .is_ok()
+    {
+        let _ = LOG_FILTER.set(LogFilter { startup, handle });
+    }
+}
+
+/// Replace log filter, `None` restores the filter used at startup
+pub(crate) fn set_log_level(directives: Option<&str>) -> Result<()> {
+    let log_filter = LOG_FILTER
+        .get()
+        .ok_or_else(|| anyhow!("log level can't be changed, tracer was not initialized by SPU"))?;
+    let directives = directives.unwrap_or(&log_filter.startup);
+    let filter = EnvFilter::try_new(directives)
+        .map_err(|err| anyhow!("invalid log level \"{directives}\": {err}"))?;
+    log_filter.handle.reload(filter)?;
+    Ok(())
+}
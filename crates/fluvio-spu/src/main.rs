@@ -4,7 +4,7 @@ static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 use clap::Parser;
 
 fn main() {
-    fluvio_future::subscriber::init_tracer(None);
+    fluvio_spu::init_tracer();
 
     let opt = fluvio_spu::SpuOpt::parse();
     fluvio_spu::main_loop(opt);
@@ -625,6 +625,7 @@ where
             replica: self.leader.id().clone(),
             leo: self.leader.leo(),
             hw: self.leader.hw(),
+            ..Default::default()
         };
 
         debug!(?offset_request, "sending offset to home");
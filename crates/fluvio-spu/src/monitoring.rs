@@ -60,8 +60,12 @@ async fn start_monitoring(ctx: DefaultSharedGlobalContext) -> Result<(), IoError
             // format a metrics object with all available metrics
             let out_metrics = json!({
                 "spu": {
+                    "profile": ctx.config().profile.as_str(),
                     "inbound": ctx.metrics().inbound(),
                     "outbound": ctx.metrics().outbound(),
+                    "write_coalesce": ctx.metrics().write_coalesce(),
+                    "stream_credits": ctx.metrics().stream_credits(),
+                    "read_cache": ctx.metrics().read_cache(),
                     "smartmodule": ctx.metrics().smartmodule_metrics(),
                 }
             });
@@ -0,0 +1,63 @@
+//!
+//! # Rebuild index
+//!
+//! Regenerate offset indexes of a partition stored by this SPU
+//!
+
+use std::process;
+
+use anyhow::{anyhow, Result};
+
+use fluvio_controlplane_metadata::partition::ReplicaKey;
+use fluvio_future::task::run_block_on;
+use fluvio_storage::config::ReplicaConfig;
+use fluvio_storage::index_rebuild::rebuild_indexes;
+use fluvio_types::print_cli_err;
+
+use crate::config::{RebuildIndexOpt, SpuConfig};
+
+pub(crate) fn rebuild_index_or_exit(opt: RebuildIndexOpt, spu_config: &SpuConfig) {
+    if let Err(err) = run_block_on(rebuild_index(opt, spu_config)) {
+        print_cli_err!(err);
+        process::exit(-1);
+    }
+}
+
+async fn rebuild_index(opt: RebuildIndexOpt, spu_config: &SpuConfig) -> Result<()> {
+    let replica: ReplicaKey = opt
+        .partition
+        .parse()
+        .map_err(|err| anyhow!("invalid partition {}: {err}", opt.partition))?;
+    let replica_config = ReplicaConfig::from(spu_config);
+    let replica_dir = replica_config
+        .base_dir
+        .join(format!("{}-{}", replica.topic, replica.partition));
+    if !replica_dir.is_dir() {
+        return Err(anyhow!(
+            "partition {replica} not found at {}",
+            replica_dir.display()
+        ));
+    }
+
+    let reports = rebuild_indexes(
+        &replica_dir,
+        replica_config.index_max_interval_bytes,
+        opt.dry_run,
+    )
+    .await?;
+
+    for report in &reports {
+        println!("{report}");
+    }
+    let with_discrepancies = reports
+        .iter()
+        .filter(|report| report.has_discrepancies())
+        .count();
+    let rewritten = reports.iter().filter(|report| report.rewritten).count();
+    println!(
+        "{} segments checked, {with_discrepancies} with discrepancies, {rewritten} indexes rewritten{}",
+        reports.len(),
+        if opt.dry_run { " (dry run)" } else { "" }
+    );
+    Ok(())
+}
@@ -11,10 +11,11 @@ use crate::core::FileGlobalContext;
 use super::FollowersState;
 use super::state::{SharedFollowersState, FollowerReplicaState};
 use super::api_key::FollowerPeerApiEnum;
-use super::sync::DefaultSyncRequest;
+use super::sync::{DefaultSyncRequest, LEADER_EPOCH_VERSION};
 use super::peer_api::FollowerPeerRequest;
+use super::session::FetchSession;
 
-/// time to resync follower offsets to leader
+/// time to resync all follower offsets to leader, between resyncs only changed offsets are sent
 const LEADER_RECONCILIATION_INTERVAL_SEC: u64 = 60; // 1 min
 
 #[derive(Debug)]
@@ -93,7 +94,7 @@ mod inner {
     use fluvio_controlplane_metadata::spu::SpuSpec;
 
     use crate::{replication::leader::UpdateOffsetRequest, core::SharedSpuConfig};
-    use crate::services::internal::FetchStreamRequest;
+    use crate::services::internal::{FetchStreamRequest, PeerApiVersions};
     use crate::core::spus::SharedSpuLocalStore;
 
     static SHORT_RECONCILLATION: Lazy<u64> = Lazy::new(|| {
@@ -111,6 +112,8 @@ mod inner {
         states: SharedFollowersState<FileReplica>,
         config: SharedSpuConfig,
         group: Arc<GroupNotification>,
+        /// versions negotiated with leader on current connection
+        versions: PeerApiVersions,
     }
 
     impl FollowGroupController {
@@ -127,6 +130,7 @@ mod inner {
                 states,
                 group: spu_ctx,
                 config,
+                versions: PeerApiVersions::default(),
             };
             spawn(controller.dispatch_loop());
         }
@@ -186,10 +190,12 @@ mod inner {
             let mut api_stream = stream.api_stream::<FollowerPeerRequest, FollowerPeerApiEnum>();
 
             let mut event_listener = self.group.events.change_listener();
+            let mut session = FetchSession::default();
 
             // starts initial sync
             debug!("performing initial offset sync to leader");
-            self.sync_all_offsets_to_leader(&mut sink).await?;
+            self.sync_all_offsets_to_leader(&mut sink, &mut session)
+                .await?;
 
             let mut counter: i32 = 0;
 
@@ -201,7 +207,7 @@ mod inner {
                 select! {
                     _ = &mut timer => {
                         debug!("timer fired - kickoff sync offsets to leader");
-                        self.sync_all_offsets_to_leader(&mut sink).await?;
+                        self.sync_all_offsets_to_leader(&mut sink, &mut session).await?;
                         timer= sleep(Duration::from_secs(LEADER_RECONCILIATION_INTERVAL_SEC));
                     },
 
@@ -210,7 +216,7 @@ mod inner {
                             debug!("terminate signal");
                             return Ok(true);
                         }
-                        self.sync_all_offsets_to_leader(&mut sink).await?;
+                        self.sync_changed_offsets_to_leader(&mut sink, &mut session).await?;
                     }
 
 
@@ -219,7 +225,10 @@ mod inner {
                             let req_msg = req_msg_res?;
 
                             match req_msg {
-                                FollowerPeerRequest::SyncRecords(sync_request)=> self.sync_from_leader(&mut sink,sync_request.request).await?,
+                                FollowerPeerRequest::SyncRecords(sync_request)=> {
+                                    let has_epoch = sync_request.header.api_version() >= LEADER_EPOCH_VERSION;
+                                    self.sync_from_leader(&mut sink,&mut session,sync_request.request,has_epoch).await?
+                                },
                                 FollowerPeerRequest::RejectedOffsetRequest(requests) => {
                                     debug!(fail_req = ?requests,"leader rejected these requests");
                                     timer= sleep(Duration::from_secs(*SHORT_RECONCILLATION));
@@ -251,11 +260,16 @@ mod inner {
             }
         }
 
-        #[instrument(skip(self, req))]
+        /// apply records from leader.
+        /// if leader's epoch is older than known epoch, leader has been deposed and records are rejected.
+        /// our offsets with newer epoch are sent back so leader can fence itself
+        #[instrument(skip(self, session, req))]
         async fn sync_from_leader(
             &self,
             sink: &mut FluvioSink,
+            session: &mut FetchSession,
             mut req: DefaultSyncRequest,
+            has_epoch: bool,
         ) -> Result<(), SocketError> {
             let mut offsets = UpdateOffsetRequest::default();
 
@@ -272,6 +286,16 @@ mod inner {
                     base_offset = p.records.base_offset(),
                     "update from leader");
                     if let Some(replica) = self.states.get(&replica_key).await {
+                        if has_epoch && replica.is_stale_leader(p.leader_epoch) {
+                            warn!(
+                                replica = %replica_key,
+                                leader_epoch = p.leader_epoch,
+                                known_epoch = replica.leader_epoch(),
+                                "rejecting records from stale leader"
+                            );
+                            offsets.replicas.push(replica.as_offset_request());
+                            continue;
+                        }
                         match replica.update_from_leader(&mut p.records, p.hw).await {
                             Ok(changes) => {
                                 if changes {
@@ -285,6 +309,15 @@ mod inner {
                                 error!("problem updating {}, error: {:#?}", replica_key, err)
                             }
                         }
+                        // leader before version 9 doesn't send log start, which decodes as 0
+                        if let Err(err) = replica.update_log_start(p.log_start_offset).await {
+                            error!(
+                                replica = %replica_key,
+                                leader_log_start = p.log_start_offset,
+                                %err,
+                                "unable to delete records deleted on leader"
+                            );
+                        }
                     } else {
                         error!(
                             "unable to find follower replica for writing: {}",
@@ -295,6 +328,7 @@ mod inner {
             }
 
             if !offsets.replicas.is_empty() {
+                session.record(&offsets.replicas);
                 self.send_offsets_to_leader(sink, offsets).await
             } else {
                 Ok(())
@@ -321,10 +355,15 @@ mod inner {
                 match FluvioSocket::connect(&leader_endpoint).await {
                     Ok(mut socket) => {
                         debug!("connected to leader");
+                        if let Err(err) = socket.apply_tuning(&self.config.socket) {
+                            warn!(%err, "unable to apply socket options");
+                        }
 
                         match self.send_fetch_stream_request(&mut socket).await {
-                            Ok(Some(spu)) => {
+                            Ok(Some((spu, versions))) => {
                                 info!("connected to leader with spu {} as replica", spu);
+                                debug!(?versions, "peer versions");
+                                self.versions = versions;
                                 return socket;
                             }
                             Ok(None) => {
@@ -360,12 +399,13 @@ mod inner {
         async fn send_fetch_stream_request(
             &self,
             socket: &mut FluvioSocket,
-        ) -> Result<Option<SpuId>, SocketError> {
+        ) -> Result<Option<(SpuId, PeerApiVersions)>, SocketError> {
             let local_spu_id = self.local_spu_id();
             debug!("sending fetch stream for leader",);
             let fetch_request = FetchStreamRequest {
                 spu_id: local_spu_id,
                 leader_spu_id: self.leader,
+                peer_versions: PeerApiVersions::current(),
                 ..Default::default()
             };
             let mut message = RequestMessage::new_request(fetch_request);
@@ -376,16 +416,38 @@ mod inner {
             let response = socket.send(&message).await?;
             trace!(?response, "follower: fetch stream response",);
             debug!("follower: established peer to peer channel to leader",);
-            Ok(response.response.spu_id)
+            let versions = PeerApiVersions::negotiate(&response.response.peer_versions);
+            Ok(response.response.spu_id.map(|spu| (spu, versions)))
         }
 
+        /// send offsets of all replicas and reset session
         async fn sync_all_offsets_to_leader(
             &self,
             sink: &mut FluvioSink,
+            session: &mut FetchSession,
+        ) -> Result<(), SocketError> {
+            let spu_replicas = FollowerGroup::filter_from(&self.states, self.leader).await;
+            let offsets = session.full(spu_replicas.replica_offsets());
+            self.send_offsets_to_leader(sink, offsets).await
+        }
+
+        /// send offsets of replicas which have changed since last sent in this session
+        async fn sync_changed_offsets_to_leader(
+            &self,
+            sink: &mut FluvioSink,
+            session: &mut FetchSession,
         ) -> Result<(), SocketError> {
             let spu_replicas = FollowerGroup::filter_from(&self.states, self.leader).await;
-            self.send_offsets_to_leader(sink, spu_replicas.replica_offsets())
-                .await
+            let offsets = session.incremental(spu_replicas.replica_offsets());
+            debug!(
+                changed = offsets.replicas.len(),
+                session = session.len(),
+                "incremental offset sync"
+            );
+            if offsets.replicas.is_empty() {
+                return Ok(());
+            }
+            self.send_offsets_to_leader(sink, offsets).await
         }
 
         /// send offset to leader
@@ -397,8 +459,11 @@ mod inner {
         ) -> Result<(), SocketError> {
             let local_spu = self.config.id();
             debug!(local_spu, "sending offsets to leader");
-            let req_msg = RequestMessage::new_request(offsets)
+            let mut req_msg = RequestMessage::new_request(offsets)
                 .set_client_id(format!("follower spu: {local_spu}"));
+            req_msg
+                .get_mut_header()
+                .set_api_version(self.versions.update_offsets);
 
             sink.send_request(&req_msg).await
         }
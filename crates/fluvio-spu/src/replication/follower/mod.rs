@@ -3,6 +3,7 @@ mod api_key;
 mod peer_api;
 mod controller;
 mod reject_request;
+mod session;
 pub mod sync;
 
 pub use self::state::{FollowersState, SharedFollowersState, FollowerReplicaState};
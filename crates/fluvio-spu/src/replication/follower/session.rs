@@ -0,0 +1,131 @@
+use std::collections::{HashMap, HashSet};
+
+use fluvio_protocol::record::{ReplicaKey, Offset};
+
+use crate::replication::leader::{ReplicaOffsetRequest, UpdateOffsetRequest};
+
+/// Fetch session between follower and leader.
+///
+/// Tracks offsets that has been sent to leader over current connection so that
+/// subsequent requests only include replicas whose offsets have changed.
+/// Session is bound to a connection; new connection starts with empty session
+/// so first request always contains full set of replicas.
+#[derive(Debug, Default)]
+pub(crate) struct FetchSession {
+    sent: HashMap<ReplicaKey, SentOffsets>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+struct SentOffsets {
+    leo: Offset,
+    hw: Offset,
+    leader_epoch: i32,
+}
+
+impl From<&ReplicaOffsetRequest> for SentOffsets {
+    fn from(request: &ReplicaOffsetRequest) -> Self {
+        Self {
+            leo: request.leo,
+            hw: request.hw,
+            leader_epoch: request.leader_epoch,
+        }
+    }
+}
+
+impl FetchSession {
+    /// number of replicas known to session
+    pub fn len(&self) -> usize {
+        self.sent.len()
+    }
+
+    /// remove replicas which are no longer followed and return only
+    /// replicas whose offsets differ from what was last sent
+    pub fn incremental(&mut self, request: UpdateOffsetRequest) -> UpdateOffsetRequest {
+        let current: HashSet<&ReplicaKey> = request.replicas.iter().map(|r| &r.replica).collect();
+        self.sent.retain(|key, _| current.contains(key));
+
+        let replicas = request
+            .replicas
+            .into_iter()
+            .filter(|replica| self.sent.get(&replica.replica) != Some(&SentOffsets::from(replica)))
+            .collect::<Vec<_>>();
+
+        self.record(&replicas);
+        UpdateOffsetRequest { replicas }
+    }
+
+    /// replace session with full set of replicas
+    pub fn full(&mut self, request: UpdateOffsetRequest) -> UpdateOffsetRequest {
+        self.sent.clear();
+        self.record(&request.replicas);
+        request
+    }
+
+    /// record offsets sent outside of session computation
+    pub fn record(&mut self, replicas: &[ReplicaOffsetRequest]) {
+        for replica in replicas {
+            self.sent
+                .insert(replica.replica.clone(), SentOffsets::from(replica));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    fn offset(partition: u32, leo: Offset, hw: Offset) -> ReplicaOffsetRequest {
+        ReplicaOffsetRequest {
+            replica: ReplicaKey::new("test", partition),
+            leo,
+            hw,
+            leader_epoch: 0,
+        }
+    }
+
+    fn request(replicas: Vec<ReplicaOffsetRequest>) -> UpdateOffsetRequest {
+        UpdateOffsetRequest { replicas }
+    }
+
+    #[test]
+    fn test_fetch_session_incremental() {
+        let mut session = FetchSession::default();
+
+        // first request contains everything
+        let first = session.incremental(request(vec![offset(0, 10, 10), offset(1, 5, 5)]));
+        assert_eq!(first.replicas.len(), 2);
+        assert_eq!(session.len(), 2);
+
+        // nothing changed
+        let idle = session.incremental(request(vec![offset(0, 10, 10), offset(1, 5, 5)]));
+        assert!(idle.replicas.is_empty());
+
+        // only changed partition is included
+        let changed = session.incremental(request(vec![offset(0, 10, 10), offset(1, 6, 5)]));
+        assert_eq!(changed.replicas.len(), 1);
+        assert_eq!(changed.replicas[0].replica, ReplicaKey::new("test", 1_u32));
+
+        // removed replica is dropped from session
+        let removed = session.incremental(request(vec![offset(1, 6, 5)]));
+        assert!(removed.replicas.is_empty());
+        assert_eq!(session.len(), 1);
+
+        // re-added replica is sent again
+        let added = session.incremental(request(vec![offset(0, 10, 10), offset(1, 6, 5)]));
+        assert_eq!(added.replicas.len(), 1);
+    }
+
+    #[test]
+    fn test_fetch_session_full_and_record() {
+        let mut session = FetchSession::default();
+        session.record(&[offset(0, 3, 3)]);
+
+        let idle = session.incremental(request(vec![offset(0, 3, 3)]));
+        assert!(idle.replicas.is_empty());
+
+        let full = session.full(request(vec![offset(0, 3, 3), offset(1, 1, 1)]));
+        assert_eq!(full.replicas.len(), 2);
+        assert_eq!(session.len(), 2);
+    }
+}
@@ -80,6 +80,7 @@ impl FollowersState<FileReplica> {
         replica: Replica,
     ) -> Result<Option<FollowerReplicaState<FileReplica>>> {
         let leader = replica.leader;
+        let leader_epoch = replica.leader_epoch;
 
         let mut writer = self.write().await;
         match writer.entry(replica.id.clone()) {
@@ -94,11 +95,12 @@ impl FollowersState<FileReplica> {
                     "creating new follower state"
                 );
 
-                let mut replica_config: ReplicaConfig = ctx.config().into();
+                let mut replica_config: ReplicaConfig = (&ctx.effective_config()).into();
                 replica_config.update_from_replica(&replica);
 
                 let replica_state =
-                    FollowerReplicaState::create(leader, replica.id, replica_config).await?;
+                    FollowerReplicaState::create(leader, leader_epoch, replica.id, replica_config)
+                        .await?;
 
                 entry.insert(replica_state.clone());
                 self.groups.check_new(ctx, leader).await;
@@ -139,7 +141,21 @@ impl FollowersState<FileReplica> {
         }
     }
 
-    pub async fn update_replica(&self, _replica: Replica) {}
+    /// apply replica metadata change without leader change
+    pub async fn update_replica(&self, replica: Replica) {
+        let mut writer = self.write().await;
+        if let Some(state) = writer.get_mut(&replica.id) {
+            if state.leader_epoch < replica.leader_epoch {
+                debug!(
+                    replica = %replica.id,
+                    leader_epoch = replica.leader_epoch,
+                    "updating leader epoch"
+                );
+                state.leader_epoch = replica.leader_epoch;
+            }
+            state.inner.update_config(&replica).await;
+        }
+    }
 }
 
 /// State for Follower Replica Controller
@@ -147,6 +163,7 @@ impl FollowersState<FileReplica> {
 #[derive(Debug)]
 pub struct FollowerReplicaState<S> {
     leader: SpuId,
+    leader_epoch: i32,
     inner: SharableReplicaStorage<S>,
 }
 
@@ -154,6 +171,7 @@ impl<S> Clone for FollowerReplicaState<S> {
     fn clone(&self) -> Self {
         Self {
             leader: self.leader,
+            leader_epoch: self.leader_epoch,
             inner: self.inner.clone(),
         }
     }
@@ -179,6 +197,7 @@ where
 {
     pub async fn create(
         leader: SpuId,
+        leader_epoch: i32,
         replica_key: ReplicaKey,
         config: S::ReplicaConfig,
     ) -> Result<Self>
@@ -188,14 +207,18 @@ where
         debug!(
             %replica_key,
             leader,
+            leader_epoch,
             %config,
             "created follower replica"
         );
 
         let replica_storage = SharableReplicaStorage::create(replica_key, config).await?;
+        // followers never serve reads, keep the shared read cache for leaders
+        replica_storage.write().await.set_read_cache_enabled(false);
 
         Ok(Self {
             leader,
+            leader_epoch,
             inner: replica_storage,
         })
     }
@@ -204,6 +227,16 @@ where
         self.leader
     }
 
+    /// leader epoch known from SC
+    pub fn leader_epoch(&self) -> i32 {
+        self.leader_epoch
+    }
+
+    /// true if sync from leader with given epoch must be rejected
+    pub fn is_stale_leader(&self, leader_epoch: i32) -> bool {
+        leader_epoch < self.leader_epoch
+    }
+
     /// update from leader with new record set
     pub async fn update_from_leader<R: BatchRecords>(
         &self,
@@ -243,6 +276,17 @@ where
         Ok(changes)
     }
 
+    /// delete records which were deleted on leader.
+    /// records above our hw are kept, they are deleted on later sync
+    pub async fn update_log_start(&self, leader_log_start: Offset) -> Result<()> {
+        let log_start = leader_log_start.min(self.hw());
+        if log_start > self.log_start_offset().await {
+            debug!(log_start, "deleting records deleted on leader");
+            self.delete_records_before(log_start).await?;
+        }
+        Ok(())
+    }
+
     /// try to write records
     /// ensure records has correct baseoffset
     async fn write_recordsets<R: BatchRecords>(&self, records: &mut RecordSet<R>) -> Result<bool> {
@@ -267,6 +311,7 @@ where
             replica: self.inner.id().to_owned(),
             leo: self.leo(),
             hw: self.hw(),
+            leader_epoch: self.leader_epoch,
         }
     }
 
@@ -301,7 +346,7 @@ mod follower_tests {
         };
 
         let follower_replica: FollowerReplicaState<FileReplica> =
-            FollowerReplicaState::create(LEADER, TEST_REPLICA.into(), config)
+            FollowerReplicaState::create(LEADER, 0, TEST_REPLICA.into(), config)
                 .await
                 .expect("create");
 
@@ -310,4 +355,25 @@ mod follower_tests {
         assert_eq!(follower_replica.hw(), 0);
         assert!(PathBuf::from(test_path).join("spu-5002").exists());
     }
+
+    #[fluvio_future::test]
+    async fn test_follower_stale_leader() {
+        let test_path = "/tmp/follower_stale_leader";
+        ensure_clean_dir(test_path);
+
+        let config = ReplicaConfig {
+            base_dir: PathBuf::from(test_path).join("spu-5002"),
+            ..Default::default()
+        };
+
+        let follower_replica: FollowerReplicaState<FileReplica> =
+            FollowerReplicaState::create(LEADER, 2, TEST_REPLICA.into(), config)
+                .await
+                .expect("create");
+
+        assert!(follower_replica.is_stale_leader(1));
+        assert!(!follower_replica.is_stale_leader(2));
+        assert!(!follower_replica.is_stale_leader(3));
+        assert_eq!(follower_replica.as_offset_request().leader_epoch, 2);
+    }
 }
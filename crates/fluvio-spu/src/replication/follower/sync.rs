@@ -26,6 +26,15 @@ pub type DefaultSyncRequest = SyncRequest<RecordSet<RawRecords>>;
 pub type PeerFilePartitionResponse = PeerFetchablePartitionResponse<FileRecordSet>;
 pub type PeerFileTopicResponse = PeerFetchableTopicResponse<FileRecordSet>;
 
+/// version of sync request sent to SPUs which don't exchange peer versions
+pub const BASE_SYNC_VERSION: i16 = 7;
+
+/// version of sync request which carries leader epoch
+pub const LEADER_EPOCH_VERSION: i16 = 8;
+
+/// version of sync request which carries leader log start offset
+pub const LOG_START_VERSION: i16 = 9;
+
 /// used for sending records and commits
 /// re purpose topic response since it has records and commit offsets
 #[derive(Default, Encoder, Decoder, Debug)]
@@ -50,14 +59,16 @@ where
 }
 
 // Request trait
-// Note that DEFAULT_API_VERSION is 7 which is required in order to map all fields for file encoding
+// Note that DEFAULT_API_VERSION must be at least 7 which is required in order to map all fields for file encoding
+// version 8 adds leader epoch
+// version 9 adds log start offset
 // TODO: come up with unify encoding
 impl<R> Request for SyncRequest<R>
 where
     R: Encoder + Decoder + Debug,
 {
     const API_KEY: u16 = FollowerPeerApiEnum::SyncRecords as u16;
-    const DEFAULT_API_VERSION: i16 = 7;
+    const DEFAULT_API_VERSION: i16 = LOG_START_VERSION;
     type Response = SyncResponse;
 }
 
@@ -110,6 +121,11 @@ where
     pub error: ErrorCode,
     pub hw: i64,
     pub leo: i64,
+    #[fluvio(min_version = 8)]
+    pub leader_epoch: i32,
+    /// records before this offset are deleted on leader
+    #[fluvio(min_version = 9)]
+    pub log_start_offset: i64,
     pub records: R,
 }
 
@@ -120,8 +136,8 @@ where
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "partition: {}, hw: {} {}",
-            self.partition, self.hw, self.records
+            "partition: {}, hw: {}, epoch: {} {}",
+            self.partition, self.hw, self.leader_epoch, self.records
         )
     }
 }
@@ -152,6 +168,12 @@ impl FileWrite for PeerFilePartitionResponse {
         self.error.encode(src, version)?;
         self.hw.encode(src, version)?;
         self.leo.encode(src, version)?;
+        if version >= LEADER_EPOCH_VERSION {
+            self.leader_epoch.encode(src, version)?;
+        }
+        if version >= LOG_START_VERSION {
+            self.log_start_offset.encode(src, version)?;
+        }
         self.records.file_encode(src, data, version)?;
         Ok(())
     }
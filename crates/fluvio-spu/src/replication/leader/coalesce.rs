@@ -0,0 +1,172 @@
+use std::fmt;
+use std::sync::Mutex;
+
+use anyhow::Result;
+use async_channel::{Sender, Receiver, bounded};
+
+use fluvio_protocol::Encoder;
+use fluvio_protocol::record::{Batch, RawRecords, RecordSet, Offset};
+
+/// base offset, leo and bytes written
+pub(crate) type WriteResult = Result<(Offset, Offset, usize)>;
+
+/// Produce writes to a partition waiting to be appended together.
+/// First write in a group schedules the flush; writes arriving before the flush join the group.
+#[derive(Default)]
+pub(crate) struct WriteCoalescer {
+    pending: Mutex<Vec<PendingWrite>>,
+}
+
+impl fmt::Debug for WriteCoalescer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pending = self.pending.lock().map(|p| p.len()).unwrap_or_default();
+        write!(f, "WriteCoalescer {{ pending: {pending} }}")
+    }
+}
+
+pub(crate) struct PendingWrite {
+    records: RecordSet<RawRecords>,
+    sender: Sender<WriteResult>,
+}
+
+impl PendingWrite {
+    pub fn reply(&self, result: WriteResult) {
+        // receiver is gone if producer has been dropped
+        let _ = self.sender.try_send(result);
+    }
+}
+
+impl WriteCoalescer {
+    /// add records to pending writes.
+    /// returns receiver of write result and true if caller is responsible for scheduling flush
+    pub fn enqueue(&self, records: RecordSet<RawRecords>) -> (Receiver<WriteResult>, bool) {
+        let (sender, receiver) = bounded(1);
+        let mut pending = self.pending.lock().unwrap();
+        pending.push(PendingWrite { records, sender });
+        (receiver, pending.len() == 1)
+    }
+
+    /// take all pending writes, next enqueue will start new group
+    pub fn take(&self) -> Vec<PendingWrite> {
+        std::mem::take(&mut *self.pending.lock().unwrap())
+    }
+}
+
+/// Record set built from group of pending writes
+pub(crate) struct CoalescedWrite {
+    pub writes: Vec<PendingWrite>,
+    pub records: RecordSet<RawRecords>,
+    batch_counts: Vec<usize>,
+}
+
+impl CoalescedWrite {
+    pub fn new(mut writes: Vec<PendingWrite>) -> Self {
+        let mut records = RecordSet::default();
+        let mut batch_counts = Vec::with_capacity(writes.len());
+        for write in &mut writes {
+            batch_counts.push(write.records.batches.len());
+            records.batches.append(&mut write.records.batches);
+        }
+        Self {
+            writes,
+            records,
+            batch_counts,
+        }
+    }
+
+    /// offsets assigned to each write after record set has been appended
+    pub fn offsets(&self) -> Vec<(Offset, Offset, usize)> {
+        split_batches(&self.records.batches, &self.batch_counts)
+            .map(|batches| {
+                let base_offset = batches.first().map(|b| b.get_base_offset()).unwrap_or(0);
+                let leo = batches
+                    .last()
+                    .map(|b| b.get_last_offset() + 1)
+                    .unwrap_or(base_offset);
+                let bytes = batches.iter().map(|b| b.write_size(0)).sum();
+                (base_offset, leo, bytes)
+            })
+            .collect()
+    }
+
+    /// split back into original record sets, used when coalesced write is rejected
+    pub fn into_record_sets(self) -> Vec<(PendingWrite, RecordSet<RawRecords>)> {
+        let mut batches = self.records.batches.into_iter();
+        self.writes
+            .into_iter()
+            .zip(self.batch_counts)
+            .map(|(write, count)| {
+                let records = RecordSet {
+                    batches: batches.by_ref().take(count).collect(),
+                };
+                (write, records)
+            })
+            .collect()
+    }
+}
+
+fn split_batches<'a>(
+    batches: &'a [Batch<RawRecords>],
+    counts: &'a [usize],
+) -> impl Iterator<Item = &'a [Batch<RawRecords>]> {
+    counts.iter().scan(0, move |start, count| {
+        let slice = &batches[*start..*start + count];
+        *start += count;
+        Some(slice)
+    })
+}
+
+#[cfg(test)]
+mod test {
+
+    use fluvio_protocol::record::Record;
+
+    use super::*;
+
+    fn records(count: usize) -> RecordSet<RawRecords> {
+        let mut batch = Batch::default();
+        for _ in 0..count {
+            batch.add_record(Record::new("value"));
+        }
+        RecordSet::default().add(batch).try_into().expect("raw")
+    }
+
+    #[test]
+    fn test_coalescer_groups() {
+        let coalescer = WriteCoalescer::default();
+        let (_r1, first) = coalescer.enqueue(records(1));
+        assert!(first);
+        let (_r2, second) = coalescer.enqueue(records(2));
+        assert!(!second);
+
+        assert_eq!(coalescer.take().len(), 2);
+        let (_r3, first) = coalescer.enqueue(records(1));
+        assert!(first);
+    }
+
+    #[test]
+    fn test_coalesced_offsets() {
+        let coalescer = WriteCoalescer::default();
+        let _r1 = coalescer.enqueue(records(2));
+        let _r2 = coalescer.enqueue(records(3).add(records(1).batches.remove(0)));
+
+        let mut write = CoalescedWrite::new(coalescer.take());
+        assert_eq!(write.records.batches.len(), 3);
+
+        // simulate offsets assigned by storage append
+        let mut leo = 10;
+        for batch in &mut write.records.batches {
+            batch.set_base_offset(leo);
+            leo = batch.get_last_offset() + 1;
+        }
+
+        let offsets = write.offsets();
+        assert_eq!(offsets.len(), 2);
+        assert_eq!((offsets[0].0, offsets[0].1), (10, 12));
+        assert_eq!((offsets[1].0, offsets[1].1), (12, 16));
+
+        let sets = write.into_record_sets();
+        assert_eq!(sets[0].1.total_records(), 2);
+        assert_eq!(sets[1].1.total_records(), 4);
+    }
+}
@@ -10,6 +10,7 @@ use fluvio_protocol::api::RequestMessage;
 use fluvio_types::SpuId;
 
 use crate::{core::DefaultSharedGlobalContext, replication::follower::sync::FileSyncRequest};
+use crate::services::internal::PeerApiVersions;
 
 use super::LeaderPeerApiEnum;
 use super::LeaderPeerRequest;
@@ -23,7 +24,8 @@ use super::super::follower::RejectOffsetRequest;
 pub struct FollowerHandler {
     ctx: DefaultSharedGlobalContext,
     follower_id: SpuId,
-    max_bytes: u32,
+    /// versions negotiated with follower
+    versions: PeerApiVersions,
     spu_update: SharedSpuPendingUpdate,
 }
 
@@ -38,14 +40,15 @@ impl FollowerHandler {
     pub async fn start(
         ctx: DefaultSharedGlobalContext,
         follower_id: SpuId,
+        versions: PeerApiVersions,
         spu_update: SharedSpuPendingUpdate,
         sink: FluvioSink,
         stream: FluvioStream,
     ) {
         let connection = Self {
             ctx: ctx.clone(),
-            max_bytes: ctx.config().peer_max_bytes,
             follower_id,
+            versions,
             spu_update,
         };
 
@@ -134,12 +137,13 @@ impl FollowerHandler {
 
         let mut sync_request = FileSyncRequest::default();
         let leaders = self.ctx.leaders_state();
+        // read on every sync so runtime config changes apply to existing followers
+        let max_bytes = self.ctx.effective_config().peer_max_bytes;
 
         for replica in replicas {
             if let Some(leader) = leaders.get(&replica).await {
-                if let Some(topic_response) = leader
-                    .follower_updates(&self.follower_id, self.max_bytes)
-                    .await
+                if let Some(topic_response) =
+                    leader.follower_updates(&self.follower_id, max_bytes).await
                 {
                     sync_request.topics.push(topic_response);
                 }
@@ -154,8 +158,9 @@ impl FollowerHandler {
         if sync_request.topics.is_empty() {
             debug!("no topics found, skipping");
         } else {
-            let request = RequestMessage::new_request(sync_request)
+            let mut request = RequestMessage::new_request(sync_request)
                 .set_client_id(format!("leader: {}", self.ctx.local_spu_id()));
+            request.get_mut_header().set_api_version(self.versions.sync);
             sink.encode_file_slices(&request, request.header.api_version())
                 .await?;
         }
@@ -174,6 +179,11 @@ impl FollowerHandler {
             debug!(?update, "request");
             let replica_key = update.replica;
             if let Some(leader) = self.ctx.leaders_state().get(&replica_key).await {
+                // follower knows about newer leader, we must not ack any writes
+                if leader.fence_if_stale(update.leader_epoch) {
+                    debug!(replica = %leader.id(), "leader is fenced, ignoring follower offsets");
+                    continue;
+                }
                 let status = leader
                     .update_states_from_followers(
                         self.follower_id,
@@ -54,7 +54,6 @@ impl<S> ReplicaLeadersState<S> {
         writer.remove(replica)
     }
 
-    #[allow(unused)]
     pub async fn insert(
         &self,
         replica: ReplicaKey,
@@ -121,7 +120,7 @@ impl ReplicaLeadersState<FileReplica> {
         let replica_id = replica.id.clone();
 
         let leader_replica =
-            LeaderReplicaState::create(replica, ctx.config(), status_update).await?;
+            LeaderReplicaState::create(replica, &ctx.effective_config(), status_update).await?;
         let leader_replica = leader_replica.init(ctx).await?;
         self.insert_leader(replica_id, leader_replica.clone()).await;
         Ok(leader_replica)
@@ -160,6 +159,7 @@ impl ReplicaLeadersState<FileReplica> {
     ) -> Result<LeaderReplicaState<FileReplica>> {
         let replica_id = replica.id.clone();
         let replica_storage = follower.inner_owned();
+        replica_storage.write().await.set_read_cache_enabled(true);
         let leader = LeaderReplicaState::new(replica, config, status_update, replica_storage);
         let leader = leader.init(ctx).await?;
         self.insert_leader(replica_id, leader.clone()).await;
@@ -7,6 +7,8 @@ mod update_offsets;
 mod actions;
 mod spu;
 mod kv;
+mod coalesce;
+mod progress;
 
 pub use self::leaders_state::{ReplicaLeadersState, SharedReplicaLeadersState};
 pub use self::replica_state::{SharedFileLeaderState, SharedLeaderState, LeaderReplicaState};
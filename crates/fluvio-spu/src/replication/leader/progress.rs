@@ -0,0 +1,198 @@
+//!
+//! # Follower progress
+//!
+//! Leader keeps when each follower last reported its offsets and when it last had all
+//! records of leader, with latest changes of its in sync state, to diagnose under-replicated partitions.
+//! This is only for diagnostics and doesn't affect high watermark computation.
+//!
+use std::collections::{BTreeMap, VecDeque};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use tracing::info;
+
+use fluvio_spu_schema::server::replication_status::{FollowerStatus, SyncTransition, NO_TIME};
+use fluvio_storage::OffsetInfo;
+use fluvio_types::SpuId;
+
+/// in sync transitions kept for each follower
+const MAX_TRANSITIONS: usize = 10;
+
+#[derive(Debug, Default)]
+pub(crate) struct ReplicationProgress {
+    followers: BTreeMap<SpuId, FollowerProgress>,
+}
+
+#[derive(Debug, Default)]
+struct FollowerProgress {
+    last_fetch: Option<Instant>,
+    /// last time follower had all records of leader
+    caught_up: Option<Instant>,
+    in_sync: bool,
+    transitions: VecDeque<SyncTransition>,
+}
+
+impl ReplicationProgress {
+    /// record offsets reported by follower.
+    /// follower is in sync when it has all committed records of leader
+    pub(crate) fn record(
+        &mut self,
+        follower_id: SpuId,
+        follower_pos: &OffsetInfo,
+        leader_pos: &OffsetInfo,
+        now: Instant,
+    ) {
+        let progress = self.followers.entry(follower_id).or_default();
+        progress.last_fetch = Some(now);
+        if follower_pos.leo >= leader_pos.leo {
+            progress.caught_up = Some(now);
+        }
+
+        let in_sync = follower_pos.leo >= leader_pos.hw;
+        if in_sync != progress.in_sync || progress.transitions.is_empty() {
+            if in_sync != progress.in_sync {
+                info!(
+                    follower_id,
+                    in_sync,
+                    ?follower_pos,
+                    ?leader_pos,
+                    "follower sync changed"
+                );
+            }
+            progress.in_sync = in_sync;
+            if progress.transitions.len() == MAX_TRANSITIONS {
+                progress.transitions.pop_front();
+            }
+            progress.transitions.push_back(SyncTransition {
+                timestamp: epoch_millis(),
+                in_sync,
+            });
+        }
+    }
+
+    /// status of follower with its last known offsets
+    pub(crate) fn status(
+        &self,
+        follower_id: SpuId,
+        follower_pos: &OffsetInfo,
+        leader_pos: &OffsetInfo,
+        now: Instant,
+    ) -> FollowerStatus {
+        let progress = self.followers.get(&follower_id);
+        let since = |instant: Option<Instant>| {
+            instant.map_or(NO_TIME, |instant| {
+                now.saturating_duration_since(instant).as_millis() as i64
+            })
+        };
+        let lag_records = (leader_pos.leo - follower_pos.leo.max(0)).max(0);
+        FollowerStatus {
+            spu: follower_id,
+            leo: follower_pos.leo,
+            hw: follower_pos.hw,
+            lag_records,
+            lag_ms: if lag_records == 0 {
+                0
+            } else {
+                since(progress.and_then(|progress| progress.caught_up))
+            },
+            last_fetch_ms: since(progress.and_then(|progress| progress.last_fetch)),
+            in_sync: progress.is_some_and(|progress| progress.in_sync),
+            transitions: progress
+                .map(|progress| progress.transitions.iter().cloned().collect())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+fn epoch_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_millis() as i64)
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_follower_progress() {
+        let mut progress = ReplicationProgress::default();
+        let start = Instant::now();
+
+        let never = progress.status(
+            5002,
+            &OffsetInfo::default(),
+            &OffsetInfo::new(10, 10),
+            start,
+        );
+        assert_eq!(never.lag_records, 10);
+        assert_eq!(never.lag_ms, NO_TIME);
+        assert_eq!(never.last_fetch_ms, NO_TIME);
+        assert!(!never.in_sync);
+
+        progress.record(
+            5002,
+            &OffsetInfo::new(10, 10),
+            &OffsetInfo::new(10, 10),
+            start,
+        );
+        let status = progress.status(
+            5002,
+            &OffsetInfo::new(10, 10),
+            &OffsetInfo::new(10, 10),
+            start + Duration::from_millis(50),
+        );
+        assert_eq!(status.lag_records, 0);
+        assert_eq!(status.lag_ms, 0);
+        assert_eq!(status.last_fetch_ms, 50);
+        assert!(status.in_sync);
+        assert_eq!(status.transitions.len(), 1);
+
+        // leader moves on and commits, follower is stuck
+        let later = start + Duration::from_secs(1);
+        progress.record(
+            5002,
+            &OffsetInfo::new(10, 10),
+            &OffsetInfo::new(30, 20),
+            later,
+        );
+        let status = progress.status(
+            5002,
+            &OffsetInfo::new(10, 10),
+            &OffsetInfo::new(30, 20),
+            later + Duration::from_secs(1),
+        );
+        assert_eq!(status.lag_records, 20);
+        assert_eq!(status.lag_ms, 2000);
+        assert_eq!(status.last_fetch_ms, 1000);
+        assert!(!status.in_sync);
+        assert_eq!(
+            status
+                .transitions
+                .iter()
+                .map(|transition| transition.in_sync)
+                .collect::<Vec<_>>(),
+            vec![true, false]
+        );
+
+        for i in 0..MAX_TRANSITIONS as i64 {
+            let leo = if i % 2 == 0 { 20 } else { 10 };
+            progress.record(
+                5002,
+                &OffsetInfo::new(leo, 10),
+                &OffsetInfo::new(30, 20),
+                later,
+            );
+        }
+        let status = progress.status(
+            5002,
+            &OffsetInfo::new(10, 10),
+            &OffsetInfo::new(30, 20),
+            later,
+        );
+        assert_eq!(status.transitions.len(), MAX_TRANSITIONS);
+        assert!(!status.transitions.last().expect("transition").in_sync);
+    }
+}
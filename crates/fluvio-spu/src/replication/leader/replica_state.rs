@@ -3,30 +3,39 @@ use std::{
     collections::{BTreeMap, HashSet, BinaryHeap},
     ops::{Deref, DerefMut},
     sync::Arc,
+    time::{Duration, Instant},
 };
 use std::iter::FromIterator;
 use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicU16, Ordering};
 
 use async_lock::Mutex;
 use fluvio_controlplane::{replica::Replica, sc_api::update_lrs::LrsRequest};
 use tracing::{debug, error, warn};
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
 use tracing::instrument;
 use async_lock::RwLock;
-use anyhow::{Result, Context};
+use anyhow::{Result, Context, anyhow};
 
+use fluvio_protocol::Encoder;
 use fluvio_protocol::record::{RecordSet, Offset, ReplicaKey, RawRecords, Batch};
 use fluvio_controlplane_metadata::partition::{PartitionMirrorConfig, PartitionStatus, ReplicaStatus};
-use fluvio_storage::{FileReplica, ReplicaStorage, OffsetInfo, ReplicaStorageConfig};
+use fluvio_storage::{FileReplica, ReplicaStorage, OffsetInfo, ReplicaStorageConfig, StorageError};
 use fluvio_types::{
     event::offsets::{SharedOffsetPublisher, WeakSharedOffsetPublisher, TOPIC_DELETED},
     SpuId,
 };
 use fluvio_spu_schema::{Isolation, COMMON_VERSION};
+use fluvio_spu_schema::server::replication_status::ReplicationStatus;
 
 use crate::{
     config::ReplicationConfig,
     control_plane::SharedLrsStatusUpdate,
-    core::GlobalContext,
+    core::{
+        GlobalContext,
+        metrics::{PartitionActivity, SpuMetrics},
+    },
     mirroring::remote::controller::{MirrorRemoteToHomeController, SharedMirrorControllerState},
     smartengine::{
         batch::process_record_set,
@@ -38,23 +47,51 @@ use crate::replication::follower::sync::{PeerFileTopicResponse, PeerFilePartitio
 use crate::storage::SharableReplicaStorage;
 
 use super::FollowerNotifier;
+use super::coalesce::{WriteCoalescer, CoalescedWrite};
+use super::progress::ReplicationProgress;
 
 pub type SharedLeaderState<S> = LeaderReplicaState<S>;
 pub type SharedFileLeaderState = LeaderReplicaState<FileReplica>;
 
 pub const CLEANUP_FREQUENCY: usize = 10;
 
+/// Leader epoch shared by all clones of leader state.
+/// Leader is fenced once a follower reports newer epoch, which means another leader has been elected
+#[derive(Debug)]
+struct LeaderEpoch {
+    epoch: AtomicI32,
+    fenced: AtomicBool,
+}
+
+impl LeaderEpoch {
+    fn new(epoch: i32) -> Self {
+        Self {
+            epoch: AtomicI32::new(epoch),
+            fenced: AtomicBool::new(false),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct LeaderReplicaState<S> {
     replica: Replica,
-    in_sync_replica: u16,
+    in_sync_replica: Arc<AtomicU16>,
+    /// replicas assigned by SC, in sync replica is raised to it as added followers catch up
+    replica_count: Arc<AtomicU16>,
     storage: SharableReplicaStorage<S>,
     config: ReplicationConfig,
     followers: Arc<RwLock<BTreeMap<SpuId, OffsetInfo>>>,
+    /// followers which haven't been sent latest log start offset
+    log_start_pending: Arc<Mutex<HashSet<SpuId>>>,
     status_update: SharedLrsStatusUpdate,
     sm_ctx: Option<SharedSmartModuleContext>,
     consumer_offset_publishers: Arc<Mutex<Vec<WeakSharedOffsetPublisher>>>,
     mirror_controller_state: Option<SharedMirrorControllerState>,
+    leader_epoch: Arc<LeaderEpoch>,
+    coalescer: Arc<WriteCoalescer>,
+    activity: Arc<PartitionActivity>,
+    /// follower fetch times and sync transitions, for diagnostics
+    progress: Arc<Mutex<ReplicationProgress>>,
 }
 
 impl<S> Clone for LeaderReplicaState<S> {
@@ -64,11 +101,17 @@ impl<S> Clone for LeaderReplicaState<S> {
             storage: self.storage.clone(),
             config: self.config.clone(),
             followers: self.followers.clone(),
-            in_sync_replica: self.in_sync_replica,
+            log_start_pending: self.log_start_pending.clone(),
+            in_sync_replica: self.in_sync_replica.clone(),
+            replica_count: self.replica_count.clone(),
             status_update: self.status_update.clone(),
             sm_ctx: self.sm_ctx.clone(),
             consumer_offset_publishers: self.consumer_offset_publishers.clone(),
             mirror_controller_state: self.mirror_controller_state.clone(),
+            leader_epoch: self.leader_epoch.clone(),
+            coalescer: self.coalescer.clone(),
+            activity: self.activity.clone(),
+            progress: self.progress.clone(),
         }
     }
 }
@@ -131,15 +174,21 @@ where
         );
 
         Uninit(Self {
+            leader_epoch: Arc::new(LeaderEpoch::new(replica.leader_epoch)),
             replica,
             storage: inner,
             config,
             followers: Arc::new(RwLock::new(followers)),
-            in_sync_replica,
+            log_start_pending: Arc::new(Mutex::new(HashSet::new())),
+            in_sync_replica: Arc::new(AtomicU16::new(in_sync_replica)),
+            replica_count: Arc::new(AtomicU16::new(in_sync_replica)),
             status_update,
             sm_ctx: None,
             consumer_offset_publishers: Arc::new(Mutex::new(Vec::new())),
             mirror_controller_state: None,
+            coalescer: Arc::new(WriteCoalescer::default()),
+            activity: Arc::new(PartitionActivity::default()),
+            progress: Arc::new(Mutex::new(ReplicationProgress::default())),
         })
     }
 
@@ -176,10 +225,113 @@ where
         &self.replica
     }
 
+    /// current leader epoch
+    pub fn leader_epoch(&self) -> i32 {
+        self.leader_epoch.epoch.load(Ordering::SeqCst)
+    }
+
+    /// true if newer leader has been elected, fenced leader must not accept or ack writes
+    pub fn is_fenced(&self) -> bool {
+        self.leader_epoch.fenced.load(Ordering::SeqCst)
+    }
+
+    /// fence this leader if follower knows newer epoch.
+    /// return true if leader is fenced
+    pub fn fence_if_stale(&self, follower_epoch: i32) -> bool {
+        if follower_epoch > self.leader_epoch()
+            && !self.leader_epoch.fenced.swap(true, Ordering::SeqCst)
+        {
+            warn!(
+                replica = %self.id(),
+                leader_epoch = self.leader_epoch(),
+                follower_epoch,
+                "newer leader epoch reported by follower, fencing leader"
+            );
+        }
+        self.is_fenced()
+    }
+
+    /// SC confirmed this SPU as leader with given epoch
+    pub fn update_leader_epoch(&self, epoch: i32) {
+        if epoch > self.leader_epoch() {
+            debug!(replica = %self.id(), epoch, "updating leader epoch");
+            self.leader_epoch.epoch.store(epoch, Ordering::SeqCst);
+            self.leader_epoch.fenced.store(false, Ordering::SeqCst);
+        }
+    }
+
+    /// apply changed replica assignment from SC.
+    /// New followers start with unknown offsets and are synced from scratch.
+    /// In sync replica is lowered to new replica count right away, but raised only as new followers
+    /// catch up, so they don't hold back HW while syncing
+    pub async fn update_followers(&self, replicas: &[SpuId]) {
+        let mut followers = self.followers.write().await;
+        followers.retain(|id, _| replicas.contains(id));
+        for id in replicas.iter().filter(|id| **id != self.leader()) {
+            if !followers.contains_key(id) {
+                debug!(replica = %self.id(), follower = id, "adding follower");
+                followers.insert(*id, OffsetInfo::default());
+            }
+        }
+        let replica_count = replicas.len() as u16;
+        self.replica_count.store(replica_count, Ordering::SeqCst);
+        let in_sync_replica = self.in_sync_replica().min(replica_count);
+        self.in_sync_replica
+            .store(in_sync_replica, Ordering::SeqCst);
+        self.refresh_in_sync_replica(&followers);
+    }
+
+    /// replicas required to commit records
+    pub fn in_sync_replica(&self) -> u16 {
+        self.in_sync_replica.load(Ordering::SeqCst)
+    }
+
+    /// raise in sync replica up to assigned replicas, counting leader and followers caught up with HW
+    fn refresh_in_sync_replica(&self, followers: &BTreeMap<SpuId, OffsetInfo>) -> u16 {
+        let current = self.in_sync_replica();
+        let hw = self.hw();
+        let caught_up = followers
+            .values()
+            .filter(|follower| follower.is_valid() && follower.leo >= hw)
+            .count() as u16
+            + 1;
+        let in_sync_replica = current
+            .max(caught_up)
+            .min(self.replica_count.load(Ordering::SeqCst));
+        if in_sync_replica != current {
+            debug!(replica = %self.id(), in_sync_replica, "in sync replica changed");
+            self.in_sync_replica
+                .store(in_sync_replica, Ordering::SeqCst);
+        }
+        in_sync_replica
+    }
+
+    /// delete committed records before offset on leader, followers delete them when they are synced.
+    /// return new log start offset
+    #[instrument(skip(self, notifier))]
+    pub async fn delete_records_before(
+        &self,
+        offset: Offset,
+        notifier: &FollowerNotifier,
+    ) -> Result<Offset> {
+        let log_start = self.storage.delete_records_before(offset).await?;
+
+        let followers: Vec<SpuId> = self.followers.read().await.keys().copied().collect();
+        self.log_start_pending
+            .lock()
+            .await
+            .extend(followers.iter().copied());
+        for follower in &followers {
+            notifier.notify_follower(follower, self.id().clone()).await;
+        }
+        self.update_status().await;
+        Ok(log_start)
+    }
+
     /// override in sync replica
     #[allow(unused)]
     fn set_in_sync_replica(&mut self, replica_count: u16) {
-        self.in_sync_replica = replica_count;
+        self.in_sync_replica.store(replica_count, Ordering::SeqCst);
     }
 
     /// update leader's state from follower's offset states
@@ -202,13 +354,19 @@ where
             return false;
         }
 
+        self.progress
+            .lock()
+            .await
+            .record(follower_id, &follower_pos, &leader_pos, Instant::now());
+
         // get follower info
         let mut followers = self.followers.write().await;
         let update = if let Some(current_follow_info) = followers.get_mut(&follower_id) {
             if current_follow_info.update(&follower_pos) {
+                let in_sync_replica = self.refresh_in_sync_replica(&followers);
                 // if our leo and hw is same there is no need to recompute hw
                 if !leader_pos.is_committed() {
-                    if let Some(hw) = compute_hw(&leader_pos, self.in_sync_replica, &followers) {
+                    if let Some(hw) = compute_hw(&leader_pos, in_sync_replica, &followers) {
                         debug!(hw, "updating hw");
                         if let Err(err) = self.update_hw(hw).await {
                             error!("error updating hw: {}", err);
@@ -246,17 +404,28 @@ where
         follower_id: &SpuId,
         max_bytes: u32,
     ) -> Option<PeerFileTopicResponse> {
+        if self.is_fenced() {
+            debug!(replica = %self.id(), "leader is fenced, no follower updates");
+            return None;
+        }
+
         let leader_offset = self.as_offset();
 
         let reader = self.followers.read().await;
         if let Some(follower_info) = reader.get(follower_id) {
-            if follower_info.is_valid() && !follower_info.is_same(&leader_offset) {
+            let log_start_pending =
+                follower_info.is_valid() && self.log_start_pending.lock().await.remove(follower_id);
+            if follower_info.is_valid()
+                && (log_start_pending || !follower_info.is_same(&leader_offset))
+            {
                 let mut topic_response = PeerFileTopicResponse {
                     name: self.id().topic.to_owned(),
                     ..Default::default()
                 };
                 let mut partition_response = PeerFilePartitionResponse {
                     partition: self.id().partition,
+                    leader_epoch: self.leader_epoch(),
+                    log_start_offset: self.storage.log_start_offset().await,
                     ..Default::default()
                 };
 
@@ -349,20 +518,44 @@ where
         records: &mut RecordSet<RawRecords>,
         notifiers: &FollowerNotifier,
     ) -> Result<(Offset, Offset, usize)> {
-        self.transform(records).await?;
-        if records.total_records() == 0 {
+        if !self.prepare_write(records).await? {
             return Ok((self.hw(), self.leo(), 0));
         }
 
+        let offsets = self.append(records).await?;
+        self.publish_write(notifiers).await;
+
+        Ok(offsets)
+    }
+
+    /// reject writes to fenced leader and apply smartmodule chain.
+    /// returns false if no records are left to write
+    async fn prepare_write(&self, records: &mut RecordSet<RawRecords>) -> Result<bool> {
+        if self.is_fenced() {
+            return Err(self.fenced_error());
+        }
+        self.transform(records).await?;
+        Ok(records.total_records() > 0)
+    }
+
+    fn fenced_error(&self) -> anyhow::Error {
+        anyhow!("leader for {} is fenced", self.id())
+    }
+
+    /// append records to storage and account them as produced
+    async fn append(&self, records: &mut RecordSet<RawRecords>) -> Result<(Offset, Offset, usize)> {
         let offsets = self
             .storage
-            .write_record_set(records, self.in_sync_replica == 1)
+            .write_record_set(records, self.in_sync_replica() == 1)
             .await?;
+        self.record_produced(records);
+        Ok(offsets)
+    }
 
-        self.notify_followers(notifiers).await;
+    /// propagate new end offset to followers and SC
+    async fn publish_write(&self, notifier: &FollowerNotifier) {
+        self.notify_followers(notifier).await;
         self.update_status().await;
-
-        Ok(offsets)
     }
 
     async fn transform(&self, records: &mut RecordSet<RawRecords>) -> Result<()> {
@@ -385,15 +578,13 @@ where
         let leader_offset = self.as_offset();
         let followers = self.followers.read().await;
         debug!(?leader_offset);
-        for follower in &self.replica.replicas {
-            if let Some(follower_info) = followers.get(follower) {
-                debug!(follower, ?follower_info);
-                if follower_info.is_valid() && !follower_info.is_same(&leader_offset) {
-                    debug!(follower, "notify");
-                    notifier.notify_follower(follower, self.id().clone()).await;
-                } else {
-                    debug!(follower, "no update");
-                }
+        for (follower, follower_info) in followers.iter() {
+            debug!(follower, ?follower_info);
+            if follower_info.is_valid() && !follower_info.is_same(&leader_offset) {
+                debug!(follower, "notify");
+                notifier.notify_follower(follower, self.id().clone()).await;
+            } else {
+                debug!(follower, "no update");
             }
         }
     }
@@ -404,11 +595,31 @@ where
     }
 
     // get copy of followers_info for debugging
-    #[allow(unused)]
     pub async fn followers_info(&self) -> BTreeMap<SpuId, OffsetInfo> {
         self.followers.read().await.clone()
     }
 
+    /// replication progress of followers
+    pub async fn replication_status(&self) -> ReplicationStatus {
+        let leader_pos = self.as_offset();
+        let followers = self.followers_info().await;
+        let progress = self.progress.lock().await;
+        let now = Instant::now();
+        ReplicationStatus {
+            leader: self.leader(),
+            leader_epoch: self.leader_epoch(),
+            leo: leader_pos.leo,
+            hw: leader_pos.hw,
+            in_sync_replica: self.in_sync_replica(),
+            followers: followers
+                .iter()
+                .map(|(follower_id, follower_pos)| {
+                    progress.status(*follower_id, follower_pos, &leader_pos, now)
+                })
+                .collect(),
+        }
+    }
+
     #[cfg(test)]
     pub fn consumer_offset_publishers(&self) -> Arc<Mutex<Vec<WeakSharedOffsetPublisher>>> {
         self.consumer_offset_publishers.clone()
@@ -433,6 +644,26 @@ where
         publishers.push(publisher);
     }
 
+    /// number of streams consuming from this replica
+    pub async fn active_consumers(&self) -> usize {
+        self.consumer_offset_publishers
+            .lock()
+            .await
+            .iter()
+            .filter(|publisher| publisher.strong_count() > 0)
+            .count()
+    }
+
+    pub(crate) fn activity(&self) -> &PartitionActivity {
+        &self.activity
+    }
+
+    fn record_produced(&self, records: &RecordSet<RawRecords>) {
+        self.activity
+            .produced()
+            .increase(records.total_records() as u64, records.write_size(0) as u64);
+    }
+
     pub async fn signal_topic_deleted(&self) {
         let offset_publishers = self.consumer_offset_publishers.lock().await;
 
@@ -469,6 +700,49 @@ where
     }
 }
 
+impl<S: ReplicaStorage + 'static> LeaderReplicaState<S>
+where
+    S: Sync + Send,
+{
+    /// apply changed topic settings of replica.
+    /// Returns new state which replaces this one, deduplication context is rebuilt if it changed
+    pub async fn reconfigure(
+        &self,
+        replica: Replica,
+        ctx: &GlobalContext<FileReplica>,
+    ) -> Result<Self> {
+        debug!(replica = %replica.id, "reconfiguring leader");
+        self.storage.update_config(&replica).await;
+        let mut state = self.clone();
+        let dedup_changed = state.replica.deduplication != replica.deduplication;
+        state.replica = replica;
+        if dedup_changed {
+            state.sm_ctx = state.dedup_context(ctx).await?;
+        }
+        Ok(state)
+    }
+
+    /// create SmartModule context of deduplication, looking back stored records
+    async fn dedup_context(
+        &self,
+        ctx: &GlobalContext<FileReplica>,
+    ) -> Result<Option<SharedSmartModuleContext>> {
+        let Some(dedup) = &self.replica.deduplication else {
+            return Ok(None);
+        };
+        debug!(?dedup, "init leader smartmodule context");
+        let dedup_filter = dedup_to_invocation(dedup);
+        let mut sm_ctx = SmartModuleContext::try_from(vec![dedup_filter], COMMON_VERSION, ctx)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("SmartModule context is required here"))?;
+        sm_ctx
+            .look_back(self)
+            .await
+            .context("leader smartmodule context lookback failed")?;
+        Ok(Some(Arc::new(RwLock::new(sm_ctx))))
+    }
+}
+
 pub struct Uninit<S>(S);
 
 impl<S: ReplicaStorage + 'static> Uninit<LeaderReplicaState<S>>
@@ -477,18 +751,7 @@ where
 {
     pub async fn init(self, ctx: &GlobalContext<FileReplica>) -> Result<LeaderReplicaState<S>> {
         let mut state = self.0;
-        if let Some(dedup) = &state.replica.deduplication {
-            debug!(?state.replica.deduplication, "init leader smartmodule context");
-            let dedup_filter = dedup_to_invocation(dedup);
-            let mut sm_ctx = SmartModuleContext::try_from(vec![dedup_filter], COMMON_VERSION, ctx)
-                .await?
-                .ok_or_else(|| anyhow::anyhow!("SmartModule context is required here"))?;
-            sm_ctx
-                .look_back(&state)
-                .await
-                .context("leader smartmodule context lookback failed")?;
-            state.sm_ctx = Some(Arc::new(RwLock::new(sm_ctx)));
-        };
+        state.sm_ctx = state.dedup_context(ctx).await?;
         // start up mirror controller if mirror is source
         if let Some(mirror) = &state.replica.mirror {
             match mirror {
@@ -587,7 +850,92 @@ fn compute_hw(
 
 impl<S> LeaderReplicaState<S> where S: ReplicaStorage {}
 
-impl LeaderReplicaState<FileReplica> {}
+impl LeaderReplicaState<FileReplica> {
+    /// write records, appending together with other produce writes to this partition
+    /// which arrive within `linger`. Zero linger writes immediately.
+    pub async fn write_record_set_coalesced(
+        &self,
+        mut records: RecordSet<RawRecords>,
+        notifier: &Arc<FollowerNotifier>,
+        linger: Duration,
+        metrics: &Arc<SpuMetrics>,
+    ) -> Result<(Offset, Offset, usize)> {
+        if linger.is_zero() {
+            return self.write_record_set(&mut records, notifier).await;
+        }
+        if !self.prepare_write(&mut records).await? {
+            return Ok((self.hw(), self.leo(), 0));
+        }
+
+        let started = Instant::now();
+        let (receiver, schedule_flush) = self.coalescer.enqueue(records);
+        if schedule_flush {
+            // flush runs in its own task so group is written even if this request is dropped
+            let leader = self.clone();
+            let notifier = notifier.clone();
+            let metrics = metrics.clone();
+            spawn(async move {
+                sleep(linger).await;
+                leader.flush_coalesced(&notifier, &metrics).await;
+            });
+        }
+
+        let result = receiver
+            .recv()
+            .await
+            .unwrap_or_else(|_| Err(anyhow!("coalesced write for {} was dropped", self.id())));
+        metrics.write_coalesce().add_latency(started.elapsed());
+        result
+    }
+
+    #[instrument(skip(self, notifier, metrics))]
+    async fn flush_coalesced(&self, notifier: &FollowerNotifier, metrics: &SpuMetrics) {
+        let pending = self.coalescer.take();
+        if pending.is_empty() {
+            return;
+        }
+        debug!(requests = pending.len(), "flushing coalesced writes");
+        metrics.write_coalesce().record_write(pending.len() as u64);
+
+        let mut write = CoalescedWrite::new(pending);
+        if self.is_fenced() {
+            for pending in &write.writes {
+                pending.reply(Err(self.fenced_error()));
+            }
+            return;
+        }
+
+        match self.append(&mut write.records).await {
+            Ok(_) => {
+                for (pending, offsets) in write.writes.iter().zip(write.offsets()) {
+                    pending.reply(Ok(offsets));
+                }
+            }
+            Err(err)
+                if matches!(
+                    err.downcast_ref::<StorageError>(),
+                    Some(StorageError::BatchTooBig(_) | StorageError::BatchExceededSegment { .. })
+                ) =>
+            {
+                // batches are validated before anything is appended,
+                // write requests one by one so only offending request fails
+                debug!("coalesced write rejected, writing requests individually");
+                for (pending, mut records) in write.into_record_sets() {
+                    pending.reply(self.append(&mut records).await);
+                }
+            }
+            Err(err) => {
+                error!(replica = %self.id(), "coalesced write failed: {err:#}");
+                for pending in &write.writes {
+                    pending.reply(Err(anyhow!("coalesced write failed: {err:#}")));
+                }
+                return;
+            }
+        }
+
+        self.publish_write(notifier).await;
+    }
+}
 
 #[cfg(test)]
 mod test_hw_updates {
@@ -835,6 +1183,7 @@ mod test_leader {
 
     use fluvio_controlplane_metadata::partition::ReplicaKey;
     use fluvio_storage::{ReplicaStorage, ReplicaStorageConfig, OffsetInfo, ReplicaSlice};
+    use fluvio_storage::cache::CachedBatch;
     use fluvio_protocol::record::Offset;
     use fluvio_protocol::link::ErrorCode;
     use fluvio_protocol::record::BatchRecords;
@@ -896,6 +1245,15 @@ mod test_leader {
             })
         }
 
+        fn read_cached_batches(
+            &self,
+            _offset: Offset,
+            _end_offset: Offset,
+            _max_len: u32,
+        ) -> Option<Vec<CachedBatch>> {
+            None
+        }
+
         // do dummy implementations of write
         async fn write_recordset<R: BatchRecords>(
             &mut self,
@@ -929,9 +1287,17 @@ mod test_leader {
             (self.pos.hw * 10) as Offset
         }
 
+        async fn delete_records_before(&mut self, offset: Offset) -> Result<Offset> {
+            Ok(offset)
+        }
+
         async fn remove(&self) -> Result<(), fluvio_storage::StorageError> {
             todo!()
         }
+
+        fn update_config(&self, _replica: &Replica) {}
+
+        fn update_flush_policy(&self, _flush_write_count: u32, _flush_idle_msec: u32) {}
     }
 
     #[fluvio_future::test]
@@ -952,7 +1318,7 @@ mod test_leader {
         .expect("state")
         .0;
 
-        assert_eq!(state.in_sync_replica, 1);
+        assert_eq!(state.in_sync_replica(), 1);
     }
 
     #[fluvio_future::test]
@@ -1036,6 +1402,99 @@ mod test_leader {
         assert!(state.follower_updates(&5001, MAX_BYTES).await.is_some()); // 5001 is still need to besync
     }
 
+    #[fluvio_future::test]
+    async fn test_leader_fencing() {
+        let leader_config = SpuConfig {
+            id: 5000,
+            ..Default::default()
+        };
+
+        let notifier = FollowerNotifier::shared();
+
+        let replica_key: ReplicaKey = ("test", 1).into();
+        let mut replica = Replica::new(replica_key, 5000, vec![5000, 5001]);
+        replica.leader_epoch = 2;
+        let state: LeaderReplicaState<MockStorage> =
+            LeaderReplicaState::create(replica, &leader_config, StatusLrsMessageSink::shared())
+                .await
+                .expect("state")
+                .0;
+        assert_eq!(state.leader_epoch(), 2);
+
+        // follower with same or older epoch doesn't fence
+        assert!(!state.fence_if_stale(2));
+        assert!(!state.fence_if_stale(1));
+        state
+            .write_record_set(&mut create_raw_recordset(2), &notifier)
+            .await
+            .expect("write");
+
+        // follower knows about newer leader
+        assert!(state.fence_if_stale(3));
+        assert!(state.clone().is_fenced());
+        assert!(
+            state
+                .write_record_set(&mut create_raw_recordset(2), &notifier)
+                .await
+                .is_err()
+        );
+        assert!(state.follower_updates(&5001, MAX_BYTES).await.is_none());
+
+        // re-elected by SC with newer epoch
+        state.update_leader_epoch(4);
+        assert!(!state.is_fenced());
+        assert_eq!(state.leader_epoch(), 4);
+    }
+
+    #[fluvio_future::test]
+    async fn test_update_followers() {
+        let leader_config = SpuConfig {
+            id: 5000,
+            ..Default::default()
+        };
+        let replica_key: ReplicaKey = ("test", 1).into();
+        let replica = Replica::new(replica_key, 5000, vec![5000, 5001]);
+        let state: LeaderReplicaState<MockStorage> =
+            LeaderReplicaState::create(replica, &leader_config, StatusLrsMessageSink::shared())
+                .await
+                .expect("state")
+                .0;
+        state
+            .followers
+            .write()
+            .await
+            .get_mut(&5001)
+            .expect("follower")
+            .update(&OffsetInfo { leo: 5, hw: 5 });
+
+        // existing follower keeps its offsets
+        state.update_followers(&[5000, 5001, 5002]).await;
+        let followers = state.followers_info().await;
+        assert_eq!(
+            followers.keys().copied().collect::<Vec<_>>(),
+            vec![5001, 5002]
+        );
+        assert_eq!(followers[&5001], OffsetInfo { leo: 5, hw: 5 });
+        assert!(!followers[&5002].is_valid());
+
+        state.update_followers(&[5000, 5002]).await;
+        assert_eq!(state.live_replicas().await, vec![5002]);
+        assert_eq!(state.in_sync_replica(), 2);
+
+        // shrinking lowers in sync replica right away
+        state.update_followers(&[5000]).await;
+        assert_eq!(state.in_sync_replica(), 1);
+
+        // added follower counts once it caught up with leader
+        state.update_followers(&[5000, 5003]).await;
+        assert_eq!(state.in_sync_replica(), 1);
+        let notifier = FollowerNotifier::shared();
+        state
+            .update_states_from_followers(5003, OffsetInfo { leo: 0, hw: 0 }, &notifier)
+            .await;
+        assert_eq!(state.in_sync_replica(), 2);
+    }
+
     #[fluvio_future::test]
     async fn test_update_leader_from_followers() {
         use crate::core::GlobalContext;
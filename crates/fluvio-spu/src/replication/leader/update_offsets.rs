@@ -14,7 +14,8 @@ pub struct UpdateOffsetRequest {
 
 impl Request for UpdateOffsetRequest {
     const API_KEY: u16 = LeaderPeerApiEnum::UpdateOffsets as u16;
-    const DEFAULT_API_VERSION: i16 = 0;
+    // version 2 adds leader epoch, mirroring still encodes offsets with version 1
+    const DEFAULT_API_VERSION: i16 = 2;
     type Response = UpdateOffsetResponse;
 }
 
@@ -23,6 +24,9 @@ pub struct ReplicaOffsetRequest {
     pub replica: ReplicaKey,
     pub leo: Offset,
     pub hw: Offset,
+    /// leader epoch known to follower
+    #[fluvio(min_version = 2)]
+    pub leader_epoch: i32,
 }
 
 // no content, this is one way request
@@ -1,9 +1,16 @@
 #![allow(clippy::assign_op_pattern)]
 
+use std::io::Error as IoError;
+
+use bytes::Buf;
+
+use fluvio_protocol::{Decoder, Encoder, Version};
 use fluvio_protocol::api::Request;
-use fluvio_protocol::derive::{Decoder, Encoder};
 use fluvio_types::SpuId;
 
+use crate::replication::follower::sync::{FileSyncRequest, BASE_SYNC_VERSION};
+use crate::replication::leader::UpdateOffsetRequest;
+
 use super::SPUPeerApiEnum;
 
 #[derive(Decoder, Encoder, Debug, Default)]
@@ -12,20 +19,170 @@ pub struct FetchStreamRequest {
     pub leader_spu_id: SpuId,
     pub min_bytes: i32,
     pub max_bytes: i32,
+    /// versions supported by follower
+    #[fluvio(min_version = 1)]
+    pub peer_versions: PeerApiVersions,
 }
 
 impl Request for FetchStreamRequest {
     const API_KEY: u16 = SPUPeerApiEnum::FetchStream as u16;
+    // version 1 exchanges peer api versions
+    const DEFAULT_API_VERSION: i16 = 1;
     type Response = FetchStreamResponse;
 }
 
 #[derive(Decoder, Encoder, Default, Debug)]
 pub struct FetchStreamResponse {
     pub spu_id: Option<SpuId>,
+    /// versions supported by leader
+    #[fluvio(min_version = 1)]
+    pub peer_versions: PeerApiVersions,
 }
 
 impl FetchStreamResponse {
     pub fn new(spu_id: Option<SpuId>) -> Self {
-        FetchStreamResponse { spu_id }
+        FetchStreamResponse {
+            spu_id,
+            peer_versions: PeerApiVersions::current(),
+        }
+    }
+}
+
+/// Highest versions of peer requests which SPU can decode.
+/// Exchanged when follower connects to leader, so each side sends requests the other understands.
+#[derive(Encoder, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeerApiVersions {
+    /// sync request, sent by leader.
+    /// Followers below `LOG_START_VERSION` keep their log start, leader's one isn't sent
+    pub sync: i16,
+    /// update offsets request, sent by follower
+    pub update_offsets: i16,
+}
+
+impl Default for PeerApiVersions {
+    /// versions of SPUs which don't exchange versions
+    fn default() -> Self {
+        Self {
+            sync: BASE_SYNC_VERSION,
+            update_offsets: 0,
+        }
+    }
+}
+
+impl PeerApiVersions {
+    /// versions of this SPU
+    pub fn current() -> Self {
+        Self {
+            sync: FileSyncRequest::DEFAULT_API_VERSION,
+            update_offsets: UpdateOffsetRequest::DEFAULT_API_VERSION,
+        }
+    }
+
+    /// versions both this SPU and peer support
+    pub fn negotiate(peer: &Self) -> Self {
+        let current = Self::current();
+        Self {
+            sync: current.sync.min(peer.sync),
+            update_offsets: current.update_offsets.min(peer.update_offsets),
+        }
+    }
+}
+
+impl Decoder for PeerApiVersions {
+    fn decode<T>(&mut self, src: &mut T, version: Version) -> Result<(), IoError>
+    where
+        T: Buf,
+    {
+        // leaders without version exchange answer without versions, keep defaults
+        if !src.has_remaining() {
+            return Ok(());
+        }
+        self.sync.decode(src, version)?;
+        self.update_offsets.decode(src, version)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn test_peer_versions_from_old_leader() {
+        // response of leader before version exchange only has spu id
+        let mut bytes = vec![];
+        Some(5001 as SpuId).encode(&mut bytes, 1).expect("encode");
+
+        let response =
+            FetchStreamResponse::decode_from(&mut Cursor::new(bytes), 1).expect("decode");
+        assert_eq!(response.spu_id, Some(5001));
+        assert_eq!(response.peer_versions, PeerApiVersions::default());
+        assert_eq!(
+            PeerApiVersions::negotiate(&response.peer_versions),
+            PeerApiVersions::default()
+        );
+    }
+
+    #[test]
+    fn test_peer_versions_exchange() {
+        let mut bytes = vec![];
+        FetchStreamResponse::new(Some(5001))
+            .encode(&mut bytes, FetchStreamRequest::DEFAULT_API_VERSION)
+            .expect("encode");
+
+        let response = FetchStreamResponse::decode_from(
+            &mut Cursor::new(bytes),
+            FetchStreamRequest::DEFAULT_API_VERSION,
+        )
+        .expect("decode");
+        assert_eq!(response.peer_versions, PeerApiVersions::current());
+        assert_eq!(
+            PeerApiVersions::negotiate(&response.peer_versions),
+            PeerApiVersions::current()
+        );
+    }
+
+    #[test]
+    fn test_sync_to_older_follower() {
+        use fluvio_protocol::record::{RawRecords, RecordSet};
+
+        use crate::replication::follower::sync::{
+            DefaultSyncRequest, LEADER_EPOCH_VERSION, PeerFetchablePartitionResponse,
+            PeerFetchableTopicResponse,
+        };
+
+        // follower before log start offset
+        let versions = PeerApiVersions::negotiate(&PeerApiVersions {
+            sync: LEADER_EPOCH_VERSION,
+            ..PeerApiVersions::current()
+        });
+        assert_eq!(versions.sync, LEADER_EPOCH_VERSION);
+
+        let request = DefaultSyncRequest {
+            topics: vec![PeerFetchableTopicResponse {
+                name: "test".to_owned(),
+                partitions: vec![PeerFetchablePartitionResponse::<RecordSet<RawRecords>> {
+                    hw: 10,
+                    leo: 10,
+                    leader_epoch: 2,
+                    log_start_offset: 5,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        };
+        let mut bytes = vec![];
+        request.encode(&mut bytes, versions.sync).expect("encode");
+
+        let decoded = DefaultSyncRequest::decode_from(&mut Cursor::new(bytes), versions.sync)
+            .expect("decode");
+        let partition = &decoded.topics[0].partitions[0];
+        assert_eq!(partition.leader_epoch, 2);
+        assert_eq!(partition.hw, 10);
+        // follower keeps its log start
+        assert_eq!(partition.log_start_offset, 0);
     }
 }
@@ -15,6 +15,7 @@ use crate::core::DefaultSharedGlobalContext;
 
 pub use self::fetch_stream_request::FetchStreamRequest;
 pub use self::fetch_stream_request::FetchStreamResponse;
+pub use self::fetch_stream_request::PeerApiVersions;
 pub use self::fetch_consumer_offset_request::FetchConsumerOffsetRequest;
 pub use self::update_consumer_offset_request::UpdateConsumerOffsetRequest;
 pub use self::api::SPUPeerApiEnum;
@@ -31,5 +32,6 @@ pub fn create_internal_server(addr: String, ctx: DefaultSharedGlobalContext) ->
         addr
     );
 
-    FluvioApiServer::new(addr, ctx, InternalService::new())
+    let tuning = ctx.config().socket;
+    FluvioApiServer::new(addr, ctx, InternalService::new()).with_socket_tuning(tuning)
 }
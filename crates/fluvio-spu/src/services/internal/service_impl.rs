@@ -15,6 +15,7 @@ use crate::services::internal::update_consumer_offset_handler::handle_update_con
 use super::SpuPeerRequest;
 use super::SPUPeerApiEnum;
 use super::FetchStreamResponse;
+use super::PeerApiVersions;
 
 #[derive(Debug)]
 pub struct InternalService {}
@@ -74,7 +75,9 @@ impl FluvioService for InternalService {
                         .send_response(&res_msg, req_msg.header.api_version())
                         .await?;
                     drop(api_stream);
-                    FollowerHandler::start(ctx, follower_id, spu_update, sink, stream).await;
+                    let versions = PeerApiVersions::negotiate(&request.peer_versions);
+                    debug!(follower_id, ?versions, "peer versions");
+                    FollowerHandler::start(ctx, follower_id, versions, spu_update, sink, stream).await;
                 } else {
                     warn!(follower_id, "unknown spu, dropping connection");
                     let response = FetchStreamResponse::new(None);
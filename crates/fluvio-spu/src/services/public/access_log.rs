@@ -0,0 +1,218 @@
+//!
+//! # Access log
+//!
+//! Structured entry for each request of the public service, for capacity and abuse analysis.
+//! Entries are logged at info level with target `fluvio_spu::access`, so they can be
+//! filtered or routed separately from other logs.
+//!
+
+use std::time::Instant;
+
+use anyhow::Result;
+use tracing::info;
+
+use fluvio_protocol::Encoder;
+use fluvio_protocol::record::ReplicaKey;
+use fluvio_spu_schema::server::SpuServerApiKey;
+use fluvio_spu_schema::server::SpuServerRequest;
+
+pub(crate) const ACCESS_LOG_TARGET: &str = "fluvio_spu::access";
+
+/// Request being served, logged when it's finished
+#[derive(Debug)]
+pub(crate) struct AccessLogEntry {
+    peer: String,
+    principal: Option<String>,
+    client_id: String,
+    api: String,
+    partitions: Vec<ReplicaKey>,
+    /// encoded size of request
+    bytes: usize,
+    started: Instant,
+}
+
+impl AccessLogEntry {
+    pub(crate) fn new(request: &SpuServerRequest, peer: &str, principal: Option<&str>) -> Self {
+        let header = request.header();
+        let api = SpuServerApiKey::try_from(header.api_key())
+            .map(|key| format!("{key:?}"))
+            .unwrap_or_else(|_| header.api_key().to_string());
+        let (partitions, bytes) = request_partitions_and_size(request);
+        Self {
+            peer: peer.to_owned(),
+            principal: principal.map(str::to_owned),
+            client_id: header.client_id().to_owned(),
+            api,
+            partitions,
+            bytes,
+            started: Instant::now(),
+        }
+    }
+
+    fn partitions(&self) -> String {
+        self.partitions
+            .iter()
+            .map(|replica| replica.to_string())
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    /// log entry with outcome of request.
+    /// For stream fetch, latency covers starting the stream only.
+    pub(crate) fn finish(self, result: &Result<()>) {
+        let latency_us = self.started.elapsed().as_micros() as u64;
+        let result = match result {
+            Ok(()) => "ok".to_owned(),
+            Err(err) => format!("error: {err}"),
+        };
+        info!(
+            target: ACCESS_LOG_TARGET,
+            peer = %self.peer,
+            principal = self.principal.as_deref().unwrap_or("-"),
+            client_id = %self.client_id,
+            api = %self.api,
+            partitions = %self.partitions(),
+            bytes = self.bytes,
+            latency_us,
+            %result,
+            "request"
+        );
+    }
+}
+
+fn request_partitions_and_size(request: &SpuServerRequest) -> (Vec<ReplicaKey>, usize) {
+    let version = request.header().api_version();
+    match request {
+        SpuServerRequest::ProduceRequest(request) => (
+            request
+                .request
+                .topics
+                .iter()
+                .flat_map(|topic| {
+                    topic
+                        .partitions
+                        .iter()
+                        .map(|partition| ReplicaKey::new(&topic.name, partition.partition_index))
+                })
+                .collect(),
+            request.request.write_size(version),
+        ),
+        SpuServerRequest::FileFetchRequest(request) => (
+            request
+                .request
+                .topics
+                .iter()
+                .flat_map(|topic| {
+                    topic
+                        .fetch_partitions
+                        .iter()
+                        .map(|partition| ReplicaKey::new(&topic.name, partition.partition_index))
+                })
+                .collect(),
+            request.request.write_size(version),
+        ),
+        SpuServerRequest::FetchOffsetsRequest(request) => (
+            request
+                .request
+                .topics
+                .iter()
+                .flat_map(|topic| {
+                    topic
+                        .partitions
+                        .iter()
+                        .map(|partition| ReplicaKey::new(&topic.name, partition.partition_index))
+                })
+                .collect(),
+            request.request.write_size(version),
+        ),
+        SpuServerRequest::FileStreamFetchRequest(request) => (
+            vec![ReplicaKey::new(
+                &request.request.topic,
+                request.request.partition,
+            )],
+            request.request.write_size(version),
+        ),
+        SpuServerRequest::DeleteConsumerOffsetRequest(request) => (
+            vec![request.request.replica_id.clone()],
+            request.request.write_size(version),
+        ),
+        SpuServerRequest::FetchConsumerOffsetsRequest(request) => (
+            request
+                .request
+                .filter_opts
+                .as_ref()
+                .and_then(|opts| opts.replica_id.clone())
+                .into_iter()
+                .collect(),
+            request.request.write_size(version),
+        ),
+        SpuServerRequest::ResetConsumerOffsetRequest(request) => (
+            vec![request.request.replica_id.clone()],
+            request.request.write_size(version),
+        ),
+        SpuServerRequest::FetchPartitionStatsRequest(request) => (
+            vec![request.request.replica_id.clone()],
+            request.request.write_size(version),
+        ),
+        SpuServerRequest::FetchReplicationStatusRequest(request) => (
+            vec![request.request.replica_id.clone()],
+            request.request.write_size(version),
+        ),
+        SpuServerRequest::DeleteRecordsRequest(request) => (
+            vec![request.request.replica_id.clone()],
+            request.request.write_size(version),
+        ),
+        SpuServerRequest::ApiVersionsRequest(request) => {
+            (vec![], request.request.write_size(version))
+        }
+        SpuServerRequest::UpdateOffsetsRequest(request) => {
+            (vec![], request.request.write_size(version))
+        }
+        SpuServerRequest::UpdateConsumerOffsetRequest(request) => {
+            (vec![], request.request.write_size(version))
+        }
+        SpuServerRequest::FetchSpuConfigRequest(request) => {
+            (vec![], request.request.write_size(version))
+        }
+        SpuServerRequest::StartMirrorRequest(request) => {
+            (vec![], request.request.write_size(version))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use fluvio_protocol::api::RequestMessage;
+    use fluvio_protocol::record::{RecordSet, RawRecords};
+    use fluvio_spu_schema::produce::{DefaultProduceRequest, PartitionProduceData, TopicProduceData};
+
+    use super::*;
+
+    #[test]
+    fn test_produce_entry() {
+        let mut topic = TopicProduceData::<RecordSet<RawRecords>> {
+            name: "orders".to_owned(),
+            ..Default::default()
+        };
+        for partition_index in [0, 2] {
+            topic.partitions.push(PartitionProduceData {
+                partition_index,
+                ..Default::default()
+            });
+        }
+        let produce = DefaultProduceRequest {
+            topics: vec![topic],
+            ..Default::default()
+        };
+        let request = SpuServerRequest::ProduceRequest(
+            RequestMessage::new_request(produce).set_client_id("billing"),
+        );
+
+        let entry = AccessLogEntry::new(&request, "10.0.0.1:5000", None);
+        assert_eq!(entry.api, "Produce");
+        assert_eq!(entry.client_id, "billing");
+        assert_eq!(entry.partitions(), "orders-0,orders-2");
+        assert!(entry.bytes > 0);
+    }
+}
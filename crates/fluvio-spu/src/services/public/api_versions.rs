@@ -4,9 +4,15 @@ use anyhow::Result;
 use fluvio_protocol::api::{RequestMessage, ResponseMessage, Request};
 use fluvio_spu_schema::produce::DefaultProduceRequest;
 use fluvio_spu_schema::fetch::DefaultFetchRequest;
-use fluvio_protocol::link::versions::ApiVersionKey;
+use fluvio_compression::Compression;
+use fluvio_protocol::link::versions::{ApiVersionKey, Capabilities, ServerCapabilities, ServerTime};
 use fluvio_spu_schema::server::SpuServerApiKey;
+use fluvio_spu_schema::server::consumer_offset::ResetConsumerOffsetRequest;
+use fluvio_spu_schema::server::delete_records::DeleteRecordsRequest;
 use fluvio_spu_schema::server::fetch_offset::FetchOffsetsRequest;
+use fluvio_spu_schema::server::partition_stats::FetchPartitionStatsRequest;
+use fluvio_spu_schema::server::replication_status::FetchReplicationStatusRequest;
+use fluvio_spu_schema::server::spu_config::FetchSpuConfigRequest;
 use fluvio_spu_schema::server::stream_fetch::DefaultStreamFetchRequest;
 use fluvio_spu_schema::server::update_offset::UpdateOffsetsRequest;
 use fluvio_spu_schema::{ApiVersionsRequest, ApiVersionsResponse};
@@ -16,7 +22,11 @@ pub async fn handle_api_version_request(
     request: RequestMessage<ApiVersionsRequest>,
 ) -> Result<ResponseMessage<ApiVersionsResponse>> {
     let client_version = &request.request.client_version;
-    let mut response = ApiVersionsResponse::default();
+    let mut response = ApiVersionsResponse {
+        server_time: ServerTime::now(),
+        capabilities: ServerCapabilities::new(capabilities()),
+        ..Default::default()
+    };
     response.api_keys.push(make_version_key(
         SpuServerApiKey::Produce,
         DefaultProduceRequest::MIN_API_VERSION,
@@ -47,11 +57,55 @@ pub async fn handle_api_version_request(
         0,
         UpdateOffsetsRequest::DEFAULT_API_VERSION,
     ));
+    response.api_keys.push(make_version_key(
+        SpuServerApiKey::FetchPartitionStats,
+        FetchPartitionStatsRequest::MIN_API_VERSION,
+        FetchPartitionStatsRequest::DEFAULT_API_VERSION,
+    ));
+    response.api_keys.push(make_version_key(
+        SpuServerApiKey::DeleteRecords,
+        DeleteRecordsRequest::MIN_API_VERSION,
+        DeleteRecordsRequest::DEFAULT_API_VERSION,
+    ));
+    response.api_keys.push(make_version_key(
+        SpuServerApiKey::FetchSpuConfig,
+        FetchSpuConfigRequest::MIN_API_VERSION,
+        FetchSpuConfigRequest::DEFAULT_API_VERSION,
+    ));
+    response.api_keys.push(make_version_key(
+        SpuServerApiKey::ResetConsumerOffset,
+        ResetConsumerOffsetRequest::MIN_API_VERSION,
+        ResetConsumerOffsetRequest::DEFAULT_API_VERSION,
+    ));
+    response.api_keys.push(make_version_key(
+        SpuServerApiKey::FetchReplicationStatus,
+        FetchReplicationStatusRequest::MIN_API_VERSION,
+        FetchReplicationStatusRequest::DEFAULT_API_VERSION,
+    ));
 
     trace!("Returning ApiVersionsResponse: {:#?}", &response);
     Ok(request.new_response(response))
 }
 
+/// Optional features of this SPU build
+fn capabilities() -> Capabilities {
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "smartengine")] {
+            let smartmodule_version = fluvio_smartengine::DEFAULT_SMARTENGINE_VERSION;
+        } else {
+            let smartmodule_version = 0;
+        }
+    }
+    Capabilities {
+        compression: Compression::supported()
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+        smartmodule_version,
+        mirroring: true,
+    }
+}
+
 /// Build version key object
 fn make_version_key(key: SpuServerApiKey, min_version: i16, max_version: i16) -> ApiVersionKey {
     let api_key = key as i16;
@@ -1,14 +1,24 @@
+use std::time::Duration;
+
 use crate::services::public::StreamPublishers;
+use crate::services::public::quota::ByteRateQuota;
 
 #[derive(Debug)]
 pub(crate) struct ConnectionContext {
     stream_publishers: StreamPublishers,
+    /// how long client waits for responses, from api versions request
+    request_timeout: Option<Duration>,
+    produce_quota: ByteRateQuota,
+    fetch_quota: ByteRateQuota,
 }
 
 impl ConnectionContext {
     pub(crate) fn new() -> Self {
         Self {
             stream_publishers: StreamPublishers::new(),
+            request_timeout: None,
+            produce_quota: ByteRateQuota::default(),
+            fetch_quota: ByteRateQuota::default(),
         }
     }
 
@@ -19,4 +29,20 @@ impl ConnectionContext {
     pub(crate) fn stream_publishers_mut(&mut self) -> &mut StreamPublishers {
         &mut self.stream_publishers
     }
+
+    pub(crate) fn request_timeout(&self) -> Option<Duration> {
+        self.request_timeout
+    }
+
+    pub(crate) fn set_request_timeout(&mut self, timeout: Option<Duration>) {
+        self.request_timeout = timeout;
+    }
+
+    pub(crate) fn produce_quota(&self) -> &ByteRateQuota {
+        &self.produce_quota
+    }
+
+    pub(crate) fn fetch_quota(&self) -> &ByteRateQuota {
+        &self.fetch_quota
+    }
 }
@@ -7,6 +7,8 @@ use fluvio_spu_schema::server::consumer_offset::DeleteConsumerOffsetRequest;
 use fluvio_spu_schema::server::consumer_offset::DeleteConsumerOffsetResponse;
 use fluvio_spu_schema::server::consumer_offset::FetchConsumerOffsetsRequest;
 use fluvio_spu_schema::server::consumer_offset::FetchConsumerOffsetsResponse;
+use fluvio_spu_schema::server::consumer_offset::ResetConsumerOffsetRequest;
+use fluvio_spu_schema::server::consumer_offset::ResetConsumerOffsetResponse;
 use fluvio_spu_schema::server::consumer_offset::UpdateConsumerOffsetRequest;
 use fluvio_spu_schema::server::consumer_offset::UpdateConsumerOffsetResponse;
 use fluvio_spu_schema::server::consumer_offset::ConsumerOffset as ConsumerOffsetResponse;
@@ -81,6 +83,47 @@ pub(crate) async fn handle_delete_consumer_offset_request(
     )
 }
 
+#[instrument(skip(req_msg, ctx))]
+pub(crate) async fn handle_reset_consumer_offset_request(
+    req_msg: RequestMessage<ResetConsumerOffsetRequest>,
+    ctx: DefaultSharedGlobalContext,
+) -> Result<ResponseMessage<ResetConsumerOffsetResponse>, IoError> {
+    let ResetConsumerOffsetRequest {
+        replica_id,
+        consumer_id,
+        offset,
+    } = req_msg.request;
+
+    let error_code = match ctx.leaders_state().get(&CONSUMER_REPLICA_KEY.into()).await {
+        Some(ref replica) => {
+            match update_offset_for_leader(
+                ctx.clone(),
+                replica,
+                replica_id.topic,
+                replica_id.partition,
+                consumer_id,
+                offset,
+            )
+            .await
+            {
+                Ok(_) => ErrorCode::None,
+                Err(err) => ErrorCode::Other(format!("unable to reset consumer: {err:?}")),
+            }
+        }
+        None => ErrorCode::PartitionNotLeader,
+    };
+
+    debug!(?error_code, "reset consumer offset result");
+
+    let response = ResetConsumerOffsetResponse { error_code };
+    Ok(
+        RequestMessage::<ResetConsumerOffsetRequest>::response_with_header(
+            &req_msg.header,
+            response,
+        ),
+    )
+}
+
 #[instrument(skip(req_msg, ctx))]
 pub(crate) async fn handle_fetch_consumer_offsets_request(
     req_msg: RequestMessage<FetchConsumerOffsetsRequest>,
@@ -0,0 +1,81 @@
+use std::io::Error as IoError;
+
+use tracing::{debug, info, instrument, warn};
+
+use fluvio_auth::{AuthContext, InstanceAction};
+use fluvio_controlplane_metadata::extended::ObjectType;
+use fluvio_protocol::api::{RequestMessage, ResponseMessage};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_spu_schema::server::delete_records::{DeleteRecordsRequest, DeleteRecordsResponse};
+
+use crate::core::DefaultSharedGlobalContext;
+
+pub(crate) const AUDIT_LOG_TARGET: &str = "fluvio_spu::audit";
+
+/// delete records of partition before offset, deletion is recorded in audit log
+#[instrument(skip(req_msg, ctx, auth))]
+pub(crate) async fn handle_delete_records_request<AC: AuthContext>(
+    req_msg: RequestMessage<DeleteRecordsRequest>,
+    ctx: DefaultSharedGlobalContext,
+    auth: &AC,
+    peer: &str,
+) -> Result<ResponseMessage<DeleteRecordsResponse>, IoError> {
+    let replica_id = &req_msg.request.replica_id;
+    let before_offset = req_msg.request.before_offset;
+
+    let authorized = auth
+        .allow_instance_action(ObjectType::Topic, InstanceAction::Delete, &replica_id.topic)
+        .await
+        .unwrap_or(false);
+    if !authorized {
+        warn!(%replica_id, peer, "unauthorized delete records request");
+        return Ok(req_msg.new_response(DeleteRecordsResponse {
+            error_code: ErrorCode::PermissionDenied,
+            ..Default::default()
+        }));
+    }
+
+    let Some(leader) = ctx.leaders_state().get(replica_id).await else {
+        debug!(%replica_id, "delete records requested from non leader");
+        return Ok(req_msg.new_response(DeleteRecordsResponse {
+            error_code: ErrorCode::PartitionNotLeader,
+            ..Default::default()
+        }));
+    };
+
+    if before_offset < 0 || before_offset > leader.hw() {
+        return Ok(req_msg.new_response(DeleteRecordsResponse {
+            error_code: ErrorCode::OffsetOutOfRange,
+            log_start_offset: leader.log_start_offset().await,
+        }));
+    }
+
+    let previous_start = leader.log_start_offset().await;
+    let response = match leader
+        .delete_records_before(before_offset, ctx.follower_notifier())
+        .await
+    {
+        Ok(log_start_offset) => {
+            info!(
+                target: AUDIT_LOG_TARGET,
+                principal = auth.principal().unwrap_or("-"),
+                peer,
+                replica = %replica_id,
+                before_offset,
+                previous_start,
+                log_start_offset,
+                "records deleted"
+            );
+            DeleteRecordsResponse {
+                error_code: ErrorCode::None,
+                log_start_offset,
+            }
+        }
+        Err(err) => DeleteRecordsResponse {
+            error_code: ErrorCode::Other(format!("unable to delete records: {err}")),
+            log_start_offset: previous_start,
+        },
+    };
+
+    Ok(req_msg.new_response(response))
+}
@@ -13,7 +13,6 @@ use fluvio_spu_schema::fetch::{
 use fluvio_controlplane_metadata::partition::ReplicaKey;
 
 use crate::core::DefaultSharedGlobalContext;
-use crate::traffic::TrafficType;
 
 /// perform log fetch request using zero copy write
 #[instrument(
@@ -33,7 +32,7 @@ pub async fn handle_fetch_request(
 
     for topic_request in &fetch_request.topics {
         let topic_response =
-            handle_fetch_topic(&ctx, &fetch_request, topic_request, header.is_connector()).await?;
+            handle_fetch_topic(&ctx, &fetch_request, topic_request, header.client_id()).await?;
         fetch_response.topics.push(topic_response);
     }
 
@@ -60,7 +59,7 @@ async fn handle_fetch_topic(
     ctx: &DefaultSharedGlobalContext,
     fetch_request: &FileFetchRequest,
     topic_request: &FetchableTopic,
-    is_connector: bool,
+    client_id: &str,
 ) -> Result<FetchableTopicResponse<FileRecordSet>> {
     let topic = &topic_request.name;
 
@@ -71,14 +70,9 @@ async fn handle_fetch_topic(
 
     for partition_request in &topic_request.fetch_partitions {
         let replica_id = ReplicaKey::new(topic.clone(), partition_request.partition_index);
-        let partition_response = handle_fetch_partition(
-            ctx,
-            replica_id,
-            fetch_request,
-            partition_request,
-            is_connector,
-        )
-        .await?;
+        let partition_response =
+            handle_fetch_partition(ctx, replica_id, fetch_request, partition_request, client_id)
+                .await?;
         topic_response.partitions.push(partition_response);
     }
 
@@ -94,7 +88,7 @@ async fn handle_fetch_partition(
     replica_id: ReplicaKey,
     fetch_request: &FileFetchRequest,
     partition_request: &FetchPartition,
-    is_connector: bool,
+    client_id: &str,
 ) -> Result<FetchablePartitionResponse<FileRecordSet>, SocketError> {
     trace!("Fetching partition:");
     let fetch_offset = partition_request.fetch_offset;
@@ -129,7 +123,7 @@ async fn handle_fetch_partition(
 
             if let Some(file_slice) = slice.file_slice {
                 metrics.outbound().increase(
-                    is_connector,
+                    client_id,
                     (slice.end.hw - slice.start) as u64,
                     file_slice.len(),
                 );
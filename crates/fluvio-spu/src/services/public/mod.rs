@@ -4,15 +4,23 @@ mod fetch_handler;
 mod offset_request;
 mod offset_update;
 mod stream_fetch;
+mod stream_credits;
 mod consumer_handler;
+mod stats_handler;
+mod replication_status_handler;
+mod delete_records_handler;
+mod spu_config_handler;
 
 #[cfg(test)]
 mod tests;
 mod conn_context;
+mod access_log;
+mod quota;
 
 use std::sync::Arc;
 use async_trait::async_trait;
-use fluvio_auth::Authorization;
+use fluvio_auth::{AuthContext, Authorization};
+use fluvio_protocol::Encoder;
 use fluvio_protocol::api::Request;
 use fluvio_protocol::api::RequestMessage;
 use fluvio_protocol::link::ErrorCode;
@@ -22,11 +30,13 @@ use tracing::{info, debug, trace, instrument};
 use futures_util::StreamExt;
 use anyhow::Result;
 
-use fluvio_socket::FluvioSocket;
+use fluvio_socket::{FluvioSocket, ExclusiveFlvSink};
 use fluvio_service::{FluvioApiServer, FluvioService, ConnectInfo, call_service};
+use fluvio_service::limits::reject_connection;
 use fluvio_spu_schema::server::SpuServerRequest;
 use fluvio_spu_schema::server::SpuServerApiKey;
 use fluvio_types::event::StickyEvent;
+use fluvio_future::timer::sleep;
 
 use crate::core::DefaultSharedGlobalContext;
 use crate::mirroring::home::connection::MirrorHomeHandler;
@@ -34,14 +44,20 @@ use crate::services::auth::SpuAuthGlobalContext;
 use crate::services::auth::SpuAuthServiceContext;
 use crate::services::public::consumer_handler::handle_delete_consumer_offset_request;
 use crate::services::public::consumer_handler::handle_fetch_consumer_offsets_request;
+use crate::services::public::consumer_handler::handle_reset_consumer_offset_request;
 use crate::services::public::consumer_handler::handle_update_consumer_offset_request;
 use self::api_versions::handle_api_version_request;
 use self::produce_handler::handle_produce_request;
 use self::fetch_handler::handle_fetch_request;
 use self::offset_request::handle_offset_request;
 use self::offset_update::handle_offset_update;
+use self::stats_handler::handle_partition_stats_request;
+use self::replication_status_handler::handle_replication_status_request;
+use self::delete_records_handler::handle_delete_records_request;
+use self::spu_config_handler::handle_spu_config_request;
 use self::stream_fetch::{StreamFetchHandler, publishers::StreamPublishers};
 use self::conn_context::ConnectionContext;
+use self::access_log::AccessLogEntry;
 use std::fmt::Debug;
 
 pub(crate) type SpuPublicServer<A> =
@@ -62,7 +78,13 @@ where
         "Starting SPU public service:",
     );
 
+    let tuning = auth_ctx.global_ctx.config().socket;
+    let limits = auth_ctx.global_ctx.config().connection_limits;
+    let ip_filter = auth_ctx.global_ctx.ip_filter().clone();
     FluvioApiServer::new(addr, auth_ctx, PublicService::<A>::new())
+        .with_socket_tuning(tuning)
+        .with_connection_limits(limits)
+        .with_ip_filter(ip_filter)
 }
 
 #[derive(Debug)]
@@ -92,7 +114,7 @@ where
         self: Arc<Self>,
         context: Self::Context,
         mut socket: FluvioSocket,
-        _connection: ConnectInfo,
+        connection: ConnectInfo,
     ) -> Result<()> {
         let auth_context = context
             .auth
@@ -102,6 +124,16 @@ where
                 let io_error: std::io::Error = err.into();
                 io_error
             })?;
+
+        // counted against limits until connection is closed
+        let permits =
+            match connection.acquire_client(auth_context.principal(), auth_context.client_addr()) {
+                Ok(permits) => permits,
+                Err(err) => {
+                    reject_connection(socket, err).await;
+                    return Ok(());
+                }
+            };
         let service_context = SpuAuthServiceContext::new(context.global_ctx.clone(), auth_context);
         let mut mirror_request: Option<RequestMessage<StartMirrorRequest>> = None;
         let shutdown = StickyEvent::shared();
@@ -112,6 +144,7 @@ where
             let api_stream = stream.api_stream::<SpuServerRequest, SpuServerApiKey>();
             let mut event_stream = api_stream.take_until(shutdown.listen_pinned());
             let mut conn_ctx = ConnectionContext::new();
+            let mut client_id: Option<String> = None;
 
             let context = &context.global_ctx;
 
@@ -119,86 +152,52 @@ where
                 let event = event_stream.next().await;
                 match event {
                     Some(Ok(req_message)) => {
-                        debug!(%req_message,"received");
+                        let request_client_id = req_message.header().client_id();
+                        if client_id.as_deref() != Some(request_client_id.as_str()) {
+                            info!(
+                                sink_id = shared_sink.id(),
+                                client_id = %request_client_id,
+                                "client identified"
+                            );
+                            client_id = Some(request_client_id.to_owned());
+                        }
+                        debug!(%req_message, client_id = %request_client_id, "received");
                         //  println!("req: {:#?}", req_message);
                         trace!(
                             "conn: {}, received request: {:#?}",
                             shared_sink.id(),
                             req_message
                         );
-                        match req_message {
-                            SpuServerRequest::ApiVersionsRequest(request) => call_service!(
-                                request,
-                                handle_api_version_request(request),
-                                shared_sink,
-                                "ApiVersionsRequest"
-                            ),
-                            SpuServerRequest::ProduceRequest(request) => call_service!(
-                                request,
-                                handle_produce_request(request, context.clone()),
-                                shared_sink,
-                                "ProduceRequest"
-                            ),
-                            SpuServerRequest::FileFetchRequest(request) => {
-                                handle_fetch_request(request, context.clone(), shared_sink.clone())
-                                    .await?
-                            }
-                            SpuServerRequest::FetchOffsetsRequest(request) => call_service!(
-                                request,
-                                handle_offset_request(request, context.clone()),
-                                shared_sink,
-                                "FetchOffsetsRequest"
-                            ),
-                            SpuServerRequest::FileStreamFetchRequest(request) => {
-                                StreamFetchHandler::start(
-                                    request,
-                                    context.clone(),
-                                    &mut conn_ctx,
-                                    shared_sink.clone(),
-                                    shutdown.clone(),
-                                )
-                                .await?;
-                            }
-                            SpuServerRequest::UpdateOffsetsRequest(request) => call_service!(
-                                request,
-                                handle_offset_update(request, &mut conn_ctx),
-                                shared_sink,
-                                "UpdateOffsetsRequest"
-                            ),
-                            SpuServerRequest::UpdateConsumerOffsetRequest(request) => {
-                                call_service!(
-                                    request,
-                                    handle_update_consumer_offset_request(
-                                        request,
-                                        context.clone(),
-                                        &mut conn_ctx
-                                    ),
-                                    shared_sink,
-                                    "UpdateConsumerRequest"
-                                )
-                            }
-                            SpuServerRequest::DeleteConsumerOffsetRequest(request) => {
-                                call_service!(
-                                    request,
-                                    handle_delete_consumer_offset_request(request, context.clone()),
-                                    shared_sink,
-                                    "DeleteConsumerRequest"
-                                )
-                            }
-                            SpuServerRequest::FetchConsumerOffsetsRequest(request) => {
-                                call_service!(
-                                    request,
-                                    handle_fetch_consumer_offsets_request(request, context.clone()),
-                                    shared_sink,
-                                    "FetchConsumersRequest"
-                                )
-                            }
+                        let req_message = match req_message {
                             SpuServerRequest::StartMirrorRequest(request) => {
                                 // send mirror mode, afer that mirror cycle will be started
                                 mirror_request = Some(request);
                                 break;
                             }
+                            req_message => req_message,
+                        };
+
+                        let access_log = context.access_log_enabled().then(|| {
+                            AccessLogEntry::new(
+                                &req_message,
+                                connection.peer(),
+                                service_context.auth.principal(),
+                            )
+                        });
+                        let result = dispatch_request(
+                            req_message,
+                            context,
+                            &service_context.auth,
+                            connection.peer(),
+                            &mut conn_ctx,
+                            &shared_sink,
+                            &shutdown,
+                        )
+                        .await;
+                        if let Some(entry) = access_log {
+                            entry.finish(&result);
                         }
+                        result?;
                     }
                     Some(Err(e)) => {
                         debug!(
@@ -220,11 +219,157 @@ where
         }
 
         shutdown.notify();
+        drop(permits);
         debug!("service terminated");
         Ok(())
     }
 }
 
+/// handle request of public service, response is sent by handler
+async fn dispatch_request<AC: AuthContext>(
+    req_message: SpuServerRequest,
+    context: &DefaultSharedGlobalContext,
+    auth: &AC,
+    peer: &str,
+    conn_ctx: &mut ConnectionContext,
+    sink: &ExclusiveFlvSink,
+    shutdown: &Arc<StickyEvent>,
+) -> Result<()> {
+    let timeout = conn_ctx.request_timeout();
+    match req_message {
+        SpuServerRequest::ApiVersionsRequest(request) => {
+            conn_ctx.set_request_timeout(request.request.request_timeout());
+            call_service!(
+                request,
+                handle_api_version_request(request),
+                sink,
+                "ApiVersionsRequest"
+            )
+        }
+        SpuServerRequest::ProduceRequest(request) => {
+            // connection over quota waits before its records are written
+            let bytes = request.request.write_size(request.header.api_version()) as u64;
+            let throttle = conn_ctx
+                .produce_quota()
+                .record(bytes, context.produce_byte_rate());
+            if !throttle.is_zero() {
+                debug!(peer, ?throttle, "produce quota exceeded, throttling");
+                sleep(throttle).await;
+            }
+            call_service!(
+                request,
+                handle_produce_request(request, context.clone()),
+                sink,
+                "ProduceRequest",
+                timeout
+            )
+        }
+        SpuServerRequest::FileFetchRequest(request) => {
+            handle_fetch_request(request, context.clone(), sink.clone()).await?
+        }
+        SpuServerRequest::FetchOffsetsRequest(request) => call_service!(
+            request,
+            handle_offset_request(request, context.clone()),
+            sink,
+            "FetchOffsetsRequest",
+            timeout
+        ),
+        SpuServerRequest::FileStreamFetchRequest(request) => {
+            StreamFetchHandler::start(
+                request,
+                context.clone(),
+                conn_ctx,
+                sink.clone(),
+                shutdown.clone(),
+            )
+            .await?;
+        }
+        SpuServerRequest::UpdateOffsetsRequest(request) => call_service!(
+            request,
+            handle_offset_update(request, conn_ctx),
+            sink,
+            "UpdateOffsetsRequest",
+            timeout
+        ),
+        SpuServerRequest::UpdateConsumerOffsetRequest(request) => {
+            call_service!(
+                request,
+                handle_update_consumer_offset_request(request, context.clone(), conn_ctx),
+                sink,
+                "UpdateConsumerRequest",
+                timeout
+            )
+        }
+        SpuServerRequest::DeleteConsumerOffsetRequest(request) => {
+            call_service!(
+                request,
+                handle_delete_consumer_offset_request(request, context.clone()),
+                sink,
+                "DeleteConsumerRequest",
+                timeout
+            )
+        }
+        SpuServerRequest::FetchConsumerOffsetsRequest(request) => {
+            call_service!(
+                request,
+                handle_fetch_consumer_offsets_request(request, context.clone()),
+                sink,
+                "FetchConsumersRequest",
+                timeout
+            )
+        }
+        SpuServerRequest::ResetConsumerOffsetRequest(request) => {
+            call_service!(
+                request,
+                handle_reset_consumer_offset_request(request, context.clone()),
+                sink,
+                "ResetConsumerRequest",
+                timeout
+            )
+        }
+        SpuServerRequest::FetchPartitionStatsRequest(request) => {
+            call_service!(
+                request,
+                handle_partition_stats_request(request, context.clone()),
+                sink,
+                "FetchPartitionStatsRequest",
+                timeout
+            )
+        }
+        SpuServerRequest::FetchReplicationStatusRequest(request) => {
+            call_service!(
+                request,
+                handle_replication_status_request(request, context.clone()),
+                sink,
+                "FetchReplicationStatusRequest",
+                timeout
+            )
+        }
+        SpuServerRequest::DeleteRecordsRequest(request) => {
+            call_service!(
+                request,
+                handle_delete_records_request(request, context.clone(), auth, peer),
+                sink,
+                "DeleteRecordsRequest",
+                timeout
+            )
+        }
+        SpuServerRequest::FetchSpuConfigRequest(request) => {
+            call_service!(
+                request,
+                handle_spu_config_request(request, context.clone(), auth, peer),
+                sink,
+                "FetchSpuConfigRequest",
+                timeout
+            )
+        }
+        SpuServerRequest::StartMirrorRequest(_) => {
+            debug!("mirror request is handled by connection");
+        }
+    }
+    Ok(())
+}
+
 async fn send_private_request_to_leader<R: Request>(
     ctx: &DefaultSharedGlobalContext,
     replica_id: &ReplicaKey,
@@ -49,6 +49,7 @@ pub async fn handle_offset_request(
                 partition_response.error_code = ErrorCode::None;
                 partition_response.start_offset = start_offset;
                 partition_response.last_stable_offset = hw;
+                partition_response.log_end_offset = replica.leo();
 
                 // This is only for compatibility with older clients
                 // now we're usign `FetchConsumerOffsetsRequest` to fetch consumer offset
@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use fluvio_controlplane::sc_api::update_partition::PartitionStatRequest;
 use tokio::select;
@@ -8,9 +8,10 @@ use anyhow::{anyhow, Result};
 
 use fluvio_protocol::api::{RequestKind, RequestHeader};
 use fluvio_spu_schema::Isolation;
-use fluvio_protocol::record::{BatchRecords, Offset, Batch, RawRecords};
+use fluvio_protocol::record::{BatchRecords, Offset, Batch, RawRecords, NO_TIMESTAMP};
 use fluvio::Compression;
-use fluvio_controlplane_metadata::topic::CompressionAlgorithm;
+use fluvio_compression::CompressionError;
+use fluvio_controlplane_metadata::topic::{CompressionAlgorithm, TimestampPolicy};
 use fluvio_storage::StorageError;
 use fluvio_spu_schema::produce::{
     ProduceResponse, TopicProduceResponse, PartitionProduceResponse, PartitionProduceData,
@@ -32,8 +33,6 @@ use crate::smartengine::EngineError;
 use crate::smartengine::map_engine_error;
 use crate::smartengine::produce_batch::ProduceBatchIterator;
 
-use crate::traffic::TrafficType;
-
 struct TopicWriteResult {
     topic: String,
     partitions: Vec<PartitionWriteResult>,
@@ -102,6 +101,15 @@ async fn handle_produce_topic(
 
     for mut partition_request in topic_request.partitions.into_iter() {
         let replica_id = ReplicaKey::new(topic.clone(), partition_request.partition_index);
+        if ctx.rejects_produce() {
+            debug!(%replica_id, "cluster is in maintenance, rejecting produce");
+            topic_result.partitions.push(PartitionWriteResult::error(
+                replica_id,
+                ErrorCode::ClusterInMaintenance,
+            ));
+            continue;
+        }
+
         let leader_state = match ctx.leaders_state().get(&replica_id).await {
             Some(leader_state) => leader_state,
             None => {
@@ -125,6 +133,15 @@ async fn handle_produce_topic(
             continue;
         }
 
+        if leader_state.is_fenced() {
+            debug!(%replica_id, "leader is fenced by newer leader epoch");
+            topic_result.partitions.push(PartitionWriteResult::error(
+                replica_id,
+                ErrorCode::NotLeaderForPartition,
+            ));
+            continue;
+        }
+
         if let Err(err) = apply_smartmodules(
             &mut partition_request,
             smartmodules,
@@ -153,7 +170,7 @@ async fn handle_produce_topic(
                 replica_id,
                 leader_state,
                 partition_request,
-                header.is_connector(),
+                header.client_id(),
             )
             .await
         };
@@ -172,7 +189,7 @@ async fn handle_produce_partition(
     replica_key: ReplicaKey,
     leader_state: SharedFileLeaderState,
     partition_request: PartitionProduceData<RecordSet<RawRecords>>,
-    is_connector: bool,
+    client_id: &str,
 ) -> PartitionWriteResult {
     trace!("Handling produce request for partition:");
 
@@ -186,21 +203,36 @@ async fn handle_produce_partition(
 
     let mut records = partition_request.records;
 
+    if let Err(err) = compress_records(&mut records, &replica_metadata.compression_type) {
+        error!(%replica_key, %err, "Unable to compress batch with topic compression");
+        return PartitionWriteResult::error(replica_key, ErrorCode::CompressionError);
+    }
+
     if validate_records(&records, replica_metadata.compression_type).is_err() {
         error!(%replica_key, "Compression in batch not supported by this topic");
         return PartitionWriteResult::error(replica_key, ErrorCode::CompressionError);
     }
 
+    if let Err(err) = apply_timestamp_policy(
+        &mut records,
+        &replica_metadata.timestamp_policy,
+        now_timestamp(),
+    ) {
+        error!(%replica_key, %err, "Timestamp rejected by topic policy");
+        return PartitionWriteResult::error(replica_key, err);
+    }
+
+    let metrics = ctx.metrics();
+    let linger = Duration::from_millis(ctx.config().log.write_linger_ms as u64);
     let write_result = leader_state
-        .write_record_set(&mut records, ctx.follower_notifier())
+        .write_record_set_coalesced(records, ctx.follower_notifier(), linger, &metrics)
         .await;
 
-    let metrics = ctx.metrics();
     match write_result {
         Ok((base_offset, leo, bytes)) => {
             metrics
                 .inbound()
-                .increase(is_connector, (leo - base_offset) as u64, bytes as u64);
+                .increase(client_id, (leo - base_offset) as u64, bytes as u64);
 
             PartitionWriteResult::ok(replica_key, base_offset, leo)
         }
@@ -302,6 +334,62 @@ async fn apply_smartmodules(
     Ok(())
 }
 
+/// overwrite batch timestamps with append time, or check producer timestamps against clock skew
+fn apply_timestamp_policy<R: BatchRecords>(
+    records: &mut RecordSet<R>,
+    policy: &TimestampPolicy,
+    now: i64,
+) -> Result<(), ErrorCode> {
+    for batch in records.batches.iter_mut() {
+        let header = batch.get_mut_header();
+        if policy.is_log_append_time() {
+            // compressed records can't be rewritten, so they keep their delta to first timestamp
+            let span = if header.first_timestamp == NO_TIMESTAMP {
+                0
+            } else {
+                (header.max_time_stamp - header.first_timestamp).max(0)
+            };
+            header.first_timestamp = now - span;
+            header.max_time_stamp = now;
+        } else if header.first_timestamp != NO_TIMESTAMP {
+            for timestamp in [header.first_timestamp, header.max_time_stamp] {
+                if !policy.accepts(timestamp, now) {
+                    return Err(ErrorCode::InvalidTimestamp {
+                        timestamp,
+                        max_skew_ms: policy.max_skew.unwrap_or_default().as_millis() as u64,
+                    });
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn now_timestamp() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(NO_TIMESTAMP, |now| now.as_millis() as i64)
+}
+
+/// compress uncompressed batches with codec of the topic,
+/// so producers which can't compress still honor topic compression
+fn compress_records(
+    records: &mut RecordSet<RawRecords>,
+    compression: &CompressionAlgorithm,
+) -> Result<(), CompressionError> {
+    let codec = match compression {
+        CompressionAlgorithm::Any | CompressionAlgorithm::None => return Ok(()),
+        CompressionAlgorithm::Gzip => Compression::Gzip,
+        CompressionAlgorithm::Snappy => Compression::Snappy,
+        CompressionAlgorithm::Lz4 => Compression::Lz4,
+        CompressionAlgorithm::Zstd => Compression::Zstd,
+    };
+    for batch in records.batches.iter_mut() {
+        batch.compress(codec)?;
+    }
+    Ok(())
+}
+
 fn validate_records<R: BatchRecords>(
     records: &RecordSet<R>,
     compression: CompressionAlgorithm,
@@ -447,3 +535,88 @@ fn into_response(topic_results: Vec<TopicWriteResult>) -> ProduceResponse {
         ..Default::default()
     }
 }
+
+#[cfg(test)]
+mod test {
+
+    use std::time::Duration;
+
+    use fluvio_controlplane_metadata::topic::TimestampType;
+    use fluvio_protocol::record::{MemoryRecords, Record};
+
+    use super::*;
+
+    fn records(first_timestamp: i64, max_time_stamp: i64) -> RecordSet<MemoryRecords> {
+        let mut batch = Batch::<MemoryRecords>::default();
+        batch.add_record(Record::new("value"));
+        let header = batch.get_mut_header();
+        header.first_timestamp = first_timestamp;
+        header.max_time_stamp = max_time_stamp;
+        RecordSet {
+            batches: vec![batch],
+        }
+    }
+
+    #[test]
+    fn test_log_append_time() {
+        let policy = TimestampPolicy {
+            type_: TimestampType::LogAppendTime,
+            max_skew: None,
+        };
+        let mut set = records(1_000, 1_050);
+        apply_timestamp_policy(&mut set, &policy, 10_000).expect("applied");
+        let header = set.batches[0].get_header();
+        assert_eq!(header.first_timestamp, 9_950);
+        assert_eq!(header.max_time_stamp, 10_000);
+
+        let mut set = records(NO_TIMESTAMP, NO_TIMESTAMP);
+        apply_timestamp_policy(&mut set, &policy, 10_000).expect("applied");
+        assert_eq!(set.batches[0].get_header().first_timestamp, 10_000);
+    }
+
+    #[test]
+    fn test_create_time_skew() {
+        let policy = TimestampPolicy {
+            type_: TimestampType::CreateTime,
+            max_skew: Some(Duration::from_secs(1)),
+        };
+        let mut set = records(9_500, 10_500);
+        apply_timestamp_policy(&mut set, &policy, 10_000).expect("within skew");
+        assert_eq!(set.batches[0].get_header().first_timestamp, 9_500);
+
+        let mut set = records(9_500, 11_500);
+        assert_eq!(
+            apply_timestamp_policy(&mut set, &policy, 10_000),
+            Err(ErrorCode::InvalidTimestamp {
+                timestamp: 11_500,
+                max_skew_ms: 1_000
+            })
+        );
+
+        let mut set = records(NO_TIMESTAMP, NO_TIMESTAMP);
+        apply_timestamp_policy(&mut set, &policy, 10_000).expect("unknown timestamp");
+    }
+
+    #[test]
+    fn test_compress_records() {
+        let raw = |set: RecordSet<MemoryRecords>| -> RecordSet<RawRecords> {
+            set.try_into().expect("raw")
+        };
+
+        let mut set = raw(records(NO_TIMESTAMP, NO_TIMESTAMP));
+        compress_records(&mut set, &CompressionAlgorithm::Any).expect("compress");
+        assert_eq!(
+            set.batches[0].get_compression().expect("compression"),
+            Compression::None
+        );
+
+        compress_records(&mut set, &CompressionAlgorithm::Zstd).expect("compress");
+        assert_eq!(
+            set.batches[0].get_compression().expect("compression"),
+            Compression::Zstd
+        );
+        validate_records(&set, CompressionAlgorithm::Zstd).expect("valid");
+        let records = set.batches[0].memory_records().expect("records");
+        assert_eq!(records[0].value.as_ref(), b"value");
+    }
+}
@@ -0,0 +1,79 @@
+//!
+//! # Byte rate quotas
+//!
+//! Bytes produced and fetched by client connection are limited per second.
+//! Connections over quota are throttled, their requests and responses are delayed
+//! until their rate is back under quota. Bursts up to one second of quota are allowed.
+//!
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// bytes transferred by connection, shared by its requests and streams
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ByteRateQuota(Arc<Mutex<Option<Bucket>>>);
+
+#[derive(Debug)]
+struct Bucket {
+    /// bytes which can be transferred without delay, negative when over quota
+    available: f64,
+    updated: Instant,
+}
+
+impl ByteRateQuota {
+    /// record transfer of `bytes`, returns how long to wait to stay under `rate` bytes per second
+    pub(crate) fn record(&self, bytes: u64, rate: Option<u32>) -> Duration {
+        self.record_at(bytes, rate, Instant::now())
+    }
+
+    fn record_at(&self, bytes: u64, rate: Option<u32>, now: Instant) -> Duration {
+        let Ok(mut bucket) = self.0.lock() else {
+            return Duration::ZERO;
+        };
+        let Some(rate) = rate.filter(|rate| *rate > 0).map(f64::from) else {
+            *bucket = None;
+            return Duration::ZERO;
+        };
+        let bucket = bucket.get_or_insert(Bucket {
+            available: rate,
+            updated: now,
+        });
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.available = (bucket.available + elapsed * rate).min(rate) - bytes as f64;
+        bucket.updated = now;
+        if bucket.available >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-bucket.available / rate)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_byte_rate_quota() {
+        let quota = ByteRateQuota::default();
+        let start = Instant::now();
+
+        assert_eq!(quota.record_at(600, Some(1000), start), Duration::ZERO);
+        assert_eq!(
+            quota.record_at(900, Some(1000), start),
+            Duration::from_millis(500)
+        );
+        // deficit is paid back over time
+        let later = start + Duration::from_millis(500);
+        assert_eq!(quota.record_at(0, Some(1000), later), Duration::ZERO);
+        assert_eq!(
+            quota.record_at(100, Some(1000), later),
+            Duration::from_millis(100)
+        );
+
+        // without quota nothing is throttled and usage is forgotten
+        assert_eq!(quota.record_at(10_000, None, later), Duration::ZERO);
+        assert_eq!(quota.record_at(1000, Some(1000), later), Duration::ZERO);
+    }
+}
@@ -0,0 +1,35 @@
+use std::io::Error as IoError;
+
+use tracing::{debug, instrument};
+
+use fluvio_protocol::api::{RequestMessage, ResponseMessage};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_spu_schema::server::replication_status::{
+    FetchReplicationStatusRequest, FetchReplicationStatusResponse,
+};
+
+use crate::core::DefaultSharedGlobalContext;
+
+#[instrument(skip(req_msg, ctx))]
+pub(crate) async fn handle_replication_status_request(
+    req_msg: RequestMessage<FetchReplicationStatusRequest>,
+    ctx: DefaultSharedGlobalContext,
+) -> Result<ResponseMessage<FetchReplicationStatusResponse>, IoError> {
+    let replica_id = &req_msg.request.replica_id;
+
+    let response = match ctx.leaders_state().get(replica_id).await {
+        Some(leader) => FetchReplicationStatusResponse {
+            error_code: ErrorCode::None,
+            status: leader.replication_status().await,
+        },
+        None => {
+            debug!(%replica_id, "replication status requested from non leader");
+            FetchReplicationStatusResponse {
+                error_code: ErrorCode::PartitionNotLeader,
+                ..Default::default()
+            }
+        }
+    };
+
+    Ok(req_msg.new_response(response))
+}
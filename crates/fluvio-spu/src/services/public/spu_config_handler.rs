@@ -0,0 +1,47 @@
+use std::io::Error as IoError;
+
+use tracing::{instrument, warn};
+
+use fluvio_auth::{AuthContext, TypeAction};
+use fluvio_controlplane_metadata::extended::ObjectType;
+use fluvio_protocol::api::{RequestMessage, ResponseMessage};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_spu_schema::server::spu_config::{FetchSpuConfigRequest, FetchSpuConfigResponse};
+
+use crate::core::DefaultSharedGlobalContext;
+
+/// configuration in effect on this SPU, with runtime overrides applied
+#[instrument(skip(req_msg, ctx, auth))]
+pub(crate) async fn handle_spu_config_request<AC: AuthContext>(
+    req_msg: RequestMessage<FetchSpuConfigRequest>,
+    ctx: DefaultSharedGlobalContext,
+    auth: &AC,
+    peer: &str,
+) -> Result<ResponseMessage<FetchSpuConfigResponse>, IoError> {
+    let authorized = auth
+        .allow_type_action(ObjectType::Spu, TypeAction::Read)
+        .await
+        .unwrap_or(false);
+    if !authorized {
+        warn!(peer, "unauthorized spu config request");
+        return Ok(req_msg.new_response(FetchSpuConfigResponse {
+            error_code: ErrorCode::PermissionDenied,
+            ..Default::default()
+        }));
+    }
+
+    let mut config = ctx.effective_config().to_json();
+    // log level is only changed at runtime, startup level comes from environment
+    config["log_level"] = ctx.runtime_config().log_level.into();
+    let response = match serde_json::to_string(&config) {
+        Ok(config) => FetchSpuConfigResponse {
+            error_code: ErrorCode::None,
+            config,
+        },
+        Err(err) => FetchSpuConfigResponse {
+            error_code: ErrorCode::Other(format!("unable to encode config: {err}")),
+            ..Default::default()
+        },
+    };
+    Ok(req_msg.new_response(response))
+}
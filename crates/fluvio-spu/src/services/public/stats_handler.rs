@@ -0,0 +1,106 @@
+use std::io::Error as IoError;
+
+use tracing::{debug, instrument};
+
+use fluvio_protocol::api::{RequestMessage, ResponseMessage};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::{BatchHeader, Offset, NO_TIMESTAMP};
+use fluvio_spu_schema::Isolation;
+use fluvio_spu_schema::server::partition_stats::{
+    FetchPartitionStatsRequest, FetchPartitionStatsResponse, PartitionStats,
+};
+use fluvio_storage::ReplicaStorage;
+use fluvio_storage::iterators::{FileBatch, FileBatchIterator};
+
+use crate::core::DefaultSharedGlobalContext;
+use crate::replication::leader::SharedFileLeaderState;
+
+#[instrument(skip(req_msg, ctx))]
+pub(crate) async fn handle_partition_stats_request(
+    req_msg: RequestMessage<FetchPartitionStatsRequest>,
+    ctx: DefaultSharedGlobalContext,
+) -> Result<ResponseMessage<FetchPartitionStatsResponse>, IoError> {
+    let replica_id = &req_msg.request.replica_id;
+
+    let response = match ctx.leaders_state().get(replica_id).await {
+        Some(leader) => match partition_stats(&leader).await {
+            Ok(stats) => FetchPartitionStatsResponse {
+                error_code: ErrorCode::None,
+                stats,
+            },
+            Err(error_code) => FetchPartitionStatsResponse {
+                error_code,
+                ..Default::default()
+            },
+        },
+        None => {
+            debug!(%replica_id, "partition stats requested from non leader");
+            FetchPartitionStatsResponse {
+                error_code: ErrorCode::PartitionNotLeader,
+                ..Default::default()
+            }
+        }
+    };
+
+    Ok(req_msg.new_response(response))
+}
+
+async fn partition_stats(leader: &SharedFileLeaderState) -> Result<PartitionStats, ErrorCode> {
+    let (start_offset, high_watermark, size) = {
+        let storage = leader.read().await;
+        (
+            storage.get_log_start_offset(),
+            storage.get_hw(),
+            storage.get_partition_size(),
+        )
+    };
+
+    let (earliest_timestamp, latest_timestamp) = if high_watermark > start_offset {
+        let earliest = match batches(leader, start_offset).await?.next() {
+            Some(batch) => batch_header(batch)?.first_timestamp,
+            None => NO_TIMESTAMP,
+        };
+        let mut latest = NO_TIMESTAMP;
+        for batch in batches(leader, high_watermark - 1).await? {
+            latest = latest.max(batch_header(batch)?.max_time_stamp);
+        }
+        (earliest, latest)
+    } else {
+        (NO_TIMESTAMP, NO_TIMESTAMP)
+    };
+
+    let activity = leader.activity();
+    Ok(PartitionStats {
+        start_offset,
+        high_watermark,
+        size,
+        earliest_timestamp,
+        latest_timestamp,
+        produced_records: activity.produced().records(),
+        produced_bytes: activity.produced().bytes(),
+        consumed_records: activity.consumed().records(),
+        consumed_bytes: activity.consumed().bytes(),
+        active_consumers: leader.active_consumers().await as u32,
+    })
+}
+
+/// committed batches starting with batch containing `offset`, batches are read lazily
+async fn batches(
+    leader: &SharedFileLeaderState,
+    offset: Offset,
+) -> Result<impl Iterator<Item = Result<FileBatch, IoError>>, ErrorCode> {
+    let slice = leader
+        .read_records(offset, u32::MAX, Isolation::ReadCommitted)
+        .await?;
+    Ok(slice
+        .file_slice
+        .map(FileBatchIterator::from_raw_slice)
+        .into_iter()
+        .flatten())
+}
+
+fn batch_header(batch: Result<FileBatch, IoError>) -> Result<BatchHeader, ErrorCode> {
+    batch
+        .map(|batch| batch.batch.header)
+        .map_err(|err| ErrorCode::Other(format!("read batch err {err}")))
+}
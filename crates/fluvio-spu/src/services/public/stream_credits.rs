@@ -0,0 +1,139 @@
+use std::collections::VecDeque;
+
+use fluvio_protocol::record::Offset;
+use fluvio_spu_schema::server::stream_fetch::MAX_STREAM_CREDITS;
+
+/// Credit based flow control for stream fetch.
+///
+/// Each response sent to consumer uses one credit until consumer acknowledges it
+/// by updating its offset. Once all credits are used, SPU waits for consumer
+/// instead of buffering more responses on connection.
+#[derive(Debug)]
+pub(crate) struct StreamCredits {
+    credits: u32,
+    /// start offsets of responses not yet acknowledged by consumer
+    inflight: VecDeque<Offset>,
+    /// offset where next response starts, known only when last response
+    /// contained all records read
+    next_offset: Option<Offset>,
+}
+
+impl StreamCredits {
+    /// old clients don't send credits, they get single response in flight
+    pub fn new(credits: u32) -> Self {
+        Self {
+            credits: credits.clamp(1, MAX_STREAM_CREDITS),
+            inflight: VecDeque::new(),
+            next_offset: None,
+        }
+    }
+
+    /// record response starting at `start`.
+    /// `next` is where following response can start without waiting for consumer
+    pub fn sent(&mut self, start: Offset, next: Option<Offset>) {
+        self.inflight.push_back(start);
+        self.next_offset = next;
+    }
+
+    /// consumer has received records up to `offset`.
+    /// acknowledges every response starting before it since offset updates may be merged.
+    /// returns number of responses acknowledged
+    pub fn ack(&mut self, offset: Offset) -> usize {
+        let before = self.inflight.len();
+        while self.inflight.front().is_some_and(|start| *start < offset) {
+            self.inflight.pop_front();
+        }
+        if self.inflight.is_empty() {
+            self.next_offset = None;
+        }
+        before - self.inflight.len()
+    }
+
+    /// no response is waiting for acknowledgement
+    pub fn is_idle(&self) -> bool {
+        self.inflight.is_empty()
+    }
+
+    pub fn inflight(&self) -> usize {
+        self.inflight.len()
+    }
+
+    pub fn has_credit(&self) -> bool {
+        self.inflight.len() < self.credits as usize
+    }
+
+    /// offset of response which can be sent ahead of acknowledgement,
+    /// if there are records before `partition_end` and credit is available
+    pub fn next_offset(&self, partition_end: Offset) -> Option<Offset> {
+        if self.is_idle() || !self.has_credit() {
+            return None;
+        }
+        self.next_offset.filter(|next| *next < partition_end)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_single_credit() {
+        let mut credits = StreamCredits::new(0);
+        assert!(credits.is_idle());
+
+        credits.sent(0, Some(10));
+        assert!(!credits.has_credit());
+        assert_eq!(credits.next_offset(20), None);
+
+        assert_eq!(credits.ack(10), 1);
+        assert!(credits.is_idle());
+        assert_eq!(credits.next_offset(20), None);
+    }
+
+    #[test]
+    fn test_credits_limit() {
+        let mut credits = StreamCredits::new(u32::MAX);
+        for offset in 0..MAX_STREAM_CREDITS {
+            assert!(credits.has_credit());
+            credits.sent(offset.into(), None);
+        }
+        assert!(!credits.has_credit());
+    }
+
+    #[test]
+    fn test_pipelined_credits() {
+        let mut credits = StreamCredits::new(3);
+
+        credits.sent(0, Some(10));
+        // nothing new produced yet
+        assert_eq!(credits.next_offset(10), None);
+        assert_eq!(credits.next_offset(15), Some(10));
+
+        credits.sent(10, Some(15));
+        credits.sent(15, Some(20));
+        assert!(!credits.has_credit());
+        assert_eq!(credits.next_offset(30), None);
+
+        // merged acknowledgement for first two responses
+        assert_eq!(credits.ack(15), 2);
+        assert_eq!(credits.inflight(), 1);
+        assert_eq!(credits.next_offset(30), Some(20));
+
+        assert_eq!(credits.ack(20), 1);
+        assert!(credits.is_idle());
+    }
+
+    #[test]
+    fn test_unknown_next_offset() {
+        let mut credits = StreamCredits::new(3);
+
+        // response was truncated, consumer must tell where to continue
+        credits.sent(0, None);
+        assert!(credits.has_credit());
+        assert_eq!(credits.next_offset(100), None);
+
+        assert_eq!(credits.ack(5), 1);
+        assert!(credits.is_idle());
+    }
+}
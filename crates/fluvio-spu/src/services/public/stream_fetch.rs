@@ -4,24 +4,28 @@ use std::time::Instant;
 use tracing::{debug, error, instrument, trace, warn};
 use tokio::select;
 
-use fluvio_compression::CompressionError;
+use fluvio_compression::{Compression, CompressionError};
 use fluvio_controlplane_metadata::partition::ReplicaKey;
 use fluvio_types::event::{
     offsets::{OffsetPublisher, INIT_OFFSET, TOPIC_DELETED},
     StickyEvent,
 };
 use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
 use fluvio_protocol::{
+    Encoder,
     api::{RequestMessage, RequestHeader},
     record::{RecordSet, Offset, RawRecords},
 };
 use fluvio_protocol::link::{ErrorCode, smartmodule::SmartModuleTransformRuntimeError};
-use fluvio_protocol::record::Batch;
+use fluvio_protocol::record::{Batch, Record};
 use fluvio_socket::{ExclusiveFlvSink, SocketError};
-use fluvio_storage::iterators::FileBatchIterator;
+use fluvio_storage::cache::CachedBatch;
+use fluvio_storage::iterators::{FileBatch, FileBatchIterator};
 use fluvio_spu_schema::{
     server::stream_fetch::{
-        DefaultStreamFetchRequest, FileStreamFetchRequest, StreamFetchRequest, StreamFetchResponse,
+        BatchBounds, DefaultStreamFetchRequest, FileStreamFetchRequest, RecordSampling,
+        StreamFetchRequest, StreamFetchResponse,
     },
     fetch::{FilePartitionResponse, FetchablePartitionResponse},
     Isolation,
@@ -32,10 +36,11 @@ use fluvio_types::event::offsets::OffsetChangeListener;
 use crate::core::{metrics::IncreaseValue, DefaultSharedGlobalContext};
 use crate::replication::leader::SharedFileLeaderState;
 use crate::services::public::conn_context::ConnectionContext;
+use crate::services::public::quota::ByteRateQuota;
+use crate::services::public::stream_credits::StreamCredits;
 use crate::smartengine::context::SmartModuleContext;
 use crate::smartengine::batch::process_batch;
 use crate::core::metrics::SpuMetrics;
-use crate::traffic::TrafficType;
 
 /// Fetch records as stream
 pub struct StreamFetchHandler {
@@ -50,6 +55,22 @@ pub struct StreamFetchHandler {
     leader_state: SharedFileLeaderState,
     stream_id: u32,
     metrics: Arc<SpuMetrics>,
+    credits: StreamCredits,
+    server_decompress: bool,
+    sampling: RecordSampling,
+    bounds: BatchBounds,
+    bypass_cache: bool,
+    /// fetch quota of connection, shared by its streams
+    quota: ByteRateQuota,
+    ctx: DefaultSharedGlobalContext,
+}
+
+impl Drop for StreamFetchHandler {
+    fn drop(&mut self) {
+        self.metrics
+            .stream_credits()
+            .remove_inflight(self.credits.inflight() as u64);
+    }
 }
 
 impl StreamFetchHandler {
@@ -70,6 +91,7 @@ impl StreamFetchHandler {
                 .create_new_publisher(msg.topic.clone(), msg.partition, msg.consumer_id.clone())
                 .await;
             let consumer_offset_listener = offset_publisher.offset_publisher.change_listener();
+            let quota = conn_ctx.fetch_quota().clone();
 
             leader_state
                 .register_offset_publisher(&offset_publisher.offset_publisher)
@@ -86,6 +108,7 @@ impl StreamFetchHandler {
                     replica,
                     consumer_offset_listener,
                     msg,
+                    quota,
                 )
                 .await
                 {
@@ -121,7 +144,7 @@ impl StreamFetchHandler {
 
     #[allow(clippy::too_many_arguments)]
     #[instrument(
-        skip(ctx,replica,end_event,leader_state,header,msg,consumer_offset_listener),
+        skip(ctx,replica,end_event,leader_state,header,msg,consumer_offset_listener,quota),
         fields(
             replica = %replica,
             sink = sink.id()
@@ -137,6 +160,7 @@ impl StreamFetchHandler {
         replica: ReplicaKey,
         consumer_offset_listener: OffsetChangeListener,
         msg: StreamFetchRequest<FileRecordSet>,
+        quota: ByteRateQuota,
     ) -> Result<(), SocketError> {
         debug!("request: {:#?}", msg);
         let version = header.api_version();
@@ -159,8 +183,17 @@ impl StreamFetchHandler {
         };
 
         let max_bytes = msg.max_bytes as u32;
-        // compute max fetch bytes depends on smart stream
-        let max_fetch_bytes = if sm_ctx.is_some() {
+        let server_decompress = msg.server_decompress;
+        let sampling = msg.sampling;
+        let bounds = msg.bounds;
+        let bypass_cache = msg.bypass_cache;
+        // records are read into memory when processed by smart stream, decompressed, sampled
+        // or bounded, max bytes then applies to output rather than to file slice
+        let max_fetch_bytes = if sm_ctx.is_some()
+            || server_decompress
+            || sampling.is_enabled()
+            || bounds.is_enabled()
+        {
             u32::MAX
         } else {
             max_bytes
@@ -168,6 +201,7 @@ impl StreamFetchHandler {
 
         let starting_offset = msg.fetch_offset;
         let isolation = msg.isolation;
+        let credits = StreamCredits::new(msg.credits);
 
         debug!(
             max_bytes,
@@ -176,6 +210,11 @@ impl StreamFetchHandler {
             stream_id,
             sink = %sink.id(),
             starting_offset,
+            ?credits,
+            server_decompress,
+            ?sampling,
+            ?bounds,
+            bypass_cache,
             "stream fetch");
 
         let handler = Self {
@@ -190,6 +229,13 @@ impl StreamFetchHandler {
             leader_state,
             max_fetch_bytes,
             metrics: ctx.metrics(),
+            credits,
+            server_decompress,
+            sampling,
+            bounds,
+            bypass_cache,
+            quota,
+            ctx,
         };
 
         if let Err(err) = handler.process(starting_offset, sm_ctx).await {
@@ -258,6 +304,13 @@ impl StreamFetchHandler {
                         return Err(StreamFetchError::Fetch(ErrorCode::TopicDeleted))
                     }
 
+                    self.acknowledge(consumer_offset_update);
+                    if !self.credits.is_idle() {
+                        // responses are still in flight, continue from where last response ended
+                        self.send_ahead(&mut last_partition_offset, sm_ctx.as_mut()).await?;
+                        continue;
+                    }
+
                     // If the consumer offset is not behind, there is no need to send records
                     if consumer_offset_update >= last_partition_offset {
                         debug!(
@@ -294,6 +347,17 @@ impl StreamFetchHandler {
                 partition_offset_update = leader_offset_receiver.listen() => {
                     debug!(partition_offset_update, "Received leader update:");
 
+                    if !self.credits.is_idle() {
+                        last_partition_offset = partition_offset_update;
+                        if !self.credits.has_credit() {
+                            debug!(inflight = self.credits.inflight(), "no credit left, waiting for consumer");
+                            self.metrics.stream_credits().add_exhausted();
+                            continue;
+                        }
+                        self.send_ahead(&mut last_partition_offset, sm_ctx.as_mut()).await?;
+                        continue;
+                    }
+
                     let last_consumer_offset = match last_known_consumer_offset {
                         Some(last_consumer_offset) => last_consumer_offset,
                         None => {
@@ -340,6 +404,55 @@ impl StreamFetchHandler {
         Ok(())
     }
 
+    /// send records without waiting for consumer acknowledgement while there are credits
+    async fn send_ahead(
+        &mut self,
+        last_partition_offset: &mut Offset,
+        mut sm_ctx: Option<&mut SmartModuleContext>,
+    ) -> Result<(), StreamFetchError> {
+        while let Some(offset) = self.credits.next_offset(*last_partition_offset) {
+            debug!(
+                offset,
+                inflight = self.credits.inflight(),
+                "sending ahead of consumer"
+            );
+            let (next_offset, sent) = self
+                .send_back_records(offset, sm_ctx.as_deref_mut())
+                .await?;
+            if !sent {
+                break;
+            }
+            *last_partition_offset = next_offset;
+            self.metrics.stream_credits().add_pipelined();
+        }
+        Ok(())
+    }
+
+    fn record_sent(&mut self, start: Offset, next: Option<Offset>) {
+        self.credits.sent(start, next);
+        self.metrics.stream_credits().add_inflight(1);
+    }
+
+    fn acknowledge(&mut self, offset: Offset) {
+        let acked = self.credits.ack(offset);
+        self.metrics.stream_credits().remove_inflight(acked as u64);
+    }
+
+    /// batches from starting offset up to next offset, if all of them are in read cache
+    async fn read_cached_batches(
+        &self,
+        starting_offset: Offset,
+        next_offset: Offset,
+    ) -> Option<Vec<CachedBatch>> {
+        if self.bypass_cache {
+            self.metrics.read_cache().record_bypass();
+            return None;
+        }
+        self.leader_state
+            .read_cached_batches(starting_offset, next_offset, self.max_fetch_bytes)
+            .await
+    }
+
     /// send back records back to consumer
     /// return (next offset, consumer wait)
     //  consumer wait flag tells that there are records send back to consumer
@@ -362,12 +475,14 @@ impl StreamFetchHandler {
         // Read records from the leader starting from `offset`
         // Returns with the HW/LEO of the latest records available in the leader
         // This describes the range of records that can be read in this request
+        let mut slice_complete = false;
         let read_end_offset = match self
             .leader_state
             .read_records(starting_offset, self.max_fetch_bytes, self.isolation)
             .await
         {
             Ok(slice) => {
+                slice_complete = slice.complete;
                 file_partition_response.high_watermark = slice.end.hw;
                 file_partition_response.log_start_offset = slice.start;
 
@@ -400,14 +515,29 @@ impl StreamFetchHandler {
             return Ok((starting_offset, false));
         }
 
+        // recently written batches are read from memory instead of segments
+        let cached = self.read_cached_batches(starting_offset, next_offset).await;
+        let cached_end = cached
+            .as_ref()
+            .and_then(|batches| batches.last())
+            .map(|last| last.batch.get_last_offset() + 1);
+        let file_batch_iterator = match cached {
+            Some(batches) => FileBatchIterator::from_cached(batches),
+            None => FileBatchIterator::from_raw_slice(file_partition_response.records.raw_slice()),
+        };
+
         let (offset, wait, metrics_update) = match sm_ctx {
             Some(sm_ctx) => {
                 // If a SmartModule is provided, we need to read records from file to memory
                 // In-memory records are then processed by SmartModule and returned to consumer
 
-                let records = &file_partition_response.records;
-                let mut file_batch_iterator =
-                    FileBatchIterator::from_raw_slice(records.raw_slice());
+                let bounds = self.bounds;
+                // batches out of bounds are not passed to SmartModules
+                let mut file_batch_iterator = file_batch_iterator.filter(|file_batch| {
+                    file_batch
+                        .as_ref()
+                        .map_or(true, |file_batch| selects_batch(&bounds, &file_batch.batch))
+                });
 
                 let (batch, smartmodule_error) = process_batch(
                     sm_ctx.chain_mut(),
@@ -429,10 +559,56 @@ impl StreamFetchHandler {
                         smartmodule_error,
                     )
                     .await?;
+                if wait {
+                    // records filtered by SmartModule, consumer tells where to continue
+                    self.record_sent(starting_offset, None);
+                }
+                (offset, wait, metrics_update)
+            }
+            None if self.sampling.is_enabled() => {
+                // only sampled records are sent, read records to memory to select them
+                let metrics_update = IncreaseValue::from(&file_partition_response);
+                let (offset, wait) = self
+                    .send_sampled_response(
+                        file_partition_response,
+                        file_batch_iterator,
+                        next_offset,
+                    )
+                    .await?;
+                if wait {
+                    self.record_sent(starting_offset, None);
+                }
+                (offset, wait, metrics_update)
+            }
+            None if self.server_decompress || self.bounds.is_enabled() => {
+                // Consumer can't decompress or only wants batches in bounds,
+                // read batches to memory and send selected ones
+                let metrics_update = IncreaseValue::from(&file_partition_response);
+                let (offset, wait) = self
+                    .send_read_response(file_partition_response, file_batch_iterator, next_offset)
+                    .await?;
+                if wait {
+                    self.record_sent(starting_offset, None);
+                }
+                (offset, wait, metrics_update)
+            }
+            None if cached_end.is_some() => {
+                // Batches are in read cache, they are sent as stored without reading segments
+                debug!(?cached_end, "No SmartModule, sending back cached batches");
+                let metrics_update = IncreaseValue::from(&file_partition_response);
+                let (offset, wait) = self
+                    .send_read_response(file_partition_response, file_batch_iterator, next_offset)
+                    .await?;
+                if wait {
+                    // all cached batches are sent, next response can start after them
+                    let complete = cached_end.is_some_and(|end| end >= next_offset);
+                    self.record_sent(starting_offset, complete.then_some(next_offset));
+                }
                 (offset, wait, metrics_update)
             }
             None => {
-                // If no SmartModule is provided, respond using raw file records
+                // If no SmartModule is provided, respond using raw file records.
+                // Batches are passed through as stored, compressed batches are not decompressed
                 debug!("No SmartModule, sending back entire log");
                 let metrics_update = IncreaseValue::from(&file_partition_response);
 
@@ -458,19 +634,180 @@ impl StreamFetchHandler {
 
                 debug!(read_time_ms = %now.elapsed().as_millis(),"finish sending back records");
 
-                (
-                    read_end_offset.isolation(&self.isolation),
-                    true,
-                    metrics_update,
-                )
+                // when all records up to end were sent, next response can start at end
+                self.record_sent(starting_offset, slice_complete.then_some(next_offset));
+
+                (next_offset, true, metrics_update)
             }
         };
+        if wait {
+            // offsets covered by response, records filtered out are read by consumer as well
+            self.leader_state.activity().consumed().increase(
+                (offset - starting_offset).max(0) as u64,
+                metrics_update.bytes(),
+            );
+        }
+        let throttle = self
+            .quota
+            .record(metrics_update.bytes(), self.ctx.fetch_byte_rate());
         self.metrics
             .outbound()
-            .increase_by_value(self.header.is_connector(), metrics_update);
+            .increase_by_value(self.header.client_id(), metrics_update);
+        if !throttle.is_zero() {
+            // connection over quota waits before reading more records
+            debug!(?throttle, "fetch quota exceeded, throttling");
+            sleep(throttle).await;
+        }
         Ok((offset, wait))
     }
 
+    /// send batches read from file slice or read cache which are in bounds,
+    /// records are decompressed if requested by consumer
+    #[instrument(skip(self, file_partition_response, file_batch_iterator))]
+    async fn send_read_response(
+        &self,
+        file_partition_response: FilePartitionResponse,
+        mut file_batch_iterator: FileBatchIterator,
+        next_offset: Offset,
+    ) -> Result<(Offset, bool), StreamFetchError> {
+        if !self.server_decompress {
+            file_batch_iterator = file_batch_iterator.keep_compressed();
+        }
+
+        let mut records = RecordSet::<RawRecords>::default();
+        let mut read_offset = None;
+        let mut total_bytes = 0;
+        for file_batch in file_batch_iterator {
+            let file_batch = file_batch.map_err(|err| {
+                StreamFetchError::Fetch(ErrorCode::Other(format!("read batch err {err}")))
+            })?;
+            read_offset = Some(file_batch.batch.get_last_offset() + 1);
+            if !selects_batch(&self.bounds, &file_batch.batch) {
+                continue;
+            }
+            // consumer ends stream on batch past bounds, later batches are not needed
+            let past = self.bounds.is_past(file_batch.batch.header.first_timestamp);
+            let batch = if self.server_decompress {
+                uncompressed_batch(file_batch)
+            } else {
+                stored_batch(file_batch)
+            };
+            total_bytes += batch.write_size(0);
+            records = records.add(batch);
+            if past || total_bytes >= self.max_bytes as usize {
+                break;
+            }
+        }
+
+        // offset after last batch read, consumer continues from there even if nothing was selected
+        let Some(next_filter_offset) = read_offset else {
+            debug!(next_offset, "No records to send back, skipping");
+            return Ok((next_offset, false));
+        };
+
+        let partition_response = FetchablePartitionResponse {
+            partition_index: self.replica.partition,
+            error_code: file_partition_response.error_code,
+            high_watermark: file_partition_response.high_watermark,
+            log_start_offset: file_partition_response.log_start_offset,
+            records,
+            next_filter_offset,
+            ..Default::default()
+        };
+
+        let stream_response = StreamFetchResponse {
+            topic: self.replica.topic.clone(),
+            stream_id: self.stream_id,
+            partition: partition_response,
+        };
+
+        let response_msg = RequestMessage::<DefaultStreamFetchRequest>::response_with_header(
+            &self.header,
+            stream_response,
+        );
+
+        let mut inner_sink = self.sink.lock().await;
+        inner_sink
+            .send_response(&response_msg, self.header.api_version())
+            .await?;
+
+        Ok((next_offset, true))
+    }
+
+    /// send sampled records of batches from file slice or read cache, records are sent uncompressed
+    #[instrument(skip(self, file_partition_response, file_batch_iterator))]
+    async fn send_sampled_response(
+        &self,
+        file_partition_response: FilePartitionResponse,
+        file_batch_iterator: FileBatchIterator,
+        next_offset: Offset,
+    ) -> Result<(Offset, bool), StreamFetchError> {
+        let mut records = RecordSet::default();
+        let mut read_offset = None;
+        let mut total_bytes = 0;
+        for file_batch in file_batch_iterator {
+            let file_batch = file_batch.map_err(|err| {
+                StreamFetchError::Fetch(ErrorCode::Other(format!("read batch err {err}")))
+            })?;
+            read_offset = Some(file_batch.batch.get_last_offset() + 1);
+            if !selects_batch(&self.bounds, &file_batch.batch) {
+                continue;
+            }
+            let batch: Batch = uncompressed_batch(file_batch).try_into()?;
+            // batch past bounds is sent whole, consumer ends stream on it
+            if self.bounds.is_past(batch.header.first_timestamp) {
+                records = records.add(batch);
+                break;
+            }
+            for sampled in sample_batch(&self.sampling, batch) {
+                total_bytes += sampled.write_size(0);
+                records = records.add(sampled);
+            }
+            if total_bytes >= self.max_bytes as usize {
+                break;
+            }
+        }
+
+        // offset after last record read, consumer continues from there even if nothing was selected
+        let Some(next_filter_offset) = read_offset else {
+            debug!(next_offset, "No records to send back, skipping");
+            return Ok((next_offset, false));
+        };
+        debug!(
+            next_filter_offset,
+            batches = records.batches.len(),
+            "sending back sampled records"
+        );
+
+        let partition_response = FetchablePartitionResponse {
+            partition_index: self.replica.partition,
+            error_code: file_partition_response.error_code,
+            high_watermark: file_partition_response.high_watermark,
+            log_start_offset: file_partition_response.log_start_offset,
+            records: records.try_into()?,
+            next_filter_offset,
+            ..Default::default()
+        };
+
+        let stream_response = StreamFetchResponse {
+            topic: self.replica.topic.clone(),
+            stream_id: self.stream_id,
+            partition: partition_response,
+        };
+
+        let response_msg = RequestMessage::<DefaultStreamFetchRequest>::response_with_header(
+            &self.header,
+            stream_response,
+        );
+
+        let mut inner_sink = self.sink.lock().await;
+        inner_sink
+            .send_response(&response_msg, self.header.api_version())
+            .await?;
+
+        Ok((next_offset, true))
+    }
+
     #[instrument(skip(self, file_partition_response, batch, smartmodule_error))]
     async fn send_processed_response(
         &self,
@@ -510,7 +847,19 @@ impl StreamFetchHandler {
 
         //trace!("batch: {:#?}",batch);
 
-        let records = RecordSet::default().add(batch);
+        let batches = if self.sampling.is_enabled() {
+            sample_batch(&self.sampling, batch)
+        } else {
+            vec![batch]
+        };
+        let records = batches
+            .into_iter()
+            .fold(RecordSet::default(), |records, mut batch| {
+                if self.server_decompress {
+                    batch.header.set_compression(Compression::None);
+                }
+                records.add(batch)
+            });
         let partition_response = DefaultPartitionResponse {
             partition_index: self.replica.partition,
             error_code,
@@ -577,6 +926,66 @@ async fn send_back_error(
     Ok(())
 }
 
+/// batch with records as read from file, which have already been decompressed
+fn uncompressed_batch(file_batch: FileBatch) -> Batch<RawRecords> {
+    let mut batch = stored_batch(file_batch);
+    batch.header.set_compression(Compression::None);
+    batch
+}
+
+/// batch with records as read from file, header is kept as stored
+fn stored_batch(file_batch: FileBatch) -> Batch<RawRecords> {
+    let FileBatch {
+        batch: file_batch,
+        records,
+    } = file_batch;
+    let mut batch = Batch::<RawRecords>::default();
+    batch.base_offset = file_batch.base_offset;
+    batch.header = file_batch.header;
+    *batch.mut_records() = RawRecords(records.into());
+    batch
+}
+
+/// true if batch may have records in bounds
+fn selects_batch(bounds: &BatchBounds, batch: &Batch) -> bool {
+    bounds.selects(
+        batch.get_base_offset(),
+        batch.header.first_timestamp,
+        batch.header.max_time_stamp,
+    )
+}
+
+/// split batch into batches of consecutive records selected by sampling.
+/// Consumer derives record offset from position in batch, so every gap starts a new batch
+fn sample_batch(sampling: &RecordSampling, batch: Batch) -> Vec<Batch> {
+    let base_offset = batch.get_base_offset();
+    let header = batch.header.clone();
+
+    let mut runs: Vec<(Offset, Vec<Record>)> = vec![];
+    let mut next_offset = None;
+    for record in batch.own_records() {
+        let offset = base_offset + record.get_header().offset_delta();
+        if !sampling.selects(offset) {
+            continue;
+        }
+        match runs.last_mut() {
+            Some((_, run)) if next_offset == Some(offset) => run.push(record),
+            _ => runs.push((offset, vec![record])),
+        }
+        next_offset = Some(offset + 1);
+    }
+
+    runs.into_iter()
+        .map(|(offset, mut records)| {
+            let mut sampled = Batch::default();
+            sampled.header = header.clone();
+            sampled.set_base_offset(offset);
+            sampled.add_records(&mut records);
+            sampled
+        })
+        .collect()
+}
+
 enum StreamFetchError {
     Compression(CompressionError),
     Socket(SocketError),
@@ -0,0 +1,87 @@
+use std::{env::temp_dir, time::Duration};
+
+use tracing::debug;
+
+use fluvio_controlplane::replica::Replica;
+use fluvio_future::timer::sleep;
+use fluvio_protocol::api::RequestMessage;
+use fluvio_protocol::fixture::create_raw_recordset;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_socket::{FluvioSocket, MultiplexerSocket};
+use fluvio_spu_schema::server::partition_stats::FetchPartitionStatsRequest;
+use flv_util::fixture::ensure_clean_dir;
+
+use crate::config::SpuConfig;
+use crate::core::GlobalContext;
+use crate::replication::leader::LeaderReplicaState;
+use crate::services::public::tests::create_public_server_with_root_auth;
+
+#[fluvio_future::test(ignore)]
+async fn test_fetch_partition_stats() {
+    let test_path = temp_dir().join("test_fetch_partition_stats");
+    ensure_clean_dir(&test_path);
+    let port = portpicker::pick_unused_port().expect("No free ports left");
+
+    let addr = format!("127.0.0.1:{port}");
+    let mut spu_config = SpuConfig::default();
+    spu_config.log.base_dir = test_path;
+    let ctx = GlobalContext::new_shared_context(spu_config);
+
+    let server_end_event = create_public_server_with_root_auth(addr.to_owned(), ctx.clone()).run();
+
+    // wait for stream controller async to start
+    sleep(Duration::from_millis(100)).await;
+
+    let client_socket =
+        MultiplexerSocket::new(FluvioSocket::connect(&addr).await.expect("connect"));
+
+    let topic = "test_stats";
+    let test = Replica::new((topic.to_owned(), 0), 5001, vec![5001]);
+    let test_id = test.id.clone();
+    let replica = LeaderReplicaState::create(test, ctx.config(), ctx.status_update_owned())
+        .await
+        .expect("replica")
+        .init(&ctx)
+        .await
+        .expect("init succeeded");
+    ctx.leaders_state().insert(test_id, replica.clone()).await;
+
+    for (count, timestamp) in [(2, 1_000), (3, 2_000)] {
+        let mut records = create_raw_recordset(count);
+        records.batches[0].header.first_timestamp = timestamp;
+        records.batches[0].header.max_time_stamp = timestamp + 500;
+        replica
+            .write_record_set(&mut records, ctx.follower_notifier())
+            .await
+            .expect("write");
+    }
+
+    let response = client_socket
+        .send_and_receive(RequestMessage::new_request(
+            FetchPartitionStatsRequest::new((topic.to_owned(), 0)),
+        ))
+        .await
+        .expect("send");
+
+    assert_eq!(response.error_code, ErrorCode::None);
+    let stats = response.stats;
+    assert_eq!(stats.records(), 5);
+    assert_eq!(stats.produced_records, 5);
+    assert!(stats.produced_bytes > 0);
+    assert!(stats.size > 0);
+    assert_eq!(stats.earliest_timestamp, 1_000);
+    assert_eq!(stats.latest_timestamp, 2_500);
+    assert_eq!(stats.consumed_records, 0);
+    assert_eq!(stats.active_consumers, 0);
+
+    let response = client_socket
+        .send_and_receive(RequestMessage::new_request(
+            FetchPartitionStatsRequest::new(("unknown".to_owned(), 0)),
+        ))
+        .await
+        .expect("send");
+    assert_eq!(response.error_code, ErrorCode::PartitionNotLeader);
+
+    server_end_event.notify();
+    debug!("terminated controller");
+}
@@ -1,6 +1,6 @@
 use std::{env::temp_dir, time::Duration};
 
-use fluvio::{SmartModuleInvocation, SmartModuleInvocationWasm, SmartModuleKind};
+use fluvio::{Compression, SmartModuleInvocation, SmartModuleInvocationWasm, SmartModuleKind};
 use fluvio_controlplane::replica::Replica;
 use fluvio_smartmodule::{Record, dataplane::smartmodule::Lookback};
 use fluvio_storage::{FileReplica, iterators::FileBatchIterator};
@@ -182,9 +182,11 @@ async fn test_produce_invalid_compression() {
 
     // Make three produce requests with <records_per_request> records and check that returned offset is correct
     let records_per_request = 9;
-    let records = create_filter_records(records_per_request)
-        .try_into()
-        .expect("filter records");
+    let mut records = create_filter_raw_records(records_per_request);
+    // uncompressed batches are compressed by SPU, batches of other codecs are rejected
+    for batch in records.batches.iter_mut() {
+        batch.compress(Compression::Snappy).expect("compress");
+    }
 
     let mut produce_request = DefaultProduceRequest {
         ..Default::default()
@@ -25,12 +25,17 @@ use fluvio_protocol::{
     link::{smartmodule::SmartModuleKind as SmartModuleKindError, ErrorCode},
     ByteBuf,
 };
-use fluvio_protocol::fixture::{TEST_RECORD, create_raw_recordset};
+use fluvio_protocol::fixture::{TEST_RECORD, create_batch_with_producer, create_raw_recordset};
+use fluvio_protocol::record::RawRecords;
+use fluvio_compression::Compression;
 use fluvio_spu_schema::{
     server::update_offset::{UpdateOffsetsRequest, OffsetUpdate},
     fetch::DefaultFetchRequest,
 };
-use fluvio_spu_schema::server::stream_fetch::DefaultStreamFetchRequest;
+use fluvio_spu_schema::server::stream_fetch::{
+    BATCH_BOUNDS_API, BYPASS_CACHE_API, BatchBounds, DefaultStreamFetchRequest, RecordSampling,
+    SAMPLING_API, SERVER_DECOMPRESS_API,
+};
 use crate::services::public::tests::{
     create_filter_raw_records, create_public_server_with_root_auth, read_records, vec_to_batch,
 };
@@ -3078,3 +3083,323 @@ async fn test_stream_fetch_sends_topic_delete_error_on_topic_delete() {
     server_end_event.notify();
     debug!("terminated controller");
 }
+
+#[fluvio_future::test(ignore)]
+async fn test_stream_fetch_sampling() {
+    let test_path = temp_dir().join("test_stream_fetch_sampling");
+    ensure_clean_dir(&test_path);
+    let port = portpicker::pick_unused_port().expect("No free ports left");
+
+    let addr = format!("127.0.0.1:{port}");
+    let mut spu_config = SpuConfig::default();
+    spu_config.log.base_dir = test_path;
+    let ctx = GlobalContext::new_shared_context(spu_config);
+
+    let server_end_event = create_public_server_with_root_auth(addr.to_owned(), ctx.clone()).run();
+
+    // wait for stream controller async to start
+    sleep(Duration::from_millis(100)).await;
+
+    let client_socket =
+        MultiplexerSocket::new(FluvioSocket::connect(&addr).await.expect("connect"));
+
+    let topic = "test_sampling".to_owned();
+    let test = Replica::new((topic.clone(), 0), 5001, vec![5001]);
+    let test_id = test.id.clone();
+    let replica = LeaderReplicaState::create(test, ctx.config(), ctx.status_update_owned())
+        .await
+        .expect("replica")
+        .init(&ctx)
+        .await
+        .expect("init succeeded");
+
+    ctx.leaders_state().insert(test_id, replica.clone()).await;
+
+    replica
+        .write_record_set(&mut create_raw_recordset(10), ctx.follower_notifier())
+        .await
+        .expect("write");
+
+    let stream_request = DefaultStreamFetchRequest::builder()
+        .topic(topic.clone())
+        .max_bytes(1000)
+        .sampling(RecordSampling::Every(3))
+        .build()
+        .expect("request");
+
+    let mut stream = client_socket
+        .create_stream(RequestMessage::new_request(stream_request), SAMPLING_API)
+        .await
+        .expect("create stream");
+
+    let response = stream.next().await.expect("first").expect("response");
+    let partition = &response.partition;
+    assert_eq!(partition.error_code, ErrorCode::None);
+    assert_eq!(partition.next_offset_for_fetch(), Some(10));
+
+    // each sampled record is in its own batch so consumer computes its offset
+    let offsets: Vec<i64> = partition
+        .records
+        .batches
+        .iter()
+        .map(|batch| {
+            assert_eq!(batch.get_last_offset(), batch.base_offset);
+            batch.base_offset
+        })
+        .collect();
+    assert_eq!(offsets, vec![0, 3, 6, 9]);
+    let records = partition.records.batches[0]
+        .memory_records()
+        .expect("records");
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].value().as_ref(), TEST_RECORD);
+
+    server_end_event.notify();
+    debug!("terminated controller");
+}
+
+fn create_gzip_recordset(num_records: u16) -> RecordSet<RawRecords> {
+    let mut batch = create_batch_with_producer(12, num_records, TEST_RECORD);
+    batch.header.set_compression(Compression::Gzip);
+    RecordSet::default().add(batch.try_into().expect("compress"))
+}
+
+#[fluvio_future::test(ignore)]
+async fn test_stream_fetch_server_decompress() {
+    let test_path = temp_dir().join("test_stream_fetch_server_decompress");
+    ensure_clean_dir(&test_path);
+    let port = portpicker::pick_unused_port().expect("No free ports left");
+
+    let addr = format!("127.0.0.1:{port}");
+    let mut spu_config = SpuConfig::default();
+    spu_config.log.base_dir = test_path;
+    let ctx = GlobalContext::new_shared_context(spu_config);
+
+    let server_end_event = create_public_server_with_root_auth(addr.to_owned(), ctx.clone()).run();
+
+    // wait for stream controller async to start
+    sleep(Duration::from_millis(100)).await;
+
+    let client_socket =
+        MultiplexerSocket::new(FluvioSocket::connect(&addr).await.expect("connect"));
+
+    let topic = "test_decompress".to_owned();
+    let test = Replica::new((topic.clone(), 0), 5001, vec![5001]);
+    let test_id = test.id.clone();
+    let replica = LeaderReplicaState::create(test, ctx.config(), ctx.status_update_owned())
+        .await
+        .expect("replica")
+        .init(&ctx)
+        .await
+        .expect("init succeeded");
+
+    ctx.leaders_state().insert(test_id, replica.clone()).await;
+
+    replica
+        .write_record_set(&mut create_gzip_recordset(2), ctx.follower_notifier())
+        .await
+        .expect("write");
+
+    for (server_decompress, expected) in [(false, Compression::Gzip), (true, Compression::None)] {
+        let stream_request = DefaultStreamFetchRequest::builder()
+            .topic(topic.clone())
+            .max_bytes(1000)
+            .server_decompress(server_decompress)
+            .build()
+            .expect("request");
+
+        let mut stream = client_socket
+            .create_stream(
+                RequestMessage::new_request(stream_request),
+                SERVER_DECOMPRESS_API,
+            )
+            .await
+            .expect("create stream");
+
+        let response = stream.next().await.expect("first").expect("response");
+        let partition = &response.partition;
+        assert_eq!(partition.error_code, ErrorCode::None);
+        assert_eq!(partition.next_offset_for_fetch(), Some(2));
+        assert_eq!(partition.records.batches.len(), 1);
+
+        let batch = &partition.records.batches[0];
+        assert_eq!(batch.get_compression().expect("compression"), expected);
+        assert_eq!(batch.base_offset, 0);
+        assert_eq!(batch.get_last_offset(), 1);
+        let records = batch.memory_records().expect("records");
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].value().as_ref(), TEST_RECORD);
+    }
+
+    server_end_event.notify();
+    debug!("terminated controller");
+}
+
+#[fluvio_future::test(ignore)]
+async fn test_stream_fetch_bounds() {
+    let test_path = temp_dir().join("test_stream_fetch_bounds");
+    ensure_clean_dir(&test_path);
+    let port = portpicker::pick_unused_port().expect("No free ports left");
+
+    let addr = format!("127.0.0.1:{port}");
+    let mut spu_config = SpuConfig::default();
+    spu_config.log.base_dir = test_path;
+    let ctx = GlobalContext::new_shared_context(spu_config);
+
+    let server_end_event = create_public_server_with_root_auth(addr.to_owned(), ctx.clone()).run();
+
+    // wait for stream controller async to start
+    sleep(Duration::from_millis(100)).await;
+
+    let client_socket =
+        MultiplexerSocket::new(FluvioSocket::connect(&addr).await.expect("connect"));
+
+    let topic = "test_bounds".to_owned();
+    let test = Replica::new((topic.clone(), 0), 5001, vec![5001]);
+    let test_id = test.id.clone();
+    let replica = LeaderReplicaState::create(test, ctx.config(), ctx.status_update_owned())
+        .await
+        .expect("replica")
+        .init(&ctx)
+        .await
+        .expect("init succeeded");
+
+    ctx.leaders_state().insert(test_id, replica.clone()).await;
+
+    // batches of 2 records at timestamps 1000, 2000 and 3000
+    for timestamp in [1_000, 2_000, 3_000] {
+        let mut records = create_gzip_recordset(2);
+        records.batches[0].header.first_timestamp = timestamp;
+        records.batches[0].header.max_time_stamp = timestamp;
+        replica
+            .write_record_set(&mut records, ctx.follower_notifier())
+            .await
+            .expect("write");
+    }
+
+    let stream_request = DefaultStreamFetchRequest::builder()
+        .topic(topic.clone())
+        .max_bytes(1000)
+        .bounds(BatchBounds {
+            min_timestamp: Some(1_500),
+            max_timestamp: Some(2_500),
+            max_offset: None,
+        })
+        .build()
+        .expect("request");
+
+    let mut stream = client_socket
+        .create_stream(
+            RequestMessage::new_request(stream_request),
+            BATCH_BOUNDS_API,
+        )
+        .await
+        .expect("create stream");
+
+    let response = stream.next().await.expect("first").expect("response");
+    let partition = &response.partition;
+    assert_eq!(partition.error_code, ErrorCode::None);
+    // batches before bounds are skipped, consumer continues after last batch read
+    assert_eq!(partition.next_offset_for_fetch(), Some(6));
+    assert_eq!(partition.records.batches.len(), 2);
+
+    let batch = &partition.records.batches[0];
+    assert_eq!(batch.base_offset, 2);
+    assert_eq!(batch.get_last_offset(), 3);
+    assert_eq!(
+        batch.get_compression().expect("compression"),
+        Compression::Gzip
+    );
+    assert_eq!(batch.memory_records().expect("records").len(), 2);
+
+    // batch past bounds is sent for consumer to end stream
+    let past = &partition.records.batches[1];
+    assert_eq!(past.base_offset, 4);
+    assert_eq!(past.header.first_timestamp, 3_000);
+
+    server_end_event.notify();
+    debug!("terminated controller");
+}
+
+#[fluvio_future::test(ignore)]
+async fn test_stream_fetch_read_cache() {
+    let test_path = temp_dir().join("test_stream_fetch_read_cache");
+    ensure_clean_dir(&test_path);
+    let port = portpicker::pick_unused_port().expect("No free ports left");
+
+    let addr = format!("127.0.0.1:{port}");
+    let mut spu_config = SpuConfig::default();
+    spu_config.log.base_dir = test_path;
+    let ctx = GlobalContext::new_shared_context(spu_config);
+
+    let server_end_event = create_public_server_with_root_auth(addr.to_owned(), ctx.clone()).run();
+
+    // wait for stream controller async to start
+    sleep(Duration::from_millis(100)).await;
+
+    let client_socket =
+        MultiplexerSocket::new(FluvioSocket::connect(&addr).await.expect("connect"));
+
+    let topic = "test_read_cache".to_owned();
+    let test = Replica::new((topic.clone(), 0), 5001, vec![5001]);
+    let test_id = test.id.clone();
+    let replica = LeaderReplicaState::create(test, ctx.config(), ctx.status_update_owned())
+        .await
+        .expect("replica")
+        .init(&ctx)
+        .await
+        .expect("init succeeded");
+
+    ctx.leaders_state().insert(test_id, replica.clone()).await;
+
+    for _ in 0..3 {
+        let mut records = create_gzip_recordset(2);
+        replica
+            .write_record_set(&mut records, ctx.follower_notifier())
+            .await
+            .expect("write");
+    }
+
+    let cache_metrics = || serde_json::to_value(ctx.metrics().read_cache()).expect("json");
+    assert!(cache_metrics()["used_bytes"].as_u64().expect("used") > 0);
+
+    for bypass_cache in [false, true] {
+        let stream_request = DefaultStreamFetchRequest::builder()
+            .topic(topic.clone())
+            .max_bytes(1000)
+            .bypass_cache(bypass_cache)
+            .build()
+            .expect("request");
+
+        let mut stream = client_socket
+            .create_stream(
+                RequestMessage::new_request(stream_request),
+                BYPASS_CACHE_API,
+            )
+            .await
+            .expect("create stream");
+
+        // batches are sent as stored, whether read from cache or segments
+        let response = stream.next().await.expect("first").expect("response");
+        let partition = &response.partition;
+        assert_eq!(partition.error_code, ErrorCode::None);
+        assert_eq!(partition.next_offset_for_fetch(), Some(6));
+        assert_eq!(partition.records.batches.len(), 3);
+        for (index, batch) in partition.records.batches.iter().enumerate() {
+            assert_eq!(batch.base_offset, index as i64 * 2);
+            assert_eq!(
+                batch.get_compression().expect("compression"),
+                Compression::Gzip
+            );
+            assert_eq!(batch.memory_records().expect("records").len(), 2);
+        }
+    }
+
+    let metrics = cache_metrics();
+    assert_eq!(metrics["hits"], 1);
+    assert_eq!(metrics["misses"], 0);
+    assert_eq!(metrics["bypassed"], 1);
+
+    server_end_event.notify();
+    debug!("terminated controller");
+}
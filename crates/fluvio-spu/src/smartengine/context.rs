@@ -66,6 +66,14 @@ impl SmartModuleContext {
             return Ok(None);
         }
 
+        if !ctx.config().smart_engine.enabled {
+            return Err(ErrorCode::SmartModuleChainInitError(format!(
+                "SmartModule engine is disabled on SPU {} ({} profile)",
+                ctx.config().id(),
+                ctx.config().profile
+            )));
+        }
+
         let mut fetched_invocations = Vec::with_capacity(invocations.len());
         for invocation in invocations {
             fetched_invocations.push(resolve_invocation(invocation, ctx)?)
@@ -106,9 +114,28 @@ fn resolve_invocation<R: ReplicaStorage>(
             .find_by_pk_key(&name)
             .map_err(|err| ErrorCode::Other(format!("error parsing SmartModule name: {err}")))?
         {
+            let mut params = invocation.params;
+            for (param, declaration) in smartmodule
+                .spec
+                .meta
+                .iter()
+                .flat_map(|meta| meta.params.iter())
+            {
+                let value = params.get(param).map(String::as_str);
+                let bound = declaration.bind(param, value).map_err(|err| {
+                    ErrorCode::SmartModuleInvalid {
+                        error: err.to_string(),
+                        name: Some(name.clone()),
+                    }
+                })?;
+                if let Some(default) = bound {
+                    params.insert(param.to_owned(), default);
+                }
+            }
             Ok(SmartModuleInvocation {
                 wasm: SmartModuleInvocationWasm::AdHoc(smartmodule.spec.wasm.payload.into()),
                 name: Some(name.clone()),
+                params,
                 ..invocation
             })
         } else {
@@ -1,21 +1,24 @@
 use std::sync::Arc;
 
 use fluvio_auth::root::RootAuthorization;
+use fluvio_auth::x509::IdentityAuthorization;
 use fluvio_storage::FileReplica;
+use fluvio_service::health::HealthServer;
 
-use crate::config::{SpuConfig, SpuOpt};
+use crate::config::{SpuCommand, SpuConfig, SpuOpt};
 use crate::services::auth::SpuAuthGlobalContext;
 use crate::services::create_internal_server;
 use crate::services::public::create_public_server;
 use crate::core::DefaultSharedGlobalContext;
 use crate::core::GlobalContext;
 use crate::control_plane::ScDispatcher;
+use crate::kv::expiry::start_consumer_offset_expiry;
 
 type FileReplicaContext = GlobalContext<FileReplica>;
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
-pub fn main_loop(opt: SpuOpt) {
+pub fn main_loop(mut opt: SpuOpt) {
     use std::time::Duration;
 
     use sysinfo::System;
@@ -26,11 +29,37 @@ pub fn main_loop(opt: SpuOpt) {
 
     use crate::monitoring::init_monitoring;
 
+    let command = opt.command.take();
+    let dry_run = opt.dry_run;
+
     // parse configuration (program exits on error)
     let (spu_config, tls_acceptor_option) = opt.process_spu_cli_or_exit();
 
+    if dry_run {
+        match serde_json::to_string_pretty(&spu_config.to_json()) {
+            Ok(config) => println!("{config}"),
+            Err(err) => eprintln!("unable to print config: {err}"),
+        }
+        return;
+    }
+
+    if let Some(SpuCommand::RebuildIndex(rebuild)) = command {
+        crate::rebuild_index::rebuild_index_or_exit(rebuild, &spu_config);
+        return;
+    }
+
     println!("starting spu server (id:{})", spu_config.id);
 
+    // executor reads thread count on first use, so it must be set before starting it
+    const EXECUTOR_THREADS: &str = "ASYNC_GLOBAL_EXECUTOR_THREADS";
+    if let Some(threads) = spu_config.profile.worker_threads()
+        && std::env::var(EXECUTOR_THREADS).is_err()
+    {
+        unsafe {
+            std::env::set_var(EXECUTOR_THREADS, threads.to_string());
+        }
+    }
+
     sysinfo::set_open_files_limit(0);
     let mut sys = System::new_all();
     sys.refresh_all();
@@ -43,9 +72,12 @@ pub fn main_loop(opt: SpuOpt) {
     info!(total_memory = sys.total_memory(), "System");
     info!(available_memory = sys.available_memory(), "System");
     info!(uptime = System::uptime(), "Uptime in secs");
+    info!(profile = %spu_config.profile, "Runtime profile");
+    info!(config = %spu_config.to_json(), "Effective config");
 
     run_block_on(async move {
-        let ctx = create_services(spu_config.clone(), true, true);
+        let tls = tls_acceptor_option.is_some();
+        let ctx = create_services(spu_config.clone(), true, true, tls);
 
         init_monitoring(ctx);
 
@@ -63,17 +95,28 @@ pub fn main_loop(opt: SpuOpt) {
 }
 
 /// create server and spin up services, but don't run server
+///
+/// when `tls` is set, public connections come through TLS proxy which passes identity of client,
+/// only admin principals of config can administer SPU
 pub fn create_services(
     local_spu: SpuConfig,
     internal: bool,
     public: bool,
+    tls: bool,
 ) -> DefaultSharedGlobalContext {
     let ctx = FileReplicaContext::new_shared_context(local_spu);
 
     let public_ep_addr = ctx.config().public_socket_addr().to_owned();
     let private_ep_addr = ctx.config().private_socket_addr().to_owned();
 
-    if public {
+    if public && tls {
+        let authorization = Arc::new(IdentityAuthorization::with_admins(
+            ctx.config().admin_principals.clone(),
+        ));
+        let auth_global_ctx = SpuAuthGlobalContext::new(ctx.clone(), authorization);
+        let pub_server = create_public_server(public_ep_addr, auth_global_ctx).with_tls_proxy(true);
+        pub_server.run();
+    } else if public {
         let authorization = Arc::new(RootAuthorization::new());
         let auth_global_ctx = SpuAuthGlobalContext::new(ctx.clone(), authorization);
         let pub_server = create_public_server(public_ep_addr, auth_global_ctx);
@@ -85,9 +128,15 @@ pub fn create_services(
         priv_server.run();
     };
 
+    if let Some(health_addr) = ctx.config().health_endpoint.clone() {
+        HealthServer::new(health_addr, ctx.readiness().clone()).run();
+    }
+
     let sc_dispatcher = ScDispatcher::new(ctx.clone());
     sc_dispatcher.run();
 
+    start_consumer_offset_expiry(ctx.clone());
+
     ctx
 }
 
@@ -98,7 +147,8 @@ mod proxy {
 
     use flv_util::print_cli_err;
     use fluvio_future::rust_tls::TlsAcceptor;
-    use flv_tls_proxy::start as proxy_start;
+    use flv_tls_proxy::start_with_authenticator as proxy_start_with_authenticator;
+    use fluvio_auth::x509::X509Authenticator;
 
     use crate::config::SpuConfig;
 
@@ -107,8 +157,15 @@ mod proxy {
         let target = config.public_endpoint;
         info!("starting TLS proxy: {}", proxy_addr);
 
-        //TODO: add X509Authenticator
-        if let Err(err) = proxy_start(&proxy_addr, tls_acceptor, target).await {
+        // no scope bindings in SPU, authenticator only passes principal to public server
+        if let Err(err) = proxy_start_with_authenticator(
+            &proxy_addr,
+            tls_acceptor,
+            target,
+            Box::new(X509Authenticator::without_scopes()),
+        )
+        .await
+        {
             print_cli_err!(err);
             process::exit(-1);
         } else {
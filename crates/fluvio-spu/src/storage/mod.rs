@@ -8,11 +8,13 @@ use anyhow::Result;
 
 use fluvio_protocol::record::BatchRecords;
 use fluvio_controlplane_metadata::partition::ReplicaKey;
+use fluvio_controlplane::replica::Replica;
 use fluvio_spu_schema::Isolation;
 use fluvio_protocol::Encoder;
 use fluvio_protocol::record::{Offset, RecordSet};
 use fluvio_protocol::link::ErrorCode;
 use fluvio_storage::{ReplicaStorage, StorageError, OffsetInfo, ReplicaSlice};
+use fluvio_storage::cache::CachedBatch;
 use fluvio_types::event::offsets::OffsetChangeListener;
 use fluvio_types::event::offsets::OffsetPublisher;
 
@@ -102,6 +104,17 @@ where
         (reader.get_log_start_offset(), reader.get_hw())
     }
 
+    /// first readable offset
+    pub async fn log_start_offset(&self) -> Offset {
+        self.read().await.get_log_start_offset()
+    }
+
+    /// delete committed records before offset, return new log start offset
+    pub async fn delete_records_before(&self, offset: Offset) -> Result<Offset> {
+        let mut writer = self.write().await;
+        writer.delete_records_before(offset).await
+    }
+
     /// read records into partition response
     /// return leo and hw
     #[instrument(skip(self, offset, max_len, isolation))]
@@ -118,6 +131,18 @@ where
             .await
     }
 
+    /// batches from offset up to end offset if they are all kept in memory
+    pub async fn read_cached_batches(
+        &self,
+        offset: Offset,
+        end_offset: Offset,
+        max_len: u32,
+    ) -> Option<Vec<CachedBatch>> {
+        self.read()
+            .await
+            .read_cached_batches(offset, end_offset, max_len)
+    }
+
     pub async fn update_hw(&self, hw: Offset) -> Result<bool, StorageError> {
         let mut writer = self.write().await;
         if writer.update_high_watermark(hw).await? {
@@ -163,6 +188,18 @@ where
         Ok((base_offset, leo, bytes_written))
     }
 
+    /// apply changed topic settings to storage
+    pub async fn update_config(&self, replica: &Replica) {
+        self.read().await.update_config(replica);
+    }
+
+    /// apply changed SPU flush settings to storage
+    pub async fn update_flush_policy(&self, flush_write_count: u32, flush_idle_msec: u32) {
+        self.read()
+            .await
+            .update_flush_policy(flush_write_count, flush_idle_msec);
+    }
+
     /// perform permanent remove
     pub async fn remove(&self) -> Result<(), StorageError> {
         self.leo.update(REMOVAL_START);
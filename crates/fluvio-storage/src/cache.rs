@@ -0,0 +1,457 @@
+//!
+//! # Read Cache
+//!
+//! Batches written to replicas are kept in memory, so consumers reading at the tail of
+//! partitions are served without reading segments. Cache is shared by replicas of SPU and
+//! bounded by memory budget, batches written first are evicted first.
+//!
+//! Cached batches of replica are always contiguous and end at its log end offset,
+//! so reads can tell whether all batches they need are in memory.
+//!
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt;
+use std::io::{Cursor, Error as IoError};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use bytes::Bytes;
+use serde::{Serialize, Serializer, ser::SerializeStruct};
+use tracing::{debug, warn};
+
+use fluvio_protocol::Encoder;
+use fluvio_protocol::record::{Batch, BatchRecords, Offset, BATCH_FILE_HEADER_SIZE};
+
+pub type SharedBatchCache = Arc<BatchCache>;
+
+/// Batch kept in memory, records are stored as written
+#[derive(Debug, Clone)]
+pub struct CachedBatch {
+    /// batch header, records are not decoded
+    pub batch: Batch,
+    pub records: Bytes,
+}
+
+impl CachedBatch {
+    /// encode batch as it is stored in segment
+    pub fn encode_from<R: BatchRecords>(batch: &Batch<R>) -> Result<Self, IoError> {
+        let mut bytes = Vec::with_capacity(batch.write_size(0));
+        batch.encode(&mut bytes, 0)?;
+
+        let mut header = Batch::default();
+        header.decode_from_file_buf(&mut Cursor::new(&bytes[..BATCH_FILE_HEADER_SIZE]), 0)?;
+        Ok(Self {
+            batch: header,
+            records: Bytes::from(bytes).slice(BATCH_FILE_HEADER_SIZE..),
+        })
+    }
+
+    fn last_offset(&self) -> Offset {
+        self.batch.get_last_offset()
+    }
+
+    /// bytes of batch in memory
+    fn size(&self) -> u64 {
+        (BATCH_FILE_HEADER_SIZE + self.records.len()) as u64
+    }
+}
+
+/// Cache of batches written to replicas of SPU, 0 max bytes disables it
+#[derive(Default)]
+pub struct BatchCache {
+    max_bytes: u64,
+    next_replica: AtomicU64,
+    state: Mutex<CacheState>,
+    metrics: CacheMetrics,
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    /// cached batches of replicas by base offset
+    replicas: HashMap<u64, BTreeMap<Offset, CachedBatch>>,
+    /// batches in order they were cached
+    order: VecDeque<(u64, Offset)>,
+    used_bytes: u64,
+}
+
+#[derive(Debug, Default)]
+struct CacheMetrics {
+    /// reads served from memory
+    hits: AtomicU64,
+    /// reads with batches not in memory
+    misses: AtomicU64,
+    /// reads which skipped cache
+    bypassed: AtomicU64,
+    evictions: AtomicU64,
+    evicted_bytes: AtomicU64,
+}
+
+impl BatchCache {
+    pub fn new(max_bytes: u64) -> Self {
+        Self {
+            max_bytes,
+            ..Default::default()
+        }
+    }
+
+    pub fn shared(max_bytes: u64) -> SharedBatchCache {
+        Arc::new(Self::new(max_bytes))
+    }
+
+    pub fn max_bytes(&self) -> u64 {
+        self.max_bytes
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.max_bytes > 0
+    }
+
+    pub fn used_bytes(&self) -> u64 {
+        self.state().used_bytes
+    }
+
+    /// count read which skipped cache, such as large scan
+    pub fn record_bypass(&self) {
+        self.metrics.bypassed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn state(&self) -> MutexGuard<'_, CacheState> {
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn insert(&self, replica: u64, batch: CachedBatch) {
+        let size = batch.size();
+        let base_offset = batch.batch.get_base_offset();
+
+        let mut state = self.state();
+        let CacheState {
+            replicas,
+            order,
+            used_bytes,
+        } = &mut *state;
+
+        // batch doesn't follow cached ones, such as after truncation, start over
+        let batches = replicas.entry(replica).or_default();
+        if batches
+            .last_key_value()
+            .is_some_and(|(_, last)| last.last_offset() + 1 != base_offset)
+        {
+            debug!(replica, base_offset, "batch not contiguous, clearing cache");
+            *used_bytes -= batches.values().map(CachedBatch::size).sum::<u64>();
+            batches.clear();
+            order.retain(|(id, _)| *id != replica);
+        }
+
+        // batch doesn't fit, cached batches would no longer end at log end offset
+        if size > self.max_bytes {
+            *used_bytes -= batches.values().map(CachedBatch::size).sum::<u64>();
+            replicas.remove(&replica);
+            order.retain(|(id, _)| *id != replica);
+            return;
+        }
+
+        while *used_bytes + size > self.max_bytes {
+            let Some((id, offset)) = order.pop_front() else {
+                break;
+            };
+            let Some(batches) = replicas.get_mut(&id) else {
+                continue;
+            };
+            // only first batch of replica is evicted, so its batches stay contiguous
+            if batches
+                .first_key_value()
+                .is_none_or(|(first, _)| *first != offset)
+            {
+                continue;
+            }
+            if let Some(evicted) = batches.remove(&offset) {
+                *used_bytes -= evicted.size();
+                self.metrics.evictions.fetch_add(1, Ordering::Relaxed);
+                self.metrics
+                    .evicted_bytes
+                    .fetch_add(evicted.size(), Ordering::Relaxed);
+            }
+            if batches.is_empty() && id != replica {
+                replicas.remove(&id);
+            }
+        }
+
+        replicas
+            .entry(replica)
+            .or_default()
+            .insert(base_offset, batch);
+        order.push_back((replica, base_offset));
+        *used_bytes += size;
+    }
+
+    /// batches from offset up to end offset (exclusive), until max bytes are read.
+    /// None if any of them is not cached
+    fn read(
+        &self,
+        replica: u64,
+        offset: Offset,
+        end_offset: Offset,
+        max_bytes: u32,
+    ) -> Option<Vec<CachedBatch>> {
+        if offset >= end_offset {
+            return None;
+        }
+
+        let batches = self.read_inner(replica, offset, end_offset, max_bytes);
+        let counter = if batches.is_some() {
+            &self.metrics.hits
+        } else {
+            &self.metrics.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        batches
+    }
+
+    fn read_inner(
+        &self,
+        replica: u64,
+        offset: Offset,
+        end_offset: Offset,
+        max_bytes: u32,
+    ) -> Option<Vec<CachedBatch>> {
+        let state = self.state();
+        let cached = state.replicas.get(&replica)?;
+        let (first_offset, first) = cached.range(..=offset).next_back()?;
+        if first.last_offset() < offset {
+            return None;
+        }
+
+        let mut batches = vec![];
+        let mut total_bytes = 0;
+        let mut read_end = offset;
+        for (_, batch) in cached.range(first_offset..) {
+            if batch.batch.get_base_offset() >= end_offset || total_bytes >= max_bytes as u64 {
+                break;
+            }
+            total_bytes += batch.size();
+            read_end = batch.last_offset() + 1;
+            batches.push(batch.clone());
+        }
+
+        // batches read up to max bytes, or up to end offset which must be cached
+        if total_bytes >= max_bytes as u64 || read_end >= end_offset {
+            Some(batches)
+        } else {
+            None
+        }
+    }
+
+    /// remove batches with all records before offset
+    fn remove_before(&self, replica: u64, offset: Offset) {
+        let mut state = self.state();
+        let CacheState {
+            replicas,
+            order,
+            used_bytes,
+        } = &mut *state;
+        let Some(batches) = replicas.get_mut(&replica) else {
+            return;
+        };
+        while let Some(entry) = batches.first_entry() {
+            if entry.get().last_offset() >= offset {
+                break;
+            }
+            *used_bytes -= entry.remove().size();
+        }
+        order.retain(|(id, base_offset)| *id != replica || batches.contains_key(base_offset));
+    }
+
+    fn clear(&self, replica: u64) {
+        let mut state = self.state();
+        let CacheState {
+            replicas,
+            order,
+            used_bytes,
+        } = &mut *state;
+        if let Some(batches) = replicas.remove(&replica) {
+            *used_bytes -= batches.values().map(CachedBatch::size).sum::<u64>();
+            order.retain(|(id, _)| *id != replica);
+        }
+    }
+}
+
+/// caches are same if they are shared
+impl PartialEq for BatchCache {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Eq for BatchCache {}
+
+impl fmt::Debug for BatchCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BatchCache")
+            .field("max_bytes", &self.max_bytes)
+            .field("used_bytes", &self.used_bytes())
+            .finish()
+    }
+}
+
+impl Serialize for BatchCache {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (used_bytes, batches) = {
+            let state = self.state();
+            (state.used_bytes, state.order.len())
+        };
+        let metrics = &self.metrics;
+        let mut state = serializer.serialize_struct("BatchCache", 8)?;
+        state.serialize_field("max_bytes", &self.max_bytes)?;
+        state.serialize_field("used_bytes", &used_bytes)?;
+        state.serialize_field("batches", &batches)?;
+        state.serialize_field("hits", &metrics.hits)?;
+        state.serialize_field("misses", &metrics.misses)?;
+        state.serialize_field("bypassed", &metrics.bypassed)?;
+        state.serialize_field("evictions", &metrics.evictions)?;
+        state.serialize_field("evicted_bytes", &metrics.evicted_bytes)?;
+        state.end()
+    }
+}
+
+/// Batches of single replica in shared cache, removed when replica is dropped
+pub(crate) struct ReplicaCache {
+    id: u64,
+    cache: SharedBatchCache,
+}
+
+impl ReplicaCache {
+    pub(crate) fn new(cache: SharedBatchCache) -> Self {
+        let id = cache.next_replica.fetch_add(1, Ordering::Relaxed);
+        Self { id, cache }
+    }
+
+    /// cache batch written to replica
+    pub(crate) fn insert<R: BatchRecords>(&self, batch: &Batch<R>) {
+        match CachedBatch::encode_from(batch) {
+            Ok(cached) => self.cache.insert(self.id, cached),
+            Err(err) => {
+                warn!(%err, "unable to cache batch");
+                self.cache.clear(self.id);
+            }
+        }
+    }
+
+    pub(crate) fn read(
+        &self,
+        offset: Offset,
+        end_offset: Offset,
+        max_bytes: u32,
+    ) -> Option<Vec<CachedBatch>> {
+        self.cache.read(self.id, offset, end_offset, max_bytes)
+    }
+
+    pub(crate) fn remove_before(&self, offset: Offset) {
+        self.cache.remove_before(self.id, offset);
+    }
+}
+
+impl Drop for ReplicaCache {
+    fn drop(&mut self) {
+        self.cache.clear(self.id);
+    }
+}
+
+impl fmt::Debug for ReplicaCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReplicaCache")
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use fluvio_protocol::record::Record;
+
+    use super::*;
+
+    fn batch(base_offset: Offset, records: usize) -> Batch {
+        let mut batch = Batch::from(
+            (0..records)
+                .map(|_| Record::new("0123456789"))
+                .collect::<Vec<_>>(),
+        );
+        batch.set_base_offset(base_offset);
+        batch
+    }
+
+    #[test]
+    fn test_cached_batch_matches_stored() {
+        let batch = batch(10, 3);
+        let cached = CachedBatch::encode_from(&batch).expect("encode");
+        assert_eq!(cached.batch.get_base_offset(), 10);
+        assert_eq!(cached.last_offset(), 12);
+        assert_eq!(cached.size() as usize, batch.write_size(0));
+
+        let stored = batch.as_bytes(0).expect("bytes");
+        assert_eq!(&stored[BATCH_FILE_HEADER_SIZE..], &cached.records[..]);
+    }
+
+    #[test]
+    fn test_read_cache() {
+        let size = CachedBatch::encode_from(&batch(0, 2))
+            .expect("encode")
+            .size();
+        let cache = BatchCache::shared(size * 3);
+        let replica = ReplicaCache::new(cache.clone());
+        for base_offset in [0, 2, 4] {
+            replica.insert(&batch(base_offset, 2));
+        }
+        assert_eq!(cache.used_bytes(), size * 3);
+
+        let batches = replica.read(3, 6, u32::MAX).expect("cached");
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0].batch.get_base_offset(), 2);
+        let batches = replica.read(0, 6, size as u32).expect("cached");
+        assert_eq!(batches.len(), 1);
+        assert!(replica.read(0, 8, u32::MAX).is_none());
+        assert!(replica.read(6, 6, u32::MAX).is_none());
+
+        // oldest batch is evicted
+        replica.insert(&batch(6, 2));
+        assert!(replica.read(0, 8, u32::MAX).is_none());
+        assert!(replica.read(2, 8, u32::MAX).is_some());
+        assert_eq!(cache.metrics.evictions.load(Ordering::Relaxed), 1);
+
+        // batch not following cached ones replaces them
+        replica.insert(&batch(4, 2));
+        assert!(replica.read(2, 6, u32::MAX).is_none());
+        assert!(replica.read(4, 6, u32::MAX).is_some());
+        assert_eq!(cache.used_bytes(), size);
+
+        replica.remove_before(6);
+        assert_eq!(cache.used_bytes(), 0);
+
+        assert_eq!(cache.metrics.misses.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn test_cache_shared_by_replicas() {
+        let size = CachedBatch::encode_from(&batch(0, 1))
+            .expect("encode")
+            .size();
+        let cache = BatchCache::shared(size * 2);
+        let first = ReplicaCache::new(cache.clone());
+        let second = ReplicaCache::new(cache.clone());
+
+        first.insert(&batch(0, 1));
+        second.insert(&batch(0, 1));
+        second.insert(&batch(1, 1));
+        assert!(first.read(0, 1, u32::MAX).is_none());
+        assert!(second.read(0, 2, u32::MAX).is_some());
+
+        drop(second);
+        assert_eq!(cache.used_bytes(), 0);
+
+        // batches larger than budget are not cached
+        let small = BatchCache::shared(size - 1);
+        let replica = ReplicaCache::new(small.clone());
+        replica.insert(&batch(0, 1));
+        assert_eq!(small.used_bytes(), 0);
+    }
+}
@@ -3,25 +3,30 @@ use std::time::Duration;
 use std::ops::Div;
 use std::ops::Rem;
 
-use tracing::{debug, info, instrument};
+use tracing::{debug, info, instrument, warn};
 
 use fluvio_future::task::spawn;
 use fluvio_future::timer::sleep;
+use fluvio_protocol::record::Offset;
 use fluvio_types::event::StickyEvent;
 
 use crate::config::{SharedReplicaConfig, StorageConfig};
 use crate::replica::ReplicaSize;
 use crate::segments::SharedSegments;
+use crate::tiered::TieredStorage;
 
 /// Replica cleaner. This is a background task that periodically checks for expired segments and
 /// removes them. It also enforces max partition size by removing first segments if replica size is
 /// exceeded. In the future, this may be done by a central cleaner pool instead of per a replica.
+/// If topic has local retention and tiered storage is configured, segments are offloaded to tiered
+/// storage before they are removed from local disk.
 #[derive(Debug)]
 pub(crate) struct Cleaner {
     config: Arc<StorageConfig>,
     replica_config: Arc<SharedReplicaConfig>,
     segments: Arc<SharedSegments>,
     replica_size: Arc<ReplicaSize>,
+    tiered: Option<Arc<TieredStorage>>,
     end_event: Arc<StickyEvent>,
 }
 
@@ -31,6 +36,7 @@ impl Cleaner {
         replica_config: Arc<SharedReplicaConfig>,
         segments: Arc<SharedSegments>,
         replica_size: Arc<ReplicaSize>,
+        tiered: Option<Arc<TieredStorage>>,
     ) -> Arc<Self> {
         let end_event = StickyEvent::shared();
         let cleaner = Arc::new(Cleaner {
@@ -38,6 +44,7 @@ impl Cleaner {
             replica_config,
             segments,
             replica_size,
+            tiered,
             end_event,
         });
 
@@ -74,6 +81,7 @@ impl Cleaner {
                 _ = sleep(sleep_period) => {
                     self.enforce_size().await;
                     self.enforce_ttl().await;
+                    self.enforce_local_retention().await;
                 }
             }
         }
@@ -103,6 +111,10 @@ impl Cleaner {
                 .read()
                 .await
                 .find_first(count_to_remove as usize);
+            let segments_to_remove = match self.offload_target() {
+                Some(tiered) => self.offload(tiered, segments_to_remove).await,
+                None => segments_to_remove,
+            };
             self.segments.remove_segments(&segments_to_remove).await;
 
             let read = self.segments.read().await;
@@ -129,6 +141,63 @@ impl Cleaner {
             let read = self.segments.read().await;
             self.replica_size.store_prev(read.occupied_memory());
         }
+        if let Some(tiered) = &self.tiered {
+            tiered.remove_expired(&retention_secs).await;
+        }
+    }
+
+    /// offload segments older than local retention to tiered storage
+    #[instrument(skip(self))]
+    async fn enforce_local_retention(&self) {
+        let Some(tiered) = self.offload_target() else {
+            return;
+        };
+        let local_retention =
+            Duration::from_secs(self.replica_config.local_retention_seconds.get() as u64);
+        let expired_segments = self
+            .segments
+            .read()
+            .await
+            .find_expired_segments(&local_retention);
+        debug!(
+            seconds = local_retention.as_secs(),
+            expired = expired_segments.len(),
+            "segments exceeding local retention"
+        );
+        if !expired_segments.is_empty() {
+            let offloaded = self.offload(tiered, expired_segments).await;
+            self.segments.remove_segments(&offloaded).await;
+            let read = self.segments.read().await;
+            self.replica_size.store_prev(read.occupied_memory());
+        }
+    }
+
+    /// tiered storage is used only by topics with local retention
+    fn offload_target(&self) -> Option<&TieredStorage> {
+        self.tiered
+            .as_deref()
+            .filter(|_| self.replica_config.local_retention_seconds.get() > 0)
+    }
+
+    /// upload segments in order, return those which can be removed from local disk.
+    /// Stops at first failure so offloaded segments stay contiguous.
+    async fn offload(&self, tiered: &TieredStorage, base_offsets: Vec<Offset>) -> Vec<Offset> {
+        let mut offloaded = vec![];
+        for base_offset in base_offsets {
+            let Some(end_offset) = self.segments.read().await.end_offset(base_offset) else {
+                continue;
+            };
+            if let Err(err) = tiered.upload_segment(base_offset, end_offset).await {
+                warn!(
+                    base_offset,
+                    %err,
+                    "failed to offload segment, keeping it on local disk"
+                );
+                break;
+            }
+            offloaded.push(base_offset);
+        }
+        offloaded
     }
 }
 
@@ -286,6 +355,7 @@ mod tests {
             replica_config: replica_config.shared(),
             segments,
             replica_size,
+            tiered: None,
             end_event: StickyEvent::shared(),
         }
     }
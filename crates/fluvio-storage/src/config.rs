@@ -19,6 +19,7 @@ use fluvio_types::defaults::SPU_LOG_SEGMENT_MAX_BYTES;
 use fluvio_protocol::record::{Size, Size64};
 
 use crate::ReplicaStorageConfig;
+use crate::cache::SharedBatchCache;
 
 // Replica specific config
 #[derive(Builder, Clone, Debug, Eq, PartialEq, Deserialize)]
@@ -57,6 +58,18 @@ pub struct ReplicaConfig {
     #[builder(default = "default_max_partition_size()")]
     #[serde(default = "default_max_partition_size")]
     pub max_partition_size: Size64,
+    /// seconds segments are kept on local disk before offloading to tiered storage, 0 disables offloading
+    #[builder(default)]
+    #[serde(default)]
+    pub local_retention_seconds: Size,
+    /// url of object store for tiered storage, such as `s3://bucket/prefix`
+    #[builder(default)]
+    #[serde(default)]
+    pub tiered_storage: Option<String>,
+    /// cache of recently written batches, shared by replicas of SPU
+    #[builder(default)]
+    #[serde(skip)]
+    pub read_cache: Option<SharedBatchCache>,
 }
 
 impl fmt::Display for ReplicaConfig {
@@ -89,6 +102,20 @@ impl ReplicaStorageConfig for ReplicaConfig {
         {
             self.max_partition_size = max_partition_size;
         }
+        if let Some(local_retention_seconds) = replica
+            .storage
+            .as_ref()
+            .and_then(|storage| storage.local_retention_seconds)
+        {
+            self.local_retention_seconds = local_retention_seconds;
+        }
+        if let Some(max_batch_size) = replica
+            .storage
+            .as_ref()
+            .and_then(|storage| storage.max_batch_size)
+        {
+            self.max_request_size = max_batch_size;
+        }
     }
 }
 
@@ -160,6 +187,9 @@ impl Default for ReplicaConfig {
             max_request_size: default_max_request_size(),
             retention_seconds: default_retention_seconds(),
             max_partition_size: default_max_partition_size(),
+            local_retention_seconds: 0,
+            tiered_storage: None,
+            read_cache: None,
             update_hw: true,
         }
     }
@@ -238,6 +268,8 @@ pub struct SharedReplicaConfig {
     pub update_hw: bool, // if true, enable hw update
     pub retention_seconds: SharedConfigU32Value,
     pub max_partition_size: SharedConfigU64Value,
+    pub local_retention_seconds: SharedConfigU32Value,
+    pub read_cache: Option<SharedBatchCache>,
 }
 
 impl From<ReplicaConfig> for SharedReplicaConfig {
@@ -254,6 +286,29 @@ impl From<ReplicaConfig> for SharedReplicaConfig {
             update_hw: config.update_hw,
             retention_seconds: SharedConfigU32Value::new(config.retention_seconds),
             max_partition_size: SharedConfigU64Value::new(config.max_partition_size),
+            local_retention_seconds: SharedConfigU32Value::new(config.local_retention_seconds),
+            read_cache: config.read_cache,
+        }
+    }
+}
+
+impl SharedReplicaConfig {
+    /// update values which can be changed on running replica.
+    /// Segment size is kept, since it applies to existing segments
+    pub fn update_from_replica(&self, replica: &Replica) {
+        if let Some(CleanupPolicy::Segment(segment)) = &replica.cleanup_policy {
+            self.retention_seconds.set(segment.retention_secs());
+        }
+        if let Some(storage) = &replica.storage {
+            if let Some(max_partition_size) = storage.max_partition_size {
+                self.max_partition_size.set(max_partition_size);
+            }
+            if let Some(local_retention_seconds) = storage.local_retention_seconds {
+                self.local_retention_seconds.set(local_retention_seconds);
+            }
+            if let Some(max_batch_size) = storage.max_batch_size {
+                self.max_request_size.set(max_batch_size);
+            }
         }
     }
 }
@@ -281,4 +336,30 @@ mod tests {
 
         assert_eq!(ReplicaConfig::default(), config);
     }
+
+    #[test]
+    fn test_update_shared_config() {
+        use fluvio_controlplane_metadata::topic::{SegmentBasedPolicy, TopicStorageConfig};
+
+        let shared: SharedReplicaConfig = ReplicaConfig::default().into();
+        let segment_max_bytes = shared.segment_max_bytes.get();
+
+        let mut replica = Replica::new(("topic", 0), 5001, vec![5001]);
+        replica.cleanup_policy = Some(CleanupPolicy::Segment(SegmentBasedPolicy {
+            time_in_seconds: 600,
+        }));
+        replica.storage = Some(TopicStorageConfig {
+            segment_size: Some(1000),
+            max_partition_size: Some(10_000),
+            max_batch_size: Some(500),
+            ..Default::default()
+        });
+        shared.update_from_replica(&replica);
+
+        assert_eq!(shared.retention_seconds.get(), 600);
+        assert_eq!(shared.max_partition_size.get(), 10_000);
+        assert_eq!(shared.max_request_size.get(), 500);
+        // segment size applies to existing segments, so it is kept
+        assert_eq!(shared.segment_max_bytes.get(), segment_max_bytes);
+    }
 }
@@ -0,0 +1,325 @@
+//!
+//! # Index rebuild
+//!
+//! Regenerate offset index of segments from their log files, after corruption or upgrade.
+//! Replica must not be open by SPU while rebuilding, its directory lock is checked before
+//! indexes are rewritten.
+//! Segments have no time index, lookups by timestamp scan batch headers of the log,
+//! so only offset indexes are rebuilt.
+//!
+
+use std::ffi::OsStr;
+use std::fmt;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use anyhow::{Result, bail};
+use tracing::{debug, info, warn};
+
+use fluvio_protocol::record::{Offset, Size, NO_TIMESTAMP};
+
+use crate::batch_header::BatchHeaderStream;
+use crate::index::{EXTENSION as INDEX_EXTENSION, Entry, INDEX_ENTRY_SIZE};
+use crate::lock::ReplicaDirLock;
+use crate::util::{generate_file_name, log_path_get_offset};
+
+/// Result of rebuilding index of a segment
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SegmentIndexReport {
+    pub base_offset: Offset,
+    pub batches: u32,
+    /// offset after last readable batch
+    pub end_offset: Offset,
+    pub old_entries: usize,
+    pub new_entries: usize,
+    /// entries of existing index which differ from rebuilt ones, including missing and extra
+    pub mismatched_entries: usize,
+    /// batches not starting at end offset of previous batch
+    pub offset_gaps: u32,
+    /// batches whose max timestamp is less than of previous batch
+    pub timestamp_regressions: u32,
+    /// position of data which couldn't be decoded, it is left untouched
+    pub unreadable_from: Option<Size>,
+    pub rewritten: bool,
+}
+
+impl SegmentIndexReport {
+    pub fn has_discrepancies(&self) -> bool {
+        self.mismatched_entries > 0
+            || self.offset_gaps > 0
+            || self.timestamp_regressions > 0
+            || self.unreadable_from.is_some()
+    }
+}
+
+impl fmt::Display for SegmentIndexReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "segment {:020}: {} batches, offsets {}..{}, index entries {} -> {}",
+            self.base_offset,
+            self.batches,
+            self.base_offset,
+            self.end_offset,
+            self.old_entries,
+            self.new_entries
+        )?;
+        if self.mismatched_entries > 0 {
+            write!(f, ", {} mismatched entries", self.mismatched_entries)?;
+        }
+        if self.offset_gaps > 0 {
+            write!(f, ", {} offset gaps", self.offset_gaps)?;
+        }
+        if self.timestamp_regressions > 0 {
+            write!(f, ", {} timestamp regressions", self.timestamp_regressions)?;
+        }
+        if let Some(pos) = self.unreadable_from {
+            write!(f, ", unreadable data from position {pos}")?;
+        }
+        if self.rewritten {
+            write!(f, ", index rewritten")?;
+        }
+        Ok(())
+    }
+}
+
+/// Rebuild indexes of all segments in replica directory, in order of base offset.
+/// With `dry_run`, only report discrepancies without writing indexes.
+/// Fails if replica is open by a running SPU, unless `dry_run` is set.
+pub async fn rebuild_indexes(
+    replica_dir: impl AsRef<Path>,
+    index_max_interval_bytes: Size,
+    dry_run: bool,
+) -> Result<Vec<SegmentIndexReport>> {
+    let replica_dir = replica_dir.as_ref();
+    // held until all indexes are rewritten, so SPU can't open replica meanwhile
+    let _lock = match ReplicaDirLock::try_lock(replica_dir)? {
+        Some(lock) => Some(lock),
+        None if dry_run => {
+            warn!("replica is open by SPU, indexes may change while checked");
+            None
+        }
+        None => bail!(
+            "replica {} is open by a running SPU, stop it before rebuilding indexes",
+            replica_dir.display()
+        ),
+    };
+    let mut base_offsets = vec![];
+    for entry in replica_dir.read_dir()? {
+        let path = entry?.path();
+        if path.extension() == Some(OsStr::new("log"))
+            && let Ok(offset) = log_path_get_offset(&path)
+        {
+            base_offsets.push(offset);
+        }
+    }
+    base_offsets.sort_unstable();
+
+    let mut reports = Vec::with_capacity(base_offsets.len());
+    for base_offset in base_offsets {
+        let report =
+            rebuild_segment_index(replica_dir, base_offset, index_max_interval_bytes, dry_run)
+                .await?;
+        info!(%report, "segment index");
+        reports.push(report);
+    }
+    Ok(reports)
+}
+
+async fn rebuild_segment_index(
+    replica_dir: &Path,
+    base_offset: Offset,
+    index_max_interval_bytes: Size,
+    dry_run: bool,
+) -> Result<SegmentIndexReport> {
+    let log_path = generate_file_name(replica_dir, base_offset, "log");
+    let index_path = generate_file_name(replica_dir, base_offset, INDEX_EXTENSION);
+    debug!(?log_path, "rebuilding index");
+
+    let mut report = SegmentIndexReport {
+        base_offset,
+        end_offset: base_offset,
+        ..Default::default()
+    };
+
+    let mut entries: Vec<Entry> = vec![];
+    // same interval as active segment, so unchanged index is rebuilt identically
+    let mut accumulated_batch_len: Size = 0;
+    let mut last_timestamp = NO_TIMESTAMP;
+
+    match BatchHeaderStream::open(&log_path).await {
+        Ok(mut stream) => loop {
+            let batch_pos = match stream.try_next().await {
+                Ok(Some(batch_pos)) => batch_pos,
+                Ok(None) => break,
+                Err(err) => {
+                    warn!(%err, pos = stream.get_pos(), "unreadable batch");
+                    report.unreadable_from = Some(stream.get_pos());
+                    break;
+                }
+            };
+            let pos = batch_pos.get_pos();
+            let batch = batch_pos.inner();
+            let batch_size = stream.get_pos() - pos;
+            let header = batch.get_header();
+
+            if batch.get_base_offset() != report.end_offset {
+                report.offset_gaps += 1;
+            }
+            if batch.get_base_offset() < base_offset {
+                // can't be indexed relative to segment
+                report.unreadable_from = Some(pos);
+                break;
+            }
+            if header.max_time_stamp != NO_TIMESTAMP {
+                if last_timestamp != NO_TIMESTAMP && header.max_time_stamp < last_timestamp {
+                    report.timestamp_regressions += 1;
+                }
+                last_timestamp = header.max_time_stamp;
+            }
+
+            if accumulated_batch_len < index_max_interval_bytes {
+                accumulated_batch_len += batch_size;
+            } else {
+                entries.push(((batch.get_base_offset() - base_offset) as Size, pos));
+                accumulated_batch_len = 0;
+            }
+
+            report.batches += 1;
+            report.end_offset = batch.get_last_offset() + 1;
+        },
+        // empty log
+        Err(err) if err.kind() == ErrorKind::UnexpectedEof => {}
+        Err(err) => return Err(err.into()),
+    }
+
+    let old_bytes = match std::fs::read(&index_path) {
+        Ok(bytes) => bytes,
+        Err(err) if err.kind() == ErrorKind::NotFound => vec![],
+        Err(err) => return Err(err.into()),
+    };
+    let old_entries = decode_entries(&old_bytes);
+    report.old_entries = old_entries.len();
+    report.new_entries = entries.len();
+    report.mismatched_entries = old_entries
+        .iter()
+        .zip(entries.iter())
+        .filter(|(old, new)| old != new)
+        .count()
+        + old_entries.len().abs_diff(entries.len());
+
+    if !dry_run && (report.mismatched_entries > 0 || old_bytes.is_empty()) {
+        // active segment index is preallocated, keep its size
+        let mut bytes = encode_entries(&entries);
+        if bytes.len() < old_bytes.len() {
+            bytes.resize(old_bytes.len(), 0);
+        }
+        let tmp_path = index_path.with_extension("index.rebuild");
+        std::fs::write(&tmp_path, &bytes)?;
+        std::fs::rename(&tmp_path, &index_path)?;
+        report.rewritten = true;
+    }
+
+    Ok(report)
+}
+
+/// entries until first empty slot, index stores them big endian
+fn decode_entries(bytes: &[u8]) -> Vec<Entry> {
+    bytes
+        .chunks_exact(INDEX_ENTRY_SIZE as usize)
+        .map(|chunk| {
+            let offset = Size::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+            let position = Size::from_be_bytes([chunk[4], chunk[5], chunk[6], chunk[7]]);
+            (offset, position)
+        })
+        .take_while(|(_, position)| *position != 0)
+        .collect()
+}
+
+fn encode_entries(entries: &[Entry]) -> Vec<u8> {
+    entries
+        .iter()
+        .flat_map(|(offset, position)| {
+            offset
+                .to_be_bytes()
+                .into_iter()
+                .chain(position.to_be_bytes())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[cfg(feature = "fixture")]
+mod tests {
+
+    use std::env::temp_dir;
+
+    use flv_util::fixture::ensure_new_dir;
+    use fluvio_protocol::fixture::{create_batch_with_producer, TEST_RECORD};
+
+    use crate::config::ReplicaConfig;
+    use crate::segment::MutableSegment;
+
+    use super::*;
+
+    #[fluvio_future::test]
+    async fn test_rebuild_corrupted_index() {
+        let test_dir = temp_dir().join("index-rebuild");
+        ensure_new_dir(&test_dir).expect("dir");
+
+        let option = ReplicaConfig {
+            base_dir: test_dir.clone(),
+            index_max_interval_bytes: 10,
+            index_max_bytes: 1000,
+            ..Default::default()
+        }
+        .shared();
+
+        let mut segment = MutableSegment::create(0, option).await.expect("create");
+        for _ in 0..5 {
+            segment
+                .append_batch(&mut create_batch_with_producer(100, 2, TEST_RECORD))
+                .await
+                .expect("write");
+        }
+        segment.close().await.expect("close");
+
+        let index_path = test_dir.join("00000000000000000000.index");
+        let original = std::fs::read(&index_path).expect("read index");
+        assert!(!decode_entries(&original).is_empty());
+
+        // unchanged index is kept
+        let reports = rebuild_indexes(&test_dir, 10, false)
+            .await
+            .expect("rebuild");
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].batches, 5);
+        assert_eq!(reports[0].end_offset, 10);
+        assert!(!reports[0].has_discrepancies());
+        assert!(!reports[0].rewritten);
+
+        // corrupt second entry
+        let mut corrupted = original.clone();
+        corrupted[12..16].copy_from_slice(&7u32.to_be_bytes());
+        std::fs::write(&index_path, &corrupted).expect("corrupt");
+
+        let reports = rebuild_indexes(&test_dir, 10, true).await.expect("dry run");
+        assert_eq!(reports[0].mismatched_entries, 1);
+        assert!(!reports[0].rewritten);
+        assert_eq!(std::fs::read(&index_path).expect("read"), corrupted);
+
+        let reports = rebuild_indexes(&test_dir, 10, false)
+            .await
+            .expect("rebuild");
+        assert!(reports[0].rewritten);
+        assert_eq!(std::fs::read(&index_path).expect("read"), original);
+
+        // replica open by SPU is not rewritten
+        let lock = ReplicaDirLock::try_lock(&test_dir)
+            .expect("lock")
+            .expect("unlocked");
+        assert!(rebuild_indexes(&test_dir, 10, false).await.is_err());
+        assert!(rebuild_indexes(&test_dir, 10, true).await.is_ok());
+        drop(lock);
+    }
+}
@@ -11,6 +11,8 @@ use fluvio_protocol::{Decoder, Version};
 use fluvio_protocol::record::{Batch, Offset, BATCH_FILE_HEADER_SIZE, BATCH_HEADER_SIZE, Record};
 use fluvio_future::file_slice::AsyncFileSlice;
 
+use crate::cache::CachedBatch;
+
 // only encode information necessary to decode batches efficiently
 pub struct FileBatch {
     pub batch: Batch,
@@ -24,38 +26,101 @@ pub struct RecordItem {
     pub timestamp: Timestamp,
 }
 
-/// Iterator that returns batch from file
+/// Iterator that returns batch from file, or from batches of file kept in memory
 pub struct FileBatchIterator {
+    source: BatchSource,
+    /// records of compressed batches are decompressed
+    decompress: bool,
+}
+
+enum BatchSource {
+    File(FileSource),
+    Cached(std::vec::IntoIter<CachedBatch>),
+}
+
+struct FileSource {
     fd: RawFd,
     offset: Offset,
     end: i64,
 }
 
 impl FileBatchIterator {
-    #[allow(unused)]
     pub fn new(fd: RawFd, offset: Offset, len: i64) -> Self {
         Self {
-            fd,
-            offset,
-            end: offset + len,
+            source: BatchSource::File(FileSource {
+                fd,
+                offset,
+                end: offset + len,
+            }),
+            decompress: true,
         }
     }
 
     pub fn from_raw_slice(slice: AsyncFileSlice) -> Self {
         use std::os::unix::io::AsRawFd;
         let offset = slice.position() as i64;
+        Self::new(slice.as_raw_fd(), offset, slice.len() as i64)
+    }
+
+    /// iterate over batches read from cache instead of file
+    pub fn from_cached(batches: Vec<CachedBatch>) -> Self {
         Self {
-            fd: slice.as_raw_fd(),
-            offset,
-            end: offset + slice.len() as i64,
+            source: BatchSource::Cached(batches.into_iter()),
+            decompress: true,
         }
     }
+
+    /// return records as stored, compressed with codec of their batch
+    pub fn keep_compressed(mut self) -> Self {
+        self.decompress = false;
+        self
+    }
 }
 
 impl Iterator for FileBatchIterator {
     type Item = Result<FileBatch, IoError>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        let (batch, raw_records) = match &mut self.source {
+            BatchSource::File(file) => match file.read_batch()? {
+                Ok(read) => read,
+                Err(err) => return Some(Err(err)),
+            },
+            BatchSource::Cached(batches) => {
+                let cached = batches.next()?;
+                (cached.batch, cached.records.to_vec())
+            }
+        };
+
+        if !self.decompress {
+            return Some(Ok(FileBatch {
+                batch,
+                records: raw_records,
+            }));
+        }
+
+        let compression = match batch.get_compression() {
+            Ok(compression) => compression,
+            Err(err) => {
+                return Some(Err(IoError::other(format!(
+                    "unknown compression value for batch {err}"
+                ))));
+            }
+        };
+
+        let records = match compression.uncompress(&raw_records) {
+            Ok(Some(records)) => records,
+            Ok(None) => raw_records,
+            Err(err) => return Some(Err(IoError::other(format!("uncompress error {err}")))),
+        };
+
+        Some(Ok(FileBatch { batch, records }))
+    }
+}
+
+impl FileSource {
+    /// read next batch header and its raw records
+    fn read_batch(&mut self) -> Option<Result<(Batch, Vec<u8>), IoError>> {
         if self.offset >= self.end {
             return None;
         }
@@ -132,24 +197,8 @@ impl Iterator for FileBatchIterator {
             )));
         }
 
-        let compression = match batch.get_compression() {
-            Ok(compression) => compression,
-            Err(err) => {
-                return Some(Err(IoError::other(format!(
-                    "unknown compression value for batch {err}"
-                ))));
-            }
-        };
-
-        let records = match compression.uncompress(&raw_records) {
-            Ok(Some(records)) => records,
-            Ok(None) => raw_records,
-            Err(err) => return Some(Err(IoError::other(format!("uncompress error {err}")))),
-        };
-
         self.offset += bytes_read as i64;
-
-        Some(Ok(FileBatch { batch, records }))
+        Some(Ok((batch, raw_records)))
     }
 }
 
@@ -227,6 +276,7 @@ mod test {
     use fluvio_protocol::record::RecordSet;
     use crate::{FileReplica, ReplicaStorage};
     use crate::config::{StorageConfigBuilder, ReplicaConfigBuilder};
+    use crate::cache::BatchCache;
 
     use super::*;
 
@@ -326,4 +376,62 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_cached_batch_iterator() -> anyhow::Result<()> {
+        //given
+        let base_dir = temp_dir().join("test_cached_batch_iterator");
+        let mut replica = run_block_on(FileReplica::create_or_load_inner(
+            format!(
+                "test_cached_batch_iterator_{}",
+                SystemTime::now()
+                    .duration_since(SystemTime::UNIX_EPOCH)?
+                    .as_millis()
+            ),
+            Default::default(),
+            Default::default(),
+            ReplicaConfigBuilder::default()
+                .base_dir(base_dir)
+                .read_cache(Some(BatchCache::shared(1_000_000)))
+                .build(),
+            Arc::new(StorageConfigBuilder::default().build()?),
+        ))?;
+
+        let mut records = RecordSet::default();
+        for value in ["1", "2", "3"] {
+            let mut batch = Batch::default();
+            batch.add_record(Record::new(value));
+            records = records.add(batch);
+        }
+        run_block_on(replica.write_recordset(&mut records, false))?;
+
+        //when
+        let slice = run_block_on(replica.read_partition_slice(
+            1,
+            u32::MAX,
+            fluvio_spu_schema::Isolation::ReadUncommitted,
+        ))?;
+        let file_slice = slice
+            .file_slice
+            .ok_or_else(|| anyhow::anyhow!("expected file slice"))?;
+        let cached = replica
+            .read_cached_batches(1, 3, u32::MAX)
+            .ok_or_else(|| anyhow::anyhow!("expected cached batches"))?;
+
+        let from_file = FileBatchIterator::from_raw_slice(file_slice)
+            .collect::<Result<Vec<FileBatch>, std::io::Error>>()?;
+        let from_cache = FileBatchIterator::from_cached(cached)
+            .collect::<Result<Vec<FileBatch>, std::io::Error>>()?;
+
+        //then
+        assert_eq!(from_cache.len(), 2);
+        assert_eq!(from_file.len(), from_cache.len());
+        for (file, cached) in from_file.iter().zip(&from_cache) {
+            assert_eq!(file.batch.get_base_offset(), cached.batch.get_base_offset());
+            assert_eq!(file.batch.batch_len, cached.batch.batch_len);
+            assert_eq!(file.records, cached.records);
+        }
+
+        Ok(())
+    }
 }
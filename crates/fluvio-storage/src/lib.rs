@@ -1,5 +1,6 @@
 pub mod batch;
 pub mod batch_header;
+pub mod cache;
 pub mod checkpoint;
 mod error;
 pub mod records;
@@ -10,6 +11,7 @@ mod segments;
 mod replica;
 pub mod segment;
 mod util;
+mod lock;
 mod validator;
 mod file;
 pub mod config;
@@ -19,6 +21,8 @@ pub mod iterators;
 #[cfg(feature = "fixture")]
 pub mod fixture;
 mod cleaner;
+mod tiered;
+pub mod index_rebuild;
 
 pub use crate::error::StorageError;
 pub use crate::records::FileRecordsSlice;
@@ -35,11 +39,13 @@ mod inner {
     use fluvio_protocol::record::BatchRecords;
     use fluvio_protocol::link::ErrorCode;
     use fluvio_spu_schema::Isolation;
-    use fluvio_protocol::record::{Offset, ReplicaKey, Size64};
+    use fluvio_protocol::record::{Offset, ReplicaKey, Size, Size64};
     use fluvio_protocol::record::RecordSet;
     use fluvio_future::file_slice::AsyncFileSlice;
     use fluvio_controlplane::replica::Replica;
 
+    use crate::cache::CachedBatch;
+
     #[derive(Debug, Clone, Eq, PartialEq)]
     pub struct OffsetInfo {
         pub hw: Offset,
@@ -118,6 +124,8 @@ mod inner {
         pub start: Offset,   // start offset
         pub end: OffsetInfo, // end offset
         pub file_slice: Option<AsyncFileSlice>,
+        /// true if file slice contains all records up to end offset
+        pub complete: bool,
     }
 
     /// some storage configuration
@@ -154,6 +162,19 @@ mod inner {
             isolation: Isolation,
         ) -> Result<ReplicaSlice, ErrorCode>;
 
+        /// batches from offset up to end offset kept in memory, until max bytes are read.
+        /// None if any of them has to be read from segments
+        fn read_cached_batches(
+            &self,
+            offset: Offset,
+            end_offset: Offset,
+            max_len: u32,
+        ) -> Option<Vec<CachedBatch>>;
+
+        /// enable or disable caching of written batches for reads.
+        /// only leaders serve reads from the cache, so followers turn it off
+        fn set_read_cache_enabled(&mut self, _enabled: bool) {}
+
         fn get_partition_size(&self) -> Size64;
 
         /// write record set
@@ -165,8 +186,17 @@ mod inner {
 
         async fn update_high_watermark(&mut self, offset: Offset) -> Result<bool, StorageError>;
 
+        /// delete committed records before offset, return new log start offset
+        async fn delete_records_before(&mut self, offset: Offset) -> Result<Offset>;
+
         /// permanently remove
         async fn remove(&self) -> Result<(), StorageError>;
+
+        /// apply changed topic settings to running replica
+        fn update_config(&self, replica: &Replica);
+
+        /// apply changed SPU flush settings to running replica
+        fn update_flush_policy(&self, flush_write_count: Size, flush_idle_msec: Size);
     }
 
     #[cfg(test)]
@@ -0,0 +1,52 @@
+use std::fs::File;
+use std::io::{Error as IoError, Result as IoResult};
+use std::os::unix::io::AsRawFd;
+use std::path::Path;
+
+/// Advisory lock of replica directory, held while replica is open by SPU,
+/// so offline tools don't rewrite files of a running replica.
+/// Lock is released when dropped, or when process exits.
+#[derive(Debug)]
+pub(crate) struct ReplicaDirLock(File);
+
+impl ReplicaDirLock {
+    /// lock directory, `None` if it is already locked
+    pub(crate) fn try_lock(dir: &Path) -> IoResult<Option<Self>> {
+        let file = File::open(dir)?;
+        // SAFETY: file descriptor is valid while file is open
+        let res = unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) };
+        if res == 0 {
+            return Ok(Some(Self(file)));
+        }
+        let err = IoError::last_os_error();
+        if err.raw_os_error() == Some(libc::EWOULDBLOCK) {
+            Ok(None)
+        } else {
+            Err(err)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::env::temp_dir;
+
+    use flv_util::fixture::ensure_new_dir;
+
+    use super::*;
+
+    #[test]
+    fn test_replica_dir_lock() {
+        let dir = temp_dir().join("replica-dir-lock");
+        ensure_new_dir(&dir).expect("dir");
+
+        let lock = ReplicaDirLock::try_lock(&dir)
+            .expect("lock")
+            .expect("unlocked");
+        assert!(ReplicaDirLock::try_lock(&dir).expect("lock").is_none());
+
+        drop(lock);
+        assert!(ReplicaDirLock::try_lock(&dir).expect("lock").is_some());
+    }
+}
@@ -47,7 +47,7 @@ pub struct MutFileRecords {
     file: File,
     len: u32,
     max_len: u32,
-    _flush_policy: FlushPolicy,
+    option: Arc<SharedReplicaConfig>,
     write_count: u64,
     flush_count: Arc<AtomicU32>,
     path: PathBuf,
@@ -97,7 +97,7 @@ impl MutFileRecords {
             file,
             len,
             max_len,
-            _flush_policy: get_flush_policy_from_config(&option),
+            option,
             write_count: 0,
             flush_count: Arc::new(AtomicU32::new(0)),
             path: log_path.to_owned(),
@@ -167,16 +167,29 @@ impl MutFileRecords {
         debug!(pos = self.get_pos(), "update pos",);
         self.write_count = self.write_count.saturating_add(1);
 
-        self.flush().await?;
-        debug!(
-            flush_count = self.flush_count(),
-            write_count = self.write_count,
-            "Flushing Now"
-        );
+        if self.should_flush() {
+            self.flush().await?;
+            debug!(
+                flush_count = self.flush_count(),
+                write_count = self.write_count,
+                "Flushing Now"
+            );
+        }
 
         Ok((true, batch_len, self.len))
     }
 
+    /// policy is read from shared config on every write, so changes apply to open segment
+    fn should_flush(&self) -> bool {
+        match get_flush_policy_from_config(&self.option) {
+            FlushPolicy::NoFlush => false,
+            FlushPolicy::EveryWrite => true,
+            FlushPolicy::CountWrites { n_writes, .. } => self.write_count % n_writes as u64 == 0,
+            // delayed flush is not scheduled yet, flush on write
+            FlushPolicy::IdleFlush { .. } => true,
+        }
+    }
+
     pub async fn flush(&mut self) -> Result<(), IoError> {
         self.flush_count.fetch_add(1, Ordering::Relaxed);
         self.file.flush().await?;
@@ -386,8 +399,7 @@ mod tests {
     // This Test configures policy to flush after every NUM_WRITES
     // and checks to see when the flush occurs relative to the write count
 
-    //#[fluvio_future::test]
-    #[allow(unused)]
+    #[fluvio_future::test]
     async fn test_write_records_count() {
         let test_dir = temp_dir().join("mut_records_word_count");
         ensure_new_dir(&test_dir).expect("new");
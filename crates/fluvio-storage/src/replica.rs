@@ -15,8 +15,9 @@ use fluvio_spu_schema::Isolation;
 use fluvio_protocol::record::{Offset, ReplicaKey, Size, Size64};
 use fluvio_protocol::record::{Batch, BatchRecords};
 use fluvio_protocol::record::RecordSet;
+use fluvio_controlplane::replica::Replica;
 
-use crate::checkpoint::HW_CHECKPOINT_FILE_NAME;
+use crate::checkpoint::{HW_CHECKPOINT_FILE_NAME, LOG_START_CHECKPOINT_FILE_NAME};
 use crate::{OffsetInfo, checkpoint::CheckPoint};
 use crate::segments::SharedSegments;
 use crate::segment::MutableSegment;
@@ -24,6 +25,9 @@ use crate::config::{ReplicaConfig, SharedReplicaConfig, StorageConfig};
 use crate::ReplicaSlice;
 use crate::{StorageError, ReplicaStorage};
 use crate::cleaner::Cleaner;
+use crate::tiered::TieredStorage;
+use crate::cache::{CachedBatch, ReplicaCache};
+use crate::lock::ReplicaDirLock;
 
 /// Replica is public abstraction for commit log which are distributed.
 /// Internally it is stored as list of segments.  Each segment contains finite sets of record batches.
@@ -38,11 +42,18 @@ pub struct FileReplica {
     active_segment: MutableSegment,
     prev_segments: Arc<SharedSegments>,
     commit_checkpoint: CheckPoint,
+    /// records before this offset are deleted, even if their segment still exists
+    log_start_checkpoint: CheckPoint,
     cleaner: Arc<Cleaner>,
+    /// segments offloaded to object store, if tiered storage is configured
+    tiered: Option<Arc<TieredStorage>>,
+    /// recently written batches, if read cache is configured
+    cache: Option<ReplicaCache>,
     size: Arc<ReplicaSize>,
     short_circuit: bool, // if this is true, last append failed, should not append again
-    max_request_size: usize,
     max_segment_size: usize,
+    /// keeps offline tools from rewriting files while replica is open
+    _dir_lock: Option<ReplicaDirLock>,
 }
 
 #[derive(Debug, Default)]
@@ -88,12 +99,13 @@ impl ReplicaStorage for FileReplica {
 
     /// earliest offset
     fn get_log_start_offset(&self) -> Offset {
-        let min_base_offset = self.prev_segments.min_offset();
-        if min_base_offset < 0 {
-            self.active_segment.get_base_offset()
-        } else {
-            min_base_offset
+        let mut segment_start = self.get_local_start_offset();
+        if let Some(tiered) = &self.tiered
+            && tiered.min_offset() >= 0
+        {
+            segment_start = segment_start.min(tiered.min_offset());
         }
+        segment_start.max(self.log_start_checkpoint.get_offset())
     }
 
     /// read partition slice
@@ -114,6 +126,26 @@ impl ReplicaStorage for FileReplica {
         }
     }
 
+    fn read_cached_batches(
+        &self,
+        offset: Offset,
+        end_offset: Offset,
+        max_len: u32,
+    ) -> Option<Vec<CachedBatch>> {
+        if offset < self.get_log_start_offset() {
+            return None;
+        }
+        self.cache.as_ref()?.read(offset, end_offset, max_len)
+    }
+
+    fn set_read_cache_enabled(&mut self, enabled: bool) {
+        if !enabled {
+            self.cache = None;
+        } else if self.cache.is_none() {
+            self.cache = self.new_read_cache();
+        }
+    }
+
     /// return the size in bytes (includes index size and log size)
     #[instrument(skip(self))]
     fn get_partition_size(&self) -> Size64 {
@@ -137,6 +169,7 @@ impl ReplicaStorage for FileReplica {
         update_highwatermark: bool,
     ) -> Result<usize> {
         let mut total_size = 0;
+        let max_request_size = self.option.max_request_size.get() as usize;
         // check if any of the records's batch exceed max length
         for batch in &records.batches {
             let batch_size = batch.write_size(0);
@@ -148,8 +181,8 @@ impl ReplicaStorage for FileReplica {
                 .into());
             }
             total_size += batch_size;
-            if batch_size > self.max_request_size {
-                return Err(StorageError::BatchTooBig(self.max_request_size).into());
+            if batch_size > max_request_size {
+                return Err(StorageError::BatchTooBig(max_request_size).into());
             }
         }
 
@@ -184,8 +217,56 @@ impl ReplicaStorage for FileReplica {
         }
     }
 
+    /// delete records before `offset`, up to high watermark.
+    /// Segments having only deleted records are removed. Deleted records of remaining segment
+    /// are not readable and removed with the segment.
+    #[instrument(skip(self))]
+    async fn delete_records_before(&mut self, offset: Offset) -> Result<Offset> {
+        let hw = self.get_hw();
+        if offset > hw {
+            return Err(StorageError::Other(format!(
+                "offset: {offset} is greater than high watermark: {hw}"
+            ))
+            .into());
+        }
+        if offset <= self.get_log_start_offset() {
+            return Ok(self.get_log_start_offset());
+        }
+
+        // all records of active segment are deleted, close it so it can be removed
+        if offset >= self.get_leo() && self.get_leo() > self.active_segment.get_base_offset() {
+            self.roll_over().await?;
+            self.size
+                .store_active(self.active_segment.occupied_memory());
+        }
+        self.log_start_checkpoint.write(offset);
+
+        let segments = self.prev_segments.read().await.find_before(offset);
+        self.prev_segments.remove_segments(&segments).await;
+        let prev_size = self.prev_segments.read().await.occupied_memory();
+        self.size.store_prev(prev_size);
+        if let Some(tiered) = &self.tiered {
+            tiered.remove_before(offset).await;
+        }
+        if let Some(cache) = &self.cache {
+            cache.remove_before(offset);
+        }
+
+        info!(
+            offset,
+            removed_segments = segments.len(),
+            path = %self.option.base_dir.display(),
+            "deleted records"
+        );
+        Ok(self.get_log_start_offset())
+    }
+
     #[instrument(skip(self))]
     async fn remove(&self) -> Result<(), StorageError> {
+        // offloaded segments are under prefix of this SPU, other replicas keep theirs
+        if let Some(tiered) = &self.tiered {
+            tiered.remove_all().await;
+        }
         remove_dir_all(&self.option.base_dir)
             .await
             .map_err(StorageError::Io)?;
@@ -193,6 +274,15 @@ impl ReplicaStorage for FileReplica {
         self.cleaner.shutdown();
         Ok(())
     }
+
+    fn update_config(&self, replica: &Replica) {
+        self.option.update_from_replica(replica);
+    }
+
+    fn update_flush_policy(&self, flush_write_count: Size, flush_idle_msec: Size) {
+        self.option.flush_write_count.set(flush_write_count);
+        self.option.flush_idle_msec.set(flush_idle_msec);
+    }
 }
 
 impl FileReplica {
@@ -229,10 +319,26 @@ impl FileReplica {
 
         info!(replica_dir = %replica_dir.display(),  "creating");
         create_dir_all(&replica_dir).await?; // ensure dir_name exits
+        let dir_lock = match ReplicaDirLock::try_lock(&replica_dir) {
+            Ok(Some(lock)) => Some(lock),
+            Ok(None) => {
+                warn!("replica directory is locked by another process");
+                None
+            }
+            Err(err) => {
+                warn!(%err, "unable to lock replica directory");
+                None
+            }
+        };
 
         let mut rep_option = replica_config.clone();
         rep_option.base_dir = replica_dir;
 
+        let tiered = match &rep_option.tiered_storage {
+            Some(url) => Some(Arc::new(TieredStorage::open(url, &rep_option).await?)),
+            None => None,
+        };
+
         let shared_config: Arc<SharedReplicaConfig> = Arc::new(rep_option.into());
 
         let (segments, last_offset_res) = SharedSegments::from_dir(shared_config.clone()).await?;
@@ -272,6 +378,9 @@ impl FileReplica {
             commit_checkpoint.write(leo);
         }
 
+        let log_start_checkpoint =
+            CheckPoint::create(shared_config.clone(), LOG_START_CHECKPOINT_FILE_NAME, 0).await?;
+
         let size = Arc::new(ReplicaSize::default());
         size.store_active(active_segment.occupied_memory());
 
@@ -280,24 +389,37 @@ impl FileReplica {
             shared_config.clone(),
             segments.clone(),
             size.clone(),
+            tiered.clone(),
         );
 
-        let max_request_size = shared_config.max_request_size.get_consistent() as usize;
         let max_segment_size = shared_config.segment_max_bytes.get_consistent() as usize;
-
-        Ok(Self {
+        let mut replica = Self {
             option: shared_config,
             last_base_offset,
             partition,
             active_segment,
             prev_segments: segments,
             commit_checkpoint,
+            log_start_checkpoint,
             cleaner,
+            tiered,
+            cache: None,
             size,
             short_circuit: false,
-            max_request_size,
             max_segment_size,
-        })
+            _dir_lock: dir_lock,
+        };
+        replica.cache = replica.new_read_cache();
+
+        Ok(replica)
+    }
+
+    fn new_read_cache(&self) -> Option<ReplicaCache> {
+        self.option
+            .read_cache
+            .clone()
+            .filter(|cache| cache.is_enabled())
+            .map(ReplicaCache::new)
     }
 
     /// clear the any holding directory for replica
@@ -313,6 +435,16 @@ impl FileReplica {
         }
     }
 
+    /// earliest offset on local disk
+    fn get_local_start_offset(&self) -> Offset {
+        let min_base_offset = self.prev_segments.min_offset();
+        if min_base_offset < 0 {
+            self.active_segment.get_base_offset()
+        } else {
+            min_base_offset
+        }
+    }
+
     /// update high watermark to end
     #[instrument(skip(self))]
     pub async fn update_high_watermark_to_end(&mut self) -> Result<bool, StorageError> {
@@ -346,14 +478,23 @@ impl FileReplica {
         let leo = self.get_leo();
         debug!(hw, leo, "starting read records",);
 
+        let log_start = self.get_log_start_offset();
+        if start_offset < log_start {
+            return Err(ErrorCode::OffsetEvicted {
+                offset: start_offset,
+                next_available: log_start,
+            });
+        }
+
         let mut slice = ReplicaSlice {
             end: OffsetInfo { hw, leo },
-            start: self.get_log_start_offset(),
+            start: log_start,
             ..Default::default()
         };
 
         let active_base_offset = self.active_segment.get_base_offset();
-        let file_slice = if start_offset >= active_base_offset {
+        let in_active_segment = start_offset >= active_base_offset;
+        let file_slice = if in_active_segment {
             debug!(start_offset, active_base_offset, "is in active segment");
             if start_offset == leo {
                 trace!("start offset is same as end offset, skipping");
@@ -374,6 +515,20 @@ impl FileReplica {
                     start_offset, max_offset, self.active_segment
                 )));
             }
+        } else if start_offset < self.get_local_start_offset()
+            && let Some(tiered) = &self.tiered
+        {
+            debug!(
+                start_offset,
+                "not in local segments, reading from tiered storage"
+            );
+            tiered
+                .find_slice(start_offset, max_offset)
+                .await?
+                .ok_or_else(|| ErrorCode::OffsetEvicted {
+                    offset: start_offset,
+                    next_available: self.get_log_start_offset(),
+                })?
         } else {
             debug!(start_offset, active_base_offset, "not in active sgments");
             self.prev_segments
@@ -398,6 +553,8 @@ impl FileReplica {
             "retrieved slice",
         );
 
+        // slices of previous segments end at segment boundary
+        slice.complete = in_active_segment && file_slice.len() <= max_len as u64;
         slice.file_slice = Some(limited_slice);
         Ok(slice)
     }
@@ -448,6 +605,9 @@ impl FileReplica {
 
         self.size
             .store_active(self.active_segment.occupied_memory());
+        if let Some(cache) = &self.cache {
+            cache.insert(item);
+        }
         Ok(())
     }
 
@@ -535,6 +695,7 @@ mod tests {
     use flv_util::fixture::ensure_clean_dir;
 
     use crate::config::{ReplicaConfig, StorageConfig};
+    use crate::cache::BatchCache;
     use crate::StorageError;
     use crate::ReplicaStorage;
     use crate::fixture::storage_config;
@@ -685,12 +846,14 @@ mod tests {
             .read_all_uncommitted_records(FileReplica::PREFER_MAX_LEN)
             .await
             .expect("read");
+        assert!(slice.complete);
         assert_eq!(slice.file_slice.unwrap().len() as usize, batch_len * 2);
 
         let slice = replica
             .read_all_uncommitted_records(50)
             .await
             .expect("read");
+        assert!(!slice.complete);
         assert_eq!(slice.file_slice.unwrap().len(), 50);
     }
 
@@ -1098,6 +1261,124 @@ mod tests {
         assert_eq!(Arc::strong_count(&segments), 1);
     }
 
+    #[fluvio_future::test]
+    async fn test_replica_delete_records_before() {
+        let mut option = base_option("test_replica_delete_records");
+        // enough for 2 batch (2 records per batch)
+        option.segment_max_bytes = 160;
+        option.index_max_interval_bytes = 50;
+
+        let producer = BatchProducer::builder()
+            .records(2u16)
+            .record_generator(Arc::new(|_, _| Record::new("1")))
+            .build()
+            .expect("batch");
+
+        let mut replica = create_replica("test", 0, option).await;
+        for _ in 0..3 {
+            replica
+                .write_batch(&mut producer.generate_batch())
+                .await
+                .expect("write");
+        }
+        replica.update_high_watermark_to_end().await.expect("hw");
+        assert_eq!(replica.prev_segments.read().await.len(), 1);
+        assert_eq!(replica.get_log_start_offset(), 0);
+
+        // first segment still has records after offset
+        assert_eq!(replica.delete_records_before(2).await.expect("delete"), 2);
+        assert_eq!(replica.prev_segments.read().await.len(), 1);
+        assert!(matches!(
+            replica.read_records(1, None, 1024).await,
+            Err(ErrorCode::OffsetEvicted {
+                offset: 1,
+                next_available: 2,
+            })
+        ));
+        assert!(replica.read_records(2, None, 1024).await.is_ok());
+
+        assert_eq!(replica.delete_records_before(5).await.expect("delete"), 5);
+        assert_eq!(replica.prev_segments.read().await.len(), 0);
+
+        // uncommitted records can't be deleted
+        assert!(replica.delete_records_before(7).await.is_err());
+
+        // deleting all records closes active segment
+        assert_eq!(replica.delete_records_before(6).await.expect("delete"), 6);
+        assert_eq!(replica.prev_segments.read().await.len(), 0);
+        assert_eq!(replica.get_leo(), 6);
+        assert_eq!(replica.get_log_start_offset(), 6);
+    }
+
+    #[fluvio_future::test]
+    async fn test_replica_read_cache() {
+        let mut option = base_option("test_replica_read_cache");
+        let cache = BatchCache::shared(1_000_000);
+        option.read_cache = Some(cache.clone());
+
+        let producer = BatchProducer::builder()
+            .records(2u16)
+            .record_generator(Arc::new(|_, _| Record::new("1")))
+            .build()
+            .expect("batch");
+
+        let mut replica = create_replica("test", 0, option).await;
+        for _ in 0..3 {
+            replica
+                .write_batch(&mut producer.generate_batch())
+                .await
+                .expect("write");
+        }
+        replica.update_high_watermark_to_end().await.expect("hw");
+
+        let batches = replica.read_cached_batches(3, 6, u32::MAX).expect("cached");
+        let base_offsets: Vec<_> = batches
+            .iter()
+            .map(|cached| cached.batch.get_base_offset())
+            .collect();
+        assert_eq!(base_offsets, vec![2, 4]);
+        assert_eq!(batches[0].batch.get_last_offset(), 3);
+
+        replica.delete_records_before(4).await.expect("delete");
+        assert!(replica.read_cached_batches(2, 6, u32::MAX).is_none());
+        assert!(replica.read_cached_batches(4, 6, u32::MAX).is_some());
+
+        drop(replica);
+        assert_eq!(cache.used_bytes(), 0);
+    }
+
+    #[fluvio_future::test]
+    async fn test_replica_read_cache_disabled() {
+        let mut option = base_option("test_replica_read_cache_disabled");
+        let cache = BatchCache::shared(1_000_000);
+        option.read_cache = Some(cache.clone());
+
+        let producer = BatchProducer::builder()
+            .records(2u16)
+            .record_generator(Arc::new(|_, _| Record::new("1")))
+            .build()
+            .expect("batch");
+
+        let mut replica = create_replica("test", 0, option).await;
+        replica.set_read_cache_enabled(false);
+        replica
+            .write_batch(&mut producer.generate_batch())
+            .await
+            .expect("write");
+        assert_eq!(cache.used_bytes(), 0);
+        assert!(replica.read_cached_batches(0, 2, u32::MAX).is_none());
+
+        replica.set_read_cache_enabled(true);
+        replica
+            .write_batch(&mut producer.generate_batch())
+            .await
+            .expect("write");
+        replica.update_high_watermark_to_end().await.expect("hw");
+        assert!(cache.used_bytes() > 0);
+        assert!(replica.read_cached_batches(0, 4, u32::MAX).is_none());
+        assert!(replica.read_cached_batches(2, 4, u32::MAX).is_some());
+    }
+
     #[fluvio_future::test]
     async fn test_replica_size_enforced() {
         //given
@@ -258,6 +258,21 @@ impl SegmentList {
             .collect()
     }
 
+    /// segments having only records before offset
+    pub(crate) fn find_before(&self, offset: Offset) -> Vec<Offset> {
+        self.segments
+            .iter()
+            .filter(|(_, segment)| segment.get_end_offset() <= offset)
+            .map(|(base_offset, _)| *base_offset)
+            .collect()
+    }
+
+    pub(crate) fn end_offset(&self, base_offset: Offset) -> Option<Offset> {
+        self.segments
+            .get(&base_offset)
+            .map(ReadSegment::get_end_offset)
+    }
+
     #[instrument(skip(self))]
     pub(crate) fn find_first(&self, count: usize) -> Vec<Offset> {
         self.segments.keys().take(count).copied().collect()
@@ -0,0 +1,581 @@
+use std::collections::BTreeMap;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::AtomicI64;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Result, anyhow};
+use async_lock::{Mutex, RwLock};
+use futures_lite::StreamExt;
+use futures_lite::io::{AsyncReadExt, AsyncWriteExt};
+use object_store::{ObjectStore, WriteMultipart};
+use object_store::path::Path as ObjectPath;
+use tracing::{debug, info, instrument, warn};
+use url::Url;
+
+use fluvio_future::file_slice::AsyncFileSlice;
+use fluvio_future::fs::{File, create_dir_all, remove_dir_all};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::{NO_TIMESTAMP, Offset};
+
+use crate::batch_header::BatchHeaderStream;
+use crate::config::{ReplicaConfig, SharedReplicaConfig};
+use crate::index::EXTENSION as INDEX_EXTENSION;
+use crate::records::MESSAGE_LOG_EXTENSION;
+use crate::segment::ReadSegment;
+use crate::util::generate_file_name;
+
+const MEM_ORDER: std::sync::atomic::Ordering = std::sync::atomic::Ordering::SeqCst;
+
+/// sub directory of replica holding segments downloaded from object store
+const CACHE_DIR: &str = "tiered";
+/// max number of downloaded segments kept on local disk per replica
+const MAX_CACHED_SEGMENTS: usize = 4;
+const UPLOAD_CHUNK_SIZE: usize = 8 * 1024 * 1024; // 8mb
+
+/// Segment which has been offloaded to object store
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RemoteSegment {
+    end_offset: Offset,
+    /// max timestamp of records in segment, in milliseconds
+    max_timestamp: i64,
+}
+
+/// Tiered storage of a replica.
+/// Closed segments are uploaded to an object store (S3, MinIO or local file system)
+/// under `<url>/<spu logs dir>/<replica dir>/` and removed from local disk.
+/// Each SPU holding the replica keeps its own copy, so removing a follower doesn't touch segments of the leader.
+/// Offloaded segments are downloaded on demand when records before the first local segment are read.
+#[derive(Debug)]
+pub(crate) struct TieredStorage {
+    store: Arc<dyn ObjectStore>,
+    /// location of segments of this replica in the store
+    prefix: ObjectPath,
+    replica_dir: PathBuf,
+    cache_config: Arc<SharedReplicaConfig>,
+    segments: RwLock<BTreeMap<Offset, RemoteSegment>>,
+    min_offset: AtomicI64,
+    cache: RwLock<BTreeMap<Offset, ReadSegment>>,
+    /// segments being downloaded, so a segment is downloaded once without blocking reads of others
+    downloads: Mutex<BTreeMap<Offset, Arc<Mutex<()>>>>,
+}
+
+impl TieredStorage {
+    /// open tiered storage at `url` for replica whose directory is the base dir of `replica_config`.
+    /// Segments already in the store are loaded, so they stay readable after restart.
+    #[instrument(skip(replica_config))]
+    pub(crate) async fn open(url: &str, replica_config: &ReplicaConfig) -> Result<Self> {
+        let replica_dir = replica_config.base_dir.clone();
+        let replica_name = dir_name(&replica_dir)?;
+        // logs dir contains spu id
+        let spu_name = replica_dir
+            .parent()
+            .ok_or_else(|| anyhow!("invalid replica dir: {}", replica_dir.display()))
+            .and_then(dir_name)?;
+
+        let (store, root) = object_store(url)?;
+        let prefix = root.child(spu_name).child(replica_name);
+
+        let mut cache_option = replica_config.clone();
+        cache_option.base_dir = replica_dir.join(CACHE_DIR);
+        // downloaded segments are not tracked across restarts
+        if cache_option.base_dir.exists() {
+            remove_dir_all(&cache_option.base_dir).await?;
+        }
+        create_dir_all(&cache_option.base_dir).await?;
+
+        let tiered = Self {
+            store: Arc::from(store),
+            prefix,
+            replica_dir,
+            cache_config: cache_option.shared(),
+            segments: RwLock::new(BTreeMap::new()),
+            min_offset: AtomicI64::new(-1),
+            cache: RwLock::new(BTreeMap::new()),
+            downloads: Mutex::new(BTreeMap::new()),
+        };
+        tiered.load_segments().await?;
+        Ok(tiered)
+    }
+
+    /// base offset of first offloaded segment, -1 if there are none
+    pub(crate) fn min_offset(&self) -> Offset {
+        self.min_offset.load(MEM_ORDER)
+    }
+
+    async fn load_segments(&self) -> Result<()> {
+        let mut listing = self.store.list(Some(&self.prefix));
+        let mut segments = self.segments.write().await;
+        while let Some(meta) = listing.next().await {
+            let meta = meta?;
+            if let Some((base_offset, segment)) = meta
+                .location
+                .filename()
+                .and_then(|name| parse_object_name(name, MESSAGE_LOG_EXTENSION))
+            {
+                segments.insert(base_offset, segment);
+            }
+        }
+        info!(
+            segments = segments.len(),
+            prefix = %self.prefix,
+            "loaded tiered segments"
+        );
+        self.update_min_offset(&segments);
+        Ok(())
+    }
+
+    /// upload closed segment from replica dir. Once this returns, segment can be removed from local disk.
+    #[instrument(skip(self))]
+    pub(crate) async fn upload_segment(
+        &self,
+        base_offset: Offset,
+        end_offset: Offset,
+    ) -> Result<()> {
+        let segment = RemoteSegment {
+            end_offset,
+            max_timestamp: self.max_timestamp(base_offset).await?,
+        };
+        // index goes first, so listed log always has its index
+        for extension in [INDEX_EXTENSION, MESSAGE_LOG_EXTENSION] {
+            let path = generate_file_name(&self.replica_dir, base_offset, extension);
+            let location = self.location(base_offset, &segment, extension);
+            self.put_file(&path, &location).await?;
+        }
+
+        let mut segments = self.segments.write().await;
+        segments.insert(base_offset, segment);
+        self.update_min_offset(&segments);
+        info!(base_offset, end_offset, prefix = %self.prefix, "offloaded segment");
+        Ok(())
+    }
+
+    /// max timestamp of records in local segment.
+    /// Falls back to modification time of log for records without timestamp.
+    async fn max_timestamp(&self, base_offset: Offset) -> Result<i64> {
+        let path = generate_file_name(&self.replica_dir, base_offset, MESSAGE_LOG_EXTENSION);
+        let mut max_timestamp = NO_TIMESTAMP;
+        match BatchHeaderStream::open(&path).await {
+            Ok(mut stream) => {
+                while let Some(batch_pos) = stream.try_next().await? {
+                    max_timestamp =
+                        max_timestamp.max(batch_pos.inner().get_header().max_time_stamp);
+                }
+            }
+            // empty log
+            Err(err) if err.kind() == ErrorKind::UnexpectedEof => {}
+            Err(err) => return Err(err.into()),
+        }
+        if max_timestamp == NO_TIMESTAMP {
+            let modified = File::open(&path).await?.metadata().await?.modified()?;
+            max_timestamp = modified.duration_since(UNIX_EPOCH)?.as_millis() as i64;
+        }
+        Ok(max_timestamp)
+    }
+
+    async fn put_file(&self, path: &Path, location: &ObjectPath) -> Result<()> {
+        let mut file = File::open(path).await?;
+        let mut writer = WriteMultipart::new(self.store.put_multipart(location).await?);
+        let mut buf = vec![0; UPLOAD_CHUNK_SIZE];
+        loop {
+            match file.read(&mut buf).await {
+                Ok(0) => break,
+                Ok(read) => writer.write(&buf[..read]),
+                Err(err) => {
+                    writer.abort().await?;
+                    return Err(err.into());
+                }
+            }
+        }
+        writer.finish().await?;
+        Ok(())
+    }
+
+    /// find slice of offloaded segment containing `start_offset`, downloading segment if needed
+    #[instrument(skip(self))]
+    pub(crate) async fn find_slice(
+        &self,
+        start_offset: Offset,
+        max_offset: Option<Offset>,
+    ) -> Result<Option<AsyncFileSlice>, ErrorCode> {
+        let Some((base_offset, segment)) = self.find_segment(start_offset).await else {
+            return Ok(None);
+        };
+
+        if let Some(slice) = self
+            .cached_slice(base_offset, start_offset, max_offset)
+            .await
+        {
+            return slice;
+        }
+
+        // only download of same segment waits
+        let download = self
+            .downloads
+            .lock()
+            .await
+            .entry(base_offset)
+            .or_default()
+            .clone();
+        let _guard = download.lock().await;
+        if let Some(slice) = self
+            .cached_slice(base_offset, start_offset, max_offset)
+            .await
+        {
+            return slice;
+        }
+
+        let slice = self
+            .download_to_cache(base_offset, &segment, start_offset, max_offset)
+            .await;
+        // removed once segment is in cache, so it isn't downloaded again
+        self.downloads.lock().await.remove(&base_offset);
+        slice
+    }
+
+    async fn download_to_cache(
+        &self,
+        base_offset: Offset,
+        segment: &RemoteSegment,
+        start_offset: Offset,
+        max_offset: Option<Offset>,
+    ) -> Result<Option<AsyncFileSlice>, ErrorCode> {
+        debug!(base_offset, "downloading segment");
+        let downloaded = self
+            .download_segment(base_offset, segment)
+            .await
+            .map_err(|err| {
+                ErrorCode::Other(format!(
+                    "failed to download segment: {base_offset} from tiered storage: {err}"
+                ))
+            })?;
+
+        let mut cache = self.cache.write().await;
+        while cache.len() >= MAX_CACHED_SEGMENTS {
+            if let Some((_, evicted)) = cache.pop_first()
+                && let Err(err) = evicted.remove().await
+            {
+                warn!(%err, "failed to remove cached segment");
+            }
+        }
+        let slice = downloaded.records_slice(start_offset, max_offset).await;
+        cache.insert(base_offset, downloaded);
+        slice
+    }
+
+    async fn cached_slice(
+        &self,
+        base_offset: Offset,
+        start_offset: Offset,
+        max_offset: Option<Offset>,
+    ) -> Option<Result<Option<AsyncFileSlice>, ErrorCode>> {
+        let cache = self.cache.read().await;
+        let segment = cache.get(&base_offset)?;
+        Some(segment.records_slice(start_offset, max_offset).await)
+    }
+
+    async fn find_segment(&self, offset: Offset) -> Option<(Offset, RemoteSegment)> {
+        let segments = self.segments.read().await;
+        segments
+            .range(..=offset)
+            .next_back()
+            .filter(|(_, segment)| offset < segment.end_offset)
+            .map(|(base_offset, segment)| (*base_offset, segment.clone()))
+    }
+
+    async fn download_segment(
+        &self,
+        base_offset: Offset,
+        segment: &RemoteSegment,
+    ) -> Result<ReadSegment> {
+        for extension in [INDEX_EXTENSION, MESSAGE_LOG_EXTENSION] {
+            let location = self.location(base_offset, segment, extension);
+            let path = generate_file_name(&self.cache_config.base_dir, base_offset, extension);
+            let mut stream = self.store.get(&location).await?.into_stream();
+            let mut file = File::create(&path).await?;
+            while let Some(chunk) = stream.next().await {
+                file.write_all(&chunk?).await?;
+            }
+            file.flush().await?;
+        }
+        ReadSegment::open_for_read(base_offset, segment.end_offset, self.cache_config.clone()).await
+    }
+
+    /// remove offloaded segments whose records are all older than `retention`
+    #[instrument(skip(self))]
+    pub(crate) async fn remove_expired(&self, retention: &Duration) {
+        let Ok(now) = SystemTime::now().duration_since(UNIX_EPOCH) else {
+            return;
+        };
+        let expired_before = now.saturating_sub(*retention).as_millis() as i64;
+        let expired: Vec<Offset> = self
+            .segments
+            .read()
+            .await
+            .iter()
+            .filter(|(_, segment)| segment.max_timestamp < expired_before)
+            .map(|(base_offset, _)| *base_offset)
+            .collect();
+        self.remove_segments(&expired).await;
+    }
+
+    /// remove offloaded segments having only records before offset
+    pub(crate) async fn remove_before(&self, offset: Offset) {
+        let before: Vec<Offset> = self
+            .segments
+            .read()
+            .await
+            .iter()
+            .filter(|(_, segment)| segment.end_offset <= offset)
+            .map(|(base_offset, _)| *base_offset)
+            .collect();
+        self.remove_segments(&before).await;
+    }
+
+    /// remove all offloaded segments of replica on this SPU
+    pub(crate) async fn remove_all(&self) {
+        let all: Vec<Offset> = self.segments.read().await.keys().copied().collect();
+        self.remove_segments(&all).await;
+    }
+
+    async fn remove_segments(&self, base_offsets: &[Offset]) {
+        if base_offsets.is_empty() {
+            return;
+        }
+        let mut segments = self.segments.write().await;
+        for base_offset in base_offsets {
+            let Some(segment) = segments.remove(base_offset) else {
+                continue;
+            };
+            info!(base_offset, prefix = %self.prefix, "removing offloaded segment");
+            for extension in [MESSAGE_LOG_EXTENSION, INDEX_EXTENSION] {
+                let location = self.location(*base_offset, &segment, extension);
+                if let Err(err) = self.store.delete(&location).await {
+                    warn!(%location, %err, "failed to delete offloaded segment");
+                }
+            }
+            if let Some(cached) = self.cache.write().await.remove(base_offset)
+                && let Err(err) = cached.remove().await
+            {
+                warn!(%err, "failed to remove cached segment");
+            }
+        }
+        self.update_min_offset(&segments);
+    }
+
+    fn update_min_offset(&self, segments: &BTreeMap<Offset, RemoteSegment>) {
+        let min_offset = segments.keys().next().copied().unwrap_or(-1);
+        self.min_offset.store(min_offset, MEM_ORDER);
+    }
+
+    fn location(
+        &self,
+        base_offset: Offset,
+        segment: &RemoteSegment,
+        extension: &str,
+    ) -> ObjectPath {
+        self.prefix.child(format!(
+            "{base_offset:020}-{:020}-{:020}.{extension}",
+            segment.end_offset, segment.max_timestamp
+        ))
+    }
+}
+
+fn dir_name(dir: &Path) -> Result<&str> {
+    dir.file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| anyhow!("invalid replica dir: {}", dir.display()))
+}
+
+/// object store for url such as `s3://bucket/prefix` or `file:///path`.
+/// Credentials and endpoint of S3 compatible stores (MinIO) are taken from `AWS_*` environment variables.
+fn object_store(url: &str) -> Result<(Box<dyn ObjectStore>, ObjectPath)> {
+    let url = Url::parse(url).map_err(|err| anyhow!("invalid tiered storage url: {url}, {err}"))?;
+    let options = std::env::vars()
+        .filter(|(key, _)| key.starts_with("AWS_"))
+        .map(|(key, value)| (key.to_ascii_lowercase(), value));
+    Ok(object_store::parse_url_opts(&url, options)?)
+}
+
+/// parse segment from object name: `<base offset>-<end offset>-<max timestamp>.<extension>`
+fn parse_object_name(name: &str, extension: &str) -> Option<(Offset, RemoteSegment)> {
+    let mut parts = name.strip_suffix(extension)?.strip_suffix('.')?.split('-');
+    let base_offset = parts.next()?.parse().ok()?;
+    let segment = RemoteSegment {
+        end_offset: parts.next()?.parse().ok()?,
+        max_timestamp: parts.next()?.parse().ok()?,
+    };
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((base_offset, segment))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::env::temp_dir;
+    use std::path::Path;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use flv_util::fixture::ensure_new_dir;
+    use fluvio_protocol::fixture::create_batch;
+
+    use crate::config::ReplicaConfig;
+    use crate::segment::MutableSegment;
+
+    use super::{RemoteSegment, TieredStorage, parse_object_name};
+
+    fn replica_option(dir: &Path) -> ReplicaConfig {
+        ReplicaConfig {
+            base_dir: dir.join("test-0"),
+            segment_max_bytes: 1000,
+            index_max_bytes: 1000,
+            index_max_interval_bytes: 0,
+            ..Default::default()
+        }
+    }
+
+    fn store_url(dir: &Path) -> String {
+        format!("file://{}", dir.join("store").display())
+    }
+
+    #[test]
+    fn test_parse_object_name() {
+        assert_eq!(
+            parse_object_name(
+                "00000000000000000100-00000000000000000600-00000001700000000000.log",
+                "log"
+            ),
+            Some((
+                100,
+                RemoteSegment {
+                    end_offset: 600,
+                    max_timestamp: 1_700_000_000_000
+                }
+            ))
+        );
+        assert_eq!(
+            parse_object_name("00000000000000000100-00000000000000000600.log", "log"),
+            None
+        );
+        assert_eq!(
+            parse_object_name(
+                "00000000000000000100-00000000000000000600-00000001700000000000.index",
+                "log"
+            ),
+            None
+        );
+    }
+
+    #[fluvio_future::test]
+    async fn test_offload_and_read_back() {
+        let dir = temp_dir().join("tiered-offload-read");
+        ensure_new_dir(&dir).expect("new");
+        ensure_new_dir(dir.join("store")).expect("store");
+        let option = replica_option(&dir);
+        ensure_new_dir(&option.base_dir).expect("replica");
+
+        let mut segment = MutableSegment::create(20, option.clone().shared())
+            .await
+            .expect("create");
+        segment
+            .append_batch(&mut create_batch())
+            .await
+            .expect("append");
+        segment
+            .append_batch(&mut create_batch())
+            .await
+            .expect("append");
+        let end_offset = segment.get_end_offset();
+        let segment = segment.convert_to_segment().await.expect("convert");
+
+        let tiered = TieredStorage::open(&store_url(&dir), &option)
+            .await
+            .expect("open");
+        assert_eq!(tiered.min_offset(), -1);
+        tiered.upload_segment(20, end_offset).await.expect("upload");
+        segment.remove().await.expect("remove local");
+        assert_eq!(tiered.min_offset(), 20);
+
+        // offloaded segments are found again after restart
+        let tiered = TieredStorage::open(&store_url(&dir), &option)
+            .await
+            .expect("reopen");
+        assert_eq!(tiered.min_offset(), 20);
+
+        let slice = tiered
+            .find_slice(22, None)
+            .await
+            .expect("read")
+            .expect("slice");
+        assert!(slice.len() > 0);
+        assert!(
+            tiered
+                .find_slice(end_offset, None)
+                .await
+                .expect("read")
+                .is_none()
+        );
+        assert!(tiered.find_slice(10, None).await.expect("read").is_none());
+
+        tiered.remove_expired(&Duration::from_secs(3600)).await;
+        assert_eq!(tiered.min_offset(), 20);
+        tiered.remove_before(end_offset).await;
+        assert_eq!(tiered.min_offset(), -1);
+    }
+
+    #[fluvio_future::test]
+    async fn test_retention_by_record_timestamp() {
+        let dir = temp_dir().join("tiered-retention");
+        ensure_new_dir(&dir).expect("new");
+        ensure_new_dir(dir.join("store")).expect("store");
+        let option = replica_option(&dir);
+        ensure_new_dir(&option.base_dir).expect("replica");
+
+        // records produced two hours ago
+        let two_hours_ago = (SystemTime::now() - Duration::from_secs(7200))
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_millis() as i64;
+        let mut batch = create_batch();
+        batch.get_mut_header().first_timestamp = two_hours_ago;
+        batch.get_mut_header().max_time_stamp = two_hours_ago;
+
+        let mut segment = MutableSegment::create(0, option.clone().shared())
+            .await
+            .expect("create");
+        segment.append_batch(&mut batch).await.expect("append");
+        let end_offset = segment.get_end_offset();
+        segment.convert_to_segment().await.expect("convert");
+
+        let tiered = TieredStorage::open(&store_url(&dir), &option)
+            .await
+            .expect("open");
+        tiered.upload_segment(0, end_offset).await.expect("upload");
+
+        // just uploaded, but records are past retention
+        tiered.remove_expired(&Duration::from_secs(3 * 3600)).await;
+        assert_eq!(tiered.min_offset(), 0);
+        tiered.remove_expired(&Duration::from_secs(3600)).await;
+        assert_eq!(tiered.min_offset(), -1);
+    }
+
+    #[fluvio_future::test]
+    async fn test_prefix_per_spu() {
+        let dir = temp_dir().join("tiered-prefix");
+        ensure_new_dir(&dir).expect("new");
+        ensure_new_dir(dir.join("store")).expect("store");
+        let option = ReplicaConfig {
+            base_dir: dir.join("spu-logs-5001").join("test-0"),
+            ..Default::default()
+        };
+        ensure_new_dir(&option.base_dir).expect("replica");
+
+        let tiered = TieredStorage::open(&store_url(&dir), &option)
+            .await
+            .expect("open");
+        assert!(tiered.prefix.as_ref().ends_with("spu-logs-5001/test-0"));
+    }
+}
@@ -12,6 +12,8 @@ pub struct LocalMetadataItem {
     parent: Option<Box<LocalMetadataItem>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     children: Option<HashMap<String, Vec<LocalMetadataItem>>>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    labels: HashMap<String, String>,
 }
 
 impl MetadataItem for LocalMetadataItem {
@@ -25,6 +27,18 @@ impl MetadataItem for LocalMetadataItem {
         self.revision > another.revision
     }
 
+    fn set_labels<T: Into<String>>(mut self, labels: Vec<(T, T)>) -> Self {
+        self.labels = labels
+            .into_iter()
+            .map(|(key, value)| (key.into(), value.into()))
+            .collect();
+        self
+    }
+
+    fn get_labels(&self) -> HashMap<String, String> {
+        self.labels.clone()
+    }
+
     fn owner(&self) -> Option<&Self> {
         self.parent.as_ref().map(|p| p.as_ref())
     }
@@ -119,7 +133,7 @@ cfg_if::cfg_if! {
         const MAX_UPDATES_CAPACITY: usize = 100;
         #[derive(Debug)]
         pub struct LocalMetadataStorage {
-            path: PathBuf,
+            backend: StorageBackend,
             stores: RwLock<HashMap<&'static str, Arc<SpecStore>>>,
         }
         pub type LocalStoreObject<S> = MetadataStoreObject<S, LocalMetadataItem>;
@@ -286,14 +300,35 @@ cfg_if::cfg_if! {
             data: RwLock<HashMap<String, SpecPointer>>,
             sender: Sender<SpecUpdate>,
             receiver: Receiver<SpecUpdate>,
-            path: PathBuf,
+            persistence: SpecPersistence,
         }
 
         #[derive(Debug, Clone)]
         struct SpecPointer {
             inner: Arc<dyn Any + Send + Sync>,
             store_revision: u64,
-            path: PathBuf,
+        }
+
+        /// where metadata of all kinds is persisted
+        #[derive(Debug)]
+        enum StorageBackend {
+            /// directory per kind
+            Files(PathBuf),
+            #[cfg(feature = "sqlite")]
+            Sqlite(Arc<SqliteMetadata>),
+        }
+
+        /// where metadata of one kind is persisted
+        #[derive(Debug)]
+        enum SpecPersistence {
+            /// yaml file per object
+            Files(PathBuf),
+            /// row per object, keyed by kind and name
+            #[cfg(feature = "sqlite")]
+            Sqlite {
+                db: Arc<SqliteMetadata>,
+                kind: &'static str,
+            },
         }
 
         #[derive(Debug)]
@@ -304,9 +339,18 @@ cfg_if::cfg_if! {
 
         impl LocalMetadataStorage {
             pub fn new<P: AsRef<Path>>(path: P) -> Self {
-                let path = path.as_ref().to_path_buf();
+                let backend = StorageBackend::Files(path.as_ref().to_path_buf());
+                let stores = Default::default();
+                Self { backend, stores }
+            }
+
+            /// metadata stored in SQLite database, database is created if it doesn't exist
+            #[cfg(feature = "sqlite")]
+            pub fn new_sqlite<P: AsRef<Path>>(path: P) -> Result<Self> {
+                let db = SqliteMetadata::open(path.as_ref())?;
+                let backend = StorageBackend::Sqlite(Arc::new(db));
                 let stores = Default::default();
-                Self { path, stores }
+                Ok(Self { backend, stores })
             }
 
             fn get_store<S: Spec + DeserializeOwned>(&self) -> Result<Arc<SpecStore>> {
@@ -317,7 +361,7 @@ cfg_if::cfg_if! {
                     None => {
                         drop(read);
                         let mut write = self.stores.write();
-                        let store = Arc::new(SpecStore::load::<S, _>(self.path.join(key))?);
+                        let store = Arc::new(SpecStore::load::<S>(self.backend.persistence(key))?);
                         write.insert(key, store.clone());
                         drop(write);
                         store
@@ -378,35 +422,22 @@ cfg_if::cfg_if! {
         }
 
         impl SpecStore {
-            fn load<S: Spec, P: AsRef<Path>>(path: P) -> Result<Self> {
-                std::fs::create_dir_all(&path)?;
+            fn load<S: Spec>(persistence: SpecPersistence) -> Result<Self> {
                 let version = Default::default();
                 let mut data: HashMap<String, SpecPointer> = Default::default();
-                for entry in std::fs::read_dir(&path)? {
-                    let Ok(entry) = entry else {
-                        continue;
-                    };
-                    let path = entry.path();
-                    if !path.extension().eq(&Some(OsStr::new("yaml"))) {
-                        continue;
-                    }
-                    let (name, item) = SpecPointer::load::<S, _>(&path).context(format!(
-                        "loading metadata '{}' from {}",
-                        S::LABEL,
-                        path.display()
-                    ))?;
+                for storage in persistence.load::<S>()? {
+                    let name = storage.meta().uid().clone();
                     debug!(kind = S::LABEL, name, "loaded");
-                    data.insert(name, item);
+                    data.insert(name, SpecPointer::try_from(storage)?);
                 }
 
                 let (sender, receiver) = bounded(MAX_UPDATES_CAPACITY);
-                let path = path.as_ref().to_path_buf();
                 Ok(Self {
                     version,
                     data: RwLock::new(data),
                     sender,
                     receiver,
-                    path,
+                    persistence,
                 })
             }
 
@@ -453,7 +484,7 @@ cfg_if::cfg_if! {
                 let removed = {
                     let mut write = self.data.write();
                     if let Some(removed) = write.remove(metadata.uid()) {
-                        removed.delete();
+                        self.persistence.delete(metadata.uid());
                         drop(write);
                         Some(removed)
                     } else {
@@ -483,9 +514,9 @@ cfg_if::cfg_if! {
                         }
                         value.ctx_mut().item_mut().revision = prev_rev + 1;
                     };
-                    let pointer = SpecPointer::new(self.spec_file_name(&id), value);
+                    let pointer = SpecPointer::new(value);
+                    self.persistence.write(&id, &VersionedSpecStorage::<S>::try_from(&pointer)?)?;
                     write.insert(id, pointer.clone());
-                    pointer.flush::<S>()?;
                     drop(write);
                     pointer
                 };
@@ -521,10 +552,6 @@ cfg_if::cfg_if! {
                 }
             }
 
-            fn spec_file_name(&self, name: &str) -> PathBuf {
-                self.path.join(format!("{name}.yaml"))
-            }
-
             async fn send_update(&self, mut update: SpecUpdate) {
                 let store_revision = self
                     .version
@@ -538,25 +565,15 @@ cfg_if::cfg_if! {
         }
 
         impl SpecPointer {
-            fn new<S: Spec, P: AsRef<Path>>(path: P, obj: LocalStoreObject<S>) -> Self {
+            fn new<S: Spec>(obj: LocalStoreObject<S>) -> Self {
                 let inner = Arc::new(obj);
-                let path = path.as_ref().to_path_buf();
                 let store_revision = Default::default();
                 Self {
                     inner,
-                    path,
                     store_revision,
                 }
             }
 
-            fn load<S: Spec, P: AsRef<Path>>(path: P) -> Result<(String, Self)> {
-                let storage: VersionedSpecStorage<S> =
-                    serde_yaml::from_reader(std::fs::File::open(&path)?)?;
-                let name = storage.meta().uid().clone();
-                let pointer = SpecPointer::try_from((storage, path.as_ref().to_path_buf()))?;
-                Ok((name, pointer))
-            }
-
             fn downcast_ref<S: Spec>(&self) -> Result<&LocalStoreObject<S>> {
                 self.inner
                     .downcast_ref::<LocalStoreObject<S>>()
@@ -566,16 +583,153 @@ cfg_if::cfg_if! {
             fn downcast<S: Spec>(&self) -> Result<LocalStoreObject<S>> {
                 self.downcast_ref().cloned()
             }
+        }
+
+        impl StorageBackend {
+            fn persistence(&self, kind: &'static str) -> SpecPersistence {
+                match self {
+                    Self::Files(path) => SpecPersistence::Files(path.join(kind)),
+                    #[cfg(feature = "sqlite")]
+                    Self::Sqlite(db) => SpecPersistence::Sqlite {
+                        db: db.clone(),
+                        kind,
+                    },
+                }
+            }
+        }
+
+        impl SpecPersistence {
+            fn load<S: Spec>(&self) -> Result<Vec<VersionedSpecStorage<S>>> {
+                let mut items = Vec::new();
+                match self {
+                    Self::Files(dir) => {
+                        std::fs::create_dir_all(dir)?;
+                        for entry in std::fs::read_dir(dir)? {
+                            let Ok(entry) = entry else {
+                                continue;
+                            };
+                            let path = entry.path();
+                            if !path.extension().eq(&Some(OsStr::new("yaml"))) {
+                                continue;
+                            }
+                            let storage = std::fs::File::open(&path)
+                                .map_err(anyhow::Error::from)
+                                .and_then(|file| Ok(serde_yaml::from_reader(file)?))
+                                .context(format!(
+                                    "loading metadata '{}' from {}",
+                                    S::LABEL,
+                                    path.display()
+                                ))?;
+                            items.push(storage);
+                        }
+                    }
+                    #[cfg(feature = "sqlite")]
+                    Self::Sqlite { db, kind } => {
+                        for (name, body) in db.load(kind)? {
+                            let storage = serde_yaml::from_str(&body).context(format!(
+                                "loading metadata '{kind}/{name}' from {}",
+                                db.path.display()
+                            ))?;
+                            items.push(storage);
+                        }
+                    }
+                }
+                Ok(items)
+            }
+
+            fn write<S: Spec>(&self, name: &str, storage: &VersionedSpecStorage<S>) -> Result<()> {
+                match self {
+                    Self::Files(dir) => {
+                        let file = std::fs::File::create(dir.join(format!("{name}.yaml")))?;
+                        serde_yaml::to_writer(file, storage)?;
+                    }
+                    #[cfg(feature = "sqlite")]
+                    Self::Sqlite { db, kind } => {
+                        db.upsert(kind, name, &serde_yaml::to_string(storage)?)?;
+                    }
+                }
+                Ok(())
+            }
 
-            fn delete(&self) {
-                if let Err(err) = std::fs::remove_file(&self.path) {
-                    warn!("unable to delete spec file {}: {err}", self.path.display());
+            fn delete(&self, name: &str) {
+                match self {
+                    Self::Files(dir) => {
+                        let path = dir.join(format!("{name}.yaml"));
+                        if let Err(err) = std::fs::remove_file(&path) {
+                            warn!("unable to delete spec file {}: {err}", path.display());
+                        }
+                    }
+                    #[cfg(feature = "sqlite")]
+                    Self::Sqlite { db, kind } => {
+                        if let Err(err) = db.delete(kind, name) {
+                            warn!("unable to delete spec {kind}/{name}: {err}");
+                        }
+                    }
                 }
             }
+        }
 
-            fn flush<S: Spec>(&self) -> Result<()> {
-                let storage: VersionedSpecStorage<S> = self.try_into()?;
-                serde_yaml::to_writer(std::fs::File::create(&self.path)?, &storage)?;
+        /// SQLite database with metadata of all kinds in one table.
+        /// Objects are stored in the same versioned format as spec files,
+        /// every write is a single atomic statement.
+        #[cfg(feature = "sqlite")]
+        #[derive(Debug)]
+        struct SqliteMetadata {
+            path: PathBuf,
+            conn: parking_lot::Mutex<rusqlite::Connection>,
+        }
+
+        #[cfg(feature = "sqlite")]
+        impl SqliteMetadata {
+            fn open(path: &Path) -> Result<Self> {
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                let conn = rusqlite::Connection::open(path)
+                    .context(format!("opening metadata database {}", path.display()))?;
+                conn.execute_batch(
+                    "PRAGMA journal_mode = WAL;
+                     PRAGMA synchronous = NORMAL;
+                     CREATE TABLE IF NOT EXISTS metadata (
+                         kind TEXT NOT NULL,
+                         name TEXT NOT NULL,
+                         body TEXT NOT NULL,
+                         PRIMARY KEY (kind, name)
+                     );",
+                )?;
+                Ok(Self {
+                    path: path.to_path_buf(),
+                    conn: parking_lot::Mutex::new(conn),
+                })
+            }
+
+            /// name and body of all objects of the kind
+            fn load(&self, kind: &str) -> Result<Vec<(String, String)>> {
+                let conn = self.conn.lock();
+                let mut statement =
+                    conn.prepare_cached("SELECT name, body FROM metadata WHERE kind = ?1")?;
+                let rows = statement
+                    .query_map([kind], |row| Ok((row.get(0)?, row.get(1)?)))?
+                    .collect::<rusqlite::Result<Vec<_>>>()?;
+                Ok(rows)
+            }
+
+            fn upsert(&self, kind: &str, name: &str, body: &str) -> Result<()> {
+                self.conn
+                    .lock()
+                    .prepare_cached(
+                        "INSERT INTO metadata (kind, name, body) VALUES (?1, ?2, ?3)
+                         ON CONFLICT (kind, name) DO UPDATE SET body = excluded.body",
+                    )?
+                    .execute([kind, name, body])?;
+                Ok(())
+            }
+
+            fn delete(&self, kind: &str, name: &str) -> Result<()> {
+                self.conn
+                    .lock()
+                    .prepare_cached("DELETE FROM metadata WHERE kind = ?1 AND name = ?2")?
+                    .execute([kind, name])?;
                 Ok(())
             }
         }
@@ -655,15 +809,13 @@ cfg_if::cfg_if! {
             }
         }
 
-        impl<S> TryFrom<(VersionedSpecStorage<S>, PathBuf)> for SpecPointer
+        impl<S> TryFrom<VersionedSpecStorage<S>> for SpecPointer
         where
             S: Spec,
         {
             type Error = anyhow::Error;
 
-            fn try_from(
-                (value, path): (VersionedSpecStorage<S>, std::path::PathBuf),
-            ) -> std::result::Result<Self, Self::Error> {
+            fn try_from(value: VersionedSpecStorage<S>) -> std::result::Result<Self, Self::Error> {
                 Ok(match value {
                     VersionedSpecStorage::V1(storage) => {
                         let SpecStorageV1 {
@@ -678,7 +830,7 @@ cfg_if::cfg_if! {
                             .map_err(|_| anyhow!("failed to parse key from '{key}'"))?;
                         let mut obj = LocalStoreObject::new_with_context(key, spec, ctx);
                         obj.set_status(status);
-                        SpecPointer::new(path, obj)
+                        SpecPointer::new(obj)
                     }
                 })
             }
@@ -783,6 +935,76 @@ spec:
                 drop(meta_folder)
             }
 
+            #[cfg(feature = "sqlite")]
+            #[fluvio_future::test]
+            async fn test_spec_store_loaded_from_sqlite() {
+                //given
+                let meta_folder = tempfile::tempdir().expect("temp dir created");
+                let db_path = meta_folder.path().join("metadata.db");
+                let meta_store = LocalMetadataStorage::new_sqlite(&db_path).expect("opened");
+                let obj1 = default_test_store_obj();
+                let obj2 = test_store_obj("meta2");
+                let obj3 = test_store_obj("meta3");
+                meta_store.apply(obj1.clone()).await.expect("applied");
+                meta_store.apply(obj2.clone()).await.expect("applied");
+                meta_store.apply(obj3.clone()).await.expect("applied");
+                meta_store
+                    .delete_item::<TestSpec>(obj3.ctx_owned().item_owned())
+                    .await
+                    .expect("deleted");
+                drop(meta_store);
+
+                //when
+                let meta_store2 = LocalMetadataStorage::new_sqlite(&db_path).expect("opened");
+                let list = meta_store2
+                    .retrieve_items::<TestSpec>(&NameSpace::All)
+                    .await
+                    .expect("read items");
+
+                //then
+                assert!(db_path.exists());
+                assert_eq!(list.items.len(), 2);
+                assert!(list.items.contains(&obj1));
+                assert!(list.items.contains(&obj2));
+                assert!(!meta_folder.path().join(TestSpec::LABEL).exists());
+
+                drop(meta_folder)
+            }
+
+            #[cfg(feature = "sqlite")]
+            #[fluvio_future::test]
+            async fn test_update_spec_in_sqlite() {
+                //given
+                let meta_folder = tempfile::tempdir().expect("temp dir created");
+                let db_path = meta_folder.path().join("metadata.db");
+                let meta_store = LocalMetadataStorage::new_sqlite(&db_path).expect("opened");
+                let obj = default_test_store_obj();
+                let spec = TestSpec {
+                    replica: 5,
+                    ..Default::default()
+                };
+                meta_store.apply(obj.clone()).await.expect("applied");
+
+                //when
+                meta_store
+                    .update_spec(obj.ctx_owned().item_owned(), spec)
+                    .await
+                    .expect("updated spec");
+                drop(meta_store);
+                let meta_store2 = LocalMetadataStorage::new_sqlite(&db_path).expect("opened");
+                let items = meta_store2
+                    .retrieve_items::<TestSpec>(&NameSpace::All)
+                    .await
+                    .expect("retrieved");
+
+                //then
+                assert_eq!(items.items.len(), 1);
+                assert_eq!(items.items[0].spec().replica, 5);
+                assert_eq!(items.items[0].ctx().item().revision, 1);
+
+                drop(meta_folder)
+            }
+
             #[fluvio_future::test]
             async fn test_update_status() {
                 //given
@@ -870,6 +1092,36 @@ spec:
                 drop(meta_folder)
             }
 
+            #[fluvio_future::test]
+            async fn test_labels_stored_on_fs() {
+                //given
+                let meta_folder = tempfile::tempdir().expect("temp dir created");
+                let meta_store = LocalMetadataStorage::new(&meta_folder);
+                let obj = default_test_store_obj();
+                meta_store.apply(obj.clone()).await.expect("applied");
+
+                //when
+                let meta = obj.ctx_owned().item_owned().set_labels(vec![("version", "2")]);
+                meta_store
+                    .update_spec(meta, TestSpec::default())
+                    .await
+                    .expect("updated spec");
+                drop(meta_store);
+
+                //then
+                let meta_store2 = LocalMetadataStorage::new(&meta_folder);
+                let items = meta_store2
+                    .retrieve_items::<TestSpec>(&NameSpace::All)
+                    .await
+                    .expect("retrieved");
+                assert_eq!(
+                    items.items[0].ctx().item().get_labels().get("version"),
+                    Some(&"2".to_owned())
+                );
+
+                drop(meta_folder)
+            }
+
             #[fluvio_future::test]
             async fn test_update_spec_upsert() {
                 //given
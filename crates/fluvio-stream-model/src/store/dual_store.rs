@@ -471,6 +471,13 @@ mod listener {
             self.event_publisher().current_change()
         }
 
+        /// reset listener so that next sync returns all objects as sync all.
+        /// used when receiver of changes has lost track and needs a snapshot
+        #[inline]
+        pub fn reset_to_sync_all(&mut self) {
+            self.last_change = -1;
+        }
+
         pub async fn listen(&self) {
             if self.has_change() {
                 trace!("before has change: {}", self.last_change());
@@ -787,6 +794,30 @@ mod test_notify {
         assert_eq!(0, topic_store.change_listener().current_change())
     }
 
+    #[fluvio_future::test]
+    async fn test_change_listener_reset_to_sync_all() {
+        let topic_store = Arc::new(DefaultTestStore::default());
+        let _ = topic_store
+            .sync_all(vec![DefaultTest::with_spec("t1", TestSpec::default())])
+            .await;
+
+        let mut listener = topic_store.change_listener();
+        let _ = listener.sync_spec_changes().await;
+        assert!(!listener.has_change());
+
+        let topic = DefaultTest::with_spec("t2", TestSpec::default());
+        let _ = topic_store.apply_changes(vec![LSUpdate::Mod(topic)]).await;
+        let changes = listener.sync_spec_changes().await;
+        assert!(!changes.is_sync_all());
+
+        listener.reset_to_sync_all();
+        assert!(listener.has_change());
+        let changes = listener.sync_spec_changes().await;
+        assert!(changes.is_sync_all());
+        assert_eq!(changes.parts().0.len(), 2);
+        assert!(!listener.has_change());
+    }
+
     #[fluvio_future::test]
     async fn test_change_listener() {
         let topic_store = Arc::new(DefaultTestStore::default());
@@ -208,6 +208,7 @@ impl TestDriver {
         let storage = TopicStorageConfig {
             segment_size: Some(option.topic_segment_size),
             max_partition_size: Some(option.topic_max_partition_size),
+            ..Default::default()
         };
         topic_spec.set_storage(storage);
 
@@ -93,7 +93,9 @@ pub async fn election(mut test_driver: TestDriver, mut test_case: TestCase) {
 
     // start previous follower
     println!("starting leader again: {}", &leader);
-    let leader_spu = cluster_manager.create_spu_absolute(leader as u16);
+    let leader_spu = cluster_manager
+        .create_spu_absolute(leader as u16)
+        .expect("spu");
     leader_spu.start().expect("start");
 
     // wait until prev leader has caught up
@@ -132,6 +132,8 @@ async fn start_spu(test_driver: &TestDriver, leader: i32) {
         .env_driver()
         .create_cluster_manager();
     println!("starting spu again: {}", &leader);
-    let leader_spu = cluster_manager.create_spu_absolute(leader as u16);
+    let leader_spu = cluster_manager
+        .create_spu_absolute(leader as u16)
+        .expect("spu");
     leader_spu.start().expect("start");
 }
@@ -0,0 +1,65 @@
+//!
+//! # Canary
+//!
+//! SC can probe each SPU by producing and consuming tiny records on a system topic
+//! led by that SPU. Each record carries outcome of previous probe, so clients can
+//! read canary health from last record of canary topics.
+//!
+
+use serde::{Serialize, Deserialize};
+
+use crate::SpuId;
+
+pub const CANARY_TOPIC_PREFIX: &str = "fluvio-canary-";
+
+/// name of canary topic of SPU
+pub fn canary_topic(spu: SpuId) -> String {
+    format!("{CANARY_TOPIC_PREFIX}{spu}")
+}
+
+/// SPU probed by canary topic
+pub fn canary_spu(topic: &str) -> Option<SpuId> {
+    topic.strip_prefix(CANARY_TOPIC_PREFIX)?.parse().ok()
+}
+
+/// Value of canary record
+#[derive(Clone, Debug, Default, Deserialize, Eq, Serialize, PartialEq)]
+pub struct CanaryRecord {
+    /// milliseconds since epoch when probe started
+    pub sent_ms: i64,
+    /// milliseconds between probes
+    pub interval_ms: u64,
+    /// end-to-end latency of previous probe, none if it failed
+    pub previous_latency_ms: Option<u64>,
+    /// probes failed since last success
+    pub failures: u64,
+}
+
+impl CanaryRecord {
+    /// last record is stale if probes stopped succeeding
+    pub fn is_stale(&self, now_ms: i64) -> bool {
+        now_ms - self.sent_ms > 3 * self.interval_ms as i64
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_canary_topic() {
+        assert_eq!(canary_topic(5001), "fluvio-canary-5001");
+        assert_eq!(canary_spu("fluvio-canary-5001"), Some(5001));
+        assert_eq!(canary_spu("fluvio-canary-x"), None);
+        assert_eq!(canary_spu("topic"), None);
+
+        let record = CanaryRecord {
+            sent_ms: 10_000,
+            interval_ms: 1_000,
+            ..Default::default()
+        };
+        assert!(!record.is_stale(12_000));
+        assert!(record.is_stale(14_000));
+    }
+}
@@ -1,8 +1,8 @@
 use std::fmt::Debug;
 use std::io::Error as IoError;
 use std::io::Write;
-use std::path::Path;
-use std::fs::{File, read_to_string};
+use std::path::{Path, PathBuf};
+use std::fs::{File, Permissions, metadata, read_to_string, rename, remove_file};
 
 use tracing::debug;
 use serde::{Serialize, de::DeserializeOwned};
@@ -30,16 +30,25 @@ impl<S> SaveLoadConfig for S
 where
     S: Serialize + DeserializeOwned + Debug,
 {
-    // save to file
+    // save to file, written to temporary file first and renamed
+    // so readers never see partially written config.
+    // Symlink is kept and its target is replaced, with same permissions
     fn save_to<T: AsRef<Path>>(&self, path: T) -> Result<(), IoError> {
         let path_ref = path.as_ref();
         debug!("saving config: {:#?} to: {:#?}", self, path_ref);
         let toml = toml::to_string(self).map_err(|err| IoError::other(format!("{err}")))?;
 
-        let mut file = File::create(path_ref)?;
-        file.write_all(toml.as_bytes())?;
-        // On windows flush() is noop, but sync_all() calls FlushFileBuffers.
-        file.sync_all()
+        let target = path_ref
+            .canonicalize()
+            .unwrap_or_else(|_| path_ref.to_owned());
+        let permissions = metadata(&target).ok().map(|meta| meta.permissions());
+        let temp_path = temp_path(&target);
+        let result = write_synced(&temp_path, toml.as_bytes(), permissions)
+            .and_then(|_| rename(&temp_path, &target));
+        if result.is_err() {
+            let _ = remove_file(&temp_path);
+        }
+        result
     }
 
     fn load_from<T: AsRef<Path>>(path: T) -> Result<Self, LoadConfigError> {
@@ -60,3 +69,27 @@ where
         Ok(config)
     }
 }
+
+/// temporary file in same directory, so rename doesn't cross file systems
+fn temp_path(path: &Path) -> PathBuf {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{file_name}.{}.tmp", std::process::id()))
+}
+
+fn write_synced(
+    path: &Path,
+    content: &[u8],
+    permissions: Option<Permissions>,
+) -> Result<(), IoError> {
+    let mut file = File::create(path)?;
+    file.write_all(content)?;
+    // On windows flush() is noop, but sync_all() calls FlushFileBuffers.
+    file.sync_all()?;
+    if let Some(permissions) = permissions {
+        file.set_permissions(permissions)?;
+    }
+    Ok(())
+}
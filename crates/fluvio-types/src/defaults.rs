@@ -20,6 +20,7 @@ pub const SC_DEFAULT_ID: i32 = 1;
 pub const SC_CONFIG_FILE: &str = "sc_server";
 pub const SC_PUBLIC_PORT: u16 = 9003;
 pub const SC_PRIVATE_PORT: u16 = 9004;
+pub const SC_HEALTH_PORT: u16 = 9008;
 pub const SC_HOSTNAME: &str = "localhost";
 pub const SC_RECONCILIATION_INTERVAL_SEC: u64 = 60; // 5 min
 
@@ -29,6 +30,7 @@ pub const SPU_DEFAULT_NAME: &str = "spu";
 pub const SPU_CONFIG_FILE: &str = "spu_server";
 pub const SPU_PUBLIC_PORT: u16 = 9005;
 pub const SPU_PRIVATE_PORT: u16 = 9006;
+pub const SPU_HEALTH_PORT: u16 = 9009;
 pub const SPU_PUBLIC_HOSTNAME: &str = "0.0.0.0";
 pub const SPU_PRIVATE_HOSTNAME: &str = "0.0.0.0";
 pub const SPU_CREDENTIALS_FILE: &str = "/etc/fluvio/.credentials/token_secret";
@@ -56,9 +58,12 @@ pub const STORAGE_MAX_REQUEST_SIZE: u32 = 33_554_432;
 
 pub const SPU_SMARTENGINE_STORE_MAX_BYTES: usize = 1_073_741_824; //1Gb
 pub const SPU_PEER_MAX_BYTES: u32 = 10_485_760; //10mb
+pub const SPU_READ_CACHE_MAX_BYTES: u64 = 268_435_456; //256mb
 
 pub const CONSUMER_STORAGE_TOPIC: &str = "consumer-offset";
 pub const CONSUMER_REPLICA_KEY: (&str, u32) = (CONSUMER_STORAGE_TOPIC, 0);
+/// consumer offsets not updated for this long are removed, 0 keeps them forever
+pub const CONSUMER_OFFSET_TTL_SECONDS: u64 = 7 * 24 * 3600;
 
 // Reconnect Backoff
 pub const RECONNECT_BACKOFF_FACTOR: f64 = 1.1;
@@ -9,6 +9,8 @@ use anyhow::{Result, anyhow};
 use fluvio_sc_schema::objects::ObjectApiUpdateRequest;
 use fluvio_sc_schema::objects::UpdateRequest;
 use fluvio_sc_schema::UpdatableAdminSpec;
+use fluvio_sc_schema::topic::{TopicUsageRequest, TopicUsageSample};
+use fluvio_sc_schema::ip_filter::{IpFilterRequest, IpFilterRules};
 use fluvio_protocol::{Decoder, Encoder};
 use fluvio_protocol::api::{Request, RequestMessage};
 use fluvio_future::net::DomainConnector;
@@ -119,8 +121,10 @@ impl FluvioAdmin {
     #[instrument(skip(config))]
     pub async fn connect_with_config(config: &FluvioClusterConfig) -> Result<Self> {
         let connector = DomainConnector::try_from(config.tls.clone())?;
-        let client_config =
+        let mut client_config =
             ClientConfig::new(&config.endpoint, connector, config.use_spu_local_address);
+        client_config.set_socket_tuning((&config.socket).into());
+        client_config.set_auth_token_source(config.auth_token_source());
         let inner_client = client_config.connect().await?;
         debug!(addr = %inner_client.config().addr(), "connected to cluster");
 
@@ -270,6 +274,47 @@ impl FluvioAdmin {
         Ok(())
     }
 
+    /// size history of topic sampled by SC, oldest first
+    #[instrument(skip(self))]
+    pub async fn topic_usage(&self, topic: &str) -> Result<Vec<TopicUsageSample>> {
+        if self.socket.lookup_version::<TopicUsageRequest>().is_none() {
+            return Err(anyhow!("topic usage is not supported by cluster"));
+        }
+        let response = self
+            .send_receive_admin::<TopicUsageRequest, _>(TopicUsageRequest::new(topic))
+            .await?;
+        if response.error_code.is_error() {
+            return Err(response.error_code.into());
+        }
+        Ok(response.samples)
+    }
+
+    /// networks accepted by SC public service
+    #[instrument(skip(self))]
+    pub async fn ip_filter(&self) -> Result<IpFilterRules> {
+        self.send_ip_filter(IpFilterRequest::get()).await
+    }
+
+    /// replace networks accepted by SC public service, returns rules in effect.
+    /// Existing connections are not affected.
+    #[instrument(skip(self))]
+    pub async fn set_ip_filter(&self, rules: IpFilterRules) -> Result<IpFilterRules> {
+        self.send_ip_filter(IpFilterRequest::set(rules)).await
+    }
+
+    async fn send_ip_filter(&self, request: IpFilterRequest) -> Result<IpFilterRules> {
+        if self.socket.lookup_version::<IpFilterRequest>().is_none() {
+            return Err(anyhow!("ip filter is not supported by cluster"));
+        }
+        let response = self
+            .send_receive_admin::<IpFilterRequest, _>(request)
+            .await?;
+        if response.error_code.is_error() {
+            return Err(response.error_code.into());
+        }
+        Ok(response.rules)
+    }
+
     /// return all instance of this spec
     #[instrument(skip(self))]
     pub async fn all<S>(&self) -> Result<Vec<Metadata<S>>>
@@ -0,0 +1,105 @@
+//!
+//! # Capabilities
+//!
+//! Optional features reported by SC and SPUs at connect time
+//!
+
+use std::fmt;
+
+use fluvio_compression::Compression;
+use fluvio_socket::Versions;
+
+use crate::FluvioError;
+
+/// Optional feature which a server may not support
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Capability {
+    Compression(Compression),
+    SmartModules,
+    Mirroring,
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Compression(compression) => write!(f, "{compression} compression"),
+            Self::SmartModules => write!(f, "SmartModules"),
+            Self::Mirroring => write!(f, "mirroring"),
+        }
+    }
+}
+
+/// Check that server supports capability.
+///
+/// Servers which don't report capabilities are assumed to support it,
+/// features are gated by their API versions instead.
+pub fn check_capability(versions: &Versions, capability: Capability) -> Result<(), FluvioError> {
+    let Some(capabilities) = versions.capabilities() else {
+        return Ok(());
+    };
+    let supported = match capability {
+        Capability::Compression(Compression::None) => true,
+        Capability::Compression(compression) => {
+            let name = compression.to_string();
+            capabilities.compression.contains(&name)
+        }
+        Capability::SmartModules => capabilities.smartmodule_version > 0,
+        Capability::Mirroring => capabilities.mirroring,
+    };
+    if supported {
+        Ok(())
+    } else {
+        Err(FluvioError::Unsupported(capability))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use fluvio_protocol::link::versions::{
+        ApiVersionsResponse, Capabilities, PlatformVersion, ServerCapabilities,
+    };
+
+    use super::*;
+
+    fn versions(capabilities: Option<Capabilities>) -> Versions {
+        Versions::new(ApiVersionsResponse {
+            platform_version: PlatformVersion::from(semver::Version::new(0, 14, 0)),
+            capabilities: capabilities
+                .map(ServerCapabilities::new)
+                .unwrap_or_default(),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_check_capability() {
+        let spu = versions(Some(Capabilities {
+            compression: vec!["none".to_owned()],
+            smartmodule_version: 0,
+            mirroring: true,
+        }));
+        assert!(check_capability(&spu, Capability::Compression(Compression::None)).is_ok());
+        assert!(check_capability(&spu, Capability::Mirroring).is_ok());
+        let err = check_capability(&spu, Capability::SmartModules).unwrap_err();
+        assert_eq!(err.to_string(), "server does not support SmartModules");
+
+        // older servers are gated by api versions
+        let old = versions(None);
+        assert!(check_capability(&old, Capability::SmartModules).is_ok());
+        assert!(check_capability(&old, Capability::Mirroring).is_ok());
+    }
+
+    #[cfg(feature = "compress")]
+    #[test]
+    fn test_check_compression_capability() {
+        let spu = versions(Some(Capabilities {
+            compression: vec!["none".to_owned(), "gzip".to_owned()],
+            smartmodule_version: 22,
+            mirroring: true,
+        }));
+        assert!(check_capability(&spu, Capability::Compression(Compression::Gzip)).is_ok());
+        let err = check_capability(&spu, Capability::Compression(Compression::Zstd)).unwrap_err();
+        assert_eq!(err.to_string(), "server does not support zstd compression");
+    }
+}
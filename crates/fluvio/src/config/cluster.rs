@@ -3,13 +3,25 @@
 //!
 //! Stores configuration parameter retrieved from the default or custom profile file.
 //!
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+
 use serde::{Serialize, Deserialize};
 use toml::Table as Metadata;
+use tracing::warn;
+
+use fluvio_socket::{AuthTokenSource, SocketTuning};
 
 use crate::{config::TlsPolicy, FluvioError};
 
 use super::ConfigFile;
 
+/// environment variable with bearer token, takes precedence over token of profile
+pub const AUTH_TOKEN_ENV: &str = "FLUVIO_AUTH_TOKEN";
+/// environment variable with path of bearer token file, takes precedence over file of profile
+pub const AUTH_TOKEN_FILE_ENV: &str = "FLUVIO_AUTH_TOKEN_FILE";
+
 //NOTE: this is to avoid breaking changes as we rename it to FluvioClusterConfig
 /// Fluvio client configuration
 pub type FluvioConfig = FluvioClusterConfig;
@@ -34,14 +46,27 @@ pub struct FluvioClusterConfig {
     #[serde(default)]
     pub tls: TlsPolicy,
 
+    /// TCP options for connections to the cluster
+    #[serde(default, skip_serializing_if = "SocketConfig::is_default")]
+    pub socket: SocketConfig,
+
     /// Cluster custom metadata
     #[serde(default = "Metadata::new", skip_serializing_if = "Metadata::is_empty")]
     metadata: Metadata,
 
-    /// This is not part of profile and doesn't persist.
-    /// It is purely to override client id when creating ClientConfig
-    #[serde(skip)]
+    /// Client id sent in request headers, identifies this client in
+    /// SC and SPU logs and per client SPU metrics
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub client_id: Option<String>,
+
+    /// Bearer token of OIDC provider, sent to SC of clusters authenticating with tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth_token: Option<AuthToken>,
+
+    /// File with bearer token, read again on every connection so tokens refreshed
+    /// by another process are used on reconnect. Takes precedence over `auth_token`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth_token_file: Option<PathBuf>,
 }
 
 impl FluvioClusterConfig {
@@ -66,8 +91,11 @@ impl FluvioClusterConfig {
             endpoint: addr.into(),
             use_spu_local_address: false,
             tls: TlsPolicy::Disabled,
+            socket: SocketConfig::default(),
             metadata: Metadata::new(),
             client_id: None,
+            auth_token: None,
+            auth_token_file: None,
         }
     }
 
@@ -77,6 +105,42 @@ impl FluvioClusterConfig {
         self
     }
 
+    /// Set TCP options for connections to this cluster.
+    pub fn with_socket(mut self, socket: SocketConfig) -> Self {
+        self.socket = socket;
+        self
+    }
+
+    /// Set client id sent to SC and SPUs.
+    pub fn with_client_id(mut self, client_id: impl Into<String>) -> Self {
+        self.client_id = Some(client_id.into());
+        self
+    }
+
+    /// Set bearer token sent to SC.
+    pub fn with_auth_token(mut self, token: impl Into<String>) -> Self {
+        self.auth_token = Some(AuthToken(token.into()));
+        self
+    }
+
+    /// Set file of bearer token sent to SC, read on every connection.
+    pub fn with_auth_token_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.auth_token_file = Some(path.into());
+        self
+    }
+
+    /// bearer token of environment, or of this cluster
+    pub fn resolved_auth_token(&self) -> Option<String> {
+        resolve_auth_token(self.auth_token_file.as_ref(), self.auth_token.as_ref())
+    }
+
+    /// source resolving bearer token again on every connection
+    pub fn auth_token_source(&self) -> AuthTokenSource {
+        let file = self.auth_token_file.clone();
+        let token = self.auth_token.clone();
+        Arc::new(move || resolve_auth_token(file.as_ref(), token.as_ref()))
+    }
+
     pub fn query_metadata_by_name<'de, T>(&self, name: &str) -> Option<T>
     where
         T: Deserialize<'de>,
@@ -109,15 +173,77 @@ impl FluvioClusterConfig {
     }
 }
 
+/// token of environment, then of token file, then of profile
+fn resolve_auth_token(file: Option<&PathBuf>, token: Option<&AuthToken>) -> Option<String> {
+    let non_empty = |token: String| Some(token.trim().to_owned()).filter(|token| !token.is_empty());
+    if let Some(token) = std::env::var(AUTH_TOKEN_ENV).ok().and_then(non_empty) {
+        return Some(token);
+    }
+    let file = std::env::var_os(AUTH_TOKEN_FILE_ENV)
+        .map(PathBuf::from)
+        .or_else(|| file.cloned());
+    if let Some(path) = file {
+        match std::fs::read_to_string(&path) {
+            Ok(token) => return non_empty(token),
+            Err(err) => warn!(path = %path.display(), %err, "unable to read auth token file"),
+        }
+    }
+    token.map(|token| token.0.clone())
+}
+
+/// Bearer token, never printed
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct AuthToken(String);
+
+impl fmt::Debug for AuthToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("AuthToken(***)")
+    }
+}
+
+/// TCP socket options.
+/// Unset options keep operating system defaults.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SocketConfig {
+    /// disable Nagle's algorithm, reduces latency of small requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nodelay: Option<bool>,
+
+    /// send buffer size in bytes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub send_buffer_size: Option<usize>,
+
+    /// receive buffer size in bytes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recv_buffer_size: Option<usize>,
+}
+
+impl SocketConfig {
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+impl From<&SocketConfig> for SocketTuning {
+    fn from(config: &SocketConfig) -> Self {
+        Self {
+            nodelay: config.nodelay,
+            send_buffer_size: config.send_buffer_size,
+            recv_buffer_size: config.recv_buffer_size,
+        }
+    }
+}
+
 impl TryFrom<FluvioClusterConfig> for fluvio_socket::ClientConfig {
     type Error = anyhow::Error;
     fn try_from(config: FluvioClusterConfig) -> Result<Self, Self::Error> {
         let connector = fluvio_future::net::DomainConnector::try_from(config.tls.clone())?;
-        Ok(Self::new(
-            &config.endpoint,
-            connector,
-            config.use_spu_local_address,
-        ))
+        let mut client_config =
+            Self::new(&config.endpoint, connector, config.use_spu_local_address);
+        client_config.set_socket_tuning((&config.socket).into());
+        client_config.set_auth_token_source(config.auth_token_source());
+        Ok(client_config)
     }
 }
 
@@ -126,7 +252,7 @@ mod test_metadata {
     use fluvio_types::config_file::SaveLoadConfig;
 
     use serde::{Deserialize, Serialize};
-    use crate::config::{Config, ConfigFile};
+    use crate::config::{Config, ConfigFile, FluvioClusterConfig};
 
     #[test]
     fn test_get_metadata_path() {
@@ -158,6 +284,65 @@ name = "foo"
         );
     }
 
+    #[test]
+    fn test_client_id_in_profile() {
+        let toml = r#"version = "2"
+[profile.local]
+cluster = "local"
+
+[cluster.local]
+endpoint = "127.0.0.1:9003"
+client_id = "billing-service"
+"#;
+        let profile = Config::load_str(toml).unwrap();
+        let config = profile.cluster("local").unwrap();
+        assert_eq!(config.client_id.as_deref(), Some("billing-service"));
+
+        let config = config.clone().with_client_id("reporting");
+        let serialized = toml::to_string(&config).expect("serialize");
+        assert!(serialized.contains("client_id = \"reporting\""));
+    }
+
+    #[test]
+    fn test_auth_token_in_profile() {
+        let toml = r#"version = "2"
+[profile.local]
+cluster = "local"
+
+[cluster.local]
+endpoint = "127.0.0.1:9003"
+auth_token = "eyJhbGciOiJSUzI1NiJ9.e30.c2ln"
+"#;
+        let profile = Config::load_str(toml).unwrap();
+        let config = profile.cluster("local").unwrap();
+        assert_eq!(
+            config.auth_token.as_ref().map(|token| token.0.as_str()),
+            Some("eyJhbGciOiJSUzI1NiJ9.e30.c2ln")
+        );
+        assert!(!format!("{config:?}").contains("eyJ"));
+
+        let serialized = toml::to_string(config).expect("serialize");
+        assert!(serialized.contains("auth_token = \"eyJhbGciOiJSUzI1NiJ9.e30.c2ln\""));
+    }
+
+    #[test]
+    fn test_auth_token_file_read_on_every_connection() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("token");
+        std::fs::write(&path, "first\n").expect("write");
+        let config = FluvioClusterConfig::new("127.0.0.1:9003")
+            .with_auth_token("profile")
+            .with_auth_token_file(&path);
+
+        let source = config.auth_token_source();
+        assert_eq!(source().as_deref(), Some("first"));
+        std::fs::write(&path, "refreshed").expect("write");
+        assert_eq!(source().as_deref(), Some("refreshed"));
+
+        std::fs::remove_file(&path).expect("remove");
+        assert_eq!(source().as_deref(), Some("profile"));
+    }
+
     #[test]
     fn test_create_metadata() {
         let toml = r#"version = "2"
@@ -363,3 +548,38 @@ type = "local"
             .expect("teardown: failed to set installation type back to local");
     }
 }
+
+#[cfg(test)]
+mod test_socket {
+    use fluvio_socket::SocketTuning;
+    use fluvio_types::config_file::SaveLoadConfig;
+
+    use crate::config::Config;
+
+    #[test]
+    fn test_socket_config() {
+        let toml = r#"version = "2"
+[profile.local]
+cluster = "local"
+
+[cluster.local]
+endpoint = "127.0.0.1:9003"
+
+[cluster.local.socket]
+nodelay = true
+send_buffer_size = 1048576
+"#;
+        let profile = Config::load_str(toml).unwrap();
+        let config = profile.cluster("local").unwrap();
+        assert!(!config.socket.is_default());
+
+        let tuning = SocketTuning::from(&config.socket);
+        assert_eq!(tuning.nodelay, Some(true));
+        assert_eq!(tuning.send_buffer_size, Some(1048576));
+        assert_eq!(tuning.recv_buffer_size, None);
+
+        let serialized = toml::to_string(config).expect("serialize");
+        assert!(serialized.contains("nodelay = true"));
+        assert!(!serialized.contains("recv_buffer_size"));
+    }
+}
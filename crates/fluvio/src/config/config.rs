@@ -8,7 +8,9 @@ use std::io::Error as IoError;
 use std::io::ErrorKind;
 use std::path::{Path, PathBuf};
 use std::collections::HashMap;
-use std::fs::create_dir_all;
+use std::fs::{File, OpenOptions, TryLockError, create_dir_all};
+use std::io::{Read, Seek, Write};
+use std::time::{Duration, Instant};
 
 use fluvio_types::config_file::LoadConfigError;
 use thiserror::Error;
@@ -51,25 +53,97 @@ pub enum ConfigError {
     NoActiveProfile,
     #[error("No cluster config for profile {profile}")]
     NoClusterForProfile { profile: String },
+    #[error(
+        "config file {path} is locked by another fluvio process{}, stop it if it's not responding",
+        .holder.map(|pid| format!(" (pid {pid})")).unwrap_or_default()
+    )]
+    ConfigLocked { path: String, holder: Option<u32> },
+}
+
+/// time to wait for other CLI invocations to finish saving config
+const CONFIG_LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+const CONFIG_LOCK_MAX_BACKOFF: Duration = Duration::from_millis(200);
+
+/// Advisory lock of config file, released when dropped.
+/// Lock is held by OS on separate lock file, so it's released if process dies.
+#[derive(Debug)]
+struct ConfigLock {
+    _file: File,
+}
+
+impl ConfigLock {
+    /// next to target of symlink, so all links to config share lock
+    fn lock_path(config_path: &Path) -> PathBuf {
+        config_path
+            .canonicalize()
+            .unwrap_or_else(|_| config_path.to_owned())
+            .with_extension("lock")
+    }
+
+    /// wait for lock with exponential backoff until timeout
+    fn acquire(config_path: &Path, timeout: Duration) -> Result<Self, ConfigError> {
+        let lock_path = Self::lock_path(config_path);
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&lock_path)
+            .map_err(|e| config_file_error(&format!("lock {lock_path:?}"), e))?;
+
+        let started = Instant::now();
+        let mut backoff = Duration::from_millis(10);
+        loop {
+            match file.try_lock() {
+                Ok(()) => break,
+                Err(TryLockError::WouldBlock) if started.elapsed() < timeout => {
+                    debug!(?backoff, "config file is locked, retrying");
+                    std::thread::sleep(backoff);
+                    backoff = (backoff * 2).min(CONFIG_LOCK_MAX_BACKOFF);
+                }
+                Err(TryLockError::WouldBlock) => {
+                    let mut holder = String::new();
+                    let _ = file.read_to_string(&mut holder);
+                    return Err(ConfigError::ConfigLocked {
+                        path: config_path.display().to_string(),
+                        holder: holder.trim().parse().ok(),
+                    });
+                }
+                Err(TryLockError::Error(e)) => {
+                    return Err(config_file_error(&format!("lock {lock_path:?}"), e));
+                }
+            }
+        }
+
+        // record holder for error reported to other processes
+        let _ = file
+            .set_len(0)
+            .and_then(|_| file.rewind())
+            .and_then(|_| write!(file, "{}", std::process::id()));
+        Ok(Self { _file: file })
+    }
 }
 
 pub struct ConfigFile {
     path: PathBuf,
     config: Config,
+    /// held from load until drop, when loaded for update
+    lock: Option<ConfigLock>,
 }
 
 impl ConfigFile {
     fn new(path: PathBuf, config: Config) -> Self {
-        Self { path, config }
+        Self {
+            path,
+            config,
+            lock: None,
+        }
     }
 
     /// create default profile
     pub fn default_config() -> Result<Self, IoError> {
         let path = Self::default_file_path()?;
-        Ok(Self {
-            path,
-            config: Config::new(),
-        })
+        Ok(Self::new(path, Config::new()))
     }
 
     /// load from default location if not found, create new one
@@ -86,10 +160,46 @@ impl ConfigFile {
 
     /// try to load from default locations
     pub fn load(optional_path: Option<String>) -> Result<Self, FluvioError> {
-        Self::from_file(match optional_path {
-            Some(p) => PathBuf::from(p),
-            None => Self::default_file_path().map_err(|e| config_file_error("default path", e))?,
-        })
+        Self::from_file(Self::resolve_path(optional_path)?)
+    }
+
+    /// Load for read-modify-write. Config file is locked before it's read and until
+    /// it's dropped, so updates of other processes are not lost
+    pub fn load_for_update(optional_path: Option<String>) -> Result<Self, FluvioError> {
+        let path = Self::resolve_path(optional_path)?;
+        let lock = Self::lock(&path)?;
+        let mut config_file = Self::from_file(path)?;
+        config_file.lock = Some(lock);
+        Ok(config_file)
+    }
+
+    /// same as [`Self::load_default_or_new`], locked as [`Self::load_for_update`]
+    pub fn load_default_or_new_for_update() -> Result<Self, FluvioError> {
+        let path = Self::resolve_path(None)?;
+        let lock = Self::lock(&path)?;
+        let mut config_file = Self::from_file(&path).unwrap_or_else(|err| {
+            debug!("profile can't be loaded, creating new one: {}", err);
+            Self::new(path, Config::new())
+        });
+        config_file.lock = Some(lock);
+        Ok(config_file)
+    }
+
+    fn resolve_path(optional_path: Option<String>) -> Result<PathBuf, FluvioError> {
+        match optional_path {
+            Some(p) => Ok(PathBuf::from(p)),
+            None => {
+                Ok(Self::default_file_path().map_err(|e| config_file_error("default path", e))?)
+            }
+        }
+    }
+
+    fn lock(path: &Path) -> Result<ConfigLock, FluvioError> {
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent)
+                .map_err(|e| config_file_error(&format!("parent {path:?}"), e))?;
+        }
+        Ok(ConfigLock::acquire(path, CONFIG_LOCK_TIMEOUT)?)
     }
 
     /// read from file
@@ -139,10 +249,14 @@ impl ConfigFile {
         &mut self.config
     }
 
-    /// Save to file
+    /// Save to file.
+    /// Concurrent saves are serialized by lock file, and config is replaced atomically.
+    /// Use [`Self::load_for_update`] to hold lock since config was read.
     pub fn save(&self) -> Result<(), FluvioError> {
-        create_dir_all(self.path.parent().unwrap())
-            .map_err(|e| config_file_error(&format!("parent {:?}", self.path), e))?;
+        let _lock = match self.lock {
+            Some(_) => None,
+            None => Some(Self::lock(&self.path)?),
+        };
         self.config
             .save_to(&self.path)
             .map_err(|e| config_file_error(&format!("{:?}", &self.path), e))?;
@@ -569,6 +683,101 @@ pub mod test {
         );
     }
 
+    #[test]
+    fn test_config_lock() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let dir = temp.path();
+        let path = dir.join("config");
+        let config_file = ConfigFile::new(
+            path.clone(),
+            Config::new_with_local_cluster("localhost:9003".to_owned()),
+        );
+
+        let lock = ConfigLock::acquire(&path, Duration::ZERO).expect("lock");
+        let err = ConfigLock::acquire(&path, Duration::from_millis(50)).unwrap_err();
+        assert!(matches!(
+            err,
+            ConfigError::ConfigLocked { holder: Some(pid), .. } if pid == std::process::id()
+        ));
+
+        drop(lock);
+        config_file.save().expect("save after lock is released");
+        let saved = ConfigFile::load(Some(path.to_string_lossy().to_string())).expect("load");
+        assert_eq!(saved.config().current_profile_name(), Some(LOCAL_PROFILE));
+        // no temporary files left
+        assert_eq!(
+            std::fs::read_dir(dir)
+                .expect("read dir")
+                .filter(|entry| entry
+                    .as_ref()
+                    .is_ok_and(|entry| entry.file_name().to_string_lossy().ends_with(".tmp")))
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_config_update_holds_lock() {
+        let temp = tempfile::tempdir().expect("temp dir");
+        let path = temp.path().join("config").to_string_lossy().to_string();
+        ConfigFile::new(
+            PathBuf::from(&path),
+            Config::new_with_local_cluster("localhost:9003".to_owned()),
+        )
+        .save()
+        .expect("save");
+
+        let mut first = ConfigFile::load_for_update(Some(path.clone())).expect("load");
+        // other update waits for first one, instead of overwriting it
+        let second = std::thread::spawn({
+            let path = path.clone();
+            move || {
+                let mut second = ConfigFile::load_for_update(Some(path)).expect("load");
+                second
+                    .add_or_replace_profile("second", "localhost:9004", &TlsPolicy::Disabled)
+                    .expect("second");
+            }
+        });
+        std::thread::sleep(Duration::from_millis(50));
+        first
+            .add_or_replace_profile("first", "localhost:9005", &TlsPolicy::Disabled)
+            .expect("first");
+        drop(first);
+        second.join().expect("second update");
+
+        let saved = ConfigFile::load(Some(path)).expect("load");
+        assert!(saved.config().profile.contains_key("first"));
+        assert!(saved.config().profile.contains_key("second"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_save_keeps_link_and_mode() {
+        use std::os::unix::fs::{PermissionsExt, symlink};
+
+        let temp = tempfile::tempdir().expect("temp dir");
+        let target = temp.path().join("config.toml");
+        let link = temp.path().join("config");
+        let config = Config::new_with_local_cluster("localhost:9003".to_owned());
+        config.save_to(&target).expect("save");
+        std::fs::set_permissions(&target, std::fs::Permissions::from_mode(0o600)).expect("mode");
+        symlink(&target, &link).expect("link");
+
+        ConfigFile::new(link.clone(), config).save().expect("save");
+
+        assert!(
+            std::fs::symlink_metadata(&link)
+                .expect("link")
+                .file_type()
+                .is_symlink()
+        );
+        let mode = std::fs::metadata(&target)
+            .expect("target")
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
     #[test]
     fn test_local_cluster() {
         let config = Config::new_with_local_cluster("localhost:9003".to_owned());
@@ -2,7 +2,11 @@
 mod config;
 mod tls;
 mod cluster;
+#[cfg(target_arch = "wasm32")]
+mod websocket;
 
 pub use config::*;
 pub use tls::*;
 pub use cluster::*;
+#[cfg(target_arch = "wasm32")]
+pub use websocket::*;
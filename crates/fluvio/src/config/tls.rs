@@ -5,7 +5,9 @@ use std::fmt::{Debug, self};
 use anyhow::{Result, Context};
 use tracing::info;
 use serde::{Deserialize, Serialize};
-use fluvio_future::net::{DomainConnector, DefaultDomainConnector};
+use fluvio_future::net::DomainConnector;
+#[cfg(not(target_arch = "wasm32"))]
+use fluvio_future::net::DefaultDomainConnector;
 
 /// Describes whether or not to use TLS and how
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
@@ -162,9 +164,19 @@ cfg_if::cfg_if! {
         impl TryFrom<TlsPolicy> for DomainConnector {
             type Error = anyhow::Error;
 
-            fn try_from(_config: TlsPolicy) -> Result<Self, Self::Error> {
-                info!("Using Default Domain connector for wasm");
-                Ok(Box::new(DefaultDomainConnector::new()))
+            fn try_from(config: TlsPolicy) -> Result<Self, Self::Error> {
+                use crate::config::WebSocketConnector;
+
+                match config {
+                    TlsPolicy::Disabled => {
+                        info!("Using WebSocket connector for wasm");
+                        Ok(Box::new(WebSocketConnector::new()))
+                    }
+                    _ => {
+                        info!("Using secure WebSocket connector for wasm, certificates are verified by browser");
+                        Ok(Box::new(WebSocketConnector::secure()))
+                    }
+                }
             }
         }
 
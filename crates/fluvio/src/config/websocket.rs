@@ -0,0 +1,124 @@
+//!
+//! # WebSocket transport
+//!
+//! Browsers can't open TCP connections, so in wasm SC and SPUs are reached through
+//! a gateway which forwards binary WebSocket frames to their TCP endpoints.
+//!
+
+use std::collections::HashMap;
+use std::io::Error as IoError;
+
+use async_trait::async_trait;
+use tracing::debug;
+use fluvio_ws_stream_wasm::WsMeta;
+use fluvio_future::net::{
+    BoxReadConnection, BoxWriteConnection, ConnectionFd, DomainConnector, TcpDomainConnector,
+};
+
+/// Connect to cluster over WebSocket.
+///
+/// Endpoints without scheme, such as SPU addresses from metadata, are connected
+/// with `ws://` or `wss://` unless mapped to gateway url with [`Self::with_endpoint`].
+#[derive(Debug, Clone, Default)]
+pub struct WebSocketConnector {
+    secure: bool,
+    domain: String,
+    endpoints: HashMap<String, String>,
+}
+
+impl WebSocketConnector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// use `wss://` for endpoints without scheme, TLS is handled by browser
+    pub fn secure() -> Self {
+        Self {
+            secure: true,
+            ..Default::default()
+        }
+    }
+
+    /// connect to `url` instead of `addr`, for gateways exposing SPUs at other urls
+    pub fn with_endpoint(mut self, addr: impl Into<String>, url: impl Into<String>) -> Self {
+        self.endpoints.insert(addr.into(), url.into());
+        self
+    }
+
+    fn url(&self, addr: &str) -> String {
+        if let Some(url) = self.endpoints.get(addr) {
+            return url.clone();
+        }
+        websocket_url(addr, self.secure)
+    }
+}
+
+#[async_trait(?Send)]
+impl TcpDomainConnector for WebSocketConnector {
+    async fn connect(
+        &self,
+        addr: &str,
+    ) -> Result<(BoxWriteConnection, BoxReadConnection, ConnectionFd), IoError> {
+        let url = self.url(addr);
+        debug!(addr, %url, "connecting websocket");
+        let (_ws, wsstream) = WsMeta::connect(url.as_str(), None)
+            .await
+            .map_err(IoError::other)?;
+        let wsstream_clone = wsstream.clone();
+        Ok((
+            Box::new(wsstream.into_io()),
+            Box::new(wsstream_clone.into_io()),
+            url,
+        ))
+    }
+
+    fn new_domain(&self, domain: String) -> DomainConnector {
+        Box::new(Self {
+            domain,
+            ..self.clone()
+        })
+    }
+
+    fn domain(&self) -> &str {
+        &self.domain
+    }
+}
+
+fn websocket_url(addr: &str, secure: bool) -> String {
+    if addr.starts_with("ws://") || addr.starts_with("wss://") {
+        addr.to_owned()
+    } else if secure {
+        format!("wss://{addr}")
+    } else {
+        format!("ws://{addr}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+
+    #[wasm_bindgen_test]
+    fn test_websocket_url() {
+        assert_eq!(
+            websocket_url("localhost:9010", false),
+            "ws://localhost:9010"
+        );
+        assert_eq!(
+            websocket_url("localhost:9010", true),
+            "wss://localhost:9010"
+        );
+        assert_eq!(
+            websocket_url("wss://gateway:3000", false),
+            "wss://gateway:3000"
+        );
+
+        let connector =
+            WebSocketConnector::new().with_endpoint("localhost:9010", "ws://localhost:3001");
+        assert_eq!(connector.url("localhost:9010"), "ws://localhost:3001");
+        assert_eq!(connector.url("localhost:9011"), "ws://localhost:9011");
+    }
+}
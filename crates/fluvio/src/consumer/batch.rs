@@ -0,0 +1,327 @@
+use std::collections::{HashMap, VecDeque};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_util::stream::{Stream, StreamExt};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_types::{PartitionId, Timestamp};
+
+use super::{ConsumerBoxFuture, ConsumerStream, Record};
+
+/// Records of a single batch of a partition, in order of offsets.
+///
+/// Records before the start offset of the stream, or filtered out by SmartModules,
+/// are not included.
+pub struct ConsumerBatch {
+    partition: PartitionId,
+    records: Vec<Record>,
+}
+
+impl ConsumerBatch {
+    /// The partition where this batch is stored.
+    pub fn partition(&self) -> PartitionId {
+        self.partition
+    }
+
+    /// Offset of first record
+    pub fn base_offset(&self) -> i64 {
+        self.records.first().map_or(0, |record| record.offset())
+    }
+
+    /// Offset of last record
+    pub fn last_offset(&self) -> i64 {
+        self.records.last().map_or(0, |record| record.offset())
+    }
+
+    /// Timestamp of first record of the batch as produced
+    pub fn first_timestamp(&self) -> Timestamp {
+        self.records
+            .first()
+            .map_or(0, |record| record.batch_first_timestamp())
+    }
+
+    /// Max timestamp of records
+    pub fn max_timestamp(&self) -> Timestamp {
+        self.records
+            .iter()
+            .map(|record| record.timestamp())
+            .max()
+            .unwrap_or_default()
+    }
+
+    pub fn records(&self) -> &[Record] {
+        &self.records
+    }
+
+    pub fn into_records(self) -> Vec<Record> {
+        self.records
+    }
+}
+
+impl IntoIterator for ConsumerBatch {
+    type Item = Record;
+    type IntoIter = std::vec::IntoIter<Record>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.records.into_iter()
+    }
+}
+
+/// Wraps [`ConsumerStream`] to yield whole batches instead of individual records.
+///
+/// Records of partitions are collected until end of their batch, so batches of different
+/// partitions may be returned out of order of their records in the inner stream.
+/// A batch is also complete once a record of a later batch of the partition is read, or
+/// the inner stream has to wait for records, since end of the batch may be filtered out.
+/// Offsets are committed for records read from the inner stream, which includes records of
+/// batches not yet returned.
+pub struct ConsumerBatchStream<S> {
+    inner: S,
+    pending: HashMap<PartitionId, Vec<Record>>,
+    completed: VecDeque<ConsumerBatch>,
+    terminated: bool,
+}
+
+impl<S: ConsumerStream> ConsumerBatchStream<S> {
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            pending: HashMap::new(),
+            completed: VecDeque::new(),
+            terminated: false,
+        }
+    }
+
+    /// Wait for next complete batch, None if stream is terminated
+    pub async fn next_batch(&mut self) -> Option<Result<ConsumerBatch, ErrorCode>> {
+        self.next().await
+    }
+
+    /// See [`ConsumerStream::offset_commit`]
+    pub fn offset_commit(&mut self) -> ConsumerBoxFuture<'_> {
+        self.inner.offset_commit()
+    }
+
+    /// See [`ConsumerStream::offset_flush`]
+    pub fn offset_flush(&mut self) -> ConsumerBoxFuture<'_> {
+        self.inner.offset_flush()
+    }
+
+    /// See [`ConsumerStream::offset_commit_async`]
+    pub fn offset_commit_async(&mut self) -> ConsumerBoxFuture<'_> {
+        self.inner.offset_commit_async()
+    }
+
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    /// add record to batch of its partition, completing batches it ends or follows
+    fn push(&mut self, record: Record) {
+        let partition = record.partition();
+        let batch_end = record.is_batch_end();
+        let records = self.pending.entry(partition).or_default();
+        if records
+            .last()
+            .is_some_and(|last| last.batch_last_offset() != record.batch_last_offset())
+        {
+            // end of previous batch was not received
+            let records = std::mem::take(records);
+            self.completed
+                .push_back(ConsumerBatch { partition, records });
+        }
+        records.push(record);
+        if batch_end {
+            let records = self.pending.remove(&partition).unwrap_or_default();
+            self.completed
+                .push_back(ConsumerBatch { partition, records });
+        }
+    }
+
+    /// incomplete batches are returned once inner stream waits or is terminated
+    fn take_pending(&mut self) -> Option<ConsumerBatch> {
+        let partition = *self.pending.keys().min()?;
+        let records = self.pending.remove(&partition)?;
+        Some(ConsumerBatch { partition, records })
+    }
+}
+
+impl<S: ConsumerStream> Stream for ConsumerBatchStream<S> {
+    type Item = Result<ConsumerBatch, ErrorCode>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let self_mut = self.get_mut();
+        loop {
+            if let Some(batch) = self_mut.completed.pop_front() {
+                return Poll::Ready(Some(Ok(batch)));
+            }
+            if self_mut.terminated {
+                return Poll::Ready(self_mut.take_pending().map(Ok));
+            }
+            match self_mut.inner.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(record))) => self_mut.push(record),
+                Poll::Ready(Some(Err(err))) => return Poll::Ready(Some(Err(err))),
+                Poll::Ready(None) => self_mut.terminated = true,
+                Poll::Pending => {
+                    return match self_mut.take_pending() {
+                        Some(batch) => Poll::Ready(Some(Ok(batch))),
+                        None => Poll::Pending,
+                    };
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use fluvio_protocol::record::Batch;
+    use fluvio_smartmodule::RecordData;
+
+    use crate::consumer::{MultiplePartitionConsumerStream, SinglePartitionConsumerStream};
+
+    use super::*;
+
+    fn batch_records(
+        partition: PartitionId,
+        base_offset: i64,
+        input: &[&'static str],
+    ) -> Vec<Result<Record, ErrorCode>> {
+        let mut records: Vec<_> = input
+            .iter()
+            .map(|item| fluvio_protocol::record::Record::new(RecordData::from(item.as_bytes())))
+            .collect();
+        let mut batch = Batch::default();
+        batch.add_records(&mut records);
+        batch.set_base_offset(base_offset);
+        batch
+            .into_consumer_records_iter(partition)
+            .map(Ok)
+            .collect()
+    }
+
+    fn values(batch: &ConsumerBatch) -> Vec<String> {
+        batch
+            .records()
+            .iter()
+            .map(|record| String::from_utf8_lossy(record.as_ref()).to_string())
+            .collect()
+    }
+
+    #[fluvio_future::test]
+    async fn test_next_batch_single_partition() {
+        let mut records = batch_records(0, 0, &["1", "2"]);
+        records.extend(batch_records(0, 2, &["3"]));
+        let (tx, _rx) = async_channel::unbounded();
+        let partition_stream = SinglePartitionConsumerStream::new(
+            futures_util::stream::iter(records),
+            Default::default(),
+            Default::default(),
+            Duration::from_millis(100),
+            tx,
+        );
+        let mut stream = ConsumerBatchStream::new(partition_stream);
+
+        let batch = stream.next_batch().await.expect("batch").expect("no error");
+        assert_eq!(values(&batch), ["1", "2"]);
+        assert_eq!(batch.base_offset(), 0);
+        assert_eq!(batch.last_offset(), 1);
+
+        let batch = stream.next_batch().await.expect("batch").expect("no error");
+        assert_eq!(values(&batch), ["3"]);
+        assert_eq!(batch.base_offset(), 2);
+
+        assert!(stream.next_batch().await.is_none());
+    }
+
+    #[fluvio_future::test]
+    async fn test_next_batch_multi_partition() {
+        let streams = [
+            batch_records(0, 0, &["1"]),
+            batch_records(1, 0, &["2", "4", "6"]),
+            batch_records(2, 0, &["3", "5"]),
+        ]
+        .into_iter()
+        .map(|records| {
+            let (tx, _rx) = async_channel::unbounded();
+            SinglePartitionConsumerStream::new(
+                futures_util::stream::iter(records),
+                Default::default(),
+                Default::default(),
+                Duration::from_millis(100),
+                tx,
+            )
+        });
+        let stream = ConsumerBatchStream::new(MultiplePartitionConsumerStream::new(streams));
+
+        let batches: Vec<_> = stream.map(|batch| batch.expect("no error")).collect().await;
+        let partitions: Vec<_> = batches.iter().map(|batch| batch.partition()).collect();
+        assert_eq!(partitions, [0, 2, 1]);
+        assert_eq!(values(&batches[1]), ["3", "5"]);
+        assert_eq!(values(&batches[2]), ["2", "4", "6"]);
+    }
+
+    #[fluvio_future::test]
+    async fn test_incomplete_batch_returned_at_end() {
+        let mut records = batch_records(0, 0, &["1", "2", "3"]);
+        records.truncate(2);
+        let (tx, _rx) = async_channel::unbounded();
+        let partition_stream = SinglePartitionConsumerStream::new(
+            futures_util::stream::iter(records),
+            Default::default(),
+            Default::default(),
+            Duration::from_millis(100),
+            tx,
+        );
+        let mut stream = ConsumerBatchStream::new(partition_stream);
+
+        let batch = stream.next_batch().await.expect("batch").expect("no error");
+        assert_eq!(values(&batch), ["1", "2"]);
+        assert!(stream.next_batch().await.is_none());
+    }
+
+    #[fluvio_future::test]
+    async fn test_batch_without_end_completed_by_next_batch() {
+        // last record of first batch filtered out
+        let mut records = batch_records(0, 0, &["1", "2", "3"]);
+        records.truncate(2);
+        records.extend(batch_records(0, 3, &["4", "5"]));
+        let (tx, _rx) = async_channel::unbounded();
+        let partition_stream = SinglePartitionConsumerStream::new(
+            futures_util::stream::iter(records),
+            Default::default(),
+            Default::default(),
+            Duration::from_millis(100),
+            tx,
+        );
+        let mut stream = ConsumerBatchStream::new(partition_stream);
+
+        let batch = stream.next_batch().await.expect("batch").expect("no error");
+        assert_eq!(values(&batch), ["1", "2"]);
+        let batch = stream.next_batch().await.expect("batch").expect("no error");
+        assert_eq!(values(&batch), ["4", "5"]);
+        assert_eq!(batch.base_offset(), 3);
+        assert!(stream.next_batch().await.is_none());
+    }
+
+    #[fluvio_future::test]
+    async fn test_incomplete_batch_returned_while_waiting() {
+        let mut records = batch_records(0, 0, &["1", "2", "3"]);
+        records.truncate(2);
+        let (tx, _rx) = async_channel::unbounded();
+        let partition_stream = SinglePartitionConsumerStream::new(
+            futures_util::stream::iter(records).chain(futures_util::stream::pending()),
+            Default::default(),
+            Default::default(),
+            Duration::from_millis(100),
+            tx,
+        );
+        let mut stream = ConsumerBatchStream::new(partition_stream);
+
+        let batch = stream.next_batch().await.expect("batch").expect("no error");
+        assert_eq!(values(&batch), ["1", "2"]);
+    }
+}
@@ -1,18 +1,28 @@
-use std::{str::FromStr, time::Duration};
+use std::{fmt, str::FromStr, sync::Arc, time::Duration};
 
 use anyhow::Result;
 use derive_builder::Builder;
 
-use fluvio_spu_schema::{server::smartmodule::SmartModuleInvocation, Isolation};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::topic::Transform;
+use fluvio_spu_schema::server::smartmodule::{
+    SmartModuleInvocation, SmartModuleInvocationWasm, SmartModuleKind,
+};
+use fluvio_spu_schema::Isolation;
+use fluvio_spu_schema::server::stream_fetch::{
+    BatchBounds, DEFAULT_STREAM_CREDITS, MAX_STREAM_CREDITS, RecordSampling,
+};
 use fluvio_types::PartitionId;
 
 use crate::{FluvioError, Offset};
 
 use super::MAX_FETCH_BYTES;
+use super::upcast::Upcasters;
 
 const DEFAULT_OFFSET_FLUSH_PERIOD: Duration = Duration::from_secs(10);
 const DEFAULT_OFFSET_FLUSHER_CHECK_PERIOD: Duration = Duration::from_millis(100);
 const DEFAULT_RETRY_MODE: RetryMode = RetryMode::TryUntil(100);
+const DEFAULT_SERVER_DECOMPRESS: bool = !cfg!(feature = "compress");
 
 /// Configures the behavior of consumer fetching and streaming
 #[derive(Debug, Builder, Clone)]
@@ -26,6 +36,27 @@ pub struct ConsumerConfig {
     pub isolation: Isolation,
     #[builder(default)]
     pub smartmodule: Vec<SmartModuleInvocation>,
+    /// Number of responses SPU can send before waiting for consumer to catch up
+    #[builder(default = "DEFAULT_STREAM_CREDITS")]
+    pub credits: u32,
+    /// SPU decompresses records before sending them, for consumers which can't decompress topic codec.
+    /// Enabled by default when client is built without compression support
+    #[builder(default = "DEFAULT_SERVER_DECOMPRESS")]
+    pub server_decompress: bool,
+    /// SPU sends only sampled records, to inspect busy topics without reading every record
+    #[builder(default)]
+    pub sampling: RecordSampling,
+    /// Only records within timestamp and offset bounds are received
+    #[builder(default)]
+    pub bounds: BatchBounds,
+    /// SPU reads records from segments instead of its read cache of recent batches.
+    /// Set for large scans, so they don't compete with consumers reading the tail
+    #[builder(default)]
+    pub bypass_cache: bool,
+    /// Continue after record SmartModule failed on, instead of reading it again.
+    /// Set when failed records are sent to dead letter topic
+    #[builder(default)]
+    pub skip_failed_records: bool,
 }
 
 impl ConsumerConfig {
@@ -39,10 +70,21 @@ impl ConsumerConfigBuilder {
         let config = self.build_impl().map_err(|e| {
             FluvioError::ConsumerConfig(format!("Missing required config option: {e}"))
         })?;
+        validate_credits(config.credits)?;
         Ok(config)
     }
 }
 
+fn validate_credits(credits: u32) -> Result<()> {
+    if !(1..=MAX_STREAM_CREDITS).contains(&credits) {
+        return Err(FluvioError::ConsumerConfig(format!(
+            "credits must be between 1 and {MAX_STREAM_CREDITS}, got {credits}"
+        ))
+        .into());
+    }
+    Ok(())
+}
+
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
 pub enum OffsetManagementStrategy {
     /// Offsets are not saved
@@ -70,6 +112,27 @@ impl FromStr for OffsetManagementStrategy {
     }
 }
 
+/// Receives errors of offset flushes nobody waits for: background flushes of [`OffsetManagementStrategy::Auto`],
+/// flushes on drop and flushes started by `offset_commit_async`
+#[derive(Clone)]
+pub struct OffsetCommitErrorHandler(Arc<dyn Fn(ErrorCode) + Send + Sync>);
+
+impl OffsetCommitErrorHandler {
+    pub fn new(handler: impl Fn(ErrorCode) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(handler))
+    }
+
+    pub(crate) fn handle(&self, error: ErrorCode) {
+        (self.0)(error)
+    }
+}
+
+impl fmt::Debug for OffsetCommitErrorHandler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("OffsetCommitErrorHandler")
+    }
+}
+
 #[derive(Debug, Default, Clone, PartialEq)]
 pub enum RetryMode {
     Disabled,
@@ -96,6 +159,12 @@ pub struct ConsumerConfigExt {
     pub offset_flush: Duration,
     #[builder(default = "DEFAULT_OFFSET_FLUSHER_CHECK_PERIOD")]
     pub offset_flusher_check_period: Duration,
+    /// With [`OffsetManagementStrategy::Auto`], also flush once this many offsets are committed
+    /// since the last flush, without waiting for `offset_flush` period. 0 disables
+    #[builder(default)]
+    pub offset_flush_records: usize,
+    #[builder(default, setter(strip_option))]
+    pub offset_commit_error_handler: Option<OffsetCommitErrorHandler>,
     #[builder(default)]
     pub disable_continuous: bool,
     #[builder(default = "*MAX_FETCH_BYTES")]
@@ -106,6 +175,36 @@ pub struct ConsumerConfigExt {
     pub smartmodule: Vec<SmartModuleInvocation>,
     #[builder(default = "DEFAULT_RETRY_MODE")]
     pub retry_mode: RetryMode,
+    /// Number of responses SPU can send before waiting for consumer to catch up
+    #[builder(default = "DEFAULT_STREAM_CREDITS")]
+    pub credits: u32,
+    /// SPU decompresses records before sending them, for consumers which can't decompress topic codec.
+    /// Enabled by default when client is built without compression support
+    #[builder(default = "DEFAULT_SERVER_DECOMPRESS")]
+    pub server_decompress: bool,
+    /// SPU sends only sampled records, to inspect busy topics without reading every record
+    #[builder(default)]
+    pub sampling: RecordSampling,
+    /// Only records within timestamp and offset bounds are received
+    #[builder(default)]
+    pub bounds: BatchBounds,
+    /// SPU reads records from segments instead of its read cache of recent batches.
+    /// Set for large scans, so they don't compete with consumers reading the tail
+    #[builder(default)]
+    pub bypass_cache: bool,
+    /// Number of batches read ahead of the application, 0 disables prefetching
+    #[builder(default)]
+    pub prefetch_batches: usize,
+    /// Don't apply transforms declared by the topic before `smartmodule`
+    #[builder(default)]
+    pub disable_default_transforms: bool,
+    /// Transform payloads of older format versions to the format version of the topic
+    #[builder(default)]
+    pub upcasters: Upcasters,
+    /// Records SmartModules or upcasters fail on are produced to this topic with error headers,
+    /// instead of ending the stream
+    #[builder(default, setter(strip_option, into))]
+    pub dead_letter_topic: Option<String>,
 }
 
 impl ConsumerConfigExt {
@@ -136,7 +235,18 @@ impl ConsumerConfigExt {
             offset_strategy,
             offset_flush,
             offset_flusher_check_period,
+            offset_flush_records: _,
+            offset_commit_error_handler: _,
             retry_mode: _,
+            credits,
+            server_decompress,
+            sampling,
+            bounds,
+            bypass_cache,
+            prefetch_batches: _,
+            disable_default_transforms: _,
+            upcasters: _,
+            dead_letter_topic,
         } = self;
 
         let config = ConsumerConfig {
@@ -144,6 +254,12 @@ impl ConsumerConfigExt {
             max_bytes,
             isolation,
             smartmodule,
+            credits,
+            server_decompress,
+            sampling,
+            bounds,
+            bypass_cache,
+            skip_failed_records: dead_letter_topic.is_some(),
         };
 
         (
@@ -155,6 +271,29 @@ impl ConsumerConfigExt {
             offset_flusher_check_period,
         )
     }
+
+    /// upcast records to format version of the topic
+    pub(crate) fn with_format_version(mut self, version: u32) -> Self {
+        self.upcasters = self.upcasters.with_latest(version);
+        self
+    }
+
+    /// prepend default transforms of the topic to smartmodules, unless disabled
+    pub(crate) fn with_default_transforms(mut self, transforms: &[Transform]) -> Self {
+        if !self.disable_default_transforms && !transforms.is_empty() {
+            self.smartmodule = transforms
+                .iter()
+                .map(|transform| SmartModuleInvocation {
+                    wasm: SmartModuleInvocationWasm::Predefined(transform.uses.clone()),
+                    kind: SmartModuleKind::Generic(Default::default()),
+                    params: transform.with.clone().into(),
+                    name: Some(transform.uses.clone()),
+                })
+                .chain(self.smartmodule)
+                .collect();
+        }
+        self
+    }
 }
 
 impl ConsumerConfigExtBuilder {
@@ -163,6 +302,8 @@ impl ConsumerConfigExtBuilder {
             FluvioError::ConsumerConfig(format!("Missing required config option: {e}"))
         })?;
 
+        validate_credits(config.credits)?;
+
         if config.offset_strategy != OffsetManagementStrategy::None
             && config.offset_consumer.is_none()
         {
@@ -192,11 +333,22 @@ impl From<ConsumerConfigExt> for ConsumerConfig {
             offset_strategy: _,
             offset_flush: _,
             offset_flusher_check_period: _,
+            offset_flush_records: _,
+            offset_commit_error_handler: _,
             disable_continuous,
             max_bytes,
             isolation,
             smartmodule,
             retry_mode: _,
+            credits,
+            server_decompress,
+            sampling,
+            bounds,
+            bypass_cache,
+            prefetch_batches: _,
+            disable_default_transforms: _,
+            upcasters: _,
+            dead_letter_topic,
         } = value;
 
         Self {
@@ -204,6 +356,62 @@ impl From<ConsumerConfigExt> for ConsumerConfig {
             max_bytes,
             isolation,
             smartmodule,
+            credits,
+            server_decompress,
+            sampling,
+            bounds,
+            bypass_cache,
+            skip_failed_records: dead_letter_topic.is_some(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_default_transforms() {
+        let transforms = vec![Transform {
+            uses: "fluvio/decompress@0.1.0".to_owned(),
+            with: [("codec".to_owned(), "gzip".to_owned())].into(),
+        }];
+        let mut builder = ConsumerConfigExt::builder();
+        builder
+            .topic("orders")
+            .offset_start(Offset::beginning())
+            .smartmodule(vec![SmartModuleInvocation {
+                wasm: SmartModuleInvocationWasm::Predefined("filter".to_owned()),
+                ..Default::default()
+            }]);
+
+        let config = builder
+            .build()
+            .expect("config")
+            .with_default_transforms(&transforms);
+        let names: Vec<_> = config
+            .smartmodule
+            .iter()
+            .map(|sm| match &sm.wasm {
+                SmartModuleInvocationWasm::Predefined(name) => name.as_str(),
+                SmartModuleInvocationWasm::AdHoc(_) => "adhoc",
+            })
+            .collect();
+        assert_eq!(names, vec!["fluvio/decompress@0.1.0", "filter"]);
+        assert_eq!(
+            config.smartmodule[0]
+                .params
+                .get("codec")
+                .map(String::as_str),
+            Some("gzip")
+        );
+
+        let config = builder
+            .disable_default_transforms(true)
+            .build()
+            .expect("config")
+            .with_default_transforms(&transforms);
+        assert_eq!(config.smartmodule.len(), 1);
+    }
+}
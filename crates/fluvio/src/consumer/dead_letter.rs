@@ -0,0 +1,173 @@
+//!
+//! # Dead Letter Queue
+//!
+//! Records consumer fails on, because a SmartModule returned an error for them or their
+//! payload can't be upcast, are produced to a dead letter topic instead of ending the stream.
+//! Headers of dead letter records tell where the record comes from and why it failed.
+//!
+use std::fmt;
+use std::sync::Arc;
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::link::smartmodule::SmartModuleTransformRuntimeError;
+use fluvio_protocol::record::{ConsumerRecord, NO_TIMESTAMP, Record};
+use fluvio_types::{PartitionId, Timestamp};
+
+use crate::TopicProducerPool;
+
+/// topic of failed record
+pub const DLQ_TOPIC_HEADER: &str = "fluvio-dlq-topic";
+/// partition of failed record
+pub const DLQ_PARTITION_HEADER: &str = "fluvio-dlq-partition";
+/// offset of failed record
+pub const DLQ_OFFSET_HEADER: &str = "fluvio-dlq-offset";
+/// stage of consumer which failed: SmartModule kind, or `upcast`
+pub const DLQ_STAGE_HEADER: &str = "fluvio-dlq-stage";
+/// error of failed record
+pub const DLQ_ERROR_HEADER: &str = "fluvio-dlq-error";
+
+const UPCAST_STAGE: &str = "upcast";
+
+/// Record consumer failed on
+#[derive(Debug, Clone)]
+pub(crate) struct FailedRecord {
+    offset: i64,
+    record: Record,
+    timestamp: Option<Timestamp>,
+    stage: String,
+    error: String,
+}
+
+impl FailedRecord {
+    /// record SmartModule returned error for
+    pub(crate) fn smartmodule(error: SmartModuleTransformRuntimeError) -> Self {
+        let mut record = Record::new(error.record_value);
+        record.key = error.record_key;
+        Self {
+            offset: error.offset,
+            record,
+            timestamp: None,
+            stage: error.kind.to_string(),
+            error: error.hint,
+        }
+    }
+
+    /// record whose payload can't be upcast
+    pub(crate) fn upcast(record: ConsumerRecord, error: ErrorCode) -> Self {
+        let offset = record.offset;
+        let timestamp = Some(record.timestamp()).filter(|timestamp| *timestamp != NO_TIMESTAMP);
+        Self {
+            offset,
+            record: record.into_inner(),
+            timestamp,
+            stage: UPCAST_STAGE.to_owned(),
+            error: error.to_string(),
+        }
+    }
+
+    /// failed record with headers of where it comes from and why it failed
+    fn into_dead_letter(self, topic: &str, partition: PartitionId) -> Record {
+        let mut record = self.record;
+        record.set_header(DLQ_TOPIC_HEADER, topic);
+        record.set_header(DLQ_PARTITION_HEADER, partition.to_string());
+        record.set_header(DLQ_OFFSET_HEADER, self.offset.to_string());
+        record.set_header(DLQ_STAGE_HEADER, self.stage);
+        record.set_header(DLQ_ERROR_HEADER, self.error);
+        record
+    }
+}
+
+/// Producer of failed records of partition to dead letter topic
+#[derive(Clone)]
+pub(crate) struct DeadLetterQueue {
+    producer: Arc<TopicProducerPool>,
+    dead_letter_topic: String,
+    topic: String,
+    partition: PartitionId,
+}
+
+impl DeadLetterQueue {
+    pub(crate) fn new(
+        producer: Arc<TopicProducerPool>,
+        dead_letter_topic: String,
+        topic: String,
+        partition: PartitionId,
+    ) -> Self {
+        Self {
+            producer,
+            dead_letter_topic,
+            topic,
+            partition,
+        }
+    }
+
+    /// produce failed record to dead letter topic, waiting until it is stored
+    pub(crate) async fn send(&self, failed: FailedRecord) -> Result<(), ErrorCode> {
+        let offset = failed.offset;
+        let timestamp = failed.timestamp;
+        let record = failed.into_dead_letter(&self.topic, self.partition);
+        let result = match self.producer.send_record(record, timestamp).await {
+            Ok(output) => output.wait_all().await.map(|_| ()),
+            Err(err) => Err(err),
+        };
+        result.map_err(|err| {
+            ErrorCode::Other(format!(
+                "unable to send record at offset {offset} to dead letter topic {}: {err}",
+                self.dead_letter_topic
+            ))
+        })
+    }
+}
+
+impl fmt::Debug for DeadLetterQueue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeadLetterQueue")
+            .field("dead_letter_topic", &self.dead_letter_topic)
+            .field("topic", &self.topic)
+            .field("partition", &self.partition)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use fluvio_protocol::link::smartmodule::SmartModuleKind;
+
+    use super::*;
+
+    #[test]
+    fn test_dead_letter_record() {
+        let failed = FailedRecord::smartmodule(SmartModuleTransformRuntimeError {
+            hint: "invalid json".to_owned(),
+            offset: 7,
+            kind: SmartModuleKind::Map,
+            record_key: Some("key".into()),
+            record_value: "value".into(),
+        });
+
+        let record = failed.into_dead_letter("orders", 2);
+        assert_eq!(record.key().map(|key| key.as_ref()), Some(b"key".as_ref()));
+        assert_eq!(record.value().as_ref(), b"value");
+        assert_eq!(
+            record.header(DLQ_TOPIC_HEADER).map(|h| h.as_ref()),
+            Some(b"orders".as_ref())
+        );
+        assert_eq!(
+            record.header(DLQ_PARTITION_HEADER).map(|h| h.as_ref()),
+            Some(b"2".as_ref())
+        );
+        assert_eq!(
+            record.header(DLQ_OFFSET_HEADER).map(|h| h.as_ref()),
+            Some(b"7".as_ref())
+        );
+        assert_eq!(
+            record.header(DLQ_STAGE_HEADER).map(|h| h.as_ref()),
+            Some(b"Map".as_ref())
+        );
+        assert_eq!(
+            record.header(DLQ_ERROR_HEADER).map(|h| h.as_ref()),
+            Some(b"invalid json".as_ref())
+        );
+    }
+}
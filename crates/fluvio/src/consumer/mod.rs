@@ -1,9 +1,13 @@
 #![allow(dead_code)]
 
+mod batch;
 mod config;
 mod stream;
 mod offset;
 mod retry;
+mod prefetch;
+mod upcast;
+mod dead_letter;
 
 use std::future::Future;
 use std::pin::Pin;
@@ -33,26 +37,38 @@ use fluvio_types::defaults::{
     RECONNECT_BACKOFF_FACTOR, RECONNECT_BACKOFF_MAX_DURATION, RECONNECT_BACKOFF_MIN_DURATION,
 };
 use fluvio_spu_schema::server::stream_fetch::{
-    DefaultStreamFetchRequest, DefaultStreamFetchResponse, CHAIN_SMARTMODULE_API,
-    OFFSET_MANAGEMENT_API,
+    DefaultStreamFetchRequest, DefaultStreamFetchResponse, CHAIN_SMARTMODULE_API, FLOW_CONTROL_API,
+    OFFSET_MANAGEMENT_API, SAMPLING_API, SERVER_DECOMPRESS_API, BATCH_BOUNDS_API, BYPASS_CACHE_API,
 };
 use fluvio_protocol::record::ReplicaKey;
 use fluvio_protocol::link::ErrorCode;
 use fluvio_protocol::record::Batch;
 
 use crate::FluvioError;
+use crate::capabilities::{Capability, check_capability};
 use crate::metrics::ClientMetrics;
 use crate::offset::{Offset, fetch_offsets};
 use crate::spu::{SpuDirectory, SpuSocketPool};
+use crate::TopicProducerPool;
 
+use dead_letter::{DeadLetterQueue, FailedRecord};
+
+pub use batch::{ConsumerBatch, ConsumerBatchStream};
 pub use config::{ConsumerConfig, ConsumerConfigBuilder};
-pub use config::{ConsumerConfigExt, ConsumerConfigExtBuilder, OffsetManagementStrategy, RetryMode};
+pub use config::{
+    ConsumerConfigExt, ConsumerConfigExtBuilder, OffsetCommitErrorHandler,
+    OffsetManagementStrategy, RetryMode,
+};
 pub use stream::{
     ConsumerStream, MultiplePartitionConsumerStream, SinglePartitionConsumerStream,
     ConsumerBoxFuture,
 };
-pub use offset::ConsumerOffset;
+pub use offset::{ConsumerOffset, PartitionOffsets};
 pub use retry::ConsumerRetryStream;
+pub use upcast::{FORMAT_VERSION_HEADER, Upcasters, format_version};
+pub use dead_letter::{
+    DLQ_ERROR_HEADER, DLQ_OFFSET_HEADER, DLQ_PARTITION_HEADER, DLQ_STAGE_HEADER, DLQ_TOPIC_HEADER,
+};
 pub use fluvio_protocol::record::ConsumerRecord;
 
 pub use fluvio_protocol::record::ConsumerRecord as Record;
@@ -61,6 +77,8 @@ pub use fluvio_spu_schema::server::smartmodule::SmartModuleInvocationWasm;
 pub use fluvio_spu_schema::server::smartmodule::SmartModuleKind;
 pub use fluvio_spu_schema::server::smartmodule::SmartModuleContextData;
 pub use fluvio_smartmodule::dataplane::smartmodule::SmartModuleExtraParams;
+pub use fluvio_spu_schema::server::stream_fetch::RecordSampling;
+pub use fluvio_spu_schema::server::stream_fetch::BatchBounds;
 
 const STREAM_TO_SERVER_CHANNEL_SIZE: usize = 100;
 const MAX_ATTEMPTS_CONSUMER_OFFSET: usize = 30;
@@ -146,6 +164,17 @@ where
         self.metrics.clone()
     }
 
+    /// Returns earliest, latest and high watermark offsets of the partition without consuming it
+    pub async fn offsets(&self) -> Result<PartitionOffsets> {
+        let replica = ReplicaKey::new(&self.topic, self.partition);
+        let mut serial_socket = self.pool.create_serial_socket(&replica).await?;
+        let offsets = fetch_offsets(&mut serial_socket, &replica).await?;
+        if offsets.error_code != ErrorCode::None {
+            return Err(offsets.error_code.into());
+        }
+        Ok(PartitionOffsets::new(replica, &offsets))
+    }
+
     /// Continuously streams events from a particular offset in the consumer's partition
     ///
     /// Streaming is one of the two ways to consume events in Fluvio.
@@ -246,6 +275,8 @@ where
         offset: Offset,
         config: ConsumerConfig,
     ) -> Result<impl Stream<Item = Result<Record, ErrorCode>> + use<P>> {
+        let bounds = config.bounds;
+        let sampling = config.sampling;
         let (stream, start_offset, _) = self
             .inner_stream_batches_with_config(offset, config, None)
             .await?;
@@ -257,7 +288,10 @@ where
                     batch
                         .into_consumer_records_iter(partition)
                         .filter_map(move |record| {
-                            if record.offset >= start_offset {
+                            if record.offset >= start_offset
+                                && bounds.contains(record.offset, record.timestamp())
+                                && sampling.selects(record.offset)
+                            {
                                 Some(Ok(record))
                             } else {
                                 None
@@ -320,11 +354,12 @@ where
         fluvio_protocol::record::Offset,
         Sender<StreamToServer>,
     )> {
+        let bounds = config.bounds;
         let (stream, start_offset, stream_to_server) =
             self.request_stream(offset, config, consumer_id).await?;
         let metrics = self.metrics.clone();
-        let flattened =
-            stream.flat_map(move |batch_result: Result<DefaultStreamFetchResponse, _>| {
+        let flattened = stream
+            .flat_map(move |batch_result: Result<DefaultStreamFetchResponse, _>| {
                 let response = match batch_result {
                     Ok(response) => response,
                     Err(e) => return Either::Right(once(err(e))),
@@ -369,6 +404,12 @@ where
 
                 let items = batches.chain(error.into_iter());
                 Either::Left(iter(items))
+            })
+            // batch past bounds ends stream, later records are out of bounds too
+            .take_while(move |result| {
+                let past =
+                    matches!(result, Ok(batch) if bounds.is_past(batch.header.first_timestamp));
+                std::future::ready(!past)
             });
 
         Ok((flattened, start_offset, stream_to_server))
@@ -394,6 +435,9 @@ where
 
         let replica = ReplicaKey::new(&self.topic, self.partition);
         let mut serial_socket = self.pool.create_serial_socket(&replica).await?;
+        if !config.smartmodule.is_empty() {
+            check_capability(serial_socket.versions(), Capability::SmartModules)?;
+        }
 
         let consumer_offset = if let Some(ref consumer_id) = consumer_id {
             let consumer_offset_socket = self.create_serial_socket_retry().await?;
@@ -426,6 +470,7 @@ where
         debug!(start_absolute_offset, end_absolute_offset, record_count);
 
         let with_consumer_id = consumer_id.is_some();
+        let skip_failed_records = config.skip_failed_records;
         let stream_request = DefaultStreamFetchRequest::builder()
             .topic(self.topic.to_owned())
             .partition(self.partition)
@@ -434,6 +479,11 @@ where
             .max_bytes(config.max_bytes)
             .smartmodules(config.smartmodule)
             .consumer_id(consumer_id)
+            .credits(config.credits)
+            .server_decompress(config.server_decompress)
+            .sampling(config.sampling)
+            .bounds(config.bounds)
+            .bypass_cache(config.bypass_cache)
             .build()?;
 
         let stream_fetch_version = serial_socket
@@ -449,6 +499,23 @@ where
         if with_consumer_id && stream_fetch_version < OFFSET_MANAGEMENT_API {
             warn!("SPU does not support Offset Management API");
         }
+        if config.credits > 1 && stream_fetch_version < FLOW_CONTROL_API {
+            warn!("SPU does not support flow control credits, single response will be in flight");
+        }
+        if config.server_decompress && stream_fetch_version < SERVER_DECOMPRESS_API {
+            warn!(
+                "SPU does not support decompressing records, compressed batches will be received"
+            );
+        }
+        if config.sampling.is_enabled() && stream_fetch_version < SAMPLING_API {
+            warn!("SPU does not support record sampling, records will be sampled by consumer");
+        }
+        if config.bounds.is_enabled() && stream_fetch_version < BATCH_BOUNDS_API {
+            warn!("SPU does not support batch bounds, records will be filtered by consumer");
+        }
+        if config.bypass_cache && stream_fetch_version < BYPASS_CACHE_API {
+            debug!("SPU does not support bypassing read cache");
+        }
 
         let mut stream = self
             .pool
@@ -533,7 +600,7 @@ where
                 });
 
                 // send back first offset records exists
-                if let Some(last_offset) = response.partition.next_offset_for_fetch() {
+                if let Some(last_offset) = next_fetch_offset(&response, skip_failed_records) {
                     debug!(last_offset, "notify new last offset");
                     let _ = server_sender_clone
                         .send(StreamToServer::UpdateOffset(last_offset))
@@ -543,7 +610,8 @@ where
                 let server_sender_clone2 = server_sender_clone.clone();
                 let update_stream = StreamExt::map(stream, move |item| {
                     item.inspect(|response| {
-                        if let Some(last_offset) = response.partition.next_offset_for_fetch() {
+                        if let Some(last_offset) = next_fetch_offset(response, skip_failed_records)
+                        {
                             debug!(last_offset, stream_id, "received last offset from spu");
                             let _ = server_sender_clone
                                 .try_send(StreamToServer::UpdateOffset(last_offset));
@@ -567,7 +635,12 @@ where
         };
 
         let stream = if config.disable_continuous {
-            TakeRecords::new(ft_stream.flatten_stream().boxed(), record_count).boxed()
+            TakeRecords::new(
+                ft_stream.flatten_stream().boxed(),
+                start_absolute_offset,
+                end_absolute_offset,
+            )
+            .boxed()
         } else {
             ft_stream.flatten_stream().boxed()
         };
@@ -602,44 +675,108 @@ where
         }
     }
 
-    #[instrument(skip(self, config))]
+    /// Records failed on are sent to dead letter topic with `dead_letter_producer`, if it is given
+    #[instrument(skip(self, config, dead_letter_producer))]
     pub(crate) async fn consumer_stream_with_config(
         self,
         config: ConsumerConfigExt,
+        dead_letter_producer: Option<Arc<TopicProducerPool>>,
     ) -> Result<SinglePartitionConsumerStream<impl Stream<Item = Result<Record, ErrorCode>> + use<P>>>
     {
+        let prefetch_batches = config.prefetch_batches;
+        let flush_records = config.offset_flush_records;
+        let error_handler = config.offset_commit_error_handler.clone();
+        let upcasters = Arc::new(config.upcasters.clone());
+        let bounds = config.bounds;
+        let sampling = config.sampling;
+        let dead_letter = config
+            .dead_letter_topic
+            .clone()
+            .zip(dead_letter_producer)
+            .map(|(dead_letter_topic, producer)| {
+                Arc::new(DeadLetterQueue::new(
+                    producer,
+                    dead_letter_topic,
+                    self.topic.clone(),
+                    self.partition,
+                ))
+            });
         let (offset, config, consumer_id, strategy, flush_period, flusher_check_period) =
             config.into_parts();
         let (stream, start_offset, stream_to_server) = self
             .inner_stream_batches_with_config(offset, config, consumer_id)
             .await?;
+        let stream = prefetch::prefetch(stream, prefetch_batches);
         let partition = self.partition;
         let flattened = stream.flat_map(move |result: Result<Batch, _>| match result {
             Err(e) => Either::Right(once(err(e))),
             Ok(batch) => {
-                let records =
-                    batch
-                        .into_consumer_records_iter(partition)
-                        .filter_map(move |record| {
-                            if record.offset >= start_offset {
-                                Some(Ok(record))
-                            } else {
-                                None
-                            }
-                        });
+                let records = batch
+                    .into_consumer_records_iter(partition)
+                    .filter(move |record| {
+                        // batches are selected by SPU, records out of bounds are dropped here.
+                        // sampling is checked again for SPUs that don't support it
+                        record.offset >= start_offset
+                            && bounds.contains(record.offset, record.timestamp())
+                            && sampling.selects(record.offset)
+                    })
+                    .map(Ok);
                 Either::Left(iter(records))
             }
         });
-        Ok(SinglePartitionConsumerStream::new(
-            flattened,
+        let upcasted = Box::pin(
+            flattened
+                .then(move |result| {
+                    upcast_or_dead_letter(result, upcasters.clone(), dead_letter.clone())
+                })
+                .filter_map(futures_util::future::ready),
+        );
+        Ok(SinglePartitionConsumerStream::with_batching(
+            upcasted,
             strategy,
             flush_period,
             flusher_check_period,
+            flush_records,
+            error_handler,
             stream_to_server,
         ))
     }
 }
 
+/// Upcast consumed record. With dead letter queue, records failed on are sent to it instead of
+/// ending the stream with error
+async fn upcast_or_dead_letter(
+    result: Result<Record, ErrorCode>,
+    upcasters: Arc<Upcasters>,
+    dead_letter: Option<Arc<DeadLetterQueue>>,
+) -> Option<Result<Record, ErrorCode>> {
+    let Some(dead_letter) = dead_letter else {
+        return Some(result.and_then(|record| upcasters.upcast(record)));
+    };
+    let failed = match result {
+        Ok(record) => match upcasters.upcast(record.clone()) {
+            Ok(upcasted) => return Some(Ok(upcasted)),
+            Err(error) => FailedRecord::upcast(record, error),
+        },
+        Err(ErrorCode::SmartModuleRuntimeError(error)) => FailedRecord::smartmodule(*error),
+        Err(error) => return Some(Err(error)),
+    };
+    debug!(?failed, "sending failed record to dead letter topic");
+    dead_letter.send(failed).await.err().map(Err)
+}
+
+/// Offset SPU continues stream from after response.
+/// Record SmartModule failed on is skipped if failed records are not read again
+fn next_fetch_offset(response: &DefaultStreamFetchResponse, skip_failed: bool) -> Option<i64> {
+    let next = response.partition.next_offset_for_fetch();
+    match &response.partition.error_code {
+        ErrorCode::SmartModuleRuntimeError(error) if skip_failed => {
+            Some(next.map_or(error.offset + 1, |next| next.max(error.offset + 1)))
+        }
+        _ => next,
+    }
+}
+
 /// Wrap an inner record stream and only stream until a given number of records have been fetched.
 ///
 /// This is used for "disable continuous" mode. In this mode, we first make a FetchOffsetPartitionResponse
@@ -649,8 +786,11 @@ where
 /// We then use `TakeRecords` to stop the stream as soon as we reach that point, so the user
 /// (e.g. on the CLI) does not spend any time waiting for new records to be produced, they are
 /// simply given all the records that are already available.
+/// Responses of filtered or sampled streams hold fewer records than were read, in which case
+/// the SPU reports the offset to continue from.
 struct TakeRecords<S> {
-    remaining: i64,
+    next_offset: i64,
+    end_offset: i64,
     stream: S,
 }
 
@@ -658,9 +798,10 @@ impl<S> TakeRecords<S>
 where
     S: Stream<Item = Result<DefaultStreamFetchResponse, ErrorCode>> + std::marker::Unpin,
 {
-    pub fn new(stream: S, until: i64) -> Self {
+    pub fn new(stream: S, start_offset: i64, end_offset: i64) -> Self {
         Self {
-            remaining: until,
+            next_offset: start_offset,
+            end_offset,
             stream,
         }
     }
@@ -678,7 +819,7 @@ where
     ) -> std::task::Poll<Option<Self::Item>> {
         use std::{pin::Pin, task::Poll};
         use futures_util::ready;
-        if self.remaining <= 0 {
+        if self.next_offset >= self.end_offset {
             return Poll::Ready(None);
         }
         let next = ready!(Pin::new(&mut self.as_mut().stream).poll_next(cx));
@@ -692,8 +833,8 @@ where
                     .iter()
                     .map(|it| it.records_len())
                     .sum();
-                let diff = self.remaining - count as i64;
-                self.remaining = diff.max(0);
+                let next_offset = self.next_offset + count as i64;
+                self.next_offset = next_offset.max(response.partition.next_filter_offset);
                 Poll::Ready(Some(Ok(response)))
             }
             other => Poll::Ready(other),
@@ -776,7 +917,10 @@ pub enum PartitionSelectionStrategy {
 }
 
 impl PartitionSelectionStrategy {
-    async fn selection(&self, spu_pool: Arc<SpuSocketPool>) -> Result<Vec<(String, PartitionId)>> {
+    pub(crate) async fn selection(
+        &self,
+        spu_pool: Arc<SpuSocketPool>,
+    ) -> Result<Vec<(String, PartitionId)>> {
         let pairs = match self {
             PartitionSelectionStrategy::All(topic) => {
                 let topics = spu_pool.metadata.topics();
@@ -940,6 +1084,19 @@ impl MultiplePartitionConsumer {
 
         Ok(select_all(streams))
     }
+
+    /// Returns earliest, latest and high watermark offsets of the selected partitions without consuming them
+    pub async fn offsets(&self) -> Result<Vec<PartitionOffsets>> {
+        let consumers = self
+            .strategy
+            .selection(self.pool.clone())
+            .await?
+            .into_iter()
+            .map(|(topic, partition)| {
+                PartitionConsumer::new(topic, partition, self.pool.clone(), self.metrics.clone())
+            });
+        try_join_all(consumers.map(|consumer| async move { consumer.offsets().await })).await
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -988,10 +1145,39 @@ async fn backoff_and_wait(backoff: &mut ExponentialBackoff) {
 
 #[cfg(test)]
 mod tests {
+    use fluvio_protocol::link::smartmodule::SmartModuleTransformRuntimeError;
+
     use super::*;
 
     #[test]
     fn test_consumer_config_default() {
         let _config = ConsumerConfig::builder().build().unwrap();
     }
+
+    #[test]
+    fn test_next_fetch_offset() {
+        let mut response = DefaultStreamFetchResponse::default();
+        response.partition.next_filter_offset = 5;
+        assert_eq!(next_fetch_offset(&response, true), Some(5));
+
+        response.partition.error_code =
+            ErrorCode::SmartModuleRuntimeError(Box::new(SmartModuleTransformRuntimeError {
+                offset: 8,
+                ..Default::default()
+            }));
+        assert_eq!(next_fetch_offset(&response, false), Some(5));
+        assert_eq!(next_fetch_offset(&response, true), Some(9));
+
+        response.partition.next_filter_offset = 12;
+        assert_eq!(next_fetch_offset(&response, true), Some(12));
+    }
+
+    #[test]
+    fn test_consumer_config_credits() {
+        let config = ConsumerConfig::builder().credits(4u32).build().unwrap();
+        assert_eq!(config.credits, 4);
+
+        assert!(ConsumerConfig::builder().credits(0u32).build().is_err());
+        assert!(ConsumerConfig::builder().credits(100u32).build().is_err());
+    }
 }
@@ -9,8 +9,11 @@ use fluvio_types::PartitionId;
 use serde::Serialize;
 
 use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::ReplicaKey;
 use fluvio_spu_schema::server::consumer_offset::ConsumerOffset as ConsumerOffsetRequest;
+use fluvio_spu_schema::server::fetch_offset::FetchOffsetPartitionResponse;
 
+use super::config::OffsetCommitErrorHandler;
 use super::{StreamToServer, StreamToServerCallback};
 
 const DEFAULT_ORDERING: std::sync::atomic::Ordering = std::sync::atomic::Ordering::Relaxed;
@@ -20,15 +23,24 @@ pub(crate) struct OffsetLocalStore {
     comitted: AtomicI64,
     flushed: AtomicI64,
     stream_to_server: Sender<StreamToServer>,
+    error_handler: Option<OffsetCommitErrorHandler>,
 }
 
 impl OffsetLocalStore {
     pub(crate) fn new(stream_to_server: Sender<StreamToServer>) -> Self {
+        Self::with_error_handler(stream_to_server, None)
+    }
+
+    pub(crate) fn with_error_handler(
+        stream_to_server: Sender<StreamToServer>,
+        error_handler: Option<OffsetCommitErrorHandler>,
+    ) -> Self {
         Self {
             seen: AtomicI64::new(-1),
             comitted: AtomicI64::new(-1),
             flushed: AtomicI64::new(-1),
             stream_to_server,
+            error_handler,
         }
     }
 
@@ -73,12 +85,36 @@ impl OffsetLocalStore {
         self.stream_to_server
             .try_send(StreamToServer::FlushManagedOffset {
                 offset: self.comitted(),
-                callback: StreamToServerCallback::NoOp,
+                callback: self.background_callback(),
             })?;
         self.set_flushed(self.comitted());
         Ok(())
     }
 
+    /// number of committed offsets not flushed yet
+    pub fn pending(&self) -> i64 {
+        self.comitted() - self.flushed()
+    }
+
+    /// callback passing result of flush nobody waits for to error handler
+    fn background_callback(&self) -> StreamToServerCallback<ErrorCode> {
+        let Some(handler) = self.error_handler.clone() else {
+            return StreamToServerCallback::NoOp;
+        };
+        let (s, r) = bounded(1);
+        fluvio_future::task::spawn(async move {
+            match r.recv().await {
+                Ok(ErrorCode::None) => {}
+                Ok(err) => handler.handle(err),
+                // request was dropped before reaching the server
+                Err(_) => handler.handle(ErrorCode::OffsetFlushRequestError(
+                    "consumer stream closed before offset was flushed".to_owned(),
+                )),
+            }
+        });
+        StreamToServerCallback::Channel(s)
+    }
+
     fn flushed(&self) -> i64 {
         self.flushed.load(DEFAULT_ORDERING)
     }
@@ -132,6 +168,43 @@ impl From<ConsumerOffsetRequest> for ConsumerOffset {
     }
 }
 
+/// Offsets of partition as reported by its leader
+#[derive(Debug, Serialize, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PartitionOffsets {
+    pub topic: String,
+    pub partition: PartitionId,
+    /// Offset of the first record available in the partition
+    pub earliest: i64,
+    /// Offset after the last record committed by replicas, `ReadCommitted` consumers read up to it
+    pub high_watermark: i64,
+    /// Offset after the last record written to the leader, including records not committed yet
+    pub latest: i64,
+}
+
+impl PartitionOffsets {
+    pub(crate) fn new(replica: ReplicaKey, offsets: &FetchOffsetPartitionResponse) -> Self {
+        Self {
+            topic: replica.topic,
+            partition: replica.partition,
+            earliest: offsets.start_offset,
+            high_watermark: offsets.last_stable_offset,
+            latest: offsets.end_offset(),
+        }
+    }
+
+    /// Number of committed records available in the partition
+    pub fn records(&self) -> i64 {
+        (self.high_watermark - self.earliest).max(0)
+    }
+
+    /// Number of committed records after the given consumer offset, the offset of last consumed record.
+    /// Records removed by retention are not counted
+    pub fn lag(&self, consumer_offset: i64) -> i64 {
+        let next = (consumer_offset + 1).max(self.earliest);
+        (self.high_watermark - next).max(0)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use async_channel::TryRecvError;
@@ -335,4 +408,52 @@ mod tests {
         assert!(res.is_err());
         assert_eq!(res.unwrap_err().to_string(), "an error occurred on the SPU");
     }
+
+    #[fluvio_future::test]
+    async fn test_try_flush_reports_error_to_handler() {
+        //given
+        let (sender, recv) = async_channel::bounded(1);
+        let (error_sender, error_recv) = async_channel::bounded(1);
+        let handler = OffsetCommitErrorHandler::new(move |err| {
+            let _ = error_sender.try_send(err);
+        });
+        let store = OffsetLocalStore::with_error_handler(sender, Some(handler));
+
+        //when
+        store.update(1);
+        store.commit();
+        assert_eq!(store.pending(), 2);
+        store.try_flush().expect("flushed");
+        assert_eq!(store.pending(), 0);
+        if let Ok(StreamToServer::FlushManagedOffset {
+            offset: _,
+            callback,
+        }) = recv.recv().await
+        {
+            callback.send(ErrorCode::SpuOffline).await;
+        }
+
+        //then
+        assert_eq!(error_recv.recv().await, Ok(ErrorCode::SpuOffline));
+    }
+
+    #[test]
+    fn test_partition_offsets() {
+        let offsets = PartitionOffsets::new(
+            ReplicaKey::new("topic", 1u32),
+            &FetchOffsetPartitionResponse {
+                partition_index: 1,
+                start_offset: 10,
+                last_stable_offset: 25,
+                log_end_offset: 27,
+                ..Default::default()
+            },
+        );
+        assert_eq!(offsets.latest, 27);
+        assert_eq!(offsets.records(), 15);
+        assert_eq!(offsets.lag(19), 5);
+        assert_eq!(offsets.lag(24), 0);
+        // consumed records removed by retention
+        assert_eq!(offsets.lag(2), 15);
+    }
 }
@@ -0,0 +1,126 @@
+use std::pin::pin;
+
+use async_channel::bounded;
+use futures_util::future::Either;
+use futures_util::stream::{Stream, StreamExt};
+use tokio::select;
+use tracing::debug;
+
+use fluvio_future::task::spawn;
+
+/// Read ahead up to `batches` items of `stream` while caller is processing previous ones.
+///
+/// Batches are pulled from the SPU stream by a background task and buffered, so
+/// offsets are acknowledged and the next fetch is issued without waiting for the
+/// application. Prefetching is disabled when `batches` is 0.
+pub(crate) fn prefetch<S>(stream: S, batches: usize) -> impl Stream<Item = S::Item>
+where
+    S: Stream + Send + 'static,
+    S::Item: Send + 'static,
+{
+    if batches == 0 {
+        return Either::Left(stream);
+    }
+
+    let (sender, receiver) = bounded(batches);
+    // closed when returned stream is dropped, so task ends even if SPU stream is idle
+    let (closed_sender, closed) = bounded::<()>(1);
+    spawn(async move {
+        let mut stream = pin!(stream);
+        loop {
+            let item = select! {
+                item = stream.next() => item,
+                _ = closed.recv() => {
+                    debug!("prefetch consumer dropped, stopping read ahead");
+                    break;
+                }
+            };
+            let Some(item) = item else {
+                break;
+            };
+            if sender.send(item).await.is_err() {
+                debug!("prefetch receiver dropped, stopping read ahead");
+                break;
+            }
+        }
+    });
+    Either::Right(receiver.map(move |item| {
+        let _ = &closed_sender;
+        item
+    }))
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use futures_util::stream::{iter, pending};
+
+    use fluvio_future::timer::sleep;
+
+    use super::*;
+
+    fn counted(count: usize, pulled: Arc<AtomicUsize>) -> impl Stream<Item = usize> + Send {
+        iter(0..count).inspect(move |_| {
+            pulled.fetch_add(1, Ordering::SeqCst);
+        })
+    }
+
+    #[fluvio_future::test]
+    async fn test_prefetch_reads_ahead() {
+        let pulled = Arc::new(AtomicUsize::new(0));
+        let mut stream = Box::pin(prefetch(counted(10, pulled.clone()), 3));
+
+        assert_eq!(stream.next().await, Some(0));
+        sleep(Duration::from_millis(50)).await;
+
+        // one item consumed, 3 buffered and 1 waiting to be sent
+        assert_eq!(pulled.load(Ordering::SeqCst), 5);
+
+        let rest: Vec<_> = stream.collect().await;
+        assert_eq!(rest, (1..10).collect::<Vec<_>>());
+    }
+
+    /// sets flag when dropped
+    struct DropFlag(Arc<AtomicBool>);
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[fluvio_future::test]
+    async fn test_prefetch_ends_on_idle_stream_when_dropped() {
+        let dropped = Arc::new(AtomicBool::new(false));
+        let flag = DropFlag(dropped.clone());
+        // idle partition, no item ever arrives
+        let idle = pending::<usize>().map(move |item| {
+            let _ = &flag;
+            item
+        });
+        let stream = prefetch(idle, 3);
+        sleep(Duration::from_millis(50)).await;
+        assert!(!dropped.load(Ordering::SeqCst));
+
+        drop(stream);
+        sleep(Duration::from_millis(50)).await;
+        assert!(
+            dropped.load(Ordering::SeqCst),
+            "prefetch task still owns stream"
+        );
+    }
+
+    #[fluvio_future::test]
+    async fn test_prefetch_disabled() {
+        let pulled = Arc::new(AtomicUsize::new(0));
+        let mut stream = Box::pin(prefetch(counted(10, pulled.clone()), 0));
+
+        assert_eq!(stream.next().await, Some(0));
+        sleep(Duration::from_millis(50)).await;
+        assert_eq!(pulled.load(Ordering::SeqCst), 1);
+    }
+}
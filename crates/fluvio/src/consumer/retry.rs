@@ -182,6 +182,13 @@ impl ConsumerStream for ConsumerRetryStream {
             stream.offset_flush().await
         })
     }
+
+    fn offset_commit_async(&mut self) -> ConsumerBoxFuture<'_> {
+        Box::pin(async move {
+            let mut stream = self.stream.lock().await;
+            stream.offset_commit_async().await
+        })
+    }
 }
 
 impl ConsumerRetryStream {
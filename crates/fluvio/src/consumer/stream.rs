@@ -1,7 +1,11 @@
 use std::pin::Pin;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::{Duration, SystemTime};
+use std::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::SystemTime;
+#[cfg(target_arch = "wasm32")]
+use web_time::SystemTime;
 
 use async_channel::Sender;
 use fluvio_future::timer::sleep;
@@ -13,7 +17,7 @@ use tokio::select;
 use tokio::sync::Notify;
 use tracing::{debug, info, warn};
 
-use super::config::OffsetManagementStrategy;
+use super::config::{OffsetCommitErrorHandler, OffsetManagementStrategy};
 use super::{offset::OffsetLocalStore, StreamToServer};
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -30,6 +34,16 @@ pub trait ConsumerStream: Stream<Item = Result<Record, ErrorCode>> + Unpin {
 
     /// Send the committed offset to the server. The method waits for the server's acknowledgment before it finishes.
     fn offset_flush(&mut self) -> ConsumerBoxFuture<'_>;
+
+    /// Commit the offset of the last yielded record and send it to the server in the background.
+    /// The method finishes once the request is queued, without waiting for the server's acknowledgment.
+    /// Failures are passed to the configured [`OffsetCommitErrorHandler`].
+    ///
+    /// Default implementation only commits the offset, as `offset_commit()` does,
+    /// so it is sent by the next `offset_flush()` or by the [`OffsetManagementStrategy`].
+    fn offset_commit_async(&mut self) -> ConsumerBoxFuture<'_> {
+        self.offset_commit()
+    }
 }
 
 pub struct MultiplePartitionConsumerStream<T> {
@@ -60,6 +74,7 @@ enum OffsetManagement {
         auto_flusher: AutomaticFlusher,
         flush_period: Duration,
         flusher_check_period: Duration,
+        flush_records: usize,
         offset_store: OffsetLocalStore,
         last_flush_time: AtomicU64,
     },
@@ -104,16 +119,38 @@ impl<T> SinglePartitionConsumerStream<T> {
         flusher_check_period: Duration,
         stream_to_server: Sender<StreamToServer>,
     ) -> Self {
+        Self::with_batching(
+            inner,
+            offset_strategy,
+            flush_period,
+            flusher_check_period,
+            0,
+            None,
+            stream_to_server,
+        )
+    }
+
+    /// Same as `new`, additionally flushing every `flush_records` offsets with auto strategy
+    /// and passing errors of background flushes to `error_handler`
+    pub(super) fn with_batching(
+        inner: T,
+        offset_strategy: OffsetManagementStrategy,
+        flush_period: Duration,
+        flusher_check_period: Duration,
+        flush_records: usize,
+        error_handler: Option<OffsetCommitErrorHandler>,
+        stream_to_server: Sender<StreamToServer>,
+    ) -> Self {
+        let offset_store = OffsetLocalStore::with_error_handler(stream_to_server, error_handler);
         let offset_mngt = match offset_strategy {
             OffsetManagementStrategy::None => OffsetManagement::None,
-            OffsetManagementStrategy::Manual => OffsetManagement::Manual {
-                offset_store: OffsetLocalStore::new(stream_to_server),
-            },
+            OffsetManagementStrategy::Manual => OffsetManagement::Manual { offset_store },
             OffsetManagementStrategy::Auto => OffsetManagement::Auto {
                 auto_flusher: AutomaticFlusher::new(),
-                offset_store: OffsetLocalStore::new(stream_to_server),
+                offset_store,
                 flush_period,
                 flusher_check_period,
+                flush_records,
                 last_flush_time: AtomicU64::new(0),
             },
         };
@@ -164,6 +201,10 @@ impl ConsumerStream for Pin<Box<dyn ConsumerStream + Send>> {
     fn offset_flush(&mut self) -> ConsumerBoxFuture<'_> {
         Box::pin(async move { self.as_mut().offset_flush().await })
     }
+
+    fn offset_commit_async(&mut self) -> ConsumerBoxFuture<'_> {
+        Box::pin(async move { self.as_mut().offset_commit_async().await })
+    }
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -175,6 +216,10 @@ impl ConsumerStream for Pin<Box<dyn ConsumerStream>> {
     fn offset_flush(&mut self) -> ConsumerBoxFuture<'_> {
         Box::pin(async move { self.as_mut().offset_flush().await })
     }
+
+    fn offset_commit_async(&mut self) -> ConsumerBoxFuture<'_> {
+        Box::pin(async move { self.as_mut().offset_commit_async().await })
+    }
 }
 
 impl<T: Stream<Item = Result<Record, ErrorCode>> + Unpin> ConsumerStream
@@ -187,6 +232,11 @@ impl<T: Stream<Item = Result<Record, ErrorCode>> + Unpin> ConsumerStream
     fn offset_flush(&mut self) -> ConsumerBoxFuture<'_> {
         Box::pin(self.offset_mngt.flush())
     }
+
+    fn offset_commit_async(&mut self) -> ConsumerBoxFuture<'_> {
+        let result = self.offset_mngt.commit_async();
+        Box::pin(async { result })
+    }
 }
 
 impl<T: Stream<Item = Result<Record, ErrorCode>> + Unpin> ConsumerStream
@@ -206,6 +256,16 @@ impl<T: Stream<Item = Result<Record, ErrorCode>> + Unpin> ConsumerStream
         let futures: Vec<_> = self.offset_mgnts.iter().map(|p| p.flush()).collect();
         Box::pin(try_join_all(futures).map(|r| r.map(|_| ())))
     }
+
+    fn offset_commit_async(&mut self) -> ConsumerBoxFuture<'_> {
+        for partition in &self.offset_mgnts {
+            if let Err(err) = partition.commit_async() {
+                return Box::pin(async { Err(err) });
+            }
+        }
+
+        Box::pin(async { Ok(()) })
+    }
 }
 
 impl<T: Stream<Item = Result<Record, ErrorCode>> + Unpin> Stream
@@ -261,14 +321,17 @@ impl OffsetManagement {
     fn run_auto_flush(&self) {
         if let OffsetManagement::Auto {
             flush_period,
+            flush_records,
             offset_store,
             last_flush_time,
             ..
         } = self
         {
-            if Duration::from_secs(now_timestamp_secs() - last_flush_time.load(Ordering::Relaxed))
-                >= *flush_period
-            {
+            let period_passed =
+                Duration::from_secs(now_timestamp_secs() - last_flush_time.load(Ordering::Relaxed))
+                    >= *flush_period;
+            let batch_full = *flush_records > 0 && offset_store.pending() >= *flush_records as i64;
+            if period_passed || batch_full {
                 debug!("auto flush offset");
                 if let Err(err) = offset_store.try_flush() {
                     warn!("auto flush failed: {err:?}");
@@ -294,6 +357,19 @@ impl OffsetManagement {
         }
     }
 
+    fn commit_async(&self) -> Result<(), ErrorCode> {
+        match self {
+            OffsetManagement::None => Err(ErrorCode::OffsetManagementDisabled),
+            OffsetManagement::Manual { offset_store }
+            | OffsetManagement::Auto { offset_store, .. } => {
+                offset_store.commit();
+                offset_store
+                    .try_flush()
+                    .map_err(|e| ErrorCode::OffsetFlushRequestError(e.to_string()))
+            }
+        }
+    }
+
     async fn flush(&self) -> Result<(), ErrorCode> {
         match self {
             OffsetManagement::None => Err(ErrorCode::OffsetManagementDisabled),
@@ -882,6 +958,74 @@ mod tests {
         assert_eq!(flush_res, Err(ErrorCode::SpuOffline), "{flush_res:?}");
     }
 
+    #[fluvio_future::test]
+    async fn test_single_partition_stream_commit_async_on_manual() {
+        //given
+        let (tx, rx) = async_channel::unbounded();
+        let mut partition_stream = SinglePartitionConsumerStream::new(
+            records_stream(0, ["1", "2", "3"]),
+            OffsetManagementStrategy::Manual,
+            Default::default(),
+            Duration::from_millis(100),
+            tx,
+        );
+
+        //when
+        assert!(partition_stream.next().await.is_some()); // seen = 0
+        assert!(partition_stream.next().await.is_some()); // seen = 1
+        let res = partition_stream.offset_commit_async().await; // flushed = 1
+
+        //then
+        assert!(res.is_ok(), "{res:?}");
+        let message1 = rx.try_recv();
+        assert!(
+            matches!(
+                message1,
+                Ok(StreamToServer::FlushManagedOffset { callback: _, offset }) if offset == 1
+            ),
+            "{message1:?}"
+        );
+        assert!(partition_stream.offset_commit_async().await.is_ok()); // ignored, nothing to flush
+        let message2 = rx.try_recv();
+        assert!(message2.is_err(), "{message2:?}");
+    }
+
+    #[fluvio_future::test]
+    async fn test_single_partition_stream_auto_flush_by_records() {
+        //given
+        let (tx, rx) = async_channel::unbounded();
+        let mut partition_stream = SinglePartitionConsumerStream::with_batching(
+            records_stream(0, ["1", "2", "3", "4"]),
+            OffsetManagementStrategy::Auto,
+            Duration::from_secs(1000),
+            Duration::from_millis(100),
+            2,
+            None,
+            tx,
+        );
+
+        //when
+        assert!(partition_stream.next().await.is_some()); // seen = 0, flushed = 0
+        assert!(partition_stream.next().await.is_some()); // seen = 1
+        assert!(partition_stream.next().await.is_some()); // seen = 2, flushed = 2
+        assert!(partition_stream.next().await.is_some()); // seen = 3
+        drop(partition_stream);
+
+        //then
+        for expected in [0, 2, 3] {
+            let message = rx.recv().await;
+            assert!(
+                matches!(
+                    message,
+                    Ok(StreamToServer::FlushManagedOffset { callback: _, offset }) if offset == expected
+                ),
+                "{message:?}"
+            );
+        }
+        let message = rx.try_recv();
+        assert!(message.is_err(), "{message:?}");
+    }
+
     fn records_stream(
         partition: PartitionId,
         input: impl IntoIterator<Item = &'static str>,
@@ -0,0 +1,184 @@
+//!
+//! # Upcasting
+//!
+//! Topics can declare a format version of their record payloads. Producers stamp records
+//! with the format version of the topic, and consumers register upcasters which transform
+//! payloads of a version to the next one, so records of older versions are consumed
+//! in the latest format of the topic.
+//!
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::{ConsumerRecord, Record};
+
+/// header with format version of record payload, records without it are version 0
+pub const FORMAT_VERSION_HEADER: &str = "fluvio-format-version";
+
+type Upcaster = Arc<dyn Fn(&[u8]) -> anyhow::Result<Vec<u8>> + Send + Sync>;
+
+/// Upcasters of record payloads, by version they transform from
+///
+/// ```
+/// use fluvio::consumer::Upcasters;
+///
+/// let upcasters = Upcasters::new()
+///     .register(1, |value| Ok([b"v2:", value].concat()))
+///     .register(2, |value| Ok([b"v3:", value].concat()));
+/// ```
+#[derive(Clone, Default)]
+pub struct Upcasters {
+    upcasters: BTreeMap<u32, Upcaster>,
+    /// format version of the topic, records are upcast to it
+    latest: u32,
+}
+
+impl Upcasters {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// register upcaster transforming payloads of `from_version` to `from_version + 1`
+    pub fn register(
+        mut self,
+        from_version: u32,
+        upcaster: impl Fn(&[u8]) -> anyhow::Result<Vec<u8>> + Send + Sync + 'static,
+    ) -> Self {
+        self.upcasters.insert(from_version, Arc::new(upcaster));
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.upcasters.is_empty()
+    }
+
+    /// set format version of topic being consumed
+    pub(crate) fn with_latest(mut self, latest: u32) -> Self {
+        self.latest = latest;
+        self
+    }
+
+    /// upcast payload of record to the latest version, applying upcasters of versions in between.
+    /// Records of the latest version or newer are returned as is
+    pub(crate) fn upcast(&self, mut record: ConsumerRecord) -> Result<ConsumerRecord, ErrorCode> {
+        if self.upcasters.is_empty() {
+            return Ok(record);
+        }
+        let mut version = format_version(&record.record).ok_or_else(|| {
+            ErrorCode::Other(format!(
+                "invalid format version of record at offset {}",
+                record.offset
+            ))
+        })?;
+        if version >= self.latest {
+            return Ok(record);
+        }
+
+        let mut value = record.record.value.as_ref().to_vec();
+        while version < self.latest {
+            let upcaster = self.upcasters.get(&version).ok_or_else(|| {
+                ErrorCode::Other(format!(
+                    "no upcaster from format version {version} for record at offset {}",
+                    record.offset
+                ))
+            })?;
+            value = upcaster(&value).map_err(|err| {
+                ErrorCode::Other(format!(
+                    "upcasting record at offset {} from format version {version}: {err}",
+                    record.offset
+                ))
+            })?;
+            version += 1;
+        }
+        record.record.value = value.into();
+        record
+            .record
+            .set_header(FORMAT_VERSION_HEADER, version.to_string());
+        Ok(record)
+    }
+}
+
+impl fmt::Debug for Upcasters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Upcasters")
+            .field("versions", &self.upcasters.keys().collect::<Vec<_>>())
+            .field("latest", &self.latest)
+            .finish()
+    }
+}
+
+/// format version stamped on record, none if header is not a number
+pub fn format_version<B: Default>(record: &Record<B>) -> Option<u32> {
+    match record.header(FORMAT_VERSION_HEADER) {
+        Some(version) => std::str::from_utf8(version.as_ref()).ok()?.parse().ok(),
+        None => Some(0),
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use fluvio_protocol::record::Batch;
+
+    use super::*;
+
+    fn consumer_record(record: Record) -> ConsumerRecord {
+        let mut batch = Batch::default();
+        batch.add_record(record);
+        batch.into_consumer_records_iter(0).next().expect("record")
+    }
+
+    fn versioned(value: &str, version: u32) -> ConsumerRecord {
+        let mut record = Record::new(value);
+        record.set_header(FORMAT_VERSION_HEADER, version.to_string());
+        consumer_record(record)
+    }
+
+    #[test]
+    fn test_upcast() {
+        let upcasters = Upcasters::new()
+            .register(0, |value| Ok([b"v1:", value].concat()))
+            .register(1, |value| Ok([b"v2:", value].concat()))
+            .with_latest(2);
+
+        let record = upcasters
+            .upcast(consumer_record(Record::new("a")))
+            .expect("upcast");
+        assert_eq!(record.value(), b"v2:v1:a");
+        assert_eq!(format_version(&record.record), Some(2));
+
+        let record = upcasters.upcast(versioned("b", 1)).expect("upcast");
+        assert_eq!(record.value(), b"v2:b");
+
+        let record = upcasters.upcast(versioned("c", 2)).expect("upcast");
+        assert_eq!(record.value(), b"c");
+
+        let record = upcasters.upcast(versioned("d", 3)).expect("upcast");
+        assert_eq!(record.value(), b"d");
+    }
+
+    #[test]
+    fn test_upcast_errors() {
+        let upcasters = Upcasters::new()
+            .register(1, |_| Err(anyhow::anyhow!("bad payload")))
+            .with_latest(3);
+
+        let err = upcasters.upcast(versioned("a", 0)).expect_err("missing");
+        assert!(
+            err.to_string()
+                .contains("no upcaster from format version 0")
+        );
+
+        let err = upcasters.upcast(versioned("a", 1)).expect_err("failed");
+        assert!(err.to_string().contains("bad payload"));
+
+        let mut record = Record::new("a");
+        record.set_header(FORMAT_VERSION_HEADER, "x");
+        assert!(upcasters.upcast(consumer_record(record)).is_err());
+
+        let unregistered = Upcasters::new().with_latest(3);
+        let record = unregistered.upcast(versioned("a", 0)).expect("passthrough");
+        assert_eq!(record.value(), b"a");
+    }
+}
@@ -9,6 +9,7 @@ use fluvio_compression::CompressionError;
 use fluvio_socket::SocketError;
 use fluvio_sc_schema::ApiError;
 
+use crate::capabilities::Capability;
 use crate::config::ConfigError;
 use crate::producer::ProducerError;
 use crate::producer::TopicProducerConfigBuilderError;
@@ -65,6 +66,8 @@ To interact with this cluster, please install the matching CLI version using the
     #[cfg(feature = "smartengine")]
     #[error("SmartModuleEngine config: {0}")]
     SmartModuleConfigBuilder(#[from] fluvio_smartengine::SmartModuleConfigBuilderError),
+    #[error("server does not support {0}")]
+    Unsupported(Capability),
     #[error("Unknown error: {0}")]
     Other(String),
 }
@@ -9,17 +9,24 @@ use tracing::{debug, info};
 
 use fluvio_future::net::DomainConnector;
 use fluvio_sc_schema::partition::PartitionMirrorConfig;
-use fluvio_sc_schema::topic::{MirrorConfig, PartitionMap, ReplicaSpec};
+use fluvio_sc_schema::clusterconfig::ClusterConfigSpec;
+use fluvio_sc_schema::topic::{MirrorConfig, PartitionMap, ReplicaSpec, TopicSpec};
 use fluvio_sc_schema::objects::ObjectApiWatchRequest;
-use fluvio_types::PartitionId;
+use fluvio_spu_schema::server::partition_stats::{FetchPartitionStatsRequest, PartitionStats};
+use fluvio_spu_schema::server::replication_status::{FetchReplicationStatusRequest, ReplicationStatus};
+use fluvio_spu_schema::server::delete_records::DeleteRecordsRequest;
+use fluvio_spu_schema::server::spu_config::FetchSpuConfigRequest;
+use fluvio_types::{PartitionId, SpuId};
 use fluvio_socket::{
     ClientConfig, Versions, VersionedSerialSocket, SharedMultiplexerSocket, MultiplexerSocket,
 };
 
 use crate::admin::FluvioAdmin;
+use crate::capabilities::{Capability, check_capability};
 use crate::consumer::{
-    ConsumerConfigExt, ConsumerOffset, ConsumerRetryStream, ConsumerStream,
-    MultiplePartitionConsumer, MultiplePartitionConsumerStream, PartitionSelectionStrategy, Record,
+    ConsumerBatchStream, ConsumerConfigExt, ConsumerOffset, ConsumerRetryStream, ConsumerStream,
+    MultiplePartitionConsumer, MultiplePartitionConsumerStream, PartitionOffsets,
+    PartitionSelectionStrategy, Record,
 };
 use crate::error::anyhow_version_error;
 use crate::metrics::ClientMetrics;
@@ -124,6 +131,8 @@ impl Fluvio {
         if let Some(client_id) = &cluster_config.client_id {
             client_config.set_client_id(client_id.to_owned());
         }
+        client_config.set_socket_tuning((&cluster_config.socket).into());
+        client_config.set_auth_token_source(cluster_config.auth_token_source());
         //Self::connect_with_client_config(client_config, fluvio_config).await
         let inner_client = client_config.connect().await?;
         debug!("connected to cluster");
@@ -220,13 +229,34 @@ impl Fluvio {
         debug!(topic = &*topic, "Creating producer");
 
         let spu_pool = self.spu_pool().await?;
-        if !spu_pool.topic_exists(topic.clone()).await? {
+        if !spu_pool.topic_exists(topic.clone()).await? && !self.auto_create_topic(&topic).await? {
             return Err(FluvioError::TopicNotFound(topic).into());
         }
 
         TopicProducer::new(topic, spu_pool, Arc::new(config), self.metric.clone()).await
     }
 
+    /// Creates single partition topic if cluster config enables auto creation of topics.
+    /// Returns false if auto creation is disabled or not supported by cluster.
+    async fn auto_create_topic(&self, topic: &str) -> Result<bool> {
+        let admin = self.admin().await;
+        let enabled = admin
+            .list::<ClusterConfigSpec, String>(vec![])
+            .await
+            .map(|configs| configs.iter().any(|config| config.spec.auto_create_topics))
+            .unwrap_or(false);
+        if !enabled {
+            return Ok(false);
+        }
+
+        info!(topic, "auto creating topic");
+        // replication and other settings are taken from cluster config
+        admin
+            .create(topic.to_owned(), false, TopicSpec::new_computed(1, 0, None))
+            .await?;
+        Ok(true)
+    }
+
     /// Creates a new `PartitionConsumer` for the given topic and partition
     ///
     /// If you have a topic with multiple partitions, then in order to receive
@@ -358,6 +388,41 @@ impl Fluvio {
         ConsumerRetryStream::new(self, self.cluster_config.clone(), config).await
     }
 
+    /// Creates a new [ConsumerBatchStream] instance.
+    ///
+    /// Same as [`Self::consumer_with_config()`], but yields whole batches of records
+    /// with their offsets and timestamps, for consumers writing records in bulk.
+    ///
+    /// ```no_run
+    /// use fluvio::{consumer::ConsumerConfigExtBuilder, Fluvio, Offset};
+    /// async fn do_consume_batches(fluvio: &Fluvio) -> anyhow::Result<()> {
+    ///    let mut stream = fluvio
+    ///        .consumer_batches_with_config(
+    ///            ConsumerConfigExtBuilder::default()
+    ///                .topic("my-topic".to_string())
+    ///                .offset_start(Offset::beginning())
+    ///                .build()?,
+    ///        )
+    ///        .await?;
+    ///    while let Some(Ok(batch)) = stream.next_batch().await {
+    ///        println!("{}..{}: {} records", batch.base_offset(), batch.last_offset(), batch.records().len());
+    ///    }
+    ///    Ok(())
+    /// }
+    /// ```
+    pub async fn consumer_batches_with_config(
+        &self,
+        config: ConsumerConfigExt,
+    ) -> Result<
+        ConsumerBatchStream<
+            impl ConsumerStream<Item = std::result::Result<Record, fluvio_protocol::link::ErrorCode>>
+            + use<>,
+        >,
+    > {
+        let stream = self.consumer_with_config(config).await?;
+        Ok(ConsumerBatchStream::new(stream))
+    }
+
     /// Creates a new [ConsumerStream] instance without retry logic.
     pub(crate) async fn consumer_with_config_inner(
         &self,
@@ -374,6 +439,10 @@ impl Fluvio {
             .await?
             .ok_or_else(|| FluvioError::TopicNotFound(topic.to_string()))?
             .spec;
+        let config = config
+            .with_default_transforms(topic_spec.get_transforms())
+            .with_format_version(topic_spec.get_format_version());
+        let topic = &config.topic;
 
         let mirror_partition = if let Some(mirror) = &config.mirror {
             match topic_spec.replicas() {
@@ -414,11 +483,21 @@ impl Fluvio {
         } else {
             config.partition.clone()
         };
+        let dead_letter_producer = match &config.dead_letter_topic {
+            Some(dead_letter_topic) => Some(Arc::new(
+                self.topic_producer(dead_letter_topic.clone()).await?,
+            )),
+            None => None,
+        };
         let mut partition_streams = Vec::with_capacity(partitions.len());
         for partition in partitions {
             let consumer =
                 PartitionConsumer::new(topic.clone(), partition, spu_pool.clone(), self.metrics());
-            partition_streams.push(consumer.consumer_stream_with_config(config.clone()).await?);
+            partition_streams.push(
+                consumer
+                    .consumer_stream_with_config(config.clone(), dead_letter_producer.clone())
+                    .await?,
+            );
         }
         Ok(MultiplePartitionConsumerStream::new(partition_streams))
     }
@@ -483,6 +562,189 @@ impl Fluvio {
         Ok(())
     }
 
+    /// Set offset of consumer on the replica, offset is of the last record consumed.
+    /// Consumer resumes reading after it, so `earliest - 1` makes it re-read the whole partition
+    pub async fn reset_consumer_offset(
+        &self,
+        consumer_id: impl Into<String>,
+        replica_id: impl Into<fluvio_protocol::record::ReplicaKey>,
+        offset: i64,
+    ) -> Result<()> {
+        use fluvio_protocol::link::ErrorCode;
+
+        use crate::spu::SpuDirectory;
+
+        let spu_pool = self.spu_pool().await?;
+        let socket = spu_pool
+            .create_serial_socket(&CONSUMER_REPLICA_KEY.into())
+            .await?;
+        if socket
+            .lookup_version::<fluvio_spu_schema::server::consumer_offset::ResetConsumerOffsetRequest>()
+            .is_none()
+        {
+            anyhow::bail!("resetting consumer offset is not supported by SPU");
+        }
+        let response = socket
+            .send_receive(
+                fluvio_spu_schema::server::consumer_offset::ResetConsumerOffsetRequest::new(
+                    replica_id,
+                    consumer_id,
+                    offset,
+                ),
+            )
+            .await?;
+        if response.error_code != ErrorCode::None {
+            anyhow::bail!("reset consumer offset failed with: {}", response.error_code);
+        }
+        Ok(())
+    }
+
+    /// Returns earliest, latest and high watermark offsets of every partition of the topic.
+    ///
+    /// Offsets are read from partition leaders without consuming, so they can be used
+    /// to compute consumer lag or progress of reading the topic.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use fluvio::Fluvio;
+    /// # async fn example(fluvio: &Fluvio) -> anyhow::Result<()> {
+    /// for offsets in fluvio.topic_offsets("my-topic").await? {
+    ///     println!("partition {} has {} records", offsets.partition, offsets.records());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn topic_offsets(&self, topic: impl Into<String>) -> Result<Vec<PartitionOffsets>> {
+        MultiplePartitionConsumer::new(
+            PartitionSelectionStrategy::All(topic.into()),
+            self.spu_pool().await?,
+            self.metric.clone(),
+        )
+        .offsets()
+        .await
+    }
+
+    /// Statistics of partition reported by its leader
+    pub async fn partition_stats(
+        &self,
+        topic: impl Into<String>,
+        partition: PartitionId,
+    ) -> Result<PartitionStats> {
+        use fluvio_protocol::link::ErrorCode;
+        use fluvio_protocol::record::ReplicaKey;
+
+        use crate::spu::SpuDirectory;
+
+        let replica = ReplicaKey::new(topic, partition);
+        let spu_pool = self.spu_pool().await?;
+        let socket = spu_pool.create_serial_socket(&replica).await?;
+        if socket
+            .lookup_version::<FetchPartitionStatsRequest>()
+            .is_none()
+        {
+            anyhow::bail!("partition stats is not supported by SPU leader of {replica}");
+        }
+        let response = socket
+            .send_receive(FetchPartitionStatsRequest::new(replica.clone()))
+            .await?;
+        if response.error_code != ErrorCode::None {
+            anyhow::bail!(
+                "fetch stats of partition {replica} failed with: {}",
+                response.error_code
+            );
+        }
+        Ok(response.stats)
+    }
+
+    /// Replication progress of partition followers reported by its leader
+    pub async fn replication_status(
+        &self,
+        topic: impl Into<String>,
+        partition: PartitionId,
+    ) -> Result<ReplicationStatus> {
+        use fluvio_protocol::link::ErrorCode;
+        use fluvio_protocol::record::ReplicaKey;
+
+        use crate::spu::SpuDirectory;
+
+        let replica = ReplicaKey::new(topic, partition);
+        let spu_pool = self.spu_pool().await?;
+        let socket = spu_pool.create_serial_socket(&replica).await?;
+        if socket
+            .lookup_version::<FetchReplicationStatusRequest>()
+            .is_none()
+        {
+            anyhow::bail!("replication status is not supported by SPU leader of {replica}");
+        }
+        let response = socket
+            .send_receive(FetchReplicationStatusRequest::new(replica.clone()))
+            .await?;
+        if response.error_code != ErrorCode::None {
+            anyhow::bail!(
+                "fetch replication status of partition {replica} failed with: {}",
+                response.error_code
+            );
+        }
+        Ok(response.status)
+    }
+
+    /// Delete records of partition before `offset`, on leader and its followers.
+    /// Offset must not be above high watermark.
+    /// Returns first readable offset after deletion
+    pub async fn delete_records_before(
+        &self,
+        topic: impl Into<String>,
+        partition: PartitionId,
+        offset: i64,
+    ) -> Result<i64> {
+        use fluvio_protocol::link::ErrorCode;
+        use fluvio_protocol::record::ReplicaKey;
+
+        use crate::spu::SpuDirectory;
+
+        let replica = ReplicaKey::new(topic, partition);
+        let spu_pool = self.spu_pool().await?;
+        let socket = spu_pool.create_serial_socket(&replica).await?;
+        if socket.lookup_version::<DeleteRecordsRequest>().is_none() {
+            anyhow::bail!("deleting records is not supported by SPU leader of {replica}");
+        }
+        let response = socket
+            .send_receive(DeleteRecordsRequest::new(replica.clone(), offset))
+            .await?;
+        if response.error_code != ErrorCode::None {
+            anyhow::bail!(
+                "delete records of partition {replica} failed with: {}",
+                response.error_code
+            );
+        }
+        Ok(response.log_start_offset)
+    }
+
+    /// Configuration in effect on SPU, as JSON.
+    /// Includes defaults, environment, startup flags and runtime overrides
+    pub async fn spu_config(&self, spu_id: SpuId) -> Result<String> {
+        use fluvio_protocol::link::ErrorCode;
+
+        use crate::spu::SpuDirectory;
+
+        let spu_pool = self.spu_pool().await?;
+        let socket = spu_pool.create_serial_socket_from_leader(spu_id).await?;
+        if socket.lookup_version::<FetchSpuConfigRequest>().is_none() {
+            anyhow::bail!("fetching config is not supported by spu {spu_id}");
+        }
+        let response = socket
+            .send_receive(FetchSpuConfigRequest::default())
+            .await?;
+        if response.error_code != ErrorCode::None {
+            anyhow::bail!(
+                "fetch config of spu {spu_id} failed with: {}",
+                response.error_code
+            );
+        }
+        Ok(response.config)
+    }
+
     /// Provides an interface for managing a Fluvio cluster
     ///
     /// # Example
@@ -510,6 +772,23 @@ impl Fluvio {
         self.versions.platform_version()
     }
 
+    /// API versions reported by the SC
+    pub fn versions(&self) -> &Versions {
+        &self.versions
+    }
+
+    /// Check that SC supports capability
+    pub fn check_capability(&self, capability: Capability) -> Result<()> {
+        Ok(check_capability(&self.versions, capability)?)
+    }
+
+    /// API versions reported by an SPU, using a new connection to it
+    pub async fn spu_versions(&self, spu: SpuId) -> Result<Versions> {
+        let spu_pool = self.spu_pool().await?;
+        let socket = spu_pool.connect_to_leader(spu).await?;
+        Ok(socket.versions().clone())
+    }
+
     /// create serial connection
     fn create_serial_client(&self) -> VersionedSerialSocket {
         VersionedSerialSocket::new(
@@ -519,6 +798,9 @@ impl Fluvio {
         )
     }
 
+    /// Counters of records and bytes consumed and produced by this client.
+    ///
+    /// Use [`ClientMetrics::set_recorder`] to forward them to metrics exporters.
     pub fn metrics(&self) -> Arc<ClientMetrics> {
         self.metric.clone()
     }
@@ -544,62 +826,20 @@ fn check_platform_compatible(cluster_version: &Version) -> Result<(), FluvioErro
 #[cfg(test)]
 #[cfg(target_arch = "wasm32")]
 mod wasm_tests {
-    use async_trait::async_trait;
-    use fluvio_ws_stream_wasm::WsMeta;
-    use std::io::Error as IoError;
-    use fluvio_future::net::{
-        BoxReadConnection, BoxWriteConnection, DomainConnector, TcpDomainConnector, ConnectionFd,
-    };
-    #[derive(Clone, Default)]
-    pub struct FluvioWebsocketConnector {}
-    impl FluvioWebsocketConnector {
-        pub fn new() -> Self {
-            Self {}
-        }
-    }
-    #[async_trait(?Send)]
-    impl TcpDomainConnector for FluvioWebsocketConnector {
-        async fn connect(
-            &self,
-            addr: &str,
-        ) -> Result<(BoxWriteConnection, BoxReadConnection, ConnectionFd), IoError> {
-            let addr = if addr == "localhost:9010" {
-                "ws://localhost:3001"
-            } else {
-                addr
-            };
-
-            let (mut _ws, wsstream) = WsMeta::connect(addr, None)
-                .await
-                .map_err(|e| IoError::other(e))?;
-            let wsstream_clone = wsstream.clone();
-            Ok((
-                Box::new(wsstream.into_io()),
-                Box::new(wsstream_clone.into_io()),
-                String::from(addr),
-            ))
-        }
-
-        fn new_domain(&self, _domain: String) -> DomainConnector {
-            Box::new(self.clone())
-        }
-
-        fn domain(&self) -> &str {
-            "localhost"
-        }
-    }
     wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
     use wasm_bindgen_test::*;
     use super::*;
+    use crate::config::WebSocketConnector;
     use crate::metadata::topic::TopicSpec;
     use futures_util::stream::StreamExt;
 
     #[wasm_bindgen_test]
     async fn my_test() {
         let config = FluvioClusterConfig::new("ws://localhost:3000");
-        let client =
-            Fluvio::connect_with_connector(Box::new(FluvioWebsocketConnector::new()), &config)
-                .await;
+        // local gateway exposes spu at different port
+        let connector =
+            WebSocketConnector::new().with_endpoint("localhost:9010", "ws://localhost:3001");
+        let client = Fluvio::connect_with_connector(Box::new(connector), &config).await;
         assert!(client.is_ok());
         let client = client.unwrap();
         let mut admin = client.admin().await;
@@ -5,6 +5,7 @@
 #![doc = include_str!("../README.md")]
 
 mod admin;
+mod capabilities;
 mod error;
 mod fluvio;
 mod offset;
@@ -15,19 +16,23 @@ pub mod config;
 pub mod consumer;
 pub mod metrics;
 pub mod spu;
+pub mod trace;
 
 pub use error::FluvioError;
+pub use capabilities::{Capability, check_capability};
 pub use config::{FluvioClusterConfig, FluvioConfig};
 pub use producer::{
     ProducerCallback, SharedProducerCallback, ProduceCompletionBatchEvent,
     TopicProducerConfigBuilder, TopicProducerConfig, TopicProducer, TopicProducerPool, RecordKey,
-    ProduceOutput, FutureRecordMetadata, RecordMetadata, DeliverySemantic, RetryPolicy,
-    RetryStrategy, Partitioner, PartitionerConfig, ProducerError,
+    ProduceOutput, FutureRecordMetadata, UnackedBatch, RecordMetadata, DeliverySemantic,
+    RetryPolicy, RetryStrategy, Partitioner, PartitionerConfig, ProducerError,
 };
 #[cfg(feature = "smartengine")]
 pub use producer::{SmartModuleChainBuilder, SmartModuleConfig, SmartModuleInitialData};
 
 pub use fluvio_spu_schema::Isolation;
+pub use fluvio_spu_schema::server::partition_stats::PartitionStats;
+pub use fluvio_spu_schema::server::replication_status::{FollowerStatus, ReplicationStatus};
 
 pub use consumer::{
     PartitionConsumer, ConsumerConfig, MultiplePartitionConsumer, PartitionSelectionStrategy,
@@ -196,6 +201,10 @@ pub mod metadata {
         pub use fluvio_sc_schema::tableformat::*;
     }
 
+    pub mod clusterconfig {
+        pub use fluvio_sc_schema::clusterconfig::*;
+    }
+
     pub mod core {
         pub use fluvio_sc_schema::core::*;
     }
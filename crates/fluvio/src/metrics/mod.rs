@@ -1,5 +1,12 @@
+use std::sync::Arc;
+
 use serde::{Serialize, Deserialize};
 
+mod recorder;
+
+pub use recorder::{ClientCounter, MetricsRecorder};
+use recorder::CounterRecorder;
+
 #[cfg(feature = "smartengine")]
 use std::collections::HashMap;
 
@@ -37,6 +44,38 @@ impl ClientMetrics {
         &self.producer_client
     }
 
+    /// Forward increments of counters to `recorder`.
+    /// Returns false if a recorder was already set.
+    pub fn set_recorder(&self, recorder: Arc<dyn MetricsRecorder>) -> bool {
+        let counters = [
+            (
+                &self.consumer,
+                ClientCounter::ConsumerRecords,
+                ClientCounter::ConsumerBytes,
+            ),
+            (
+                &self.producer_connector,
+                ClientCounter::ProducerConnectorRecords,
+                ClientCounter::ProducerConnectorBytes,
+            ),
+            (
+                &self.producer_client,
+                ClientCounter::ProducerClientRecords,
+                ClientCounter::ProducerClientBytes,
+            ),
+        ];
+        counters.into_iter().all(|(counter, records, bytes)| {
+            counter
+                .recorder
+                .set(CounterRecorder {
+                    recorder: recorder.clone(),
+                    records,
+                    bytes,
+                })
+                .is_ok()
+        })
+    }
+
     #[cfg(feature = "smartengine")]
     pub(crate) fn metrics_append(
         &self,
@@ -57,41 +96,100 @@ impl ClientMetrics {
 cfg_if::cfg_if! {
     if #[cfg(any(target_arch = "wasm32", target_arch = "arm"))] {
 
+        use std::sync::OnceLock;
+
         #[derive(Default, Debug, Deserialize, Serialize)]
         pub struct RecordCounter {
-
+            #[serde(skip)]
+            recorder: OnceLock<CounterRecorder>,
         }
 
         impl RecordCounter {
             #[inline]
-            pub(crate) fn add_records(&self, _value: u64) {
+            pub(crate) fn add_records(&self, value: u64) {
+                if let Some(recorder) = self.recorder.get() {
+                    recorder.recorder.increment_counter(recorder.records, value);
+                }
             }
 
             #[inline]
-            pub(crate) fn add_bytes(&self, _value: u64) {
+            pub(crate) fn add_bytes(&self, value: u64) {
+                if let Some(recorder) = self.recorder.get() {
+                    recorder.recorder.increment_counter(recorder.bytes, value);
+                }
             }
         }
 
     } else {
+        use std::sync::OnceLock;
         use std::sync::atomic::{AtomicU64, Ordering};
 
         #[derive(Default, Debug, Serialize, Deserialize)]
         pub struct RecordCounter {
             pub records: AtomicU64,
             pub bytes: AtomicU64,
+            #[serde(skip)]
+            recorder: OnceLock<CounterRecorder>,
         }
 
         impl RecordCounter {
             #[inline]
             pub(crate) fn add_records(&self, value: u64) {
                 self.records.fetch_add(value, Ordering::SeqCst);
+                if let Some(recorder) = self.recorder.get() {
+                    recorder.recorder.increment_counter(recorder.records, value);
+                }
             }
 
             #[inline]
             pub(crate) fn add_bytes(&self, value: u64) {
                 self.bytes.fetch_add(value, Ordering::SeqCst);
+                if let Some(recorder) = self.recorder.get() {
+                    recorder.recorder.increment_counter(recorder.bytes, value);
+                }
             }
         }
 
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct TestRecorder {
+        increments: Mutex<Vec<(ClientCounter, u64)>>,
+    }
+
+    impl MetricsRecorder for TestRecorder {
+        fn increment_counter(&self, counter: ClientCounter, value: u64) {
+            self.increments.lock().expect("lock").push((counter, value));
+        }
+    }
+
+    #[test]
+    fn test_recorder_receives_increments() {
+        let metrics = ClientMetrics::new();
+        metrics.consumer().add_records(1);
+
+        let recorder = Arc::new(TestRecorder::default());
+        assert!(metrics.set_recorder(recorder.clone()));
+        assert!(!metrics.set_recorder(recorder.clone()));
+
+        metrics.consumer().add_records(2);
+        metrics.consumer().add_bytes(20);
+        metrics.producer_client().add_bytes(30);
+
+        assert_eq!(
+            *recorder.increments.lock().expect("lock"),
+            [
+                (ClientCounter::ConsumerRecords, 2),
+                (ClientCounter::ConsumerBytes, 20),
+                (ClientCounter::ProducerClientBytes, 30),
+            ]
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use std::fmt;
+use std::sync::Arc;
+
+/// Counter of [`super::ClientMetrics`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ClientCounter {
+    ConsumerRecords,
+    ConsumerBytes,
+    ProducerConnectorRecords,
+    ProducerConnectorBytes,
+    ProducerClientRecords,
+    ProducerClientBytes,
+}
+
+impl ClientCounter {
+    /// Name of counter, following Prometheus conventions
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::ConsumerRecords => "fluvio_consumer_records_total",
+            Self::ConsumerBytes => "fluvio_consumer_bytes_total",
+            Self::ProducerConnectorRecords => "fluvio_producer_connector_records_total",
+            Self::ProducerConnectorBytes => "fluvio_producer_connector_bytes_total",
+            Self::ProducerClientRecords => "fluvio_producer_client_records_total",
+            Self::ProducerClientBytes => "fluvio_producer_client_bytes_total",
+        }
+    }
+}
+
+impl fmt::Display for ClientCounter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Receives increments of client counters, to forward them to exporters of the application.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+/// use fluvio::metrics::{ClientCounter, ClientMetrics, MetricsRecorder};
+///
+/// struct LogRecorder;
+///
+/// impl MetricsRecorder for LogRecorder {
+///     fn increment_counter(&self, counter: ClientCounter, value: u64) {
+///         // e.g. `metrics::counter!(counter.name()).increment(value)`
+///         println!("{counter} += {value}");
+///     }
+/// }
+///
+/// let metrics = ClientMetrics::new();
+/// assert!(metrics.set_recorder(Arc::new(LogRecorder)));
+/// ```
+pub trait MetricsRecorder: Send + Sync {
+    fn increment_counter(&self, counter: ClientCounter, value: u64);
+}
+
+/// Recorder of a [`super::RecordCounter`]
+#[derive(Clone)]
+pub(crate) struct CounterRecorder {
+    pub(crate) recorder: Arc<dyn MetricsRecorder>,
+    pub(crate) records: ClientCounter,
+    pub(crate) bytes: ClientCounter,
+}
+
+impl fmt::Debug for CounterRecorder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CounterRecorder")
+            .field("records", &self.records)
+            .field("bytes", &self.bytes)
+            .finish()
+    }
+}
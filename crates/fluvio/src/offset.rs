@@ -352,6 +352,7 @@ mod tests {
             partition_index: 0,
             start_offset: 0,
             last_stable_offset: 10,
+            log_end_offset: 10,
         };
 
         let offset_inner = OffsetInner::FromBeginning(3);
@@ -366,6 +367,7 @@ mod tests {
             partition_index: 0,
             start_offset: 5,
             last_stable_offset: 10,
+            log_end_offset: 10,
         };
 
         let offset_inner = OffsetInner::FromBeginning(3);
@@ -380,6 +382,7 @@ mod tests {
             partition_index: 0,
             start_offset: 0,
             last_stable_offset: 10,
+            log_end_offset: 10,
         };
 
         let offset_inner = OffsetInner::FromBeginning(15);
@@ -394,6 +397,7 @@ mod tests {
             partition_index: 0,
             start_offset: 5,
             last_stable_offset: 10,
+            log_end_offset: 10,
         };
 
         let offset_inner = OffsetInner::FromBeginning(15);
@@ -408,6 +412,7 @@ mod tests {
             partition_index: 0,
             start_offset: 0,
             last_stable_offset: 10,
+            log_end_offset: 10,
         };
 
         let offset_inner = OffsetInner::FromEnd(3);
@@ -422,6 +427,7 @@ mod tests {
             partition_index: 0,
             start_offset: 6,
             last_stable_offset: 10,
+            log_end_offset: 10,
         };
 
         let offset_inner = OffsetInner::FromEnd(6);
@@ -436,6 +442,7 @@ mod tests {
             partition_index: 0,
             start_offset: 0,
             last_stable_offset: 10,
+            log_end_offset: 10,
         };
 
         let offset_inner = OffsetInner::FromEnd(100);
@@ -452,6 +459,7 @@ mod tests {
             partition_index: 0,
             start_offset: 0,
             last_stable_offset: 10,
+            log_end_offset: 10,
         };
 
         let offset_inner = OffsetInner::Absolute(4);
@@ -467,6 +475,7 @@ mod tests {
             partition_index: 0,
             start_offset: 0,
             last_stable_offset: 15,
+            log_end_offset: 15,
         };
 
         let offset_inner = OffsetInner::FromBeginning(3);
@@ -482,6 +491,7 @@ mod tests {
             partition_index: 0,
             start_offset: 10,
             last_stable_offset: 22,
+            log_end_offset: 22,
         };
 
         let offset_inner = OffsetInner::FromBeginning(5);
@@ -497,6 +507,7 @@ mod tests {
             partition_index: 0,
             start_offset: 0,
             last_stable_offset: 15,
+            log_end_offset: 15,
         };
 
         let offset_inner = OffsetInner::FromEnd(3);
@@ -512,6 +523,7 @@ mod tests {
             partition_index: 0,
             start_offset: 0,
             last_stable_offset: 15,
+            log_end_offset: 15,
         };
 
         let offset_inner = OffsetInner::FromEnd(10);
@@ -100,10 +100,21 @@ impl RecordAccumulator {
     }
 
     /// Add a record to the accumulator.
+    #[cfg(test)]
     pub(crate) async fn push_record(
         &self,
         record: Record,
         partition_id: PartitionId,
+    ) -> Result<PushRecord, ProducerError> {
+        self.push_record_at(record, partition_id, None).await
+    }
+
+    /// Add a record with given timestamp to the accumulator, current time is used if there is none.
+    pub(crate) async fn push_record_at(
+        &self,
+        record: Record,
+        partition_id: PartitionId,
+        timestamp: Option<Timestamp>,
     ) -> Result<PushRecord, ProducerError> {
         let created_at = Instant::now();
 
@@ -118,7 +129,7 @@ impl RecordAccumulator {
 
         // If the last batch is not full, push the record to it
         if let Some(batch) = batches.back_mut() {
-            match batch.push_record(record) {
+            match batch.push_record_at(record, timestamp) {
                 Ok(ProduceBatchStatus::Added(push_record)) => {
                     if batch.is_full() {
                         batch_events.notify_batch_full().await;
@@ -134,7 +145,14 @@ impl RecordAccumulator {
 
                     // Create and push a new batch if needed
                     let push_record = self
-                        .create_and_new_batch(batch_events, &mut batches, record, 1, created_at)
+                        .create_and_new_batch(
+                            batch_events,
+                            &mut batches,
+                            record,
+                            timestamp,
+                            1,
+                            created_at,
+                        )
                         .await?;
 
                     return Ok(PushRecord::new(
@@ -151,7 +169,7 @@ impl RecordAccumulator {
 
         // Create and push a new batch if needed
         let push_record = self
-            .create_and_new_batch(batch_events, &mut batches, record, 1, created_at)
+            .create_and_new_batch(batch_events, &mut batches, record, timestamp, 1, created_at)
             .await?;
 
         Ok(PushRecord::new(
@@ -183,6 +201,7 @@ impl RecordAccumulator {
         batch_events: &BatchEvents,
         batches: &mut VecDeque<ProducerBatch>,
         record: Record,
+        timestamp: Option<Timestamp>,
         attempts: usize,
         created_at: Instant,
     ) -> Result<PartialFutureRecordMetadata, ProducerError> {
@@ -200,7 +219,7 @@ impl RecordAccumulator {
             created_at,
         );
 
-        match batch.push_record(record) {
+        match batch.push_record_at(record, timestamp) {
             Ok(ProduceBatchStatus::Added(push_record)) => {
                 batch_events.notify_new_batch().await;
                 if batch.is_full() {
@@ -222,6 +241,7 @@ impl RecordAccumulator {
                     batch_events,
                     batches,
                     record,
+                    timestamp,
                     attempts + 1,
                     created_at,
                 ))
@@ -298,8 +318,17 @@ impl ProducerBatch {
     /// Add a record to the batch.
     /// Return ProducerError::BatchFull if record does not fit in the batch, so
     /// the RecordAccumulator can create more batches if needed.
+    #[cfg(test)]
     fn push_record(&mut self, record: Record) -> Result<ProduceBatchStatus, ProducerError> {
-        match self.batch.push_record(record) {
+        self.push_record_at(record, None)
+    }
+
+    fn push_record_at(
+        &mut self,
+        record: Record,
+        timestamp: Option<Timestamp>,
+    ) -> Result<ProduceBatchStatus, ProducerError> {
+        match self.batch.push_record_at(record, timestamp) {
             Ok(MemoryBatchStatus::Added(offset)) => Ok(ProduceBatchStatus::Added(
                 PartialFutureRecordMetadata::new(offset, self.batch_metadata.clone()),
             )),
@@ -124,6 +124,11 @@ pub struct TopicProducerConfig {
     /// Callback that will be called after the record is sent to the server.
     #[builder(setter(into, strip_option), default)]
     pub(crate) callback: Option<SharedProducerCallback>,
+
+    /// Stamp records with trace origin id and hops, see [`crate::trace`].
+    /// Records which already carry a trace get the topic appended to hops regardless.
+    #[builder(default)]
+    pub(crate) trace: bool,
 }
 
 impl TopicProducerConfigBuilder {
@@ -161,6 +166,10 @@ impl TopicProducerConfig {
         self.isolation
     }
 
+    pub fn trace(&self) -> bool {
+        self.trace
+    }
+
     pub fn delivery_semantic(&self) -> DeliverySemantic {
         self.delivery_semantic
     }
@@ -2,6 +2,7 @@ use async_channel::RecvError;
 use fluvio_future::retry::TimeoutError;
 use fluvio_protocol::link::ErrorCode;
 
+use super::pending::UnackedBatch;
 use super::record::RecordMetadata;
 
 use crate::producer::PartitionId;
@@ -27,4 +28,8 @@ pub enum ProducerError {
     ProduceRequestRetryTimeout(#[from] TimeoutError),
     #[error("the batch enqueue timeout limit reached")]
     BatchQueueWaitTimeout,
+    #[error("{} records were not acknowledged", .0.iter().map(|batch| batch.records).sum::<usize>())]
+    Unacknowledged(Vec<UnackedBatch>),
+    #[error("producer is closed")]
+    Closed,
 }
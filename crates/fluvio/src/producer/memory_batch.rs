@@ -20,6 +20,8 @@ pub struct MemoryBatch {
     current_size_uncompressed: usize,
     is_full: bool,
     create_time: Timestamp,
+    /// timestamp which timestamps of records are relative to
+    base_timestamp: Timestamp,
     records: Vec<Record>,
 }
 impl MemoryBatch {
@@ -31,6 +33,7 @@ impl MemoryBatch {
             batch_limit,
             write_limit,
             create_time: now,
+            base_timestamp: now,
             current_size_uncompressed: Vec::<RawRecords>::default().write_size(0),
             records: vec![],
         }
@@ -42,7 +45,17 @@ impl MemoryBatch {
 
     /// Add a record to the batch.
     /// The value of `Offset` is relative to the `MemoryBatch` instance.
-    pub fn push_record(&mut self, mut record: Record) -> Result<MemoryBatchStatus, ProducerError> {
+    #[cfg(test)]
+    pub fn push_record(&mut self, record: Record) -> Result<MemoryBatchStatus, ProducerError> {
+        self.push_record_at(record, None)
+    }
+
+    /// Add a record with given timestamp, or current time if there is none.
+    pub fn push_record_at(
+        &mut self,
+        mut record: Record,
+        timestamp: Option<Timestamp>,
+    ) -> Result<MemoryBatchStatus, ProducerError> {
         let is_the_first_record = self.records_len() == 0;
 
         let current_offset = self.offset() as i64;
@@ -50,7 +63,15 @@ impl MemoryBatch {
             .get_mut_header()
             .set_offset_delta(current_offset as Offset);
 
-        let timestamp_delta = self.elapsed();
+        let timestamp_delta = match timestamp {
+            Some(timestamp) => {
+                if is_the_first_record {
+                    self.base_timestamp = timestamp;
+                }
+                timestamp - self.base_timestamp
+            }
+            None => std::cmp::max(0, Utc::now().timestamp_millis() - self.base_timestamp),
+        };
         record.get_mut_header().set_timestamp_delta(timestamp_delta);
 
         let record_size = record.write_size(0);
@@ -127,11 +148,12 @@ impl From<MemoryBatch> for Batch<MemoryRecords> {
         let header = batch.get_mut_header();
         header.last_offset_delta = if len > 0 { len - 1 } else { len };
 
-        let first_timestamp = p_batch.create_time;
+        let first_timestamp = p_batch.base_timestamp;
 
         let max_time_stamp = records
-            .last()
+            .iter()
             .map(|r| first_timestamp + r.timestamp_delta())
+            .max()
             .unwrap_or(0);
 
         header.set_first_timestamp(first_timestamp);
@@ -213,6 +235,29 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_memory_batch_with_timestamps() {
+        let mut mb = MemoryBatch::new(1_048_576, 1_048_576, Compression::None);
+
+        for timestamp in [1_000, 1_500, 1_200] {
+            assert!(matches!(
+                mb.push_record_at(Record::from(("key", "value")), Some(timestamp)),
+                Ok(MemoryBatchStatus::Added(_))
+            ));
+        }
+
+        let batch: Batch<MemoryRecords> = mb.into();
+        assert_eq!(batch.header.first_timestamp, 1_000);
+        assert_eq!(batch.header.max_time_stamp, 1_500);
+
+        let records_delta: Vec<_> = batch
+            .records()
+            .iter()
+            .map(|record| record.timestamp_delta())
+            .collect();
+        assert_eq!(records_delta, vec![0, 500, 200]);
+    }
+
     #[test]
     fn test_is_the_first_record_from_batch_and_actual_batch_size_larger_then_batch_limit() {
         let record = Record::from(("key", "value"));
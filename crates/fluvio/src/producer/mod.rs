@@ -6,32 +6,34 @@
 //! It includes the `TopicProducerPool` struct, which manages the production of messages
 //! to specific topics and partitions, respectively.
 //!
-use std::collections::HashMap;
-use std::sync::atomic::AtomicU32;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
 use event_listener::Event;
 use tokio::select;
-use tracing::instrument;
+use tracing::{debug, instrument};
 use async_lock::RwLock;
 use anyhow::Result;
 
 use fluvio_future::task::spawn;
 use fluvio_future::timer::sleep;
 use fluvio_protocol::record::ReplicaKey;
-use fluvio_protocol::record::Record;
+use fluvio_protocol::record::{HeaderEntry, Record};
 use fluvio_compression::Compression;
 #[cfg(feature = "compress")]
 use fluvio_sc_schema::topic::CompressionAlgorithm;
 use fluvio_sc_schema::topic::TopicSpec;
 use fluvio_sc_schema::partition::PartitionSpec;
-use fluvio_types::PartitionId;
+use fluvio_types::{PartitionId, Timestamp};
 use fluvio_types::event::StickyEvent;
 
 mod accumulator;
 mod config;
 mod error;
 mod output;
+mod pending;
 mod record;
 mod partitioning;
 mod partition_producer;
@@ -41,11 +43,14 @@ pub mod event;
 
 pub use fluvio_protocol::record::{RecordKey, RecordData};
 
+use crate::capabilities::{Capability, check_capability};
 use crate::spu::SpuPool;
 use crate::spu::SpuSocketPool;
 use crate::sync::StoreContext;
 use crate::FluvioError;
 use crate::metrics::ClientMetrics;
+use crate::trace::RecordTrace;
+use crate::consumer::FORMAT_VERSION_HEADER;
 use crate::producer::accumulator::{RecordAccumulator, PushRecord};
 
 pub use crate::producer::partitioning::{Partitioner, PartitionerConfig};
@@ -63,6 +68,8 @@ pub use self::config::{
 pub use self::error::ProducerError;
 use self::event::EventHandler;
 pub use self::output::ProduceOutput;
+use self::pending::PendingBatches;
+pub use self::pending::UnackedBatch;
 use self::partition_producer::PartitionProducer;
 pub use self::record::{FutureRecordMetadata, RecordMetadata};
 
@@ -245,6 +252,10 @@ where
     partition_tracker: Arc<PartitionAvailabilityTracker>,
     producer_pool: Arc<RwLock<ProducerPool>>,
     metrics: Arc<ClientMetrics>,
+    pending: PendingBatches,
+    closed: AtomicBool,
+    /// format version of the topic, stamped on records
+    format_version: u32,
 }
 
 impl<S> InnerTopicProducer<S>
@@ -257,24 +268,38 @@ where
         Ok(())
     }
 
-    async fn push_record(self: Arc<Self>, record: Record) -> Result<PushRecord> {
-        let partition_count = self.partition_tracker.partition_count();
-        let available_partitions = self.partition_tracker.available_partitions();
-        let available_partitions_lock = available_partitions.read().await;
-
-        let partition_config = PartitionerConfig {
-            partition_count,
-            available_partitions: available_partitions_lock.clone(),
+    /// Flush all the PartitionProducers and wait until records sent so far are acknowledged,
+    /// up to `timeout` if set.
+    async fn flush_and_wait(&self, timeout: Option<Duration>) -> Result<()> {
+        let flush = async {
+            self.flush().await?;
+            self.pending.wait().await;
+            Ok::<_, anyhow::Error>(())
+        };
+        let flushed = match timeout {
+            Some(timeout) => fluvio_future::future::timeout(timeout, flush)
+                .await
+                .unwrap_or(Ok(())),
+            None => flush.await,
         };
 
-        drop(available_partitions_lock);
+        let unacked = self.pending.take_unacked();
+        if !unacked.is_empty() {
+            return Err(ProducerError::Unacknowledged(unacked).into());
+        }
+        flushed
+    }
 
-        let key = record.key.as_ref().map(|k| k.as_ref());
-        let value = record.value.as_ref();
-        let partition = self
-            .config
-            .partitioner
-            .partition(&partition_config, key, value);
+    async fn push_record(
+        self: Arc<Self>,
+        record: Record,
+        timestamp: Option<Timestamp>,
+    ) -> Result<PushRecord> {
+        if self.closed.load(Ordering::Acquire) {
+            return Err(ProducerError::Closed.into());
+        }
+
+        let partition = self.route(&record).await;
 
         let mut producer_pool = self.producer_pool.write().await;
 
@@ -302,12 +327,33 @@ where
 
         let push_record = self
             .record_accumulator
-            .push_record(record, partition)
+            .push_record_at(record, partition, timestamp)
             .await?;
+        self.pending.add(&push_record.future);
 
         Ok(push_record)
     }
 
+    /// partition selected by partitioner for the record
+    async fn route(&self, record: &Record) -> PartitionId {
+        let partition_count = self.partition_tracker.partition_count();
+        let available_partitions = self.partition_tracker.available_partitions();
+        let available_partitions_lock = available_partitions.read().await;
+
+        let partition_config = PartitionerConfig {
+            partition_count,
+            available_partitions: available_partitions_lock.clone(),
+        };
+
+        drop(available_partitions_lock);
+
+        let key = record.key.as_ref().map(|k| k.as_ref());
+        let value = record.value.as_ref();
+        self.config
+            .partitioner
+            .partition(&partition_config, key, value)
+    }
+
     async fn clear_errors(&self) {
         self.producer_pool.read().await.clear_errors().await;
     }
@@ -526,15 +572,26 @@ where
             .spec;
 
         let partition_count = topic_spec.partitions();
+        let format_version = topic_spec.get_format_version();
 
         cfg_if::cfg_if! {
             if #[cfg(feature = "compress")] {
                 let compression = determine_producer_compression_algo(config.clone(), topic_spec)?;
             } else {
+                // SPU compresses batches with codec of the topic
                 let compression = Compression::None;
             }
         }
 
+        let mut required = vec![];
+        if compression != Compression::None {
+            required.push(Capability::Compression(compression));
+        }
+        if !config.smartmodules.is_empty() {
+            required.push(Capability::SmartModules);
+        }
+        check_leader_capabilities(&topic, partition_count, spu_pool.as_ref(), &required).await?;
+
         let record_accumulator = RecordAccumulator::new(
             config.batch_size,
             config.max_request_size,
@@ -570,6 +627,9 @@ where
                 record_accumulator: Arc::new(record_accumulator),
                 partition_tracker,
                 metrics: metrics.clone(),
+                pending: Default::default(),
+                closed: AtomicBool::new(false),
+                format_version,
             }),
             #[cfg(feature = "smartengine")]
             sm_chain: Default::default(),
@@ -600,6 +660,39 @@ where
         self.inner.flush().await
     }
 
+    /// Send all the queued records and wait up to `timeout` until all records sent
+    /// so far are acknowledged by the SPUs.
+    ///
+    /// Returns [`ProducerError::Unacknowledged`] listing batches which failed or
+    /// were not acknowledged in time. Failed batches are reported once.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use fluvio::TopicProducerPool;
+    /// # async fn example(producer: &TopicProducerPool) -> anyhow::Result<()> {
+    /// producer.send("Key", "Value").await?;
+    /// producer.flush_with_timeout(Duration::from_secs(5)).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn flush_with_timeout(&self, timeout: Duration) -> Result<()> {
+        self.inner.flush_and_wait(Some(timeout)).await
+    }
+
+    /// Send all the queued records, wait until they are acknowledged and stop the producer.
+    ///
+    /// Returns [`ProducerError::Unacknowledged`] listing batches which failed.
+    /// Afterwards, `send` on this producer and its clones returns [`ProducerError::Closed`].
+    /// Use [`Self::flush_with_timeout`] first to bound the time spent waiting.
+    pub async fn close(&self) -> Result<()> {
+        self.inner.closed.store(true, Ordering::Release);
+        let result = self.inner.flush_and_wait(None).await;
+        self.inner.producer_pool.read().await.end();
+        result
+    }
+
     /// Sends a key/value record to this producer's Topic.
     ///
     /// The partition that the record will be sent to is derived from the Key.
@@ -628,43 +721,76 @@ where
         key: impl Into<RecordKey>,
         value: impl Into<RecordData>,
     ) -> Result<ProduceOutput> {
-        let record_key = key.into();
-        let record_value = value.into();
-        let record = Record::from((record_key, record_value));
-
-        cfg_if::cfg_if! {
-            if #[cfg(feature = "smartengine")] {
-                let mut entries = vec![record];
-
-                use chrono::Utc;
+        self.send_record(Record::from((key.into(), value.into())), None)
+            .await
+    }
 
-                use fluvio_smartengine::DEFAULT_SMARTENGINE_VERSION;
-                use fluvio_smartmodule::dataplane::smartmodule::SmartModuleInput;
+    /// Sends a key/value record with given timestamp, in milliseconds since epoch,
+    /// instead of time when record is added to batch.
+    /// Useful to keep original timestamps of records copied from other topics.
+    #[instrument(
+        skip(self, key, value),
+        fields(topic = %self.inner.topic),
+    )]
+    pub async fn send_with_timestamp(
+        &self,
+        key: impl Into<RecordKey>,
+        value: impl Into<RecordData>,
+        timestamp: Timestamp,
+    ) -> Result<ProduceOutput> {
+        self.send_record(Record::from((key.into(), value.into())), Some(timestamp))
+            .await
+    }
 
-                if let Some(
-                    smart_chain_ref
-                ) = &self.sm_chain {
-                    let mut sm_chain = smart_chain_ref.write().await;
-                    let mut sm_input = SmartModuleInput::try_from_records(entries, DEFAULT_SMARTENGINE_VERSION)?;
-                    let current_time = Utc::now().timestamp_millis();
+    /// Sends a key/value record with headers, such as trace of record copied from other topic.
+    /// Timestamp, in milliseconds since epoch, is used instead of time when record is added to batch.
+    #[instrument(
+        skip(self, key, value, headers),
+        fields(topic = %self.inner.topic),
+    )]
+    pub async fn send_with_headers(
+        &self,
+        key: impl Into<RecordKey>,
+        value: impl Into<RecordData>,
+        headers: Vec<HeaderEntry>,
+        timestamp: Option<Timestamp>,
+    ) -> Result<ProduceOutput> {
+        let mut record = Record::from((key.into(), value.into()));
+        record.headers = headers;
+        self.send_record(record, timestamp).await
+    }
 
-                    sm_input.set_base_timestamp(current_time);
-                    let output = sm_chain.process(sm_input).map_err(|e| FluvioError::Other(format!("SmartEngine - {e:?}")))?;
+    /// Trace of record forwarded from other topic is kept with this topic appended to its hops,
+    /// records SmartModule transformations output inherit the trace of their input.
+    /// Records are stamped with format version of the topic, if it declares one.
+    pub(crate) async fn send_record(
+        &self,
+        record: Record,
+        timestamp: Option<Timestamp>,
+    ) -> Result<ProduceOutput> {
+        let trace = match RecordTrace::from_record(&record) {
+            Some(trace) => Some(trace),
+            None if self.inner.config.trace => Some(RecordTrace::new()),
+            None => None,
+        }
+        .map(|trace| trace.with_hop(&self.inner.topic));
 
-                    // update_smartmodule metrics needs to access the sm_chain
-                    // w/ a read lock so we need to drop the write lock first
-                    drop(sm_chain);
-                    self.update_smartmodule_metrics().await?;
-                    entries = output.successes;
-                }
-            } else {
-                let  entries = vec![record];
-                }
+        let mut entries = self.transform(record).await?;
+        if let Some(trace) = trace {
+            for record in entries.iter_mut() {
+                trace.stamp(record);
+            }
+        }
+        if self.inner.format_version > 0 {
+            let version = self.inner.format_version.to_string();
+            for record in entries.iter_mut() {
+                record.set_header(FORMAT_VERSION_HEADER, version.as_str());
+            }
         }
 
         let mut results = ProduceOutput::default();
         for record in entries {
-            let push_record = self.inner.clone().push_record(record).await?;
+            let push_record = self.inner.clone().push_record(record, timestamp).await?;
             results.add(push_record.future);
         }
         Ok(results)
@@ -687,6 +813,59 @@ where
         Ok(results)
     }
 
+    /// Returns records `send` would produce for the key/value, after SmartModule transformations,
+    /// with partitions they would be routed to. Nothing is sent to the cluster.
+    ///
+    /// Note that stateful partitioners, like round robin, advance as if records were sent.
+    pub async fn preview(
+        &self,
+        key: impl Into<RecordKey>,
+        value: impl Into<RecordData>,
+    ) -> Result<Vec<(PartitionId, Record)>> {
+        let entries = self
+            .transform(Record::from((key.into(), value.into())))
+            .await?;
+        let mut routed = Vec::with_capacity(entries.len());
+        for record in entries {
+            routed.push((self.inner.route(&record).await, record));
+        }
+        Ok(routed)
+    }
+
+    /// records to send in place of the record, after SmartModule transformations
+    async fn transform(&self, record: Record) -> Result<Vec<Record>> {
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "smartengine")] {
+                let mut entries = vec![record];
+
+                use chrono::Utc;
+
+                use fluvio_smartengine::DEFAULT_SMARTENGINE_VERSION;
+                use fluvio_smartmodule::dataplane::smartmodule::SmartModuleInput;
+
+                if let Some(
+                    smart_chain_ref
+                ) = &self.sm_chain {
+                    let mut sm_chain = smart_chain_ref.write().await;
+                    let mut sm_input = SmartModuleInput::try_from_records(entries, DEFAULT_SMARTENGINE_VERSION)?;
+                    let current_time = Utc::now().timestamp_millis();
+
+                    sm_input.set_base_timestamp(current_time);
+                    let output = sm_chain.process(sm_input).map_err(|e| FluvioError::Other(format!("SmartEngine - {e:?}")))?;
+
+                    // update_smartmodule metrics needs to access the sm_chain
+                    // w/ a read lock so we need to drop the write lock first
+                    drop(sm_chain);
+                    self.update_smartmodule_metrics().await?;
+                    entries = output.successes;
+                }
+                Ok(entries)
+            } else {
+                Ok(vec![record])
+            }
+        }
+    }
+
     /// Clear partition producers errors in order to make partition producers available.
     /// This is needed once an error is present in order to send new records again.
     pub async fn clear_errors(&self) {
@@ -711,6 +890,37 @@ where
     }
 }
 
+/// Fail early if partition leaders report they don't support features used by producer.
+/// Leaders which can't be reached yet are not checked.
+async fn check_leader_capabilities<S: SpuPool>(
+    topic: &str,
+    partition_count: PartitionId,
+    spu_pool: &S,
+    required: &[Capability],
+) -> Result<()> {
+    if required.is_empty() {
+        return Ok(());
+    }
+    let mut leaders = HashSet::new();
+    for partition in 0..partition_count {
+        let replica = ReplicaKey::new(topic, partition);
+        if let Some(partition) = spu_pool.partitions().lookup_by_key(&replica).await? {
+            leaders.insert(partition.spec.leader);
+        }
+    }
+    for leader in leaders {
+        match spu_pool.create_serial_socket_from_leader(leader).await {
+            Ok(socket) => {
+                for capability in required {
+                    check_capability(socket.versions(), *capability)?;
+                }
+            }
+            Err(err) => debug!(leader, %err, "skipping capabilities check of leader"),
+        }
+    }
+    Ok(())
+}
+
 #[cfg(feature = "compress")]
 fn determine_producer_compression_algo(
     config: Arc<TopicProducerConfig>,
@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use fluvio_types::PartitionId;
+
+use super::record::{BatchMetadata, FutureRecordMetadata};
+
+/// Records of a batch which were not acknowledged by the SPU
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnackedBatch {
+    pub partition: PartitionId,
+    pub records: usize,
+    /// None if batch was still waiting to be sent or acknowledged
+    pub error: Option<String>,
+}
+
+/// Batches with records sent by producer which are not acknowledged yet.
+///
+/// Updated for every record sent, so it's only locked briefly and never across await.
+#[derive(Default)]
+pub(crate) struct PendingBatches {
+    batches: Mutex<Batches>,
+}
+
+/// batches by address of their metadata, shared by all records of the batch
+#[derive(Default)]
+struct Batches {
+    by_metadata: HashMap<usize, PendingBatch>,
+    next_seq: u64,
+}
+
+struct PendingBatch {
+    /// order in which batches were created
+    seq: u64,
+    partition: PartitionId,
+    metadata: Arc<BatchMetadata>,
+    records: usize,
+}
+
+impl PendingBatches {
+    /// track record pushed to batch
+    pub(crate) fn add(&self, record: &FutureRecordMetadata) {
+        let mut batches = self.batches.lock().expect("Poisoned lock");
+        // address is not reused while batch is tracked, since `PendingBatch` keeps it alive
+        let key = Arc::as_ptr(&record.batch_metadata) as usize;
+        if let Some(batch) = batches.by_metadata.get_mut(&key) {
+            batch.records += 1;
+            return;
+        }
+
+        // new batch, forget acknowledged ones
+        batches
+            .by_metadata
+            .retain(|_, batch| !matches!(batch.metadata.try_base_offset(), Some(Ok(_))));
+        let seq = batches.next_seq;
+        batches.next_seq += 1;
+        batches.by_metadata.insert(
+            key,
+            PendingBatch {
+                seq,
+                partition: record.partition_id,
+                metadata: record.batch_metadata.clone(),
+                records: 1,
+            },
+        );
+    }
+
+    /// Wait until batches tracked so far are acknowledged or failed
+    pub(crate) async fn wait(&self) {
+        let batches: Vec<_> = self
+            .batches
+            .lock()
+            .expect("Poisoned lock")
+            .by_metadata
+            .values()
+            .map(|batch| batch.metadata.clone())
+            .collect();
+        for metadata in batches {
+            // failure is kept in metadata
+            let _ = metadata.base_offset().await;
+        }
+    }
+
+    /// Forget acknowledged and failed batches, returning failed ones and ones still waiting
+    pub(crate) fn take_unacked(&self) -> Vec<UnackedBatch> {
+        let mut batches = self.batches.lock().expect("Poisoned lock");
+        let mut settled = vec![];
+        batches
+            .by_metadata
+            .retain(|_, batch| match batch.metadata.try_base_offset() {
+                Some(Ok(_)) => false,
+                Some(Err(err)) => {
+                    settled.push((
+                        batch.seq,
+                        UnackedBatch {
+                            partition: batch.partition,
+                            records: batch.records,
+                            error: Some(err.to_string()),
+                        },
+                    ));
+                    false
+                }
+                None => {
+                    settled.push((
+                        batch.seq,
+                        UnackedBatch {
+                            partition: batch.partition,
+                            records: batch.records,
+                            error: None,
+                        },
+                    ));
+                    true
+                }
+            });
+        settled.sort_by_key(|(seq, _)| *seq);
+        settled.into_iter().map(|(_, batch)| batch).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fluvio_protocol::link::ErrorCode;
+
+    use crate::producer::accumulator::ProducePartitionResponseFuture;
+
+    use super::*;
+
+    fn record(partition: PartitionId, metadata: &Arc<BatchMetadata>) -> FutureRecordMetadata {
+        FutureRecordMetadata {
+            partition_id: partition,
+            relative_offset: 0,
+            batch_metadata: metadata.clone(),
+        }
+    }
+
+    #[fluvio_future::test]
+    async fn test_pending_batches() {
+        let (acked_sender, receiver) = async_channel::bounded(1);
+        let acked = Arc::new(BatchMetadata::new(receiver, None));
+        let (failed_sender, receiver) = async_channel::bounded(1);
+        let failed = Arc::new(BatchMetadata::new(receiver, None));
+        let (_queued_sender, receiver) = async_channel::bounded(1);
+        let queued = Arc::new(BatchMetadata::new(receiver, None));
+
+        let pending = PendingBatches::default();
+        pending.add(&record(0, &acked));
+        pending.add(&record(0, &acked));
+        pending.add(&record(1, &failed));
+        pending.add(&record(0, &queued));
+
+        acked_sender
+            .send(ProducePartitionResponseFuture::ready(10, ErrorCode::None))
+            .await
+            .expect("send");
+        failed_sender
+            .send(ProducePartitionResponseFuture::ready(
+                0,
+                ErrorCode::NotLeaderForPartition,
+            ))
+            .await
+            .expect("send");
+
+        let unacked = pending.take_unacked();
+        assert_eq!(unacked.len(), 2);
+        assert_eq!(unacked[0].partition, 1);
+        assert_eq!(unacked[0].records, 1);
+        assert!(unacked[0].error.is_some());
+        assert_eq!(
+            unacked[1],
+            UnackedBatch {
+                partition: 0,
+                records: 1,
+                error: None,
+            }
+        );
+
+        // failed batch is reported once, queued one until it is settled
+        let unacked = pending.take_unacked();
+        assert_eq!(unacked.len(), 1);
+        assert_eq!(unacked[0].error, None);
+    }
+}
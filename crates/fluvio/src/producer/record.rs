@@ -5,8 +5,9 @@ use std::time::Instant;
 #[cfg(target_arch = "wasm32")]
 use web_time::Instant;
 
-use async_channel::Receiver;
+use async_channel::{Receiver, TryRecvError};
 use async_lock::RwLock;
+use futures_util::FutureExt;
 
 use fluvio_protocol::record::Offset;
 use fluvio_protocol::link::ErrorCode;
@@ -42,6 +43,8 @@ impl RecordMetadata {
 pub(crate) enum BatchMetadataState {
     /// The batch is buffered and ready to be sent to the SPU
     Buffered(Receiver<ProducePartitionResponseFuture>),
+    /// The batch was sent to the SPU, waiting for response
+    Sending(ProducePartitionResponseFuture),
     /// The batch was sent to the SPU. Base offset is known
     Sent(Offset),
     /// There was an error sending the batch to the SPU
@@ -72,33 +75,53 @@ impl BatchMetadata {
     /// record in the batch and it is known once the batch is sent to the server.
     pub(crate) async fn base_offset(&self) -> Result<Offset> {
         let mut state = self.state.write().await;
+        if let BatchMetadataState::Buffered(receiver) = &*state {
+            *state = match receiver.recv().await {
+                Ok(offset_future) => BatchMetadataState::Sending(offset_future),
+                Err(err) => BatchMetadataState::Failed(ProducerError::GetRecordMetadata(Some(err))),
+            };
+        }
+        if let BatchMetadataState::Sending(offset_future) = &mut *state {
+            let (offset, error) = offset_future.await;
+            *state = BatchMetadataState::settled(offset, error);
+        }
         match &*state {
-            BatchMetadataState::Buffered(receiver) => {
-                let msg = receiver
-                    .recv()
-                    .await
-                    .map_err(|err| ProducerError::GetRecordMetadata(Some(err)));
-
-                match msg {
-                    Ok(offset_future) => {
-                        let (offset, error) = offset_future.await;
-                        if error == ErrorCode::None {
-                            *state = BatchMetadataState::Sent(offset);
-                            Ok(offset)
-                        } else {
-                            let error = ProducerError::SpuErrorCode(error);
-                            *state = BatchMetadataState::Failed(error.clone());
-                            Err(error.into())
-                        }
-                    }
-                    Err(err) => {
-                        *state = BatchMetadataState::Failed(err.clone());
-                        Err(err.into())
-                    }
-                }
-            }
             BatchMetadataState::Sent(offset) => Ok(*offset),
             BatchMetadataState::Failed(error) => Err(error.clone().into()),
+            _ => Err(ProducerError::GetRecordMetadata(None).into()),
+        }
+    }
+
+    /// Base offset of the batch or its error, if known without waiting
+    pub(crate) fn try_base_offset(&self) -> Option<Result<Offset, ProducerError>> {
+        let mut state = self.state.try_write()?;
+        if let BatchMetadataState::Buffered(receiver) = &*state {
+            *state = match receiver.try_recv() {
+                Ok(offset_future) => BatchMetadataState::Sending(offset_future),
+                Err(TryRecvError::Empty) => return None,
+                Err(TryRecvError::Closed) => {
+                    BatchMetadataState::Failed(ProducerError::GetRecordMetadata(None))
+                }
+            };
+        }
+        if let BatchMetadataState::Sending(offset_future) = &mut *state {
+            let (offset, error) = offset_future.now_or_never()?;
+            *state = BatchMetadataState::settled(offset, error);
+        }
+        match &*state {
+            BatchMetadataState::Sent(offset) => Some(Ok(*offset)),
+            BatchMetadataState::Failed(error) => Some(Err(error.clone())),
+            _ => None,
+        }
+    }
+}
+
+impl BatchMetadataState {
+    fn settled(offset: Offset, error: ErrorCode) -> Self {
+        if error == ErrorCode::None {
+            Self::Sent(offset)
+        } else {
+            Self::Failed(ProducerError::SpuErrorCode(error))
         }
     }
 }
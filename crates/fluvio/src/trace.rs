@@ -0,0 +1,105 @@
+//!
+//! # Record tracing
+//!
+//! Records can be stamped with an origin id and a list of topics they passed through,
+//! stored in record headers. Producers with tracing enabled stamp new records, and keep
+//! the origin id of records forwarded from other topics while appending the topic to hops.
+//! Mirrored records are copied with their headers, so they keep the trace too.
+//!
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use fluvio_protocol::record::Record;
+
+/// header with id assigned to record when it was first produced
+pub const TRACE_ID_HEADER: &str = "fluvio-trace-id";
+
+/// header with comma separated topics the record was produced to, in order
+pub const TRACE_HOPS_HEADER: &str = "fluvio-trace-hops";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordTrace {
+    pub origin_id: String,
+    pub hops: Vec<String>,
+}
+
+impl RecordTrace {
+    /// trace with new random origin id and no hops
+    pub fn new() -> Self {
+        Self {
+            origin_id: new_origin_id(),
+            hops: vec![],
+        }
+    }
+
+    /// trace stamped on record, if any
+    pub fn from_record<B: Default>(record: &Record<B>) -> Option<Self> {
+        let origin_id = record.header(TRACE_ID_HEADER)?;
+        let origin_id = std::str::from_utf8(origin_id.as_ref()).ok()?.to_owned();
+        let hops = record
+            .header(TRACE_HOPS_HEADER)
+            .and_then(|hops| std::str::from_utf8(hops.as_ref()).ok())
+            .map(|hops| {
+                hops.split(',')
+                    .filter(|hop| !hop.is_empty())
+                    .map(str::to_owned)
+                    .collect()
+            })
+            .unwrap_or_default();
+        Some(Self { origin_id, hops })
+    }
+
+    /// append hop unless record is already there, as with retried sends
+    pub fn with_hop(mut self, hop: &str) -> Self {
+        if self.hops.last().map(String::as_str) != Some(hop) {
+            self.hops.push(hop.to_owned());
+        }
+        self
+    }
+
+    /// write trace to record headers
+    pub fn stamp<B: Default>(&self, record: &mut Record<B>) {
+        record.set_header(TRACE_ID_HEADER, self.origin_id.as_str());
+        record.set_header(TRACE_HOPS_HEADER, self.hops.join(","));
+    }
+}
+
+impl Default for RecordTrace {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// random 64 bit hex id, unique across producers of the process by counter
+fn new_origin_id() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+    format!("{:016x}", hasher.finish())
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_trace_headers() {
+        let mut record = Record::new("value");
+        assert!(RecordTrace::from_record(&record).is_none());
+
+        let trace = RecordTrace::new().with_hop("orders");
+        assert_eq!(trace.origin_id.len(), 16);
+        trace.stamp(&mut record);
+
+        let forwarded = RecordTrace::from_record(&record)
+            .expect("trace")
+            .with_hop("orders-enriched")
+            .with_hop("orders-enriched");
+        assert_eq!(forwarded.origin_id, trace.origin_id);
+        assert_eq!(forwarded.hops, vec!["orders", "orders-enriched"]);
+
+        assert_ne!(RecordTrace::new().origin_id, trace.origin_id);
+    }
+}
@@ -190,3 +190,38 @@ setup_file() {
     assert_output --partial "topic \"$TOPIC_NAME\" deleted"
 }
 
+
+@test "Reset consumer offset should re-read partition" {
+    if [ "$FLUVIO_CLI_RELEASE_CHANNEL" == "stable" ]; then
+	skip "don't run on fluvio cli stable version"
+    fi
+    if [ "$FLUVIO_CLUSTER_RELEASE_CHANNEL" == "stable" ]; then
+	skip "don't run on cluster stable version"
+    fi
+
+    run timeout 15s "$FLUVIO_BIN" topic create "$TOPIC_NAME"
+    assert_output --partial "topic \"$TOPIC_NAME\" created"
+
+    echo 1 | "$FLUVIO_BIN" produce $TOPIC_NAME
+    echo 2 | "$FLUVIO_BIN" produce $TOPIC_NAME
+
+    CONSUMER_NAME=$(random_string)
+    run timeout 15s "$FLUVIO_BIN" consume "$TOPIC_NAME" --consumer "$CONSUMER_NAME" -B -d
+    assert_success
+
+    run timeout 15s "$FLUVIO_BIN" consumer reset "$CONSUMER_NAME" --topic "$TOPIC_NAME" --to-offset 1
+    assert_success
+    assert_output --partial "reset to offset 1"
+
+    LAG=$("$FLUVIO_BIN" consumer describe "$CONSUMER_NAME" -O json | jq ".[0].lag")
+    assert [ $LAG == "1" ]
+
+    run timeout 15s "$FLUVIO_BIN" consume "$TOPIC_NAME" --consumer "$CONSUMER_NAME" -d
+    assert_success
+    assert_line "2"
+    refute_line "1"
+
+    # cleanup
+    run timeout 15s "$FLUVIO_BIN" topic delete "$TOPIC_NAME"
+    assert_output --partial "topic \"$TOPIC_NAME\" deleted"
+}